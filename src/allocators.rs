@@ -0,0 +1,69 @@
+use crate::pointee::Pointee;
+use llvm_ir::TypeRef;
+
+/// Heap allocation and deallocation functions which we model directly (see
+/// `Config::model_allocators`), rather than as calls to external functions
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Allocator {
+    /// `malloc`, `calloc`, `operator new` and `operator new[]` (in all their
+    /// variants), and `__rust_alloc` and `__rust_alloc_zeroed`: return a
+    /// pointer to fresh memory
+    Alloc,
+    /// `realloc` and `__rust_realloc`: return a pointer to fresh memory, with
+    /// the contents of the memory pointed to by their first argument
+    Realloc,
+    /// `free`, `operator delete` and `operator delete[]` (in all their
+    /// variants), and `__rust_dealloc`: have no effect on taint, since the
+    /// contents of the memory are no longer used, and memory reused by a later
+    /// allocation is a fresh `Allocation`
+    Free,
+}
+
+impl Allocator {
+    /// Classify the function with the given (mangled) name
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "malloc" | "calloc" | "__rust_alloc" | "__rust_alloc_zeroed" => Some(Allocator::Alloc),
+            "realloc" | "__rust_realloc" => Some(Allocator::Realloc),
+            "free" | "__rust_dealloc" => Some(Allocator::Free),
+            // `operator new(size_t)` and `operator new[](size_t)`, with
+            // 64-bit (`m`) or 32-bit (`j`) `size_t`, optionally followed by
+            // `std::align_val_t` and/or `const std::nothrow_t&` parameters
+            _ => match name.strip_prefix("_Znw").or_else(|| name.strip_prefix("_Zna")) {
+                Some(rest) => match rest.strip_prefix('m').or_else(|| rest.strip_prefix('j')) {
+                    Some("") | Some("RKSt9nothrow_t") | Some("St11align_val_t") | Some("St11align_val_tRKSt9nothrow_t") => Some(Allocator::Alloc),
+                    _ => None,
+                },
+                None => match name.strip_prefix("_ZdlPv").or_else(|| name.strip_prefix("_ZdaPv")) {
+                    // `operator delete(void*)` and `operator delete[](void*)`,
+                    // optionally followed by a `size_t`, `std::align_val_t`,
+                    // and/or `const std::nothrow_t&` parameters
+                    Some(rest) => match rest.strip_prefix('m').or_else(|| rest.strip_prefix('j')).unwrap_or(rest) {
+                        "" | "RKSt9nothrow_t" | "St11align_val_t" | "St11align_val_tRKSt9nothrow_t" => Some(Allocator::Free),
+                        _ => None,
+                    },
+                    None => None,
+                },
+            },
+        }
+    }
+}
+
+/// The memory allocated by one call of an `Allocator`.
+///
+/// The allocator returns an `i8*`, which is typically cast right away to a
+/// pointer to the type actually stored there. We track the memory with two
+/// `Pointee`s, which we keep in sync: `raw`, the pointee of the `i8*`, and
+/// `typed`, the pointee of each cast to `typed_ty*`. Having the same
+/// `Pointee`s on each pass over the function means that taint stored through
+/// one cast is seen through every other.
+pub(crate) struct Allocation {
+    /// Pointee of the `i8*` the allocator returned
+    pub(crate) raw: Pointee,
+    /// The LLVM type of the memory, recovered from the first cast of the
+    /// allocator's result to another pointer type in the function (if there
+    /// is none, the memory is only used as `i8`s)
+    pub(crate) typed_ty: TypeRef,
+    /// Pointee of casts of the `i8*` to `typed_ty*`
+    pub(crate) typed: Pointee,
+}
@@ -0,0 +1,83 @@
+use crate::function_taint_state::DISPLAY_MAX_DEPTH;
+use crate::taint_result::TaintResult;
+use crate::tainted_type::TaintedType;
+use llvm_ir::Name;
+use std::io::Write;
+
+/// Write the functions with results as textual IR, annotated with the results
+/// of the analysis. See `TaintResult::write_annotated_ir()`.
+pub(crate) fn write_annotated_ir(taint_result: &TaintResult, mut writer: impl Write) -> Result<(), String> {
+    let mut fn_names: Vec<(&str, &str)> = taint_result.fn_taint_states
+        .iter()
+        .map(|(fn_name, fts)| (fts.module.name.as_str(), *fn_name))
+        .collect();
+    fn_names.sort_unstable();
+
+    let mut ir = String::new();
+    let mut globals: Vec<(&Name, &TaintedType)> = taint_result.global_types.iter().collect();
+    globals.sort_by_key(|(name, _)| *name);
+    for (name, ty) in globals {
+        ir.push_str(&format!("; {}: {}\n", global_name(name), annotation(taint_result, ty)));
+    }
+
+    let mut cur_module = None;
+    for (mod_name, fn_name) in fn_names {
+        if cur_module != Some(mod_name) {
+            ir.push_str(&format!("\n; ModuleID = '{}'\n", mod_name));
+            cur_module = Some(mod_name);
+        }
+        let fts = &taint_result.fn_taint_states[fn_name];
+        let func = match fts.module.get_func_by_name(fn_name) {
+            Some(func) => func,
+            None => continue,
+        };
+        let taint_map = fts.get_taint_map();
+        let params: Vec<String> = func.parameters.iter().map(|param| format!("{} {}", param.ty, param.name)).collect();
+        ir.push_str(&format!("\ndefine {} @{}({}) {{\n", func.return_type, fn_name, params.join(", ")));
+        for param in &func.parameters {
+            if let Some(ty) = taint_map.get(&param.name) {
+                ir.push_str(&format!("  ; {}: {}\n", param.name, annotation(taint_result, ty)));
+            }
+        }
+        for (i, bb) in func.basic_blocks.iter().enumerate() {
+            if i > 0 {
+                ir.push('\n');
+            }
+            ir.push_str(&format!("{}:\n", label(&bb.name)));
+            for inst in &bb.instrs {
+                ir.push_str(&format!("  {}", inst));
+                if let Some(ty) = inst.try_get_result().and_then(|dest| taint_map.get(dest)) {
+                    ir.push_str(&format!("  ; {}", annotation(taint_result, ty)));
+                }
+                ir.push('\n');
+            }
+            ir.push_str(&format!("  {}", bb.term));
+            if fts.is_terminator_tainted(&bb.name) {
+                ir.push_str("  ; [T]");
+            }
+            ir.push('\n');
+        }
+        ir.push_str("}\n");
+    }
+    writer.write_all(ir.as_bytes()).map_err(|e| format!("Failed to write annotated IR: {}", e))
+}
+
+/// The annotation for a value of the given type: the type, flagged with `[T]`
+/// if tainted
+fn annotation(taint_result: &TaintResult, ty: &TaintedType) -> String {
+    let flag = if taint_result.is_type_tainted_if_known(ty) { " [T]" } else { "" };
+    format!("{}{}", ty.abbreviated(DISPLAY_MAX_DEPTH), flag)
+}
+
+/// The name of the given block, as it appears in its label
+fn label(name: &Name) -> String {
+    match name {
+        Name::Name(name) => name.to_string(),
+        Name::Number(n) => n.to_string(),
+    }
+}
+
+/// The name of the given global, as it appears in the IR
+fn global_name(name: &Name) -> String {
+    format!("@{}", label(name))
+}
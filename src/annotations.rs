@@ -0,0 +1,133 @@
+use llvm_ir::{Constant, Module, Name, Type, TypeRef};
+use std::collections::HashSet;
+
+/// Annotation marking a function, global, or local variable as a taint source
+pub(crate) const SOURCE_ANNOTATION: &str = "taint.source";
+
+/// Annotation marking a function as a taint sink
+pub(crate) const SINK_ANNOTATION: &str = "taint.sink";
+
+/// The taint policy embedded in the analyzed module(s) by annotations on
+/// functions and globals (see `Config::honor_annotations`)
+#[derive(Default)]
+pub(crate) struct Annotations {
+    /// Names of the functions annotated as sources
+    source_functions: HashSet<String>,
+    /// Names of the functions annotated as sinks
+    sink_functions: HashSet<String>,
+    /// Names of the globals annotated as sources, with the LLVM types of
+    /// their contents
+    source_globals: Vec<(Name, TypeRef)>,
+}
+
+impl Annotations {
+    /// Collect the annotations in the given modules, which are the entries of
+    /// their `llvm.global.annotations` arrays. Each entry is a struct whose
+    /// first two fields are (a cast of) the annotated function or global, and
+    /// the annotation string; as Clang produces for
+    /// `__attribute__((annotate("taint.source")))`.
+    pub(crate) fn from_modules<'m>(modules: impl IntoIterator<Item = &'m Module>) -> Self {
+        let mut annotations = Self::default();
+        for module in modules {
+            let entries = module.global_vars
+                .iter()
+                .find(|var| var.name == Name::from("llvm.global.annotations"))
+                .and_then(|var| var.initializer.as_ref())
+                .map(|init| match init.as_ref() {
+                    Constant::Array { elements, .. } => elements.clone(),
+                    _ => vec![],
+                })
+                .unwrap_or_default();
+            for entry in entries {
+                let (target, annotation) = match entry.as_ref() {
+                    Constant::Struct { values, .. } if values.len() >= 2 => (&values[0], &values[1]),
+                    _ => continue,
+                };
+                let target = match strip_casts(target) {
+                    Constant::GlobalReference { name, .. } => name.clone(),
+                    _ => continue,
+                };
+                match constant_string(module, annotation).as_deref() {
+                    Some(SOURCE_ANNOTATION) => annotations.add_source(module, target),
+                    Some(SINK_ANNOTATION) => {
+                        if let Name::Name(name) = target {
+                            annotations.sink_functions.insert(name.to_string());
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+        annotations
+    }
+
+    fn add_source(&mut self, module: &Module, target: Name) {
+        let global = module.global_vars.iter().find(|var| var.name == target);
+        match (global, target) {
+            (Some(global), name) => {
+                if let Type::PointerType { pointee_type, .. } = global.ty.as_ref() {
+                    self.source_globals.push((name, pointee_type.clone()));
+                }
+            },
+            (None, Name::Name(name)) => {
+                self.source_functions.insert(name.to_string());
+            },
+            (None, Name::Number(_)) => {},
+        }
+    }
+
+    /// Is the function with the given name annotated as a source?
+    pub(crate) fn is_source_function(&self, fn_name: &str) -> bool {
+        self.source_functions.contains(fn_name)
+    }
+
+    /// Iterate over the names of the functions annotated as sources
+    pub(crate) fn source_functions(&self) -> impl Iterator<Item = &str> {
+        self.source_functions.iter().map(String::as_str)
+    }
+
+    /// Is the function with the given name annotated as a sink?
+    pub(crate) fn is_sink_function(&self, fn_name: &str) -> bool {
+        self.sink_functions.contains(fn_name)
+    }
+
+    /// Iterate over the globals annotated as sources, with the LLVM types of
+    /// their contents
+    pub(crate) fn source_globals(&self) -> impl Iterator<Item = &(Name, TypeRef)> {
+        self.source_globals.iter()
+    }
+}
+
+/// Get the string held by the global which the given constant points to, if
+/// it's a constant string (without its terminating NUL), as annotation
+/// strings are
+pub(crate) fn constant_string(module: &Module, constant: &Constant) -> Option<String> {
+    let name = match strip_casts(constant) {
+        Constant::GlobalReference { name, .. } => name,
+        _ => return None,
+    };
+    let init = module.global_vars.iter().find(|var| &var.name == name)?.initializer.as_ref()?;
+    let bytes = match init.as_ref() {
+        Constant::Array { elements, .. } => elements
+            .iter()
+            .map(|element| match element.as_ref() {
+                Constant::Int { bits: 8, value } => Some(*value as u8),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()?,
+        _ => return None,
+    };
+    let bytes = bytes.split(|&b| b == 0).next().unwrap_or(&[]);
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+/// Look through any `bitcast`s, `addrspacecast`s, and `getelementptr`s (e.g.,
+/// to the first character of a string) in the given constant
+pub(crate) fn strip_casts(constant: &Constant) -> &Constant {
+    match constant {
+        Constant::BitCast(bc) => strip_casts(&bc.operand),
+        Constant::AddrSpaceCast(ac) => strip_casts(&ac.operand),
+        Constant::GetElementPtr(gep) => strip_casts(&gep.address),
+        constant => constant,
+    }
+}
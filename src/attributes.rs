@@ -0,0 +1,73 @@
+use crate::config::ExternalFunctionHandling;
+use llvm_ir::function::{FunctionAttribute, ParameterAttribute};
+
+// Braced patterns below match these attributes whether or not they carry a
+// type, which they do only in LLVM 12 or greater
+
+/// Is a parameter (or argument) with the given attributes a hidden pointer to
+/// the function's return value (`sret`)?
+pub(crate) fn is_sret(attrs: &[ParameterAttribute]) -> bool {
+    attrs.iter().any(|attr| matches!(attr, ParameterAttribute::SRet { .. }))
+}
+
+/// Is a parameter (or argument) with the given attributes passed by value
+/// (`byval`), i.e., is the callee's pointee a copy of the caller's?
+pub(crate) fn is_byval(attrs: &[ParameterAttribute]) -> bool {
+    attrs.iter().any(|attr| matches!(attr, ParameterAttribute::ByVal { .. }))
+}
+
+/// Get the index of the `sret` parameter among parameters (or arguments) with
+/// the given attributes, if any
+pub(crate) fn sret_index<'a>(attrs: impl IntoIterator<Item = &'a [ParameterAttribute]>) -> Option<usize> {
+    attrs.into_iter().position(is_sret)
+}
+
+/// Refine the configured handling of an external function using its
+/// attributes at each of its call sites (see `Config::ext_function_attributes`).
+/// An attribute is only used if it's present at every call site.
+///
+/// Only the `PropagateTaint` handlings are refined:
+///   - a function which can't read memory (`readnone` or `writeonly`) can't
+///     look at the data its arguments point to, so `PropagateTaintDeep`
+///     becomes `PropagateTaintShallow`. (This is the only way calls with a
+///     `PropagateTaintDeep` handling can currently be processed; otherwise
+///     they hit its `unimplemented!()`.)
+///   - a function whose result is `noalias` returns a pointer to fresh
+///     memory, so the pointer value itself isn't derived from the arguments:
+///     `PropagateTaintShallow` becomes `IgnoreAndReturnUntainted`. So does
+///     `PropagateTaintDeep`, if the function also can't write memory
+///     (`readnone` or `readonly`), leaving the fresh memory untainted.
+pub(crate) fn refine_ext_handling<'a>(
+    handling: ExternalFunctionHandling,
+    call_sites: impl IntoIterator<Item = (&'a [FunctionAttribute], &'a [ParameterAttribute])>,
+) -> ExternalFunctionHandling {
+    let mut call_sites = call_sites.into_iter().peekable();
+    if call_sites.peek().is_none() {
+        return handling;
+    }
+    let (mut reads_memory, mut writes_memory, mut returns_noalias) = (false, false, true);
+    for (fn_attrs, ret_attrs) in call_sites {
+        let has = |attr: FunctionAttribute| fn_attrs.contains(&attr);
+        reads_memory |= !has(FunctionAttribute::ReadNone) && !has(FunctionAttribute::WriteOnly);
+        writes_memory |= !has(FunctionAttribute::ReadNone) && !has(FunctionAttribute::ReadOnly);
+        returns_noalias &= ret_attrs.contains(&ParameterAttribute::NoAlias);
+    }
+    match handling {
+        ExternalFunctionHandling::PropagateTaintDeep if returns_noalias && !writes_memory => {
+            ExternalFunctionHandling::IgnoreAndReturnUntainted
+        },
+        ExternalFunctionHandling::PropagateTaintDeep if !reads_memory => {
+            refine_shallow(returns_noalias)
+        },
+        ExternalFunctionHandling::PropagateTaintShallow => refine_shallow(returns_noalias),
+        handling => handling,
+    }
+}
+
+fn refine_shallow(returns_noalias: bool) -> ExternalFunctionHandling {
+    if returns_noalias {
+        ExternalFunctionHandling::IgnoreAndReturnUntainted
+    } else {
+        ExternalFunctionHandling::PropagateTaintShallow
+    }
+}
@@ -0,0 +1,113 @@
+use crate::config::Config;
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use crate::transfer::fully_tainted;
+use llvm_ir::module::{Linkage, Visibility};
+use llvm_ir::{Module, Name};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The result of a library audit (see `do_library_audit()`): what becomes
+/// tainted when every exported function of a library is called with entirely
+/// tainted arguments
+pub struct LibraryAuditResult<'m> {
+    /// Names of the exported functions, sorted
+    entry_points: Vec<&'m str>,
+
+    /// Result of the analysis of all the entry points together
+    taint_result: TaintResult<'m>,
+}
+
+impl<'m> LibraryAuditResult<'m> {
+    /// Get the names of the exported functions which were treated as entry
+    /// points, sorted
+    pub fn get_entry_points(&self) -> &[&'m str] {
+        &self.entry_points
+    }
+
+    /// Get the underlying `TaintResult`, e.g. for the taint of individual
+    /// variables
+    pub fn get_taint_result(&self) -> &TaintResult<'m> {
+        &self.taint_result
+    }
+
+    /// Get the instructions which pass tainted data to a sink (see `Config`),
+    /// as (function name, block name, index of the instruction within the
+    /// block), sorted
+    pub fn get_tainted_sinks(&self) -> Vec<(&str, &Name, usize)> {
+        let mut fn_names: Vec<&str> = self.taint_result.fn_taint_states.keys().copied().collect();
+        fn_names.sort_unstable();
+        fn_names
+            .into_iter()
+            .flat_map(|fn_name| self.taint_result.get_tainted_sinks(fn_name).into_iter().map(move |(block, index)| (fn_name, block, index)))
+            .collect()
+    }
+
+    /// Get the names of the globals whose contents become tainted, sorted
+    pub fn get_tainted_globals(&self) -> Vec<&Name> {
+        self.taint_result.get_tainted_globals()
+    }
+
+    /// Get the (top-level) fields of named structs which become tainted, as
+    /// (struct name, field index), sorted
+    pub fn get_tainted_fields(&self) -> Vec<(&str, u32)> {
+        let mut fields: Vec<(&str, u32)> = self.taint_result
+            .named_struct_types
+            .iter()
+            .flat_map(|(name, ty)| {
+                let num_fields = match ty {
+                    TaintedType::Struct(elements) => elements.len() as u32,
+                    _ => 0,
+                };
+                (0 .. num_fields)
+                    .filter(move |&index| self.taint_result.is_field_tainted(name, &[index]))
+                    .map(move |index| (name.as_str(), index))
+            })
+            .collect();
+        fields.sort_unstable();
+        fields
+    }
+}
+
+impl<'m> fmt::Display for LibraryAuditResult<'m> {
+    /// Displays the entry points, followed by the tainted sinks, globals, and
+    /// named struct fields, one per line
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "entry points: {}", self.entry_points.join(", "))?;
+        writeln!(f, "tainted sinks:")?;
+        for (fn_name, block, index) in self.get_tainted_sinks() {
+            writeln!(f, "  {} block {} instruction {}", fn_name, block, index)?;
+        }
+        writeln!(f, "tainted globals:")?;
+        for global in self.get_tainted_globals() {
+            writeln!(f, "  {}", global)?;
+        }
+        writeln!(f, "tainted fields:")?;
+        for (struct_name, index) in self.get_tainted_fields() {
+            writeln!(f, "  {:?} field {}", struct_name, index)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn audit_library<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+) -> LibraryAuditResult<'m> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let exported: HashMap<&'m str, Vec<TaintedType>> = modules
+        .iter()
+        .flat_map(|module| module.functions.iter())
+        .filter(|func| func.linkage == Linkage::External && func.visibility != Visibility::Hidden)
+        .map(|func| (func.name.as_str(), func.parameters.iter().map(|param| fully_tainted(&param.ty)).collect()))
+        .collect();
+    let mut entry_points: Vec<&'m str> = exported.keys().copied().collect();
+    entry_points.sort_unstable();
+    let taint_result = TaintState::do_analysis_multiple_functions(modules, config, exported, HashMap::new(), HashMap::new())
+        .into_taint_result();
+    LibraryAuditResult {
+        entry_points,
+        taint_result,
+    }
+}
@@ -0,0 +1,203 @@
+//! Reading the input files: bitcode, textual IR, and static archives of
+//! bitcode, named directly or in response files.
+
+use llvm_ir::Module;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Magic number at the start of a bitcode file
+const BITCODE_MAGIC: &[u8] = b"BC\xc0\xde";
+/// Magic number at the start of a bitcode file in a wrapper header, as on
+/// Darwin
+const BITCODE_WRAPPER_MAGIC: &[u8] = b"\xde\xc0\x17\x0b";
+/// Magic string at the start of a static archive
+const ARCHIVE_MAGIC: &[u8] = b"!<arch>\n";
+/// Magic string at the start of a thin archive, whose members are stored
+/// outside it
+const THIN_ARCHIVE_MAGIC: &[u8] = b"!<thin>\n";
+/// Length of the header of each archive member
+const MEMBER_HEADER_LEN: usize = 60;
+
+/// Expand the response files among the given command-line arguments: an
+/// argument `@file` is replaced by the arguments in `file`, which are
+/// separated by whitespace and may be quoted with `'` or `"`, with `\`
+/// escaping the next character, as for linkers. Response files may refer to
+/// other response files.
+pub fn expand_response_files(args: impl IntoIterator<Item = String>) -> Result<Vec<String>, String> {
+    let mut expanded = Vec::new();
+    for arg in args {
+        expand_arg(arg, &mut expanded, &mut Vec::new())?;
+    }
+    Ok(expanded)
+}
+
+/// Expand the given argument into `expanded`. `open` is the response files
+/// currently being expanded, to detect cycles.
+fn expand_arg(arg: String, expanded: &mut Vec<String>, open: &mut Vec<String>) -> Result<(), String> {
+    let path = match arg.strip_prefix('@') {
+        Some(path) if !path.is_empty() => path.to_owned(),
+        _ => {
+            expanded.push(arg);
+            return Ok(());
+        },
+    };
+    if open.contains(&path) {
+        return Err(format!("response file {:?} includes itself", path));
+    }
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("failed to read response file {:?}: {}", path, e))?;
+    open.push(path);
+    for arg in split_response_file(&text) {
+        expand_arg(arg, expanded, open)?;
+    }
+    open.pop();
+    Ok(())
+}
+
+/// Split the contents of a response file into arguments
+fn split_response_file(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    arg.get_or_insert_with(String::new).push(escaped);
+                }
+            },
+            c if Some(c) == quote => quote = None,
+            '\'' | '"' if quote.is_none() => {
+                quote = Some(c);
+                arg.get_or_insert_with(String::new);
+            },
+            c if c.is_whitespace() && quote.is_none() => args.extend(arg.take()),
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+    args
+}
+
+/// Parse the modules in the input file at the given path: a bitcode file, a
+/// textual IR file (assembled with `llvm-as`, or the program named by the
+/// `LLVM_AS` environment variable), or a static archive, whose bitcode
+/// members are each a module. Other (e.g. native object) members of an
+/// archive are skipped with a warning.
+pub fn read_input(path: &str) -> Result<Vec<Module>, String> {
+    let contents = std::fs::read(path).map_err(|e| format!("failed to read {:?}: {}", path, e))?;
+    if contents.starts_with(ARCHIVE_MAGIC) {
+        read_archive(path, &contents)
+    } else if contents.starts_with(THIN_ARCHIVE_MAGIC) {
+        Err(format!("{:?} is a thin archive, which isn't supported; pass its members instead", path))
+    } else if is_bitcode(&contents) {
+        Ok(vec![Module::from_bc_path(path).map_err(|e| format!("failed to parse {:?}: {}", path, e))?])
+    } else if path.ends_with(".ll") {
+        let bitcode = TempFile::new("bc");
+        let llvm_as = std::env::var("LLVM_AS").unwrap_or_else(|_| "llvm-as".into());
+        let output = Command::new(&llvm_as)
+            .arg(path)
+            .arg("-o")
+            .arg(&bitcode.0)
+            .output()
+            .map_err(|e| format!("failed to run {:?} on {:?}: {}", llvm_as, path, e))?;
+        if !output.status.success() {
+            return Err(format!("failed to assemble {:?}:\n{}", path, String::from_utf8_lossy(&output.stderr)));
+        }
+        let mut module = Module::from_bc_path(&bitcode.0).map_err(|e| format!("failed to parse {:?}: {}", path, e))?;
+        module.name = path.to_owned();
+        Ok(vec![module])
+    } else {
+        Err(format!("{:?} is neither bitcode, textual IR (.ll), nor an archive", path))
+    }
+}
+
+/// Is the given file contents bitcode?
+fn is_bitcode(contents: &[u8]) -> bool {
+    contents.starts_with(BITCODE_MAGIC) || contents.starts_with(BITCODE_WRAPPER_MAGIC)
+}
+
+/// Parse the bitcode members of the archive at the given path, with the given
+/// contents, in either the GNU or the BSD format. Each module is named
+/// `archive(member)`.
+fn read_archive(path: &str, contents: &[u8]) -> Result<Vec<Module>, String> {
+    let malformed = |what: &str| format!("malformed archive {:?}: {}", path, what);
+    let mut modules = Vec::new();
+    // the GNU table of long member names
+    let mut long_names: &[u8] = &[];
+    let mut offset = ARCHIVE_MAGIC.len();
+    while offset < contents.len() {
+        let header = contents
+            .get(offset .. offset + MEMBER_HEADER_LEN)
+            .ok_or_else(|| malformed("truncated member header"))?;
+        if &header[58 ..] != b"`\n" {
+            return Err(malformed("bad member header"));
+        }
+        let field = |range: std::ops::Range<usize>| String::from_utf8_lossy(&header[range]).trim_end().to_owned();
+        let raw_name = field(0 .. 16);
+        let size: usize = field(48 .. 58).parse().map_err(|_| malformed("bad member size"))?;
+        let data_start = offset + MEMBER_HEADER_LEN;
+        let mut data = contents
+            .get(data_start .. data_start + size)
+            .ok_or_else(|| malformed("truncated member"))?;
+        // members are aligned to 2 bytes
+        offset = data_start + size + size % 2;
+
+        let name = if raw_name == "/" || raw_name == "/SYM64/" {
+            // GNU symbol table
+            continue;
+        } else if raw_name == "//" {
+            long_names = data;
+            continue;
+        } else if let Some(len) = raw_name.strip_prefix("#1/") {
+            // BSD: the name precedes the data
+            let len: usize = len.parse().map_err(|_| malformed("bad member name length"))?;
+            let name = data.get(.. len).ok_or_else(|| malformed("truncated member name"))?;
+            data = &data[len ..];
+            String::from_utf8_lossy(name).trim_end_matches('\0').to_owned()
+        } else if let Some(name_offset) = raw_name.strip_prefix('/') {
+            // GNU: the name is in the table of long names, ending with "/\n"
+            let name_offset: usize = name_offset.parse().map_err(|_| malformed("bad long member name"))?;
+            let name = long_names.get(name_offset ..).ok_or_else(|| malformed("bad long member name"))?;
+            let end = name.iter().position(|&b| b == b'\n').unwrap_or(name.len());
+            String::from_utf8_lossy(&name[.. end]).trim_end_matches('/').to_owned()
+        } else {
+            raw_name.trim_end_matches('/').to_owned()
+        };
+        if name.starts_with("__.SYMDEF") {
+            // BSD symbol table
+            continue;
+        }
+
+        if !is_bitcode(data) {
+            eprintln!("llvm-ir-taint: warning: skipping {}({}), which isn't bitcode", path, name);
+            continue;
+        }
+        // `llvm-ir` only parses bitcode from files
+        let member = TempFile::new("bc");
+        std::fs::write(&member.0, data).map_err(|e| format!("failed to extract {}({}): {}", path, name, e))?;
+        let mut module = Module::from_bc_path(&member.0).map_err(|e| format!("failed to parse {}({}): {}", path, name, e))?;
+        module.name = format!("{}({})", path, name);
+        modules.push(module);
+    }
+    Ok(modules)
+}
+
+/// A file in the temporary directory, removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Make a path for a new temporary file with the given extension
+    fn new(extension: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!("llvm-ir-taint-{}-{}.{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed), extension);
+        Self(std::env::temp_dir().join(name))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
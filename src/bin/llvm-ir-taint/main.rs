@@ -0,0 +1,192 @@
+//! Command-line interface to the taint analysis: analyzes LLVM bitcode files
+//! together and writes the results to stdout in the chosen format. Run with
+//! `--help` for usage.
+
+mod inputs;
+
+use llvm_ir_taint::config::ExternalFunctionHandling;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process;
+
+const USAGE: &str = "\
+usage: llvm-ir-taint [options] <input>...
+
+Analyzes the given inputs together, resolving calls between them, and writes
+the results to stdout. Each input is an LLVM bitcode file (.bc), a textual IR
+file (.ll, assembled with llvm-as, or $LLVM_AS if set), or a static archive of
+bitcode files (.a). An argument @<file> is replaced by the arguments in <file>.
+
+options:
+  --entry <fn>         start the analysis in the given function (may be
+                       repeated); by default, all functions are analyzed
+  --tainted-arg <n>    the <n>th (0-based) argument of each entry function,
+                       and everything reachable through it, is tainted (may be
+                       repeated)
+  --source <fn>        calls of the given external function return tainted
+                       data (may be repeated); calls of other external
+                       functions return tainted data if any of their
+                       arguments is
+  --format <format>    output format:
+                         text   the taint of each variable (default)
+                         json   a JSON array with one object per value
+                         csv    a table with one row per value
+                         dot    the call graph, or with --function, the CFG of
+                                each of the given functions, in Graphviz format
+                         sarif  the tainted sinks, frees, and format strings, as
+                                a SARIF log; allocators, format functions, and
+                                register writes are modeled for this
+  --function <fn>      only output the given function (may be repeated)
+  --only-tainted       only output tainted values (text, json, and csv)
+  -h, --help           print this message
+";
+
+/// Output format, as selected with `--format`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+    Dot,
+    Sarif,
+}
+
+/// The parsed command line
+#[derive(Debug)]
+struct Options {
+    inputs: Vec<String>,
+    entries: Vec<String>,
+    tainted_args: Vec<usize>,
+    sources: Vec<String>,
+    format: Format,
+    filter: OutputFilter,
+}
+
+fn main() {
+    let options = inputs::expand_response_files(std::env::args().skip(1))
+        .and_then(|args| parse_args(args.into_iter()))
+        .unwrap_or_else(|e| {
+            eprintln!("llvm-ir-taint: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        });
+    if let Err(e) = run(&options) {
+        eprintln!("llvm-ir-taint: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Parse the given command-line arguments (without the program name)
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut inputs = Vec::new();
+    let mut entries = Vec::new();
+    let mut tainted_args = Vec::new();
+    let mut sources = Vec::new();
+    let mut format = Format::Text;
+    let mut filter = OutputFilter::default();
+    while let Some(arg) = args.next() {
+        let mut value = |option: &str| args.next().ok_or_else(|| format!("missing value for {}", option));
+        match arg.as_str() {
+            "--entry" => entries.push(value("--entry")?),
+            "--tainted-arg" => {
+                let n = value("--tainted-arg")?;
+                tainted_args.push(n.parse().map_err(|_| format!("invalid argument index {:?}", n))?);
+            },
+            "--source" => sources.push(value("--source")?),
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    "dot" => Format::Dot,
+                    "sarif" => Format::Sarif,
+                    other => return Err(format!("unknown format {:?}", other)),
+                }
+            },
+            "--function" => filter.functions.push(value("--function")?),
+            "--only-tainted" => filter.only_tainted = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            },
+            option if option.starts_with('-') => return Err(format!("unknown option {:?}", option)),
+            _ => inputs.push(arg),
+        }
+    }
+    if !tainted_args.is_empty() && entries.is_empty() {
+        return Err("--tainted-arg requires --entry".into());
+    }
+    if inputs.is_empty() {
+        return Err("no input files".into());
+    }
+    Ok(Options { inputs, entries, tainted_args, sources, format, filter })
+}
+
+/// Analyze the input and write the results to stdout
+fn run(options: &Options) -> Result<(), String> {
+    let mut modules = Vec::new();
+    for input in &options.inputs {
+        modules.extend(inputs::read_input(input)?);
+    }
+
+    let mut config = Config::default();
+    config.ext_functions_default = ExternalFunctionHandling::PropagateTaintShallow;
+    for source in &options.sources {
+        config.ext_functions.insert(source.clone(), ExternalFunctionHandling::IgnoreAndReturnTainted);
+    }
+    if options.format == Format::Sarif {
+        config.model_allocators = true;
+        config.report_tainted_frees = true;
+        config.model_format_functions = true;
+        config.report_tainted_format_strings = true;
+        config.register_writes_are_sinks = true;
+    }
+
+    let taint_result = if options.entries.is_empty() {
+        do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new())
+    } else {
+        let mut args = HashMap::new();
+        for entry in &options.entries {
+            let func = modules
+                .iter()
+                .find_map(|module| module.get_func_by_name(entry))
+                .ok_or_else(|| format!("no function named {:?} in the input", entry))?;
+            let mut fn_args = Vec::new();
+            for (i, param) in func.parameters.iter().enumerate() {
+                fn_args.push(if options.tainted_args.contains(&i) {
+                    TaintedType::fully_tainted_from_llvm_type(&param.ty)
+                } else {
+                    TaintedType::from_llvm_type(&param.ty)
+                });
+            }
+            if let Some(&n) = options.tainted_args.iter().find(|&&n| n >= func.parameters.len()) {
+                return Err(format!("{:?} has no argument {}", entry, n));
+            }
+            args.insert(func.name.as_str(), fn_args);
+        }
+        TaintState::do_analysis_multiple_functions(&modules, &config, args, HashMap::new(), HashMap::new())
+            .into_taint_result()
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    match options.format {
+        Format::Text => taint_result.write_text(&options.filter, &mut out)?,
+        Format::Json => taint_result.write_filtered_table(TableFormat::Json, &options.filter, &mut out)?,
+        Format::Csv => taint_result.write_filtered_table(TableFormat::Csv, &options.filter, &mut out)?,
+        Format::Dot if options.filter.functions.is_empty() => taint_result.write_call_graph_dot(&mut out)?,
+        Format::Dot => {
+            for name in &options.filter.functions {
+                let fn_names = taint_result.find_functions(name);
+                if fn_names.is_empty() {
+                    return Err(format!("no results for function {:?}", name));
+                }
+                for fn_name in fn_names {
+                    taint_result.write_cfg_dot(fn_name, &mut out)?;
+                }
+            }
+        },
+        Format::Sarif => taint_result.write_sarif(&options.filter, &mut out)?,
+    }
+    out.flush().map_err(|e| format!("failed to write output: {}", e))
+}
@@ -0,0 +1,117 @@
+use crate::cfg_dot::quote;
+use crate::demangle::demangle;
+use crate::taint_result::TaintResult;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+/// Color of edges along which tainted data flows
+const TAINTED_EDGE_COLOR: &str = "red";
+
+/// Fill color of functions which return tainted data
+const TAINTED_RETURN_COLOR: &str = "lightpink";
+
+/// All the direct calls from one function to another
+#[derive(Default)]
+struct Edge {
+    /// Number of call sites
+    calls: usize,
+    /// Is any of the calls passed tainted data?
+    args_tainted: bool,
+    /// Does any of the calls return tainted data?
+    ret_tainted: bool,
+}
+
+impl Edge {
+    /// Describe the taint flowing along this edge, e.g. `args, ret`, or
+    /// `None` if there is none
+    fn taint_label(&self) -> Option<&'static str> {
+        match (self.args_tainted, self.ret_tainted) {
+            (true, true) => Some("args, ret"),
+            (true, false) => Some("args"),
+            (false, true) => Some("ret"),
+            (false, false) => None,
+        }
+    }
+}
+
+/// The call graph of the functions with results: the functions (including the
+/// external functions they call), and the edges between them, each sorted
+struct CallGraph<'m> {
+    functions: BTreeSet<&'m str>,
+    edges: BTreeMap<(&'m str, &'m str), Edge>,
+}
+
+impl<'m> CallGraph<'m> {
+    fn new(taint_result: &TaintResult<'m>) -> Self {
+        let mut functions: BTreeSet<&'m str> = taint_result.fn_taint_states.keys().copied().collect();
+        let mut edges: BTreeMap<(&'m str, &'m str), Edge> = BTreeMap::new();
+        for call in taint_result.direct_calls() {
+            functions.insert(call.callee);
+            let edge = edges.entry((call.caller, call.callee)).or_default();
+            edge.calls += 1;
+            edge.args_tainted |= call.args_tainted;
+            edge.ret_tainted |= call.ret_tainted;
+        }
+        Self { functions, edges }
+    }
+}
+
+/// Write the call graph as Graphviz. See `TaintResult::write_call_graph_dot()`.
+pub(crate) fn write_call_graph_dot(taint_result: &TaintResult, mut writer: impl Write) -> Result<(), String> {
+    let call_graph = CallGraph::new(taint_result);
+    let mut dot = String::new();
+    dot.push_str("digraph \"call graph\" {\n");
+    dot.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+    for fn_name in &call_graph.functions {
+        let mut attrs = vec![format!("label={}", quote(&demangle(fn_name).unwrap_or_else(|| fn_name.to_string())))];
+        if !taint_result.fn_taint_states.contains_key(fn_name) {
+            attrs.push("style=dashed".to_owned());
+        } else if taint_result.does_function_return_tainted(fn_name) {
+            attrs.push(format!("style=filled, fillcolor={}", TAINTED_RETURN_COLOR));
+        }
+        dot.push_str(&format!("  {} [{}];\n", quote(fn_name), attrs.join(", ")));
+    }
+    for ((caller, callee), edge) in &call_graph.edges {
+        let attrs = match edge.taint_label() {
+            Some(label) => format!(" [label={}, color={}, fontcolor={}]", quote(label), TAINTED_EDGE_COLOR, TAINTED_EDGE_COLOR),
+            None => String::new(),
+        };
+        dot.push_str(&format!("  {} -> {}{};\n", quote(caller), quote(callee), attrs));
+    }
+    dot.push_str("}\n");
+    writer.write_all(dot.as_bytes()).map_err(|e| format!("Failed to write call graph: {}", e))
+}
+
+/// Write the call graph as JSON. See `TaintResult::write_call_graph_json()`.
+pub(crate) fn write_call_graph_json(taint_result: &TaintResult, writer: impl Write) -> Result<(), String> {
+    let call_graph = CallGraph::new(taint_result);
+    let functions: Vec<Value> = call_graph.functions
+        .iter()
+        .map(|fn_name| {
+            let analyzed = taint_result.fn_taint_states.contains_key(fn_name);
+            json!({
+                "name": fn_name,
+                "demangled": demangle(fn_name),
+                "module": if analyzed { Some(taint_result.get_module_name(fn_name)) } else { None },
+                "analyzed": analyzed,
+                "returns_tainted": taint_result.does_function_return_tainted(fn_name),
+            })
+        })
+        .collect();
+    let edges: Vec<Value> = call_graph.edges
+        .iter()
+        .map(|((caller, callee), edge)| json!({
+            "caller": caller,
+            "callee": callee,
+            "calls": edge.calls,
+            "tainted_args": edge.args_tainted,
+            "tainted_return": edge.ret_tainted,
+        }))
+        .collect();
+    let graph = json!({
+        "functions": functions,
+        "edges": edges,
+    });
+    serde_json::to_writer_pretty(writer, &graph).map_err(|e| format!("Failed to write call graph: {}", e))
+}
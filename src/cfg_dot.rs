@@ -0,0 +1,107 @@
+use crate::function_taint_state::{FunctionTaintState, DISPLAY_MAX_DEPTH};
+use crate::taint_result::TaintResult;
+use llvm_ir::Name;
+use llvm_ir_analysis::{CFGNode, ControlDependenceGraph, FunctionAnalysis};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Fill color of blocks which execute under tainted control
+const TAINTED_CONTROL_COLOR: &str = "lightpink";
+
+/// Write the control-flow graph of the given function as Graphviz, annotated
+/// with the results of the analysis. See `TaintResult::write_cfg_dot()`.
+pub(crate) fn write_cfg_dot(taint_result: &TaintResult, fn_name: &str, mut writer: impl Write) -> Result<(), String> {
+    let fts = taint_result.fn_taint_states
+        .get(fn_name)
+        .ok_or_else(|| format!("write_cfg_dot: no taint map found for function {:?}", fn_name))?;
+    let f = fts.module
+        .get_func_by_name(fn_name)
+        .ok_or_else(|| format!("write_cfg_dot: function {:?} not found in module {:?}", fn_name, fts.module.name))?;
+    let fn_analysis = FunctionAnalysis::new(f);
+    let cfg = fn_analysis.control_flow_graph();
+    let cdg = fn_analysis.control_dependence_graph();
+    let taint_map = fts.get_taint_map();
+    let node_ids: HashMap<&Name, String> = f.basic_blocks
+        .iter()
+        .enumerate()
+        .map(|(i, bb)| (&bb.name, format!("b{}", i)))
+        .collect();
+    let node_id = |node: CFGNode| match node {
+        CFGNode::Block(name) => node_ids[name].as_str(),
+        CFGNode::Return => "ret",
+    };
+
+    let mut dot = String::new();
+    dot.push_str(&format!("digraph {} {{\n", quote(fn_name)));
+    dot.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+    for bb in &f.basic_blocks {
+        let mut label = format!("{}:\\l", escape(&bb.name.to_string()));
+        for inst in &bb.instrs {
+            label.push_str(&escape(&inst.to_string()));
+            if let Some(ty) = inst.try_get_result().and_then(|dest| taint_map.get(dest)) {
+                let flag = if taint_result.is_type_tainted_if_known(ty) { " [T]" } else { "" };
+                label.push_str(&format!("  ; {}{}", escape(&ty.abbreviated(DISPLAY_MAX_DEPTH).to_string()), flag));
+            }
+            label.push_str("\\l");
+        }
+        label.push_str(&escape(&bb.term.to_string()));
+        if fts.is_terminator_tainted(&bb.name) {
+            label.push_str("  ; [T]");
+        }
+        label.push_str("\\l");
+        let style = if is_under_tainted_control(fts, &cdg, &bb.name) {
+            format!(", style=filled, fillcolor={}", TAINTED_CONTROL_COLOR)
+        } else {
+            String::new()
+        };
+        dot.push_str(&format!("  {} [label=\"{}\"{}];\n", node_ids[&bb.name], label, style));
+    }
+    dot.push_str("  ret [label=\"return\", shape=oval];\n");
+    for bb in &f.basic_blocks {
+        for succ in cfg.succs(&bb.name) {
+            dot.push_str(&format!("  {} -> {};\n", node_ids[&bb.name], node_id(succ)));
+        }
+    }
+    dot.push_str("}\n");
+    writer.write_all(dot.as_bytes()).map_err(|e| format!("Failed to write CFG: {}", e))
+}
+
+/// Find the blocks which execute under tainted control, in all the functions
+/// with results. See `TaintResult::blocks_under_tainted_control()`.
+pub(crate) fn blocks_under_tainted_control<'m>(taint_result: &TaintResult<'m>) -> Vec<(&'m str, &'m Name)> {
+    let mut blocks = Vec::new();
+    for (fn_name, fts) in &taint_result.fn_taint_states {
+        if fts.get_tainted_terminators().next().is_none() {
+            continue;
+        }
+        let f = match fts.module.get_func_by_name(fn_name) {
+            Some(f) => f,
+            None => continue,
+        };
+        let fn_analysis = FunctionAnalysis::new(f);
+        let cdg = fn_analysis.control_dependence_graph();
+        for bb in &f.basic_blocks {
+            if is_under_tainted_control(fts, &cdg, &bb.name) {
+                blocks.push((*fn_name, &bb.name));
+            }
+        }
+    }
+    blocks.sort_unstable();
+    blocks
+}
+
+/// Is the given block control-dependent, directly or transitively, on a block
+/// with a tainted terminator?
+fn is_under_tainted_control(fts: &FunctionTaintState, cdg: &ControlDependenceGraph, block: &Name) -> bool {
+    cdg.get_control_dependencies(block).any(|dep| fts.is_terminator_tainted(dep))
+}
+
+/// Escape the given text for use in a double-quoted Graphviz string
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\l")
+}
+
+/// Quote the given text as a Graphviz string
+pub(crate) fn quote(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
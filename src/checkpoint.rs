@@ -0,0 +1,312 @@
+use crate::pointee::Pointee;
+use crate::tainted_type::{FnTargets, TaintedType};
+use llvm_ir::Name;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::RwLock;
+
+/// A snapshot of an in-progress (or finished) analysis, which can be written
+/// to disk and later used to resume the analysis, possibly on another machine.
+///
+/// Create one with `TaintState::checkpoint()`, and resume from it with
+/// `TaintState::resume_from_checkpoint()`.
+///
+/// A `Checkpoint` doesn't contain any references to the analyzed `Module`(s):
+/// functions, variables, named structs, and globals are all identified by
+/// name. It also doesn't contain the `Config`. To resume, you must provide the
+/// same `Module`(s), and should provide an equivalent `Config`.
+///
+/// `Checkpoint` implements `serde`'s `Serialize` and `Deserialize`, so it can
+/// be stored in any format `serde` supports; `write_json()` and `read_json()`
+/// are provided for convenience.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    /// Names of the functions on the worklist
+    pub(crate) worklist: Vec<String>,
+
+    /// Contents of every `Pointee` reachable from the rest of the checkpoint.
+    /// `Pointee`s are referred to by their index in this vector, so that
+    /// pointers which share a `Pointee` still share it after resuming.
+    pub(crate) pointees: Vec<TypeEntry>,
+
+    /// Taint state of each function we have a taint map for
+    pub(crate) functions: Vec<FunctionEntry>,
+
+    /// Summary of each function we have a summary for
+    pub(crate) summaries: Vec<SummaryEntry>,
+
+    /// Current type of each named struct we have a type for
+    pub(crate) named_struct_types: Vec<(String, TypeEntry)>,
+
+    /// Names of the named structs which are considered entirely tainted
+    pub(crate) tainted_named_structs: Vec<String>,
+
+    /// Functions which use each named struct
+    pub(crate) named_struct_users: Vec<(String, Vec<String>)>,
+
+    /// Deep pointee for each LLVM type (see `Config::max_pointer_depth`)
+    pub(crate) deep_pointees: Vec<(String, PointeeEntry)>,
+
+    /// Current type of each global we have a type for
+    pub(crate) global_types: Vec<(NameEntry, TypeEntry)>,
+
+    /// Functions which use each global
+    pub(crate) global_users: Vec<(NameEntry, Vec<String>)>,
+
+    /// Whether coroutine frames are tainted
+    pub(crate) coro_frames_tainted: bool,
+
+    /// Functions which use `llvm.coro.*` intrinsics
+    pub(crate) coro_users: Vec<String>,
+
+    /// Whether the value passed to any `longjmp` is tainted
+    pub(crate) longjmp_values_tainted: bool,
+
+    /// Functions which call `setjmp`
+    pub(crate) setjmp_users: Vec<String>,
+
+    /// Clones of functions (see `FunctionClones`)
+    pub(crate) clones: Vec<CloneEntry>,
+
+    /// Callers of each function or clone which aren't in the call graph
+    pub(crate) clone_callers: Vec<(String, Vec<String>)>,
+
+    /// Number of steps recorded so far in the propagation trace
+    pub(crate) trace_steps: usize,
+}
+
+impl Checkpoint {
+    /// Write the `Checkpoint` as JSON
+    pub fn write_json(&self, writer: impl Write) -> Result<(), String> {
+        serde_json::to_writer(writer, self).map_err(|e| format!("Failed to write checkpoint: {}", e))
+    }
+
+    /// Read a `Checkpoint` previously written with `write_json()`
+    pub fn read_json(reader: impl Read) -> Result<Self, String> {
+        serde_json::from_reader(reader).map_err(|e| format!("Failed to read checkpoint: {}", e))
+    }
+}
+
+/// Serializable form of a `TaintedType`, where `Pointee`s are replaced by
+/// `PointeeEntry`s
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum TypeEntry {
+    UntaintedValue,
+    TaintedValue,
+    UntaintedPointer(PointeeEntry),
+    TaintedPointer(PointeeEntry),
+    ArrayOrVector(PointeeEntry),
+    Struct(Vec<PointeeEntry>),
+    NamedStruct(String),
+    /// A function pointer with no known targets
+    UntaintedFnPtr,
+    TaintedFnPtr,
+    Token,
+    /// A function pointer to any of the given functions
+    UntaintedFnPtrTo(Vec<String>),
+    TaintedFnPtrTo(Vec<String>),
+}
+
+/// Serializable form of a `Pointee`.
+///
+/// Clones of a `Pointee` share its contents, but each may carry its own named
+/// struct, global, and deep tags; so the contents are stored once in
+/// `Checkpoint::pointees`, while the tags are stored with each reference.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct PointeeEntry {
+    pub(crate) index: usize,
+    pub(crate) named_struct: Option<String>,
+    pub(crate) global: Option<NameEntry>,
+    pub(crate) deep: bool,
+}
+
+/// Serializable form of an llvm-ir `Name`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum NameEntry {
+    Name(String),
+    Number(usize),
+}
+
+impl From<&Name> for NameEntry {
+    fn from(name: &Name) -> Self {
+        match name {
+            Name::Name(s) => NameEntry::Name((**s).clone()),
+            Name::Number(n) => NameEntry::Number(*n),
+        }
+    }
+}
+
+impl From<&NameEntry> for Name {
+    fn from(name: &NameEntry) -> Self {
+        match name {
+            NameEntry::Name(s) => Name::from(s.as_str()),
+            NameEntry::Number(n) => Name::Number(*n),
+        }
+    }
+}
+
+/// Serializable form of a `FunctionTaintState`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct FunctionEntry {
+    pub(crate) name: String,
+    pub(crate) vars: Vec<(NameEntry, TypeEntry)>,
+    pub(crate) tainted_terminators: Vec<NameEntry>,
+    pub(crate) tainted_sinks: Vec<(NameEntry, usize)>,
+    #[serde(default)]
+    pub(crate) tainted_frees: Vec<(NameEntry, usize)>,
+    #[serde(default)]
+    pub(crate) tainted_format_strings: Vec<(NameEntry, usize)>,
+    #[serde(default)]
+    pub(crate) unhandled_constructs: Vec<(NameEntry, Option<usize>, String)>,
+    #[serde(default)]
+    pub(crate) assumptions: Vec<(NameEntry, Option<usize>, String)>,
+    #[serde(default)]
+    pub(crate) tainted_bits: Vec<(NameEntry, u64)>,
+}
+
+/// Serializable form of a clone of a function (see `FunctionClones`)
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct CloneEntry {
+    pub(crate) name: String,
+    pub(crate) function: String,
+    pub(crate) call_string: Option<Vec<(String, NameEntry, usize)>>,
+    pub(crate) arg_taint: Option<Vec<bool>>,
+}
+
+/// Serializable form of a `FunctionSummary`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct SummaryEntry {
+    pub(crate) name: String,
+    pub(crate) params: Vec<TypeEntry>,
+    pub(crate) ret: Option<TypeEntry>,
+    pub(crate) varargs_tainted: bool,
+    #[serde(default)]
+    pub(crate) sret_param: Option<usize>,
+}
+
+/// Converts `TaintedType`s to `TypeEntry`s, assigning each distinct `Pointee`
+/// an index the first time it is encountered
+#[derive(Default)]
+pub(crate) struct Encoder {
+    /// Map from `Pointee::as_ptr()` to that `Pointee`'s index
+    indices: HashMap<*const RwLock<TaintedType>, usize>,
+    /// Contents of the `Pointee`s seen so far, by index. An entry is `None`
+    /// only while we're in the middle of encoding that `Pointee`'s contents.
+    pointees: Vec<Option<TypeEntry>>,
+}
+
+impl Encoder {
+    pub(crate) fn encode(&mut self, ty: &TaintedType) -> TypeEntry {
+        match ty {
+            TaintedType::UntaintedValue => TypeEntry::UntaintedValue,
+            TaintedType::TaintedValue => TypeEntry::TaintedValue,
+            TaintedType::UntaintedPointer(pointee) => TypeEntry::UntaintedPointer(self.encode_pointee(pointee)),
+            TaintedType::TaintedPointer(pointee) => TypeEntry::TaintedPointer(self.encode_pointee(pointee)),
+            TaintedType::ArrayOrVector(pointee) => TypeEntry::ArrayOrVector(self.encode_pointee(pointee)),
+            TaintedType::Struct(elements) => TypeEntry::Struct(elements.iter().map(|e| self.encode_pointee(e)).collect()),
+            TaintedType::NamedStruct(name) => TypeEntry::NamedStruct(name.clone()),
+            TaintedType::UntaintedFnPtr(targets) if targets.is_empty() => TypeEntry::UntaintedFnPtr,
+            TaintedType::TaintedFnPtr(targets) if targets.is_empty() => TypeEntry::TaintedFnPtr,
+            TaintedType::UntaintedFnPtr(targets) => TypeEntry::UntaintedFnPtrTo(targets.iter().cloned().collect()),
+            TaintedType::TaintedFnPtr(targets) => TypeEntry::TaintedFnPtrTo(targets.iter().cloned().collect()),
+            TaintedType::Token => TypeEntry::Token,
+        }
+    }
+
+    pub(crate) fn encode_pointee(&mut self, pointee: &Pointee) -> PointeeEntry {
+        let index = match self.indices.get(&pointee.as_ptr()) {
+            Some(&index) => index,
+            None => {
+                // reserve the index before encoding the contents, in case the
+                // contents (transitively) point back to this `Pointee`
+                let index = self.pointees.len();
+                self.indices.insert(pointee.as_ptr(), index);
+                self.pointees.push(None);
+                let ty = self.encode(&pointee.ty());
+                self.pointees[index] = Some(ty);
+                index
+            },
+        };
+        PointeeEntry {
+            index,
+            named_struct: pointee.get_struct_name().clone(),
+            global: pointee.get_global_name().as_ref().map(NameEntry::from),
+            deep: pointee.is_deep(),
+        }
+    }
+
+    /// Iterate over (`Pointee::id()`, index) pairs for all the `Pointee`s
+    /// encoded so far
+    pub(crate) fn pointee_ids(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.indices.iter().map(|(ptr, index)| (*ptr as usize, *index))
+    }
+
+    /// Get the contents of all the `Pointee`s encoded so far, by index
+    pub(crate) fn into_pointees(self) -> Vec<TypeEntry> {
+        self.pointees
+            .into_iter()
+            .map(|entry| entry.expect("all pointees should be fully encoded by now"))
+            .collect()
+    }
+}
+
+/// Converts `TypeEntry`s back to `TaintedType`s, recreating the sharing of
+/// `Pointee`s that the `Encoder` recorded
+pub(crate) struct Decoder {
+    pointees: Vec<Pointee>,
+}
+
+impl Decoder {
+    pub(crate) fn new(contents: &[TypeEntry]) -> Result<Self, String> {
+        // first create all the `Pointee`s, so that their contents can refer to
+        // any of them (even cyclically), then fill in their contents
+        let decoder = Self {
+            pointees: contents.iter().map(|_| Pointee::new(TaintedType::UntaintedValue)).collect(),
+        };
+        for (pointee, ty) in decoder.pointees.iter().zip(contents) {
+            pointee.set_ty(decoder.decode(ty)?);
+        }
+        Ok(decoder)
+    }
+
+    pub(crate) fn decode(&self, entry: &TypeEntry) -> Result<TaintedType, String> {
+        Ok(match entry {
+            TypeEntry::UntaintedValue => TaintedType::UntaintedValue,
+            TypeEntry::TaintedValue => TaintedType::TaintedValue,
+            TypeEntry::UntaintedPointer(pointee) => TaintedType::UntaintedPointer(self.pointee(pointee)?),
+            TypeEntry::TaintedPointer(pointee) => TaintedType::TaintedPointer(self.pointee(pointee)?),
+            TypeEntry::ArrayOrVector(pointee) => TaintedType::ArrayOrVector(self.pointee(pointee)?),
+            TypeEntry::Struct(elements) => TaintedType::Struct(elements.iter().map(|e| self.pointee(e)).collect::<Result<_, _>>()?),
+            TypeEntry::NamedStruct(name) => TaintedType::NamedStruct(name.clone()),
+            TypeEntry::UntaintedFnPtr => TaintedType::UntaintedFnPtr(FnTargets::new()),
+            TypeEntry::TaintedFnPtr => TaintedType::TaintedFnPtr(FnTargets::new()),
+            TypeEntry::UntaintedFnPtrTo(targets) => TaintedType::UntaintedFnPtr(targets.iter().cloned().collect()),
+            TypeEntry::TaintedFnPtrTo(targets) => TaintedType::TaintedFnPtr(targets.iter().cloned().collect()),
+            TypeEntry::Token => TaintedType::Token,
+        })
+    }
+
+    /// Get the `Pointee::id()` of the (untagged) `Pointee` decoded for the
+    /// given index, if there is one
+    pub(crate) fn pointee_id(&self, index: usize) -> Option<usize> {
+        self.pointees.get(index).map(Pointee::id)
+    }
+
+    pub(crate) fn pointee(&self, entry: &PointeeEntry) -> Result<Pointee, String> {
+        let mut pointee = self.pointees
+            .get(entry.index)
+            .cloned()
+            .ok_or_else(|| format!("Checkpoint refers to pointee {}, but only has {} pointees", entry.index, self.pointees.len()))?;
+        if let Some(struct_name) = &entry.named_struct {
+            pointee.set_struct_name(struct_name.clone())?;
+        }
+        if let Some(global_name) = &entry.global {
+            pointee.set_global_name(Name::from(global_name))?;
+        }
+        if entry.deep {
+            pointee.set_deep();
+        }
+        Ok(pointee)
+    }
+}
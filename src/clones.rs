@@ -0,0 +1,211 @@
+use llvm_ir::Name;
+use llvm_ir_analysis::CallGraph;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Keeps track of the clones of functions which we analyze separately, for
+/// `Config::recursion_call_string_depth` and `Config::polymorphic_summaries`.
+///
+/// A call may go not to the callee's ordinary taint state and summary, but to
+/// a clone of the callee specific to the call's context. Clones have their own
+/// names (of the form `callee#n`), and are otherwise treated just like
+/// functions: they have their own taint maps and summaries, and are put on the
+/// worklist by name.
+///
+/// For `Config::recursion_call_string_depth`, a call from a function to
+/// another function in the same recursive cycle (including a function calling
+/// itself) goes to a clone specific to the call string: the sequence of such
+/// recursive call sites leading to it. Once a call string would be longer than
+/// the configured depth, the call goes instead to a single merged clone of the
+/// callee, shared by all such calls. Recursive calls from a merged clone also
+/// go to merged clones.
+///
+/// For `Config::polymorphic_summaries`, every call to a function defined in
+/// the analyzed module(s) goes to a clone specific to which of the arguments
+/// are tainted at the call site.
+///
+/// Either way there are only finitely many clones, and the analysis still
+/// terminates.
+pub(crate) struct FunctionClones<'m> {
+    /// Maximum length of a call string; 0 disables cloning by call string
+    max_depth: usize,
+
+    /// Map from the name of each clone to its context
+    clones: HashMap<&'m str, CloneContext<'m>>,
+
+    /// Map from each clone's context back to its name
+    names: HashMap<CloneContext<'m>, &'m str>,
+
+    /// Map from a function or clone name to the names of clones which call it,
+    /// the names of functions which call it if it's a clone, and any other
+    /// callers recorded with `add_caller()`. These callers aren't in the call
+    /// graph, but need to be put on the worklist when its summary changes.
+    callers: HashMap<&'m str, HashSet<&'m str>>,
+
+    /// Cache of the functions reachable in the call graph from each function
+    reachable: HashMap<&'m str, HashSet<&'m str>>,
+}
+
+/// The context of a clone: which function it's a clone of, and for which call
+/// string and arguments
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct CloneContext<'m> {
+    /// Name of the function this is a clone of
+    pub(crate) function: &'m str,
+    /// The call string, or `None` for the merged clone. This is empty for
+    /// clones which are only distinguished by `arg_taint`.
+    pub(crate) call_string: Option<Vec<CallSite<'m>>>,
+    /// For `Config::polymorphic_summaries`, which of the arguments are
+    /// tainted; otherwise `None`
+    pub(crate) arg_taint: Option<Vec<bool>>,
+}
+
+/// A call site in a call string
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct CallSite<'m> {
+    /// Name of the function containing the call (never a clone name)
+    pub(crate) function: &'m str,
+    /// Name of the basic block containing the call
+    pub(crate) block: Name,
+    /// Index of the call within the block
+    pub(crate) index: usize,
+}
+
+impl<'m> FunctionClones<'m> {
+    pub(crate) fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            clones: HashMap::new(),
+            names: HashMap::new(),
+            callers: HashMap::new(),
+            reachable: HashMap::new(),
+        }
+    }
+
+    /// Construct a `FunctionClones` directly from its contents, e.g. when
+    /// restoring a checkpoint
+    pub(crate) fn from_parts(
+        max_depth: usize,
+        clones: HashMap<&'m str, CloneContext<'m>>,
+        callers: HashMap<&'m str, HashSet<&'m str>>,
+    ) -> Self {
+        Self {
+            max_depth,
+            names: clones.iter().map(|(name, context)| (context.clone(), *name)).collect(),
+            clones,
+            callers,
+            reachable: HashMap::new(),
+        }
+    }
+
+    /// Iterate over (clone name, context) pairs for all the clones
+    pub(crate) fn all_clones(&self) -> impl Iterator<Item = (&'m str, &CloneContext<'m>)> {
+        self.clones.iter().map(|(name, context)| (*name, context))
+    }
+
+    /// Iterate over (function or clone name, names of its extra callers) pairs;
+    /// see `callers_of()`
+    pub(crate) fn all_callers(&self) -> impl Iterator<Item = (&'m str, &HashSet<&'m str>)> {
+        self.callers.iter().map(|(name, callers)| (*name, callers))
+    }
+
+    /// Is this the name of a clone?
+    pub(crate) fn is_clone(&self, name: &str) -> bool {
+        self.clones.contains_key(name)
+    }
+
+    /// Get the name of the function which the given name refers to: the name of
+    /// the function a clone is a clone of, or else the name itself
+    pub(crate) fn base_function(&self, name: &'m str) -> &'m str {
+        self.clones.get(name).map(|context| context.function).unwrap_or(name)
+    }
+
+    /// Get the callers of the given function or clone which aren't in the call
+    /// graph: clones which call it, if it's a clone, whatever calls it, and
+    /// any callers recorded with `add_caller()`
+    pub(crate) fn callers_of<'s>(&'s self, name: &str) -> impl Iterator<Item = &'m str> + 's {
+        self.callers.get(name).into_iter().flatten().copied()
+    }
+
+    /// Record a call of `callee` from `caller` which isn't in the call graph,
+    /// e.g. one wrapped in a `llvm.experimental.gc.statepoint`
+    pub(crate) fn add_caller(&mut self, callee: &'m str, caller: &'m str) {
+        self.callers.entry(callee).or_default().insert(caller);
+    }
+
+    /// Get the name of the function or clone which should be analyzed for a
+    /// call to `callee`, at the given call site in `caller` (which may be the
+    /// name of a clone).
+    ///
+    /// `arg_taint` should be `Some` (saying which of the arguments are
+    /// tainted) exactly when the call should go to a clone specific to its
+    /// arguments, for `Config::polymorphic_summaries`.
+    pub(crate) fn resolve_callee(
+        &mut self,
+        call_graph: &CallGraph<'m>,
+        caller: &'m str,
+        block: &Name,
+        index: usize,
+        callee: &'m str,
+        arg_taint: Option<Vec<bool>>,
+    ) -> &'m str {
+        let caller_fn = self.base_function(caller);
+        let recursive = self.max_depth > 0 && self.is_recursive_call(call_graph, caller_fn, callee);
+        let resolved = if recursive {
+            let call_string = match self.clones.get(caller) {
+                Some(CloneContext { call_string: None, .. }) => None,
+                caller_context => {
+                    let mut call_string = caller_context.and_then(|context| context.call_string.clone()).unwrap_or_default();
+                    call_string.push(CallSite { function: caller_fn, block: block.clone(), index });
+                    if call_string.len() > self.max_depth {
+                        None
+                    } else {
+                        Some(call_string)
+                    }
+                },
+            };
+            self.get_or_create_clone(CloneContext { function: callee, call_string, arg_taint })
+        } else if arg_taint.is_some() {
+            self.get_or_create_clone(CloneContext { function: callee, call_string: Some(Vec::new()), arg_taint })
+        } else {
+            callee
+        };
+        if self.is_clone(caller) || self.is_clone(resolved) {
+            self.callers.entry(resolved).or_default().insert(caller);
+        }
+        resolved
+    }
+
+    /// Get the name of the clone with the given context, creating it if it
+    /// doesn't exist yet
+    fn get_or_create_clone(&mut self, context: CloneContext<'m>) -> &'m str {
+        if let Some(name) = self.names.get(&context) {
+            return name;
+        }
+        // Clone names need to live as long as the `Module`s, like function
+        // names. There are only finitely many clones, so we just leak them.
+        let name: &'m str = Box::leak(format!("{}#{}", context.function, self.clones.len() + 1).into_boxed_str());
+        self.clones.insert(name, context.clone());
+        self.names.insert(context, name);
+        name
+    }
+
+    /// Are `caller` and `callee` in the same recursive cycle in the call
+    /// graph, i.e., can `callee` (transitively) call `caller`?
+    fn is_recursive_call(&mut self, call_graph: &CallGraph<'m>, caller: &'m str, callee: &'m str) -> bool {
+        self.reachable
+            .entry(callee)
+            .or_insert_with(|| {
+                let mut reachable = HashSet::new();
+                let mut queue: VecDeque<&'m str> = std::iter::once(callee).collect();
+                while let Some(f) = queue.pop_front() {
+                    for g in call_graph.callees(f) {
+                        if reachable.insert(g) {
+                            queue.push_back(g);
+                        }
+                    }
+                }
+                reachable
+            })
+            .contains(caller)
+    }
+}
@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use crate::demangle;
+use llvm_ir::{FPPredicate, IntPredicate, Module};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::ops::Range;
+use std::sync::Mutex;
 
 #[non_exhaustive]
 pub struct Config {
@@ -13,28 +18,1022 @@ pub struct Config {
     /// Default is `true`.
     pub dereferencing_tainted_ptr_gives_tainted: bool,
 
+    /// If `true`, a pointer computed by a `getelementptr` with a tainted index
+    /// is itself tainted, as for an attacker-controlled offset. Values loaded
+    /// through it are then tainted too if
+    /// `dereferencing_tainted_ptr_gives_tainted` is set. Indices which are
+    /// sanitized where the `getelementptr` is (see `sanitize_bounds_checks`)
+    /// don't count.
+    ///
+    /// If `false`, the taint of the pointer ignores the indices: only the
+    /// taint of the base pointer matters.
+    ///
+    /// Default is `false`.
+    pub taint_pointers_with_tainted_indices: bool,
+
+    /// How taint propagates through each class of instructions (see
+    /// `InstructionClass`), for threat models which need different rules
+    /// than the defaults: for instance, a pure data-flow analysis may not
+    /// want comparisons to taint their results, while a side-channel analysis
+    /// may want tainted divisors to taint everything they touch.
+    ///
+    /// Classes with no entry use `PropagationPolicy::Propagate`, except
+    /// `InstructionClass::GepIndex`, which uses `PropagationPolicy::Propagate`
+    /// if `taint_pointers_with_tainted_indices` is set and
+    /// `PropagationPolicy::Drop` otherwise.
+    ///
+    /// Default is empty.
+    pub propagation_policies: HashMap<InstructionClass, PropagationPolicy>,
+
+    /// If `true`, track which bits of integer values (of up to 64 bits) may be
+    /// tainted, rather than tainting whole values, through the instructions
+    /// which move bits around predictably: `and`, `or`, `xor`, shifts,
+    /// `trunc`, `zext`, `sext`, and `add`, `sub`, and `mul` (whose carries only
+    /// reach upward). For instance, `(x >> 8) & 0xff` has only bits 0-7
+    /// tainted, and `(x & 0xf0) >> 8` isn't tainted at all. This suits
+    /// analyses of parsers and bit-packed protocols. Values whose bits are
+    /// all untainted are untainted, and `TaintResult::get_tainted_bits()`
+    /// gives the tainted bits of a value.
+    ///
+    /// Instructions whose class has a policy other than
+    /// `PropagationPolicy::Propagate` (see `propagation_policies`) follow
+    /// that policy instead.
+    ///
+    /// If `false`, an integer value is either tainted or not, as a whole.
+    ///
+    /// Default is `false`.
+    pub bit_level_taint: bool,
+
+    /// If this is `Some(max)`, the result of an `and` of a tainted integer with
+    /// a constant of at most `max` is untainted: the result lies in
+    /// `0..=max`, so the taint can only influence a few bits of it, as when
+    /// extracting flags (e.g., `and %x, 15` if `max` is at least 15). The
+    /// right `max` depends on the use case: for instance, 1 only sanitizes
+    /// single-bit flags, while 255 sanitizes any extracted byte.
+    ///
+    /// This applies only while `InstructionClass::Arithmetic` has the policy
+    /// `PropagationPolicy::Propagate` (see `propagation_policies`), and with
+    /// `bit_level_taint` it clears the masked bits too.
+    ///
+    /// If `None`, masking doesn't affect taint (other than with
+    /// `bit_level_taint`).
+    ///
+    /// Default is `None`.
+    pub sanitizing_mask_max: Option<u64>,
+
+    /// If this is `Some(max)`, the result of a `trunc` of a tainted integer (or
+    /// vector of integers) to at most `max` bits is untainted: for instance,
+    /// with `Some(8)`, truncations to `i1` and `i8`. Many consumers consider
+    /// such narrow values derived from tainted data non-exploitable.
+    ///
+    /// This applies only while `InstructionClass::Cast` has the policy
+    /// `PropagationPolicy::Propagate` (see `propagation_policies`), and with
+    /// `bit_level_taint` it clears the remaining bits too.
+    ///
+    /// If `None`, truncation propagates taint like any other cast.
+    ///
+    /// Default is `None`.
+    pub sanitizing_trunc_max_bits: Option<u32>,
+
+    /// If this is `Some`, comparisons (`icmp` and `fcmp`) of tainted values
+    /// only give tainted results if they use one of the given predicates: for
+    /// instance, only the equality tests `ComparisonPredicate::Int(IntPredicate::EQ)`
+    /// and `ComparisonPredicate::Int(IntPredicate::NE)`, but not orderings.
+    /// Comparisons with other predicates give untainted results, which then
+    /// don't taint the branches they control.
+    ///
+    /// To treat all comparison results as untainted, as for an analysis of
+    /// data flow only, give `InstructionClass::Comparison` the policy
+    /// `PropagationPolicy::Drop` (see `propagation_policies`). This field
+    /// applies only while that policy is `PropagationPolicy::Propagate`.
+    ///
+    /// Default is `None`: comparisons with any predicate taint their results.
+    pub tainting_predicates: Option<Vec<ComparisonPredicate>>,
+
     /// How to handle external functions -- that is, functions not defined in the
     /// `Module`.
     /// This is a map from LLVM function name to the handling that should be used
     /// for that function.
+    ///
+    /// Functions with mangled Rust or C++ names may also be given by their
+    /// demangled names (see `demangle()`), with or without the parameter
+    /// types, e.g. `foo::bar(char const*)` or `foo::bar` for
+    /// `_ZN3foo3barEPKc`. An entry for the mangled name takes precedence.
     pub ext_functions: HashMap<String, ExternalFunctionHandling>,
 
     /// How to handle external functions which _aren't_ present in the
     /// `ext_functions` map above; or function pointers where no valid target for
     /// the function pointer exists in the `Module`.
     pub ext_functions_default: ExternalFunctionHandling,
+
+    /// Overrides of `ext_functions_default` for the external functions called
+    /// from particular modules: for instance, to treat everything unresolved
+    /// in a vendor's binary blob as `PropagateTaintShallow`, but everything
+    /// unresolved in one's own modules as `Panic`. This is a map from module
+    /// name to the default handling for external functions called from that
+    /// module.
+    ///
+    /// Each entry matches the module whose `Module::name` (for modules parsed
+    /// from bitcode, usually the path of the file) is its key, or ends with
+    /// `/` followed by its key; so `vendor_blob.bc` matches
+    /// `libs/vendor_blob.bc`. An external function called from several
+    /// modules gets the override of the first of them (in the order the
+    /// modules were given to the analysis) which has one. Function pointers
+    /// with no valid target get the override of the module making the call.
+    ///
+    /// Entries in `ext_functions` take precedence over these overrides.
+    ///
+    /// Default is empty.
+    pub ext_functions_default_by_module: HashMap<String, ExternalFunctionHandling>,
+
+    /// If `true`, the handling of an external function (from `ext_functions`
+    /// or `ext_functions_default`) is refined using the attributes at its
+    /// call sites. For instance, a function whose result is `noalias` (like
+    /// `malloc`) returns a fresh pointer, which isn't tainted by the
+    /// arguments, so `PropagateTaintShallow` becomes `IgnoreAndReturnUntainted`
+    /// for it. Only the `PropagateTaint` handlings are refined; an attribute
+    /// is used only if it's present at every call site.
+    ///
+    /// `PropagateTaintDeep` itself isn't implemented yet, but a `readnone`
+    /// or `writeonly` function can't read the data its arguments point to,
+    /// so for one of those it's refined to `PropagateTaintShallow` (or
+    /// further, as above). Calls of other functions with that handling still
+    /// panic.
+    ///
+    /// Default is `false`.
+    pub ext_function_attributes: bool,
+
+    /// If `true`, calls of the heap allocators `malloc`, `calloc`, `realloc`,
+    /// `operator new` and `operator new[]`, and Rust's `__rust_alloc`,
+    /// `__rust_alloc_zeroed`, and `__rust_realloc` are modeled directly,
+    /// unless the allocator is defined in the analyzed module(s) or appears
+    /// in `ext_functions`. Each call returns an untainted pointer to fresh,
+    /// untainted memory, whose type is recovered from the cast of the
+    /// returned `i8*` to a pointer to the type actually stored there. All
+    /// casts of the same allocation share its contents, so taint stored
+    /// through one is seen through the others. Memory returned by `realloc`
+    /// carries forward the taint of the memory it was passed.
+    ///
+    /// Likewise, calls of the deallocators `free`, `operator delete` and
+    /// `operator delete[]`, and Rust's `__rust_dealloc` are modeled as having
+    /// no effect on taint.
+    ///
+    /// Default is `false`: allocators and deallocators are handled like any
+    /// other external function.
+    pub model_allocators: bool,
+
+    /// If `true`, calls of deallocators (see `model_allocators`, which must
+    /// also be set) which are passed a tainted pointer are reported by
+    /// `TaintResult::get_tainted_frees()`. An attacker who controls which
+    /// memory is freed may be able to cause a double free or use after free,
+    /// so these are worth triaging separately from other sinks.
+    ///
+    /// Default is `false`.
+    pub report_tainted_frees: bool,
+
+    /// If `true`, calls of the `printf` family (`printf`, `fprintf`,
+    /// `sprintf`, and `snprintf`) and the `scanf` family (`scanf`, `fscanf`,
+    /// and `sscanf`) are modeled directly, unless the function is defined in
+    /// the analyzed module(s) or appears in `ext_functions`:
+    ///   - the `printf` family writes the formatted data to the destination
+    ///     buffer, if any, whose contents become tainted if the format string
+    ///     or any of the extra arguments (or the data they point to) is
+    ///     tainted
+    ///   - the `scanf` family stores the parsed data through the extra
+    ///     arguments, whose pointees become tainted if the input is: for
+    ///     `sscanf`, the input string (or the data it points to); for `scanf`
+    ///     and `fscanf`, see `stream_input_tainted`
+    ///
+    /// In either case, the result is tainted if the data formatted or parsed
+    /// is.
+    ///
+    /// Default is `false`: format functions are handled like any other
+    /// external function.
+    pub model_format_functions: bool,
+
+    /// If `true`, input read from a stream by `scanf` and `fscanf` (see
+    /// `model_format_functions`) is tainted.
+    ///
+    /// Default is `false`.
+    pub stream_input_tainted: bool,
+
+    /// If `true`, calls of format functions (see `model_format_functions`)
+    /// whose format string is tainted (either the pointer itself, or the data
+    /// it points to) are reported by `TaintResult::get_tainted_format_strings()`,
+    /// as potential format-string vulnerabilities.
+    ///
+    /// Default is `false`.
+    pub report_tainted_format_strings: bool,
+
+    /// If `true`, model the `setjmp` and `longjmp` families of functions
+    /// directly, unless they're defined in the analyzed module(s) or
+    /// configured in `ext_functions`. A `setjmp` returns a second time for
+    /// each `longjmp` to its buffer, with the value passed to `longjmp`; we
+    /// don't track which buffer a `longjmp` uses, so the result of every
+    /// `setjmp` is tainted if the value passed to any `longjmp` is.
+    ///
+    /// Taint established before a `longjmp` in globals, or in memory the
+    /// `setjmp`'s caller can reach through pointers, is already visible when
+    /// the `setjmp` returns, since the analysis doesn't order it. The one
+    /// exception is stack slots tracked for `flow_sensitive_memory`, which
+    /// don't see the edge from a `longjmp` back to the `setjmp`; so we don't
+    /// track stack slots flow-sensitively in functions which call `setjmp`
+    /// (or any other function which returns twice).
+    ///
+    /// Default is `false`: these are handled like any other external function.
+    pub model_setjmp_longjmp: bool,
+
+    /// If `true`, model the Linux kernel's primitives for copying data between
+    /// user and kernel memory, for analyzing kernel (module) bitcode, unless
+    /// they're defined in the analyzed module(s) or configured in
+    /// `ext_functions`:
+    ///   - `copy_from_user`, `get_user`, `strncpy_from_user`, and their
+    ///     variants taint the kernel memory they copy into
+    ///   - `memdup_user` and `strndup_user` return pointers to tainted memory
+    ///   - `copy_to_user`, `put_user`, and their variants are sinks: calls
+    ///     which copy out tainted data (or data through a tainted pointer,
+    ///     e.g. a kernel pointer an attacker chose) are reported by
+    ///     `TaintResult::get_tainted_sinks()`
+    ///
+    /// Results other than those of `memdup_user` and `strndup_user` (i.e.,
+    /// error codes and byte counts) are untainted.
+    ///
+    /// Default is `false`.
+    pub model_kernel_user_copies: bool,
+
+    /// If `true`, model the WASI imports which read data from outside the
+    /// WebAssembly sandbox as taint sources, for modules built for WASI
+    /// targets, unless they're defined in the analyzed module(s) or configured
+    /// in `ext_functions`:
+    ///   - `fd_read`, `fd_pread`, and `sock_recv` taint the buffers of the
+    ///     `iovec`s they read into
+    ///   - `fd_readdir` and `path_readlink` taint the buffer they read into
+    ///   - `args_get` and `environ_get` taint the strings they fill in (both
+    ///     the buffer, and the data the array of pointers points to)
+    ///   - `args_sizes_get` and `environ_sizes_get` taint the counts and sizes
+    ///     they store
+    ///
+    /// These are matched by the names wasi-libc gives them: either the
+    /// `__wasi_`-prefixed wrappers, or the raw
+    /// `__imported_wasi_snapshot_preview1_`-prefixed imports. (The raw imports
+    /// take pointers as `i32`s, which we can't follow.) Their results (WASI
+    /// error codes) are untainted, as are the byte counts they store.
+    ///
+    /// Default is `false`.
+    pub model_wasi_imports: bool,
+
+    /// If `true`, model the JNI functions which pass data from Java to native
+    /// code as taint sources, for auditing native (e.g., Android) libraries:
+    ///   - `GetStringUTFChars`, `GetByteArrayElements`, and the other
+    ///     functions giving a pointer to Java data (strings, primitive arrays,
+    ///     and direct buffers) return a pointer to tainted data
+    ///   - `GetStringUTFRegion`, `GetByteArrayRegion`, and the other functions
+    ///     copying Java data into a buffer taint the buffer's contents
+    ///   - `GetArrayLength`, the string length functions, and the functions
+    ///     getting primitive fields or calling methods returning primitives
+    ///     return tainted values
+    ///
+    /// Native code calls these through the `JNIEnv`'s function table, so they
+    /// are recognized as calls of a function pointer loaded from the
+    /// corresponding field of `JNINativeInterface_` (or the Android NDK's
+    /// `JNINativeInterface`), rather than by name. Other calls through the
+    /// table are handled like any other call of a function pointer.
+    ///
+    /// Default is `false`.
+    pub model_jni: bool,
+
+    /// If `true`, model the OpenCL and CUDA builtins GPU kernels use, and the
+    /// NVVM and AMDGPU intrinsics they're lowered to, unless they're defined
+    /// in the analyzed module(s) or configured in `ext_functions`:
+    ///   - the work-item and work-group queries (`get_global_id`,
+    ///     `get_local_size`, ...), barriers (`barrier`, `__syncthreads`, ...),
+    ///     and memory fences have no effect, and return untainted values
+    ///   - the work-group and sub-group collectives (`work_group_broadcast`,
+    ///     `sub_group_reduce_add`, ...), and the warp shuffles and votes,
+    ///     return a tainted value if any of their arguments is tainted
+    ///   - the device atomics (`atomicAdd`, `atomic_add`, `atom_add`,
+    ///     `atomic_fetch_add`, and the other operations) taint the data their
+    ///     pointer points to if any of their other arguments is tainted, and
+    ///     return a tainted value if that data is tainted
+    ///   - the asynchronous copies (`async_work_group_copy`, `memcpy_async`,
+    ///     `__pipeline_memcpy_async`, and `cp.async`) taint the destination
+    ///     contents if the source contents are tainted, like `memcpy`
+    ///
+    /// Together with `gpu_kernels_as_entry_points`, this lets kernels be
+    /// analyzed for GPU side channels: branches on tainted data show up as
+    /// tainted terminators, and with `taint_pointers_with_tainted_indices`,
+    /// shared-memory accesses at tainted indices use tainted pointers.
+    ///
+    /// Default is `false`.
+    pub model_gpu_builtins: bool,
+
+    /// If `true`, honor the taint policy embedded in the analyzed module(s)
+    /// with annotations, as Clang produces for
+    /// `__attribute__((annotate("...")))`. This lets front-end tooling mark
+    /// sources and sinks directly in the bitcode it hands to this crate:
+    ///   - a global annotated `taint.source` has tainted contents
+    ///   - a function annotated `taint.source` returns tainted data, wherever
+    ///     it's called
+    ///   - a local variable annotated `taint.source` (i.e., the `alloca`
+    ///     passed to `llvm.var.annotation`) has tainted contents
+    ///   - calls of a function annotated `taint.sink` which pass it tainted
+    ///     data (either an argument, or the data it points to) are reported by
+    ///     `TaintResult::get_tainted_sinks()`
+    ///
+    /// Annotations of functions and globals are read from the modules'
+    /// `llvm.global.annotations` arrays. (`llvm-ir` doesn't give us the
+    /// modules' named metadata, so annotations are the only embedded policy
+    /// we support.)
+    ///
+    /// Default is `false`.
+    pub honor_annotations: bool,
+
+    /// Intrinsics which have no effect on taint. Calls to these are ignored,
+    /// and any value they return is untainted.
+    ///
+    /// Each entry matches the intrinsic with that name, and also any intrinsic
+    /// whose name extends it with more `.`-separated components. For instance,
+    /// `llvm.lifetime` matches `llvm.lifetime.start.p0i8` and
+    /// `llvm.lifetime.end.p0i8`, but not `llvm.lifetimes`.
+    ///
+    /// Default is `DEFAULT_NOOP_INTRINSICS`; add to it to ignore other
+    /// intrinsics, rather than giving them `ext_functions` handling.
+    pub noop_intrinsics: HashSet<String>,
+
+    /// If `true`, values read from registers with `llvm.read_register` or
+    /// `llvm.read_volatile_register` (e.g., the stack pointer) are tainted.
+    /// If `false`, they are untainted.
+    ///
+    /// Default is `false`.
+    pub register_reads_tainted: bool,
+
+    /// If `true`, `llvm.write_register` is treated as a sink: calls which
+    /// write tainted data to a register are reported by
+    /// `TaintResult::get_tainted_sinks()`.
+    /// If `false`, writes to registers are ignored.
+    ///
+    /// Default is `false`.
+    pub register_writes_are_sinks: bool,
+
+    /// Ranges of physical addresses of tainted device memory, e.g. the
+    /// memory-mapped registers of peripherals an attacker can drive, for
+    /// embedded firmware. Loads from a constant address in one of these
+    /// ranges give tainted data. The address may be cast from the integer with
+    /// `inttoptr`, either as a constant expression (as for the usual
+    /// `*(volatile uint32_t *)0x40021000` register macros) or as an
+    /// instruction on a constant; casts and `getelementptr`s of such an
+    /// address are seen through, taking a `getelementptr`'s address to be its
+    /// base address.
+    ///
+    /// Default is empty.
+    pub tainted_address_ranges: Vec<Range<u64>>,
+
+    /// Linker sections holding tainted device memory. Globals placed in one
+    /// of these sections (e.g., with `__attribute__((section(".periph")))`, as
+    /// linker scripts often map peripherals) have tainted contents, like
+    /// globals annotated as sources (see `honor_annotations`).
+    ///
+    /// Default is empty.
+    pub tainted_sections: HashSet<String>,
+
+    /// LLVM address spaces whose memory is tainted, e.g. memory shared with an
+    /// untrusted host, as is the natural way to express trust boundaries on
+    /// GPUs and some embedded targets. Loads through a pointer in one of these
+    /// address spaces (`addrspace(N)`) give tainted data.
+    ///
+    /// Default is empty.
+    pub tainted_address_spaces: HashSet<u32>,
+
+    /// LLVM address spaces whose memory is a sink, e.g. memory an untrusted
+    /// party can read. Stores of tainted data through a pointer in one of
+    /// these address spaces are reported by `TaintResult::get_tainted_sinks()`.
+    ///
+    /// Default is empty.
+    pub sink_address_spaces: HashSet<u32>,
+
+    /// If `true`, a conditional branch on a comparison of a variable against
+    /// an untainted bound (e.g., `x < 10` or `x <= len`, in either operand
+    /// order) sanitizes the variable: in the blocks dominated by the successor
+    /// the branch takes when the variable is bounded above (or equal to the
+    /// bound), uses of the variable are treated as untainted. So are values
+    /// loaded in those blocks from the address the variable was loaded from,
+    /// if any, as is common in unoptimized code.
+    ///
+    /// The check only counts if that successor has no other predecessors. The
+    /// branch itself is not considered tainted, so stores and phis in the
+    /// dominated blocks aren't tainted by implicit flow from the check.
+    ///
+    /// Default is `false`.
+    pub sanitize_bounds_checks: bool,
+
+    /// Names of validator functions. A conditional branch on the result of a
+    /// call to a validator (either an `i1` result directly, or compared
+    /// against zero) sanitizes the call's arguments, just like the bounds
+    /// checks described for `sanitize_bounds_checks`. The validator's result
+    /// is taken to be nonzero when the arguments are valid.
+    ///
+    /// Like `ext_functions`, validators may be given by their demangled names.
+    ///
+    /// This applies whether or not `sanitize_bounds_checks` is set. Default is
+    /// empty.
+    pub validators: HashSet<String>,
+
+    /// If `true`, assume the analyzed program is multithreaded, so that data
+    /// written to memory by one function may be read by any other function,
+    /// not just by functions related to it in the call graph (e.g., by a
+    /// function running concurrently in another thread).
+    ///
+    /// Concretely, whenever an instruction changes the taint of any memory
+    /// (e.g., by storing tainted data through a pointer), every function
+    /// analyzed so far is processed again, so that functions which read that
+    /// memory (through a global, or through a pointer which escaped to them)
+    /// see the new taint. This is conservative, and can make the analysis much
+    /// slower.
+    ///
+    /// If `false`, only the users of a global are processed again when its
+    /// contents change, and other memory is assumed to be communicated only
+    /// along the call graph.
+    ///
+    /// Default is `false`.
+    pub shared_memory: bool,
+
+    /// Functions which start a thread running a callback, such as
+    /// `pthread_create`, mapped to the index of the callback argument and the
+    /// index of the argument which is passed on to the callback. A call to
+    /// one of these whose callback is a function in the analyzed module(s) is
+    /// also treated as a call of the callback with that argument, so that
+    /// taint passed to the new thread reaches its parameter. (What the
+    /// callback returns is dropped.) The call to the spawn function itself is
+    /// still handled as usual, e.g. according to `ext_functions`.
+    ///
+    /// Like `ext_functions`, these may be given by their demangled names, so
+    /// wrappers of the thread APIs (e.g., a runtime's thread-spawn shim) can
+    /// be added too.
+    ///
+    /// Default is `DEFAULT_THREAD_SPAWN_FUNCTIONS`.
+    pub thread_spawn_functions: HashMap<String, (usize, usize)>,
+
+    /// If `true`, calls through a function pointer which C++ code loaded from
+    /// an object's vtable (the `this->vptr[k](this, ...)` pattern Clang emits
+    /// for virtual calls) only go to the functions in slot `k` of the vtables
+    /// of the object's class and the classes derived from it (according to
+    /// their RTTI), rather than to every function of the appropriate type.
+    /// If no such function has the appropriate type, or the call doesn't
+    /// match the pattern, all functions of the appropriate type are still
+    /// considered, as for any other call of a function pointer.
+    ///
+    /// This relies on the module(s) containing the vtables and RTTI of all
+    /// the classes derived from the object's class: an override in a class
+    /// whose vtable is only in some other module is missed.
+    ///
+    /// Default is `false`.
+    pub devirtualize_virtual_calls: bool,
+
+    /// If `true`, calls through a function pointer loaded from a field of a
+    /// named struct only go to the functions stored into that field of that
+    /// struct type anywhere in the module(s), rather than to every function of
+    /// the appropriate type. This resolves the thunks behind `std::function`
+    /// (whose `_M_invoker` field is set by its constructor) and closures which
+    /// capture a function pointer next to their environment pointer; the
+    /// environment pointer is passed to the resolved thunk as usual, so the
+    /// taint of what it points to reaches the closure body.
+    ///
+    /// This only sees stores of constant functions through a `getelementptr`
+    /// of the field, and initializers of globals of the struct's type, so it
+    /// may miss targets written to the field some other way (e.g., by a
+    /// `memcpy` from a different type). If any store writes a non-constant
+    /// function pointer to the field, or none of the functions stored has the
+    /// appropriate type, all functions of the appropriate type are considered.
+    ///
+    /// Default is `false`.
+    pub resolve_closure_thunks: bool,
+
+    /// If `true`, calls through a function pointer whose targets we know (the
+    /// functions whose addresses were taken to produce it; see
+    /// `TaintedType::UntaintedFnPtr`) only go to those functions, rather than
+    /// to every function of the appropriate type. In particular, function
+    /// pointers loaded from global tables of function pointers (e.g., `static
+    /// handler_t handlers[] = {...}`) are known to point to the functions in
+    /// the table's initializer, along with any stored to the table later.
+    ///
+    /// A function pointer which may also come from outside the analyzed code
+    /// (e.g., returned by an external function) may point to other functions
+    /// too, which this misses. If none of the known targets has the
+    /// appropriate type, all functions of the appropriate type are
+    /// considered.
+    ///
+    /// Default is `false`.
+    pub resolve_fn_ptr_targets: bool,
+
+    /// If `true`, track the contents of stack slots flow-sensitively: a load
+    /// from a stack slot only sees the taint of the stores which can reach it,
+    /// rather than of every store to the slot anywhere in the function. In
+    /// particular, stores to a stack slot are strong updates: overwriting a
+    /// tainted value with an untainted one makes later loads untainted.
+    ///
+    /// A stack slot here is an `alloca` of a single integer, floating-point,
+    /// or vector value whose address doesn't escape: it's only used as the
+    /// address of loads and stores, and as the argument to the
+    /// `llvm.lifetime.*` intrinsics. Each `llvm.lifetime.start` of a slot
+    /// begins a new lifetime, so stores before it don't reach loads after it.
+    /// Other memory is handled flow-insensitively as usual.
+    ///
+    /// Default is `false`.
+    pub flow_sensitive_memory: bool,
+
+    /// If `true`, promote non-escaping `alloca`s of scalars and pointers to
+    /// SSA values, as LLVM's `mem2reg` pass would, for precision on
+    /// unoptimized (`-O0`) bitcode, which keeps every local variable in an
+    /// `alloca` and reloads it at each use. This enables the flow-sensitive
+    /// tracking of stack slots described for `flow_sensitive_memory`, and
+    /// extends it to slots holding (data) pointers: a load from such a slot
+    /// which exactly one store can reach gives the stored pointer itself,
+    /// pointing to the same data, rather than a pointer to the join of
+    /// everything any pointer stored to the slot points to. A load which
+    /// several stores (or none) can reach is handled flow-insensitively as
+    /// usual, since joining the pointers would lose track of what they point
+    /// to.
+    ///
+    /// Default is `false`.
+    pub promote_allocas: bool,
+
+    /// If this is `Some(n)`, data nested more than `n` levels of pointers,
+    /// arrays, vectors, or structs deep within a value is tracked only by its
+    /// LLVM type: all such data of the same type, anywhere in the program,
+    /// shares a single summary taint. (Data up to `n` levels deep is tracked
+    /// precisely, as usual.) For instance, with `Some(1)`, the `i32*` which an
+    /// `i32**` points to is tracked precisely, but the `i32` the `i32*` points
+    /// to is tracked together with all other `i32`s at depth 2 or more.
+    ///
+    /// This bounds the memory used to represent pathologically nested types,
+    /// while remaining sound: the analysis just treats more data as possibly
+    /// aliasing. Whenever a summary taint changes, every function analyzed so
+    /// far is processed again.
+    ///
+    /// Default is `None`, i.e., no limit.
+    pub max_pointer_depth: Option<usize>,
+
+    /// If `true`, named structs which LLVM renamed when linking modules from
+    /// different contexts (e.g., `%struct.foo.123` for a second
+    /// `%struct.foo`) share a single definition with the struct they were
+    /// renamed from, so that taint flows across module boundaries through
+    /// the "same" struct. If `false`, they're unrelated structs, and data
+    /// passed between modules as one and the other causes a type mismatch.
+    ///
+    /// Two named structs are unified if their names are the same apart from
+    /// trailing `.N` suffixes, and their definitions are structurally the
+    /// same, taking named structs nested in them to be the same under the
+    /// same rule. An opaque struct is unified with the one defined struct of
+    /// the same base name, if there's exactly one. Each set of unified
+    /// structs is known by the name of the first of them (the one without a
+    /// suffix, if it's among them), so `TaintResult`s report their types
+    /// under that name.
+    ///
+    /// Default is `false`.
+    pub unify_renamed_structs: bool,
+
+    /// If `true`, joining two `TaintedType`s of different shapes (e.g., a
+    /// pointer with a `blockaddress` constant typed as a value, or two
+    /// differently-shaped views of the same struct after casts) gives a
+    /// conservative join rather than aborting the analysis with a type
+    /// mismatch error. A value joined with a pointer gives the pointer,
+    /// tainted if the value is; otherwise the join keeps the shape of the
+    /// type we already had, and is tainted if the other type is tainted at
+    /// all, and if both are pointers, so is the data the first points to if
+    /// the data the second points to is.
+    ///
+    /// This loses precision (and the mismatch may point to a bug in the
+    /// analysis, or to IR it doesn't understand), so it's off by default.
+    ///
+    /// Default is `false`.
+    pub lenient_joins: bool,
+
+    /// If this is nonzero, calls between functions in the same recursive cycle
+    /// (including a function calling itself) are analyzed context-sensitively,
+    /// distinguishing call strings of up to this many such recursive calls.
+    /// Each call string gets its own clone of the callee, with its own
+    /// summary, so that, e.g., tainted arguments passed deeper in a recursion
+    /// don't make the outermost call's result tainted. Recursive calls beyond
+    /// this depth all share one merged clone of each function, so the
+    /// analysis still terminates.
+    ///
+    /// Clones appear in the propagation trace under names of the form
+    /// `function#n`. In the final `TaintResult`, each clone's taint map and
+    /// summary are joined into those of the function it's a clone of.
+    ///
+    /// Default is `0`, i.e., a single summary for each function, covering all
+    /// of its calls.
+    pub recursion_call_string_depth: usize,
+
+    /// If `true`, each function's summary is polymorphic in the taint of its
+    /// arguments: rather than joining the arguments of all calls to a
+    /// function, and giving every call the same (joined) result, the
+    /// function's taint transfer is computed separately for each combination
+    /// of tainted and untainted arguments it's called with. So, e.g., once one
+    /// call to a function passes it tainted data, other calls which pass it
+    /// only untainted data still get an untainted result (unless the function
+    /// introduces taint itself). Data reachable through pointer arguments is
+    /// still shared by all calls, as with any other memory.
+    ///
+    /// As with `recursion_call_string_depth`, each such combination is
+    /// analyzed as a clone of the function, named `function#n` in traces; in
+    /// the final `TaintResult`, the clones' taint maps and summaries are
+    /// joined into those of the function itself. This costs up to one pass
+    /// over a function per distinct combination of argument taint.
+    ///
+    /// Default is `false`.
+    pub polymorphic_summaries: bool,
+
+    /// If `true`, compute function summaries bottom-up before propagating
+    /// taint top-down. The analysis starts by processing every function
+    /// reachable (in the call graph) from the start function(s), callees
+    /// before callers, so that when a function is first processed, the
+    /// summaries of the functions it calls already reflect any taint they
+    /// introduce themselves (e.g., from external sources or globals).
+    /// Afterwards, whenever several functions are waiting to be processed
+    /// again, callees are still processed before their callers.
+    ///
+    /// The result is the same as without this option, but for deep call
+    /// graphs it usually takes far fewer passes over functions, since callers
+    /// aren't processed repeatedly as taint works its way up from their
+    /// callees.
+    ///
+    /// Default is `false`, i.e., start only from the start function(s), and
+    /// process waiting functions in arbitrary order.
+    pub bottom_up: bool,
+
+    /// If `true`, analyses of whole modules (e.g., with
+    /// `do_taint_analysis_on_module()`) start only from the entry points: the
+    /// functions given `args` or `nonargs`, and the functions reachable from
+    /// them in the call graph. Functions which can't be reached from the entry
+    /// points, like dead code or unrelated library functions, aren't analyzed
+    /// at all, and have no results. If no entry points are given, all
+    /// functions are analyzed, as usual.
+    ///
+    /// Default is `false`, i.e., analyze every function in the module(s).
+    pub prune_unreachable_functions: bool,
+
+    /// If `true`, analyses of whole modules (e.g., with
+    /// `do_taint_analysis_on_module()`) first make a cheap pass to find the
+    /// _taint-blind_ functions: those which can't observe any source, because
+    /// they aren't entry points given tainted inputs, don't call anything
+    /// which may introduce taint (external functions other than those handled
+    /// as `IgnoreAndReturnUntainted` or `PropagateTaint*`, sources, or inline
+    /// assembly), and aren't connected to any function which does, in the call
+    /// graph or through mutable globals. Taint-blind functions are skipped
+    /// entirely, and have no results, as with `prune_unreachable_functions`.
+    ///
+    /// Everything in a taint-blind function would be untainted anyway, so
+    /// this doesn't change the results of any other function. In large
+    /// codebases, most functions are often taint-blind.
+    ///
+    /// This is ignored if any named struct definitions are initially tainted,
+    /// since any function may use them.
+    ///
+    /// Default is `false`, i.e., analyze every function in the module(s).
+    pub skip_taint_blind_functions: bool,
+
+    /// Modules which are loaded only for context: their types, globals, and
+    /// named struct definitions are used as usual, but the functions they
+    /// define aren't analyzed. Instead, calls of those functions are handled
+    /// like calls of external functions, i.e., according to `ext_functions`,
+    /// or else the override in `ext_functions_default_by_module` for the
+    /// context-only module defining them (or, failing that, for the modules
+    /// calling them). This effectively freezes them at their configured
+    /// handling, which is useful when linking against huge third-party
+    /// bitcode.
+    ///
+    /// Modules are matched by name, as in `ext_functions_default_by_module`.
+    ///
+    /// Default is empty, i.e., analyze the functions of all modules.
+    pub context_only_modules: HashSet<String>,
+
+    /// If `true`, the static constructors and destructors of the module(s)
+    /// (the functions in `llvm.global_ctors` and `llvm.global_dtors`) are
+    /// analyzed as additional entry points, with untainted arguments, so that
+    /// their effects on global taint are included. Static initializers often
+    /// read the environment or configuration files, tainting globals before
+    /// `main()` even runs. They're added to the initial worklist in priority
+    /// order, constructors first.
+    ///
+    /// Default is `false`.
+    pub static_initializers_as_entry_points: bool,
+
+    /// If `true`, the functions the module(s) register as signal handlers,
+    /// with `signal` or `sigaction`, are analyzed as additional entry points,
+    /// with untainted arguments. A handler can run at any time, so it may
+    /// observe tainted global state, and its writes to globals may taint what
+    /// the rest of the program reads; since it's analyzed like any other
+    /// function, its reads and writes of globals take part in the fixpoint.
+    ///
+    /// Handlers passed to `signal` are found directly. For `sigaction`, whose
+    /// handler is passed in a `struct sigaction`, every function whose address
+    /// is stored by a function which calls `sigaction` is taken to be a
+    /// handler.
+    ///
+    /// Default is `false`.
+    pub signal_handlers_as_entry_points: bool,
+
+    /// If `true`, the GPU kernels in the module(s) (the functions with the
+    /// `ptx_kernel`, `amdgpu_kernel` or `spir_kernel` calling conventions)
+    /// are analyzed as additional entry points, with untainted arguments. A
+    /// kernel is launched by the host rather than called from anywhere in the
+    /// device code, so without this, kernels aren't analyzed unless one is
+    /// the function passed to `do_taint_analysis_on_function()`.
+    ///
+    /// GPU shared and local memory (e.g., `addrspace(3)` and `addrspace(5)`)
+    /// are modeled like any other memory; use `tainted_address_spaces` to
+    /// taint what's read from a particular address space.
+    ///
+    /// Default is `false`.
+    pub gpu_kernels_as_entry_points: bool,
+
+    /// If `true`, `do_taint_analysis_on_module()` also analyzes each of the
+    /// given entry points (the functions in `args` or `nonargs`) separately,
+    /// in addition to the usual analysis of all of them together, and keeps
+    /// those results by entry point (see `TaintResult::get_entry_result()`).
+    /// This tells which entry points a tainted value is actually reachable
+    /// from, e.g., which request handlers can taint a shared helper, at the
+    /// cost of one extra analysis per entry point.
+    ///
+    /// Default is `false`.
+    pub per_entry_results: bool,
+
+    /// If `true`, the separate analyses of each entry point for
+    /// `per_entry_results` share a `SummaryStore`, computed once up front:
+    /// calls of leaf functions (as described on `SummaryStore`) are answered
+    /// from their summaries there, rather than analyzing those functions
+    /// again for every entry point. Leaf functions then have no results of
+    /// their own in the results for each entry point. This has no effect
+    /// unless `per_entry_results` is set.
+    ///
+    /// Default is `false`.
+    pub share_leaf_summaries: bool,
+
+    /// If this is `Some(n)`, functions with at least `n` basic blocks (e.g.,
+    /// generated parsers) are processed partly in parallel. The blocks are
+    /// partitioned into levels, so that no block uses a value defined in an
+    /// earlier block of the same level. Within each level, blocks consisting
+    /// only of instructions whose results depend only on their operands
+    /// (arithmetic, casts between non-pointer types, comparisons, and
+    /// selects) are processed in parallel, and the other blocks one at a time
+    /// as usual.
+    ///
+    /// The result is the same as without this option. This has no effect on
+    /// functions processed with `sanitize_bounds_checks` or `validators`,
+    /// which depend on the block being processed.
+    ///
+    /// Default is `None`, i.e., always process blocks one at a time.
+    pub parallel_blocks_threshold: Option<usize>,
+
+    /// If `true`, functions waiting to be processed are always processed in
+    /// the same order (by name, unless `bottom_up` is set), rather than in an
+    /// arbitrary order which may differ from run to run. Everything else the
+    /// analysis does is already in a fixed order, so with this option, the
+    /// same input always produces the same steps (e.g., for
+    /// `propagation_trace`), and the same results, down to which pointers
+    /// share pointees. This is useful for comparing results and reproducing
+    /// bugs.
+    ///
+    /// Default is `false`.
+    pub deterministic: bool,
+
+    /// How to handle instructions and terminators which the analysis has no
+    /// specific handling for (e.g., `freeze`, `landingpad`, `invoke`, or calls
+    /// of inline assembly).
+    ///
+    /// Default is `UnknownInstructionHandling::Panic`.
+    pub unknown_instructions: UnknownInstructionHandling,
+
+    /// How to handle the constructs for which the analysis makes a documented
+    /// unsound assumption (see `TaintResult::get_unsound_assumptions()`):
+    /// `inttoptr`, inline assembly, and calls of external functions (or of
+    /// function pointers with no known targets) whose results are assumed
+    /// untainted by default. Users who need a sound over-approximation can
+    /// choose to make these an error, or to handle them conservatively.
+    ///
+    /// Default is `UnsoundAssumptionHandling::Assume`.
+    pub unsound_assumptions: UnsoundAssumptionHandling,
+
+    /// If this is `Some`, every propagation step made by the analysis is
+    /// recorded to the given writer, as one line of JSON per step. A step is
+    /// recorded whenever processing an instruction or terminator changes some
+    /// taint information, and whenever a function summary changes.
+    ///
+    /// Each line is a JSON object with the following fields:
+    ///   - `step`: sequence number of the step, starting from 0
+    ///   - `kind`: `"instruction"`, `"terminator"`, or `"summary"`
+    ///   - `function`: name of the function being processed (for `"summary"`
+    ///     steps, the function whose summary changed)
+    ///
+    /// Steps of kind `"instruction"` or `"terminator"` additionally have:
+    ///   - `block`: name of the basic block containing the instruction
+    ///   - `instruction`: the instruction or terminator, as LLVM text
+    ///   - `operands`: array of objects with fields `operand` (as LLVM text),
+    ///     `type` (its `TaintedType`), and `tainted` (boolean)
+    ///   - `result`: name of the variable the instruction defines, or `null`
+    ///   - `type`: the resulting `TaintedType` of `result`, or `null`
+    ///   - `tainted`: whether `result` is tainted (for terminators: whether
+    ///     the terminator is tainted)
+    ///
+    /// Steps of kind `"summary"` additionally have `params`, an array of
+    /// objects with fields `type` and `tainted`; `ret`, a similar object
+    /// (or `null` for void functions); and `varargs_tainted`, whether any
+    /// extra argument to the (variadic) function is tainted.
+    ///
+    /// Default is `None`.
+    pub propagation_trace: Option<Mutex<Box<dyn Write + Send>>>,
+
+    /// If `true`, the analysis records why each variable, and each piece of
+    /// memory, first became tainted: the instruction, and which of its
+    /// operands were tainted at the time. `TaintResult::explain()` follows
+    /// these records to explain why a value is tainted.
+    ///
+    /// This costs some time and memory for every change to taint
+    /// information. Records are not saved in checkpoints, so an analysis
+    /// resumed from a checkpoint only has records from after it resumed.
+    ///
+    /// Default is `false`.
+    pub record_provenance: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             dereferencing_tainted_ptr_gives_tainted: true,
+            taint_pointers_with_tainted_indices: false,
+            propagation_policies: HashMap::new(),
+            bit_level_taint: false,
+            sanitizing_mask_max: None,
+            sanitizing_trunc_max_bits: None,
+            tainting_predicates: None,
             ext_functions: HashMap::new(),
             ext_functions_default: ExternalFunctionHandling::Panic,
+            ext_functions_default_by_module: HashMap::new(),
+            ext_function_attributes: false,
+            model_allocators: false,
+            report_tainted_frees: false,
+            model_format_functions: false,
+            stream_input_tainted: false,
+            report_tainted_format_strings: false,
+            model_setjmp_longjmp: false,
+            model_kernel_user_copies: false,
+            model_wasi_imports: false,
+            model_jni: false,
+            model_gpu_builtins: false,
+            honor_annotations: false,
+            noop_intrinsics: DEFAULT_NOOP_INTRINSICS.iter().map(|&name| name.to_owned()).collect(),
+            register_reads_tainted: false,
+            register_writes_are_sinks: false,
+            tainted_address_ranges: Vec::new(),
+            tainted_sections: HashSet::new(),
+            tainted_address_spaces: HashSet::new(),
+            sink_address_spaces: HashSet::new(),
+            sanitize_bounds_checks: false,
+            validators: HashSet::new(),
+            shared_memory: false,
+            thread_spawn_functions: DEFAULT_THREAD_SPAWN_FUNCTIONS.iter().map(|&(name, callback, arg)| (name.to_owned(), (callback, arg))).collect(),
+            devirtualize_virtual_calls: false,
+            resolve_closure_thunks: false,
+            resolve_fn_ptr_targets: false,
+            flow_sensitive_memory: false,
+            promote_allocas: false,
+            max_pointer_depth: None,
+            unify_renamed_structs: false,
+            lenient_joins: false,
+            recursion_call_string_depth: 0,
+            polymorphic_summaries: false,
+            bottom_up: false,
+            prune_unreachable_functions: false,
+            skip_taint_blind_functions: false,
+            context_only_modules: HashSet::new(),
+            static_initializers_as_entry_points: false,
+            signal_handlers_as_entry_points: false,
+            gpu_kernels_as_entry_points: false,
+            per_entry_results: false,
+            share_leaf_summaries: false,
+            parallel_blocks_threshold: None,
+            deterministic: false,
+            unknown_instructions: UnknownInstructionHandling::Panic,
+            unsound_assumptions: UnsoundAssumptionHandling::Assume,
+            propagation_trace: None,
+            record_provenance: false,
         }
     }
 }
 
+impl Config {
+    /// Get the handling configured in `ext_functions` for the function with
+    /// the given (mangled) name, if any, looking it up by its demangled names
+    /// too
+    pub(crate) fn get_ext_function(&self, name: &str) -> Option<ExternalFunctionHandling> {
+        demangle::names_for(name)
+            .iter()
+            .find_map(|name| self.ext_functions.get(&**name))
+            .copied()
+    }
+
+    /// Get the default handling of external functions called from the given
+    /// modules: the override in `ext_functions_default_by_module` for the
+    /// first of them which has one, or else `ext_functions_default`
+    pub(crate) fn ext_functions_default_for<'m>(&self, modules: impl IntoIterator<Item = &'m Module>) -> ExternalFunctionHandling {
+        modules
+            .into_iter()
+            .find_map(|module| {
+                // the most specific entry, if several match
+                self.ext_functions_default_by_module
+                    .iter()
+                    .filter(|(name, _)| module_name_matches(module, name))
+                    .max_by_key(|(name, _)| name.len())
+                    .map(|(_, handling)| *handling)
+            })
+            .unwrap_or(self.ext_functions_default)
+    }
+
+    /// Is the given module one of the `context_only_modules`?
+    pub(crate) fn is_context_only(&self, module: &Module) -> bool {
+        self.context_only_modules.iter().any(|name| module_name_matches(module, name))
+    }
+
+    /// Is the function with the given (mangled) name one of the `validators`,
+    /// by any of its names?
+    pub(crate) fn is_validator(&self, name: &str) -> bool {
+        demangle::names_for(name)
+            .iter()
+            .any(|name| self.validators.contains(&**name))
+    }
+
+    /// Get the indices of the callback argument and the argument passed to it
+    /// configured in `thread_spawn_functions` for the function with the given
+    /// (mangled) name, if any, looking it up by its demangled names too
+    pub(crate) fn get_thread_spawn_function(&self, name: &str) -> Option<(usize, usize)> {
+        demangle::names_for(name)
+            .iter()
+            .find_map(|name| self.thread_spawn_functions.get(&**name))
+            .copied()
+    }
+
+    /// Get the policy for the given class of instructions, from
+    /// `propagation_policies` or else the default
+    pub(crate) fn propagation_policy(&self, class: InstructionClass) -> PropagationPolicy {
+        match self.propagation_policies.get(&class) {
+            Some(policy) => *policy,
+            None if class == InstructionClass::GepIndex && !self.taint_pointers_with_tainted_indices => PropagationPolicy::Drop,
+            None => PropagationPolicy::Propagate,
+        }
+    }
+
+    /// Is the intrinsic with the given name (including any type suffix)
+    /// matched by `noop_intrinsics`?
+    pub(crate) fn is_noop_intrinsic(&self, name: &str) -> bool {
+        // try each prefix of `name` which ends at a component boundary
+        name.match_indices('.')
+            .map(|(idx, _)| &name[.. idx])
+            .chain(std::iter::once(name))
+            .any(|prefix| self.noop_intrinsics.contains(prefix))
+    }
+}
+
+/// The default contents of `Config::noop_intrinsics`: intrinsics which only
+/// give hints to the optimizer, or otherwise don't move data around in any
+/// way relevant to taint.
+pub const DEFAULT_NOOP_INTRINSICS: &[&str] = &[
+    "llvm.lifetime",
+    "llvm.invariant",
+    "llvm.launder.invariant",
+    "llvm.strip.invariant",
+    "llvm.dbg",
+    "llvm.stacksave",
+    "llvm.stackrestore",
+    "llvm.prefetch",
+    "llvm.donothing",
+    "llvm.sideeffect",
+    "llvm.assume",
+    "llvm.experimental.noalias.scope.decl",
+    "llvm.pseudoprobe",
+    "llvm.var.annotation",
+    "llvm.codeview.annotation",
+    // GPU thread and block ids, barriers, and memory fences
+    "llvm.nvvm.read.ptx.sreg",
+    "llvm.nvvm.barrier0",
+    "llvm.nvvm.barrier",
+    "llvm.nvvm.bar",
+    "llvm.nvvm.membar",
+    "llvm.amdgcn.workitem.id",
+    "llvm.amdgcn.workgroup.id",
+    "llvm.amdgcn.s.barrier",
+    "llvm.amdgcn.wave.barrier",
+    "llvm.amdgcn.s.waitcnt",
+];
+
+/// The default contents of `Config::thread_spawn_functions`: the thread
+/// APIs of POSIX and C11, as (name, callback index, argument index).
+pub const DEFAULT_THREAD_SPAWN_FUNCTIONS: &[(&str, usize, usize)] = &[
+    ("pthread_create", 2, 3),
+    ("thrd_create", 1, 2),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ExternalFunctionHandling {
     /// Ignore the call to the function, and assume it returns fully untainted
     /// data.
@@ -60,3 +1059,89 @@ pub enum ExternalFunctionHandling {
     /// Panic if we encounter a call to this function.
     Panic,
 }
+
+/// Classes of instructions which `Config::propagation_policies` can give
+/// different policies
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InstructionClass {
+    /// `add`, `sub`, `mul`, `and`, `or`, `xor`, and their floating-point
+    /// counterparts `fadd`, `fsub`, `fmul`, and `fneg`
+    Arithmetic,
+    /// `icmp` and `fcmp`
+    Comparison,
+    /// `shl`, `lshr`, and `ashr`
+    Shift,
+    /// Casts between non-pointer types: `trunc`, `zext`, `sext`, `fptrunc`,
+    /// `fpext`, `fptoui`, `fptosi`, `uitofp`, and `sitofp`
+    Cast,
+    /// The indices of a `getelementptr`, as they affect the resulting
+    /// pointer. (The base pointer always propagates.)
+    GepIndex,
+    /// `udiv`, `sdiv`, `urem`, `srem`, `fdiv`, and `frem`
+    Division,
+}
+
+/// The predicate of a comparison, for `Config::tainting_predicates`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComparisonPredicate {
+    /// The predicate of an `icmp`
+    Int(IntPredicate),
+    /// The predicate of an `fcmp`
+    FP(FPPredicate),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PropagationPolicy {
+    /// The result is tainted if any operand is tainted. For
+    /// `InstructionClass::GepIndex`, a tainted index taints the resulting
+    /// pointer, as with `Config::taint_pointers_with_tainted_indices`.
+    Propagate,
+    /// The operands never taint the result. For `InstructionClass::GepIndex`,
+    /// the resulting pointer is tainted only if the base pointer is.
+    Drop,
+    /// Like `Propagate`, but also taint the result if any operand points to
+    /// tainted data (for comparisons of pointers). For
+    /// `InstructionClass::GepIndex`, a tainted index also taints the data the
+    /// resulting pointer points to.
+    TaintConservatively,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnknownInstructionHandling {
+    /// Panic if we encounter an instruction or terminator we have no specific
+    /// handling for.
+    Panic,
+    /// Log a warning, and assume the result of the instruction (if any) is
+    /// tainted if and only if any of its operands are tainted (looking at the
+    /// shallow types of the operands, as for
+    /// `ExternalFunctionHandling::PropagateTaintShallow`). Terminators are
+    /// likewise tainted if any of their operands are. Other effects of the
+    /// instruction, such as writes to memory, are ignored.
+    ///
+    /// Each such instruction or terminator is recorded, and reported by
+    /// `TaintResult::get_unhandled_constructs()`.
+    JoinOperands,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnsoundAssumptionHandling {
+    /// Make the assumption, and record where it was made, to be reported by
+    /// `TaintResult::get_unsound_assumptions()`.
+    Assume,
+    /// Panic if the analysis would need to make an unsound assumption.
+    Panic,
+    /// Instead of making the assumption, treat everything involved as
+    /// tainted: the result of the instruction or call, and the contents of
+    /// any pointers passed to it (for `inttoptr`, the contents of the
+    /// resulting pointer). Nothing is recorded, since the result is sound.
+    /// Note that this handles inline assembly, so it is no longer reported by
+    /// `TaintResult::get_unhandled_constructs()`.
+    Conservative,
+}
+
+/// Does the given name (as in `Config::ext_functions_default_by_module` or
+/// `Config::context_only_modules`) match the given module: is it the module's
+/// name, or the last components of its path?
+fn module_name_matches(module: &Module, name: &str) -> bool {
+    module.name == name || module.name.ends_with(&format!("/{}", name))
+}
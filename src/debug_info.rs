@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The struct layouts described by the debug info of a module, which let
+/// fields be referred to by their names in the source, as in
+/// `TaintResult::get_field_type_by_path()`.
+///
+/// `llvm-ir` doesn't give us debug-info metadata, so this is read from the
+/// module's textual IR (e.g., from `clang -g -S -emit-llvm`, or `llvm-dis` of
+/// the bitcode) rather than from the parsed `Module`.
+#[derive(Clone, Debug, Default)]
+pub struct DebugInfo {
+    /// Map from metadata node number to the struct that node describes
+    structs: HashMap<usize, DebugStruct>,
+    /// Map from the name of a struct in the source (e.g. `request`) to its
+    /// metadata node number
+    struct_names: HashMap<String, usize>,
+}
+
+#[derive(Clone, Debug)]
+struct DebugStruct {
+    /// Prefix of the LLVM name of the struct: `struct`, `class`, or `union`
+    kind: &'static str,
+    /// Name of the struct in the source, if it has one
+    name: Option<String>,
+    /// Members occupying a field of the LLVM struct, in order
+    members: Vec<Member>,
+    /// Does the struct have bitfields? Then several members may share a field
+    /// of the LLVM struct, so we can't tell which field a member is in
+    has_bitfields: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Member {
+    /// Name of the member, or `None` for e.g. a C++ base class
+    name: Option<String>,
+    /// Metadata node number of the struct this member is (or is an array of),
+    /// if any
+    struct_ty: Option<usize>,
+    /// Number of levels of arrays the member is, e.g. 1 for `int body[4]`
+    array_depth: usize,
+}
+
+/// A metadata node in the textual IR
+enum Node<'a> {
+    /// A specialized node such as `!DICompositeType(...)`, with its kind (e.g.
+    /// `DICompositeType`) and its fields
+    Specialized(&'a str, HashMap<&'a str, &'a str>),
+    /// A tuple `!{...}` of the given elements
+    Tuple(Vec<&'a str>),
+}
+
+impl DebugInfo {
+    /// Read the debug info from the textual IR in the file at the given path
+    pub fn from_ll_path(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        Ok(Self::from_ll_str(&text))
+    }
+
+    /// Read the debug info from the given textual IR. Metadata which isn't
+    /// debug info about structs is ignored, so this gives an empty
+    /// `DebugInfo` for IR without debug info.
+    pub fn from_ll_str(text: &str) -> Self {
+        let nodes: HashMap<usize, Node> = text.lines().filter_map(parse_node).collect();
+        let mut debug_info = Self::default();
+        for (&id, node) in &nodes {
+            let fields = match node {
+                Node::Specialized("DICompositeType", fields) => fields,
+                _ => continue,
+            };
+            let kind = match fields.get("tag").copied() {
+                Some("DW_TAG_structure_type") => "struct",
+                Some("DW_TAG_class_type") => "class",
+                Some("DW_TAG_union_type") => "union",
+                _ => continue,
+            };
+            let name = fields.get("name").map(|name| unquote(name).to_owned());
+            let elements = match fields.get("elements").and_then(|elements| nodes.get(&node_ref(elements)?)) {
+                Some(Node::Tuple(elements)) => elements.as_slice(),
+                _ => &[],
+            };
+            let mut members = Vec::new();
+            let mut has_bitfields = false;
+            for element in elements {
+                let fields = match node_ref(element).and_then(|element| nodes.get(&element)) {
+                    Some(Node::Specialized("DIDerivedType", fields)) => fields,
+                    _ => continue,
+                };
+                let flags = fields.get("flags").copied().unwrap_or("");
+                match fields.get("tag").copied() {
+                    Some("DW_TAG_member") | Some("DW_TAG_inheritance") if !flags.contains("DIFlagStaticMember") => {},
+                    _ => continue,
+                }
+                has_bitfields |= flags.contains("DIFlagBitField");
+                let (struct_ty, array_depth) = member_type(&nodes, fields.get("baseType").copied());
+                members.push(Member {
+                    name: fields.get("name").map(|name| unquote(name).to_owned()),
+                    struct_ty,
+                    array_depth,
+                });
+            }
+            if let Some(name) = &name {
+                debug_info.struct_names.insert(name.clone(), id);
+            }
+            debug_info.structs.insert(id, DebugStruct { kind, name, members, has_bitfields });
+        }
+        debug_info
+    }
+
+    /// Resolve a path like `"request.hdr.len"`, that is, the name of a struct
+    /// in the source followed by the names of (nested) members, to the LLVM
+    /// name of the struct (e.g. `struct.request`) and the path of field indices
+    /// into it, as taken by `TaintResult::get_field_type()`.
+    ///
+    /// A member which is an array (of structs) refers to its elements, so the
+    /// path can continue with the members of the element struct: e.g.,
+    /// `"request.items.len"` for `struct request { struct item items[4]; }`.
+    pub fn resolve_field_path(&self, path: &str) -> Result<(String, Vec<u32>), String> {
+        let mut components = path.split('.');
+        let struct_name = components.next().unwrap_or("");
+        let mut id = *self.struct_names
+            .get(struct_name)
+            .ok_or_else(|| format!("resolve_field_path: no struct named {:?} in the debug info", struct_name))?;
+        let llvm_name = format!("{}.{}", self.structs[&id].kind, struct_name);
+        let mut field_path = Vec::new();
+        let mut member: Option<&Member> = None;
+        for component in components {
+            if let Some(member) = member {
+                id = member.struct_ty.ok_or_else(|| format!(
+                    "resolve_field_path: {:?} in path {:?} is a member of a non-struct",
+                    component, path,
+                ))?;
+                field_path.extend(std::iter::repeat_n(0, member.array_depth));
+            }
+            let debug_struct = &self.structs[&id];
+            let struct_desc = debug_struct.name.as_deref().unwrap_or("<anonymous>");
+            if debug_struct.kind == "union" {
+                return Err(format!("resolve_field_path: can't resolve members of union {:?}, in path {:?}", struct_desc, path));
+            }
+            if debug_struct.has_bitfields {
+                return Err(format!("resolve_field_path: can't resolve members of struct {:?}, which has bitfields, in path {:?}", struct_desc, path));
+            }
+            let (index, found) = debug_struct.members
+                .iter()
+                .enumerate()
+                .find(|(_, member)| member.name.as_deref() == Some(component))
+                .ok_or_else(|| format!("resolve_field_path: struct {:?} has no member {:?}, in path {:?}", struct_desc, component, path))?;
+            field_path.push(index as u32);
+            member = Some(found);
+        }
+        Ok((llvm_name, field_path))
+    }
+}
+
+/// Get the metadata node number of the struct the member of the given type is
+/// (or is an array of), and the number of levels of arrays, looking through
+/// typedefs and qualifiers
+fn member_type<'a>(nodes: &HashMap<usize, Node<'a>>, mut ty: Option<&'a str>) -> (Option<usize>, usize) {
+    let mut array_depth = 0;
+    while let Some(id) = ty.and_then(node_ref) {
+        match nodes.get(&id) {
+            Some(Node::Specialized("DICompositeType", fields)) => match fields.get("tag").copied() {
+                Some("DW_TAG_array_type") => {
+                    array_depth += 1;
+                    ty = fields.get("baseType").copied();
+                },
+                Some("DW_TAG_structure_type") | Some("DW_TAG_class_type") | Some("DW_TAG_union_type") => return (Some(id), array_depth),
+                _ => break,
+            },
+            Some(Node::Specialized("DIDerivedType", fields)) => match fields.get("tag").copied() {
+                Some("DW_TAG_typedef") | Some("DW_TAG_const_type") | Some("DW_TAG_volatile_type") | Some("DW_TAG_restrict_type") | Some("DW_TAG_atomic_type") => {
+                    ty = fields.get("baseType").copied();
+                },
+                // pointers etc
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+    (None, array_depth)
+}
+
+/// Parse a line of textual IR defining a metadata node, such as
+/// `!5 = distinct !DICompositeType(tag: DW_TAG_structure_type, ...)` or
+/// `!6 = !{!7, !8}`
+fn parse_node(line: &str) -> Option<(usize, Node<'_>)> {
+    let (id, def) = line.strip_prefix('!')?.split_once(" = ")?;
+    let id = id.parse().ok()?;
+    let def = def.strip_prefix("distinct ").unwrap_or(def).trim_end();
+    if let Some(elements) = def.strip_prefix("!{").and_then(|def| def.strip_suffix('}')) {
+        return Some((id, Node::Tuple(split_top_level(elements))));
+    }
+    let (kind, fields) = def.strip_prefix('!')?.strip_suffix(')')?.split_once('(')?;
+    let fields = split_top_level(fields).into_iter().filter_map(|field| field.split_once(": ")).collect();
+    Some((id, Node::Specialized(kind, fields)))
+}
+
+/// Split the given text at the commas which aren't in a string or nested in
+/// brackets
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            },
+            '"' => in_string = !in_string,
+            '(' | '{' | '[' if !in_string => depth += 1,
+            ')' | '}' | ']' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                parts.push(text[start .. i].trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    let last = text[start ..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// Get the node number of a reference like `!12`
+fn node_ref(text: &str) -> Option<usize> {
+    text.strip_prefix('!')?.parse().ok()
+}
+
+/// Strip the quotes from a string like `"request"`
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"').and_then(|text| text.strip_suffix('"')).unwrap_or(text)
+}
@@ -0,0 +1,70 @@
+use std::borrow::Cow;
+
+/// Demangle the given symbol name, if it's a mangled Rust or C++ name.
+///
+/// Rust names are given without their hash (e.g., `foo::bar` rather than
+/// `foo::bar::h0123456789abcdef`), and C++ names with their parameter types
+/// (e.g., `foo::bar(char const*)`).
+///
+/// Returns `None` if the name isn't mangled (e.g., a C name like `malloc`).
+pub fn demangle(name: &str) -> Option<String> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Some(format!("{:#}", demangled));
+    }
+    let symbol = cpp_demangle::Symbol::new(name).ok()?;
+    symbol.demangle(&cpp_demangle::DemangleOptions::default()).ok()
+}
+
+/// The names by which the user may refer to the function with the given
+/// (mangled) name, e.g. in `Config`: the name itself, and if it's mangled, the
+/// demangled name, and the demangled name without its parameter types (e.g.,
+/// `foo::bar` for `foo::bar(char const*)`).
+pub(crate) fn names_for(name: &str) -> Vec<Cow<'_, str>> {
+    let mut names = vec![Cow::Borrowed(name)];
+    if let Some(demangled) = demangle(name) {
+        let without_params = strip_params(&demangled).map(str::to_owned);
+        names.push(Cow::Owned(demangled));
+        names.extend(without_params.map(Cow::Owned));
+    }
+    names
+}
+
+/// Get the (demangled) name of the C++ class whose vtable (`_ZTV`) or RTTI
+/// (`_ZTI`) has the given mangled name, e.g. `ns::Base` for `_ZTVN2ns4BaseE`
+pub(crate) fn class_of_vtable_or_rtti(name: &str) -> Option<String> {
+    let class = name.strip_prefix("_ZTV").or_else(|| name.strip_prefix("_ZTI"))?;
+    // `cpp_demangle` renders vtables unlike typeinfo, as `{vtable(Base)}`
+    let demangled = demangle(&format!("_ZTI{}", class))?;
+    demangled.strip_prefix("typeinfo for ").map(str::to_owned)
+}
+
+/// Get the name of the C++ class of the LLVM named struct type with the given
+/// name, e.g. `ns::Base` for `class.ns::Base`, or for `class.ns::Base.base`
+/// (the type of its base-class subobjects) or `class.ns::Base.0` (a renamed
+/// copy)
+pub(crate) fn class_of_struct(name: &str) -> Option<&str> {
+    let mut class = name.strip_prefix("class.").or_else(|| name.strip_prefix("struct."))?;
+    while let Some((rest, suffix)) = class.rsplit_once('.') {
+        if suffix == "base" || (!suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit())) {
+            class = rest;
+        } else {
+            break;
+        }
+    }
+    Some(class)
+}
+
+/// Strip the parameter list from the given demangled C++ name, if it has one.
+/// The parameter list starts at the first `(` outside any template arguments.
+fn strip_params(demangled: &str) -> Option<&str> {
+    let mut depth = 0usize;
+    for (idx, c) in demangled.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            '(' if depth == 0 && idx > 0 => return Some(&demangled[.. idx]),
+            _ => {},
+        }
+    }
+    None
+}
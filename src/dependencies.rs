@@ -0,0 +1,67 @@
+use crate::taint_blind;
+use crate::taint_result::TaintResult;
+use llvm_ir::Module;
+use llvm_ir_analysis::CrossModuleAnalysis;
+use std::collections::{HashMap, HashSet};
+
+/// How the functions of the module(s) a `TaintResult` is for are connected:
+/// the call graph, and the functions sharing mutable globals. Finding these
+/// takes a pass over all the instructions, so a `TaintResult` finds them on
+/// the first call of `TaintResult::functions_affected_by()` and keeps them
+/// for later calls. They never need to be invalidated, since a `TaintResult`
+/// (and the modules it borrows) can't change; results of a new analysis,
+/// e.g. after editing the modules, find them anew.
+pub(crate) struct Dependencies<'m> {
+    /// The callees and callers of each function
+    neighbors: HashMap<&'m str, Vec<&'m str>>,
+    /// The functions which refer to mutable globals
+    global_users: Vec<&'m str>,
+}
+
+impl<'m> Dependencies<'m> {
+    /// Find the dependencies between the functions of the module(s) the given
+    /// `TaintResult` is for
+    pub(crate) fn new(taint_result: &TaintResult<'m>) -> Self {
+        let mut modules: Vec<&'m Module> = Vec::new();
+        for fts in taint_result.fn_taint_states.values() {
+            if !modules.iter().any(|module| std::ptr::eq(*module, fts.module)) {
+                modules.push(fts.module);
+            }
+        }
+        let analysis = CrossModuleAnalysis::new(modules.iter().copied());
+        let globals = taint_blind::mutable_globals(&analysis);
+        let call_graph = analysis.call_graph();
+        let mut neighbors = HashMap::new();
+        let mut global_users = Vec::new();
+        for func in analysis.functions() {
+            let name = func.name.as_str();
+            neighbors.insert(name, call_graph.callees(name).chain(call_graph.callers(name)).collect());
+            if taint_blind::refers_to_globals(func, &globals) {
+                global_users.push(name);
+            }
+        }
+        Self { neighbors, global_users }
+    }
+
+    /// Find the functions with results which may depend on any of the
+    /// `changed` functions. See `TaintResult::functions_affected_by()`.
+    pub(crate) fn affected_functions(&self, taint_result: &TaintResult<'m>, changed: &[&str]) -> Vec<&'m str> {
+        let seeds: Vec<&'m str> = changed
+            .iter()
+            .filter_map(|fn_name| self.neighbors.get_key_value(*fn_name))
+            .map(|(fn_name, _)| *fn_name)
+            .collect();
+        // results flow between functions exactly where taint can, so this is
+        // the same connectivity as for taint-blind functions
+        let neighbors = |f: &'m str| self.neighbors.get(f).into_iter().flatten().copied();
+        let connected: HashSet<&'m str> = taint_blind::connected_functions(neighbors, seeds, self.global_users.clone(), false);
+        let mut affected: Vec<&'m str> = taint_result
+            .fn_taint_states
+            .keys()
+            .copied()
+            .filter(|fn_name| connected.contains(fn_name))
+            .collect();
+        affected.sort_unstable();
+        affected
+    }
+}
@@ -0,0 +1,66 @@
+use crate::checkpoint::{Decoder, Encoder};
+use crate::config::Config;
+use crate::named_structs::NamedStructInitialDef;
+use crate::summary_store::SummaryStore;
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{Module, Name};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Like `do_taint_analysis_on_module()`, but also analyze from each of the
+/// given entry points separately, keeping those results in the merged result
+/// (see `Config::per_entry_results`)
+pub(crate) fn analyze_module<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    args: HashMap<&'m str, Vec<TaintedType>>,
+    nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> TaintResult<'m> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let entries: BTreeSet<&'m str> = args.keys().chain(nonargs.keys()).copied().collect();
+    let summary_store = if config.share_leaf_summaries {
+        Some(Arc::new(SummaryStore::new(modules.iter().copied(), config)))
+    } else {
+        None
+    };
+    let mut entry_results = HashMap::new();
+    for entry in entries {
+        // each analysis gets its own copies of the initial types, since
+        // analyses update the data those point to in place
+        let entry_args = args.get(entry).map(|tys| tys.iter().map(deep_copy).collect());
+        let entry_nonargs = nonargs
+            .get(entry)
+            .map(|vars| vars.iter().map(|(name, ty)| (name.clone(), deep_copy(ty))).collect())
+            .unwrap_or_default();
+        let entry_named_structs = named_structs.iter().map(|(name, def)| (name.clone(), copy_def(def))).collect();
+        let mut ts = TaintState::new_single_function(modules.iter().copied(), config, entry, entry_args, entry_nonargs, entry_named_structs);
+        if let Some(store) = &summary_store {
+            ts.use_summary_store(Arc::clone(store));
+        }
+        ts.run_until(usize::MAX);
+        entry_results.insert(entry, ts.into_taint_result());
+    }
+    let mut result = TaintState::do_analysis_multiple_functions(modules, config, args, nonargs, named_structs).into_taint_result();
+    result.entry_results = entry_results;
+    result
+}
+
+/// Copy the given type, along with all the data it points to, so that the
+/// copy shares no `Pointee`s with the original
+fn deep_copy(ty: &TaintedType) -> TaintedType {
+    let mut encoder = Encoder::default();
+    let entry = encoder.encode(ty);
+    let decoder = Decoder::new(&encoder.into_pointees()).expect("types we just encoded should decode");
+    decoder.decode(&entry).expect("types we just encoded should decode")
+}
+
+fn copy_def(def: &NamedStructInitialDef) -> NamedStructInitialDef {
+    match def {
+        NamedStructInitialDef::AllFieldsUntainted => NamedStructInitialDef::AllFieldsUntainted,
+        NamedStructInitialDef::AllFieldsTainted => NamedStructInitialDef::AllFieldsTainted,
+        NamedStructInitialDef::InitialDef(ty) => NamedStructInitialDef::InitialDef(deep_copy(ty)),
+    }
+}
@@ -0,0 +1,46 @@
+/// Functions taking a `printf`- or `scanf`-style format string, which we model
+/// directly (see `Config::model_format_functions`), rather than as calls to
+/// external functions
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum FormatFunction {
+    /// `printf`, `fprintf`, `sprintf`, and `snprintf`: format the extra
+    /// arguments, and write the result to the destination buffer, if any
+    Print {
+        /// Index of the format string argument
+        format: usize,
+        /// Index of the destination buffer argument, if any
+        dest: Option<usize>,
+    },
+    /// `scanf`, `fscanf`, and `sscanf`: parse the input according to the
+    /// format string, and store the results through the extra arguments
+    Scan {
+        /// Index of the format string argument
+        format: usize,
+        /// Index of the input string argument, or `None` if the input is read
+        /// from a stream
+        input: Option<usize>,
+    },
+}
+
+impl FormatFunction {
+    /// Classify the function with the given name
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "printf" => Some(FormatFunction::Print { format: 0, dest: None }),
+            "fprintf" => Some(FormatFunction::Print { format: 1, dest: None }),
+            "sprintf" => Some(FormatFunction::Print { format: 1, dest: Some(0) }),
+            "snprintf" => Some(FormatFunction::Print { format: 2, dest: Some(0) }),
+            "scanf" => Some(FormatFunction::Scan { format: 0, input: None }),
+            "fscanf" => Some(FormatFunction::Scan { format: 1, input: None }),
+            "sscanf" => Some(FormatFunction::Scan { format: 1, input: Some(0) }),
+            _ => None,
+        }
+    }
+
+    /// Index of the format string argument
+    pub(crate) fn format_index(self) -> usize {
+        match self {
+            FormatFunction::Print { format, .. } | FormatFunction::Scan { format, .. } => format,
+        }
+    }
+}
@@ -1,41 +1,78 @@
 use crate::named_structs::NamedStructs;
 use crate::tainted_type::TaintedType;
 use llvm_ir::{Type, TypeRef};
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
+/// Summary of the taint transfer of a function: the `TaintedType`s of its
+/// parameters and of its return value, joined over all of its callers.
 pub struct FunctionSummary<'m> {
-    /// `TaintedType`s of the function parameters
+    /// `TaintedType`s of the function parameters. For variadic functions, this
+    /// only includes the fixed parameters.
     params: Vec<TaintedType>,
 
     /// `TaintedType` of the return type, or `None` for void return type
     ret: Option<TaintedType>,
 
+    /// For variadic functions: is any of the extra (variadic) arguments
+    /// tainted, at any call site? The extra arguments may have many different
+    /// types, so we track only their joined taint.
+    varargs_tainted: bool,
+
+    /// Index of the `sret` parameter, if any: a hidden pointer through which
+    /// the function returns a struct. The contents of its pointee are part of
+    /// the return value.
+    sret_param: Option<usize>,
+
     /// Reference to the module's named struct types
-    named_structs: Rc<RefCell<NamedStructs<'m>>>,
+    named_structs: Arc<RwLock<NamedStructs<'m>>>,
 }
 
 impl<'m> FunctionSummary<'m> {
-    pub fn new_untainted(
+    pub(crate) fn new_untainted(
         param_llvm_types: impl IntoIterator<Item = TypeRef>,
         ret_llvm_type: &Type,
-        named_structs: Rc<RefCell<NamedStructs<'m>>>,
+        named_structs: Arc<RwLock<NamedStructs<'m>>>,
     ) -> Self {
-        Self {
-            params: param_llvm_types
+        let (params, ret) = {
+            let structs = named_structs.read().unwrap();
+            let params = param_llvm_types
                 .into_iter()
-                .map(|ty| TaintedType::from_llvm_type(&ty))
-                .collect(),
-            ret: match ret_llvm_type {
+                .map(|ty| structs.fresh_type_from_llvm(&ty))
+                .collect();
+            let ret = match ret_llvm_type {
                 Type::VoidType => None,
-                ty => Some(TaintedType::from_llvm_type(ty)),
-            },
+                ty => Some(structs.fresh_type_from_llvm(ty)),
+            };
+            (params, ret)
+        };
+        Self {
+            params,
+            ret,
+            varargs_tainted: false,
+            sret_param: None,
+            named_structs,
+        }
+    }
+
+    /// Construct a `FunctionSummary` directly from its contents, e.g. when
+    /// restoring a checkpoint
+    pub(crate) fn from_parts(
+        params: Vec<TaintedType>,
+        ret: Option<TaintedType>,
+        varargs_tainted: bool,
+        named_structs: Arc<RwLock<NamedStructs<'m>>>,
+    ) -> Self {
+        Self {
+            params,
+            ret,
+            varargs_tainted,
+            sret_param: None,
             named_structs,
         }
     }
 
     /// Iterate over the parameters of the function
-    pub fn get_params<'s>(&'s self) -> impl Iterator<Item = &'s TaintedType> {
+    pub fn get_params(&self) -> impl Iterator<Item = &TaintedType> {
         self.params.iter()
     }
 
@@ -44,11 +81,47 @@ impl<'m> FunctionSummary<'m> {
         &self.ret
     }
 
+    /// Get the index of the `sret` parameter, if any: a hidden pointer through
+    /// which the function returns a struct
+    pub fn get_sret_param(&self) -> Option<usize> {
+        self.sret_param
+    }
+
+    /// Get the `TaintedType` of the struct the function returns through its
+    /// `sret` parameter, or `None` if it has no `sret` parameter
+    pub fn get_sret_ty(&self) -> Option<TaintedType> {
+        match self.params.get(self.sret_param?)? {
+            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => Some(pointee.ty().clone()),
+            _ => None,
+        }
+    }
+
+    /// Record that the parameter with the given index is the `sret` parameter
+    pub(crate) fn set_sret_param(&mut self, index: usize) {
+        self.sret_param = Some(index);
+    }
+
+    /// For variadic functions: is any of the extra (variadic) arguments tainted?
+    ///
+    /// Always `false` for non-variadic functions.
+    pub fn are_varargs_tainted(&self) -> bool {
+        self.varargs_tainted
+    }
+
+    /// Mark the extra (variadic) arguments as tainted.
+    ///
+    /// Returns `true` if a change was made to the `FunctionSummary`.
+    pub(crate) fn taint_varargs(&mut self) -> bool {
+        let changed = !self.varargs_tainted;
+        self.varargs_tainted = true;
+        changed
+    }
+
     /// Update the `TaintedType`s of the function parameters.
     /// Performs a `join` of each type with the corresponding existing type.
     ///
     /// Returns `true` if a change was made to the `FunctionSummary`.
-    pub fn update_params(&mut self, new_params: Vec<TaintedType>) -> Result<bool, String> {
+    pub(crate) fn update_params(&mut self, new_params: Vec<TaintedType>) -> Result<bool, String> {
         if new_params.len() != self.params.len() {
             Err(format!(
                 "trying to update function from {} parameter(s) to {} parameter(s)",
@@ -57,7 +130,7 @@ impl<'m> FunctionSummary<'m> {
             ))
         } else {
             let mut retval = false;
-            for (param, new_param) in self.params.iter_mut().zip(new_params.into_iter()) {
+            for (param, new_param) in self.params.iter_mut().zip(new_params) {
                 let joined = param.join(&new_param)?;
                 if param != &joined {
                     retval = true;
@@ -72,7 +145,7 @@ impl<'m> FunctionSummary<'m> {
     /// Performs a `join` of the given type and the existing return type.
     ///
     /// Returns `true` if a change was made to the `FunctionSummary`.
-    pub fn update_ret(&mut self, new_ret: &Option<&TaintedType>) -> Result<bool, String> {
+    pub(crate) fn update_ret(&mut self, new_ret: &Option<&TaintedType>) -> Result<bool, String> {
         match new_ret {
             None => match &self.ret {
                 Some(ret) => Err(format!("update_ret: trying to update function from non-void to void. Old return type: {}", ret)),
@@ -91,21 +164,61 @@ impl<'m> FunctionSummary<'m> {
         }
     }
 
-    /// Taint the return type.
+    /// Join `other` (e.g., the summary of a clone of this function) into this
+    /// summary
+    pub(crate) fn join_with(&mut self, other: &Self) -> Result<(), String> {
+        self.update_params(other.get_params().cloned().collect())?;
+        self.update_ret(&other.get_ret_ty().as_ref())?;
+        if other.are_varargs_tainted() {
+            self.taint_varargs();
+        }
+        if let Some(index) = other.get_sret_param() {
+            self.set_sret_param(index);
+        }
+        Ok(())
+    }
+
+    /// Taint the return type, including the struct returned through the
+    /// `sret` parameter, if any.
     ///
     /// Returns `true` if a change was made to the `FunctionSummary`.
-    pub fn taint_ret(&mut self) -> bool {
-        match &mut self.ret {
+    pub(crate) fn taint_ret(&mut self) -> bool {
+        let mut changed = match &mut self.ret {
             None => false,
             Some(ret) => {
-                let tainted = self.named_structs.borrow_mut().to_tainted(ret);
+                let mut named_structs = self.named_structs.write().unwrap();
+                // `to_tainted()` marks the elements of arrays, vectors, and
+                // structs (and named structs) tainted in place, so the
+                // tainted type would compare equal to `ret`; ask the elements
+                // whether they change instead
+                let elements_changed = match &*ret {
+                    TaintedType::ArrayOrVector(element) => element.taint_elements(&mut named_structs),
+                    TaintedType::Struct(elements) => {
+                        let mut changed = false;
+                        for element in elements {
+                            changed |= element.taint_elements(&mut named_structs);
+                        }
+                        changed
+                    },
+                    TaintedType::NamedStruct(name) => !named_structs.all_tainted_named_structs().any(|s| s == name),
+                    _ => false,
+                };
+                let tainted = named_structs.to_tainted(ret);
                 if ret == &tainted {
-                    false
+                    elements_changed
                 } else {
                     *ret = tainted;
                     true
                 }
             }
+        };
+        if let Some(index) = self.sret_param {
+            if let TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) = &self.params[index] {
+                if pointee.taint_elements(&mut self.named_structs.write().unwrap()) {
+                    changed = true;
+                }
+            }
         }
+        changed
     }
 }
@@ -1,32 +1,66 @@
+use crate::demangle::demangle;
 use crate::globals::Globals;
 use crate::named_structs::NamedStructs;
 use crate::pointee::Pointee;
-use crate::tainted_type::TaintedType;
+use crate::tainted_type::{FnTargets, TaintedType};
 use crate::worklist::Worklist;
 use llvm_ir::*;
 use llvm_ir::constant::ConstBinaryOp;
-use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry;
-use std::rc::Rc;
+use std::fmt;
+use std::sync::{Arc, RwLock};
 
 #[derive(Clone)]
 pub struct FunctionTaintState<'m> {
     /// Name of the function
-    name: &'m str,
+    pub(crate) name: &'m str,
     /// Map from `Name`s of variables to their (currently believed) types
     map: HashMap<Name, TaintedType>,
     /// Set of basic blocks that have tainted terminators (e.g., the branch
     /// condition is tainted). Unconditional branches are never tainted.
     tainted_terminators: HashSet<Name>,
+    /// Set of instructions which pass tainted data to a sink (e.g., a tainted
+    /// `llvm.write_register`), each identified by the name of its basic block
+    /// and its index within that block
+    tainted_sinks: HashSet<(Name, usize)>,
+    /// Set of calls of deallocators which are passed a tainted pointer (see
+    /// `Config::report_tainted_frees`), identified like `tainted_sinks`
+    tainted_frees: HashSet<(Name, usize)>,
+    /// Set of calls of format functions whose format string is tainted (see
+    /// `Config::report_tainted_format_strings`), identified like
+    /// `tainted_sinks`
+    tainted_format_strings: HashSet<(Name, usize)>,
+    /// Instructions and terminators which the analysis has no specific
+    /// handling for (see `Config::unknown_instructions`), each identified by
+    /// the name of its basic block and its index within that block (`None`
+    /// for the terminator), mapped to a description of the construct (e.g.
+    /// `"freeze"`)
+    unhandled_constructs: HashMap<(Name, Option<usize>), String>,
+    /// Instructions where the analysis applied a documented unsound
+    /// assumption, identified like `unhandled_constructs`, mapped to a
+    /// description of the assumption
+    assumptions: HashMap<(Name, Option<usize>), String>,
+    /// Bits of integer variables which may be tainted, as masks, for the
+    /// variables whose bits are tracked individually (see
+    /// `Config::bit_level_taint`). Any other tainted integer variable may have
+    /// all its bits tainted.
+    tainted_bits: HashMap<Name, u64>,
+    /// Variables which are sanitized in the basic block currently being
+    /// processed (see `Config::sanitize_bounds_checks`), and so are treated as
+    /// untainted when used there
+    sanitized_vars: HashSet<Name>,
+    /// Addresses which are sanitized in the basic block currently being
+    /// processed: values loaded from them are treated as untainted
+    sanitized_addrs: HashSet<Name>,
     /// Reference to the llvm-ir `Module` containing this function
     pub(crate) module: &'m Module,
     /// Reference to the `TaintState`'s named struct types
-    pub(crate) named_structs: Rc<RefCell<NamedStructs<'m>>>,
+    pub(crate) named_structs: Arc<RwLock<NamedStructs<'m>>>,
     /// Reference to the `TaintState`'s globals
-    pub(crate) globals: Rc<RefCell<Globals<'m>>>,
+    pub(crate) globals: Arc<RwLock<Globals<'m>>>,
     /// Reference to the `TaintState`'s worklist
-    pub(crate) worklist: Rc<RefCell<Worklist<'m>>>,
+    pub(crate) worklist: Arc<RwLock<Worklist<'m>>>,
 }
 
 impl<'m> FunctionTaintState<'m> {
@@ -34,14 +68,22 @@ impl<'m> FunctionTaintState<'m> {
         name: &'m str,
         taintmap: HashMap<Name, TaintedType>,
         module: &'m Module,
-        named_structs: Rc<RefCell<NamedStructs<'m>>>,
-        globals: Rc<RefCell<Globals<'m>>>,
-        worklist: Rc<RefCell<Worklist<'m>>>,
+        named_structs: Arc<RwLock<NamedStructs<'m>>>,
+        globals: Arc<RwLock<Globals<'m>>>,
+        worklist: Arc<RwLock<Worklist<'m>>>,
     ) -> Self {
         Self {
             name,
             map: taintmap,
             tainted_terminators: HashSet::new(),
+            tainted_sinks: HashSet::new(),
+            tainted_frees: HashSet::new(),
+            tainted_format_strings: HashSet::new(),
+            unhandled_constructs: HashMap::new(),
+            assumptions: HashMap::new(),
+            tainted_bits: HashMap::new(),
+            sanitized_vars: HashSet::new(),
+            sanitized_addrs: HashSet::new(),
             module,
             named_structs,
             globals,
@@ -53,8 +95,44 @@ impl<'m> FunctionTaintState<'m> {
         &self.map
     }
 
+    /// Copy this taint state, with the `TaintedType` of each variable replaced
+    /// by `f` of its name and `TaintedType`
+    pub(crate) fn map_types(&self, mut f: impl FnMut(&Name, &TaintedType) -> TaintedType) -> Self {
+        Self {
+            name: self.name,
+            map: self.map.iter().map(|(name, ty)| (name.clone(), f(name, ty))).collect(),
+            tainted_terminators: self.tainted_terminators.clone(),
+            tainted_sinks: self.tainted_sinks.clone(),
+            tainted_frees: self.tainted_frees.clone(),
+            tainted_format_strings: self.tainted_format_strings.clone(),
+            unhandled_constructs: self.unhandled_constructs.clone(),
+            assumptions: self.assumptions.clone(),
+            tainted_bits: self.tainted_bits.clone(),
+            sanitized_vars: self.sanitized_vars.clone(),
+            sanitized_addrs: self.sanitized_addrs.clone(),
+            module: self.module,
+            named_structs: Arc::clone(&self.named_structs),
+            globals: Arc::clone(&self.globals),
+            worklist: Arc::clone(&self.worklist),
+        }
+    }
+
     /// Get the `TaintedType` of the given `Operand`, according to the current state.
     pub(crate) fn get_type_of_operand(&self, op: &Operand) -> Result<TaintedType, String> {
+        match op {
+            Operand::ConstantOperand(constant) => self.get_type_of_constant(constant),
+            Operand::MetadataOperand => Ok(TaintedType::UntaintedValue),
+            Operand::LocalOperand { name, .. } if self.sanitized_vars.contains(name) => {
+                let ty = self.get_type_of_operand_unsanitized(op)?;
+                Ok(ty.sanitized())
+            },
+            _ => self.get_type_of_operand_unsanitized(op),
+        }
+    }
+
+    /// Like `get_type_of_operand()`, but ignoring any sanitization in the
+    /// current block
+    pub(crate) fn get_type_of_operand_unsanitized(&self, op: &Operand) -> Result<TaintedType, String> {
         match op {
             Operand::ConstantOperand(constant) => self.get_type_of_constant(constant),
             Operand::MetadataOperand => Ok(TaintedType::UntaintedValue),
@@ -75,20 +153,37 @@ impl<'m> FunctionTaintState<'m> {
                     // be corrected on a future pass. (And we'll definitely have
                     // a future pass, because that variable becoming defined
                     // counts as a change for the fixpoint algorithm.)
-                    Ok(TaintedType::from_llvm_type(&self.module.type_of(op)))
+                    Ok(self.type_from_llvm(&self.module.type_of(op)))
                 },
             },
         }
     }
 
+    /// Set the variables and addresses which are sanitized in the basic block
+    /// about to be processed
+    pub(crate) fn set_sanitized(&mut self, vars: HashSet<Name>, addrs: HashSet<Name>) {
+        self.sanitized_vars = vars;
+        self.sanitized_addrs = addrs;
+    }
+
+    /// Is the given address sanitized in the basic block currently being
+    /// processed, so that values loaded from it are untainted?
+    pub(crate) fn is_address_sanitized(&self, addr: &Operand) -> bool {
+        match addr {
+            Operand::LocalOperand { name, .. } => self.sanitized_addrs.contains(name),
+            _ => false,
+        }
+    }
+
     /// Return `true` if the given `op` has been marked tainted, otherwise `false`.
     /// This function should only be called on scalars, not pointers, arrays, or structs.
     pub(crate) fn is_scalar_operand_tainted(&self, op: &Operand) -> Result<bool, String> {
         match self.get_type_of_operand(op)? {
             TaintedType::UntaintedValue => Ok(false),
             TaintedType::TaintedValue => Ok(true),
-            TaintedType::UntaintedFnPtr => Ok(false),
-            TaintedType::TaintedFnPtr => Ok(true),
+            TaintedType::UntaintedFnPtr(_) => Ok(false),
+            TaintedType::TaintedFnPtr(_) => Ok(true),
+            TaintedType::Token => Ok(false),
             TaintedType::UntaintedPointer(_) => Err(format!(
                 "is_scalar_operand_tainted(): operand has pointer type: {:?}",
                 op
@@ -108,13 +203,19 @@ impl<'m> FunctionTaintState<'m> {
         }
     }
 
+    /// Produce the (untainted) `TaintedType` for a given LLVM type, respecting
+    /// `Config::max_pointer_depth` (see `NamedStructs::type_from_llvm()`)
+    pub(crate) fn type_from_llvm(&self, llvm_ty: &Type) -> TaintedType {
+        self.named_structs.write().unwrap().type_from_llvm(llvm_ty)
+    }
+
     /// Get the `TaintedType` of a `Constant`.
     pub(crate) fn get_type_of_constant(&self, constant: &Constant) -> Result<TaintedType, String> {
         match constant {
             Constant::Int { .. } => Ok(TaintedType::UntaintedValue),
             Constant::Float(_) => Ok(TaintedType::UntaintedValue),
-            Constant::Null(ty) => Ok(TaintedType::from_llvm_type(ty)),
-            Constant::AggregateZero(ty) => Ok(TaintedType::from_llvm_type(ty)),
+            Constant::Null(ty) => Ok(self.type_from_llvm(ty)),
+            Constant::AggregateZero(ty) => Ok(self.type_from_llvm(ty)),
             Constant::Struct { values, .. } => {
                 let elements = values
                     .iter()
@@ -123,22 +224,28 @@ impl<'m> FunctionTaintState<'m> {
                 Ok(TaintedType::struct_of(elements))
             },
             Constant::Array { element_type, .. } => {
-                Ok(TaintedType::array_or_vec_of(TaintedType::from_llvm_type(element_type)))
+                Ok(TaintedType::array_or_vec_of(self.type_from_llvm(element_type)))
             },
             Constant::Vector(vec) => {
                 // all elements should be the same type, so we do the type of the first one
-                Ok(TaintedType::array_or_vec_of(TaintedType::from_llvm_type(
-                    &self.module.type_of(vec.get(0).expect("Constant::Vector should not be empty"))
+                Ok(TaintedType::array_or_vec_of(self.type_from_llvm(
+                    &self.module.type_of(vec.first().expect("Constant::Vector should not be empty"))
                 )))
             },
-            Constant::Undef(ty) => Ok(TaintedType::from_llvm_type(ty)),
-            Constant::BlockAddress => Ok(TaintedType::UntaintedValue), // technically a pointer, but for our purposes an opaque constant
+            Constant::Undef(ty) => Ok(self.type_from_llvm(ty)),
+            // the address of code, which holds no tainted data
+            Constant::BlockAddress => Ok(TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)),
+            Constant::TokenNone => Ok(TaintedType::Token),
             Constant::GlobalReference { name, ty } => {
                 match ty.as_ref() {
-                    Type::FuncType { .. } => Ok(TaintedType::UntaintedFnPtr),
+                    Type::FuncType { .. } => match name {
+                        Name::Name(fn_name) => Ok(TaintedType::fn_ptr_to(fn_name.as_str())),
+                        Name::Number(_) => Ok(TaintedType::UntaintedFnPtr(FnTargets::new())),
+                    },
                     _ => {
-                        let mut globals = self.globals.borrow_mut();
-                        Ok(globals.get_type_of_global(name.clone(), ty, &self.name).clone())
+                        let named_structs = self.named_structs.read().unwrap();
+                        let mut globals = self.globals.write().unwrap();
+                        Ok(globals.get_type_of_global(name.clone(), ty, self.name, &named_structs).clone())
                     },
                 }
             },
@@ -172,18 +279,18 @@ impl<'m> FunctionTaintState<'m> {
             Constant::FPToSI(f) => self.get_type_of_constant(&f.operand),
             Constant::IntToPtr(itp) => {
                 let int_type = self.get_type_of_constant(&itp.operand)?;
-                let ptr_type = TaintedType::from_llvm_type(&self.module.type_of(itp));
+                let ptr_type = self.type_from_llvm(&self.module.type_of(itp));
                 if int_type.is_tainted_nonamedstruct() {
-                    Ok(self.named_structs.borrow_mut().to_tainted(&ptr_type))
+                    Ok(self.named_structs.write().unwrap().to_tainted(&ptr_type))
                 } else {
                     Ok(ptr_type)
                 }
             },
             Constant::PtrToInt(pti) => {
                 let ptr_type = self.get_type_of_constant(&pti.operand)?;
-                let int_type = TaintedType::from_llvm_type(&self.module.type_of(pti));
-                if ptr_type.is_tainted(Rc::clone(&self.named_structs), self.name) {
-                    Ok(self.named_structs.borrow_mut().to_tainted(&int_type))
+                let int_type = self.type_from_llvm(&self.module.type_of(pti));
+                if ptr_type.is_tainted(Arc::clone(&self.named_structs), self.name) {
+                    Ok(self.named_structs.write().unwrap().to_tainted(&int_type))
                 } else {
                     Ok(int_type)
                 }
@@ -191,20 +298,23 @@ impl<'m> FunctionTaintState<'m> {
             Constant::BitCast(bc) => {
                 let from_ty = self.get_type_of_constant(&bc.operand)?;
                 match &from_ty {
-                    TaintedType::UntaintedValue | TaintedType::UntaintedFnPtr => {
-                        Ok(TaintedType::from_llvm_type(&bc.to_type))
+                    TaintedType::UntaintedValue | TaintedType::UntaintedFnPtr(_) => {
+                        Ok(self.type_from_llvm(&bc.to_type).with_fn_targets_of(&from_ty))
                     },
-                    TaintedType::TaintedValue | TaintedType::TaintedFnPtr => {
-                        Ok(self.named_structs.borrow_mut().to_tainted(&TaintedType::from_llvm_type(&bc.to_type)))
+                    TaintedType::TaintedValue | TaintedType::TaintedFnPtr(_) => {
+                        let mut named_structs = self.named_structs.write().unwrap();
+                        let to_type = named_structs.type_from_llvm(&bc.to_type).with_fn_targets_of(&from_ty);
+                        Ok(named_structs.to_tainted(&to_type))
                     },
                     TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => match bc.to_type.as_ref() {
                         Type::PointerType { pointee_type, .. } => {
-                            let mut named_structs = self.named_structs.borrow_mut();
+                            let mut named_structs = self.named_structs.write().unwrap();
                             let is_pointee_tainted = named_structs.is_type_tainted(&pointee.ty(), self.name);
+                            let result_pointee_type = named_structs.type_from_llvm(pointee_type);
                             let result_pointee_type = if is_pointee_tainted {
-                                named_structs.to_tainted(&TaintedType::from_llvm_type(&pointee_type))
+                                named_structs.to_tainted(&result_pointee_type)
                             } else {
-                                TaintedType::from_llvm_type(&pointee_type)
+                                result_pointee_type
                             };
                             if named_structs.is_type_tainted(&from_ty, self.name) {
                                 Ok(TaintedType::tainted_ptr_to(result_pointee_type))
@@ -214,29 +324,37 @@ impl<'m> FunctionTaintState<'m> {
                         },
                         _ => Err("Bitcast from pointer to non-pointer".into()), // my reading of the LLVM 9 LangRef disallows this
                     },
+                    TaintedType::Token => Err("Bitcast of a token".into()), // tokens can't be cast
                     from_ty @ TaintedType::ArrayOrVector(_)
                     | from_ty @ TaintedType::Struct(_) => {
-                        let mut named_structs = self.named_structs.borrow_mut();
+                        let mut named_structs = self.named_structs.write().unwrap();
                         if named_structs.is_type_tainted(from_ty, self.name) {
-                            Ok(named_structs.to_tainted(&TaintedType::from_llvm_type(&bc.to_type)))
+                            let to_type = named_structs.type_from_llvm(&bc.to_type);
+                            Ok(named_structs.to_tainted(&to_type))
                         } else {
-                            Ok(TaintedType::from_llvm_type(&bc.to_type))
+                            Ok(named_structs.type_from_llvm(&bc.to_type))
                         }
                     }
                     TaintedType::NamedStruct(name) => {
-                        let mut named_structs = self.named_structs.borrow_mut();
+                        let mut named_structs = self.named_structs.write().unwrap();
                         let def = named_structs.get_named_struct_type(name.clone(), self.name).clone();
                         if named_structs.is_type_tainted(&def, self.name) {
-                            Ok(named_structs.to_tainted(&TaintedType::from_llvm_type(&bc.to_type)))
+                            let to_type = named_structs.type_from_llvm(&bc.to_type);
+                            Ok(named_structs.to_tainted(&to_type))
                         } else {
-                            Ok(TaintedType::from_llvm_type(&bc.to_type))
+                            Ok(named_structs.type_from_llvm(&bc.to_type))
                         }
                     }
                 }
             },
+            Constant::ICmp(_) | Constant::FCmp(_) => Ok(self.type_from_llvm(&self.module.type_of(constant))),
+            Constant::Select(select) => {
+                self.get_type_of_constant(&select.true_value)?
+                    .join(&self.get_type_of_constant(&select.false_value)?)
+            },
             Constant::GetElementPtr(gep) => {
                 let parent_ptr = self.get_type_of_constant(&gep.address)?;
-                self.named_structs.borrow_mut().get_element_ptr(&self.name, &parent_ptr, &gep.indices)
+                self.named_structs.write().unwrap().get_element_ptr(self.name, &parent_ptr, &gep.indices)
             },
             _ => unimplemented!("get_type_of_constant on {:?}", constant),
         }
@@ -262,7 +380,10 @@ impl<'m> FunctionTaintState<'m> {
     ) -> Result<bool, String> {
         match self.map.entry(name) {
             Entry::Occupied(mut oentry) => {
-                let joined = oentry.get().join(&taintedtype)?;
+                let joined = match oentry.get().join(&taintedtype) {
+                    Ok(joined) => joined,
+                    Err(_) => self.named_structs.write().unwrap().join(oentry.get(), &taintedtype, self.name)?,
+                };
                 if oentry.get() == &joined {
                     Ok(false)
                 } else {
@@ -277,6 +398,24 @@ impl<'m> FunctionTaintState<'m> {
         }
     }
 
+    /// Join all of the taint information in `other` (e.g., the taint state of
+    /// a clone of this function) into this taint state
+    pub(crate) fn join_with(&mut self, other: &Self) -> Result<(), String> {
+        for (name, ty) in &other.map {
+            self.update_var_taintedtype(name.clone(), ty.clone())?;
+        }
+        self.tainted_terminators.extend(other.tainted_terminators.iter().cloned());
+        self.tainted_sinks.extend(other.tainted_sinks.iter().cloned());
+        self.tainted_frees.extend(other.tainted_frees.iter().cloned());
+        self.tainted_format_strings.extend(other.tainted_format_strings.iter().cloned());
+        self.unhandled_constructs.extend(other.unhandled_constructs.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.assumptions.extend(other.assumptions.iter().map(|(k, v)| (k.clone(), v.clone())));
+        for (name, bits) in &other.tainted_bits {
+            self.update_tainted_bits(name.clone(), *bits);
+        }
+        Ok(())
+    }
+
     /// Update the given pointee to the given `TaintedType`.
     /// This performs a `join` just like `update_var_taintedtype`.
     ///
@@ -287,7 +426,7 @@ impl<'m> FunctionTaintState<'m> {
         pointee: &mut Pointee,
         new_pointee: &TaintedType,
     ) -> Result<bool, String> {
-        pointee.update(new_pointee, &self)
+        pointee.update(new_pointee, self)
     }
 
     /// Is the terminator of the given block tainted?
@@ -295,10 +434,194 @@ impl<'m> FunctionTaintState<'m> {
         self.tainted_terminators.contains(block)
     }
 
+    /// Iterate over the names of the blocks whose terminators are tainted
+    pub(crate) fn get_tainted_terminators(&self) -> impl Iterator<Item = &Name> {
+        self.tainted_terminators.iter()
+    }
+
     /// Mark the terminator of the block with the given `Name` as tainted.
     ///
     /// Returns `true` if this was a change.
     pub(crate) fn mark_terminator_tainted(&mut self, block: Name) -> bool {
         self.tainted_terminators.insert(block)
     }
+
+    /// Get the bits of the given integer variable which may be tainted, as a
+    /// mask, if they're tracked individually (see `Config::bit_level_taint`)
+    pub(crate) fn get_tainted_bits(&self, name: &Name) -> Option<u64> {
+        self.tainted_bits.get(name).copied()
+    }
+
+    /// Iterate over the variables whose bits are tracked individually, and
+    /// their tainted bits
+    pub(crate) fn get_all_tainted_bits(&self) -> impl Iterator<Item = (&Name, &u64)> {
+        self.tainted_bits.iter()
+    }
+
+    /// Mark the given bits of the given integer variable as (possibly)
+    /// tainted, in addition to any which already are.
+    ///
+    /// Returns `true` if this was a change.
+    pub(crate) fn update_tainted_bits(&mut self, name: Name, bits: u64) -> bool {
+        match self.tainted_bits.entry(name) {
+            Entry::Occupied(mut oentry) => {
+                let joined = *oentry.get() | bits;
+                if *oentry.get() == joined {
+                    false
+                } else {
+                    oentry.insert(joined);
+                    true
+                }
+            },
+            Entry::Vacant(ventry) => {
+                ventry.insert(bits);
+                true
+            },
+        }
+    }
+
+    /// Iterate over the instructions which pass tainted data to a sink, as
+    /// (block name, index within the block) pairs
+    pub(crate) fn get_tainted_sinks(&self) -> impl Iterator<Item = &(Name, usize)> {
+        self.tainted_sinks.iter()
+    }
+
+    /// Mark the instruction at the given index in the block with the given
+    /// `Name` as passing tainted data to a sink.
+    ///
+    /// Returns `true` if this was a change.
+    pub(crate) fn mark_sink_tainted(&mut self, block: Name, index: usize) -> bool {
+        self.tainted_sinks.insert((block, index))
+    }
+
+    /// Iterate over the calls of deallocators which are passed a tainted
+    /// pointer, as (block name, index within the block) pairs
+    pub(crate) fn get_tainted_frees(&self) -> impl Iterator<Item = &(Name, usize)> {
+        self.tainted_frees.iter()
+    }
+
+    /// Mark the call at the given index in the block with the given `Name` as
+    /// passing a tainted pointer to a deallocator.
+    ///
+    /// Returns `true` if this was a change.
+    pub(crate) fn mark_free_tainted(&mut self, block: Name, index: usize) -> bool {
+        self.tainted_frees.insert((block, index))
+    }
+
+    /// Iterate over the calls of format functions whose format string is
+    /// tainted, as (block name, index within the block) pairs
+    pub(crate) fn get_tainted_format_strings(&self) -> impl Iterator<Item = &(Name, usize)> {
+        self.tainted_format_strings.iter()
+    }
+
+    /// Mark the call at the given index in the block with the given `Name` as
+    /// passing a tainted format string to a format function.
+    ///
+    /// Returns `true` if this was a change.
+    pub(crate) fn mark_format_string_tainted(&mut self, block: Name, index: usize) -> bool {
+        self.tainted_format_strings.insert((block, index))
+    }
+
+    /// Iterate over the instructions and terminators which the analysis has
+    /// no specific handling for, as ((block name, index within the block, or
+    /// `None` for the terminator), description of the construct) pairs
+    pub(crate) fn get_unhandled_constructs(&self) -> impl Iterator<Item = (&(Name, Option<usize>), &String)> {
+        self.unhandled_constructs.iter()
+    }
+
+    /// Record that the analysis has no specific handling for the instruction
+    /// at the given index in the block with the given `Name` (or for its
+    /// terminator, if `index` is `None`), which is the given construct.
+    ///
+    /// Returns `true` if this instruction or terminator wasn't recorded
+    /// already.
+    pub(crate) fn mark_unhandled(&mut self, block: Name, index: Option<usize>, construct: impl Into<String>) -> bool {
+        self.unhandled_constructs.insert((block, index), construct.into()).is_none()
+    }
+
+    /// Iterate over the instructions where the analysis applied an unsound
+    /// assumption, as ((block name, index within the block, or `None` for the
+    /// terminator), description of the assumption) pairs
+    pub(crate) fn get_assumptions(&self) -> impl Iterator<Item = (&(Name, Option<usize>), &String)> {
+        self.assumptions.iter()
+    }
+
+    /// Record that the analysis applied the given unsound assumption to the
+    /// instruction at the given index in the block with the given `Name` (or
+    /// to its terminator, if `index` is `None`).
+    ///
+    /// Returns `true` if this instruction or terminator wasn't recorded
+    /// already.
+    pub(crate) fn mark_assumption(&mut self, block: Name, index: Option<usize>, assumption: impl Into<String>) -> bool {
+        self.assumptions.insert((block, index), assumption.into()).is_none()
+    }
+
+    /// Write a human-readable description of the taint map, grouped by basic
+    /// block and in the order the variables are defined in the function.
+    /// Variables whose type is tainted according to `is_tainted` are flagged
+    /// with `[T]`; if `only_tainted`, the others are left out.
+    ///
+    /// Types are abbreviated after a few levels of nesting, unless the
+    /// formatter's alternate flag (`{:#}`) is set.
+    pub(crate) fn fmt_with(&self, f: &mut fmt::Formatter, is_tainted: &dyn Fn(&TaintedType) -> bool, only_tainted: bool) -> fmt::Result {
+        let max_depth = if f.alternate() { usize::MAX } else { DISPLAY_MAX_DEPTH };
+        let mut printed: HashSet<&Name> = HashSet::new();
+        let fmt_var = |f: &mut fmt::Formatter, name: &Name, ty: &TaintedType| {
+            let tainted = is_tainted(ty);
+            if only_tainted && !tainted {
+                return Ok(());
+            }
+            let flag = if tainted { "[T]" } else { "   " };
+            writeln!(f, "  {} {}: {}", flag, name, ty.abbreviated(max_depth))
+        };
+        match demangle(self.name) {
+            Some(demangled) => writeln!(f, "function {:?} ({}):", self.name, demangled)?,
+            None => writeln!(f, "function {:?}:", self.name)?,
+        }
+        if let Some(func) = self.module.get_func_by_name(self.name) {
+            writeln!(f, " parameters:")?;
+            for param in &func.parameters {
+                if let Some(ty) = self.map.get(&param.name) {
+                    fmt_var(f, &param.name, ty)?;
+                    printed.insert(&param.name);
+                }
+            }
+            for bb in &func.basic_blocks {
+                if self.is_terminator_tainted(&bb.name) {
+                    writeln!(f, " block {} [tainted terminator]:", bb.name)?;
+                } else {
+                    writeln!(f, " block {}:", bb.name)?;
+                }
+                for name in bb.instrs.iter().filter_map(|inst| inst.try_get_result()) {
+                    if let Some(ty) = self.map.get(name) {
+                        fmt_var(f, name, ty)?;
+                        printed.insert(name);
+                    }
+                }
+            }
+        }
+        // anything else in the map (e.g., initial types provided for variables
+        // which don't appear in the function) goes at the end, in sorted order
+        let mut others: Vec<(&Name, &TaintedType)> = self.map.iter().filter(|(name, _)| !printed.contains(name)).collect();
+        if !others.is_empty() {
+            writeln!(f, " other variables:")?;
+            others.sort_by_key(|(name, _)| *name);
+            for (name, ty) in others {
+                fmt_var(f, name, ty)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Types nested more deeply than this are abbreviated by the `Display`
+/// implementations of `FunctionTaintState` and `TaintResult`
+pub(crate) const DISPLAY_MAX_DEPTH: usize = 3;
+
+impl<'m> fmt::Display for FunctionTaintState<'m> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let named_structs = Arc::clone(&self.named_structs);
+        let name = self.name;
+        self.fmt_with(f, &|ty| named_structs.write().unwrap().is_type_tainted(ty, name), false)
+    }
 }
@@ -1,5 +1,6 @@
+use crate::named_structs::NamedStructs;
 use crate::pointee::Pointee;
-use crate::tainted_type::TaintedType;
+use crate::tainted_type::{FnTargets, TaintedType};
 use llvm_ir::{Name, Type};
 use std::collections::{HashMap, HashSet};
 
@@ -24,6 +25,26 @@ impl<'m> Globals<'m> {
         }
     }
 
+    /// Construct a `Globals` directly from its contents, e.g. when restoring a
+    /// checkpoint
+    pub(crate) fn from_parts(global_types: HashMap<Name, TaintedType>, global_users: HashMap<Name, HashSet<&'m str>>) -> Self {
+        Self {
+            global_types,
+            global_users,
+        }
+    }
+
+    /// Iterate over (global name, `TaintedType`) pairs for all the globals we
+    /// have types for
+    pub(crate) fn all_global_types(&self) -> impl Iterator<Item = (&Name, &TaintedType)> {
+        self.global_types.iter()
+    }
+
+    /// Iterate over (global name, names of functions using it) pairs
+    pub(crate) fn all_global_users<'s>(&'s self) -> impl Iterator<Item = (&'s Name, &'s HashSet<&'m str>)> {
+        self.global_users.iter()
+    }
+
     /// Get the (currently believed) `TaintedType` of the global with the given
     /// name and LLVM `Type`. This `TaintedType` will always be a pointer type.
     ///
@@ -35,14 +56,42 @@ impl<'m> Globals<'m> {
     ///
     /// Creates an untainted `TaintedType` for this global if no type previously
     /// existed for it.
-    pub fn get_type_of_global(&mut self, name: Name, llvm_pointee_ty: &Type, cur_fn: &'m str) -> &mut TaintedType {
-        self.global_users.entry(name.clone()).or_default().insert(cur_fn.into());
+    pub fn get_type_of_global(&mut self, name: Name, llvm_pointee_ty: &Type, cur_fn: &'m str, named_structs: &NamedStructs<'m>) -> &mut TaintedType {
+        self.global_users.entry(name.clone()).or_default().insert(cur_fn);
         self.global_types.entry(name.clone()).or_insert_with(|| {
-            let pointee = Pointee::new_global_contents(TaintedType::from_llvm_type(llvm_pointee_ty), name);
+            let pointee = Pointee::new_global_contents(named_structs.fresh_type_from_llvm(llvm_pointee_ty), name);
             TaintedType::untainted_ptr_to_pointee(pointee)
         })
     }
 
+    /// Mark the contents of the global with the given name and LLVM pointee
+    /// type tainted, e.g. because it's annotated as a source (see
+    /// `Config::honor_annotations`).
+    pub(crate) fn taint_global(&mut self, name: Name, llvm_pointee_ty: &Type, named_structs: &mut NamedStructs<'m>) {
+        let ty = self.global_types.entry(name.clone()).or_insert_with(|| {
+            let pointee = Pointee::new_global_contents(named_structs.fresh_type_from_llvm(llvm_pointee_ty), name);
+            TaintedType::untainted_ptr_to_pointee(pointee)
+        });
+        ty.taint_contents(named_structs);
+    }
+
+    /// Record that the function pointers in the global table with the given
+    /// name and LLVM array type point to the given functions, the ones in its
+    /// initializer (see `Config::resolve_fn_ptr_targets`). This must be done
+    /// before anything else creates a type for the global.
+    pub(crate) fn add_fn_ptr_table(&mut self, name: Name, llvm_pointee_ty: &Type, targets: &FnTargets) {
+        fn contents(llvm_ty: &Type, targets: &FnTargets) -> TaintedType {
+            match llvm_ty {
+                Type::ArrayType { element_type, .. } => TaintedType::array_or_vec_of(contents(element_type, targets)),
+                _ => TaintedType::UntaintedFnPtr(targets.clone()),
+            }
+        }
+        self.global_types.entry(name.clone()).or_insert_with(|| {
+            let pointee = Pointee::new_global_contents(contents(llvm_pointee_ty, targets), name);
+            TaintedType::untainted_ptr_to_pointee(pointee)
+        });
+    }
+
     /// Get the names of the functions which are currently known to use the
     /// global with the given name.
     pub fn get_global_users(&self, global_name: &Name) -> impl IntoIterator<Item = &'m str> {
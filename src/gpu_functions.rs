@@ -0,0 +1,128 @@
+use crate::demangle;
+
+/// OpenCL and CUDA builtins, and the NVVM and AMDGPU intrinsics they're
+/// lowered to, which we model directly (see `Config::model_gpu_builtins`),
+/// rather than as calls to external functions
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum GpuFunction {
+    /// Work-item and work-group queries (`get_global_id`, `get_local_size`,
+    /// ...), barriers, and memory fences: no effect on taint, and any value
+    /// they return is untainted
+    NoEffect,
+    /// Work-group and sub-group collectives (`work_group_broadcast`,
+    /// `sub_group_reduce_add`, ...) and warp shuffles and votes: the result
+    /// is tainted if any argument is
+    Collective,
+    /// Atomic read-modify-writes (`atomicAdd`, `atomic_add`,
+    /// `atomic_fetch_add`, ...): the other arguments are combined into the
+    /// data the pointer points to, and the result is the old contents
+    Atomic {
+        /// Index of the pointer argument
+        ptr: usize,
+    },
+    /// Asynchronous copies (`async_work_group_copy`, `memcpy_async`,
+    /// `cp.async`, ...), e.g. from global into shared memory: like `memcpy`
+    AsyncCopy {
+        /// Index of the destination pointer argument
+        dest: usize,
+        /// Index of the source pointer argument
+        src: usize,
+    },
+}
+
+impl GpuFunction {
+    /// Classify the function with the given (possibly mangled) name
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        if name.starts_with("llvm.") {
+            return Self::from_intrinsic_name(name);
+        }
+        demangle::names_for(name).iter().find_map(|name| Self::from_unmangled_name(name))
+    }
+
+    fn from_intrinsic_name(name: &str) -> Option<Self> {
+        if name.starts_with("llvm.nvvm.cp.async.ca.shared.global.") || name.starts_with("llvm.nvvm.cp.async.cg.shared.global.") {
+            Some(GpuFunction::AsyncCopy { dest: 0, src: 1 })
+        } else if name.starts_with("llvm.nvvm.cp.async.") {
+            // committing and waiting for groups of copies
+            Some(GpuFunction::NoEffect)
+        } else if name.starts_with("llvm.nvvm.shfl.") || name.starts_with("llvm.nvvm.vote.")
+            || name.starts_with("llvm.amdgcn.readlane") || name.starts_with("llvm.amdgcn.readfirstlane")
+            || name.starts_with("llvm.amdgcn.ds.bpermute") || name.starts_with("llvm.amdgcn.ds.swizzle")
+        {
+            Some(GpuFunction::Collective)
+        } else {
+            None
+        }
+    }
+
+    fn from_unmangled_name(name: &str) -> Option<Self> {
+        let (namespaces, name) = split_name(name);
+        if !namespaces.is_empty() {
+            // `cuda::memcpy_async(dest, src, ...)`, and the cooperative
+            // groups version, which takes the group first
+            return match name {
+                "memcpy_async" if namespaces.starts_with("cooperative_groups") => Some(GpuFunction::AsyncCopy { dest: 1, src: 2 }),
+                "memcpy_async" if namespaces.starts_with("cuda") => Some(GpuFunction::AsyncCopy { dest: 0, src: 1 }),
+                _ => None,
+            };
+        }
+        match name {
+            "get_global_id" | "get_local_id" | "get_group_id" | "get_global_size" | "get_local_size"
+            | "get_enqueued_local_size" | "get_num_groups" | "get_work_dim" | "get_global_offset"
+            | "get_global_linear_id" | "get_local_linear_id" | "get_sub_group_size" | "get_max_sub_group_size"
+            | "get_num_sub_groups" | "get_sub_group_id" | "get_sub_group_local_id" => Some(GpuFunction::NoEffect),
+            "barrier" | "work_group_barrier" | "sub_group_barrier" | "mem_fence" | "read_mem_fence"
+            | "write_mem_fence" | "atomic_work_item_fence" | "wait_group_events" => Some(GpuFunction::NoEffect),
+            "__syncthreads" | "__syncwarp" | "__threadfence" | "__threadfence_block" | "__threadfence_system"
+            | "__pipeline_commit" | "__pipeline_wait_prior" => Some(GpuFunction::NoEffect),
+            "async_work_group_copy" | "async_work_group_strided_copy" | "__pipeline_memcpy_async" => Some(GpuFunction::AsyncCopy { dest: 0, src: 1 }),
+            _ if name.starts_with("work_group_") || name.starts_with("sub_group_") => Some(GpuFunction::Collective),
+            _ => {
+                // OpenCL 1.x `atomic_*` and `atom_*`, OpenCL 2.0
+                // `atomic_fetch_*` (and their `_explicit` variants), and CUDA
+                // `atomic*` (and their `_block` and `_system` variants)
+                let op = name
+                    .strip_prefix("atomic_fetch_")
+                    .or_else(|| name.strip_prefix("atomic_"))
+                    .or_else(|| name.strip_prefix("atom_"))
+                    .map(|op| op.strip_suffix("_explicit").unwrap_or(op))
+                    .or_else(|| {
+                        let op = name.strip_prefix("atomic")?;
+                        let op = op.strip_suffix("_block").or_else(|| op.strip_suffix("_system")).unwrap_or(op);
+                        Some(op)
+                    })?;
+                match op.to_ascii_lowercase().as_str() {
+                    "add" | "sub" | "xchg" | "exch" | "exchange" | "inc" | "dec" | "cmpxchg" | "cas" | "min" | "max"
+                    | "and" | "or" | "xor" | "load" | "store" => Some(GpuFunction::Atomic { ptr: 0 }),
+                    _ => None,
+                }
+            },
+        }
+    }
+}
+
+/// Split the given demangled name without its parameters into its
+/// namespaces and its base name, dropping any return type and template
+/// arguments (e.g., `("cuda", "memcpy_async")` for `void
+/// cuda::memcpy_async<int>`)
+fn split_name(name: &str) -> (&str, &str) {
+    let mut name = name;
+    if name.ends_with('>') {
+        let mut depth = 0usize;
+        for (idx, c) in name.char_indices().rev() {
+            match c {
+                '>' => depth += 1,
+                '<' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        name = &name[.. idx];
+                        break;
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+    let name = name.rsplit(' ').next().unwrap_or(name);
+    name.rsplit_once("::").unwrap_or(("", name))
+}
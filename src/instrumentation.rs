@@ -0,0 +1,118 @@
+use crate::allocators::Allocator;
+use crate::taint_result::{called_global, TaintResult};
+use crate::tainted_type::TaintedType;
+use either::Either;
+use llvm_ir::{Instruction, Module, Name, Operand};
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Which parts of the program need dynamic taint instrumentation (e.g., with
+/// DataFlowSanitizer), according to the static results: the rest can't
+/// handle tainted data, so it can run uninstrumented. See
+/// `TaintResult::instrumentation_guidance()`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InstrumentationGuidance<'m> {
+    /// Functions which handle tainted data: a variable of theirs is tainted
+    /// or points to tainted data, or a terminator of theirs is tainted.
+    /// Sorted.
+    pub functions: Vec<&'m str>,
+    /// Functions defined in the same modules which never handle tainted data,
+    /// including those the analysis never reached. Sorted.
+    pub uninstrumented: Vec<&'m str>,
+    /// Stack and heap allocations whose memory holds tainted data, as
+    /// (function name, name of the `alloca` or of the allocator call's
+    /// result). Sorted.
+    pub allocations: Vec<(&'m str, &'m Name)>,
+    /// Globals whose contents are tainted. Sorted.
+    pub globals: Vec<&'m Name>,
+}
+
+impl<'m> InstrumentationGuidance<'m> {
+    /// Write an ABI list for DataFlowSanitizer (`-fsanitize-ignorelist` /
+    /// `-dfsan-abilist`) to `writer`, marking each of the `uninstrumented`
+    /// functions as `uninstrumented` and `discard`, so that DataFlowSanitizer
+    /// neither instruments them nor propagates labels through calls of them.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_dfsan_abilist(&self, mut writer: impl Write) -> Result<(), String> {
+        let mut list = String::new();
+        for fn_name in &self.uninstrumented {
+            list.push_str(&format!("fun:{}=uninstrumented\n", fn_name));
+            list.push_str(&format!("fun:{}=discard\n", fn_name));
+        }
+        writer.write_all(list.as_bytes()).map_err(|e| format!("Failed to write ABI list: {}", e))
+    }
+}
+
+pub(crate) fn instrumentation_guidance<'m>(taint_result: &TaintResult<'m>) -> InstrumentationGuidance<'m> {
+    let handles_taint = |ty: &TaintedType| match ty {
+        TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => {
+            taint_result.is_type_tainted_if_known(ty) || taint_result.is_type_tainted_if_known(&pointee.ty())
+        },
+        ty => taint_result.is_type_tainted_if_known(ty),
+    };
+    let mut functions = Vec::new();
+    let mut allocations = Vec::new();
+    let mut modules: Vec<&'m Module> = Vec::new();
+    for (fn_name, fts) in &taint_result.fn_taint_states {
+        if !modules.iter().any(|module| std::ptr::eq(*module, fts.module)) {
+            modules.push(fts.module);
+        }
+        let taint_map = fts.get_taint_map();
+        if taint_map.values().any(handles_taint) || fts.get_tainted_terminators().next().is_some() {
+            functions.push(*fn_name);
+        }
+        let func = match fts.module.get_func_by_name(fn_name) {
+            Some(func) => func,
+            None => continue,
+        };
+        for inst in func.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()) {
+            let dest = match inst {
+                Instruction::Alloca(alloca) => &alloca.dest,
+                Instruction::Call(call) => match (&call.function, &call.dest) {
+                    (Either::Right(Operand::ConstantOperand(cref)), Some(dest)) => match called_global(cref) {
+                        Some(Name::Name(callee)) if matches!(Allocator::from_name(callee), Some(Allocator::Alloc) | Some(Allocator::Realloc)) => dest,
+                        _ => continue,
+                    },
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let holds_taint = match taint_map.get(dest) {
+                Some(TaintedType::UntaintedPointer(pointee)) | Some(TaintedType::TaintedPointer(pointee)) => taint_result.is_type_tainted_if_known(&pointee.ty()),
+                _ => false,
+            };
+            if holds_taint {
+                allocations.push((*fn_name, dest));
+            }
+        }
+    }
+    functions.sort_unstable();
+    allocations.sort_unstable();
+
+    let instrumented: HashSet<&str> = functions.iter().copied().collect();
+    let mut uninstrumented: Vec<&'m str> = modules
+        .iter()
+        .flat_map(|module| module.functions.iter())
+        .filter(|func| !func.basic_blocks.is_empty())
+        .map(|func| func.name.as_str())
+        .filter(|fn_name| !instrumented.contains(fn_name))
+        .collect();
+    uninstrumented.sort_unstable();
+    uninstrumented.dedup();
+    let mut globals: Vec<&'m Name> = modules
+        .iter()
+        .flat_map(|module| module.global_vars.iter())
+        .map(|var| &var.name)
+        .filter(|name| taint_result.is_global_tainted(name))
+        .collect();
+    globals.sort_unstable();
+    globals.dedup();
+
+    InstrumentationGuidance {
+        functions,
+        uninstrumented,
+        allocations,
+        globals,
+    }
+}
@@ -0,0 +1,52 @@
+/// Functions of the JNI interface function table which pass data from Java to
+/// native code, which we model directly as taint sources (see
+/// `Config::model_jni`). Native code calls these through the function table
+/// `JNIEnv` points to, so we identify them by their index in the table, as
+/// given in the JNI specification.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum JniFunction {
+    /// `GetStringChars`, `GetStringUTFChars`, the `Get<Type>ArrayElements`
+    /// family, `GetPrimitiveArrayCritical`, `GetStringCritical`, and
+    /// `GetDirectBufferAddress`: return a pointer to Java data
+    GetData,
+    /// `GetStringLength`, `GetStringUTFLength`, `GetArrayLength`, the
+    /// `Get<Type>Field` and `GetStatic<Type>Field` families for primitive
+    /// types, and the `Call<Type>Method` families for primitive types: return
+    /// a Java value
+    GetValue,
+    /// The `Get<Type>ArrayRegion` family, `GetStringRegion`, and
+    /// `GetStringUTFRegion`: copy Java data into a buffer
+    CopyRegion {
+        /// Index of the buffer argument
+        buf: usize,
+    },
+}
+
+/// The names of the named structs of the JNI function table: `jni.h` from the
+/// JDK calls it `JNINativeInterface_`, and the Android NDK's
+/// `JNINativeInterface`
+pub(crate) const JNI_TABLE_STRUCTS: &[&str] = &["struct.JNINativeInterface_", "struct.JNINativeInterface"];
+
+impl JniFunction {
+    /// Classify the function at the given index in the JNI function table
+    pub(crate) fn from_table_index(index: u64) -> Option<Self> {
+        match index {
+            // GetStringChars, GetStringUTFChars
+            165 | 169 => Some(JniFunction::GetData),
+            // Get<Type>ArrayElements
+            183 ..= 190 => Some(JniFunction::GetData),
+            // GetPrimitiveArrayCritical, GetStringCritical, GetDirectBufferAddress
+            222 | 224 | 230 => Some(JniFunction::GetData),
+            // GetStringLength, GetStringUTFLength, GetArrayLength
+            164 | 168 | 171 => Some(JniFunction::GetValue),
+            // Call<Type>Method(V/A), CallNonvirtual<Type>Method(V/A), and
+            // CallStatic<Type>Method(V/A), for Boolean through Double
+            37 ..= 60 | 67 ..= 90 | 117 ..= 140 => Some(JniFunction::GetValue),
+            // Get<Type>Field and GetStatic<Type>Field, for Boolean through Double
+            96 ..= 103 | 146 ..= 153 => Some(JniFunction::GetValue),
+            // Get<Type>ArrayRegion, GetStringRegion, GetStringUTFRegion
+            199 ..= 206 | 220 | 221 => Some(JniFunction::CopyRegion { buf: 4 }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+/// The `setjmp` and `longjmp` families of functions, which we model directly
+/// (see `Config::model_setjmp_longjmp`), rather than as calls to external
+/// functions
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum JumpFunction {
+    /// `setjmp`, `_setjmp`, `sigsetjmp`, and `__sigsetjmp`: return zero, and
+    /// then return again with the value passed to each `longjmp` to the
+    /// buffer
+    Setjmp,
+    /// `longjmp`, `_longjmp`, `siglongjmp`, and `__longjmp_chk`: return from
+    /// the `setjmp` which filled the buffer, with the given value
+    Longjmp {
+        /// Index of the value argument
+        value: usize,
+    },
+}
+
+impl JumpFunction {
+    /// Classify the function with the given name
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "setjmp" | "_setjmp" | "sigsetjmp" | "__sigsetjmp" => Some(JumpFunction::Setjmp),
+            "longjmp" | "_longjmp" | "siglongjmp" | "__longjmp_chk" => Some(JumpFunction::Longjmp { value: 1 }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+/// The Linux kernel's primitives for copying data between user and kernel
+/// memory, which we model directly (see `Config::model_kernel_user_copies`),
+/// rather than as calls to external functions
+// the variants are named after the functions, which all end in `_user`
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum KernelFunction {
+    /// `copy_from_user`, `get_user`, `strncpy_from_user`, and their variants:
+    /// copy user data into kernel memory, whose contents become tainted
+    FromUser {
+        /// Index of the kernel destination pointer argument
+        dest: usize,
+    },
+    /// `memdup_user` and `strndup_user`: copy user data into newly allocated
+    /// kernel memory, whose contents are tainted
+    DupUser,
+    /// `copy_to_user` and its variants: copy the data a kernel pointer points
+    /// to out to user memory
+    ToUser {
+        /// Index of the kernel source pointer argument
+        src: usize,
+    },
+    /// `put_user` and its variants: copy a value out to user memory
+    PutUser {
+        /// Index of the value argument
+        value: usize,
+    },
+}
+
+impl KernelFunction {
+    /// Classify the function with the given name
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "copy_from_user" | "_copy_from_user" | "__copy_from_user" | "raw_copy_from_user"
+            | "__copy_from_user_inatomic" | "copy_from_user_nofault" | "strncpy_from_user" => Some(KernelFunction::FromUser { dest: 0 }),
+            "get_user" | "__get_user" => Some(KernelFunction::FromUser { dest: 0 }),
+            "memdup_user" | "vmemdup_user" | "memdup_user_nul" | "strndup_user" => Some(KernelFunction::DupUser),
+            "copy_to_user" | "_copy_to_user" | "__copy_to_user" | "raw_copy_to_user"
+            | "__copy_to_user_inatomic" | "copy_to_user_nofault" => Some(KernelFunction::ToUser { src: 1 }),
+            "put_user" | "__put_user" => Some(KernelFunction::PutUser { value: 0 }),
+            _ => None,
+        }
+    }
+}
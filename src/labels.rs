@@ -0,0 +1,297 @@
+use crate::config::Config;
+use crate::named_structs::NamedStructInitialDef;
+use crate::table::{self, Row, TableFormat};
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{Module, Name};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io::Write;
+
+/// A taint label, drawn from a user-defined lattice: for instance, levels of
+/// trust (`Trusted < SemiTrusted < Untrusted`), or classification levels.
+///
+/// The partial order on labels is defined by `join()`: `a <= b` exactly when
+/// `a.join(b) == b`. `join()` must therefore be associative, commutative, and
+/// idempotent, and `bottom()` must be its identity.
+pub trait TaintLabel: Clone + Eq + Hash + Debug {
+    /// The least label. Data with this label is untainted.
+    fn bottom() -> Self;
+
+    /// The least upper bound of the two labels
+    fn join(&self, other: &Self) -> Self;
+
+    /// The label of the data returned by the functions configured to return
+    /// tainted data (with `ExternalFunctionHandling::IgnoreAndReturnTainted`,
+    /// in `Config::ext_functions` or by default) or annotated as sources (see
+    /// `Config::honor_annotations`). If this is bottom, their data is
+    /// untainted.
+    fn ext_source() -> Self;
+
+    /// Is this label less than or equal to the other in the lattice?
+    fn leq(&self, other: &Self) -> bool {
+        &self.join(other) == other
+    }
+}
+
+/// The result of a labeled taint analysis (see
+/// `do_labeled_taint_analysis_on_function()`): each value's label is the join
+/// of the labels of all the sources whose data may flow to it.
+///
+/// Internally, this runs the ordinary (two-level) taint analysis once per
+/// distinct source label, with only the sources of that label tainted, and
+/// then joins the labels of the runs in which each value is tainted. Since
+/// taint only ever flows by joining, this gives the same labels as running a
+/// single fixpoint over the lattice. The functions configured as sources are
+/// sources with label `TaintLabel::ext_source()`, so they are tainted only in
+/// the run for that label. A labeled analysis thus costs as much as N
+/// ordinary ones, where N is the number of distinct labels given, plus one
+/// if `ext_source()` is neither bottom nor among them.
+pub struct LabeledTaintResult<'m, L: TaintLabel> {
+    /// Result of the analysis of each distinct source label. There is always
+    /// at least one, even if no source has a label other than bottom.
+    runs: Vec<(L, TaintResult<'m>)>,
+}
+
+impl<'m, L: TaintLabel> LabeledTaintResult<'m, L> {
+    /// Get the label of a variable by name
+    pub fn get_var_label(&self, fn_name: &str, var_name: &Name) -> L {
+        self.runs
+            .iter()
+            .filter(|(_, result)| result.is_type_tainted(result.get_var_type(fn_name, var_name)))
+            .fold(L::bottom(), |acc, (label, _)| acc.join(label))
+    }
+
+    /// Given a function name, returns a map from variable name to label for all
+    /// the variables in that function
+    pub fn get_function_label_map(&self, fn_name: &str) -> HashMap<Name, L> {
+        let (_, first) = &self.runs[0];
+        first
+            .get_function_taint_map(fn_name)
+            .keys()
+            .map(|var_name| (var_name.clone(), self.get_var_label(fn_name, var_name)))
+            .collect()
+    }
+
+    /// Get the label of the data returned by the function with the given name,
+    /// according to its final `FunctionSummary`s. This is bottom for void
+    /// functions and for functions which have no summary.
+    pub fn get_return_label(&self, fn_name: &str) -> L {
+        self.runs
+            .iter()
+            .filter(|(_, result)| result.does_function_return_tainted(fn_name))
+            .fold(L::bottom(), |acc, (label, _)| acc.join(label))
+    }
+
+    /// Get the `TaintResult` of the analysis in which only the sources with the
+    /// given label were tainted, or `None` if no source had that label
+    pub fn get_taint_result_for_label(&self, label: &L) -> Option<&TaintResult<'m>> {
+        self.runs.iter().find(|(l, _)| l == label).map(|(_, result)| result)
+    }
+
+    /// Write the results to `writer` as a table in the given format, like
+    /// `TaintResult::write_table()`, but with each row's `labels` column
+    /// giving its label (in `Debug` format), and its `status` being `tainted`
+    /// if the label isn't bottom.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_table(&self, format: TableFormat, writer: impl Write) -> Result<(), String> {
+        // the rows of all runs, in the order they first appear, each with the
+        // join of the labels of the runs in which it's tainted
+        let mut rows: Vec<(Row, L)> = Vec::new();
+        let mut row_indices: HashMap<(String, String, String), usize> = HashMap::new();
+        for (label, result) in &self.runs {
+            for row in table::rows(result) {
+                let (module, function, value) = row.key();
+                let key = (module.to_owned(), function.to_owned(), value.to_owned());
+                let index = *row_indices.entry(key).or_insert_with(|| {
+                    rows.push((row.clone(), L::bottom()));
+                    rows.len() - 1
+                });
+                if row.tainted {
+                    let (_, row_label) = &mut rows[index];
+                    *row_label = row_label.join(label);
+                }
+            }
+        }
+        let rows = rows.into_iter().map(|(row, label)| Row {
+            tainted: label != L::bottom(),
+            labels: format!("{:?}", label),
+            ..row
+        });
+        table::write_table(rows, format, writer)
+    }
+}
+
+/// Get the distinct non-bottom labels among the given ones, in the order they
+/// first appear
+pub(crate) fn distinct_labels<'a, L: TaintLabel + 'a>(labels: impl IntoIterator<Item = &'a L>) -> Vec<L> {
+    let mut distinct: Vec<L> = Vec::new();
+    for label in labels {
+        if label != &L::bottom() && !distinct.contains(label) {
+            distinct.push(label.clone());
+        }
+    }
+    distinct
+}
+
+/// Get the `TaintedType`s of the given function's parameters when they are all
+/// untainted
+pub(crate) fn untainted_params(modules: &[&Module], fn_name: &str) -> Vec<TaintedType> {
+    let func = modules
+        .iter()
+        .find_map(|module| module.get_func_by_name(fn_name))
+        .unwrap_or_else(|| panic!("Failed to find function named {:?}", fn_name));
+    func.parameters.iter().map(|param| TaintedType::from_llvm_type(&param.ty)).collect()
+}
+
+/// The sources for one run of the analysis: those with one particular label
+pub(crate) struct Run<L, A> {
+    pub(crate) label: L,
+    pub(crate) args: A,
+    pub(crate) nonargs: HashMap<Name, TaintedType>,
+    pub(crate) named_structs: HashMap<String, NamedStructInitialDef>,
+}
+
+/// Set up one `Run` for each distinct label, with `args` giving the arguments
+/// for that run (with sources of other labels replaced by untainted types)
+pub(crate) fn partition_sources<L: TaintLabel, A>(
+    labels: Vec<L>,
+    mut args: impl FnMut(&L) -> A,
+    nonargs: HashMap<Name, (TaintedType, L)>,
+    named_structs: HashMap<String, (NamedStructInitialDef, L)>,
+) -> Vec<Run<L, A>> {
+    let mut runs: Vec<Run<L, A>> = labels
+        .into_iter()
+        .map(|label| Run {
+            args: args(&label),
+            label,
+            nonargs: HashMap::new(),
+            named_structs: HashMap::new(),
+        })
+        .collect();
+    for (name, (ty, label)) in nonargs {
+        if let Some(run) = runs.iter_mut().find(|run| run.label == label) {
+            run.nonargs.insert(name, ty);
+        }
+    }
+    for (name, (def, label)) in named_structs {
+        if let Some(run) = runs.iter_mut().find(|run| run.label == label) {
+            run.named_structs.insert(name, def);
+        }
+    }
+    runs
+}
+
+/// Run the given analysis, for the sources with the given label, to
+/// completion. The functions configured as sources return tainted data only
+/// if that is their label.
+fn finish_run<'m, L: TaintLabel>(mut ts: TaintState<'m>, label: &L) -> TaintResult<'m> {
+    ts.set_ext_sources_tainted(label != &L::bottom() && label == &L::ext_source());
+    ts.compute();
+    ts.into_taint_result()
+}
+
+pub(crate) fn analyze_function<'m, L: TaintLabel>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<(TaintedType, L)>>,
+    nonargs: HashMap<Name, (TaintedType, L)>,
+    named_structs: HashMap<String, (NamedStructInitialDef, L)>,
+) -> LabeledTaintResult<'m, L> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let mut labels = distinct_labels(
+        args.iter()
+            .flatten()
+            .map(|(_, label)| label)
+            .chain(nonargs.values().map(|(_, label)| label))
+            .chain(named_structs.values().map(|(_, label)| label))
+            .chain(std::iter::once(&L::ext_source())),
+    );
+    if labels.is_empty() {
+        labels.push(L::bottom());
+    }
+    let runs = partition_sources(
+        labels,
+        |label| args.as_ref().map(|args| {
+            args.iter()
+                .zip(untainted_params(&modules, start_fn_name))
+                .map(|((ty, arg_label), untainted)| if arg_label == label { ty.clone() } else { untainted })
+                .collect()
+        }),
+        nonargs,
+        named_structs,
+    );
+    LabeledTaintResult {
+        runs: runs
+            .into_iter()
+            .map(|run| {
+                let ts = TaintState::new_single_function(modules.iter().copied(), config, start_fn_name, run.args, run.nonargs, run.named_structs);
+                let result = finish_run(ts, &run.label);
+                (run.label, result)
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn analyze_module<'m, L: TaintLabel>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    args: HashMap<&'m str, Vec<(TaintedType, L)>>,
+    nonargs: HashMap<&'m str, HashMap<Name, (TaintedType, L)>>,
+    named_structs: HashMap<String, (NamedStructInitialDef, L)>,
+) -> LabeledTaintResult<'m, L> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let mut labels = distinct_labels(
+        args.values()
+            .flatten()
+            .map(|(_, label)| label)
+            .chain(nonargs.values().flat_map(|vars| vars.values()).map(|(_, label)| label))
+            .chain(named_structs.values().map(|(_, label)| label))
+            .chain(std::iter::once(&L::ext_source())),
+    );
+    if labels.is_empty() {
+        labels.push(L::bottom());
+    }
+    // `partition_sources()` handles nonargs of a single function, so we
+    // partition the nonargs of each function ourselves
+    let mut run_nonargs: Vec<HashMap<&'m str, HashMap<Name, TaintedType>>> = labels.iter().map(|_| HashMap::new()).collect();
+    for (fn_name, vars) in nonargs {
+        for (name, (ty, label)) in vars {
+            if let Some(idx) = labels.iter().position(|l| *l == label) {
+                run_nonargs[idx].entry(fn_name).or_default().insert(name, ty);
+            }
+        }
+    }
+    let runs = partition_sources(
+        labels,
+        |label| {
+            args.iter()
+                .map(|(fn_name, fn_args)| {
+                    let fn_args = fn_args
+                        .iter()
+                        .zip(untainted_params(&modules, fn_name))
+                        .map(|((ty, arg_label), untainted)| if arg_label == label { ty.clone() } else { untainted })
+                        .collect();
+                    (*fn_name, fn_args)
+                })
+                .collect::<HashMap<&'m str, Vec<TaintedType>>>()
+        },
+        HashMap::new(),
+        named_structs,
+    );
+    LabeledTaintResult {
+        runs: runs
+            .into_iter()
+            .zip(run_nonargs)
+            .map(|(run, nonargs)| {
+                let ts = TaintState::new_multiple_functions(modules.iter().copied(), config, run.args, nonargs, run.named_structs);
+                let result = finish_run(ts, &run.label);
+                (run.label, result)
+            })
+            .collect(),
+    }
+}
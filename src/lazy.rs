@@ -0,0 +1,112 @@
+use crate::config::Config;
+use crate::named_structs::NamedStructInitialDef;
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{Module, Name};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// A set of bitcode files, each parsed into a `Module` only when a function
+/// defined in it is first needed (see `do_taint_analysis_on_function_lazily()`)
+///
+/// Creating a `LazyModules` parses each file once, to find which functions it
+/// defines, but keeps only that index; so at most one unneeded `Module` is in
+/// memory at a time.
+pub struct LazyModules {
+    /// Paths of the bitcode files
+    paths: Vec<PathBuf>,
+
+    /// Map from function name to the index (in `paths`) of the file defining
+    /// it. If several files define the function, the first one is used.
+    definitions: HashMap<String, usize>,
+
+    /// The `Module` parsed from each file, if it has been needed so far
+    modules: Vec<OnceLock<Module>>,
+}
+
+impl LazyModules {
+    /// Index the given bitcode files, without keeping any of them in memory
+    pub fn new(paths: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<Self, String> {
+        let paths: Vec<PathBuf> = paths.into_iter().map(|path| path.as_ref().to_owned()).collect();
+        let mut definitions = HashMap::new();
+        for (index, path) in paths.iter().enumerate() {
+            let module = parse(path)?;
+            for func in &module.functions {
+                definitions.entry(func.name.clone()).or_insert(index);
+            }
+        }
+        Ok(Self {
+            modules: paths.iter().map(|_| OnceLock::new()).collect(),
+            paths,
+            definitions,
+        })
+    }
+
+    /// Get the paths of the bitcode files, in the order given to `new()`
+    pub fn get_paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Get the `Module` defining the function with the given name, parsing it
+    /// if it hasn't been parsed yet, or `None` if no file defines the function
+    pub fn get_defining_module(&self, fn_name: &str) -> Result<Option<&Module>, String> {
+        let index = match self.definitions.get(fn_name) {
+            Some(&index) => index,
+            None => return Ok(None),
+        };
+        if let Some(module) = self.modules[index].get() {
+            return Ok(Some(module));
+        }
+        let module = parse(&self.paths[index])?;
+        Ok(Some(self.modules[index].get_or_init(|| module)))
+    }
+
+    /// Iterate over the `Module`s which have been parsed so far
+    pub fn loaded_modules(&self) -> impl Iterator<Item = &Module> {
+        self.modules.iter().filter_map(|module| module.get())
+    }
+}
+
+fn parse(path: &Path) -> Result<Module, String> {
+    Module::from_bc_path(path).map_err(|e| format!("Failed to parse module {:?}: {}", path, e))
+}
+
+pub(crate) fn analyze_function_lazily<'m>(
+    modules: &'m LazyModules,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> Result<TaintResult<'m>, String> {
+    let start_module = modules
+        .get_defining_module(start_fn_name)?
+        .ok_or_else(|| format!("Failed to find function named {:?} in the given bitcode files", start_fn_name))?;
+    let mut ts = TaintState::new_single_function(std::iter::once(start_module), config, start_fn_name, args, nonargs, named_structs);
+    // functions we've already looked for in the bitcode files
+    let mut looked_up: HashSet<&'m str> = HashSet::new();
+    loop {
+        // A called function gets a summary (and goes on the worklist) when its
+        // call is processed, so loading its module before the next step means
+        // it's never treated as external
+        let mut new_modules: Vec<&'m Module> = Vec::new();
+        for fn_name in ts.get_undefined_functions() {
+            if looked_up.insert(fn_name) {
+                if let Some(module) = modules.get_defining_module(fn_name)? {
+                    if !new_modules.iter().any(|&m| std::ptr::eq(m, module)) {
+                        new_modules.push(module);
+                    }
+                }
+            }
+        }
+        if !new_modules.is_empty() {
+            ts.add_modules(new_modules, false);
+        }
+        if !ts.step() {
+            break;
+        }
+    }
+    Ok(ts.into_taint_result())
+}
@@ -1,23 +1,76 @@
+mod allocators;
+mod annotated_ir;
+mod annotations;
+mod attributes;
+mod audit;
+mod call_graph_export;
+mod cfg_dot;
+mod checkpoint;
+mod clones;
 pub mod config;
+mod debug_info;
+mod demangle;
+mod dependencies;
+mod entries;
+mod format_functions;
 mod function_summary;
 mod function_taint_state;
 mod globals;
+mod gpu_functions;
+mod instrumentation;
+mod jni_functions;
+mod jump_functions;
+mod kernel_functions;
+mod labels;
+mod lazy;
 mod modules;
 mod named_structs;
+mod operands;
+mod output_filter;
 mod pointee;
+mod provenance;
+mod sarif;
+mod spectre;
+mod stack_slots;
+mod summary_store;
+mod table;
+mod taint_blind;
+mod taint_degree;
+mod taint_paths;
 mod taint_result;
 mod taint_state;
 mod tainted_type;
+mod trace;
+mod transfer;
+mod wasi_functions;
+mod what_if;
 mod worklist;
 
+pub use audit::LibraryAuditResult;
+pub use checkpoint::Checkpoint;
 pub use config::Config;
-pub use tainted_type::TaintedType;
+pub use debug_info::DebugInfo;
+pub use demangle::demangle;
+pub use function_summary::FunctionSummary;
+pub use instrumentation::InstrumentationGuidance;
+pub use labels::{LabeledTaintResult, TaintLabel};
+pub use lazy::LazyModules;
+pub use named_structs::NamedStructInitialDef;
+pub use output_filter::OutputFilter;
 pub use pointee::Pointee;
+pub use provenance::{ExplanationStep, ExplanationStepKind};
+pub use spectre::SpectreGadget;
+pub use summary_store::SummaryStore;
+pub use table::TableFormat;
+pub use taint_degree::{TaintDegreeResult, TaintSource};
+pub use taint_paths::{TaintFlow, TaintPathStep};
 pub use taint_result::TaintResult;
-pub use named_structs::NamedStructInitialDef;
+pub use taint_state::TaintState;
+pub use tainted_type::{FnTargets, TaintedType};
+pub use transfer::TransferSummary;
+pub use what_if::WhatIfAnalysis;
 
 use llvm_ir::{Module, Name};
-use taint_state::TaintState;
 use std::collections::HashMap;
 
 /// The main function in this module. Given an LLVM module or modules and the
@@ -45,6 +98,29 @@ pub fn do_taint_analysis_on_function<'m>(
         .into_taint_result()
 }
 
+/// Like `do_taint_analysis_on_function`, but rather than taking all the
+/// `Module`s up front, parses each of the given bitcode files only once a
+/// function defined in it is called. This keeps memory manageable when
+/// analyzing a program along with many libraries, most of which it hardly
+/// uses.
+///
+/// Only the `Module`s parsed so far are considered as targets of function
+/// pointers, and named structs in `named_structs` must be defined in the
+/// `Module` defining the start function.
+///
+/// Returns an error if one of the bitcode files can't be parsed, or none of
+/// them defines the start function.
+pub fn do_taint_analysis_on_function_lazily<'m>(
+    modules: &'m LazyModules,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> Result<TaintResult<'m>, String> {
+    lazy::analyze_function_lazily(modules, config, start_fn_name, args, nonargs, named_structs)
+}
+
 /// Like `do_taint_analysis_on_function`, but analyzes all functions in the
 /// `Module`, rather than only a start function and the functions it calls.
 ///
@@ -58,6 +134,10 @@ pub fn do_taint_analysis_on_function<'m>(
 /// set some variable in the middle of some function to tainted. All variables
 /// not specified this way will simply be inferred normally from the argument
 /// `TaintedType`s.
+///
+/// If `Config::per_entry_results` is set, each function in `args` or
+/// `nonargs` is also analyzed separately as an entry point, and the results
+/// for each are available through `TaintResult::get_entry_result()`.
 pub fn do_taint_analysis_on_module<'m>(
     modules: impl IntoIterator<Item = &'m Module>,
     config: &'m Config,
@@ -65,6 +145,134 @@ pub fn do_taint_analysis_on_module<'m>(
     nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
     named_structs: HashMap<String, NamedStructInitialDef>,
 ) -> TaintResult<'m> {
+    if config.per_entry_results {
+        return entries::analyze_module(modules, config, args, nonargs, named_structs);
+    }
     TaintState::do_analysis_multiple_functions(modules, config, args, nonargs, named_structs)
         .into_taint_result()
 }
+
+/// Like `do_taint_analysis_on_function`, but rather than each source being
+/// simply tainted or untainted, each has a label from a user-defined lattice
+/// (see `TaintLabel`). Each argument, nonargument, and named struct definition
+/// is given with its label; data with label `L::bottom()` is untainted,
+/// regardless of its `TaintedType`. The label of each value in the result is
+/// the join of the labels of all the sources whose data may flow to it.
+///
+/// The functions configured to return tainted data, or annotated as sources,
+/// are sources too, with label `TaintLabel::ext_source()`.
+///
+/// This runs the analysis once per distinct label given (other than bottom),
+/// and once more for `TaintLabel::ext_source()` unless it is bottom or among
+/// them.
+pub fn do_labeled_taint_analysis_on_function<'m, L: TaintLabel>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<(TaintedType, L)>>,
+    nonargs: HashMap<Name, (TaintedType, L)>,
+    named_structs: HashMap<String, (NamedStructInitialDef, L)>,
+) -> LabeledTaintResult<'m, L> {
+    labels::analyze_function(modules, config, start_fn_name, args, nonargs, named_structs)
+}
+
+/// Like `do_labeled_taint_analysis_on_function`, but analyzes all functions in
+/// the `Module`, like `do_taint_analysis_on_module`.
+pub fn do_labeled_taint_analysis_on_module<'m, L: TaintLabel>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    args: HashMap<&'m str, Vec<(TaintedType, L)>>,
+    nonargs: HashMap<&'m str, HashMap<Name, (TaintedType, L)>>,
+    named_structs: HashMap<String, (NamedStructInitialDef, L)>,
+) -> LabeledTaintResult<'m, L> {
+    labels::analyze_module(modules, config, args, nonargs, named_structs)
+}
+
+/// Like `do_taint_analysis_on_function`, but also computes the _taint degree_
+/// of each value: the number of distinct sources whose data may flow to it.
+/// Each argument which isn't entirely untainted, each nonargument, and each
+/// named struct definition other than `AllFieldsUntainted` is a separate
+/// source, and the functions configured to return tainted data (or annotated
+/// as sources) are one more, `TaintSource::ExtFunctions`. This is useful for
+/// prioritizing results: for instance, a value influenced by one
+/// attacker-controlled byte vs. by the whole input.
+///
+/// This runs the analysis once per source.
+pub fn do_taint_degree_analysis_on_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> TaintDegreeResult<'m> {
+    taint_degree::analyze_function(modules, config, start_fn_name, args, nonargs, named_structs)
+}
+
+/// Like `do_taint_degree_analysis_on_function`, but analyzes all functions in
+/// the `Module`, like `do_taint_analysis_on_module`.
+pub fn do_taint_degree_analysis_on_module<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    args: HashMap<&'m str, Vec<TaintedType>>,
+    nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> TaintDegreeResult<'m> {
+    taint_degree::analyze_module(modules, config, args, nonargs, named_structs)
+}
+
+/// Like `do_taint_degree_analysis_on_function`, but produces a
+/// `WhatIfAnalysis`, which can cheaply re-evaluate the results with any of the
+/// sources disabled, or with validators added to the `Config`, for
+/// experimenting with configurations without re-running the whole analysis
+/// each time.
+///
+/// This runs the analysis once per source.
+pub fn do_what_if_analysis_on_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> WhatIfAnalysis<'m> {
+    what_if::analyze_function(modules, config, start_fn_name, args, nonargs, named_structs)
+}
+
+/// Analyze a single function in isolation, treating the taint of each of its
+/// parameters symbolically, and produce its `TransferSummary`: which
+/// parameters' taint flows to its return value, and to the data reachable
+/// through each of its pointer parameters. This is useful, e.g., for auditing
+/// the individual functions of a library's API, without any of the programs
+/// which use it.
+///
+/// Functions called by the given function are analyzed as usual if they're
+/// defined in the given module(s), and handled according to
+/// `Config::ext_functions` if not.
+///
+/// This runs the analysis once with all parameters untainted, and once per
+/// parameter with just that parameter tainted.
+pub fn do_isolated_analysis_on_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    fn_name: &str,
+) -> TransferSummary {
+    transfer::analyze_function(modules, config, fn_name)
+}
+
+/// Audit the attack surface of a library: treat every exported function (i.e.,
+/// every function defined with external linkage and non-hidden visibility) as
+/// an entry point whose arguments, and everything reachable through them, are
+/// entirely tainted, and report which sinks (see `Config`), globals, and named
+/// struct fields become tainted.
+///
+/// Named structs passed to the exported functions aren't tainted themselves,
+/// since the taint of a named struct is shared by the whole program; to
+/// consider them attacker-controlled too, use `do_taint_analysis_on_module()`
+/// with the appropriate `NamedStructInitialDef`s.
+pub fn do_library_audit<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+) -> LibraryAuditResult<'m> {
+    audit::audit_library(modules, config)
+}
@@ -1,6 +1,12 @@
-use llvm_ir::{Function, Module};
+use crate::annotations;
+use crate::demangle;
+use either::Either;
+use itertools::Itertools;
+use llvm_ir::{instruction, Constant, ConstantRef, Function, Instruction, Module, Name, Operand, Terminator, Type, TypeRef};
+use llvm_ir::function::{CallingConvention, FunctionAttribute, ParameterAttribute};
 use llvm_ir::types::NamedStructDef;
-use std::iter::FromIterator;
+use std::collections::{HashMap, HashSet};
+use std::iter::{Extend, FromIterator};
 
 /// Stores references to all of the `Module`(s) which we're working with
 pub struct Modules<'m> {
@@ -19,8 +25,425 @@ impl<'m> Modules<'m> {
     // This function mostly lifted from `haybale`'s project.rs
     pub fn all_functions<'s>(&'s self) -> impl Iterator<Item = (&'m Function, &'m Module)> + 's {
         self.iter()
-            .map(|m| m.functions.iter().zip(std::iter::repeat(m)))
-            .flatten()
+            .flat_map(|m| m.functions.iter().zip(std::iter::repeat(m)))
+    }
+
+    /// Iterate over the names of all functions called directly (i.e., not
+    /// through a function pointer) from the `Modules`. This includes external
+    /// functions, which have no definition in any of the `Modules`.
+    ///
+    /// May contain duplicates.
+    pub fn all_direct_callees<'s>(&'s self) -> impl Iterator<Item = &'m str> + 's {
+        self.all_direct_callee_refs().map(|(name, _)| name)
+    }
+
+    /// Iterate over the `Module`(s) which call the function with the given
+    /// name directly, in order
+    pub fn modules_calling<'s>(&'s self, fn_name: &'s str) -> impl Iterator<Item = &'m Module> + 's {
+        self.iter().filter(move |module| {
+            std::iter::once(*module)
+                .collect::<Modules<'m>>()
+                .all_direct_callees()
+                .any(|name| name == fn_name)
+        })
+    }
+
+    /// Get the type (a `FuncType`) of the function with the given name, as
+    /// declared in one of the `Modules` which calls it directly. This works
+    /// for external functions too, which have no definition in any of the
+    /// `Modules`.
+    ///
+    /// Returns `None` if no function in the `Modules` calls the function
+    /// directly.
+    pub fn declared_type_of_callee(&self, fn_name: &str) -> Option<TypeRef> {
+        self.all_direct_callee_refs()
+            .find(|(name, _)| *name == fn_name)
+            .map(|(_, ty)| ty.clone())
+    }
+
+    /// Iterate over the function and return attributes at each direct call of
+    /// the function with the given name, in any of the `Modules`.
+    ///
+    /// For external functions, this is all we know of their attributes, as
+    /// `llvm-ir` doesn't give us the attributes of function declarations.
+    pub fn call_site_attributes<'s>(&'s self, fn_name: &'s str) -> impl Iterator<Item = (&'m [FunctionAttribute], &'m [ParameterAttribute])> + 's {
+        self.all_direct_calls()
+            .filter(move |call| call.name == fn_name)
+            .map(|call| (call.function_attributes, call.return_attributes))
+    }
+
+    /// Like `all_direct_callees()`, but also gives the type of each callee (as
+    /// referred to at the call)
+    fn all_direct_callee_refs<'s>(&'s self) -> impl Iterator<Item = (&'m str, &'m TypeRef)> + 's {
+        self.all_direct_calls().map(|call| (call.name, call.ty))
+    }
+
+    /// Iterate over all direct calls (and invokes) in the `Modules`
+    fn all_direct_calls<'s>(&'s self) -> impl Iterator<Item = DirectCall<'m>> + 's {
+        self.all_functions()
+            .flat_map(|(f, _)| f.basic_blocks.iter())
+            .flat_map(|bb| {
+                let calls = bb.instrs.iter().filter_map(|inst| match inst {
+                    Instruction::Call(call) => Some((&call.function, &call.function_attributes, &call.return_attributes)),
+                    _ => None,
+                });
+                let invoke = match &bb.term {
+                    Terminator::Invoke(invoke) => Some((&invoke.function, &invoke.function_attributes, &invoke.return_attributes)),
+                    _ => None,
+                };
+                calls.chain(invoke)
+            })
+            .filter_map(|(target, function_attributes, return_attributes)| match target {
+                Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+                    Constant::GlobalReference { name: Name::Name(name), ty } => Some(DirectCall {
+                        name: name.as_str(),
+                        ty,
+                        function_attributes,
+                        return_attributes,
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+
+    /// Get the names of the static constructors and destructors of the
+    /// `Modules`: the functions in their `llvm.global_ctors` arrays, in order of
+    /// priority (and then in the order the `Modules` are in), followed by the
+    /// functions in their `llvm.global_dtors` arrays, likewise
+    pub fn static_initializers(&self) -> Vec<&'m str> {
+        let mut fn_names = Vec::new();
+        for array_name in &["llvm.global_ctors", "llvm.global_dtors"] {
+            let mut entries: Vec<(u64, &'m str)> = Vec::new();
+            for module in self.iter() {
+                let elements = module.global_vars
+                    .iter()
+                    .find(|var| var.name == Name::from(*array_name))
+                    .and_then(|var| var.initializer.as_ref())
+                    .map(|init| match init.as_ref() {
+                        Constant::Array { elements, .. } => elements.as_slice(),
+                        _ => &[],
+                    })
+                    .unwrap_or_default();
+                // each entry is a struct of the priority, the function, and
+                // (optionally) the data it initializes
+                for element in elements {
+                    if let Constant::Struct { values, .. } = element.as_ref() {
+                        let priority = match values.first().map(|value| value.as_ref()) {
+                            Some(Constant::Int { value, .. }) => *value,
+                            _ => continue,
+                        };
+                        if let Some(Constant::GlobalReference { name: Name::Name(name), .. }) = values.get(1).map(|value| annotations::strip_casts(value)) {
+                            entries.push((priority, name.as_str()));
+                        }
+                    }
+                }
+            }
+            // stable, so entries with the same priority stay in order
+            entries.sort_by_key(|(priority, _)| *priority);
+            fn_names.extend(entries.into_iter().map(|(_, name)| name));
+        }
+        fn_names
+    }
+
+    /// Get the names of the functions the `Modules` register as signal
+    /// handlers, in the order the registrations appear, without duplicates:
+    ///   - the handler argument of each direct call of `signal` (or its
+    ///     `bsd_signal` and `sysv_signal` variants)
+    ///   - for each function which calls `sigaction`, every function whose
+    ///     address it stores, since the handler is passed in the
+    ///     `sa_handler` or `sa_sigaction` field of a `struct sigaction`
+    ///
+    /// This may include functions which aren't defined in the `Modules`.
+    pub fn signal_handlers(&self) -> Vec<&'m str> {
+        let function_name = |op: &'m Operand| match op {
+            Operand::ConstantOperand(cref) => match annotations::strip_casts(cref.as_ref()) {
+                Constant::GlobalReference { name: Name::Name(name), ty } if matches!(ty.as_ref(), Type::FuncType { .. }) => Some(name.as_str()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let callee_name = |call: &'m instruction::Call| match &call.function {
+            Either::Right(op) => function_name(op),
+            Either::Left(_) => None,
+        };
+        let mut fn_names: Vec<&'m str> = Vec::new();
+        for (f, _) in self.all_functions() {
+            let calls_sigaction = f.basic_blocks
+                .iter()
+                .flat_map(|bb| bb.instrs.iter())
+                .any(|inst| matches!(inst, Instruction::Call(call) if callee_name(call) == Some("sigaction")));
+            for inst in f.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()) {
+                let handler = match inst {
+                    Instruction::Call(call) => match callee_name(call) {
+                        Some("signal") | Some("bsd_signal") | Some("sysv_signal") | Some("__sysv_signal") => call.arguments.get(1).and_then(|(op, _)| function_name(op)),
+                        _ => None,
+                    },
+                    Instruction::Store(store) if calls_sigaction => function_name(&store.value),
+                    _ => None,
+                };
+                if let Some(handler) = handler {
+                    if !fn_names.contains(&handler) {
+                        fn_names.push(handler);
+                    }
+                }
+            }
+        }
+        fn_names
+    }
+
+    /// Get the names of the GPU kernels defined in the `Modules`: the
+    /// functions with the `ptx_kernel`, `amdgpu_kernel` or `spir_kernel`
+    /// calling conventions, in the order the `Modules` and their functions
+    /// are in
+    pub fn gpu_kernels(&self) -> Vec<&'m str> {
+        self.all_functions()
+            .filter(|(f, _)| matches!(
+                f.calling_convention,
+                CallingConvention::PTX_Kernel | CallingConvention::AMDGPU_Kernel | CallingConvention::SPIR_KERNEL
+            ))
+            .map(|(f, _)| f.name.as_str())
+            .collect()
+    }
+
+    /// Get the functions in each slot of the C++ vtables in the `Modules`
+    /// (the globals whose Itanium-mangled names start with `_ZTV`), keyed by
+    /// the (demangled) name of the class and the slot's index from the
+    /// vtable's address point, which is where objects' vtable pointers point.
+    /// The address point of each of the arrays making up a vtable (one for
+    /// each base with its own vtable pointer) is its first function entry,
+    /// after the offsets and the RTTI pointer.
+    ///
+    /// A pointer to a class may point to an object of any class derived from
+    /// it, so each class's slots also include those of the classes derived
+    /// from it, according to their RTTI (the `_ZTI` globals).
+    pub fn vtable_slots(&self) -> HashMap<(String, u64), HashSet<&'m str>> {
+        // the slots of each class's own vtable, and the direct bases of each
+        // class
+        let mut own_slots: HashMap<String, HashMap<u64, HashSet<&'m str>>> = HashMap::new();
+        let mut bases: HashMap<String, Vec<String>> = HashMap::new();
+        for var in self.iter().flat_map(|module| module.global_vars.iter()) {
+            let (name, init) = match (&var.name, var.initializer.as_ref()) {
+                (Name::Name(name), Some(init)) => (name.as_str(), init.as_ref()),
+                _ => continue,
+            };
+            let class = match demangle::class_of_vtable_or_rtti(name) {
+                Some(class) => class,
+                None => continue,
+            };
+            if name.starts_with("_ZTI") {
+                // after the type_info's own vtable pointer and name come the
+                // pointers to the RTTI of the bases, if any
+                if let Constant::Struct { values, .. } = init {
+                    let direct_bases = values
+                        .iter()
+                        .skip(2)
+                        .filter_map(|value| match annotations::strip_casts(value) {
+                            Constant::GlobalReference { name: Name::Name(base), .. } => demangle::class_of_vtable_or_rtti(base),
+                            _ => None,
+                        })
+                        .collect();
+                    bases.insert(class, direct_bases);
+                }
+                continue;
+            }
+            let arrays: Vec<&'m [ConstantRef]> = match init {
+                Constant::Array { elements, .. } => vec![elements.as_slice()],
+                Constant::Struct { values, .. } => values
+                    .iter()
+                    .filter_map(|value| match value.as_ref() {
+                        Constant::Array { elements, .. } => Some(elements.as_slice()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => continue,
+            };
+            let class_slots = own_slots.entry(class).or_default();
+            for elements in arrays {
+                let entries: Vec<Option<&'m str>> = elements
+                    .iter()
+                    .map(|element| match annotations::strip_casts(element) {
+                        Constant::GlobalReference { name: Name::Name(name), ty } if matches!(ty.as_ref(), Type::FuncType { .. }) => Some(name.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                let address_point = match entries.iter().position(Option::is_some) {
+                    Some(address_point) => address_point,
+                    None => continue,
+                };
+                for (slot, entry) in entries[address_point ..].iter().enumerate() {
+                    if let Some(fn_name) = entry {
+                        class_slots.entry(slot as u64).or_default().insert(*fn_name);
+                    }
+                }
+            }
+        }
+        let mut slots: HashMap<(String, u64), HashSet<&'m str>> = HashMap::new();
+        for (class, class_slots) in own_slots {
+            // the class and all its (transitive) bases
+            let mut ancestors: Vec<String> = vec![class];
+            let mut i = 0;
+            while i < ancestors.len() {
+                for base in bases.get(&ancestors[i]).into_iter().flatten() {
+                    if !ancestors.contains(base) {
+                        ancestors.push(base.clone());
+                    }
+                }
+                i += 1;
+            }
+            for ancestor in ancestors {
+                for (slot, fn_names) in &class_slots {
+                    slots.entry((ancestor.clone(), *slot)).or_default().extend(fn_names.iter().copied());
+                }
+            }
+        }
+        slots
+    }
+
+    /// Get the functions stored into each field of each named struct in the
+    /// `Modules`, keyed by (struct name, field index), as for the captured
+    /// function pointers of closures and `std::function`s. Only the stores
+    /// to a `getelementptr` of the field are seen, and the initializers of
+    /// globals of the struct's type.
+    ///
+    /// A field maps to `None` if a value other than a constant function (or
+    /// null) is stored to it, in which case we don't know what it may hold.
+    pub fn functions_in_fields(&self) -> HashMap<(String, u64), Option<HashSet<&'m str>>> {
+        let function_name = |constant: &'m Constant| match annotations::strip_casts(constant) {
+            Constant::GlobalReference { name: Name::Name(name), ty } if matches!(ty.as_ref(), Type::FuncType { .. }) => Some(Some(name.as_str())),
+            Constant::Null(_) => Some(None),
+            _ => None,
+        };
+        let mut fields: HashMap<(String, u64), Option<HashSet<&'m str>>> = HashMap::new();
+        let mut record = |field: (String, u64), stored: Option<Option<&'m str>>| {
+            let entry = fields.entry(field).or_insert_with(|| Some(HashSet::new()));
+            match (entry.as_mut(), stored) {
+                (Some(fn_names), Some(Some(fn_name))) => {
+                    fn_names.insert(fn_name);
+                },
+                (_, Some(None)) => {}, // null
+                _ => *entry = None,
+            }
+        };
+        for (f, module) in self.all_functions() {
+            let geps: HashMap<&Name, &instruction::GetElementPtr> = f.basic_blocks
+                .iter()
+                .flat_map(|bb| bb.instrs.iter())
+                .filter_map(|inst| match inst {
+                    Instruction::GetElementPtr(gep) => Some((&gep.dest, gep)),
+                    _ => None,
+                })
+                .collect();
+            for inst in f.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()) {
+                let store = match inst {
+                    Instruction::Store(store) => store,
+                    _ => continue,
+                };
+                let field = match &store.address {
+                    Operand::LocalOperand { name, .. } => geps.get(name).and_then(|gep| struct_field(module, gep)),
+                    _ => None,
+                };
+                if let Some(field) = field {
+                    let stored = match &store.value {
+                        Operand::ConstantOperand(cref) => function_name(cref),
+                        _ => None,
+                    };
+                    // only function pointers (maybe cast) concern us
+                    let is_fn_ptr = matches!(module.type_of(&store.value).as_ref(), Type::PointerType { pointee_type, .. } if matches!(pointee_type.as_ref(), Type::FuncType { .. }));
+                    if stored.is_some() || is_fn_ptr {
+                        record(field, stored);
+                    }
+                }
+            }
+        }
+        for var in self.iter().flat_map(|module| module.global_vars.iter()) {
+            if let Some(Constant::Struct { name: Some(struct_name), values, .. }) = var.initializer.as_ref().map(|init| init.as_ref()) {
+                for (index, value) in values.iter().enumerate() {
+                    if let Some(Some(fn_name)) = function_name(value) {
+                        record((struct_name.clone(), index as u64), Some(Some(fn_name)));
+                    }
+                }
+            }
+        }
+        fields
+    }
+
+    /// Get the global tables of function pointers in the `Modules` (arrays,
+    /// possibly nested, whose elements are function pointers, e.g.
+    /// `static handler_t handlers[] = {...}`), as (global name, LLVM type of
+    /// the array, names of the functions in its initializer). Tables whose
+    /// initializers have an element other than a constant function (maybe
+    /// cast) or null are left out, as are tables with no functions at all.
+    pub fn fn_ptr_tables(&self) -> Vec<(&'m Name, &'m Type, HashSet<&'m str>)> {
+        fn collect<'m>(constant: &'m Constant, fn_names: &mut HashSet<&'m str>) -> bool {
+            match annotations::strip_casts(constant) {
+                Constant::Array { elements, .. } => elements.iter().all(|element| collect(element, fn_names)),
+                Constant::GlobalReference { name: Name::Name(name), ty } if matches!(ty.as_ref(), Type::FuncType { .. }) => {
+                    fn_names.insert(name.as_str());
+                    true
+                },
+                Constant::Null(_) => true,
+                _ => false,
+            }
+        }
+        fn is_table(ty: &Type) -> bool {
+            match ty {
+                Type::ArrayType { element_type, .. } => is_table(element_type),
+                Type::PointerType { pointee_type, .. } => matches!(pointee_type.as_ref(), Type::FuncType { .. }),
+                _ => false,
+            }
+        }
+        let mut tables = Vec::new();
+        for var in self.iter().flat_map(|module| module.global_vars.iter()) {
+            let array_ty = match var.ty.as_ref() {
+                Type::PointerType { pointee_type, .. } if matches!(pointee_type.as_ref(), Type::ArrayType { .. }) && is_table(pointee_type) => pointee_type.as_ref(),
+                _ => continue,
+            };
+            let initializer = match &var.initializer {
+                Some(initializer) => initializer,
+                None => continue,
+            };
+            let mut fn_names = HashSet::new();
+            if collect(initializer, &mut fn_names) && !fn_names.is_empty() {
+                tables.push((&var.name, array_ty, fn_names));
+            }
+        }
+        tables
+    }
+
+    /// Get the named structs which LLVM renamed when linking (e.g.,
+    /// `struct.foo.123`), each mapped to the name of the struct they're
+    /// equivalent to, as described for `Config::unify_renamed_structs`.
+    /// Structs which aren't equivalent to any other aren't included.
+    pub(crate) fn renamed_struct_aliases(&self) -> HashMap<String, String> {
+        let mut names: Vec<&'m String> = self.iter().flat_map(|module| module.types.all_struct_names()).collect();
+        // the unsuffixed name comes first in its group, then the others in order
+        names.sort_by(|a, b| (base_struct_name(a), a.len(), *a).cmp(&(base_struct_name(b), b.len(), *b)));
+        names.dedup();
+        let mut aliases = HashMap::new();
+        for (_, group) in &names.into_iter().group_by(|name: &&'m String| base_struct_name(name.as_str())) {
+            let mut canonical: Vec<(&'m String, &TypeRef)> = Vec::new();
+            let mut opaque: Vec<&'m String> = Vec::new();
+            for name in group {
+                match self.named_struct_def(name) {
+                    Some((NamedStructDef::Defined(ty), _)) => {
+                        match canonical.iter().find(|(_, canonical_ty)| equivalent_types(ty, canonical_ty)) {
+                            Some((canonical_name, _)) => {
+                                aliases.insert(name.clone(), (*canonical_name).clone());
+                            },
+                            None => canonical.push((name, ty)),
+                        }
+                    },
+                    _ => opaque.push(name),
+                }
+            }
+            if let [(canonical_name, _)] = canonical.as_slice() {
+                for name in opaque {
+                    aliases.insert(name.clone(), (*canonical_name).clone());
+                }
+            }
+        }
+        aliases
     }
 
     /// Get the `NamedStructDef` for a named struct.
@@ -54,6 +477,15 @@ impl<'m> Modules<'m> {
     }
 }
 
+/// A direct call (i.e., not through a function pointer) of the named function
+struct DirectCall<'m> {
+    name: &'m str,
+    /// Type of the callee, as referred to at the call
+    ty: &'m TypeRef,
+    function_attributes: &'m [FunctionAttribute],
+    return_attributes: &'m [ParameterAttribute],
+}
+
 impl<'m> FromIterator<&'m Module> for Modules<'m> {
     fn from_iter<I: IntoIterator<Item = &'m Module>>(iter: I) -> Self {
         Self {
@@ -61,3 +493,73 @@ impl<'m> FromIterator<&'m Module> for Modules<'m> {
         }
     }
 }
+
+impl<'m> Extend<&'m Module> for Modules<'m> {
+    fn extend<I: IntoIterator<Item = &'m Module>>(&mut self, iter: I) {
+        self.modules.extend(iter)
+    }
+}
+
+/// If the given `getelementptr` computes the address of a field of a named
+/// struct (i.e., its indices are `0` and a constant field index), get the
+/// struct's name and the field index
+pub(crate) fn struct_field(module: &Module, gep: &instruction::GetElementPtr) -> Option<(String, u64)> {
+    let constant_index = |op: &Operand| match op {
+        Operand::ConstantOperand(cref) => match cref.as_ref() {
+            Constant::Int { value, .. } => Some(*value),
+            _ => None,
+        },
+        _ => None,
+    };
+    match gep.indices.as_slice() {
+        [first, field] if constant_index(first) == Some(0) => {
+            let field = constant_index(field)?;
+            match module.type_of(&gep.address).as_ref() {
+                Type::PointerType { pointee_type, .. } => match pointee_type.as_ref() {
+                    Type::NamedStructType { name } => Some((name.clone(), field)),
+                    _ => None,
+                },
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// The name of the named struct with the given name, without any `.N`
+/// suffixes LLVM added when renaming it (e.g., `struct.foo` for
+/// `struct.foo.123`)
+fn base_struct_name(name: &str) -> &str {
+    let mut name = name;
+    while let Some((base, suffix)) = name.rsplit_once('.') {
+        if base.is_empty() || suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+            break;
+        }
+        name = base;
+    }
+    name
+}
+
+/// Are the given types structurally the same, taking named structs to be the
+/// same if their base names (see `base_struct_name()`) are?
+fn equivalent_types(a: &Type, b: &Type) -> bool {
+    let all_equivalent = |a: &[TypeRef], b: &[TypeRef]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equivalent_types(a, b));
+    match (a, b) {
+        (Type::NamedStructType { name: a }, Type::NamedStructType { name: b }) => base_struct_name(a) == base_struct_name(b),
+        (Type::PointerType { pointee_type: a, addr_space: a_space }, Type::PointerType { pointee_type: b, addr_space: b_space }) => {
+            a_space == b_space && equivalent_types(a, b)
+        },
+        (Type::ArrayType { element_type: a, num_elements: a_len }, Type::ArrayType { element_type: b, num_elements: b_len })
+        | (Type::VectorType { element_type: a, num_elements: a_len, .. }, Type::VectorType { element_type: b, num_elements: b_len, .. }) => {
+            a_len == b_len && equivalent_types(a, b)
+        },
+        (Type::StructType { element_types: a, is_packed: a_packed }, Type::StructType { element_types: b, is_packed: b_packed }) => {
+            a_packed == b_packed && all_equivalent(a, b)
+        },
+        (
+            Type::FuncType { result_type: a_result, param_types: a_params, is_var_arg: a_var_arg },
+            Type::FuncType { result_type: b_result, param_types: b_params, is_var_arg: b_var_arg },
+        ) => a_var_arg == b_var_arg && equivalent_types(a_result, b_result) && all_equivalent(a_params, b_params),
+        (a, b) => a == b,
+    }
+}
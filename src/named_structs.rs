@@ -1,8 +1,12 @@
 use crate::modules::Modules;
+use crate::pointee::Pointee;
 use crate::tainted_type::TaintedType;
-use llvm_ir::{Constant, ConstantRef, Operand};
+use llvm_ir::{Constant, ConstantRef, Module, Operand, Type};
 use llvm_ir::types::NamedStructDef;
+#[cfg(not(feature = "tracing"))]
 use log::warn;
+#[cfg(feature = "tracing")]
+use tracing::warn;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
@@ -25,6 +29,22 @@ pub struct NamedStructs<'m> {
     /// types in those functions.
     named_struct_users: HashMap<String, HashSet<&'m str>>,
 
+    /// See `Config::max_pointer_depth`
+    max_pointer_depth: Option<usize>,
+
+    /// See `Config::lenient_joins`
+    lenient_joins: bool,
+
+    /// Map from an LLVM type (as text) to the deep pointee shared by all data
+    /// of that type which is nested beyond `max_pointer_depth`. See
+    /// `type_from_llvm()`.
+    deep_pointees: HashMap<String, Pointee>,
+
+    /// Map from the name of a named struct which LLVM renamed when linking
+    /// (e.g., `struct.foo.123`), to the name of the equivalent struct whose
+    /// definition it shares. Empty unless `Config::unify_renamed_structs`.
+    struct_aliases: HashMap<String, String>,
+
     /// The `Modules` being analyzed
     modules: Modules<'m>,
 }
@@ -32,6 +52,7 @@ pub struct NamedStructs<'m> {
 /// Describes the initial definition (taint state) of a named struct.
 /// It may always become more tainted than this initial state during
 /// taint-tracking, but never less.
+#[derive(Clone)]
 pub enum NamedStructInitialDef {
     /// All fields of this named struct begin untainted. This is the default
     /// for all named structs unless otherwise specified.
@@ -45,35 +66,49 @@ pub enum NamedStructInitialDef {
 }
 
 #[derive(Clone)]
-pub(crate) struct TaintedNamedStructs(HashSet<String>);
+pub(crate) struct TaintedNamedStructs {
+    structs: HashSet<String>,
+
+    /// Set whenever a deep pointee (see `NamedStructs::type_from_llvm()`) is
+    /// tainted or updated, until it's cleared with
+    /// `NamedStructs::take_deep_pointees_changed()`
+    pub(crate) deep_pointees_changed: bool,
+}
 
 impl TaintedNamedStructs {
+    fn new(structs: HashSet<String>) -> Self {
+        Self { structs, deep_pointees_changed: false }
+    }
+
     fn insert(&mut self, struct_name: String) {
-        self.0.insert(struct_name);
+        self.structs.insert(struct_name);
     }
 
     fn contains(&self, struct_name: impl AsRef<str>) -> bool {
-        self.0.contains(struct_name.as_ref())
+        self.structs.contains(struct_name.as_ref())
     }
 
     fn remove(&mut self, struct_name: impl AsRef<str>) {
-        self.0.remove(struct_name.as_ref());
+        self.structs.remove(struct_name.as_ref());
     }
 
     /// Convert this (tainted or untainted) type to the equivalent tainted type.
     ///
     /// This may have side effects, such as permanently marking struct fields or
     /// pointees as tainted.
+    // takes `&mut self` for the side effects
+    #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_tainted(&mut self, ty: &TaintedType) -> TaintedType {
         match ty {
             TaintedType::UntaintedValue => TaintedType::TaintedValue,
             TaintedType::TaintedValue => TaintedType::TaintedValue,
             TaintedType::UntaintedPointer(pointee) => TaintedType::TaintedPointer(pointee.clone()),
             TaintedType::TaintedPointer(pointee) => TaintedType::TaintedPointer(pointee.clone()),
-            TaintedType::UntaintedFnPtr => TaintedType::TaintedFnPtr,
-            TaintedType::TaintedFnPtr => TaintedType::TaintedFnPtr,
+            TaintedType::UntaintedFnPtr(targets) => TaintedType::TaintedFnPtr(targets.clone()),
+            TaintedType::TaintedFnPtr(targets) => TaintedType::TaintedFnPtr(targets.clone()),
+            TaintedType::Token => TaintedType::Token,
             TaintedType::NamedStruct(name) => {
-                self.0.insert(name.clone());
+                self.structs.insert(name.clone());
                 TaintedType::NamedStruct(name.clone())
             }
             TaintedType::ArrayOrVector(pointee) => {
@@ -96,8 +131,12 @@ impl<'m> NamedStructs<'m> {
     pub fn new(modules: Modules<'m>) -> Self {
         Self {
             named_struct_types: HashMap::new(),
-            tainted_named_structs: TaintedNamedStructs(HashSet::new()),
+            tainted_named_structs: TaintedNamedStructs::new(HashSet::new()),
             named_struct_users: HashMap::new(),
+            max_pointer_depth: None,
+            lenient_joins: false,
+            deep_pointees: HashMap::new(),
+            struct_aliases: HashMap::new(),
             modules,
         }
     }
@@ -108,7 +147,7 @@ impl<'m> NamedStructs<'m> {
     pub fn with_initial_defs(modules: Modules<'m>, defs: HashMap<String, NamedStructInitialDef>) -> Self {
         use NamedStructInitialDef::*;
         let mut named_struct_types: HashMap<String, TaintedType> = HashMap::new();
-        let mut tainted_named_structs = TaintedNamedStructs(HashSet::new());
+        let mut tainted_named_structs = TaintedNamedStructs::new(HashSet::new());
         for (structname, initialdef) in defs.into_iter() {
             match (initialdef, modules.named_struct_def(&structname)) {
                 (_, None) => panic!("Struct name {:?} not found in the Module(s)", structname),
@@ -129,7 +168,7 @@ impl<'m> NamedStructs<'m> {
                             named_struct_types.insert(structname, structty);
                         },
                         NamedStructDef::Defined(ty) => {
-                            match structty.join(&TaintedType::from_llvm_type(&ty)) {
+                            match structty.join(&TaintedType::from_llvm_type(ty)) {
                                 Ok(_) => {
                                     named_struct_types.insert(structname, structty);
                                 },
@@ -144,23 +183,258 @@ impl<'m> NamedStructs<'m> {
             named_struct_types,
             tainted_named_structs,
             named_struct_users: HashMap::new(),
+            max_pointer_depth: None,
+            lenient_joins: false,
+            deep_pointees: HashMap::new(),
+            struct_aliases: HashMap::new(),
             modules,
         }
     }
 
     /// Iterate over all the (struct name, `TaintedType`) pairs
-    pub(crate) fn all_named_struct_types<'s>(&'s self) -> impl Iterator<Item = (&'s String, &'s TaintedType)> {
+    pub(crate) fn all_named_struct_types(&self) -> impl Iterator<Item = (&String, &TaintedType)> {
         self.named_struct_types.iter()
     }
 
+    /// Iterate over the names of the named structs which are considered to have
+    /// all their fields tainted
+    pub(crate) fn all_tainted_named_structs(&self) -> impl Iterator<Item = &String> {
+        self.tainted_named_structs.structs.iter()
+    }
+
+    /// Iterate over (named struct name, names of functions using it) pairs
+    pub(crate) fn all_named_struct_users<'s>(&'s self) -> impl Iterator<Item = (&'s String, &'s HashSet<&'m str>)> {
+        self.named_struct_users.iter()
+    }
+
+    /// Iterate over (LLVM type, deep pointee) pairs; see `type_from_llvm()`
+    pub(crate) fn all_deep_pointees(&self) -> impl Iterator<Item = (&String, &Pointee)> {
+        self.deep_pointees.iter()
+    }
+
+    /// Construct a `NamedStructs` directly from its contents, e.g. when
+    /// restoring a checkpoint
+    pub(crate) fn from_parts(
+        modules: Modules<'m>,
+        named_struct_types: HashMap<String, TaintedType>,
+        tainted_named_structs: HashSet<String>,
+        named_struct_users: HashMap<String, HashSet<&'m str>>,
+        deep_pointees: HashMap<String, Pointee>,
+    ) -> Self {
+        Self {
+            named_struct_types,
+            tainted_named_structs: TaintedNamedStructs::new(tainted_named_structs),
+            named_struct_users,
+            max_pointer_depth: None,
+            lenient_joins: false,
+            deep_pointees,
+            struct_aliases: HashMap::new(),
+            modules,
+        }
+    }
+
+    /// Add more `Module`s, e.g. ones added to an analysis already in
+    /// progress, so that their named struct definitions can be found
+    pub(crate) fn add_modules(&mut self, modules: impl IntoIterator<Item = &'m Module>) {
+        self.modules.extend(modules)
+    }
+
+    /// Set the depth beyond which `type_from_llvm()` summarizes nested data;
+    /// see `Config::max_pointer_depth`
+    pub(crate) fn set_max_pointer_depth(&mut self, max_pointer_depth: Option<usize>) {
+        self.max_pointer_depth = max_pointer_depth;
+    }
+
+    /// Set whether `join()` joins mismatched types conservatively rather than
+    /// failing; see `Config::lenient_joins`
+    pub(crate) fn set_lenient_joins(&mut self, lenient_joins: bool) {
+        self.lenient_joins = lenient_joins;
+    }
+
+    /// Does `join()` join mismatched types conservatively (see
+    /// `Config::lenient_joins`)?
+    pub(crate) fn lenient_joins(&self) -> bool {
+        self.lenient_joins
+    }
+
+    /// Join the given types, like `TaintedType::join()`; but if their shapes
+    /// don't match and `Config::lenient_joins` is set, get a conservative
+    /// join (see `join_mismatched()`) rather than an error.
+    ///
+    /// Marks the current function (whose name is provided as an argument) as a
+    /// user of any named struct whose taint this checks.
+    pub(crate) fn join(&mut self, ty1: &TaintedType, ty2: &TaintedType, cur_fn: &'m str) -> Result<TaintedType, String> {
+        match ty1.join(ty2) {
+            Err(_) if self.lenient_joins => Ok(self.join_mismatched(ty1, ty2, cur_fn)),
+            result => result,
+        }
+    }
+
+    /// Conservatively join the given types, whose shapes don't match.
+    ///
+    /// A value (e.g., a `null` or a constant integer cast to a pointer) joined
+    /// with a pointer gives the pointer, tainted if the value is. Anything
+    /// else keeps the shape of `ty1`, the type we already had (as derived
+    /// from its LLVM type), and is tainted if `ty2` is tainted at all; if both
+    /// are pointers, the data `ty1` points to is also tainted if the data
+    /// `ty2` points to is.
+    pub(crate) fn join_mismatched(&mut self, ty1: &TaintedType, ty2: &TaintedType, cur_fn: &'m str) -> TaintedType {
+        use TaintedType::*;
+        let is_pointer = |ty: &TaintedType| matches!(ty, UntaintedPointer(_) | TaintedPointer(_) | UntaintedFnPtr(_) | TaintedFnPtr(_));
+        match (ty1, ty2) {
+            (UntaintedValue, ptr) | (ptr, UntaintedValue) if is_pointer(ptr) => ptr.clone(),
+            (TaintedValue, ptr) | (ptr, TaintedValue) if is_pointer(ptr) => self.to_tainted(ptr),
+            _ => {
+                let pointee = |ty: &TaintedType| match ty {
+                    UntaintedPointer(pointee) | TaintedPointer(pointee) => Some(pointee.clone()),
+                    _ => None,
+                };
+                if let (Some(pointee1), Some(pointee2)) = (pointee(ty1), pointee(ty2)) {
+                    let pointee2_ty = pointee2.ty().clone();
+                    if self.is_type_tainted(&pointee2_ty, cur_fn) {
+                        pointee1.taint(self);
+                    }
+                }
+                if self.is_type_tainted(ty2, cur_fn) {
+                    self.to_tainted(ty1)
+                } else {
+                    ty1.clone()
+                }
+            },
+        }
+    }
+
+    /// Unify the named structs which LLVM renamed when linking with the
+    /// structs they're equivalent to, so that they share a definition; see
+    /// `Config::unify_renamed_structs`. Any definitions already given under
+    /// the renamed names (e.g., initial definitions) are moved to the names
+    /// they're unified under.
+    pub(crate) fn unify_renamed_structs(&mut self) {
+        self.struct_aliases = self.modules.renamed_struct_aliases();
+        for (alias, canonical) in &self.struct_aliases {
+            if let Some(ty) = self.named_struct_types.remove(alias) {
+                self.named_struct_types.entry(canonical.clone()).or_insert(ty);
+            }
+            if self.tainted_named_structs.contains(alias) {
+                self.tainted_named_structs.remove(alias);
+                self.tainted_named_structs.insert(canonical.clone());
+            }
+        }
+        for ty in self.named_struct_types.values_mut() {
+            ty.rename_named_structs(&self.struct_aliases);
+        }
+    }
+
+    /// Get the name under which the named struct with the given name is
+    /// known: the name of the struct it's unified with, if any (see
+    /// `Config::unify_renamed_structs`), or else its own name
+    pub(crate) fn canonical_struct_name<'a>(&'a self, struct_name: &'a str) -> &'a str {
+        self.struct_aliases.get(struct_name).map_or(struct_name, String::as_str)
+    }
+
+    /// Refer to the named structs in the given type (including in the data it
+    /// points to) by the names they're known under; see
+    /// `canonical_struct_name()`. This is for types we didn't create
+    /// ourselves, e.g. the types of the arguments we're given.
+    pub(crate) fn canonicalize(&self, ty: &mut TaintedType) {
+        if !self.struct_aliases.is_empty() {
+            ty.rename_named_structs(&self.struct_aliases);
+        }
+    }
+
+    /// Produce the (untainted) `TaintedType` for a given LLVM type, exactly
+    /// like `TaintedType::from_llvm_type()`, except that named structs are
+    /// referred to by the names they're known under; see
+    /// `canonical_struct_name()`
+    pub(crate) fn fresh_type_from_llvm(&self, llvm_ty: &Type) -> TaintedType {
+        TaintedType::from_llvm_type_with_aliases(llvm_ty, &self.struct_aliases)
+    }
+
+    /// Produce the (untainted) `TaintedType` for a given LLVM type, like
+    /// `TaintedType::from_llvm_type()`, but respecting
+    /// `Config::max_pointer_depth`.
+    ///
+    /// Pointees (including elements of arrays, vectors, and structs) nested
+    /// within the value up to `max_pointer_depth` levels deep are fresh, as
+    /// with `fresh_type_from_llvm()`. Pointees nested any deeper are instead
+    /// "deep pointees": there is a single deep pointee for each LLVM type,
+    /// shared by all the data of that type at that depth anywhere in the
+    /// program. So this is sound, just less precise; and the number of
+    /// distinct pointees we create is bounded even for pathologically nested
+    /// types.
+    pub(crate) fn type_from_llvm(&mut self, llvm_ty: &Type) -> TaintedType {
+        match self.max_pointer_depth {
+            None => self.fresh_type_from_llvm(llvm_ty),
+            Some(max_depth) => self.bounded_type_from_llvm(llvm_ty, max_depth),
+        }
+    }
+
+    /// Produce a `Pointee` with (untainted) contents of the given LLVM type,
+    /// to be pointed to by a value we're creating. Like `type_from_llvm()`,
+    /// this respects `Config::max_pointer_depth`.
+    pub(crate) fn pointee_from_llvm(&mut self, llvm_ty: &Type) -> Pointee {
+        match self.max_pointer_depth {
+            None => Pointee::new(self.fresh_type_from_llvm(llvm_ty)),
+            Some(max_depth) => self.bounded_pointee_from_llvm(llvm_ty, max_depth),
+        }
+    }
+
+    /// `type_from_llvm()`, where pointees more than `depth_left` levels deep
+    /// are deep pointees
+    fn bounded_type_from_llvm(&mut self, llvm_ty: &Type, depth_left: usize) -> TaintedType {
+        match llvm_ty {
+            Type::PointerType { pointee_type, .. } if !matches!(pointee_type.as_ref(), Type::FuncType { .. }) => {
+                TaintedType::untainted_ptr_to_pointee(self.bounded_pointee_from_llvm(pointee_type, depth_left))
+            },
+            Type::ArrayType { element_type, .. } | Type::VectorType { element_type, .. } => {
+                TaintedType::array_or_vec_of_pointee(self.bounded_pointee_from_llvm(element_type, depth_left))
+            },
+            Type::StructType { element_types, .. } => {
+                let elements: Vec<Pointee> = element_types
+                    .iter()
+                    .map(|ty| self.bounded_pointee_from_llvm(ty, depth_left))
+                    .collect();
+                TaintedType::struct_of_pointees(elements)
+            },
+            _ => self.fresh_type_from_llvm(llvm_ty),
+        }
+    }
+
+    /// `pointee_from_llvm()`, where the pointee is a deep pointee if
+    /// `depth_left` is 0
+    fn bounded_pointee_from_llvm(&mut self, llvm_ty: &Type, depth_left: usize) -> Pointee {
+        if depth_left > 0 {
+            return Pointee::new(self.bounded_type_from_llvm(llvm_ty, depth_left - 1));
+        }
+        let key = llvm_ty.to_string();
+        if let Some(pointee) = self.deep_pointees.get(&key) {
+            return pointee.clone();
+        }
+        // anything nested within a deep pointee is also deep. This terminates
+        // because each nested type is smaller than `llvm_ty` (named structs
+        // aren't expanded here)
+        let contents = self.bounded_type_from_llvm(llvm_ty, 0);
+        let pointee = Pointee::new_deep(contents);
+        self.deep_pointees.insert(key, pointee.clone());
+        pointee
+    }
+
+    /// Has any deep pointee (see `type_from_llvm()`) changed since the last
+    /// call to this function?
+    pub(crate) fn take_deep_pointees_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.tainted_named_structs.deep_pointees_changed, false)
+    }
+
     /// Get the `TaintedType` for the given struct name.
     /// Marks the current function (whose name is provided as an argument) as a
     /// user of this named struct.
     /// Creates an untainted `TaintedType` for this named struct if no type
     /// previously existed for it.
     pub fn get_named_struct_type(&mut self, struct_name: String, cur_fn: &'m str) -> &TaintedType {
+        let struct_name = self.canonical_struct_name(&struct_name).to_owned();
         let modules = &self.modules; // this is for the borrow checker - allows us to access `modules` without needing to borrow `self`
-        self.named_struct_users.entry(struct_name.clone()).or_default().insert(cur_fn.into());
+        let struct_aliases = &self.struct_aliases;
+        self.named_struct_users.entry(struct_name.clone()).or_default().insert(cur_fn);
         let def = self.named_struct_types.entry(struct_name.clone()).or_insert_with(|| {
             match modules.named_struct_def(&struct_name) {
                 None => panic!("get_named_struct_type on unknown named struct: name {:?}", &struct_name),
@@ -168,7 +442,7 @@ impl<'m> NamedStructs<'m> {
                     "get_named_struct_type on an opaque struct named {:?}",
                     &struct_name
                 ),
-                Some((NamedStructDef::Defined(ty), _)) => TaintedType::from_llvm_type(&ty),
+                Some((NamedStructDef::Defined(ty), _)) => TaintedType::from_llvm_type_with_aliases(ty, struct_aliases),
             }
         });
         if self.tainted_named_structs.contains(&struct_name) {
@@ -183,7 +457,7 @@ impl<'m> NamedStructs<'m> {
     /// Get the names of the functions which are currently known to use the named
     /// struct with the given name.
     pub fn get_named_struct_users(&self, struct_name: &str) -> impl IntoIterator<Item = &'m str> {
-        match self.named_struct_users.get(struct_name) {
+        match self.named_struct_users.get(self.canonical_struct_name(struct_name)) {
             None => vec![],
             Some(users) => users.iter().copied().collect::<Vec<&'m str>>(),
         }
@@ -205,8 +479,37 @@ impl<'m> NamedStructs<'m> {
                 let inner_ty = self.get_named_struct_type(name.into(), cur_fn).clone();
                 self.is_type_tainted(&inner_ty, cur_fn)
             },
-            TaintedType::UntaintedFnPtr => false,
-            TaintedType::TaintedFnPtr => true,
+            TaintedType::UntaintedFnPtr(_) => false,
+            TaintedType::TaintedFnPtr(_) => true,
+            TaintedType::Token => false,
+        }
+    }
+
+    /// Like `is_type_tainted()`, but needs only shared access to the
+    /// `NamedStructs`. Returns `None` if the answer depends on a named struct
+    /// whose definition hasn't been created yet, still has taint to apply, or
+    /// isn't known to be used by `cur_fn`: then use `is_type_tainted()`, which
+    /// takes care of those.
+    pub(crate) fn is_type_tainted_if_cached(&self, ty: &TaintedType, cur_fn: &'m str) -> Option<bool> {
+        match ty {
+            TaintedType::ArrayOrVector(element) => self.is_type_tainted_if_cached(&element.ty(), cur_fn),
+            TaintedType::Struct(elements) => {
+                for element in elements {
+                    if self.is_type_tainted_if_cached(&element.ty(), cur_fn)? {
+                        return Some(true);
+                    }
+                }
+                Some(false)
+            },
+            TaintedType::NamedStruct(name) => {
+                let name = self.canonical_struct_name(name);
+                let is_user = self.named_struct_users.get(name).is_some_and(|users| users.contains(cur_fn));
+                if !is_user || self.tainted_named_structs.contains(name) {
+                    return None;
+                }
+                self.is_type_tainted_if_cached(self.named_struct_types.get(name)?, cur_fn)
+            },
+            _ => Some(ty.is_tainted_nonamedstruct()),
         }
     }
 
@@ -216,6 +519,8 @@ impl<'m> NamedStructs<'m> {
     /// pointees as tainted.
     ///
     /// Alternately you can also use `TaintState::to_tainted()`.
+    // takes `&mut self` for the side effects
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_tainted(&mut self, ty: &TaintedType) -> TaintedType {
         self.tainted_named_structs.to_tainted(ty)
     }
@@ -244,17 +549,17 @@ impl<'m> NamedStructs<'m> {
         mut indices: std::iter::Peekable<impl Iterator<Item = &'b I>>,
     ) -> Result<TaintedType, String> {
         match parent_ptr {
-            TaintedType::UntaintedValue | TaintedType::TaintedValue => {
+            TaintedType::UntaintedValue | TaintedType::TaintedValue | TaintedType::Token => {
                 Err("get_element_ptr: address is not a pointer, or too many indices".into())
             },
-            TaintedType::UntaintedFnPtr | TaintedType::TaintedFnPtr => {
+            TaintedType::UntaintedFnPtr(_) | TaintedType::TaintedFnPtr(_) => {
                 Err("get_element_ptr on a function pointer".into())
             },
             TaintedType::ArrayOrVector(_) | TaintedType::Struct(_) | TaintedType::NamedStruct(_) => {
                 Err("get_element_ptr: address is not a pointer, or too many indices".into())
             },
             TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => {
-                let pointee_ty: &TaintedType = &pointee.ty();
+                let pointee_ty: &TaintedType = &pointee.cloned_ty();
                 let existing_struct_name = pointee.get_struct_name();
                 let existing_global = pointee.get_global_name();
                 match pointee_ty {
@@ -266,7 +571,10 @@ impl<'m> NamedStructs<'m> {
                             Ok(TaintedType::UntaintedPointer(pointee.clone()))
                         }
                     },
-                    TaintedType::TaintedFnPtr | TaintedType::UntaintedFnPtr => {
+                    TaintedType::Token => {
+                        Err("get_element_ptr on a pointer to a token".into())
+                    },
+                    TaintedType::TaintedFnPtr(_) | TaintedType::UntaintedFnPtr(_) => {
                         match indices.peek() {
                             None if self.is_type_tainted(parent_ptr, cur_fn) => {
                                 Ok(TaintedType::TaintedPointer(pointee.clone()))
@@ -407,6 +715,13 @@ impl Index for Constant {
     fn as_constant(&self) -> Option<u64> {
         match self {
             Constant::Int { value, .. } => Some(*value),
+            // a vector index, in a `getelementptr` producing a vector of
+            // pointers, is constant if all its lanes are
+            Constant::Vector(elements) => match elements.split_first() {
+                Some((first, rest)) if rest.iter().all(|element| element == first) => first.as_constant(),
+                _ => None,
+            },
+            Constant::AggregateZero(_) => Some(0),
             _ => unimplemented!("as_constant on {:?}", self),
         }
     }
@@ -0,0 +1,86 @@
+use either::Either;
+use llvm_ir::instruction::groups;
+use llvm_ir::instruction::UnaryOp;
+use llvm_ir::{Instruction, Operand, Terminator};
+use std::convert::TryInto;
+
+/// Get all of the `Operand`s used by the given `Instruction`, in the order they
+/// appear in the instruction.
+///
+/// For calls, this includes the called function pointer (unless it's inline
+/// assembly) followed by the arguments. For phis, this includes only the
+/// incoming values, not the incoming block names.
+pub(crate) fn instruction_operands(inst: &Instruction) -> Vec<Operand> {
+    if inst.is_binary_op() {
+        let bop: groups::BinaryOp = inst.clone().try_into().unwrap();
+        use llvm_ir::instruction::BinaryOp;
+        return vec![bop.get_operand0().clone(), bop.get_operand1().clone()];
+    }
+    match inst {
+        Instruction::ExtractElement(ee) => vec![ee.vector.clone(), ee.index.clone()],
+        Instruction::InsertElement(ie) => vec![ie.vector.clone(), ie.element.clone(), ie.index.clone()],
+        Instruction::ShuffleVector(sv) => vec![sv.operand0.clone(), sv.operand1.clone()],
+        Instruction::ExtractValue(ev) => vec![ev.aggregate.clone()],
+        Instruction::InsertValue(iv) => vec![iv.aggregate.clone(), iv.element.clone()],
+        Instruction::Alloca(alloca) => vec![alloca.num_elements.clone()],
+        Instruction::Load(load) => vec![load.address.clone()],
+        Instruction::Store(store) => vec![store.address.clone(), store.value.clone()],
+        Instruction::Fence(_) => vec![],
+        Instruction::CmpXchg(cx) => vec![cx.address.clone(), cx.expected.clone(), cx.replacement.clone()],
+        Instruction::AtomicRMW(rmw) => vec![rmw.address.clone(), rmw.value.clone()],
+        Instruction::GetElementPtr(gep) => std::iter::once(&gep.address).chain(gep.indices.iter()).cloned().collect(),
+        Instruction::ICmp(icmp) => vec![icmp.operand0.clone(), icmp.operand1.clone()],
+        Instruction::FCmp(fcmp) => vec![fcmp.operand0.clone(), fcmp.operand1.clone()],
+        Instruction::Phi(phi) => phi.incoming_values.iter().map(|(op, _)| op.clone()).collect(),
+        Instruction::Select(select) => vec![select.condition.clone(), select.true_value.clone(), select.false_value.clone()],
+        Instruction::Call(call) => {
+            let function = match &call.function {
+                Either::Left(_) => None, // inline assembly
+                Either::Right(op) => Some(op.clone()),
+            };
+            function.into_iter().chain(call.arguments.iter().map(|(op, _)| op.clone())).collect()
+        },
+        Instruction::VAArg(va) => vec![va.arg_list.clone()],
+        Instruction::LandingPad(_) => vec![],
+        Instruction::CatchPad(cp) => std::iter::once(&cp.catch_switch).chain(cp.args.iter()).cloned().collect(),
+        Instruction::CleanupPad(cp) => std::iter::once(&cp.parent_pad).chain(cp.args.iter()).cloned().collect(),
+        _ => {
+            // all remaining instructions (the casts, `fneg`, and `freeze`) are
+            // unary ops
+            let uop: Result<groups::UnaryOp, _> = inst.clone().try_into();
+            match uop {
+                Ok(uop) => vec![uop.get_operand().clone()],
+                Err(_) => vec![],
+            }
+        },
+    }
+}
+
+/// Get all of the `Operand`s used by the given `Terminator`, in the order they
+/// appear in the terminator.
+///
+/// Like `instruction_operands()`, for invokes this includes the called
+/// function pointer (unless it's inline assembly) followed by the arguments.
+pub(crate) fn terminator_operands(term: &Terminator) -> Vec<Operand> {
+    match term {
+        Terminator::Ret(ret) => ret.return_operand.iter().cloned().collect(),
+        Terminator::Br(_) => vec![],
+        Terminator::CondBr(condbr) => vec![condbr.condition.clone()],
+        Terminator::Switch(switch) => vec![switch.operand.clone()],
+        Terminator::IndirectBr(ibr) => vec![ibr.operand.clone()],
+        Terminator::Invoke(invoke) => {
+            let function = match &invoke.function {
+                Either::Left(_) => None, // inline assembly
+                Either::Right(op) => Some(op.clone()),
+            };
+            function.into_iter().chain(invoke.arguments.iter().map(|(op, _)| op.clone())).collect()
+        },
+        Terminator::Resume(resume) => vec![resume.operand.clone()],
+        Terminator::Unreachable(_) => vec![],
+        Terminator::CleanupRet(cr) => vec![cr.cleanup_pad.clone()],
+        Terminator::CatchRet(cr) => vec![cr.catch_pad.clone()],
+        Terminator::CatchSwitch(cs) => vec![cs.parent_pad.clone()],
+        #[allow(unreachable_patterns)] // `CallBr` exists only for LLVM 9+
+        _ => vec![],
+    }
+}
@@ -0,0 +1,33 @@
+use crate::demangle;
+
+/// Which parts of a `TaintResult` to write, for `TaintResult::write_text()`,
+/// `TaintResult::write_filtered_table()`, and `TaintResult::write_sarif()`.
+/// The default keeps everything.
+#[derive(Clone, Debug, Default)]
+pub struct OutputFilter {
+    /// If nonempty, only the functions going by one of these names (their
+    /// mangled or demangled name, as in `TaintResult::find_functions()`);
+    /// globals and named structs are then left out
+    pub functions: Vec<String>,
+    /// Only the values which are tainted
+    pub only_tainted: bool,
+}
+
+impl OutputFilter {
+    /// Does the filter keep the function with the given (mangled) name?
+    pub fn keeps_function(&self, fn_name: &str) -> bool {
+        self.functions.is_empty()
+            || demangle::names_for(fn_name).iter().any(|name| self.functions.iter().any(|f| f == name))
+    }
+
+    /// Does the filter keep values which don't belong to a function, i.e.,
+    /// globals and named structs?
+    pub fn keeps_non_function_values(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Does the filter keep a value with the given taint status?
+    pub fn keeps_taint(&self, tainted: bool) -> bool {
+        tainted || !self.only_tainted
+    }
+}
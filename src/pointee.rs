@@ -2,10 +2,13 @@ use crate::function_taint_state::FunctionTaintState;
 use crate::named_structs::{NamedStructs, TaintedNamedStructs};
 use crate::tainted_type::TaintedType;
 use llvm_ir::Name;
+#[cfg(not(feature = "tracing"))]
 use log::debug;
-use std::cell::{Ref, RefCell};
+#[cfg(feature = "tracing")]
+use tracing::debug;
+use std::collections::HashMap;
 use std::fmt;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 /// A `Pointee` represents the `TaintedType` which a pointer points to.
 /// A `Pointee` may represent either a scalar or an array, or an element of a
@@ -13,12 +16,13 @@ use std::rc::Rc;
 ///
 /// Cloning a `Pointee` gives another reference to the _same_ `Pointee`; if one
 /// clone is updated with `update()` or `taint()`, all clones will be updated.
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct Pointee {
     /// The pointed-to type, or the element type of the array.
-    /// `Rc<RefCell<>>` allows us to have multiple pointers to the same
-    /// underlying type.
-    ty: Rc<RefCell<TaintedType>>,
+    /// `Arc<RwLock<>>` allows us to have multiple pointers to the same
+    /// underlying type, while keeping `Pointee` (and everything containing
+    /// it) `Send` and `Sync`.
+    ty: Arc<RwLock<TaintedType>>,
 
     /// If this is a pointer to an element of a _named_ struct, here is the name
     /// of that named struct.
@@ -33,6 +37,13 @@ pub struct Pointee {
     /// We have this so that if a change is made to the Pointee type, we know
     /// that the users of this global need to be re-added to the worklist.
     global: Option<Name>,
+
+    /// Is this a deep pointee, i.e., one shared by all the data of its LLVM
+    /// type nested beyond `Config::max_pointer_depth`?
+    ///
+    /// We have this so that if a change is made to the Pointee type, we know
+    /// that every function may need to be re-added to the worklist.
+    deep: bool,
 }
 
 impl Pointee {
@@ -41,9 +52,10 @@ impl Pointee {
     /// `Pointee`s -- that is, no pointer aliasing.
     pub fn new(pointee_ty: TaintedType) -> Self {
         Self {
-            ty: Rc::new(RefCell::new(pointee_ty)),
+            ty: Arc::new(RwLock::new(pointee_ty)),
             named_struct: None,
             global: None,
+            deep: false,
         }
     }
 
@@ -53,9 +65,10 @@ impl Pointee {
     /// `Pointee`s -- that is, no pointer aliasing.
     pub fn new_named_struct_element(element_ty: TaintedType, struct_name: String) -> Self {
         Self {
-            ty: Rc::new(RefCell::new(element_ty)),
+            ty: Arc::new(RwLock::new(element_ty)),
             named_struct: Some(struct_name),
             global: None,
+            deep: false,
         }
     }
 
@@ -65,15 +78,47 @@ impl Pointee {
     /// `Pointee`s -- that is, no pointer aliasing.
     pub fn new_global_contents(contents_ty: TaintedType, global_name: Name) -> Self {
         Self {
-            ty: Rc::new(RefCell::new(contents_ty)),
+            ty: Arc::new(RwLock::new(contents_ty)),
             named_struct: None,
             global: Some(global_name),
+            deep: false,
+        }
+    }
+
+    /// Construct a new deep pointee (see `NamedStructs::type_from_llvm()`)
+    /// with the given `TaintedType` for its contents.
+    pub(crate) fn new_deep(contents_ty: TaintedType) -> Self {
+        Self {
+            ty: Arc::new(RwLock::new(contents_ty)),
+            named_struct: None,
+            global: None,
+            deep: true,
         }
     }
 
-    /// Get a `Ref` to the `TaintedType` representing the pointee's contents
-    pub fn ty(&self) -> Ref<TaintedType> {
-        self.ty.borrow()
+    /// Get a read guard for the `TaintedType` representing the pointee's
+    /// contents.
+    ///
+    /// The pointee's lock isn't reentrant, so while the guard is held, this
+    /// thread mustn't update this pointee (or any clone of it). Nor may it lock
+    /// the `NamedStructs`, which are always locked before any pointee. To pass
+    /// the contents to something which may do either, such as
+    /// `TaintState::to_tainted()` or `TaintState::is_type_tainted()`, use
+    /// `cloned_ty()` instead.
+    pub fn ty(&self) -> RwLockReadGuard<'_, TaintedType> {
+        self.ty.read().unwrap()
+    }
+
+    /// Get a copy of the `TaintedType` representing the pointee's contents,
+    /// without holding the pointee's lock (see `ty()`)
+    pub fn cloned_ty(&self) -> TaintedType {
+        self.ty.read().unwrap().clone()
+    }
+
+    /// An identifier of this pointee, which all its clones share (so long as
+    /// any of them exists)
+    pub(crate) fn id(&self) -> usize {
+        Arc::as_ptr(&self.ty) as usize
     }
 
     /// If this pointee is an element of a named struct, get the name of that
@@ -124,6 +169,20 @@ impl Pointee {
         }
     }
 
+    /// Is this a deep pointee (see `NamedStructs::type_from_llvm()`)?
+    pub(crate) fn is_deep(&self) -> bool {
+        self.deep
+    }
+
+    /// Mark the pointee as being a deep pointee, e.g. when restoring a
+    /// checkpoint.
+    ///
+    /// If the pointee is later updated with `update()` or `taint()`, we will
+    /// re-add all functions to the worklist.
+    pub(crate) fn set_deep(&mut self) {
+        self.deep = true;
+    }
+
     /// Update the `TaintedType` representing the pointed-to contents with the
     /// given `TaintedType`.
     /// This perfoms a `join` of the given `TaintedType` and the previous
@@ -132,9 +191,25 @@ impl Pointee {
     /// Returns `true` if the contents' `TaintedType` changed, accounting for the
     /// join operation.
     pub(crate) fn update(&mut self, new_pointee_ty: &TaintedType, fts: &FunctionTaintState) -> Result<bool, String> {
-        let mut pointee_ty = self.ty.borrow_mut();
-        let joined_pointee_ty = pointee_ty.join(new_pointee_ty)?;
-        if &*pointee_ty == &joined_pointee_ty {
+        let mut pointee_ty = self.ty.write().unwrap();
+        let joined_pointee_ty = match pointee_ty.join(new_pointee_ty) {
+            Ok(joined) => joined,
+            Err(e) => {
+                // Joining mismatched types (see `Config::lenient_joins`) needs
+                // the named structs, which must be locked before the pointee
+                let cur_ty = pointee_ty.clone();
+                drop(pointee_ty);
+                let mut named_structs = fts.named_structs.write().unwrap();
+                if !named_structs.lenient_joins() {
+                    return Err(e);
+                }
+                let joined = named_structs.join_mismatched(&cur_ty, new_pointee_ty, fts.name);
+                drop(named_structs);
+                pointee_ty = self.ty.write().unwrap();
+                joined
+            },
+        };
+        if *pointee_ty == joined_pointee_ty {
             // no change is necessary
             Ok(false)
         } else {
@@ -148,22 +223,30 @@ impl Pointee {
             // pointer-to-tainted.
             debug!("Updating pointee {:?} to {}", self.as_ptr(), joined_pointee_ty);
             *pointee_ty = joined_pointee_ty;
+            // release the pointee before locking anything else, as the named
+            // structs must be locked first
+            drop(pointee_ty);
             // If we just updated an element of a named struct, add all the
             // users of that named struct to the worklist
             if let Some(struct_name) = &self.named_struct {
-                let mut worklist = fts.worklist.borrow_mut();
-                for user in fts.named_structs.borrow().get_named_struct_users(struct_name) {
+                let mut worklist = fts.worklist.write().unwrap();
+                for user in fts.named_structs.read().unwrap().get_named_struct_users(struct_name) {
                     worklist.add(user);
                 }
             }
             // If we just updated all or part of the contents of a global, add
             // all the users of that global to the worklist
             if let Some(global_name) = &self.global {
-                let mut worklist = fts.worklist.borrow_mut();
-                for user in fts.globals.borrow().get_global_users(global_name) {
+                let mut worklist = fts.worklist.write().unwrap();
+                for user in fts.globals.read().unwrap().get_global_users(global_name) {
                     worklist.add(user);
                 }
             }
+            // If we just updated a deep pointee, any function may see the
+            // change
+            if self.deep {
+                fts.named_structs.write().unwrap().tainted_named_structs.deep_pointees_changed = true;
+            }
             Ok(true)
         }
     }
@@ -175,10 +258,34 @@ impl Pointee {
         self.taint_with_tns(&mut named_structs.tainted_named_structs)
     }
 
-    pub(crate) fn taint_with_tns<'m>(&self, tns: &mut TaintedNamedStructs) -> bool {
-        let mut pointee_ty = self.ty.borrow_mut();
+    /// Like `taint()`, but for struct, array, and vector contents, also reports
+    /// changes to their elements. (`taint()` marks the elements tainted in
+    /// place, so the contents' `TaintedType` itself compares unchanged.)
+    ///
+    /// Returns `true` if the contents or any of their elements changed.
+    pub(crate) fn taint_elements<'m>(&self, named_structs: &mut NamedStructs<'m>) -> bool {
+        let ty = self.ty().clone();
+        let elements = match ty {
+            TaintedType::Struct(elements) => elements,
+            TaintedType::ArrayOrVector(element) => vec![element],
+            TaintedType::NamedStruct(name) => {
+                let already_tainted = named_structs.all_tainted_named_structs().any(|s| *s == name);
+                self.taint(named_structs);
+                return !already_tainted;
+            },
+            _ => return self.taint(named_structs),
+        };
+        let mut changed = false;
+        for element in &elements {
+            changed |= element.taint_elements(named_structs);
+        }
+        changed
+    }
+
+    pub(crate) fn taint_with_tns(&self, tns: &mut TaintedNamedStructs) -> bool {
+        let mut pointee_ty = self.ty.write().unwrap();
         let tainted_ty = tns.to_tainted(&pointee_ty);
-        if &*pointee_ty == &tainted_ty {
+        if *pointee_ty == tainted_ty {
             // no change is necessary
             false
         } else {
@@ -186,18 +293,47 @@ impl Pointee {
             // See notes on and inside `update()`
             debug!("Updating pointee {:?} to {}", self.as_ptr(), tainted_ty);
             *pointee_ty = tainted_ty;
+            if self.deep {
+                tns.deep_pointees_changed = true;
+            }
             true
         }
     }
 
+    /// Overwrite the `TaintedType` representing the pointed-to contents,
+    /// without performing a `join` and without adding anything to the
+    /// worklist. This is only appropriate when restoring a checkpoint.
+    pub(crate) fn set_ty(&self, ty: TaintedType) {
+        *self.ty.write().unwrap() = ty;
+    }
+
+    /// Rename the named structs in the pointed-to contents; see
+    /// `TaintedType::rename_named_structs()`
+    pub(crate) fn rename_named_structs(&self, struct_aliases: &HashMap<String, String>) {
+        self.ty.write().unwrap().rename_named_structs(struct_aliases)
+    }
+
     /// Intended for use in debugging. Allows you to distinguish which `Pointee`s
     /// are linked (in that updating one updates the others) because they return
     /// the same pointer here
-    pub(crate) fn as_ptr(&self) -> *const TaintedType {
-        self.ty.as_ptr()
+    pub(crate) fn as_ptr(&self) -> *const RwLock<TaintedType> {
+        Arc::as_ptr(&self.ty)
     }
 }
 
+impl PartialEq for Pointee {
+    /// Two `Pointee`s are equal if their contents' `TaintedType`s are equal
+    /// (whether or not they are linked), and they belong to the same named
+    /// struct and global (if any)
+    fn eq(&self, other: &Self) -> bool {
+        self.named_struct == other.named_struct
+            && self.global == other.global
+            && *self.ty() == *other.ty()
+    }
+}
+
+impl Eq for Pointee {}
+
 impl fmt::Display for Pointee {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.ty())
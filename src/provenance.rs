@@ -0,0 +1,313 @@
+use crate::taint_result::TaintResult;
+use crate::tainted_type::TaintedType;
+use either::Either;
+use llvm_ir::{Constant, Instruction, Name, Operand};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of steps in an explanation, as a safeguard
+const MAX_EXPLANATION_STEPS: usize = 1000;
+
+/// Why a variable, or the contents of some memory, first became tainted: the
+/// instruction which tainted it, and which of its operands were tainted at
+/// the time
+#[derive(Clone, Debug)]
+pub(crate) struct Cause {
+    /// Name of the block containing the instruction
+    pub(crate) block: Name,
+    /// Index of the instruction within the block
+    pub(crate) index: usize,
+    /// The instruction's local operands which were tainted
+    pub(crate) tainted_operands: Vec<Name>,
+    /// The instruction's local pointer operands which pointed to tainted
+    /// data, with the `Pointee::id()`s of their pointees
+    pub(crate) tainted_contents: Vec<(Name, usize)>,
+}
+
+/// The propagation edges recorded during the analysis, if
+/// `Config::record_provenance` is set: for each variable and each `Pointee`,
+/// the `Cause` of its first becoming tainted
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Provenance<'m> {
+    /// Were propagation edges recorded at all?
+    enabled: bool,
+    /// Map from function name to the causes of its variables
+    vars: HashMap<&'m str, HashMap<Name, Cause>>,
+    /// Map from `Pointee::id()` to the cause of its contents, and the function
+    /// containing the instruction
+    contents: HashMap<usize, (&'m str, Cause)>,
+}
+
+impl<'m> Provenance<'m> {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self { enabled, ..Self::default() }
+    }
+
+    /// Record the cause of the given variable becoming tainted, unless we
+    /// already have one
+    pub(crate) fn record_var(&mut self, fn_name: &'m str, var: Name, cause: Cause) {
+        self.vars.entry(fn_name).or_default().entry(var).or_insert(cause);
+    }
+
+    /// Record the cause of the contents of the `Pointee` with the given id
+    /// becoming tainted, unless we already have one
+    pub(crate) fn record_contents(&mut self, pointee_id: usize, fn_name: &'m str, cause: Cause) {
+        self.contents.entry(pointee_id).or_insert((fn_name, cause));
+    }
+
+    /// Copy these propagation edges for a copy of the types they refer to, in
+    /// which the `Pointee` with each id in `new_ids` is replaced by the one
+    /// with the corresponding new id. Edges involving `Pointee`s which weren't
+    /// copied are dropped.
+    pub(crate) fn with_pointee_ids(&self, new_ids: &HashMap<usize, usize>) -> Self {
+        let copy_cause = |cause: &Cause| Cause {
+            tainted_contents: cause.tainted_contents
+                .iter()
+                .filter_map(|(ptr, id)| Some((ptr.clone(), *new_ids.get(id)?)))
+                .collect(),
+            ..cause.clone()
+        };
+        Self {
+            enabled: self.enabled,
+            vars: self.vars
+                .iter()
+                .map(|(fn_name, causes)| (*fn_name, causes.iter().map(|(var, cause)| (var.clone(), copy_cause(cause))).collect()))
+                .collect(),
+            contents: self.contents
+                .iter()
+                .filter_map(|(id, (fn_name, cause))| Some((*new_ids.get(id)?, (*fn_name, copy_cause(cause)))))
+                .collect(),
+        }
+    }
+}
+
+/// What happened at one step of an `explain()`ed derivation
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExplanationStepKind {
+    /// The value was tainted from the start of the analysis (e.g., an
+    /// argument given as tainted), or we have no record of how it became
+    /// tainted
+    Source,
+    /// The value is the result of the instruction, computed from the value of
+    /// the previous step (or, if this is the first step, e.g. a call of an
+    /// external function returning tainted data, from no tainted value)
+    Instruction,
+    /// The value (memory) was written by the instruction, from the value of
+    /// the previous step
+    MemoryWrite,
+    /// The value (a parameter) was passed the value of the previous step by
+    /// the call
+    Argument,
+    /// The value is the result of the call, which returned the value of the
+    /// previous step
+    Return,
+}
+
+/// One step of the derivation of a tainted value (see `TaintResult::explain()`)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExplanationStep<'m> {
+    /// How the value became tainted
+    pub kind: ExplanationStepKind,
+    /// Function containing the value
+    pub function: &'m str,
+    /// The value which became tainted at this step: a variable, e.g. `%x`; or
+    /// memory pointed to by a variable, e.g. `*%p`
+    pub value: String,
+    /// The instruction which tainted the value, as (function name, block
+    /// name, index of the instruction within the block, the instruction as
+    /// LLVM text), or `None` for `Source` steps. For `Argument` steps, this is
+    /// the call, in the caller.
+    pub site: Option<(&'m str, Name, usize, String)>,
+}
+
+/// What remains to be explained
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Query<'m> {
+    /// The variable with the given name, in the given function
+    Var(&'m str, Name),
+    /// The contents of the `Pointee` with the given id, as pointed to by the
+    /// variable with the given name in the given function
+    Contents(&'m str, Name, usize),
+}
+
+/// Get the id of the pointee of the given type, if it's a pointer
+fn pointee_id(ty: &TaintedType) -> Option<usize> {
+    match ty {
+        TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => Some(pointee.id()),
+        _ => None,
+    }
+}
+
+/// Explain why the given variable is tainted. See `TaintResult::explain()`.
+pub(crate) fn explain<'m>(taint_result: &TaintResult<'m>, fn_name: &str, var: &Name) -> Result<Vec<ExplanationStep<'m>>, String> {
+    let provenance = &taint_result.provenance;
+    if !provenance.enabled {
+        return Err("explain: no propagation edges were recorded; set Config::record_provenance".into());
+    }
+    let (fn_name, fts) = taint_result.fn_taint_states
+        .get_key_value(fn_name)
+        .ok_or_else(|| format!("explain: no taint map found for function {:?}", fn_name))?;
+    let fn_name: &'m str = fn_name;
+    let ty = fts.get_taint_map()
+        .get(var)
+        .ok_or_else(|| format!("explain: no variable {} in function {:?}", var, fn_name))?;
+    let mut query = if taint_result.is_type_tainted_if_known(ty) {
+        Query::Var(fn_name, var.clone())
+    } else {
+        match ty {
+            TaintedType::UntaintedPointer(pointee) if taint_result.is_type_tainted_if_known(&pointee.ty()) => {
+                Query::Contents(fn_name, var.clone(), pointee.id())
+            },
+            _ => return Err(format!("explain: variable {} in function {:?} is not tainted", var, fn_name)),
+        }
+    };
+
+    // walk back from the queried value towards a source
+    let mut steps = Vec::new();
+    let mut visited = HashSet::new();
+    while visited.insert(query.clone()) && steps.len() < MAX_EXPLANATION_STEPS {
+        let (step, next) = explain_one(taint_result, &query);
+        steps.push(step);
+        match next {
+            Some(next) => query = next,
+            None => break,
+        }
+    }
+    steps.reverse();
+    Ok(steps)
+}
+
+/// Explain one step of the derivation of the given value: the step, and what
+/// remains to be explained, if anything
+fn explain_one<'m>(taint_result: &TaintResult<'m>, query: &Query<'m>) -> (ExplanationStep<'m>, Option<Query<'m>>) {
+    let provenance = &taint_result.provenance;
+    let site = |fn_name: &'m str, cause: &Cause| {
+        let text = instruction_at(taint_result, fn_name, &cause.block, cause.index).map(|inst| inst.to_string()).unwrap_or_default();
+        Some((fn_name, cause.block.clone(), cause.index, text))
+    };
+    match query {
+        Query::Var(fn_name, var) => {
+            let fn_name = *fn_name;
+            let value = var.to_string();
+            if let Some(cause) = provenance.vars.get(fn_name).and_then(|vars| vars.get(var)) {
+                let step = |kind| ExplanationStep { kind, function: fn_name, value: value.clone(), site: site(fn_name, cause) };
+                // the result of a call of a defined function is what it
+                // returns, whatever its arguments
+                if let Some(next) = returned_value(taint_result, fn_name, cause) {
+                    (step(ExplanationStepKind::Return), Some(next))
+                } else if let Some(op) = cause.tainted_operands.first() {
+                    (step(ExplanationStepKind::Instruction), Some(Query::Var(fn_name, op.clone())))
+                } else if let Some((ptr, id)) = cause.tainted_contents.first() {
+                    (step(ExplanationStepKind::Instruction), Some(Query::Contents(fn_name, ptr.clone(), *id)))
+                } else {
+                    (step(ExplanationStepKind::Instruction), None)
+                }
+            } else if let Some((step, next)) = passed_argument(taint_result, fn_name, var) {
+                (step, next)
+            } else {
+                (ExplanationStep { kind: ExplanationStepKind::Source, function: fn_name, value, site: None }, None)
+            }
+        },
+        Query::Contents(fn_name, ptr, id) => {
+            match provenance.contents.get(id) {
+                Some((writer_fn, cause)) => {
+                    let writer_fn = *writer_fn;
+                    let value = match cause.tainted_contents.iter().find(|(_, i)| i == id) {
+                        Some((writer_ptr, _)) => format!("*{}", writer_ptr),
+                        None => format!("*{}", ptr),
+                    };
+                    // the value written: a tainted operand other than the
+                    // pointer to this memory, or tainted memory copied from
+                    let written_ptrs: Vec<&Name> = cause.tainted_contents.iter().filter(|(_, i)| i == id).map(|(p, _)| p).collect();
+                    let next = cause.tainted_operands
+                        .iter()
+                        .find(|op| !written_ptrs.contains(op))
+                        .map(|op| Query::Var(writer_fn, op.clone()))
+                        .or_else(|| cause.tainted_contents
+                            .iter()
+                            .find(|(_, i)| i != id)
+                            .map(|(p, i)| Query::Contents(writer_fn, p.clone(), *i)));
+                    (ExplanationStep { kind: ExplanationStepKind::MemoryWrite, function: writer_fn, value, site: site(writer_fn, cause) }, next)
+                },
+                None => (ExplanationStep { kind: ExplanationStepKind::Source, function: fn_name, value: format!("*{}", ptr), site: None }, None),
+            }
+        },
+    }
+}
+
+/// Get the instruction at the given location, if any
+fn instruction_at<'m>(taint_result: &TaintResult<'m>, fn_name: &str, block: &Name, index: usize) -> Option<&'m Instruction> {
+    let module = taint_result.fn_taint_states.get(fn_name)?.module;
+    let func = module.get_func_by_name(fn_name)?;
+    func.get_bb_by_name(block)?.instrs.get(index)
+}
+
+/// If the instruction of the given `Cause` is a direct call of a defined
+/// function, get the tainted value it returns, if any
+fn returned_value<'m>(taint_result: &TaintResult<'m>, fn_name: &'m str, cause: &Cause) -> Option<Query<'m>> {
+    let call = match instruction_at(taint_result, fn_name, &cause.block, cause.index)? {
+        Instruction::Call(call) => call,
+        _ => return None,
+    };
+    let callee = match &call.function {
+        Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+            Constant::GlobalReference { name: Name::Name(name), .. } => name.as_str(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let (callee, fts) = taint_result.fn_taint_states.get_key_value(callee)?;
+    let func = fts.module.get_func_by_name(callee)?;
+    func.basic_blocks.iter().find_map(|bb| match &bb.term {
+        llvm_ir::Terminator::Ret(ret) => match &ret.return_operand {
+            Some(Operand::LocalOperand { name, .. }) => {
+                let ty = fts.get_taint_map().get(name)?;
+                if taint_result.is_type_tainted_if_known(ty) {
+                    Some(Query::Var(callee, name.clone()))
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// If the given variable is a parameter of the given function, find a call of
+/// the function which passes it tainted data: the `Argument` step, and what
+/// remains to be explained
+fn passed_argument<'m>(taint_result: &TaintResult<'m>, fn_name: &'m str, var: &Name) -> Option<(ExplanationStep<'m>, Option<Query<'m>>)> {
+    let fts = taint_result.fn_taint_states.get(fn_name)?;
+    let func = fts.module.get_func_by_name(fn_name)?;
+    let param_index = func.parameters.iter().position(|param| &param.name == var)?;
+    let mut calls = taint_result.direct_calls();
+    calls.sort_by(|a, b| (a.caller, a.block, a.index).cmp(&(b.caller, b.block, b.index)));
+    calls.into_iter().filter(|call| call.callee == fn_name).find_map(|call| {
+        let inst = instruction_at(taint_result, call.caller, call.block, call.index)?;
+        let arg = match inst {
+            Instruction::Call(c) => &c.arguments.get(param_index)?.0,
+            _ => return None,
+        };
+        let caller_fts = taint_result.fn_taint_states.get(call.caller)?;
+        let arg_ty = caller_fts.get_type_of_operand_unsanitized(arg).ok()?;
+        let next = match arg {
+            Operand::LocalOperand { name, .. } if taint_result.is_type_tainted_if_known(&arg_ty) => Some(Query::Var(call.caller, name.clone())),
+            Operand::LocalOperand { name, .. } => match &arg_ty {
+                TaintedType::UntaintedPointer(pointee) if taint_result.is_type_tainted_if_known(&pointee.ty()) => {
+                    Some(Query::Contents(call.caller, name.clone(), pointee_id(&arg_ty)?))
+                },
+                _ => return None,
+            },
+            // e.g., a tainted global
+            _ if taint_result.is_type_tainted_if_known(&arg_ty) => None,
+            _ => return None,
+        };
+        let step = ExplanationStep {
+            kind: ExplanationStepKind::Argument,
+            function: fn_name,
+            value: var.to_string(),
+            site: Some((call.caller, call.block.clone(), call.index, inst.to_string())),
+        };
+        Some((step, next))
+    })
+}
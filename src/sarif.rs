@@ -0,0 +1,95 @@
+use crate::demangle::demangle;
+use crate::output_filter::OutputFilter;
+use crate::taint_result::TaintResult;
+use llvm_ir::{DebugLoc, HasDebugLoc, Name};
+use serde_json::{json, Value};
+use std::io::Write;
+
+/// The kinds of findings reported, as (rule ID, description)
+const RULES: [(&str, &str); 3] = [
+    ("tainted-sink", "Tainted data is passed to a sink"),
+    ("tainted-free", "A tainted pointer is deallocated"),
+    ("tainted-format-string", "A format function is passed a tainted format string"),
+];
+
+/// Write the findings as a SARIF log. See `TaintResult::write_sarif()`.
+pub(crate) fn write_sarif(taint_result: &TaintResult, filter: &OutputFilter, writer: impl Write) -> Result<(), String> {
+    let mut fn_names: Vec<&str> = taint_result.fn_taint_states.keys().copied().filter(|fn_name| filter.keeps_function(fn_name)).collect();
+    fn_names.sort_unstable();
+    let mut results = Vec::new();
+    for fn_name in fn_names {
+        let findings = [
+            taint_result.get_tainted_sinks(fn_name),
+            taint_result.get_tainted_frees(fn_name),
+            taint_result.get_tainted_format_strings(fn_name),
+        ];
+        for ((rule_id, description), findings) in RULES.iter().zip(findings) {
+            for (block, index) in findings {
+                results.push(sarif_result(taint_result, fn_name, rule_id, description, block, index));
+            }
+        }
+    }
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|(rule_id, description)| json!({
+            "id": rule_id,
+            "shortDescription": { "text": description },
+        }))
+        .collect();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_writer_pretty(writer, &log).map_err(|e| format!("Failed to write SARIF: {}", e))
+}
+
+/// Make the SARIF result for the finding at the given instruction
+fn sarif_result(taint_result: &TaintResult, fn_name: &str, rule_id: &str, description: &str, block: &Name, index: usize) -> Value {
+    let inst = taint_result.fn_taint_states[fn_name]
+        .module
+        .get_func_by_name(fn_name)
+        .and_then(|func| func.basic_blocks.iter().find(|bb| &bb.name == block))
+        .and_then(|bb| bb.instrs.get(index));
+    let text = match inst {
+        Some(inst) => format!("{} in {}: {}", description, fn_name, inst),
+        None => format!("{} in {}, block {} instruction {}", description, fn_name, block, index),
+    };
+    let mut location = json!({
+        "logicalLocations": [{
+            "name": fn_name,
+            "fullyQualifiedName": demangle(fn_name).unwrap_or_else(|| fn_name.to_owned()),
+            "kind": "function",
+        }],
+    });
+    if let Some(DebugLoc { line, col, filename, directory, .. }) = inst.and_then(|inst| inst.get_debug_loc().as_ref()) {
+        let uri = match directory {
+            Some(directory) if !filename.starts_with('/') => format!("{}/{}", directory, filename),
+            _ => filename.clone(),
+        };
+        let mut region = json!({ "startLine": line });
+        if let Some(col) = col {
+            region["startColumn"] = json!(col);
+        }
+        location["physicalLocation"] = json!({
+            "artifactLocation": { "uri": uri },
+            "region": region,
+        });
+    }
+    json!({
+        "ruleId": rule_id,
+        "level": "warning",
+        "message": { "text": text },
+        "locations": [location],
+    })
+}
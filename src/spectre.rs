@@ -0,0 +1,177 @@
+use crate::operands::{instruction_operands, terminator_operands};
+use crate::taint_result::TaintResult;
+use llvm_ir::{Function, Instruction, Name, Operand, Terminator};
+use llvm_ir_analysis::FunctionAnalysis;
+use std::collections::{HashMap, HashSet};
+
+/// A candidate Spectre-v1 (bounds check bypass) gadget: a load whose address
+/// is indexed by tainted data, which is bounds-checked by a conditional
+/// branch, and whose result then influences a second memory access or a
+/// branch. If the bounds check is mispredicted, the first load may read out
+/// of bounds speculatively, and the second access may leak what it read
+/// through the cache. See `TaintResult::find_spectre_gadgets()`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpectreGadget<'m> {
+    /// Name of the function containing the gadget
+    pub function: &'m str,
+    /// Name of the tainted index used by the first load
+    pub index: &'m Name,
+    /// Name of the block whose conditional branch bounds-checks the index
+    pub bounds_check: &'m Name,
+    /// Name of the block containing the first load
+    pub access_block: &'m Name,
+    /// Index of the first load within its block
+    pub access_index: usize,
+    /// Name of the block containing the instruction which may leak the loaded
+    /// data
+    pub transmit_block: &'m Name,
+    /// Index of that instruction within its block: a load whose address
+    /// depends on the loaded data, or `None` for a terminator branching on it
+    pub transmit_index: Option<usize>,
+}
+
+/// Find the candidate gadgets. See `TaintResult::find_spectre_gadgets()`.
+pub(crate) fn find_spectre_gadgets<'m>(taint_result: &TaintResult<'m>) -> Vec<SpectreGadget<'m>> {
+    let mut gadgets = Vec::new();
+    for (fn_name, fts) in &taint_result.fn_taint_states {
+        let func = match fts.module.get_func_by_name(fn_name) {
+            Some(func) => func,
+            None => continue,
+        };
+        let taint_map = fts.get_taint_map();
+        let is_tainted = |name: &Name| taint_map.get(name).is_some_and(|ty| taint_result.is_type_tainted_if_known(ty));
+        let fn_analysis = FunctionAnalysis::new(func);
+        let cdg = fn_analysis.control_dependence_graph();
+        let defs: HashMap<&Name, &'m Instruction> = func.basic_blocks
+            .iter()
+            .flat_map(|bb| bb.instrs.iter())
+            .filter_map(|inst| inst.try_get_result().map(|dest| (dest, inst)))
+            .collect();
+
+        for bb in &func.basic_blocks {
+            for (access_index, inst) in bb.instrs.iter().enumerate() {
+                let (load, gep) = match inst {
+                    Instruction::Load(load) => match local_name(&load.address).and_then(|addr| defs.get(addr).copied()) {
+                        Some(Instruction::GetElementPtr(gep)) => (load, gep),
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                let index = match gep.indices.iter().filter_map(local_name).find(|index| is_tainted(index)) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                let bounds_check = match cdg
+                    .get_control_dependencies(&bb.name)
+                    .filter_map(|dep| func.get_bb_by_name(dep))
+                    .find(|dep| is_bounds_check(&defs, &dep.term, index))
+                {
+                    Some(dep) => &dep.name,
+                    None => continue,
+                };
+                if let Some((transmit_block, transmit_index)) = find_transmitter(func, &load.dest) {
+                    gadgets.push(SpectreGadget {
+                        function: fn_name,
+                        index,
+                        bounds_check,
+                        access_block: &bb.name,
+                        access_index,
+                        transmit_block,
+                        transmit_index,
+                    });
+                }
+            }
+        }
+    }
+    gadgets.sort_by(|a, b| (a.function, a.access_block, a.access_index).cmp(&(b.function, b.access_block, b.access_index)));
+    gadgets
+}
+
+fn local_name(op: &Operand) -> Option<&Name> {
+    match op {
+        Operand::LocalOperand { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Get the given value, and the values it was cast from (through integer
+/// extensions and truncations)
+fn cast_chain<'a>(defs: &HashMap<&Name, &'a Instruction>, mut name: &'a Name) -> Vec<&'a Name> {
+    let mut chain = vec![name];
+    loop {
+        let source = match defs.get(name).copied() {
+            Some(Instruction::ZExt(zext)) => &zext.operand,
+            Some(Instruction::SExt(sext)) => &sext.operand,
+            Some(Instruction::Trunc(trunc)) => &trunc.operand,
+            _ => return chain,
+        };
+        match local_name(source) {
+            Some(source) if !chain.contains(&source) => {
+                chain.push(source);
+                name = source;
+            },
+            _ => return chain,
+        }
+    }
+}
+
+/// Is the given terminator a conditional branch on a comparison involving
+/// `index` (or a value it was cast from or to)
+fn is_bounds_check<'a>(defs: &HashMap<&Name, &'a Instruction>, term: &'a Terminator, index: &'a Name) -> bool {
+    let condition = match term {
+        Terminator::CondBr(condbr) => &condbr.condition,
+        _ => return false,
+    };
+    let icmp = match local_name(condition).and_then(|cond| defs.get(cond).copied()) {
+        Some(Instruction::ICmp(icmp)) => icmp,
+        _ => return false,
+    };
+    let index_chain = cast_chain(defs, index);
+    [&icmp.operand0, &icmp.operand1]
+        .iter()
+        .filter_map(|op| local_name(op))
+        .any(|op| cast_chain(defs, op).iter().any(|name| index_chain.contains(name)))
+}
+
+/// Find the first instruction which may leak the result of the load defining
+/// `loaded`: a load whose address is computed from it, or failing that, a
+/// terminator branching on a value computed from it. Values are followed
+/// through computations within the function, but not through memory or
+/// calls.
+fn find_transmitter<'m>(func: &'m Function, loaded: &'m Name) -> Option<(&'m Name, Option<usize>)> {
+    let mut derived: HashSet<&Name> = std::iter::once(loaded).collect();
+    let depends = |derived: &HashSet<&Name>, ops: Vec<Operand>| ops.iter().filter_map(local_name).any(|op| derived.contains(op));
+    loop {
+        let mut changed = false;
+        for inst in func.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()) {
+            match inst {
+                Instruction::Load(_) | Instruction::Call(_) => continue,
+                _ => {},
+            }
+            if let Some(dest) = inst.try_get_result() {
+                if !derived.contains(dest) && depends(&derived, instruction_operands(inst)) {
+                    derived.insert(dest);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let load = func.basic_blocks.iter().find_map(|bb| {
+        bb.instrs.iter().enumerate().find_map(|(index, inst)| match inst {
+            Instruction::Load(load) if depends(&derived, vec![load.address.clone()]) => Some((&bb.name, Some(index))),
+            _ => None,
+        })
+    });
+    load.or_else(|| {
+        func.basic_blocks
+            .iter()
+            .find(|bb| match &bb.term {
+                Terminator::CondBr(_) | Terminator::Switch(_) => depends(&derived, terminator_operands(&bb.term)),
+                _ => false,
+            })
+            .map(|bb| (&bb.name, None))
+    })
+}
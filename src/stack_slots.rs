@@ -0,0 +1,265 @@
+use crate::jump_functions::JumpFunction;
+use crate::operands::{instruction_operands, terminator_operands};
+use either::Either;
+use llvm_ir::*;
+use llvm_ir_analysis::ControlFlowGraph;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Flow-sensitive information about the stack slots of a function, for
+/// `Config::flow_sensitive_memory` and `Config::promote_allocas`.
+///
+/// A stack slot is an `alloca` of a single scalar (integer, floating-point, or
+/// vector of those, or with `Config::promote_allocas`, a pointer) which
+/// doesn't escape: its address is only used directly as
+/// the address of loads and stores, and (possibly via a bitcast) as the
+/// argument to `llvm.lifetime.start` and `llvm.lifetime.end`. For each load
+/// from a stack slot, we record which stores to the slot can reach it. Since
+/// nothing else can write to a stack slot, each store to it overwrites its
+/// contents entirely (a strong update).
+pub(crate) struct StackSlots<'m> {
+    /// Map from (block name, index within the block) of each load from a stack
+    /// slot, to the stores to that slot which can reach it
+    reaching_stores: HashMap<(&'m Name, usize), Vec<ReachingStore<'m>>>,
+}
+
+/// A store which can reach a load from a stack slot. Stores are identified by
+/// their location, so equality and hashing ignore `value`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ReachingStore<'m> {
+    /// Name of the block containing the store
+    pub(crate) block: &'m Name,
+    /// Index of the store within its block
+    pub(crate) index: usize,
+    /// The value stored
+    pub(crate) value: &'m Operand,
+}
+
+impl<'m> PartialEq for ReachingStore<'m> {
+    fn eq(&self, other: &Self) -> bool {
+        self.block == other.block && self.index == other.index
+    }
+}
+
+impl<'m> Eq for ReachingStore<'m> {}
+
+impl<'m> Hash for ReachingStore<'m> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.block.hash(state);
+        self.index.hash(state);
+    }
+}
+
+/// What an instruction does to a stack slot
+enum SlotEffect<'m> {
+    /// Stores the given value to the slot, replacing its contents
+    Store(&'m Operand),
+    /// Starts a new lifetime of the slot (`llvm.lifetime.start`), after which
+    /// its contents are undefined, so no earlier store can reach a load
+    LifetimeStart,
+}
+
+impl<'m> StackSlots<'m> {
+    /// `include_pointers`: whether `alloca`s of pointers can be stack slots
+    pub(crate) fn new(f: &'m Function, cfg: &ControlFlowGraph<'m>, include_pointers: bool) -> Self {
+        let slots = find_stack_slots(f, include_pointers);
+        let slot_of = |op: &Operand| match op {
+            Operand::LocalOperand { name, .. } => slots.get(name).copied(),
+            _ => None,
+        };
+        // the effect of each instruction on each stack slot, if any
+        let effect = |inst: &'m Instruction| -> Option<(&'m Name, SlotEffect<'m>)> {
+            match inst {
+                Instruction::Store(store) => slot_of(&store.address).map(|slot| (slot, SlotEffect::Store(&store.value))),
+                Instruction::Call(call) if is_lifetime_start(call) => {
+                    let (op, _) = call.arguments.get(1)?;
+                    slot_of(op).map(|slot| (slot, SlotEffect::LifetimeStart))
+                },
+                _ => None,
+            }
+        };
+
+        // Reaching-definitions dataflow: the stores to each slot which can
+        // reach the end of each block. We iterate to a fixpoint, which must
+        // exist because the sets only grow.
+        type Reaching<'m> = HashMap<&'m Name, HashSet<ReachingStore<'m>>>;
+        let apply = |reaching: &mut Reaching<'m>, bb: &'m BasicBlock, index: usize, inst: &'m Instruction| {
+            match effect(inst) {
+                Some((slot, SlotEffect::Store(value))) => {
+                    // the slot is a unique allocation which nothing else can
+                    // write to, so this is a strong update: the store kills
+                    // all earlier stores to the slot
+                    reaching.insert(slot, std::iter::once(ReachingStore { block: &bb.name, index, value }).collect());
+                },
+                Some((slot, SlotEffect::LifetimeStart)) => {
+                    reaching.remove(slot);
+                },
+                None => {},
+            }
+        };
+        let transfer = |bb: &'m BasicBlock, mut reaching: Reaching<'m>| -> Reaching<'m> {
+            for (index, inst) in bb.instrs.iter().enumerate() {
+                apply(&mut reaching, bb, index, inst);
+            }
+            reaching
+        };
+        let reaching_in = |outs: &HashMap<&'m Name, Reaching<'m>>, bb: &'m BasicBlock| -> Reaching<'m> {
+            let mut reaching: Reaching<'m> = HashMap::new();
+            for pred in cfg.preds(&bb.name) {
+                for (slot, stores) in outs.get(pred).into_iter().flatten() {
+                    reaching.entry(slot).or_default().extend(stores.iter().copied());
+                }
+            }
+            reaching
+        };
+        let mut outs: HashMap<&'m Name, Reaching<'m>> = HashMap::new();
+        let mut changed = !slots.is_empty();
+        while changed {
+            changed = false;
+            for bb in &f.basic_blocks {
+                let out = transfer(bb, reaching_in(&outs, bb));
+                if outs.get(&bb.name) != Some(&out) {
+                    outs.insert(&bb.name, out);
+                    changed = true;
+                }
+            }
+        }
+
+        // now record the stores reaching each load
+        let mut reaching_stores = HashMap::new();
+        if !slots.is_empty() {
+            for bb in &f.basic_blocks {
+                let mut reaching = reaching_in(&outs, bb);
+                for (index, inst) in bb.instrs.iter().enumerate() {
+                    if let Instruction::Load(load) = inst {
+                        if let Some(slot) = slot_of(&load.address) {
+                            let mut stores: Vec<ReachingStore<'m>> = reaching.get(slot).into_iter().flatten().copied().collect();
+                            stores.sort_by_key(|store| (store.block, store.index));
+                            reaching_stores.insert((&bb.name, index), stores);
+                        }
+                    }
+                    apply(&mut reaching, bb, index, inst);
+                }
+            }
+        }
+        Self { reaching_stores }
+    }
+
+    /// If the instruction at the given index in the given block is a load from
+    /// a stack slot, get the stores which can reach it
+    pub(crate) fn reaching_stores(&self, block: &'m Name, index: usize) -> Option<&[ReachingStore<'m>]> {
+        self.reaching_stores.get(&(block, index)).map(|stores| stores.as_slice())
+    }
+}
+
+/// Is this a call to `llvm.lifetime.start`?
+fn is_lifetime_start(call: &instruction::Call) -> bool {
+    match &call.function {
+        Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+            Constant::GlobalReference { name: Name::Name(name), .. } => name.starts_with("llvm.lifetime.start."),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Is this a call to a function which may return twice, like `setjmp`?
+fn returns_twice(call: &instruction::Call) -> bool {
+    call.function_attributes.contains(&function::FunctionAttribute::ReturnsTwice)
+        || match &call.function {
+            Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+                Constant::GlobalReference { name: Name::Name(name), .. } => JumpFunction::from_name(name) == Some(JumpFunction::Setjmp),
+                _ => false,
+            },
+            _ => false,
+        }
+}
+
+/// Is this a call to `llvm.lifetime.start` or `llvm.lifetime.end`?
+fn is_lifetime_marker(call: &instruction::Call) -> bool {
+    match &call.function {
+        Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+            Constant::GlobalReference { name: Name::Name(name), .. } => name.starts_with("llvm.lifetime.start.") || name.starts_with("llvm.lifetime.end."),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Find the stack slots of the given function, including those holding
+/// pointers if `include_pointers`. Returns a map from the name of each slot's
+/// `alloca`, and of each bitcast of it used in lifetime markers, to the name
+/// of the slot's `alloca`.
+fn find_stack_slots(f: &Function, include_pointers: bool) -> HashMap<Name, &Name> {
+    let instrs = || f.basic_blocks.iter().flat_map(|bb| bb.instrs.iter());
+    // the control flow graph misses the second return of a `setjmp`, so
+    // reaching stores can't be trusted in functions which call one
+    let calls_returns_twice = instrs().any(|inst| matches!(inst, Instruction::Call(call) if returns_twice(call)));
+    if calls_returns_twice {
+        return HashMap::new();
+    }
+    let is_scalar = |ty: &Type| match ty {
+        Type::IntegerType { .. } | Type::FPType(_) => true,
+        Type::VectorType { element_type, .. } => matches!(element_type.as_ref(), Type::IntegerType { .. } | Type::FPType(_)),
+        Type::PointerType { .. } => include_pointers,
+        _ => false,
+    };
+    let is_name = |op: &Operand, name: &Name| matches!(op, Operand::LocalOperand { name: n, .. } if n == name);
+    // number of times each local is used as an operand anywhere in the function
+    let mut uses: HashMap<&Name, usize> = HashMap::new();
+    let all_operands: Vec<Operand> = instrs()
+        .flat_map(instruction_operands)
+        .chain(f.basic_blocks.iter().flat_map(|bb| terminator_operands(&bb.term)))
+        .collect();
+    for op in &all_operands {
+        if let Operand::LocalOperand { name, .. } = op {
+            *uses.entry(name).or_default() += 1;
+        }
+    }
+    let num_uses = |name: &Name| uses.get(name).copied().unwrap_or(0);
+
+    let mut slots = HashMap::new();
+    for alloca in instrs().filter_map(|inst| match inst {
+        Instruction::Alloca(alloca) => Some(alloca),
+        _ => None,
+    }) {
+        let is_single_element = matches!(&alloca.num_elements, Operand::ConstantOperand(cref) if matches!(cref.as_ref(), Constant::Int { value: 1, .. }));
+        if !is_single_element || !is_scalar(&alloca.allocated_type) {
+            continue;
+        }
+        let slot = &alloca.dest;
+        // bitcasts of the slot which are only used in lifetime markers
+        let marker_casts: Vec<&Name> = instrs()
+            .filter_map(|inst| match inst {
+                Instruction::BitCast(bc) if is_name(&bc.operand, slot) => Some(&bc.dest),
+                _ => None,
+            })
+            .collect();
+        let marker_uses = |name: &Name| {
+            instrs()
+                .filter(|inst| matches!(inst, Instruction::Call(call) if is_lifetime_marker(call) && call.arguments.iter().any(|(op, _)| is_name(op, name))))
+                .count()
+        };
+        if marker_casts.iter().any(|cast| marker_uses(cast) != num_uses(cast)) {
+            continue;
+        }
+        // every other use of the slot must be as the address of a load or store
+        let allowed_uses = instrs()
+            .map(|inst| match inst {
+                Instruction::Load(load) if is_name(&load.address, slot) => 1,
+                Instruction::Store(store) if is_name(&store.address, slot) && !is_name(&store.value, slot) => 1,
+                Instruction::BitCast(bc) if is_name(&bc.operand, slot) => 1,
+                Instruction::Call(call) if is_lifetime_marker(call) => call.arguments.iter().filter(|(op, _)| is_name(op, slot)).count(),
+                _ => 0,
+            })
+            .sum::<usize>();
+        if allowed_uses != num_uses(slot) {
+            continue;
+        }
+        slots.insert(slot.clone(), slot);
+        for cast in marker_casts {
+            slots.insert(cast.clone(), slot);
+        }
+    }
+    slots
+}
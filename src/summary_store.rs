@@ -0,0 +1,149 @@
+use crate::config::Config;
+use crate::operands::{instruction_operands, terminator_operands};
+use crate::taint_result::called_global;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use either::Either;
+use llvm_ir::{Constant, Function, Instruction, Module, Name, Operand, Terminator, Type};
+use std::collections::HashMap;
+
+/// Summaries of the leaf functions of some module(s), computed once and then
+/// shared, read-only, between any number of analyses of those module(s) (see
+/// `TaintState::use_summary_store()` and `Config::share_leaf_summaries`).
+/// Calls of a function in the store are answered from its summary, so the
+/// function isn't analyzed again for every entry point.
+///
+/// A leaf function here is one whose parameters and return value are all
+/// integers or floating-point values, and which calls nothing (other than
+/// debug intrinsics), refers to no globals, and creates no pointers from
+/// integers. Whether its result is tainted then depends only on which of its
+/// arguments are, so each leaf function is summarized by analyzing it once
+/// per parameter.
+#[derive(Clone, Debug, Default)]
+pub struct SummaryStore {
+    /// Map from function name to the summary of that function
+    summaries: HashMap<String, LeafSummary>,
+}
+
+#[derive(Clone, Debug)]
+struct LeafSummary {
+    /// Is the return value tainted even if no argument is
+    always_tainted: bool,
+    /// For each parameter, is the return value tainted if that argument is
+    tainted_by_param: Vec<bool>,
+}
+
+impl SummaryStore {
+    /// Summarize all the leaf functions in the given module(s), other than
+    /// those in `Config::context_only_modules`, analyzing them with the given
+    /// `Config`. The store should only be used in analyses with the same
+    /// `Config`.
+    pub fn new<'m>(modules: impl IntoIterator<Item = &'m Module>, config: &'m Config) -> Self {
+        let modules: Vec<&'m Module> = modules.into_iter().collect();
+        let mut summaries = HashMap::new();
+        for module in modules.iter().filter(|module| !config.is_context_only(module)) {
+            for func in module.functions.iter().filter(|func| is_leaf(func)) {
+                let summary = if func.return_type.as_ref() == &Type::VoidType {
+                    // nothing to be tainted
+                    LeafSummary {
+                        always_tainted: false,
+                        tainted_by_param: vec![false; func.parameters.len()],
+                    }
+                } else {
+                    let is_ret_tainted = |tainted_param: Option<usize>| {
+                        let args = (0 .. func.parameters.len())
+                            .map(|i| if Some(i) == tainted_param { TaintedType::TaintedValue } else { TaintedType::UntaintedValue })
+                            .collect();
+                        let result = TaintState::do_analysis_single_function(modules.iter().copied(), config, &func.name, Some(args), HashMap::new(), HashMap::new())
+                            .into_taint_result();
+                        let fts = &result.fn_taint_states[func.name.as_str()];
+                        func.basic_blocks
+                            .iter()
+                            .any(|bb| matches!(bb.term, Terminator::Ret(_)) && fts.is_terminator_tainted(&bb.name))
+                    };
+                    LeafSummary {
+                        always_tainted: is_ret_tainted(None),
+                        tainted_by_param: (0 .. func.parameters.len()).map(|i| is_ret_tainted(Some(i))).collect(),
+                    }
+                };
+                summaries.insert(func.name.clone(), summary);
+            }
+        }
+        Self { summaries }
+    }
+
+    /// Get the names of the functions in the store, sorted
+    pub fn functions(&self) -> Vec<&str> {
+        let mut fn_names: Vec<&str> = self.summaries.keys().map(String::as_str).collect();
+        fn_names.sort_unstable();
+        fn_names
+    }
+
+    /// Is the result of the given function tainted, given whether each of its
+    /// arguments is tainted?
+    ///
+    /// Returns `None` if the function isn't in the store.
+    pub(crate) fn is_ret_tainted(&self, fn_name: &str, arg_taint: &[bool]) -> Option<bool> {
+        let summary = self.summaries.get(fn_name)?;
+        Some(summary.always_tainted || summary.tainted_by_param.iter().zip(arg_taint).any(|(&by_param, &tainted)| by_param && tainted))
+    }
+}
+
+/// Is the given function a leaf function, as described on `SummaryStore`?
+fn is_leaf(func: &Function) -> bool {
+    let is_scalar = |ty: &Type| matches!(ty, Type::IntegerType { .. } | Type::FPType(_));
+    if func.basic_blocks.is_empty() || func.is_var_arg {
+        return false;
+    }
+    if !func.parameters.iter().all(|param| is_scalar(param.ty.as_ref())) {
+        return false;
+    }
+    if !(is_scalar(func.return_type.as_ref()) || func.return_type.as_ref() == &Type::VoidType) {
+        return false;
+    }
+    for bb in &func.basic_blocks {
+        for inst in &bb.instrs {
+            match inst {
+                Instruction::Call(call) => match &call.function {
+                    Either::Right(Operand::ConstantOperand(cref)) => match called_global(cref) {
+                        Some(Name::Name(callee)) if callee.starts_with("llvm.dbg.") => continue,
+                        _ => return false,
+                    },
+                    _ => return false,
+                },
+                Instruction::IntToPtr(_) | Instruction::VAArg(_) => return false,
+                _ => {},
+            }
+            if instruction_operands(inst).iter().any(mentions_global) {
+                return false;
+            }
+        }
+        match &bb.term {
+            Terminator::Ret(_) | Terminator::Br(_) | Terminator::CondBr(_) | Terminator::Switch(_) | Terminator::Unreachable(_) => {
+                if terminator_operands(&bb.term).iter().any(mentions_global) {
+                    return false;
+                }
+            },
+            // calls, and exception handling
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Might the given operand refer to a global? Constant expressions are
+/// assumed to, rather than looking inside them.
+fn mentions_global(op: &Operand) -> bool {
+    fn constant_mentions_global(constant: &Constant) -> bool {
+        match constant {
+            Constant::Int { .. } | Constant::Float(_) | Constant::Null(_) | Constant::AggregateZero(_) | Constant::Undef(_) => false,
+            Constant::Struct { values, .. } => values.iter().any(|value| constant_mentions_global(value)),
+            Constant::Array { elements, .. } | Constant::Vector(elements) => elements.iter().any(|element| constant_mentions_global(element)),
+            _ => true,
+        }
+    }
+    match op {
+        Operand::ConstantOperand(cref) => constant_mentions_global(cref),
+        Operand::LocalOperand { .. } | Operand::MetadataOperand => false,
+    }
+}
@@ -0,0 +1,212 @@
+use crate::taint_result::TaintResult;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{DebugLoc, HasDebugLoc, Name};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Format of the tables written by `TaintResult::write_table()` and
+/// `LabeledTaintResult::write_table()`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TableFormat {
+    /// Comma-separated values, quoted as in RFC 4180: fields containing a
+    /// comma, double quote, or line break are enclosed in double quotes, with
+    /// double quotes doubled
+    Csv,
+    /// Tab-separated values: tabs, line breaks, and backslashes in fields are
+    /// escaped as `\t`, `\n`, `\r`, and `\\`
+    Tsv,
+    /// A JSON array with one object per row, mapping each column name to the
+    /// field (a string), rather than a header line
+    Json,
+}
+
+/// Columns of the table, in order
+const HEADER: [&str; 6] = ["module", "function", "value", "status", "labels", "location"];
+
+/// One row of the table: a variable of a function, the contents of a global,
+/// or a field of a named struct
+#[derive(Clone, Debug)]
+pub(crate) struct Row {
+    /// Name of the module defining the value, or empty for named structs,
+    /// which are shared between modules
+    pub(crate) module: String,
+    /// Name of the function the variable belongs to, or empty for globals and
+    /// named struct fields
+    pub(crate) function: String,
+    /// `%name` of a variable, `@name` of a global, or struct name followed by
+    /// the field path (e.g. `struct.foo[1][0]`) of a named struct field
+    pub(crate) value: String,
+    /// Is the value tainted?
+    pub(crate) tainted: bool,
+    /// Labels of the value, for a labeled analysis
+    pub(crate) labels: String,
+    /// Source location (`file:line:col`) of the definition of the value, if
+    /// the module has debug info for it
+    pub(crate) location: String,
+}
+
+impl Row {
+    /// Key identifying the value the row describes, the same across analyses
+    /// of the same modules
+    pub(crate) fn key(&self) -> (&str, &str, &str) {
+        (&self.module, &self.function, &self.value)
+    }
+}
+
+/// Flatten the given `TaintResult` into rows: the variables of each function,
+/// sorted by function and then variable name; then the globals, sorted by
+/// name; then the fields of each named struct, sorted by struct name. Labels
+/// are left empty.
+pub(crate) fn rows(taint_result: &TaintResult) -> Vec<Row> {
+    let mut rows = Vec::new();
+
+    let mut fn_names: Vec<&&str> = taint_result.fn_taint_states.keys().collect();
+    fn_names.sort();
+    for fn_name in fn_names {
+        let fts = &taint_result.fn_taint_states[*fn_name];
+        let func = fts.module.get_func_by_name(fn_name);
+        // locations of the definitions of the function's variables
+        let mut locations: HashMap<&Name, String> = HashMap::new();
+        if let Some(func) = func {
+            let fn_location = location(func.get_debug_loc());
+            for param in &func.parameters {
+                locations.insert(&param.name, fn_location.clone());
+            }
+            for bb in &func.basic_blocks {
+                for inst in &bb.instrs {
+                    if let Some(dest) = inst.try_get_result() {
+                        locations.insert(dest, location(inst.get_debug_loc()));
+                    }
+                }
+            }
+        }
+        let taint_map = fts.get_taint_map();
+        let mut var_names: Vec<&Name> = taint_map.keys().collect();
+        var_names.sort();
+        for var_name in var_names {
+            rows.push(Row {
+                module: fts.module.name.clone(),
+                function: fn_name.to_string(),
+                value: var_name.to_string(),
+                tainted: taint_result.is_type_tainted_if_known(&taint_map[var_name]),
+                labels: String::new(),
+                location: locations.get(var_name).cloned().unwrap_or_default(),
+            });
+        }
+    }
+
+    let mut global_names: Vec<&Name> = taint_result.global_types.keys().collect();
+    global_names.sort();
+    for global_name in global_names {
+        let global = taint_result.fn_taint_states
+            .values()
+            .map(|fts| fts.module)
+            .find_map(|module| module.global_vars.iter().find(|var| &var.name == global_name).map(|var| (module, var)));
+        rows.push(Row {
+            module: global.map(|(module, _)| module.name.clone()).unwrap_or_default(),
+            function: String::new(),
+            value: match global_name {
+                Name::Name(name) => format!("@{}", name),
+                Name::Number(n) => format!("@{}", n),
+            },
+            tainted: taint_result.is_global_tainted(global_name),
+            labels: String::new(),
+            location: global.map(|(_, var)| location(var.get_debug_loc())).unwrap_or_default(),
+        });
+    }
+
+    let mut struct_names: Vec<&String> = taint_result.named_struct_types.keys().collect();
+    struct_names.sort();
+    for struct_name in struct_names {
+        push_field_rows(taint_result, &mut rows, struct_name.clone(), &taint_result.named_struct_types[struct_name]);
+    }
+
+    rows
+}
+
+/// Push a row for each field of the given type (the contents of a named
+/// struct, or one of its fields), recursing into fields which are themselves
+/// (unnamed) structs. Fields which are named structs get their own rows under
+/// their own name, so aren't recursed into.
+fn push_field_rows(taint_result: &TaintResult, rows: &mut Vec<Row>, path: String, ty: &TaintedType) {
+    match ty {
+        TaintedType::Struct(elements) => {
+            for (i, element) in elements.iter().enumerate() {
+                push_field_rows(taint_result, rows, format!("{}[{}]", path, i), &element.ty());
+            }
+        },
+        ty => rows.push(Row {
+            module: String::new(),
+            function: String::new(),
+            value: path,
+            tainted: taint_result.is_type_tainted_if_known(ty),
+            labels: String::new(),
+            location: String::new(),
+        }),
+    }
+}
+
+/// Format the given debug location as `file:line:col`, or `file:line` if it
+/// has no column. Empty if there is no debug location.
+fn location(debugloc: &Option<DebugLoc>) -> String {
+    match debugloc {
+        Some(DebugLoc { filename, line, col: Some(col), .. }) => format!("{}:{}:{}", filename, line, col),
+        Some(DebugLoc { filename, line, col: None, .. }) => format!("{}:{}", filename, line),
+        None => String::new(),
+    }
+}
+
+/// Write the given rows to `writer` as a table in the given format, with a
+/// header line naming the columns (except for JSON)
+pub(crate) fn write_table(rows: impl IntoIterator<Item = Row>, format: TableFormat, mut writer: impl Write) -> Result<(), String> {
+    if format == TableFormat::Json {
+        let objects: Vec<Value> = rows
+            .into_iter()
+            .map(|row| {
+                let status = if row.tainted { "tainted" } else { "untainted" };
+                let fields = [row.module.as_str(), &row.function, &row.value, status, &row.labels, &row.location];
+                Value::Object(HEADER.iter().zip(fields).map(|(column, field)| (column.to_string(), json!(field))).collect())
+            })
+            .collect();
+        return serde_json::to_writer_pretty(writer, &objects).map_err(|e| format!("Failed to write table: {}", e));
+    }
+    let mut table = String::new();
+    push_line(&mut table, format, &HEADER);
+    for row in rows {
+        let status = if row.tainted { "tainted" } else { "untainted" };
+        push_line(&mut table, format, &[&row.module, &row.function, &row.value, status, &row.labels, &row.location]);
+    }
+    writer.write_all(table.as_bytes()).map_err(|e| format!("Failed to write table: {}", e))
+}
+
+/// Push one line with the given fields to `table`
+fn push_line(table: &mut String, format: TableFormat, fields: &[&str]) {
+    let separator = match format {
+        TableFormat::Csv => ",",
+        TableFormat::Tsv => "\t",
+        TableFormat::Json => unreachable!("JSON tables have no lines"),
+    };
+    let fields: Vec<String> = fields.iter().map(|field| escape(field, format)).collect();
+    table.push_str(&fields.join(separator));
+    table.push('\n');
+}
+
+/// Escape the given field for the given format
+fn escape(field: &str, format: TableFormat) -> String {
+    match format {
+        TableFormat::Csv => {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        },
+        TableFormat::Tsv => field
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r"),
+        TableFormat::Json => unreachable!("JSON tables have no lines"),
+    }
+}
@@ -0,0 +1,179 @@
+use crate::annotations::Annotations;
+use crate::config::{Config, ExternalFunctionHandling};
+use crate::format_functions::FormatFunction;
+use crate::modules::Modules;
+use crate::operands::{instruction_operands, terminator_operands};
+use either::Either;
+use llvm_ir::{Constant, Function, Instruction, Name, Operand};
+use llvm_ir_analysis::CrossModuleAnalysis;
+use std::collections::HashSet;
+
+/// Find the functions which may observe taint (see
+/// `Config::skip_taint_blind_functions`). All other functions are
+/// _taint-blind_: everything in them is untainted, whatever the rest of the
+/// program does, so the analysis can skip them entirely.
+///
+/// A function may observe taint if it's one of the `tainted_entries` (the
+/// entry points given tainted arguments or nonarguments), or calls something
+/// which may introduce taint (an external function not handled as
+/// `IgnoreAndReturnUntainted` or `PropagateTaint*`, a source, or inline
+/// assembly), or is connected to such a function in the call graph (in either
+/// direction, since taint flows both into callees and back to callers). All
+/// functions which refer to a mutable global are connected to each other
+/// through its contents, as are those which refer to a global annotated as a
+/// source (for which the annotations must be given).
+///
+/// This is a cheap pass over the instructions, not a fixpoint, so it's quite
+/// conservative; but on large codebases many functions are still taint-blind.
+pub(crate) fn observing_functions<'m>(
+    analysis: &CrossModuleAnalysis<'m>,
+    config: &Config,
+    annotations: &Annotations,
+    tainted_entries: impl IntoIterator<Item = &'m str>,
+) -> HashSet<&'m str> {
+    let mut globals: HashSet<&Name> = mutable_globals(analysis);
+    globals.extend(annotations.source_globals().map(|(name, _)| name));
+    let mut seeds: Vec<&'m str> = Vec::new();
+    // functions which refer to mutable globals, which all observe taint once
+    // any of them does
+    let mut global_users: Vec<&'m str> = Vec::new();
+    for func in analysis.functions() {
+        let name = func.name.as_str();
+        if refers_to_globals(func, &globals) {
+            global_users.push(name);
+        }
+        if introduces_taint(func, analysis, config, annotations) {
+            seeds.push(name);
+        }
+    }
+    seeds.extend(tainted_entries);
+    let globals_observed = annotations.source_globals().next().is_some();
+    let call_graph = analysis.call_graph();
+    connected_functions(|f| call_graph.callees(f).chain(call_graph.callers(f)), seeds, global_users, globals_observed)
+}
+
+/// Find the functions connected to any of the `seeds` (including the seeds
+/// themselves), through `neighbors` (the call graph in either direction), or
+/// through the contents of globals: once any of the `global_users` is
+/// connected (or from the start, if `globals_observed`), all of them are.
+pub(crate) fn connected_functions<'m, N: IntoIterator<Item = &'m str>>(
+    neighbors: impl Fn(&'m str) -> N,
+    seeds: impl IntoIterator<Item = &'m str>,
+    mut global_users: Vec<&'m str>,
+    mut globals_observed: bool,
+) -> HashSet<&'m str> {
+    let mut observing: HashSet<&'m str> = HashSet::new();
+    let mut queue: Vec<&'m str> = Vec::new();
+    for seed in seeds {
+        if observing.insert(seed) {
+            queue.push(seed);
+        }
+    }
+
+    loop {
+        while let Some(f) = queue.pop() {
+            for neighbor in neighbors(f) {
+                if observing.insert(neighbor) {
+                    queue.push(neighbor);
+                }
+            }
+        }
+        if !globals_observed && global_users.iter().any(|f| observing.contains(f)) {
+            globals_observed = true;
+        }
+        if globals_observed {
+            queue.extend(global_users.drain(..).filter(|f| observing.insert(*f)));
+        }
+        if queue.is_empty() {
+            break;
+        }
+    }
+    observing
+}
+
+/// Find the mutable globals of the module(s): those whose contents can carry
+/// taint from one function to another
+pub(crate) fn mutable_globals<'m>(analysis: &CrossModuleAnalysis<'m>) -> HashSet<&'m Name> {
+    analysis
+        .modules()
+        .flat_map(|module| module.global_vars.iter())
+        .filter(|var| !var.is_constant)
+        .map(|var| &var.name)
+        .collect()
+}
+
+/// May the given function introduce taint itself, i.e., does it call
+/// something which may?
+fn introduces_taint(func: &Function, analysis: &CrossModuleAnalysis, config: &Config, annotations: &Annotations) -> bool {
+    func.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()).any(|inst| match inst {
+        Instruction::Call(call) => match &call.function {
+            Either::Left(_) => true, // inline assembly
+            Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+                Constant::GlobalReference { name: Name::Name(callee), .. } => {
+                    may_introduce_taint(callee, analysis, config, annotations)
+                },
+                _ => false,
+            },
+            Either::Right(_) => false, // the call graph covers the possible callees
+        },
+        _ => false,
+    })
+}
+
+/// Does the given function refer to any of the given globals?
+pub(crate) fn refers_to_globals(func: &Function, globals: &HashSet<&Name>) -> bool {
+    func.basic_blocks
+        .iter()
+        .flat_map(|bb| bb.instrs.iter().flat_map(instruction_operands).chain(terminator_operands(&bb.term)))
+        .any(|op| match op {
+            Operand::ConstantOperand(cref) => refers_to_global(&cref, globals),
+            _ => false,
+        })
+}
+
+/// May a call of the function with the given name introduce taint, no matter
+/// what's passed to it?
+fn may_introduce_taint(callee: &str, analysis: &CrossModuleAnalysis, config: &Config, annotations: &Annotations) -> bool {
+    if annotations.is_source_function(callee) {
+        true
+    } else if let Some(intrinsic) = callee.strip_prefix("llvm.") {
+        if config.register_reads_tainted {
+            intrinsic.starts_with("read_register") || intrinsic.starts_with("read_volatile_register")
+        } else {
+            false
+        }
+    } else if analysis.get_func_by_name(callee).is_some_and(|(_, module)| !config.is_context_only(module)) {
+        false
+    } else if let Some(format_fn) = FormatFunction::from_name(callee).filter(|_| config.model_format_functions) {
+        // only reading from a stream introduces taint
+        matches!(format_fn, FormatFunction::Scan { input: None, .. }) && config.stream_input_tainted
+    } else {
+        let modules: Modules = analysis.modules().collect();
+        let defining_module = analysis.get_func_by_name(callee).map(|(_, module)| module);
+        let handling = config
+            .get_ext_function(callee)
+            .unwrap_or_else(|| config.ext_functions_default_for(defining_module.into_iter().chain(modules.modules_calling(callee))));
+        !matches!(
+            handling,
+            ExternalFunctionHandling::IgnoreAndReturnUntainted
+                | ExternalFunctionHandling::PropagateTaintShallow
+                | ExternalFunctionHandling::PropagateTaintDeep
+        )
+    }
+}
+
+/// Does the given constant refer to any of the given globals, directly or
+/// through a constant expression?
+fn refers_to_global(constant: &Constant, globals: &HashSet<&Name>) -> bool {
+    match constant {
+        Constant::GlobalReference { name, .. } => globals.contains(name),
+        Constant::GetElementPtr(gep) => refers_to_global(&gep.address, globals),
+        Constant::BitCast(cast) => refers_to_global(&cast.operand, globals),
+        Constant::AddrSpaceCast(cast) => refers_to_global(&cast.operand, globals),
+        Constant::PtrToInt(cast) => refers_to_global(&cast.operand, globals),
+        Constant::Struct { values, .. } => values.iter().any(|value| refers_to_global(value, globals)),
+        Constant::Array { elements, .. } => elements.iter().any(|element| refers_to_global(element, globals)),
+        Constant::Vector(elements) => elements.iter().any(|element| refers_to_global(element, globals)),
+        _ => false,
+    }
+}
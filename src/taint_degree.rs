@@ -0,0 +1,219 @@
+use crate::config::Config;
+use crate::labels::{self, LabeledTaintResult, TaintLabel};
+use crate::named_structs::NamedStructInitialDef;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{Module, Name};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// A source of taint in a taint-degree analysis (see
+/// `do_taint_degree_analysis_on_function()`)
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TaintSource {
+    /// The argument with the given index to the function with the given name
+    Arg { function: String, index: usize },
+    /// The nonargument variable with the given name, in the function with the
+    /// given name
+    NonArg { function: String, name: Name },
+    /// The initial definition of the named struct with the given name
+    NamedStruct(String),
+    /// The functions configured to return tainted data or annotated as
+    /// sources (see `TaintLabel::ext_source()`), together
+    ExtFunctions,
+}
+
+/// The set of sources whose data may flow to a value. Sets are joined by
+/// union, with the empty set (untainted) as bottom.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub(crate) struct SourceSet(pub(crate) BTreeSet<TaintSource>);
+
+impl TaintLabel for SourceSet {
+    fn bottom() -> Self {
+        SourceSet(BTreeSet::new())
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        SourceSet(self.0.union(&other.0).cloned().collect())
+    }
+
+    fn ext_source() -> Self {
+        SourceSet::singleton(TaintSource::ExtFunctions)
+    }
+}
+
+impl SourceSet {
+    pub(crate) fn singleton(source: TaintSource) -> Self {
+        SourceSet(std::iter::once(source).collect())
+    }
+}
+
+/// The result of a taint-degree analysis: for each value, which of the sources
+/// (and how many) its data may come from
+pub struct TaintDegreeResult<'m> {
+    labeled: LabeledTaintResult<'m, SourceSet>,
+}
+
+impl<'m> TaintDegreeResult<'m> {
+    /// Get the taint degree of a variable by name: the number of distinct
+    /// sources whose data may flow to it. This is 0 for untainted variables.
+    pub fn get_var_degree(&self, fn_name: &str, var_name: &Name) -> usize {
+        self.labeled.get_var_label(fn_name, var_name).0.len()
+    }
+
+    /// Get the sources whose data may flow to the given variable, sorted
+    pub fn get_var_sources(&self, fn_name: &str, var_name: &Name) -> Vec<TaintSource> {
+        self.labeled.get_var_label(fn_name, var_name).0.into_iter().collect()
+    }
+
+    /// Given a function name, returns a map from variable name to taint degree
+    /// for all the variables in that function
+    pub fn get_function_degree_map(&self, fn_name: &str) -> HashMap<Name, usize> {
+        self.labeled
+            .get_function_label_map(fn_name)
+            .into_iter()
+            .map(|(name, sources)| (name, sources.0.len()))
+            .collect()
+    }
+
+    /// Get the taint degree of the data returned by the function with the given
+    /// name, according to its final `FunctionSummary`s
+    pub fn get_return_degree(&self, fn_name: &str) -> usize {
+        self.labeled.get_return_label(fn_name).0.len()
+    }
+
+    /// Get a minimal set of sources whose taint alone suffices to taint all of
+    /// the given target variables, given as (function name, variable name)
+    /// pairs, sorted. Returns `None` if any of the targets is untainted.
+    ///
+    /// Since taint only ever flows by joining, one source of a variable (see
+    /// `get_var_sources()`) suffices to taint it, so for a single target this
+    /// is a single source. For several targets, the set is chosen greedily
+    /// and then pruned, so that no source in it can be removed; but it isn't
+    /// necessarily the smallest such set, which is expensive to find in
+    /// general.
+    pub fn get_minimal_sources(&self, targets: &[(&str, &Name)]) -> Option<Vec<TaintSource>> {
+        let target_sources: Vec<BTreeSet<TaintSource>> = targets
+            .iter()
+            .map(|(fn_name, var_name)| self.labeled.get_var_label(fn_name, var_name).0)
+            .collect();
+        if target_sources.iter().any(BTreeSet::is_empty) {
+            return None;
+        }
+        // greedily choose the source which taints the most remaining targets
+        // (the least such source, on ties)
+        let mut chosen: Vec<TaintSource> = Vec::new();
+        let mut remaining: Vec<&BTreeSet<TaintSource>> = target_sources.iter().collect();
+        while !remaining.is_empty() {
+            let mut counts: BTreeMap<&TaintSource, usize> = BTreeMap::new();
+            for sources in &remaining {
+                for source in sources.iter() {
+                    *counts.entry(source).or_default() += 1;
+                }
+            }
+            let best = counts
+                .into_iter()
+                .fold(None, |best: Option<(&TaintSource, usize)>, (source, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((source, count)),
+                })
+                .map(|(source, _)| source.clone())
+                .expect("remaining targets all have sources");
+            remaining.retain(|sources| !sources.contains(&best));
+            chosen.push(best);
+        }
+        // drop any source whose targets are all tainted by the others
+        let mut i = chosen.len();
+        while i > 0 {
+            i -= 1;
+            let others_suffice = target_sources
+                .iter()
+                .all(|sources| chosen.iter().enumerate().any(|(j, source)| j != i && sources.contains(source)));
+            if others_suffice {
+                chosen.remove(i);
+            }
+        }
+        chosen.sort();
+        Some(chosen)
+    }
+}
+
+/// Label each argument of the given function as its own source, unless it is
+/// entirely untainted
+pub(crate) fn label_args(modules: &[&Module], fn_name: &str, args: Vec<TaintedType>) -> Vec<(TaintedType, SourceSet)> {
+    args.into_iter()
+        .zip(labels::untainted_params(modules, fn_name))
+        .enumerate()
+        .map(|(index, (ty, untainted))| {
+            let label = if ty == untainted {
+                SourceSet::bottom()
+            } else {
+                SourceSet::singleton(TaintSource::Arg { function: fn_name.into(), index })
+            };
+            (ty, label)
+        })
+        .collect()
+}
+
+/// Label each nonargument of the given function as its own source
+pub(crate) fn label_nonargs(fn_name: &str, nonargs: HashMap<Name, TaintedType>) -> HashMap<Name, (TaintedType, SourceSet)> {
+    nonargs
+        .into_iter()
+        .map(|(name, ty)| {
+            let label = SourceSet::singleton(TaintSource::NonArg { function: fn_name.into(), name: name.clone() });
+            (name, (ty, label))
+        })
+        .collect()
+}
+
+/// Label each named struct definition as its own source, unless it is
+/// `AllFieldsUntainted`
+pub(crate) fn label_named_structs(named_structs: HashMap<String, NamedStructInitialDef>) -> HashMap<String, (NamedStructInitialDef, SourceSet)> {
+    named_structs
+        .into_iter()
+        .map(|(name, def)| {
+            let label = match def {
+                NamedStructInitialDef::AllFieldsUntainted => SourceSet::bottom(),
+                _ => SourceSet::singleton(TaintSource::NamedStruct(name.clone())),
+            };
+            (name, (def, label))
+        })
+        .collect()
+}
+
+pub(crate) fn analyze_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> TaintDegreeResult<'m> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let args = args.map(|args| label_args(&modules, start_fn_name, args));
+    let nonargs = label_nonargs(start_fn_name, nonargs);
+    let named_structs = label_named_structs(named_structs);
+    TaintDegreeResult {
+        labeled: labels::analyze_function(modules, config, start_fn_name, args, nonargs, named_structs),
+    }
+}
+
+pub(crate) fn analyze_module<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    args: HashMap<&'m str, Vec<TaintedType>>,
+    nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> TaintDegreeResult<'m> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let args = args
+        .into_iter()
+        .map(|(fn_name, args)| (fn_name, label_args(&modules, fn_name, args)))
+        .collect();
+    let nonargs = nonargs
+        .into_iter()
+        .map(|(fn_name, vars)| (fn_name, label_nonargs(fn_name, vars)))
+        .collect();
+    let named_structs = label_named_structs(named_structs);
+    TaintDegreeResult {
+        labeled: labels::analyze_module(modules, config, args, nonargs, named_structs),
+    }
+}
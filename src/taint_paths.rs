@@ -0,0 +1,116 @@
+use crate::taint_result::{matches_callee, DirectCall, TaintResult};
+use llvm_ir::Name;
+use std::collections::{HashMap, VecDeque};
+
+/// How taint crosses a call in a `TaintPathStep`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TaintFlow {
+    /// The callee returns tainted data to the caller
+    Return,
+    /// The caller passes tainted data to the callee
+    Argument,
+}
+
+/// One step of a path along which taint propagates from a source function to
+/// a sink function (see `TaintResult::find_taint_paths()`): a call which taint
+/// crosses, in the direction given by `flow`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TaintPathStep<'m> {
+    /// Name of the calling function
+    pub caller: &'m str,
+    /// Name of the block containing the call
+    pub block: &'m Name,
+    /// Index of the call within the block
+    pub index: usize,
+    /// Name of the called function
+    pub callee: &'m str,
+    /// Whether taint crosses the call into the callee, or back to the caller
+    pub flow: TaintFlow,
+}
+
+impl<'m> TaintPathStep<'m> {
+    fn new(call: &DirectCall<'m>, flow: TaintFlow) -> Self {
+        Self {
+            caller: call.caller,
+            block: call.block,
+            index: call.index,
+            callee: call.callee,
+            flow,
+        }
+    }
+}
+
+/// Where taint is in a partial path: in the given function, either still
+/// being returned up the call chain from the source, or already being passed
+/// down a call chain towards the sink
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Position<'m> {
+    Returning(&'m str),
+    Passing(&'m str),
+}
+
+/// Find the paths from `source` to `sink`. See `TaintResult::find_taint_paths()`.
+pub(crate) fn find_taint_paths<'m>(taint_result: &TaintResult<'m>, source: &str, sink: &str, max_paths: usize) -> Vec<Vec<TaintPathStep<'m>>> {
+    let mut calls = taint_result.direct_calls();
+    calls.sort_by(|a, b| (a.caller, a.block, a.index).cmp(&(b.caller, b.block, b.index)));
+    // calls returning tainted data, by callee
+    let mut tainted_returns: HashMap<&'m str, Vec<&DirectCall<'m>>> = HashMap::new();
+    // calls passed tainted data, by caller
+    let mut tainted_args: HashMap<&'m str, Vec<&DirectCall<'m>>> = HashMap::new();
+    for call in &calls {
+        if call.ret_tainted {
+            tainted_returns.entry(call.callee).or_default().push(call);
+        }
+        if call.args_tainted {
+            tainted_args.entry(call.caller).or_default().push(call);
+        }
+    }
+
+    // breadth-first, so that shorter paths are found first. Each partial path
+    // is kept with the positions it has visited, so that it stays simple.
+    let mut paths = Vec::new();
+    let mut queue: VecDeque<(Vec<TaintPathStep<'m>>, Vec<Position<'m>>)> = calls
+        .iter()
+        .filter(|call| call.ret_tainted && matches_callee(call.callee, source))
+        .map(|call| (vec![TaintPathStep::new(call, TaintFlow::Return)], vec![Position::Returning(call.caller)]))
+        .collect();
+    while let Some((path, visited)) = queue.pop_front() {
+        if paths.len() >= max_paths {
+            break;
+        }
+        let (fn_name, returning) = match visited.last().copied() {
+            Some(Position::Returning(fn_name)) => (fn_name, true),
+            Some(Position::Passing(fn_name)) => (fn_name, false),
+            None => continue,
+        };
+        let mut extend = |call: &DirectCall<'m>, flow: TaintFlow, position: Position<'m>| {
+            if !visited.contains(&position) {
+                let mut path = path.clone();
+                path.push(TaintPathStep::new(call, flow));
+                let mut visited = visited.clone();
+                visited.push(position);
+                queue.push_back((path, visited));
+            }
+        };
+        if returning {
+            // the taint may continue up to the callers which get it back
+            for call in tainted_returns.get(fn_name).into_iter().flatten() {
+                extend(call, TaintFlow::Return, Position::Returning(call.caller));
+            }
+        }
+        // or down to the callees this function passes tainted data to
+        for call in tainted_args.get(fn_name).into_iter().flatten() {
+            if matches_callee(call.callee, sink) {
+                let mut path = path.clone();
+                path.push(TaintPathStep::new(call, TaintFlow::Argument));
+                paths.push(path);
+                if paths.len() >= max_paths {
+                    break;
+                }
+            } else if taint_result.fn_taint_states.contains_key(call.callee) {
+                extend(call, TaintFlow::Argument, Position::Passing(call.callee));
+            }
+        }
+    }
+    paths
+}
@@ -1,16 +1,54 @@
-use crate::function_taint_state::FunctionTaintState;
+use crate::annotated_ir;
+use crate::call_graph_export;
+use crate::cfg_dot;
+use crate::debug_info::DebugInfo;
+use crate::demangle;
+use crate::dependencies::Dependencies;
+use crate::function_summary::FunctionSummary;
+use crate::function_taint_state::{FunctionTaintState, DISPLAY_MAX_DEPTH};
+use crate::instrumentation::{self, InstrumentationGuidance};
+use crate::output_filter::OutputFilter;
+use crate::provenance::{self, ExplanationStep, Provenance};
+use crate::sarif;
+use crate::spectre::{self, SpectreGadget};
+use crate::table::{self, TableFormat};
+use crate::taint_paths::{self, TaintPathStep};
 use crate::tainted_type::TaintedType;
-use llvm_ir::Name;
+use either::Either;
+use llvm_ir::{Constant, Instruction, Name, Operand};
 use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::OnceLock;
 
 /// The result of taint-tracking analysis on LLVM module(s)
 pub struct TaintResult<'m> {
     /// Map from function name to the `FunctionTaintState` for that function
     pub(crate) fn_taint_states: HashMap<&'m str, FunctionTaintState<'m>>,
 
+    /// Map from function name to the final `FunctionSummary` for that function
+    pub(crate) fn_summaries: HashMap<&'m str, FunctionSummary<'m>>,
+
     /// Map from the name of a named struct, to the type for that struct's
     /// contents.
     pub(crate) named_struct_types: HashMap<String, TaintedType>,
+
+    /// Map from the name of a global, to the type for that global (always a
+    /// pointer to the global's contents), for the globals which the analysis
+    /// encountered
+    pub(crate) global_types: HashMap<Name, TaintedType>,
+
+    /// Propagation edges recorded during the analysis, if
+    /// `Config::record_provenance` was set
+    pub(crate) provenance: Provenance<'m>,
+
+    /// Map from the name of an entry point, to the result of analyzing from
+    /// just that entry point, if `Config::per_entry_results` was set
+    pub(crate) entry_results: HashMap<&'m str, TaintResult<'m>>,
+
+    /// The dependencies between functions, found on the first call of
+    /// `functions_affected_by()`
+    pub(crate) dependencies: OnceLock<Dependencies<'m>>,
 }
 
 impl<'m> TaintResult<'m> {
@@ -28,11 +66,355 @@ impl<'m> TaintResult<'m> {
             .get_taint_map()
     }
 
+    /// Get the (mangled) names of the functions with results which go by the
+    /// given name: either their mangled name, or their demangled name, with or
+    /// without the parameter types (see `demangle()`), sorted. There may be
+    /// several, e.g. for overloaded C++ functions.
+    pub fn find_functions(&self, name: &str) -> Vec<&'m str> {
+        let mut fn_names: Vec<&'m str> = self.fn_taint_states
+            .keys()
+            .copied()
+            .filter(|fn_name| demangle::names_for(fn_name).iter().any(|n| n == name))
+            .collect();
+        fn_names.sort_unstable();
+        fn_names
+    }
+
     /// Get the `TaintedType` for the given struct name.
     pub fn get_named_struct_type(&self, struct_name: &str) -> &TaintedType {
         self.named_struct_types.get(struct_name).unwrap_or_else(|| panic!("get_named_struct_type: unknown named struct: name {:?}", struct_name))
     }
 
+    /// Get the bits of the given integer variable in the given function which
+    /// may be tainted, as a mask, if `Config::bit_level_taint` is set and the
+    /// variable is the result of an instruction it tracks bits through.
+    /// Otherwise, returns `None`: the variable is tainted or untainted as a
+    /// whole (see `get_var_type()`).
+    pub fn get_tainted_bits(&self, fn_name: &str, var: &Name) -> Option<u64> {
+        self.fn_taint_states
+            .get(fn_name)
+            .unwrap_or_else(|| panic!("get_tainted_bits: no taint map found for function {:?}", fn_name))
+            .get_tainted_bits(var)
+    }
+
+    /// Get the instructions in the given function which pass tainted data to a
+    /// sink (e.g., a tainted `llvm.write_register`; see `Config`), as (block
+    /// name, index of the instruction within the block) pairs, sorted.
+    pub fn get_tainted_sinks(&self, fn_name: &str) -> Vec<(&Name, usize)> {
+        let mut sinks: Vec<(&Name, usize)> = self.fn_taint_states
+            .get(fn_name)
+            .unwrap_or_else(|| panic!("get_tainted_sinks: no taint map found for function {:?}", fn_name))
+            .get_tainted_sinks()
+            .map(|(block, index)| (block, *index))
+            .collect();
+        sinks.sort();
+        sinks
+    }
+
+    /// Get the calls of deallocators in the given function which are passed a
+    /// tainted pointer (see `Config::report_tainted_frees`), as (block name,
+    /// index of the call within the block) pairs, sorted.
+    pub fn get_tainted_frees(&self, fn_name: &str) -> Vec<(&Name, usize)> {
+        let mut frees: Vec<(&Name, usize)> = self.fn_taint_states
+            .get(fn_name)
+            .unwrap_or_else(|| panic!("get_tainted_frees: no taint map found for function {:?}", fn_name))
+            .get_tainted_frees()
+            .map(|(block, index)| (block, *index))
+            .collect();
+        frees.sort();
+        frees
+    }
+
+    /// Get the calls of format functions in the given function whose format
+    /// string is tainted (see `Config::report_tainted_format_strings`), as
+    /// (block name, index of the call within the block) pairs, sorted.
+    pub fn get_tainted_format_strings(&self, fn_name: &str) -> Vec<(&Name, usize)> {
+        let mut calls: Vec<(&Name, usize)> = self.fn_taint_states
+            .get(fn_name)
+            .unwrap_or_else(|| panic!("get_tainted_format_strings: no taint map found for function {:?}", fn_name))
+            .get_tainted_format_strings()
+            .map(|(block, index)| (block, *index))
+            .collect();
+        calls.sort();
+        calls
+    }
+
+    /// Get the direct calls of the function with the given name which are passed
+    /// tainted data: a tainted argument, or a pointer argument to tainted data.
+    /// Returns (calling function name, block name, index of the call within
+    /// the block) tuples, sorted, over all functions with results.
+    ///
+    /// The callee is matched by its mangled or demangled name, as in
+    /// `find_functions()`. A name such as `memcpy` also matches the
+    /// corresponding intrinsics (`llvm.memcpy.*`), which compilers often emit
+    /// in place of calls of the library function.
+    ///
+    /// Arguments are judged by their final `TaintedType`s, without regard to
+    /// any sanitization in effect at the call.
+    pub fn tainted_callsites_of(&self, callee: &str) -> Vec<(&'m str, &'m Name, usize)> {
+        let mut callsites: Vec<(&'m str, &'m Name, usize)> = self.direct_calls()
+            .into_iter()
+            .filter(|call| call.args_tainted && matches_callee(call.callee, callee))
+            .map(|call| (call.caller, call.block, call.index))
+            .collect();
+        callsites.sort();
+        callsites
+    }
+
+    /// Explain why the variable with the given name in the given function is
+    /// tainted (or, for a pointer which isn't itself tainted, why the data it
+    /// points to is): the steps of a derivation from a source to the
+    /// variable, source first. Each step is an instruction computing a tainted
+    /// value from the value of the previous step, a write of it to memory, or
+    /// its passage into or out of a call; see `ExplanationStep`.
+    ///
+    /// This follows the propagation edges recorded during the analysis, which
+    /// requires `Config::record_provenance`. Each value is explained by the
+    /// instruction which first tainted it, so this gives one derivation, not
+    /// necessarily the only one. The derivation ends early (with a `Source`
+    /// step) at data for which there's no record, e.g. memory tainted through
+    /// a pointer the analysis doesn't link to the one being read.
+    ///
+    /// Returns an error if provenance wasn't recorded, or the variable isn't
+    /// tainted.
+    pub fn explain(&self, fn_name: &str, var: &Name) -> Result<Vec<ExplanationStep<'m>>, String> {
+        provenance::explain(self, fn_name, var)
+    }
+
+    /// Find call chains along which taint propagates from the function named
+    /// `source` to the function named `sink`, shortest first, up to
+    /// `max_paths` of them. Both are matched as in `tainted_callsites_of()`.
+    ///
+    /// Each path starts with a call of `source` which returns tainted data;
+    /// the taint is returned up through zero or more callers, then passed down
+    /// through zero or more callees as tainted arguments, and the path ends
+    /// with a call of `sink` which is passed tainted data. Only calls which
+    /// taint actually crosses (according to the final taint of their
+    /// arguments and results) are followed, not every path in the call graph.
+    /// No function appears twice in the same direction in a path.
+    ///
+    /// Taint is tracked per value, not per source, so a path shows that each
+    /// of its calls carries taint, but not that the taint at the sink came
+    /// from that particular call of the source.
+    pub fn find_taint_paths(&self, source: &str, sink: &str, max_paths: usize) -> Vec<Vec<TaintPathStep<'m>>> {
+        taint_paths::find_taint_paths(self, source, sink, max_paths)
+    }
+
+    /// Determine which functions, allocations, and globals need dynamic taint
+    /// instrumentation, according to these results, for a hybrid flow where
+    /// only the part of the program which static analysis found to handle
+    /// tainted data is instrumented at runtime (e.g., with DataFlowSanitizer;
+    /// see `InstrumentationGuidance::write_dfsan_abilist()`).
+    ///
+    /// The guidance is only as sound as the analysis: functions it never
+    /// reached are listed as uninstrumented, so the start functions should
+    /// cover every way tainted data enters the program.
+    pub fn instrumentation_guidance(&self) -> InstrumentationGuidance<'m> {
+        instrumentation::instrumentation_guidance(self)
+    }
+
+    /// Find candidate Spectre-v1 (bounds check bypass) gadgets in the functions
+    /// with results, sorted by function and location of the first load. A
+    /// gadget is a load through a `getelementptr` with a tainted index, in a
+    /// block which is control-dependent on a conditional branch comparing that
+    /// index (possibly through integer casts), where the loaded value is then
+    /// used, through computations in the same function, in the address of
+    /// another load or in the condition of a branch.
+    ///
+    /// This is a syntactic search over the final results, meant to point out
+    /// code for review: it doesn't check that the comparison is actually an
+    /// upper bound, or that any of the instructions are reachable together.
+    pub fn find_spectre_gadgets(&self) -> Vec<SpectreGadget<'m>> {
+        spectre::find_spectre_gadgets(self)
+    }
+
+    /// Get all the direct calls in the functions with results, with whether
+    /// tainted data flows into and out of each, in no particular order.
+    ///
+    /// Arguments are judged by their final `TaintedType`s, without regard to
+    /// any sanitization in effect at the call.
+    pub(crate) fn direct_calls(&self) -> Vec<DirectCall<'m>> {
+        let mut calls = Vec::new();
+        for (fn_name, fts) in &self.fn_taint_states {
+            let func = match fts.module.get_func_by_name(fn_name) {
+                Some(func) => func,
+                None => continue,
+            };
+            let taint_map = fts.get_taint_map();
+            for bb in &func.basic_blocks {
+                for (index, inst) in bb.instrs.iter().enumerate() {
+                    let call = match inst {
+                        Instruction::Call(call) => call,
+                        _ => continue,
+                    };
+                    let callee = match &call.function {
+                        Either::Right(Operand::ConstantOperand(cref)) => match called_global(cref) {
+                            Some(Name::Name(name)) => name.as_str(),
+                            _ => continue,
+                        },
+                        _ => continue,
+                    };
+                    let args_tainted = call.arguments.iter().any(|(arg, _)| match fts.get_type_of_operand_unsanitized(arg) {
+                        Ok(TaintedType::UntaintedPointer(pointee)) => self.is_type_tainted_if_known(&pointee.ty()),
+                        Ok(ty) => self.is_type_tainted_if_known(&ty),
+                        Err(_) => false,
+                    });
+                    // the result at this call if it has one, otherwise (e.g.
+                    // for a struct returned through `sret`) the callee's summary
+                    let ret_tainted = match call.dest.as_ref().and_then(|dest| taint_map.get(dest)) {
+                        Some(ty) => self.is_type_tainted_if_known(ty),
+                        None => self.does_function_return_tainted(callee),
+                    };
+                    calls.push(DirectCall {
+                        caller: fn_name,
+                        block: &bb.name,
+                        index,
+                        callee,
+                        args_tainted,
+                        ret_tainted,
+                    });
+                }
+            }
+        }
+        calls
+    }
+
+    /// Get the instructions and terminators which the analysis had no specific
+    /// handling for (see `Config::unknown_instructions`), across all
+    /// functions, as (function name, block name, index of the instruction
+    /// within the block or `None` for the terminator, description of the
+    /// construct) tuples, sorted. An empty result means the analysis handled
+    /// every construct it encountered precisely.
+    pub fn get_unhandled_constructs(&self) -> Vec<(&'m str, &Name, Option<usize>, &str)> {
+        let mut constructs: Vec<(&'m str, &Name, Option<usize>, &str)> = self.fn_taint_states
+            .iter()
+            .flat_map(|(fn_name, fts)| {
+                fts.get_unhandled_constructs().map(move |((block, index), construct)| (*fn_name, block, *index, construct.as_str()))
+            })
+            .collect();
+        constructs.sort();
+        constructs
+    }
+
+    /// Get the places where the analysis applied one of its documented unsound
+    /// assumptions, across all functions, as (function name, block name, index
+    /// of the instruction within the block or `None` for the terminator,
+    /// description of the assumption) tuples, sorted. These are where a
+    /// verdict of "no taint" could be wrong:
+    ///   - `inttoptr`, whose result is assumed to point to untainted memory
+    ///     which nothing else points to
+    ///   - inline assembly, which is assumed not to access memory (see also
+    ///     `get_unhandled_constructs()`)
+    ///   - calls of external functions which aren't configured in
+    ///     `Config::ext_functions`, and calls through function pointers with no
+    ///     known targets, whose results are assumed untainted by default
+    pub fn get_unsound_assumptions(&self) -> Vec<(&'m str, &Name, Option<usize>, &str)> {
+        let mut assumptions: Vec<(&'m str, &Name, Option<usize>, &str)> = self.fn_taint_states
+            .iter()
+            .flat_map(|(fn_name, fts)| {
+                fts.get_assumptions().map(move |((block, index), assumption)| (*fn_name, block, *index, assumption.as_str()))
+            })
+            .collect();
+        assumptions.sort();
+        assumptions
+    }
+
+    /// Get the blocks which execute under tainted control, i.e., which are
+    /// control-dependent (directly or transitively) on a block with a tainted
+    /// terminator, as (function name, block name), sorted. Whether these
+    /// blocks execute at all depends on tainted data, so they may reveal it,
+    /// e.g. through timing; and phis where their paths re-converge are tainted
+    /// by implicit flow.
+    pub fn blocks_under_tainted_control(&self) -> Vec<(&'m str, &'m Name)> {
+        cfg_dot::blocks_under_tainted_control(self)
+    }
+
+    /// Write the control-flow graph of the function with the given name to
+    /// `writer`, in Graphviz (DOT) format. Each block lists its instructions,
+    /// each annotated with the final `TaintedType` of its result (flagged with
+    /// `[T]` if tainted); tainted terminators are likewise flagged. Blocks
+    /// which execute under tainted control, i.e., which are control-dependent
+    /// on a block with a tainted terminator, are filled in color.
+    ///
+    /// Returns an error if there is no taint map for the function, or writing
+    /// fails.
+    pub fn write_cfg_dot(&self, fn_name: &str, writer: impl Write) -> Result<(), String> {
+        cfg_dot::write_cfg_dot(self, fn_name, writer)
+    }
+
+    /// Write the functions with results to `writer` as textual LLVM IR, with
+    /// each instruction annotated, in a trailing comment, with the final
+    /// `TaintedType` of its result (flagged with `[T]` if tainted); tainted
+    /// terminators are likewise flagged. The parameters of each function and
+    /// the globals the analysis encountered are annotated in comments of their
+    /// own. Functions are grouped by module and sorted by name.
+    ///
+    /// The IR is printed from the parsed modules, so it lists only the
+    /// definitions of the functions, not the declarations, types, or metadata
+    /// of the original `.ll` file, and needn't assemble.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_annotated_ir(&self, writer: impl Write) -> Result<(), String> {
+        annotated_ir::write_annotated_ir(self, writer)
+    }
+
+    /// Write the call graph of the functions with results to `writer`, in
+    /// Graphviz (DOT) format, annotated with where taint crosses function
+    /// boundaries. There is one node per function (labeled with its demangled
+    /// name, if any), including the external functions they call, which are
+    /// dashed; functions which return tainted data are filled in color. There
+    /// is one edge per (caller, callee) pair with direct calls; if any of the
+    /// calls is passed tainted data or returns tainted data, the edge is
+    /// colored and labeled `args`, `ret`, or `args, ret`.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_call_graph_dot(&self, writer: impl Write) -> Result<(), String> {
+        call_graph_export::write_call_graph_dot(self, writer)
+    }
+
+    /// Write the same call graph as `write_call_graph_dot()` to `writer`, as
+    /// a JSON object with:
+    ///   - `functions`: for each function, its `name`, `demangled` name (or
+    ///     `null`), `module` (or `null` if it wasn't analyzed), whether it was
+    ///     `analyzed`, and whether it `returns_tainted` data
+    ///   - `edges`: for each (caller, callee) pair, the `caller` and `callee`
+    ///     names, the number of `calls`, and whether any of them has
+    ///     `tainted_args` or a `tainted_return`
+    ///
+    /// Both are sorted by name. Returns an error if writing fails.
+    pub fn write_call_graph_json(&self, writer: impl Write) -> Result<(), String> {
+        call_graph_export::write_call_graph_json(self, writer)
+    }
+
+    /// Write the results to `writer` as a table in the given format (CSV,
+    /// TSV, or JSON), for consumption by spreadsheets and other tools. After a
+    /// header line, there is one row per variable of each function, per global
+    /// the analysis encountered, and per field of each named struct, with
+    /// columns:
+    ///   - `module`: the module defining the function or global
+    ///   - `function`: the function the variable belongs to
+    ///   - `value`: `%name` of a variable, `@name` of a global, or the struct
+    ///     name followed by the field path (e.g. `struct.foo[1][0]`)
+    ///   - `status`: `tainted` or `untainted`
+    ///   - `labels`: always empty here; see `LabeledTaintResult::write_table()`
+    ///   - `location`: `file:line:col` of the definition, if the module has
+    ///     debug info for it
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_table(&self, format: TableFormat, writer: impl Write) -> Result<(), String> {
+        self.write_filtered_table(format, &OutputFilter::default(), writer)
+    }
+
+    /// Like `write_table()`, but with only the rows the given `OutputFilter`
+    /// keeps
+    pub fn write_filtered_table(&self, format: TableFormat, filter: &OutputFilter, writer: impl Write) -> Result<(), String> {
+        let rows = table::rows(self).into_iter().filter(|row| {
+            let keeps_value = if row.function.is_empty() { filter.keeps_non_function_values() } else { filter.keeps_function(&row.function) };
+            keeps_value && filter.keeps_taint(row.tainted)
+        });
+        table::write_table(rows, format, writer)
+    }
+
     /// Iterate over all function names for which we have a taint map
     pub fn get_function_names<'s: 'm>(&'s self) -> impl Iterator<Item = &'s &'m str> {
         self.fn_taint_states.keys()
@@ -69,13 +451,323 @@ impl<'m> TaintResult<'m> {
                 let inner_ty = self.get_named_struct_type(name);
                 self.is_type_tainted(inner_ty)
             },
-            TaintedType::UntaintedFnPtr => false,
-            TaintedType::TaintedFnPtr => true,
+            TaintedType::UntaintedFnPtr(_) => false,
+            TaintedType::TaintedFnPtr(_) => true,
+            TaintedType::Token => false,
+        }
+    }
+
+    /// Get the final `FunctionSummary` for the function with the given name, or
+    /// `None` if the analysis never created a summary for it.
+    ///
+    /// Summaries exist for every function which was processed or called
+    /// (including external functions), but the start function(s) of an
+    /// analysis may have no summary if nothing called them.
+    pub fn get_function_summary(&self, fn_name: &str) -> Option<&FunctionSummary<'m>> {
+        self.fn_summaries.get(fn_name)
+    }
+
+    /// Iterate over all (function name, `FunctionSummary`) pairs
+    pub fn get_function_summaries<'s>(&'s self) -> impl Iterator<Item = (&'m str, &'s FunctionSummary<'m>)> {
+        self.fn_summaries.iter().map(|(name, summary)| (*name, summary))
+    }
+
+    /// Does the function with the given name return tainted data, according to
+    /// its final `FunctionSummary`? This includes a struct returned through
+    /// an `sret` parameter.
+    ///
+    /// Returns `false` for void functions without an `sret` parameter, and for
+    /// functions which have no summary.
+    pub fn does_function_return_tainted(&self, fn_name: &str) -> bool {
+        match self.get_function_summary(fn_name) {
+            Some(summary) => {
+                summary.get_ret_ty().as_ref().is_some_and(|ret_ty| self.is_type_tainted_if_known(ret_ty))
+                    || summary.get_sret_ty().is_some_and(|sret_ty| self.is_type_tainted_if_known(&sret_ty))
+            },
+            None => false,
         }
     }
 
+    /// Iterate over the names of all functions which return tainted data,
+    /// according to their final `FunctionSummary`s
+    pub fn get_functions_returning_tainted<'s>(&'s self) -> impl Iterator<Item = &'m str> + 's {
+        self.fn_summaries
+            .keys()
+            .copied()
+            .filter(move |fn_name| self.does_function_return_tainted(fn_name))
+    }
+
+    /// Like `is_type_tainted()`, but treats named structs which we have no
+    /// type for as untainted, rather than panicking
+    pub(crate) fn is_type_tainted_if_known(&self, ty: &TaintedType) -> bool {
+        match ty {
+            TaintedType::NamedStruct(name) => match self.named_struct_types.get(name) {
+                Some(inner_ty) => self.is_type_tainted_if_known(inner_ty),
+                None => false,
+            },
+            TaintedType::ArrayOrVector(element) => self.is_type_tainted_if_known(&element.ty()),
+            TaintedType::Struct(elements) => elements.iter().any(|e| self.is_type_tainted_if_known(&e.ty())),
+            _ => self.is_type_tainted(ty),
+        }
+    }
+
+    /// Get the `TaintedType` of the global with the given name: a pointer to
+    /// the global's contents. Returns `None` if the analysis never encountered
+    /// the global.
+    pub fn get_global_type(&self, global_name: &Name) -> Option<&TaintedType> {
+        self.global_types.get(global_name)
+    }
+
+    /// Are the contents of the global with the given name tainted? Returns
+    /// `false` if the analysis never encountered the global.
+    pub fn is_global_tainted(&self, global_name: &Name) -> bool {
+        match self.global_types.get(global_name) {
+            Some(TaintedType::UntaintedPointer(pointee)) | Some(TaintedType::TaintedPointer(pointee)) => self.is_type_tainted_if_known(&pointee.ty()),
+            _ => false,
+        }
+    }
+
+    /// Get the names of all globals whose contents are tainted, sorted
+    pub fn get_tainted_globals(&self) -> Vec<&Name> {
+        let mut globals: Vec<&Name> = self.global_types
+            .keys()
+            .filter(|name| self.is_global_tainted(name))
+            .collect();
+        globals.sort();
+        globals
+    }
+
+    /// Get the result of analyzing from just the given entry point, i.e.,
+    /// which values are tainted when the program is entered through that
+    /// function, rather than through any of the entry points as in this
+    /// `TaintResult`. See `Config::per_entry_results`.
+    ///
+    /// Returns `None` if there is no separate result for that entry point.
+    pub fn get_entry_result(&self, entry: &str) -> Option<&TaintResult<'m>> {
+        self.entry_results.get(entry)
+    }
+
+    /// Get the entry points which have separate results (see
+    /// `get_entry_result()`), sorted
+    pub fn get_entry_points(&self) -> Vec<&'m str> {
+        let mut entries: Vec<&'m str> = self.entry_results.keys().copied().collect();
+        entries.sort_unstable();
+        entries
+    }
+
+    /// Get the functions with results which may depend on any of the given
+    /// functions, sorted: those connected to them in the call graph (in either
+    /// direction, since summaries carry taint both ways) or through the
+    /// contents of mutable globals. If only the given functions change, e.g.
+    /// while editing, only the results of these functions need to be
+    /// recomputed; the rest remain valid. (Changing the `Config` may affect
+    /// any result.)
+    ///
+    /// Dependencies are found in the module(s) these results are for, so if
+    /// the changed functions now call other functions or refer to other
+    /// globals, the functions affected in the changed module(s) should be
+    /// included as well.
+    ///
+    /// The first call finds the dependencies between all the functions, which
+    /// takes a pass over their instructions; later calls reuse them.
+    pub fn functions_affected_by(&self, changed: &[&str]) -> Vec<&'m str> {
+        self.dependencies
+            .get_or_init(|| Dependencies::new(self))
+            .affected_functions(self, changed)
+    }
+
     /// Get the `TaintedType` of a variable by name
     pub fn get_var_type(&self, funcname: &str, varname: &Name) -> &TaintedType {
         &self.fn_taint_states[funcname].get_taint_map()[varname]
     }
+
+    /// Get the `TaintedType` of a (possibly nested) field of the named struct
+    /// with the given name.
+    ///
+    /// `field_path` gives the index of the field at each level of nesting, just
+    /// like the indices of an LLVM `extractvalue`. For instance, `&[2, 0]`
+    /// refers to element 0 of the struct which is element 2 of the named
+    /// struct. Indexing into an array or vector gives its element type, no
+    /// matter the index (since all elements share a single `TaintedType`).
+    ///
+    /// Returns an error if the path doesn't describe a field of the struct,
+    /// e.g. because an index is out of range or the path tries to index into a
+    /// scalar or pointer.
+    pub fn get_field_type(&self, struct_name: &str, field_path: &[u32]) -> Result<TaintedType, String> {
+        let mut ty = self.get_named_struct_type(struct_name).clone();
+        for (depth, &index) in field_path.iter().enumerate() {
+            ty = match ty {
+                TaintedType::Struct(elements) => match elements.get(index as usize) {
+                    Some(element) => element.ty().clone(),
+                    None => return Err(format!(
+                        "get_field_type: index {} out of range for struct of {} elements, at depth {} of path {:?} into struct {:?}",
+                        index, elements.len(), depth, field_path, struct_name,
+                    )),
+                },
+                TaintedType::NamedStruct(name) => match self.get_named_struct_type(&name) {
+                    TaintedType::Struct(elements) => match elements.get(index as usize) {
+                        Some(element) => element.ty().clone(),
+                        None => return Err(format!(
+                            "get_field_type: index {} out of range for struct {:?} of {} elements, at depth {} of path {:?} into struct {:?}",
+                            index, name, elements.len(), depth, field_path, struct_name,
+                        )),
+                    },
+                    ty => return Err(format!("get_field_type: expected named struct {:?} to have a Struct type, but got {}", name, ty)),
+                },
+                TaintedType::ArrayOrVector(element) => element.ty().clone(),
+                ty => return Err(format!(
+                    "get_field_type: can't index into {}, at depth {} of path {:?} into struct {:?}",
+                    ty, depth, field_path, struct_name,
+                )),
+            };
+        }
+        Ok(ty)
+    }
+
+    /// Is the given (possibly nested) field of the named struct with the given
+    /// name tainted?
+    ///
+    /// See `get_field_type()` for the meaning of `field_path`. This panics if
+    /// `field_path` doesn't describe a field of the struct.
+    pub fn is_field_tainted(&self, struct_name: &str, field_path: &[u32]) -> bool {
+        let ty = self.get_field_type(struct_name, field_path).unwrap_or_else(|e| panic!("is_field_tainted: {}", e));
+        self.is_type_tainted(&ty)
+    }
+
+    /// Get the `TaintedType` of a (possibly nested) struct field given by its
+    /// path in the source, such as `"request.hdr.len"`, using the given
+    /// `DebugInfo` of the analyzed module(s).
+    ///
+    /// See `DebugInfo::resolve_field_path()` for the form of `path`. Returns an
+    /// error if the path doesn't describe a field, or if the struct isn't one
+    /// of the named structs in the analyzed module(s) (with the LLVM name given
+    /// by the debug info, e.g. `struct.request`).
+    pub fn get_field_type_by_path(&self, debug_info: &DebugInfo, path: &str) -> Result<TaintedType, String> {
+        let (struct_name, field_path) = debug_info.resolve_field_path(path)?;
+        if !self.named_struct_types.contains_key(&struct_name) {
+            return Err(format!("get_field_type_by_path: unknown named struct {:?}, in path {:?}", struct_name, path));
+        }
+        self.get_field_type(&struct_name, &field_path)
+    }
+
+    /// Is the (possibly nested) struct field given by its path in the source,
+    /// such as `"request.hdr.len"`, tainted?
+    ///
+    /// See `get_field_type_by_path()`. This panics if `path` doesn't describe a
+    /// field.
+    pub fn is_field_tainted_by_path(&self, debug_info: &DebugInfo, path: &str) -> bool {
+        let ty = self.get_field_type_by_path(debug_info, path).unwrap_or_else(|e| panic!("is_field_tainted_by_path: {}", e));
+        self.is_type_tainted(&ty)
+    }
+
+    /// Write the results to `writer` as text, in the same format as the
+    /// `Display` implementation (with the types abbreviated), but with only
+    /// what the given `OutputFilter` keeps: the blocks of each function are
+    /// still listed, but e.g. with `only_tainted`, only the tainted variables
+    /// in them.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_text(&self, filter: &OutputFilter, mut writer: impl Write) -> Result<(), String> {
+        write!(writer, "{}", Filtered { taint_result: self, filter })
+            .map_err(|e| format!("Failed to write text: {}", e))
+    }
+
+    /// Write the findings of the analysis in the functions the given
+    /// `OutputFilter` keeps to `writer`, as a SARIF 2.1.0 log for code
+    /// scanning tools: the instructions passing tainted data to a sink (see
+    /// `get_tainted_sinks()`), the deallocations of tainted pointers (see
+    /// `get_tainted_frees()`), and the tainted format strings (see
+    /// `get_tainted_format_strings()`), with rule IDs `tainted-sink`,
+    /// `tainted-free`, and `tainted-format-string` respectively. Each result
+    /// has the function as its logical location, and the source location of
+    /// the instruction as its physical location if the module has debug info
+    /// for it.
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_sarif(&self, filter: &OutputFilter, writer: impl Write) -> Result<(), String> {
+        sarif::write_sarif(self, filter, writer)
+    }
+}
+
+/// A direct call in one of the functions with results
+pub(crate) struct DirectCall<'m> {
+    /// Name of the calling function
+    pub(crate) caller: &'m str,
+    /// Name of the block containing the call
+    pub(crate) block: &'m Name,
+    /// Index of the call within the block
+    pub(crate) index: usize,
+    /// Name of the called function
+    pub(crate) callee: &'m str,
+    /// Is the call passed tainted data: a tainted argument, or a pointer
+    /// argument to tainted data?
+    pub(crate) args_tainted: bool,
+    /// Does the call return tainted data?
+    pub(crate) ret_tainted: bool,
+}
+
+/// Does the function with the given (mangled) name go by the given name, as in
+/// `TaintResult::tainted_callsites_of()`: by its mangled or demangled name, or
+/// as an intrinsic variant of it (e.g. `llvm.memcpy.*` for `memcpy`)?
+pub(crate) fn matches_callee(fn_name: &str, name: &str) -> bool {
+    fn_name.strip_prefix("llvm.").and_then(|rest| rest.strip_prefix(name)).is_some_and(|rest| rest.starts_with('.'))
+        || demangle::names_for(fn_name).iter().any(|n| n == name)
+}
+
+/// Get the name of the global (function) which the given constant refers to,
+/// looking through bitcasts, e.g. of a function called with a different type
+pub(crate) fn called_global(constant: &Constant) -> Option<&Name> {
+    match constant {
+        Constant::GlobalReference { name, .. } => Some(name),
+        Constant::BitCast(bc) => called_global(&bc.operand),
+        _ => None,
+    }
+}
+
+impl<'m> fmt::Display for TaintResult<'m> {
+    /// Displays the taint maps of all functions, followed by the types of all
+    /// named structs, each sorted by name. Tainted entries are flagged with
+    /// `[T]`.
+    ///
+    /// Types are abbreviated after a few levels of nesting, unless the
+    /// alternate flag (`{:#}`) is used.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&Filtered { taint_result: self, filter: &OutputFilter::default() }, f)
+    }
+}
+
+/// A `TaintResult` displayed with only what the `OutputFilter` keeps, for
+/// `TaintResult::write_text()`
+struct Filtered<'a, 'm> {
+    taint_result: &'a TaintResult<'m>,
+    filter: &'a OutputFilter,
+}
+
+impl<'a, 'm> fmt::Display for Filtered<'a, 'm> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let taint_result = self.taint_result;
+        let mut fn_names: Vec<&&'m str> = taint_result.fn_taint_states.keys().filter(|fn_name| self.filter.keeps_function(fn_name)).collect();
+        fn_names.sort();
+        for fn_name in fn_names {
+            taint_result.fn_taint_states[*fn_name].fmt_with(f, &|ty| taint_result.is_type_tainted_if_known(ty), self.filter.only_tainted)?;
+        }
+        if !self.filter.keeps_non_function_values() {
+            return Ok(());
+        }
+        let max_depth = if f.alternate() { usize::MAX } else { DISPLAY_MAX_DEPTH };
+        let mut struct_names: Vec<&String> = taint_result.named_struct_types
+            .keys()
+            .filter(|struct_name| self.filter.keeps_taint(taint_result.is_type_tainted_if_known(&taint_result.named_struct_types[*struct_name])))
+            .collect();
+        struct_names.sort();
+        if !struct_names.is_empty() {
+            writeln!(f, "named structs:")?;
+        }
+        for struct_name in struct_names {
+            let ty = &taint_result.named_struct_types[struct_name];
+            let flag = if taint_result.is_type_tainted_if_known(ty) { "[T]" } else { "   " };
+            writeln!(f, "  {} {:?}: {}", flag, struct_name, ty.abbreviated(max_depth))?;
+        }
+        Ok(())
+    }
 }
@@ -1,26 +1,56 @@
+use crate::allocators::{Allocation, Allocator};
+use crate::annotations::{self, Annotations};
+use crate::attributes;
+use crate::checkpoint::{Checkpoint, CloneEntry, Decoder, Encoder, FunctionEntry, NameEntry, SummaryEntry, TypeEntry};
 use crate::config::{self, Config};
+use crate::demangle;
+use crate::format_functions::FormatFunction;
 use crate::function_summary::FunctionSummary;
 use crate::function_taint_state::FunctionTaintState;
 use crate::globals::Globals;
-use crate::modules::Modules;
+use crate::gpu_functions::GpuFunction;
+use crate::jni_functions::{JniFunction, JNI_TABLE_STRUCTS};
+use crate::jump_functions::JumpFunction;
+use crate::kernel_functions::KernelFunction;
+use crate::modules::{self, Modules};
 use crate::named_structs::{Index, NamedStructs, NamedStructInitialDef};
+use crate::operands::{instruction_operands, terminator_operands};
+use crate::pointee::Pointee;
+use crate::clones::{CallSite, CloneContext, FunctionClones};
+use crate::stack_slots::{ReachingStore, StackSlots};
+use crate::summary_store::SummaryStore;
+use crate::taint_blind;
 use crate::taint_result::TaintResult;
-use crate::tainted_type::TaintedType;
+use crate::tainted_type::{FnTargets, TaintedType};
+use crate::wasi_functions::WasiFunction;
+use crate::provenance::{Cause, Provenance};
+use crate::trace;
 use crate::worklist::Worklist;
 use either::Either;
 use itertools::Itertools;
 use llvm_ir::instruction::{groups, BinaryOp, HasResult, UnaryOp};
 use llvm_ir::*;
-use llvm_ir_analysis::CrossModuleAnalysis;
-use log::debug;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use llvm_ir_analysis::{CallGraph, CFGNode, CrossModuleAnalysis};
+#[cfg(not(feature = "tracing"))]
+use log::{debug, warn};
+#[cfg(feature = "tracing")]
+use tracing::{debug, warn};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
-use std::rc::Rc;
+use std::sync::{Arc, OnceLock, RwLock};
 
-pub(crate) struct TaintState<'m> {
+/// The state of an in-progress (or finished) taint-tracking analysis.
+///
+/// Most users will want `do_taint_analysis_on_function()` or
+/// `do_taint_analysis_on_module()`, which run the analysis to completion.
+/// `TaintState` is for hosts which want to interleave the analysis with other
+/// work, or inspect intermediate results: create one with
+/// `new_single_function()` or `new_multiple_functions()`, drive it with
+/// `step()` or `run_until()`, and finally call `into_taint_result()`.
+pub struct TaintState<'m> {
     /// `CrossModuleAnalysis` for the llvm-ir `Module`(s) we're analyzing
     analysis: CrossModuleAnalysis<'m>,
 
@@ -34,14 +64,52 @@ pub(crate) struct TaintState<'m> {
     fn_summaries: HashMap<&'m str, FunctionSummary<'m>>,
 
     /// Named structs used in the module(s), and their definitions (taint statuses)
-    named_structs: Rc<RefCell<NamedStructs<'m>>>,
+    named_structs: Arc<RwLock<NamedStructs<'m>>>,
 
     /// Globals used in the module(s), and their definitions (taint statuses)
-    globals: Rc<RefCell<Globals<'m>>>,
+    globals: Arc<RwLock<Globals<'m>>>,
 
     /// Set of functions which need to be processed again because there's been a
     /// change to taint information which might be relevant to them
-    worklist: Rc<RefCell<Worklist<'m>>>,
+    worklist: Arc<RwLock<Worklist<'m>>>,
+
+    /// Whether coroutine frames are tainted. We don't track which coroutine a
+    /// handle refers to, so all coroutine frames share this one joined taint.
+    /// See `process_coro_intrinsic()`.
+    coro_frames_tainted: bool,
+
+    /// Functions which use `llvm.coro.*` intrinsics, and so need to be
+    /// processed again if `coro_frames_tainted` changes
+    coro_users: HashSet<&'m str>,
+
+    /// Whether the value passed to any `longjmp` is tainted. We don't track
+    /// which buffer a `longjmp` uses, so this is joined over all of them. See
+    /// `process_jump_call()`.
+    longjmp_values_tainted: bool,
+
+    /// Functions which call `setjmp`, and so need to be processed again if
+    /// `longjmp_values_tainted` changes
+    setjmp_users: HashSet<&'m str>,
+
+    /// `StackSlots` of each function processed so far, if
+    /// `Config::flow_sensitive_memory` is set. These depend only on the
+    /// function's code, so are computed once per function.
+    stack_slots: HashMap<&'m str, StackSlots<'m>>,
+
+    /// Memory allocated by each call of an `Allocator`, if
+    /// `Config::model_allocators` is set, keyed by the function containing
+    /// the call and the name of the call's result. These are created the
+    /// first time each call is processed, and not saved in checkpoints.
+    allocations: HashMap<(&'m str, Name), Allocation>,
+
+    /// The taint policy embedded in the module(s) by annotations, if
+    /// `Config::honor_annotations` is set
+    annotations: Annotations,
+
+    /// Clones of recursive functions, if `Config::recursion_call_string_depth`
+    /// is nonzero. Wherever this struct refers to a function by name, that may
+    /// be the name of a clone instead.
+    clones: FunctionClones<'m>,
 
     /// Name of the function currently being processed
     cur_fn: &'m str,
@@ -51,6 +119,37 @@ pub(crate) struct TaintState<'m> {
 
     /// Name of the block currently being processed, if any
     cur_block: Option<&'m Name>,
+
+    /// Index (within `cur_block`) of the instruction currently being
+    /// processed, if any
+    cur_inst: Option<usize>,
+
+    /// Number of steps recorded so far in the propagation trace (see
+    /// `Config::propagation_trace`)
+    trace_steps: usize,
+
+    /// Propagation edges recorded so far, if `Config::record_provenance` is
+    /// set. These are not saved in checkpoints.
+    provenance: Provenance<'m>,
+
+    /// The functions in each slot of the vtables of each class in the
+    /// module(s), if `Config::devirtualize_virtual_calls` is set (see
+    /// `Modules::vtable_slots()`)
+    vtable_slots: HashMap<(String, u64), HashSet<&'m str>>,
+
+    /// The functions stored into each field of each named struct, if
+    /// `Config::resolve_closure_thunks` is set (see
+    /// `Modules::functions_in_fields()`)
+    functions_in_fields: HashMap<(String, u64), Option<HashSet<&'m str>>>,
+
+    /// Shared summaries of leaf functions, if any (see `use_summary_store()`).
+    /// Calls of functions in the store are answered from it.
+    summary_store: Option<Arc<SummaryStore>>,
+
+    /// Do the functions configured or annotated as sources return tainted
+    /// data? Unset for the runs of a labeled analysis other than the one for
+    /// `TaintLabel::ext_source()` (see `set_ext_sources_tainted()`).
+    ext_sources_tainted: bool,
 }
 
 /// Owns all of the `FunctionTaintState`s which we're working with
@@ -113,6 +212,23 @@ impl<'m> TaintState<'m> {
         args: Option<Vec<TaintedType>>,
         nonargs: HashMap<Name, TaintedType>,
         named_structs: HashMap<String, NamedStructInitialDef>,
+    ) -> Self {
+        let mut ts = Self::new_single_function(modules, config, start_fn_name, args, nonargs, named_structs);
+        ts.compute();
+        ts
+    }
+
+    /// Set up an analysis starting in the given function, but don't run it
+    /// yet: use `step()` or `run_until()` to make progress.
+    ///
+    /// Arguments are the same as for `do_taint_analysis_on_function()`.
+    pub fn new_single_function(
+        modules: impl IntoIterator<Item = &'m Module>,
+        config: &'m Config,
+        start_fn_name: &str,
+        args: Option<Vec<TaintedType>>,
+        nonargs: HashMap<Name, TaintedType>,
+        named_structs: HashMap<String, NamedStructInitialDef>,
     ) -> Self {
         let modules: Modules<'m> = modules.into_iter().collect();
         let analysis = CrossModuleAnalysis::new(modules.iter());
@@ -128,16 +244,14 @@ impl<'m> TaintState<'m> {
                 .parameters
                 .iter()
                 .map(|p| p.name.clone())
-                .zip_eq(args.into_iter())
+                .zip_eq(args)
             {
                 initial_taintmap.insert(name, ty);
             }
         }
 
         let fn_taint_maps = std::iter::once((f.name.as_str(), initial_taintmap)).collect();
-        let mut ts = Self::new(modules, analysis, config, std::iter::once(f.name.as_str()).collect(), fn_taint_maps, named_structs);
-        ts.compute();
-        ts
+        Self::new(modules, analysis, config, std::iter::once(f.name.as_str()).collect(), fn_taint_maps, named_structs)
     }
 
     /// Compute the tainted state of all variables using our fixpoint algorithm,
@@ -155,57 +269,161 @@ impl<'m> TaintState<'m> {
         args: HashMap<&'m str, Vec<TaintedType>>,
         nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
         named_structs: HashMap<String, NamedStructInitialDef>,
+    ) -> Self {
+        let mut ts = Self::new_multiple_functions(modules, config, args, nonargs, named_structs);
+        ts.compute();
+        ts
+    }
+
+    /// Set up an analysis of all functions in the given module(s), but don't
+    /// run it yet: use `step()` or `run_until()` to make progress.
+    ///
+    /// Arguments are the same as for `do_taint_analysis_on_module()`.
+    pub fn new_multiple_functions(
+        modules: impl IntoIterator<Item = &'m Module>,
+        config: &'m Config,
+        args: HashMap<&'m str, Vec<TaintedType>>,
+        nonargs: HashMap<&'m str, HashMap<Name, TaintedType>>,
+        named_structs: HashMap<String, NamedStructInitialDef>,
     ) -> Self {
         let modules: Modules<'m> = modules.into_iter().collect();
         let analysis = CrossModuleAnalysis::new(modules.iter());
+        // entry points given any tainted inputs, for `skip_taint_blind_functions`
+        let mut tainted_entries: HashSet<&'m str> = nonargs.iter().filter(|(_, vars)| !vars.is_empty()).map(|(funcname, _)| *funcname).collect();
         let mut initial_fn_taint_maps = nonargs;
         for (funcname, argtypes) in args.into_iter() {
-            let (func, _) = analysis.get_func_by_name(&funcname).unwrap_or_else(|| {
+            let (func, _) = analysis.get_func_by_name(funcname).unwrap_or_else(|| {
                 panic!(
                     "Failed to find function named {:?} in the given module(s)",
                     funcname
                 );
             });
-            let initial_fn_taint_map: &mut HashMap<Name, TaintedType> = initial_fn_taint_maps.entry(funcname.clone()).or_default();
-            for (name, ty) in func.parameters.iter().map(|p| p.name.clone()).zip_eq(argtypes.into_iter()) {
-                initial_fn_taint_map.insert(name, ty);
+            let initial_fn_taint_map: &mut HashMap<Name, TaintedType> = initial_fn_taint_maps.entry(funcname).or_default();
+            for (param, ty) in func.parameters.iter().zip_eq(argtypes) {
+                if ty != TaintedType::from_llvm_type(&param.ty) {
+                    tainted_entries.insert(funcname);
+                }
+                initial_fn_taint_map.insert(param.name.clone(), ty);
             }
         }
-        let all_fns = modules.all_functions().map(|(f, _)| f.name.as_str());
-        let initial_worklist: Worklist<'m> = all_fns.collect();
-        let mut ts = Self::new(modules, analysis, config, initial_worklist, initial_fn_taint_maps, named_structs);
-        ts.compute();
-        ts
+        let mut initial_fns: Vec<&'m str> = if config.prune_unreachable_functions && !initial_fn_taint_maps.is_empty() {
+            let entry_points: Vec<&'m str> = initial_fn_taint_maps.keys().copied().collect();
+            reachable_functions(&analysis, &analysis.call_graph(), entry_points).into_iter().collect()
+        } else {
+            modules.all_functions().map(|(f, _)| f.name.as_str()).collect()
+        };
+        initial_fns.retain(|f| match analysis.get_func_by_name(f) {
+            Some((_, module)) => !config.is_context_only(module),
+            None => true,
+        });
+        let named_structs_tainted = named_structs.values().any(|def| !matches!(def, NamedStructInitialDef::AllFieldsUntainted));
+        if config.skip_taint_blind_functions && !named_structs_tainted {
+            let annotations = if config.honor_annotations {
+                Annotations::from_modules(modules.iter())
+            } else {
+                Annotations::default()
+            };
+            let observing = taint_blind::observing_functions(&analysis, config, &annotations, tainted_entries);
+            initial_fns.retain(|f| observing.contains(f));
+        }
+        let initial_worklist: Worklist<'m> = initial_fns.into_iter().collect();
+        Self::new(modules, analysis, config, initial_worklist, initial_fn_taint_maps, named_structs)
     }
 
     fn new(
         modules: Modules<'m>,
         analysis: CrossModuleAnalysis<'m>,
         config: &'m Config,
-        initial_worklist: Worklist<'m>,
-        fn_taint_maps: HashMap<&'m str, HashMap<Name, TaintedType>>,
+        mut initial_worklist: Worklist<'m>,
+        mut fn_taint_maps: HashMap<&'m str, HashMap<Name, TaintedType>>,
         named_structs: HashMap<String, NamedStructInitialDef>,
     ) -> Self {
         let cur_mod = modules.iter().next().unwrap(); // doesn't matter what `cur_mod` starts as - we shouldn't use it until we set `cur_fn` and `cur_mod` together
-        let named_structs = Rc::new(RefCell::new(NamedStructs::with_initial_defs(modules, named_structs)));
-        let globals = Rc::new(RefCell::new(Globals::new()));
-        let worklist = Rc::new(RefCell::new(initial_worklist));
+        let annotations = if config.honor_annotations {
+            Annotations::from_modules(modules.iter())
+        } else {
+            Annotations::default()
+        };
+        let mut extra_entry_points = Vec::new();
+        if config.static_initializers_as_entry_points {
+            extra_entry_points.extend(modules.static_initializers());
+        }
+        if config.signal_handlers_as_entry_points {
+            extra_entry_points.extend(modules.signal_handlers());
+        }
+        if config.gpu_kernels_as_entry_points {
+            extra_entry_points.extend(modules.gpu_kernels());
+        }
+        for fn_name in extra_entry_points {
+            let analyzed = analysis.get_func_by_name(fn_name).is_some_and(|(_, module)| !config.is_context_only(module));
+            if analyzed {
+                initial_worklist.add(fn_name);
+            }
+        }
+        let vtable_slots = if config.devirtualize_virtual_calls {
+            modules.vtable_slots()
+        } else {
+            HashMap::new()
+        };
+        let functions_in_fields = if config.resolve_closure_thunks {
+            modules.functions_in_fields()
+        } else {
+            HashMap::new()
+        };
+        let device_globals = modules
+            .iter()
+            .flat_map(|module| module.global_vars.iter())
+            .filter(|var| var.section.as_ref().is_some_and(|section| config.tainted_sections.contains(section)))
+            .collect::<Vec<_>>();
+        let fn_ptr_tables = if config.resolve_fn_ptr_targets {
+            modules.fn_ptr_tables()
+        } else {
+            Vec::new()
+        };
+        let mut named_structs = NamedStructs::with_initial_defs(modules, named_structs);
+        named_structs.set_max_pointer_depth(config.max_pointer_depth);
+        named_structs.set_lenient_joins(config.lenient_joins);
+        if config.unify_renamed_structs {
+            named_structs.unify_renamed_structs();
+            for ty in fn_taint_maps.values_mut().flat_map(|taint_map| taint_map.values_mut()) {
+                named_structs.canonicalize(ty);
+            }
+        }
+        let mut globals = Globals::new();
+        for (name, llvm_ty, fn_names) in fn_ptr_tables {
+            let targets: FnTargets = fn_names.into_iter().map(str::to_owned).collect();
+            globals.add_fn_ptr_table(name.clone(), llvm_ty, &targets);
+        }
+        for (name, llvm_ty) in annotations.source_globals() {
+            globals.taint_global(name.clone(), llvm_ty, &mut named_structs);
+        }
+        for var in device_globals {
+            if let Type::PointerType { pointee_type, .. } = var.ty.as_ref() {
+                globals.taint_global(var.name.clone(), pointee_type, &mut named_structs);
+            }
+        }
+        let named_structs = Arc::new(RwLock::new(named_structs));
+        let globals = Arc::new(RwLock::new(globals));
+        if config.deterministic {
+            initial_worklist.set_deterministic();
+        }
+        let worklist = Arc::new(RwLock::new(initial_worklist));
         let fn_taint_states = fn_taint_maps
             .into_iter()
             .map(|(s, taintmap)| {
                 let (_, module) = analysis.get_func_by_name(s).expect("Function named {:?} not found");
                 let fts = FunctionTaintState::from_taint_map(
-                    &s,
+                    s,
                     taintmap,
                     module,
-                    Rc::clone(&named_structs),
-                    Rc::clone(&globals),
-                    Rc::clone(&worklist),
+                    Arc::clone(&named_structs),
+                    Arc::clone(&globals),
+                    Arc::clone(&worklist),
                 );
-                (s.into(), fts)
+                (s, fts)
             })
             .collect();
-        Self {
+        let mut ts = Self {
             analysis,
             config,
             fn_taint_states,
@@ -213,26 +431,728 @@ impl<'m> TaintState<'m> {
             named_structs,
             globals,
             worklist,
+            coro_frames_tainted: false,
+            coro_users: HashSet::new(),
+            longjmp_values_tainted: false,
+            setjmp_users: HashSet::new(),
+            stack_slots: HashMap::new(),
+            allocations: HashMap::new(),
+            annotations,
+            clones: FunctionClones::new(config.recursion_call_string_depth),
             cur_fn: "", // we shouldn't use `cur_fn` until it's set to the first one we pop off the worklist
             cur_mod, // likewise, we shouldn't use `cur_mod` until we set `cur_fn`
             cur_block: None,
+            cur_inst: None,
+            trace_steps: 0,
+            provenance: Provenance::new(config.record_provenance),
+            vtable_slots,
+            functions_in_fields,
+            summary_store: None,
+            ext_sources_tainted: true,
+        };
+        if config.bottom_up {
+            ts.set_up_bottom_up(true);
         }
+        ts
     }
 
-    pub(crate) fn into_taint_result(self) -> TaintResult<'m> {
+    /// Consume the `TaintState` and produce a `TaintResult`.
+    ///
+    /// If the analysis hasn't reached its fixpoint yet (see `is_done()`), the
+    /// result reflects only the taint discovered so far.
+    pub fn into_taint_result(mut self) -> TaintResult<'m> {
+        merge_clones(&self.clones, &mut self.fn_taint_states.map, &mut self.fn_summaries);
         TaintResult {
             fn_taint_states: self.fn_taint_states.map,
+            fn_summaries: self.fn_summaries,
             named_struct_types: self
                 .named_structs
-                .borrow()
+                .read().unwrap()
                 .all_named_struct_types()
                 .map(|(name, ty)| (name.clone(), ty.clone()))
                 .collect(),
+            global_types: self
+                .globals
+                .read().unwrap()
+                .all_global_types()
+                .map(|(name, ty)| (name.clone(), ty.clone()))
+                .collect(),
+            provenance: self.provenance,
+            entry_results: HashMap::new(),
+            dependencies: OnceLock::new(),
+        }
+    }
+
+    /// Produce a `TaintResult` for the current state of the analysis, without
+    /// consuming the `TaintState`: e.g., to inspect intermediate results
+    /// between calls of `step()`, or to keep the `TaintState` for later
+    /// incremental work.
+    ///
+    /// The `TaintResult` gets its own copy of all the `TaintedType`s (much as
+    /// if it were resumed from a `Checkpoint`), so it doesn't change as the
+    /// analysis continues. As with `into_taint_result()`, if the analysis
+    /// hasn't reached its fixpoint yet, the result reflects only the taint
+    /// discovered so far.
+    pub fn snapshot(&self) -> TaintResult<'m> {
+        // encoding and then decoding all the types copies each `Pointee`
+        // once, keeping the sharing between them
+        let mut encoder = Encoder::default();
+        let fn_taint_maps: Vec<(&'m str, Vec<(&Name, TypeEntry)>)> = self.fn_taint_states.map
+            .iter()
+            .map(|(fn_name, fts)| (*fn_name, fts.get_taint_map().iter().map(|(name, ty)| (name, encoder.encode(ty))).collect()))
+            .collect();
+        let summaries: Vec<(&'m str, Vec<TypeEntry>, Option<TypeEntry>)> = self.fn_summaries
+            .iter()
+            .map(|(fn_name, summary)| (
+                *fn_name,
+                summary.get_params().map(|ty| encoder.encode(ty)).collect(),
+                summary.get_ret_ty().as_ref().map(|ty| encoder.encode(ty)),
+            ))
+            .collect();
+        let named_struct_types: Vec<(String, TypeEntry)> = self.named_structs
+            .read().unwrap()
+            .all_named_struct_types()
+            .map(|(name, ty)| (name.clone(), encoder.encode(ty)))
+            .collect();
+        let global_types: Vec<(Name, TypeEntry)> = self.globals
+            .read().unwrap()
+            .all_global_types()
+            .map(|(name, ty)| (name.clone(), encoder.encode(ty)))
+            .collect();
+        let pointee_indices: Vec<(usize, usize)> = encoder.pointee_ids().collect();
+        let decoder = Decoder::new(&encoder.into_pointees()).expect("snapshot: types we just encoded should decode");
+        let decode = |entry: &TypeEntry| decoder.decode(entry).expect("snapshot: types we just encoded should decode");
+
+        let mut fn_taint_states: HashMap<&'m str, FunctionTaintState<'m>> = fn_taint_maps
+            .into_iter()
+            .map(|(fn_name, vars)| {
+                let mut vars: HashMap<&Name, TaintedType> = vars.iter().map(|(name, entry)| (*name, decode(entry))).collect();
+                let fts = self.fn_taint_states.map[fn_name].map_types(|name, _| vars.remove(name).unwrap());
+                (fn_name, fts)
+            })
+            .collect();
+        let mut fn_summaries: HashMap<&'m str, FunctionSummary<'m>> = summaries
+            .iter()
+            .map(|(fn_name, params, ret)| {
+                let original = &self.fn_summaries[fn_name];
+                let mut summary = FunctionSummary::from_parts(
+                    params.iter().map(decode).collect(),
+                    ret.as_ref().map(decode),
+                    original.are_varargs_tainted(),
+                    Arc::clone(&self.named_structs),
+                );
+                if let Some(index) = original.get_sret_param() {
+                    summary.set_sret_param(index);
+                }
+                (*fn_name, summary)
+            })
+            .collect();
+        merge_clones(&self.clones, &mut fn_taint_states, &mut fn_summaries);
+        let new_pointee_ids: HashMap<usize, usize> = pointee_indices
+            .into_iter()
+            .filter_map(|(id, index)| Some((id, decoder.pointee_id(index)?)))
+            .collect();
+        TaintResult {
+            fn_taint_states,
+            fn_summaries,
+            named_struct_types: named_struct_types.iter().map(|(name, entry)| (name.clone(), decode(entry))).collect(),
+            global_types: global_types.iter().map(|(name, entry)| (name.clone(), decode(entry))).collect(),
+            provenance: self.provenance.with_pointee_ids(&new_pointee_ids),
+            entry_results: HashMap::new(),
+            dependencies: OnceLock::new(),
+        }
+    }
+
+    /// Take a `Checkpoint` of the current state of the analysis, which can be
+    /// used to resume it later with `resume_from_checkpoint()`.
+    ///
+    /// The `Checkpoint` is deterministic: the same state always produces the
+    /// same `Checkpoint`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let mut encoder = Encoder::default();
+        let mut worklist: Vec<String> = self.worklist.read().unwrap().iter().map(String::from).collect();
+        worklist.sort();
+        let mut fn_names: Vec<&&'m str> = self.fn_taint_states.map.keys().collect();
+        fn_names.sort();
+        let functions = fn_names.into_iter().map(|fn_name| {
+            let fts = &self.fn_taint_states.map[*fn_name];
+            let mut vars: Vec<(&Name, &TaintedType)> = fts.get_taint_map().iter().collect();
+            vars.sort_by_key(|(name, _)| *name);
+            let mut tainted_terminators: Vec<&Name> = fts.get_tainted_terminators().collect();
+            tainted_terminators.sort();
+            let mut tainted_sinks: Vec<&(Name, usize)> = fts.get_tainted_sinks().collect();
+            tainted_sinks.sort();
+            let mut tainted_frees: Vec<&(Name, usize)> = fts.get_tainted_frees().collect();
+            tainted_frees.sort();
+            let mut tainted_format_strings: Vec<&(Name, usize)> = fts.get_tainted_format_strings().collect();
+            tainted_format_strings.sort();
+            let mut unhandled_constructs: Vec<(&(Name, Option<usize>), &String)> = fts.get_unhandled_constructs().collect();
+            unhandled_constructs.sort();
+            let mut assumptions: Vec<(&(Name, Option<usize>), &String)> = fts.get_assumptions().collect();
+            assumptions.sort();
+            let mut tainted_bits: Vec<(&Name, &u64)> = fts.get_all_tainted_bits().collect();
+            tainted_bits.sort();
+            FunctionEntry {
+                name: fn_name.to_string(),
+                vars: vars.into_iter().map(|(name, ty)| (name.into(), encoder.encode(ty))).collect(),
+                tainted_terminators: tainted_terminators.into_iter().map(NameEntry::from).collect(),
+                tainted_sinks: tainted_sinks.into_iter().map(|(block, index)| (block.into(), *index)).collect(),
+                tainted_frees: tainted_frees.into_iter().map(|(block, index)| (block.into(), *index)).collect(),
+                tainted_format_strings: tainted_format_strings.into_iter().map(|(block, index)| (block.into(), *index)).collect(),
+                unhandled_constructs: unhandled_constructs.into_iter().map(|((block, index), construct)| (block.into(), *index, construct.clone())).collect(),
+                assumptions: assumptions.into_iter().map(|((block, index), assumption)| (block.into(), *index, assumption.clone())).collect(),
+                tainted_bits: tainted_bits.into_iter().map(|(name, bits)| (name.into(), *bits)).collect(),
+            }
+        }).collect();
+        let mut summary_names: Vec<&&'m str> = self.fn_summaries.keys().collect();
+        summary_names.sort();
+        let summaries = summary_names.into_iter().map(|fn_name| {
+            let summary = &self.fn_summaries[*fn_name];
+            SummaryEntry {
+                name: fn_name.to_string(),
+                params: summary.get_params().map(|ty| encoder.encode(ty)).collect(),
+                ret: summary.get_ret_ty().as_ref().map(|ty| encoder.encode(ty)),
+                varargs_tainted: summary.are_varargs_tainted(),
+                sret_param: summary.get_sret_param(),
+            }
+        }).collect();
+        let named_structs = self.named_structs.read().unwrap();
+        let mut named_struct_types: Vec<(&String, &TaintedType)> = named_structs.all_named_struct_types().collect();
+        named_struct_types.sort_by_key(|(name, _)| *name);
+        let mut tainted_named_structs: Vec<String> = named_structs.all_tainted_named_structs().cloned().collect();
+        tainted_named_structs.sort();
+        let mut named_struct_users: Vec<(String, Vec<String>)> = named_structs
+            .all_named_struct_users()
+            .map(|(name, users)| (name.clone(), sorted_fn_names(users)))
+            .collect();
+        named_struct_users.sort();
+        let mut deep_pointees: Vec<(&String, &Pointee)> = named_structs.all_deep_pointees().collect();
+        deep_pointees.sort_by_key(|(llvm_ty, _)| *llvm_ty);
+        let globals = self.globals.read().unwrap();
+        let mut global_types: Vec<(&Name, &TaintedType)> = globals.all_global_types().collect();
+        global_types.sort_by_key(|(name, _)| *name);
+        let mut global_users: Vec<(&Name, Vec<String>)> = globals
+            .all_global_users()
+            .map(|(name, users)| (name, sorted_fn_names(users)))
+            .collect();
+        global_users.sort();
+        let mut clones: Vec<CloneEntry> = self.clones
+            .all_clones()
+            .map(|(name, context)| CloneEntry {
+                name: name.to_string(),
+                function: context.function.to_string(),
+                call_string: context.call_string.as_ref().map(|sites| sites
+                    .iter()
+                    .map(|site| (site.function.to_string(), NameEntry::from(&site.block), site.index))
+                    .collect()),
+                arg_taint: context.arg_taint.clone(),
+            })
+            .collect();
+        clones.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut clone_callers: Vec<(String, Vec<String>)> = self.clones
+            .all_callers()
+            .map(|(name, callers)| (name.to_string(), sorted_fn_names(callers)))
+            .collect();
+        clone_callers.sort();
+        Checkpoint {
+            worklist,
+            functions,
+            summaries,
+            named_struct_types: named_struct_types.into_iter().map(|(name, ty)| (name.clone(), encoder.encode(ty))).collect(),
+            tainted_named_structs,
+            named_struct_users,
+            deep_pointees: deep_pointees.into_iter().map(|(llvm_ty, pointee)| (llvm_ty.clone(), encoder.encode_pointee(pointee))).collect(),
+            global_types: global_types.into_iter().map(|(name, ty)| (name.into(), encoder.encode(ty))).collect(),
+            global_users: global_users.into_iter().map(|(name, users)| (name.into(), users)).collect(),
+            coro_frames_tainted: self.coro_frames_tainted,
+            coro_users: sorted_fn_names(&self.coro_users),
+            longjmp_values_tainted: self.longjmp_values_tainted,
+            setjmp_users: sorted_fn_names(&self.setjmp_users),
+            clones,
+            clone_callers,
+            trace_steps: self.trace_steps,
+            pointees: encoder.into_pointees(),
+        }
+    }
+
+    /// Resume an analysis from a `Checkpoint` previously taken with
+    /// `checkpoint()`.
+    ///
+    /// `modules` must be the same `Module`(s) that the checkpointed analysis
+    /// was working with, and `config` should be equivalent to its `Config`.
+    ///
+    /// Returns an error if the `Checkpoint` refers to functions which aren't
+    /// defined or called in `modules`, or is otherwise inconsistent.
+    pub fn resume_from_checkpoint(
+        modules: impl IntoIterator<Item = &'m Module>,
+        config: &'m Config,
+        checkpoint: &Checkpoint,
+    ) -> Result<Self, String> {
+        let modules: Modules<'m> = modules.into_iter().collect();
+        let analysis = CrossModuleAnalysis::new(modules.iter());
+        let cur_mod = modules.iter().next().ok_or_else(|| "resume_from_checkpoint: no modules given".to_owned())?;
+        // all the function names the analysis could have encountered: these
+        // give us the `&'m str`s to use in place of the checkpoint's `String`s
+        let mut known_fn_names: HashSet<&'m str> = modules
+            .all_functions()
+            .map(|(f, _)| f.name.as_str())
+            .chain(modules.all_direct_callees())
+            .collect();
+        // clone names need to live as long as the `Module`s; see
+        // `FunctionClones::get_or_create_clone()`
+        let clone_names: Vec<&'m str> = checkpoint.clones
+            .iter()
+            .map(|entry| &*Box::leak(entry.name.clone().into_boxed_str()))
+            .collect();
+        known_fn_names.extend(clone_names.iter().copied());
+        let fn_name = |name: &str| -> Result<&'m str, String> {
+            known_fn_names.get(name).copied().ok_or_else(|| format!(
+                "Checkpoint refers to function {:?}, which is not defined or called in the given module(s)",
+                name
+            ))
+        };
+        let fn_names = |names: &[String]| -> Result<HashSet<&'m str>, String> {
+            names.iter().map(|name| fn_name(name)).collect()
+        };
+        let clones = checkpoint.clones
+            .iter()
+            .zip(clone_names)
+            .map(|(entry, name)| {
+                let call_string = entry.call_string
+                    .as_ref()
+                    .map(|sites| sites
+                        .iter()
+                        .map(|(function, block, index)| Ok(CallSite { function: fn_name(function)?, block: block.into(), index: *index }))
+                        .collect::<Result<_, String>>())
+                    .transpose()?;
+                Ok((name, CloneContext { function: fn_name(&entry.function)?, call_string, arg_taint: entry.arg_taint.clone() }))
+            })
+            .collect::<Result<_, String>>()?;
+        let clone_callers = checkpoint.clone_callers
+            .iter()
+            .map(|(name, callers)| Ok((fn_name(name)?, fn_names(callers)?)))
+            .collect::<Result<_, String>>()?;
+        let clones = FunctionClones::from_parts(config.recursion_call_string_depth, clones, clone_callers);
+
+        let decoder = Decoder::new(&checkpoint.pointees)?;
+        let named_struct_types = checkpoint.named_struct_types
+            .iter()
+            .map(|(name, ty)| Ok((name.clone(), decoder.decode(ty)?)))
+            .collect::<Result<_, String>>()?;
+        let named_struct_users = checkpoint.named_struct_users
+            .iter()
+            .map(|(name, users)| Ok((name.clone(), fn_names(users)?)))
+            .collect::<Result<_, String>>()?;
+        let global_types = checkpoint.global_types
+            .iter()
+            .map(|(name, ty)| Ok((name.into(), decoder.decode(ty)?)))
+            .collect::<Result<_, String>>()?;
+        let global_users = checkpoint.global_users
+            .iter()
+            .map(|(name, users)| Ok((name.into(), fn_names(users)?)))
+            .collect::<Result<_, String>>()?;
+        let mut worklist: Worklist<'m> = checkpoint.worklist
+            .iter()
+            .map(|name| fn_name(name))
+            .collect::<Result<_, String>>()?;
+        if config.deterministic {
+            worklist.set_deterministic();
+        }
+
+        let deep_pointees = checkpoint.deep_pointees
+            .iter()
+            .map(|(llvm_ty, pointee)| Ok((llvm_ty.clone(), decoder.pointee(pointee)?)))
+            .collect::<Result<_, String>>()?;
+        let vtable_slots = if config.devirtualize_virtual_calls {
+            modules.vtable_slots()
+        } else {
+            HashMap::new()
+        };
+        let functions_in_fields = if config.resolve_closure_thunks {
+            modules.functions_in_fields()
+        } else {
+            HashMap::new()
+        };
+        // the checkpoint already has the taint of annotated globals
+        let annotations = if config.honor_annotations {
+            Annotations::from_modules(modules.iter())
+        } else {
+            Annotations::default()
+        };
+        let mut named_structs = NamedStructs::from_parts(
+            modules,
+            named_struct_types,
+            checkpoint.tainted_named_structs.iter().cloned().collect(),
+            named_struct_users,
+            deep_pointees,
+        );
+        named_structs.set_max_pointer_depth(config.max_pointer_depth);
+        named_structs.set_lenient_joins(config.lenient_joins);
+        if config.unify_renamed_structs {
+            named_structs.unify_renamed_structs();
+        }
+        let named_structs = Arc::new(RwLock::new(named_structs));
+        let globals = Arc::new(RwLock::new(Globals::from_parts(global_types, global_users)));
+        let worklist = Arc::new(RwLock::new(worklist));
+        let fn_taint_states = checkpoint.functions
+            .iter()
+            .map(|entry| {
+                let name = fn_name(&entry.name)?;
+                let (_, module) = analysis.get_func_by_name(clones.base_function(name)).ok_or_else(|| format!(
+                    "Checkpoint has a taint map for function {:?}, which is not defined in the given module(s)",
+                    entry.name
+                ))?;
+                let taintmap = entry.vars
+                    .iter()
+                    .map(|(name, ty)| Ok((name.into(), decoder.decode(ty)?)))
+                    .collect::<Result<_, String>>()?;
+                let mut fts = FunctionTaintState::from_taint_map(
+                    name,
+                    taintmap,
+                    module,
+                    Arc::clone(&named_structs),
+                    Arc::clone(&globals),
+                    Arc::clone(&worklist),
+                );
+                for block in &entry.tainted_terminators {
+                    fts.mark_terminator_tainted(block.into());
+                }
+                for (block, index) in &entry.tainted_sinks {
+                    fts.mark_sink_tainted(block.into(), *index);
+                }
+                for (block, index) in &entry.tainted_frees {
+                    fts.mark_free_tainted(block.into(), *index);
+                }
+                for (block, index) in &entry.tainted_format_strings {
+                    fts.mark_format_string_tainted(block.into(), *index);
+                }
+                for (block, index, construct) in &entry.unhandled_constructs {
+                    fts.mark_unhandled(block.into(), *index, construct.as_str());
+                }
+                for (block, index, assumption) in &entry.assumptions {
+                    fts.mark_assumption(block.into(), *index, assumption.as_str());
+                }
+                for (name, bits) in &entry.tainted_bits {
+                    fts.update_tainted_bits(name.into(), *bits);
+                }
+                Ok((name, fts))
+            })
+            .collect::<Result<_, String>>()?;
+        let fn_summaries = checkpoint.summaries
+            .iter()
+            .map(|entry| {
+                let params = entry.params.iter().map(|ty| decoder.decode(ty)).collect::<Result<_, _>>()?;
+                let ret = entry.ret.as_ref().map(|ty| decoder.decode(ty)).transpose()?;
+                let mut summary = FunctionSummary::from_parts(params, ret, entry.varargs_tainted, Arc::clone(&named_structs));
+                if let Some(index) = entry.sret_param {
+                    summary.set_sret_param(index);
+                }
+                Ok((fn_name(&entry.name)?, summary))
+            })
+            .collect::<Result<_, String>>()?;
+        let mut ts = Self {
+            analysis,
+            config,
+            fn_taint_states,
+            fn_summaries,
+            named_structs,
+            globals,
+            worklist,
+            coro_frames_tainted: checkpoint.coro_frames_tainted,
+            coro_users: fn_names(&checkpoint.coro_users)?,
+            longjmp_values_tainted: checkpoint.longjmp_values_tainted,
+            setjmp_users: fn_names(&checkpoint.setjmp_users)?,
+            stack_slots: HashMap::new(),
+            allocations: HashMap::new(),
+            annotations,
+            clones,
+            cur_fn: "", // as in `new()`, we shouldn't use `cur_fn` until it's set to the first one we pop off the worklist
+            cur_mod, // likewise, we shouldn't use `cur_mod` until we set `cur_fn`
+            cur_block: None,
+            cur_inst: None,
+            trace_steps: checkpoint.trace_steps,
+            provenance: Provenance::new(config.record_provenance),
+            vtable_slots,
+            functions_in_fields,
+            summary_store: None,
+            ext_sources_tainted: true,
+        };
+        if config.bottom_up {
+            // the checkpoint's worklist already includes whatever remains of
+            // the initial bottom-up pass, so we only need the ranks again
+            ts.set_up_bottom_up(false);
+        }
+        Ok(ts)
+    }
+
+    /// Set up the worklist for `Config::bottom_up`: rank all functions so that
+    /// callees are popped before their callers, and if `seed` is `true`, add
+    /// every function reachable in the call graph from the functions already
+    /// on the worklist.
+    ///
+    /// Ranks follow a postorder of the call graph, so within a recursive cycle
+    /// the order is arbitrary, but otherwise every callee comes before its
+    /// callers. External functions are ranked too, but aren't added: they're
+    /// added by their callers, which create summaries for them.
+    fn set_up_bottom_up(&mut self, seed: bool) {
+        let call_graph = self.analysis.call_graph();
+        // sorted for a deterministic order, where the call graph leaves it open
+        let sorted_callees = |f: &'m str| -> Vec<&'m str> {
+            let mut callees: Vec<&'m str> = call_graph.callees(f).collect();
+            callees.sort_unstable_by(|a, b| b.cmp(a)); // reversed, since we pop from the end
+            callees
+        };
+        let mut roots: Vec<&'m str> = self.analysis.functions().map(|f| f.name.as_str()).collect();
+        roots.sort_unstable();
+        let mut ranks: HashMap<&'m str, usize> = HashMap::new();
+        let mut visited: HashSet<&'m str> = HashSet::new();
+        for root in roots {
+            if !visited.insert(root) {
+                continue;
+            }
+            // iterative depth-first search, to handle deep call graphs
+            let mut stack: Vec<(&'m str, Vec<&'m str>)> = vec![(root, sorted_callees(root))];
+            while let Some((f, callees)) = stack.last_mut() {
+                let f = *f;
+                match callees.pop() {
+                    Some(callee) => {
+                        if visited.insert(callee) {
+                            stack.push((callee, sorted_callees(callee)));
+                        }
+                    },
+                    None => {
+                        stack.pop();
+                        ranks.insert(f, ranks.len());
+                    },
+                }
+            }
+        }
+
+        let mut worklist = self.worklist.write().unwrap();
+        if seed {
+            let roots: Vec<&'m str> = worklist.iter().collect();
+            for f in reachable_functions(&self.analysis, &call_graph, roots) {
+                worklist.add(f);
+            }
+        }
+        worklist.set_ranks(ranks);
+    }
+
+    /// Add more `Module`s to the analysis, e.g. a plugin discovered after the
+    /// analysis was set up (or even finished), without starting over.
+    ///
+    /// Functions which were external but are defined in the new `Module`s are
+    /// analyzed from now on, along with their callers, which see the new
+    /// summaries. Taint already propagated from the external function's
+    /// `ExternalFunctionHandling` is kept, since taint never goes away; so the
+    /// results may be more tainted than those of a fresh analysis of all the
+    /// `Module`s, but never less.
+    ///
+    /// If `analyze_new_functions` is `true`, every other function defined in
+    /// the new `Module`s is analyzed too, as in `new_multiple_functions()`;
+    /// otherwise they're only analyzed once something calls them.
+    ///
+    /// Like the constructors, this doesn't run the analysis: use `step()` or
+    /// `run_until()` to make progress.
+    pub fn add_modules(&mut self, modules: impl IntoIterator<Item = &'m Module>, analyze_new_functions: bool) {
+        let new_modules: Modules<'m> = modules.into_iter().collect();
+        let mut all_modules: Modules<'m> = self.analysis.modules().collect();
+        all_modules.extend(new_modules.iter());
+        self.analysis = CrossModuleAnalysis::new(all_modules.iter());
+        self.named_structs.write().unwrap().add_modules(new_modules.iter());
+        if self.config.resolve_fn_ptr_targets {
+            let mut globals = self.globals.write().unwrap();
+            for (name, llvm_ty, fn_names) in new_modules.fn_ptr_tables() {
+                let targets: FnTargets = fn_names.into_iter().map(str::to_owned).collect();
+                globals.add_fn_ptr_table(name.clone(), llvm_ty, &targets);
+            }
+        }
+        if self.config.honor_annotations {
+            let new_annotations = Annotations::from_modules(new_modules.iter());
+            let mut named_structs = self.named_structs.write().unwrap();
+            let mut globals = self.globals.write().unwrap();
+            for (name, llvm_ty) in new_annotations.source_globals() {
+                globals.taint_global(name.clone(), llvm_ty, &mut named_structs);
+            }
+            drop(globals);
+            drop(named_structs);
+            self.annotations = Annotations::from_modules(all_modules.iter());
+        }
+        for (func, _) in new_modules.all_functions() {
+            let fn_name = func.name.as_str();
+            if let Some(old_summary) = self.fn_summaries.remove(fn_name) {
+                // previously external: the callers' arguments still apply, but
+                // the return value now comes from the definition
+                let ret = match func.return_type.as_ref() {
+                    Type::VoidType => None,
+                    ty => Some(TaintedType::from_llvm_type(ty)),
+                };
+                let mut summary = FunctionSummary::from_parts(
+                    old_summary.get_params().cloned().collect(),
+                    ret,
+                    old_summary.are_varargs_tainted(),
+                    Arc::clone(&self.named_structs),
+                );
+                if let Some(index) = old_summary.get_sret_param() {
+                    summary.set_sret_param(index);
+                }
+                self.fn_summaries.insert(fn_name, summary);
+                self.worklist.write().unwrap().add(fn_name);
+                self.add_callers_to_worklist(fn_name);
+            } else if analyze_new_functions {
+                self.worklist.write().unwrap().add(fn_name);
+            }
+        }
+        if self.config.bottom_up {
+            // the call graph changed, so the ranks did too
+            self.set_up_bottom_up(false);
+        }
+    }
+
+    /// Has the analysis reached its fixpoint? If so, `step()` and
+    /// `run_until()` will make no further changes.
+    pub fn is_done(&self) -> bool {
+        self.worklist.read().unwrap().is_empty()
+    }
+
+    /// Iterate over the names of the functions still waiting to be processed
+    /// (in no particular order)
+    pub fn get_pending_functions(&self) -> Vec<&'m str> {
+        self.worklist.read().unwrap().iter().collect()
+    }
+
+    /// Get the names of the functions which have been called (so far) but
+    /// aren't defined in any of the `Module`s (or only in context-only ones,
+    /// see `Config::context_only_modules`), sorted
+    pub fn get_undefined_functions(&self) -> Vec<&'m str> {
+        let mut fn_names: Vec<&'m str> = self.fn_summaries
+            .keys()
+            .copied()
+            .filter(|fn_name| !self.clones.is_clone(fn_name) && self.get_analyzed_func_by_name(fn_name).is_none())
+            .collect();
+        fn_names.sort_unstable();
+        fn_names
+    }
+
+    /// Get the configured sources whose taint never reached any other value,
+    /// sorted: the functions configured to return tainted data (with
+    /// `ExternalFunctionHandling::IgnoreAndReturnTainted` in
+    /// `Config::ext_functions`, or annotated as sources if
+    /// `Config::honor_annotations` is set) which have no call, in any function
+    /// the analysis reached, whose result is used.
+    ///
+    /// A dead source usually means a misconfiguration, which otherwise
+    /// silently produces an entirely untainted result: a misspelled or
+    /// mangled name, a source which is only called from code the analysis
+    /// didn't reach, or one which returns its data through a pointer argument
+    /// rather than its return value.
+    pub fn get_dead_sources(&self) -> Vec<&str> {
+        let mut live: HashSet<&'m str> = HashSet::new();
+        for fn_name in self.fn_taint_states.map.keys() {
+            let func = match self.get_analyzed_func_by_name(self.clones.base_function(fn_name)) {
+                Some((func, _)) => func,
+                None => continue,
+            };
+            let used: HashSet<Name> = func.basic_blocks
+                .iter()
+                .flat_map(|bb| bb.instrs.iter().flat_map(instruction_operands).chain(terminator_operands(&bb.term)))
+                .filter_map(|op| match op {
+                    Operand::LocalOperand { name, .. } => Some(name),
+                    _ => None,
+                })
+                .collect();
+            for inst in func.basic_blocks.iter().flat_map(|bb| bb.instrs.iter()) {
+                let call = match inst {
+                    Instruction::Call(call) => call,
+                    _ => continue,
+                };
+                let callee = match &call.function {
+                    Either::Right(Operand::ConstantOperand(cref)) => match annotations::strip_casts(cref) {
+                        Constant::GlobalReference { name: Name::Name(name), .. } => name.as_str(),
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+                if call.dest.as_ref().is_some_and(|dest| used.contains(dest)) {
+                    live.insert(callee);
+                }
+            }
         }
+        let mut sources: Vec<&str> = self.config.ext_functions
+            .iter()
+            .filter(|(_, handling)| **handling == config::ExternalFunctionHandling::IgnoreAndReturnTainted)
+            .map(|(name, _)| name.as_str())
+            .chain(self.annotations.source_functions())
+            .filter(|source| !live.iter().any(|callee| demangle::names_for(callee).iter().any(|name| name == source)))
+            .collect();
+        sources.sort_unstable();
+        sources.dedup();
+        sources
+    }
+
+    /// Get the current taint map (from variable name to `TaintedType`) for the
+    /// function with the given name, or `None` if the analysis hasn't reached
+    /// that function yet.
+    ///
+    /// While the analysis is in progress, this reflects only the taint
+    /// discovered so far.
+    pub fn get_function_taint_map(&self, fn_name: &str) -> Option<&HashMap<Name, TaintedType>> {
+        self.fn_taint_states.map.get(fn_name).map(|fts| fts.get_taint_map())
+    }
+
+    /// Get the current `FunctionSummary` for the function with the given name,
+    /// or `None` if the analysis hasn't created one yet.
+    pub fn get_function_summary(&self, fn_name: &str) -> Option<&FunctionSummary<'m>> {
+        self.fn_summaries.get(fn_name)
+    }
+
+    /// Answer calls of the functions in the given `SummaryStore` from their
+    /// summaries there, rather than analyzing those functions. The store can
+    /// be shared between any number of analyses of the same module(s) with the
+    /// same `Config`, e.g., of separate entry points.
+    ///
+    /// Functions answered from the store have no taint states or summaries
+    /// of their own in this analysis.
+    pub fn use_summary_store(&mut self, store: Arc<SummaryStore>) {
+        self.summary_store = Some(store);
+    }
+
+    /// Set whether the functions configured to return tainted data (with
+    /// `ExternalFunctionHandling::IgnoreAndReturnTainted`, in
+    /// `Config::ext_functions` or by default) or annotated as sources do so.
+    /// If not, their results are untainted, as if they weren't sources at all.
+    pub(crate) fn set_ext_sources_tainted(&mut self, tainted: bool) {
+        self.ext_sources_tainted = tainted;
     }
 
     /// Run the fixpoint algorithm to completion.
-    fn compute(&mut self) {
+    pub(crate) fn compute(&mut self) {
+        while self.step() {}
+        for source in self.get_dead_sources() {
+            warn!("Taint from source {:?} never reached any other value; check its name and signature", source);
+        }
+    }
+
+    /// Run at most `budget` steps of the fixpoint algorithm (see `step()`).
+    ///
+    /// Returns `true` if the analysis has reached its fixpoint, or `false` if
+    /// it ran out of budget first (in which case it can be resumed with
+    /// another call to `run_until()` or `step()`).
+    pub fn run_until(&mut self, budget: usize) -> bool {
+        for _ in 0 .. budget {
+            if !self.step() {
+                return true;
+            }
+        }
+        self.is_done()
+    }
+
+    /// Run a single step of the fixpoint algorithm: pop one function off the
+    /// worklist and process it.
+    ///
+    /// Returns `true` if a step was taken, or `false` if the worklist was
+    /// already empty (i.e., the analysis has reached its fixpoint).
+    pub fn step(&mut self) -> bool {
         // We use a worklist fixpoint algorithm where `self.worklist` contains
         // names of functions which need another pass because of changes made to
         // the `TaintedType` of variables that may affect that function's analysis.
@@ -246,126 +1166,326 @@ impl<'m> TaintState<'m> {
         // In either case, this is guaranteed to converge because we only ever
         // change things from untainted to tainted. In the limit, everything becomes
         // tainted, and then nothing can change so the algorithm must terminate.
-        loop {
-            let fn_name = match self.worklist.borrow_mut().pop() {
-                Some(fn_name) => fn_name,
-                None => break,
-            };
-            debug!("Popped {:?} from worklist", fn_name);
-            let changed = match self.analysis.get_func_by_name(fn_name) {
-                Some((func, module)) => {
-                    // internal function (defined in one of the available modules):
-                    // process it normally
-                    self
-                        .process_function(func, module)
-                        .unwrap_or_else(|e| panic!("In module {:?}:\nin function {:?}:\n{}", &module.name, fn_name, e))
-                },
-                None => {
-                    // external function (not defined in the current module):
-                    // see how we're configured to handle this function
-                    use config::ExternalFunctionHandling;
-                    let handling = self.config.ext_functions.get(fn_name).unwrap_or(&self.config.ext_functions_default);
-                    match handling {
-                        ExternalFunctionHandling::IgnoreAndReturnUntainted => {
-                            // no need to do anything
-                            false
-                        },
-                        ExternalFunctionHandling::IgnoreAndReturnTainted => {
-                            // mark the return value tainted, if it wasn't already.
-                            // we require that anyone who places an external
-                            // function on the worklist is responsible for
-                            // making sure it has at least a default summary in
-                            // place, so we can assume here that there is a
-                            // summary
-                            let summary = self.fn_summaries.get_mut(fn_name).unwrap_or_else(|| panic!("Internal invariant violated: External function {:?} on the worklist has no summary", fn_name));
-                            summary.taint_ret()
-                        },
-                        ExternalFunctionHandling::PropagateTaintShallow => {
-                            // again, we require that anyone who places an
-                            // external function on the worklist is responsible
-                            // for making sure it has at least a default summary
-                            // in place, so we can assume here that there is a
-                            // summary
-                            let summary = self.fn_summaries.get_mut(fn_name).unwrap_or_else(|| panic!("Internal invariant violated: External function {:?} on the worklist has no summary", fn_name));
-                            // we effectively inline self.is_type_tainted(), in order to prove to the borrow checker that `summary` borrows a different part of `self` than we need for `is_type_tainted()`
-                            let mut named_structs = self.named_structs.borrow_mut();
-                            let cur_fn = self.cur_fn;
-                            if summary.get_params().any(|p| named_structs.is_type_tainted(p, cur_fn)) {
+        let fn_name = match self.worklist.write().unwrap().pop() {
+            Some(fn_name) => fn_name,
+            None => return false,
+        };
+        debug!("Popped {:?} from worklist", fn_name);
+        let changed = match self.get_analyzed_func_by_name(self.clones.base_function(fn_name)) {
+            Some((func, module)) => {
+                // internal function (defined in one of the available modules),
+                // or a clone of one: process it normally
+                self
+                    .process_function(fn_name, func, module)
+                    .unwrap_or_else(|e| panic!("In module {:?}:\nin function {:?}:\n{}", &module.name, fn_name, e))
+            },
+            None => {
+                // external function (not defined in the current module):
+                // see how we're configured to handle this function
+                use config::ExternalFunctionHandling;
+                let summary_changed = match self.ext_function_handling(fn_name) {
+                    ExternalFunctionHandling::IgnoreAndReturnUntainted => {
+                        // no need to do anything
+                        false
+                    },
+                    ExternalFunctionHandling::IgnoreAndReturnTainted if !self.ext_sources_tainted => {
+                        // sources are off: just like IgnoreAndReturnUntainted
+                        false
+                    },
+                    ExternalFunctionHandling::IgnoreAndReturnTainted => {
+                        // mark the return value tainted, if it wasn't already
+                        match self.get_or_create_external_summary(fn_name) {
+                            Some(summary) => summary.taint_ret(),
+                            None => false,
+                        }
+                    },
+                    ExternalFunctionHandling::PropagateTaintShallow => {
+                        // we effectively inline self.is_type_tainted(), since `summary` borrows `self`
+                        let named_structs = Arc::clone(&self.named_structs);
+                        let cur_fn = self.cur_fn;
+                        match self.get_or_create_external_summary(fn_name) {
+                            Some(summary) if summary.are_varargs_tainted() || summary.get_params().any(|p| named_structs.write().unwrap().is_type_tainted(p, cur_fn)) => {
                                 summary.taint_ret()
-                            } else {
+                            },
+                            _ => {
                                 // no need to do anything, just like the IgnoreAndReturnUntainted case
                                 false
-                            }
-                        },
-                        ExternalFunctionHandling::PropagateTaintDeep => {
-                            unimplemented!("ExternalFunctionHandling::PropagateTaintDeep")
-                        },
-                        ExternalFunctionHandling::Panic => {
-                            panic!("Call of a function named {:?} not found in the module", fn_name)
-                        },
-                    }
-                },
-            };
-            if changed {
-                self.worklist.borrow_mut().add(fn_name);
+                            },
+                        }
+                    },
+                    ExternalFunctionHandling::PropagateTaintDeep => {
+                        unimplemented!("ExternalFunctionHandling::PropagateTaintDeep")
+                    },
+                    ExternalFunctionHandling::Panic => {
+                        panic!("Call of a function named {:?} not found in the module", fn_name)
+                    },
+                };
+                if summary_changed {
+                    // the callers were processed with the old summary, so
+                    // they need to see the new one
+                    self.add_callers_to_worklist(fn_name);
+                    self.trace_summary(fn_name);
+                }
+                summary_changed
+            },
+        };
+        if changed {
+            self.worklist.write().unwrap().add(fn_name);
+        }
+        true
+    }
+
+    /// Get the handling of the given external function: as configured in
+    /// `Config::ext_functions`, or else the default for the module defining it
+    /// (if it's context-only) or the modules calling it (see
+    /// `Config::ext_functions_default_by_module`), refined by
+    /// its attributes if `Config::ext_function_attributes` is set. Functions
+    /// annotated as sources (see `Config::honor_annotations`) always return
+    /// tainted data.
+    fn ext_function_handling(&self, fn_name: &str) -> config::ExternalFunctionHandling {
+        if self.annotations.is_source_function(fn_name) {
+            return config::ExternalFunctionHandling::IgnoreAndReturnTainted;
+        }
+        let modules: Modules<'m> = self.analysis.modules().collect();
+        // a function defined in a context-only module gets that module's
+        // default first
+        let defining_module = self.analysis.get_func_by_name(fn_name).map(|(_, module)| module);
+        let handling = self.config
+            .get_ext_function(fn_name)
+            .unwrap_or_else(|| self.config.ext_functions_default_for(defining_module.into_iter().chain(modules.modules_calling(fn_name))));
+        if self.config.ext_function_attributes {
+            attributes::refine_ext_handling(handling, modules.call_site_attributes(fn_name))
+        } else {
+            handling
+        }
+    }
+
+    /// Get the definition of the function with the given name, and the module
+    /// defining it, if we analyze it: i.e., it's defined in one of the modules,
+    /// but not one of the `Config::context_only_modules`. Functions we don't
+    /// analyze are handled as external functions.
+    fn get_analyzed_func_by_name(&self, fn_name: &str) -> Option<(&'m Function, &'m Module)> {
+        self.analysis
+            .get_func_by_name(fn_name)
+            .filter(|(_, module)| !self.config.is_context_only(module))
+    }
+
+    /// Get the summary of the given external function. If it doesn't have one
+    /// yet (e.g., it was put on the worklist other than by a call of it),
+    /// first create the default one, with nothing tainted, using the parameter
+    /// and return types it's declared with.
+    ///
+    /// Returns `None` if the function has no summary and isn't called directly
+    /// anywhere, so its type is unknown. (Then nothing can use its summary
+    /// anyway.)
+    fn get_or_create_external_summary(&mut self, fn_name: &'m str) -> Option<&mut FunctionSummary<'m>> {
+        if !self.fn_summaries.contains_key(fn_name) {
+            let modules: Modules<'m> = self.analysis.modules().collect();
+            let (param_types, result_type) = match modules.declared_type_of_callee(fn_name)?.as_ref() {
+                Type::FuncType { param_types, result_type, .. } => (param_types.clone(), result_type.clone()),
+                ty => panic!("External function {:?} is declared with non-function type {}", fn_name, ty),
+            };
+            let summary = FunctionSummary::new_untainted(param_types, &result_type, Arc::clone(&self.named_structs));
+            self.fn_summaries.insert(fn_name, summary);
+        }
+        self.fn_summaries.get_mut(fn_name)
+    }
+
+    /// Record a propagation step for the given instruction, if we're recording
+    /// a propagation trace
+    fn trace_instruction(&mut self, inst: &'m Instruction) {
+        if self.config.propagation_trace.is_none() {
+            return;
+        }
+        let result = inst.try_get_result();
+        let event = self.trace_event_for(&inst.to_string(), &instruction_operands(inst), result, "instruction");
+        self.write_trace_event(event);
+    }
+
+    /// Record a propagation step for the given terminator, if we're recording
+    /// a propagation trace
+    fn trace_terminator(&mut self, term: &'m Terminator) {
+        if self.config.propagation_trace.is_none() {
+            return;
+        }
+        let mut event = self.trace_event_for(&term.to_string(), &terminator_operands(term), None, "terminator");
+        let cur_block = self.cur_block.expect("trace_terminator should only be called while processing a block");
+        event["tainted"] = self.fn_taint_states.get_current().is_terminator_tainted(cur_block).into();
+        self.write_trace_event(event);
+    }
+
+    /// Build the trace event describing the processing of an instruction or
+    /// terminator in the current function
+    fn trace_event_for(&mut self, text: &str, operands: &[Operand], result: Option<&Name>, kind: &str) -> Value {
+        let cur_fn_name = self.cur_fn;
+        let cur_fn = self.fn_taint_states.get_current();
+        let operand_entries = trace::operand_entries(operands, cur_fn, cur_fn_name);
+        let mut named_structs = self.named_structs.write().unwrap();
+        let mut event = json!({
+            "kind": kind,
+            "function": cur_fn_name,
+            "block": self.cur_block.map(|block| block.to_string()),
+            "instruction": text,
+            "operands": operand_entries,
+            "result": result.map(|name| name.to_string()),
+            "type": Value::Null,
+            "tainted": false,
+        });
+        if let Some(ty) = result.and_then(|name| cur_fn.get_taint_map().get(name)) {
+            let entry = trace::type_entry(ty, &mut named_structs, cur_fn_name);
+            event["type"] = entry["type"].clone();
+            event["tainted"] = entry["tainted"].clone();
+        }
+        event
+    }
+
+    /// Record a propagation step for a change to the summary of the function
+    /// with the given name, if we're recording a propagation trace
+    fn trace_summary(&mut self, fn_name: &str) {
+        if self.config.propagation_trace.is_none() {
+            return;
+        }
+        let summary = match self.fn_summaries.get(fn_name) {
+            Some(summary) => summary,
+            None => return,
+        };
+        let mut named_structs = self.named_structs.write().unwrap();
+        let cur_fn = self.cur_fn;
+        let event = json!({
+            "kind": "summary",
+            "function": fn_name,
+            "params": summary.get_params().map(|ty| trace::type_entry(ty, &mut named_structs, cur_fn)).collect::<Vec<_>>(),
+            "ret": summary.get_ret_ty().as_ref().map(|ty| trace::type_entry(ty, &mut named_structs, cur_fn)),
+            "varargs_tainted": summary.are_varargs_tainted(),
+        });
+        drop(named_structs);
+        self.write_trace_event(event);
+    }
+
+    /// Record why the result of the given instruction (which must be the
+    /// current instruction, and just changed some taint information), and any
+    /// memory it wrote, became tainted, if we're recording provenance and
+    /// have no record for them yet
+    fn record_provenance(&mut self, inst: &'m Instruction) {
+        if !self.config.record_provenance {
+            return;
+        }
+        let fn_name = self.clones.base_function(self.cur_fn);
+        let cur_fn = self.fn_taint_states.get_current();
+        let operand_tys: Vec<(Name, TaintedType)> = instruction_operands(inst)
+            .iter()
+            .filter_map(|op| match op {
+                Operand::LocalOperand { name, .. } => cur_fn.get_type_of_operand(op).ok().map(|ty| (name.clone(), ty)),
+                _ => None,
+            })
+            .collect();
+        let result_ty = inst.try_get_result().and_then(|dest| cur_fn.get_taint_map().get(dest).map(|ty| (dest.clone(), ty.clone())));
+        let mut cause = Cause {
+            block: self.cur_block.cloned().unwrap(),
+            index: self.cur_inst.unwrap(),
+            tainted_operands: vec![],
+            tainted_contents: vec![],
+        };
+        for (name, ty) in operand_tys {
+            if self.is_type_tainted(&ty) {
+                cause.tainted_operands.push(name.clone());
+            }
+            if let TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) = &ty {
+                let pointee_ty = pointee.ty().clone();
+                if self.is_type_tainted(&pointee_ty) {
+                    cause.tainted_contents.push((name, pointee.id()));
+                }
+            }
+        }
+        if may_write_memory(inst) {
+            for (_, pointee_id) in &cause.tainted_contents {
+                self.provenance.record_contents(*pointee_id, fn_name, cause.clone());
+            }
+        }
+        if let Some((dest, ty)) = result_ty {
+            if self.is_type_tainted(&ty) {
+                self.provenance.record_var(fn_name, dest, cause);
             }
         }
     }
 
+    /// Number the given event and write it to the propagation trace, if we're
+    /// recording one
+    fn write_trace_event(&mut self, mut event: Value) {
+        if let Some(propagation_trace) = &self.config.propagation_trace {
+            event["step"] = self.trace_steps.into();
+            self.trace_steps += 1;
+            trace::write_event(propagation_trace, &event);
+        }
+    }
+
     /// Get the `TaintedType` for the given struct name.
     /// Marks the current function as a user of this named struct.
     /// Creates an untainted `TaintedType` for this named struct if no type
     /// previously existed for it.
-    pub fn get_named_struct_type(&mut self, struct_name: impl Into<String>) -> TaintedType {
-        self.named_structs.borrow_mut().get_named_struct_type(struct_name.into(), &self.cur_fn).clone()
+    pub(crate) fn get_named_struct_type(&mut self, struct_name: impl Into<String>) -> TaintedType {
+        self.named_structs.write().unwrap().get_named_struct_type(struct_name.into(), self.cur_fn).clone()
     }
 
     /// Is this type tainted (or, for structs, is any element of the struct tainted)
-    pub fn is_type_tainted(&mut self, ty: &TaintedType) -> bool {
-        self.named_structs.borrow_mut().is_type_tainted(ty, &self.cur_fn)
+    pub(crate) fn is_type_tainted(&mut self, ty: &TaintedType) -> bool {
+        // most queries only need to read the named structs
+        let cached = self.named_structs.read().unwrap().is_type_tainted_if_cached(ty, self.cur_fn);
+        cached.unwrap_or_else(|| self.named_structs.write().unwrap().is_type_tainted(ty, self.cur_fn))
     }
 
     /// Convert this (tainted or untainted) type to the equivalent tainted type.
     ///
     /// This may have side effects, such as permanently marking struct fields or
     /// pointees as tainted.
-    pub fn to_tainted(&self, ty: &TaintedType) -> TaintedType {
-        self.named_structs.borrow_mut().to_tainted(ty)
+    pub(crate) fn to_tainted(&self, ty: &TaintedType) -> TaintedType {
+        self.named_structs.write().unwrap().to_tainted(ty)
+    }
+
+    /// Produce the (untainted) `TaintedType` for a given LLVM type, respecting
+    /// `Config::max_pointer_depth` (see `NamedStructs::type_from_llvm()`)
+    fn type_from_llvm(&self, llvm_ty: &Type) -> TaintedType {
+        self.named_structs.write().unwrap().type_from_llvm(llvm_ty)
     }
 
     /// Process the given `Function` in the given `Module`.
     ///
     /// Returns `true` if a change was made to the function's taint state, or `false` if not.
-    fn process_function(&mut self, f: &'m Function, m: &'m Module) -> Result<bool, String> {
-        debug!("Processing function {:?}", &f.name);
-        self.cur_fn = &f.name;
+    ///
+    /// `fn_name` is the name of the function, or of the clone of it (see
+    /// `FunctionClones`) to process.
+    fn process_function(&mut self, fn_name: &'m str, f: &'m Function, m: &'m Module) -> Result<bool, String> {
+        debug!("Processing function {:?}", fn_name);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("function_pass", function = fn_name, module = %m.name).entered();
+        self.cur_fn = fn_name;
         self.cur_mod = m;
-        self.fn_taint_states.set_current_fn(&f.name);
+        self.fn_taint_states.set_current_fn(fn_name);
 
         // get the taint state for the current function, creating a new one if necessary
         let cur_mod = self.cur_mod; // this is for the borrow checker - allows us to access `cur_mod` without needing to borrow `self`
-        let named_structs: &Rc<_> = &self.named_structs; // similarly for the borrow checker - see note on above line
-        let worklist: &Rc<_> = &self.worklist; // similarly for the borrow checker - see note on above line
-        let globals: &Rc<_> = &self.globals; // similarly for the borrow checker - see note on above line
+        let named_structs: &Arc<_> = &self.named_structs; // similarly for the borrow checker - see note on above line
+        let worklist: &Arc<_> = &self.worklist; // similarly for the borrow checker - see note on above line
+        let globals: &Arc<_> = &self.globals; // similarly for the borrow checker - see note on above line
         let cur_fn = self
             .fn_taint_states
             .get_current_or_insert_with(|| {
                 FunctionTaintState::from_taint_map(
-                    &f.name,
+                    fn_name,
                     f.parameters
                         .iter()
                         .map(|p| {
-                            (p.name.clone(), TaintedType::from_llvm_type(&cur_mod.type_of(p)))
+                            (p.name.clone(), named_structs.write().unwrap().type_from_llvm(&cur_mod.type_of(p)))
                         })
                         .collect(),
                     cur_mod,
-                    Rc::clone(named_structs),
-                    Rc::clone(globals),
-                    Rc::clone(worklist),
+                    Arc::clone(named_structs),
+                    Arc::clone(globals),
+                    Arc::clone(worklist),
                 )
             });
 
-        let summary = match self.fn_summaries.entry(&f.name) {
+        let summary = match self.fn_summaries.entry(fn_name) {
             Entry::Vacant(ventry) => {
                 // no summary: make a starter one, assuming everything is untainted
                 let cur_mod = self.cur_mod;
@@ -374,11 +1494,14 @@ impl<'m> TaintState<'m> {
                 ventry.insert(FunctionSummary::new_untainted(
                     param_llvm_types,
                     ret_llvm_type,
-                    Rc::clone(&self.named_structs),
+                    Arc::clone(&self.named_structs),
                 ))
             },
             Entry::Occupied(oentry) => oentry.into_mut(),
         };
+        if let Some(index) = attributes::sret_index(f.parameters.iter().map(|p| p.attributes.as_slice())) {
+            summary.set_sret_param(index);
+        }
         // update the function parameter types from the current summary
         for (param, param_ty) in f.parameters.iter().zip_eq(summary.get_params()) {
             let _: bool = cur_fn.update_var_taintedtype(param.name.clone(), param_ty.clone()).map_err(|e| {
@@ -401,529 +1524,2145 @@ impl<'m> TaintState<'m> {
         if summary.update_params(param_tainted_types)? {
             // summary changed: put all callers of this function on the worklist
             // because the new summary could affect inferred types in its callers
-            let mut worklist = self.worklist.borrow_mut();
-            for caller in self.analysis.call_graph().callers(self.cur_fn) {
-                worklist.add(caller);
-            }
+            self.add_callers_to_worklist(self.cur_fn);
+            self.trace_summary(self.cur_fn);
         }
 
         // now do a pass over the function to propagate taints
         let mut changed = false;
-        for bb in &f.basic_blocks {
-            self.cur_block = Some(&bb.name);
-            for inst in &bb.instrs {
-                changed |= self.process_instruction(inst).map_err(|e| {
-                    format!(
-                        "Encountered this error:\n  {}\nwhile processing this instruction:\n  {:?}",
-                        e, inst
-                    )
-                })?;
+        let sanitization_enabled = self.config.sanitize_bounds_checks || !self.config.validators.is_empty();
+        if (self.config.flow_sensitive_memory || self.config.promote_allocas) && !self.stack_slots.contains_key(self.cur_fn) {
+            let cfg = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(&f.name).control_flow_graph();
+            let slots = StackSlots::new(f, &cfg, self.config.promote_allocas);
+            self.stack_slots.insert(self.cur_fn, slots);
+        }
+        let parallel = !sanitization_enabled
+            && self.config.parallel_blocks_threshold.is_some_and(|threshold| f.basic_blocks.len() >= threshold);
+        if parallel {
+            changed |= self.process_blocks_in_parallel(f)?;
+        } else {
+            for bb in &f.basic_blocks {
+                changed |= self.process_block(f, bb, sanitization_enabled)?;
             }
-            changed |= self.process_terminator(&bb.term).map_err(|e| {
+        }
+        self.cur_block = None;
+        if sanitization_enabled {
+            self.fn_taint_states.get_current().set_sanitized(HashSet::new(), HashSet::new());
+        }
+        Ok(changed)
+    }
+
+    /// Process the given basic block of the given function (which must be the
+    /// current function), one instruction at a time.
+    ///
+    /// Returns `true` if any taint information changed.
+    fn process_block(&mut self, f: &'m Function, bb: &'m BasicBlock, sanitization_enabled: bool) -> Result<bool, String> {
+        let mut changed = false;
+        self.cur_block = Some(&bb.name);
+        if sanitization_enabled {
+            let (vars, addrs) = self.compute_sanitized(f, &bb.name);
+            self.fn_taint_states.get_current().set_sanitized(vars, addrs);
+        }
+        for (index, inst) in bb.instrs.iter().enumerate() {
+            self.cur_inst = Some(index);
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("instruction", kind = instruction_kind(inst), block = %bb.name, index).entered();
+            let inst_changed = self.process_instruction(inst).map_err(|e| {
                 format!(
-                    "Encountered this error:\n  {}\nwhile processing this terminator:\n  {:?}",
-                    e, &bb.term
+                    "Encountered this error:\n  {}\nwhile processing this instruction:\n  {:?}",
+                    e, inst
                 )
             })?;
+            if self.config.max_pointer_depth.is_some() && self.named_structs.write().unwrap().take_deep_pointees_changed() {
+                // deep pointees are shared by all functions, so any
+                // function may see the change
+                let mut worklist = self.worklist.write().unwrap();
+                for fn_name in self.fn_taint_states.map.keys() {
+                    worklist.add(fn_name);
+                }
+            }
+            if inst_changed {
+                self.trace_instruction(inst);
+                self.record_provenance(inst);
+                if self.config.shared_memory && may_write_memory(inst) {
+                    // conservatively, the change may be to memory which
+                    // any other function can read
+                    let mut worklist = self.worklist.write().unwrap();
+                    for fn_name in self.fn_taint_states.map.keys() {
+                        worklist.add(fn_name);
+                    }
+                }
+            }
+            changed |= inst_changed;
+        }
+        self.cur_inst = None;
+        changed |= self.process_block_terminator(bb)?;
+        Ok(changed)
+    }
+
+    /// Process the terminator of the given basic block, which must be the
+    /// current block
+    fn process_block_terminator(&mut self, bb: &'m BasicBlock) -> Result<bool, String> {
+        let term_changed = self.process_terminator(&bb.term).map_err(|e| {
+            format!(
+                "Encountered this error:\n  {}\nwhile processing this terminator:\n  {:?}",
+                e, &bb.term
+            )
+        })?;
+        if term_changed {
+            self.trace_terminator(&bb.term);
+        }
+        Ok(term_changed)
+    }
+
+    /// Do a pass over the given function (which must be the current function)
+    /// as described for `Config::parallel_blocks_threshold`: level by level
+    /// (see `block_levels()`), processing the blocks which contain only
+    /// instructions handled by `local_result_ty()` in parallel, and then the
+    /// other blocks one at a time.
+    ///
+    /// The results of the parallel blocks are computed from the taint map as
+    /// it was at the start of the level, and applied in block order, so
+    /// they're the same as if the blocks had been processed one at a time.
+    ///
+    /// Returns `true` if any taint information changed.
+    fn process_blocks_in_parallel(&mut self, f: &'m Function) -> Result<bool, String> {
+        let mut changed = false;
+        let num_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        // instructions with bit-level taint aren't handled by
+        // `local_result_ty()`
+        let bit_level = self.config.bit_level_taint;
+        for level in block_levels(f) {
+            let (local_blocks, other_blocks): (Vec<&'m BasicBlock>, Vec<&'m BasicBlock>) = level
+                .into_iter()
+                .partition(|bb| bb.instrs.iter().all(|inst| is_local_instruction(inst) && !(bit_level && tracks_bits(inst))));
+            let cur_fn_name = self.cur_fn;
+            let cur_fn: &FunctionTaintState<'m> = self.fn_taint_states.get_current();
+            let named_structs = &self.named_structs;
+            let config = self.config;
+            let chunk_size = local_blocks.len().div_ceil(num_threads).max(1);
+            #[cfg(feature = "tracing")]
+            let pass_span = &tracing::Span::current();
+            let results: Vec<Result<Vec<TaintedType>, String>> = std::thread::scope(|scope| {
+                let handles: Vec<_> = local_blocks
+                    .chunks(chunk_size)
+                    .map(|chunk| scope.spawn(move || {
+                        // the worker's instruction spans belong to this pass
+                        #[cfg(feature = "tracing")]
+                        let _span = pass_span.enter();
+                        chunk.iter().map(|bb| local_block_result_tys(bb, cur_fn, named_structs, cur_fn_name, config)).collect::<Vec<_>>()
+                    }))
+                    .collect();
+                handles.into_iter().flat_map(|handle| handle.join().expect("thread processing blocks panicked")).collect()
+            });
+            for (bb, result_tys) in local_blocks.into_iter().zip(results) {
+                self.cur_block = Some(&bb.name);
+                for (index, (inst, result_ty)) in bb.instrs.iter().zip(result_tys?).enumerate() {
+                    self.cur_inst = Some(index);
+                    let dest = inst.try_get_result().expect("instructions handled by local_result_ty() have results").clone();
+                    let inst_changed = self.fn_taint_states.get_current().update_var_taintedtype(dest, result_ty).map_err(|e| {
+                        format!(
+                            "Encountered this error:\n  {}\nwhile processing this instruction:\n  {:?}",
+                            e, inst
+                        )
+                    })?;
+                    if inst_changed {
+                        self.trace_instruction(inst);
+                        self.record_provenance(inst);
+                    }
+                    changed |= inst_changed;
+                }
+                self.cur_inst = None;
+                changed |= self.process_block_terminator(bb)?;
+            }
+            for bb in other_blocks {
+                changed |= self.process_block(f, bb, false)?;
+            }
         }
-        self.cur_block = None;
         Ok(changed)
     }
 
+    /// Compute the variables and addresses which are sanitized (see
+    /// `Config::sanitize_bounds_checks` and `Config::validators`) in the given
+    /// block of the given function, which must be the current function
+    fn compute_sanitized(&self, f: &'m Function, block: &'m Name) -> (HashSet<Name>, HashSet<Name>) {
+        let fn_analysis = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(&f.name);
+        let cfg = fn_analysis.control_flow_graph();
+        let domtree = fn_analysis.dominator_tree();
+        let mut vars = HashSet::new();
+        let mut addrs = HashSet::new();
+        for bb in &f.basic_blocks {
+            if let Terminator::CondBr(condbr) = &bb.term {
+                if let Some((checked_vars, valid_dest)) = self.sanitizing_check(f, condbr) {
+                    let only_pred_is_check = cfg.preds(valid_dest).all(|pred| pred == &bb.name);
+                    if only_pred_is_check && domtree.dominates(CFGNode::Block(valid_dest), CFGNode::Block(block)) {
+                        for var in checked_vars {
+                            if let Some(Instruction::Load(load)) = find_definition(f, &var) {
+                                if let Operand::LocalOperand { name, .. } = &load.address {
+                                    addrs.insert(name.clone());
+                                }
+                            }
+                            vars.insert(var);
+                        }
+                    }
+                }
+            }
+        }
+        (vars, addrs)
+    }
+
+    /// If the given conditional branch in the given function (which must be
+    /// the current function) is a sanitizing check (see
+    /// `Config::sanitize_bounds_checks` and `Config::validators`), get the
+    /// variables it sanitizes, and the successor taken when the check passes
+    fn sanitizing_check(&self, f: &'m Function, condbr: &'m terminator::CondBr) -> Option<(Vec<Name>, &'m Name)> {
+        let validator_args = |inst: &Instruction| -> Option<Vec<Name>> {
+            match inst {
+                Instruction::Call(call) => match &call.function {
+                    Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+                        Constant::GlobalReference { name: Name::Name(name), .. } if self.config.is_validator(name) => {
+                            Some(call.arguments.iter().filter_map(|(op, _)| match op {
+                                Operand::LocalOperand { name, .. } => Some(name.clone()),
+                                _ => None,
+                            }).collect())
+                        },
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+        let cond_name = match &condbr.condition {
+            Operand::LocalOperand { name, .. } => name,
+            _ => return None,
+        };
+        let (pass, fail) = (&condbr.true_dest, &condbr.false_dest);
+        match find_definition(f, cond_name)? {
+            inst @ Instruction::Call(_) => validator_args(inst).map(|args| (args, pass)),
+            Instruction::ICmp(icmp) => {
+                // a validator's result compared against zero
+                let is_zero = |op: &Operand| matches!(op, Operand::ConstantOperand(cref) if matches!(cref.as_ref(), Constant::Int { value: 0, .. }));
+                let validator_result = match (&icmp.operand0, &icmp.operand1) {
+                    (Operand::LocalOperand { name, .. }, zero) | (zero, Operand::LocalOperand { name, .. }) if is_zero(zero) => {
+                        find_definition(f, name).and_then(validator_args)
+                    },
+                    _ => None,
+                };
+                if let Some(args) = validator_result {
+                    return match icmp.predicate {
+                        IntPredicate::NE => Some((args, pass)),
+                        IntPredicate::EQ => Some((args, fail)),
+                        _ => None,
+                    };
+                }
+                if !self.config.sanitize_bounds_checks {
+                    return None;
+                }
+                // a bounds check: exactly one operand must be tainted
+                let cur_fn = self.fn_taint_states.map.get(self.cur_fn)?;
+                let is_tainted = |op: &Operand| cur_fn.get_type_of_operand_unsanitized(op).map(|ty| ty.is_tainted_nonamedstruct()).unwrap_or(false);
+                let (var, var_on_left) = match (&icmp.operand0, &icmp.operand1) {
+                    (Operand::LocalOperand { name, .. }, bound) if !is_tainted(bound) => (name, true),
+                    (bound, Operand::LocalOperand { name, .. }) if !is_tainted(bound) => (name, false),
+                    _ => return None,
+                };
+                use IntPredicate::*;
+                let bounded_above_if_true = match icmp.predicate {
+                    EQ => return Some((vec![var.clone()], pass)),
+                    NE => return Some((vec![var.clone()], fail)),
+                    ULT | ULE | SLT | SLE => var_on_left,
+                    UGT | UGE | SGT | SGE => !var_on_left,
+                };
+                Some((vec![var.clone()], if bounded_above_if_true { pass } else { fail }))
+            },
+            _ => None,
+        }
+    }
+
     /// Process the given `Instruction`, updating the current function's
     /// `FunctionTaintState` if appropriate.
     ///
     /// Returns `true` if a change was made to the `FunctionTaintState`, or `false` if not.
     fn process_instruction(&mut self, inst: &'m Instruction) -> Result<bool, String> {
         // debug!("Processing {}", brief_display_instruction(inst));
-        if inst.is_binary_op() {
-            let cur_fn = self.fn_taint_states.get_current();
-            let bop: groups::BinaryOp = inst.clone().try_into().unwrap();
-            let op0_ty = cur_fn.get_type_of_operand(bop.get_operand0())?;
-            let op1_ty = cur_fn.get_type_of_operand(bop.get_operand1())?;
-            let result_ty = op0_ty.join(&op1_ty)?;
-            cur_fn.update_var_taintedtype(bop.get_result().clone(), result_ty)
-        } else {
-            match inst {
-                // the unary ops which output the same type they input, in our type system
-                Instruction::AddrSpaceCast(_)
-                | Instruction::FNeg(_)
-                | Instruction::FPExt(_)
-                | Instruction::FPToSI(_)
-                | Instruction::FPToUI(_)
-                | Instruction::FPTrunc(_)
-                | Instruction::SExt(_)
-                | Instruction::SIToFP(_)
-                | Instruction::Trunc(_)
-                | Instruction::UIToFP(_)
-                | Instruction::ZExt(_) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let uop: groups::UnaryOp = inst.clone().try_into().unwrap();
-                    let op_ty = cur_fn.get_type_of_operand(uop.get_operand())?;
-                    cur_fn.update_var_taintedtype(uop.get_result().clone(), op_ty)
-                },
-                Instruction::BitCast(bc) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let from_ty = cur_fn.get_type_of_operand(&bc.operand)?;
-                    let result_ty = match &from_ty {
-                        TaintedType::UntaintedValue | TaintedType::UntaintedFnPtr => {
-                            TaintedType::from_llvm_type(&bc.to_type)
-                        },
-                        TaintedType::TaintedValue | TaintedType::TaintedFnPtr => {
-                            self.to_tainted(&TaintedType::from_llvm_type(&bc.to_type))
-                        },
-                        TaintedType::UntaintedPointer(pointee)
-                        | TaintedType::TaintedPointer(pointee) => match bc.to_type.as_ref() {
-                            Type::PointerType { pointee_type, .. } => {
-                                let result_pointee_type = if self.is_type_tainted(&pointee.ty()) {
-                                    self.to_tainted(&TaintedType::from_llvm_type(&pointee_type))
-                                } else {
-                                    TaintedType::from_llvm_type(&pointee_type)
-                                };
-                                if self.is_type_tainted(&from_ty) {
-                                    TaintedType::tainted_ptr_to(result_pointee_type)
-                                } else {
-                                    TaintedType::untainted_ptr_to(result_pointee_type)
-                                }
-                            },
-                            _ => return Err("Bitcast from pointer to non-pointer".into()), // my reading of the LLVM 9 LangRef disallows this
-                        },
-                        from_ty @ TaintedType::ArrayOrVector(_)
-                        | from_ty @ TaintedType::Struct(_) => {
-                            if self.is_type_tainted(from_ty) {
-                                self.to_tainted(&TaintedType::from_llvm_type(&bc.to_type))
+        if self.config.bit_level_taint {
+            if let Some(changed) = self.process_bit_level_instruction(inst)? {
+                return Ok(changed);
+            }
+        }
+        let cur_fn_name = self.cur_fn;
+        let named_structs = &self.named_structs;
+        let config = self.config;
+        let cur_fn = self.fn_taint_states.get_current();
+        let local_ty = local_result_ty(
+            inst,
+            |op| cur_fn.get_type_of_operand(op),
+            |ty| named_structs.write().unwrap().is_type_tainted(ty, cur_fn_name),
+            |ty1, ty2| named_structs.write().unwrap().join(ty1, ty2, cur_fn_name),
+            |class| config.propagation_policy(class),
+            |inst| sanitizes_result(inst, config),
+        );
+        if let Some(result_ty) = local_ty {
+            let dest = inst.try_get_result().expect("instructions handled by local_result_ty() have results").clone();
+            return cur_fn.update_var_taintedtype(dest, result_ty?);
+        }
+        match inst {
+            Instruction::BitCast(bc) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let from_ty = cur_fn.get_type_of_operand(&bc.operand)?;
+                let result_ty = match &from_ty {
+                    TaintedType::UntaintedValue | TaintedType::UntaintedFnPtr(_) => {
+                        self.type_from_llvm(&bc.to_type).with_fn_targets_of(&from_ty)
+                    },
+                    TaintedType::TaintedValue | TaintedType::TaintedFnPtr(_) => {
+                        self.to_tainted(&self.type_from_llvm(&bc.to_type).with_fn_targets_of(&from_ty))
+                    },
+                    TaintedType::UntaintedPointer(_)
+                    | TaintedType::TaintedPointer(_) if self.is_cast_of_allocation(bc) => {
+                        return self.process_allocation_cast(bc, &from_ty);
+                    },
+                    TaintedType::UntaintedPointer(pointee)
+                    | TaintedType::TaintedPointer(pointee) => match bc.to_type.as_ref() {
+                        Type::PointerType { pointee_type, .. } => {
+                            let result_pointee_type = if self.is_type_tainted(&pointee.cloned_ty()) {
+                                self.to_tainted(&self.type_from_llvm(pointee_type))
                             } else {
-                                TaintedType::from_llvm_type(&bc.to_type)
+                                self.type_from_llvm(pointee_type)
+                            };
+                            if self.is_type_tainted(&from_ty) {
+                                TaintedType::tainted_ptr_to(result_pointee_type)
+                            } else {
+                                TaintedType::untainted_ptr_to(result_pointee_type)
                             }
                         },
-                        TaintedType::NamedStruct(name) => {
-                            let def = self.get_named_struct_type(name);
-                            if self.is_type_tainted(&def) {
-                                self.to_tainted(&TaintedType::from_llvm_type(&bc.to_type))
+                        _ => return Err("Bitcast from pointer to non-pointer".into()), // my reading of the LLVM 9 LangRef disallows this
+                    },
+                    TaintedType::Token => return Err("Bitcast of a token".into()), // tokens can't be cast
+                    from_ty @ TaintedType::ArrayOrVector(_)
+                    | from_ty @ TaintedType::Struct(_) => {
+                        if self.is_type_tainted(from_ty) {
+                            self.to_tainted(&self.type_from_llvm(&bc.to_type))
+                        } else {
+                            self.type_from_llvm(&bc.to_type)
+                        }
+                    },
+                    TaintedType::NamedStruct(name) => {
+                        let def = self.get_named_struct_type(name);
+                        if self.is_type_tainted(&def) {
+                            self.to_tainted(&self.type_from_llvm(&bc.to_type))
+                        } else {
+                            self.type_from_llvm(&bc.to_type)
+                        }
+                    },
+                };
+                self.fn_taint_states.get_current().update_var_taintedtype(bc.get_result().clone(), result_ty)
+            },
+            Instruction::ExtractElement(ee) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                // the lanes of a vector all share one type (see
+                // `TaintedType::ArrayOrVector`)
+                let element_ty = match cur_fn.get_type_of_operand(&ee.vector)? {
+                    TaintedType::ArrayOrVector(element) => element.cloned_ty(),
+                    ty => ty,
+                };
+                let result_ty = if cur_fn.is_scalar_operand_tainted(&ee.index)? {
+                    taint_lane(element_ty)
+                } else {
+                    element_ty
+                };
+                cur_fn.update_var_taintedtype(ee.get_result().clone(), result_ty)
+            },
+            Instruction::InsertElement(ie) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let vector_ty = cur_fn.get_type_of_operand(&ie.vector)?;
+                let result_ty = if cur_fn.is_scalar_operand_tainted(&ie.index)?
+                    || cur_fn.is_scalar_operand_tainted(&ie.element)?
+                {
+                    map_lanes(vector_ty, taint_lane)
+                } else {
+                    vector_ty // in our type system, inserting an untainted element does't change the type of the vector
+                };
+                cur_fn.update_var_taintedtype(ie.get_result().clone(), result_ty)
+            },
+            Instruction::ExtractValue(ev) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                // We make a pointer to the struct, and add an extra index
+                // representing getting element 0 of the resulting implicit
+                // array of structs, because get_element_ptr expects a pointer
+                let ptr_to_struct =
+                    TaintedType::untainted_ptr_to(cur_fn.get_type_of_operand(&ev.aggregate)?);
+                let indices: Vec<u32> = std::iter::once(&0).chain(ev.indices.iter()).copied().collect();
+                let element_ptr_ty = self.get_element_ptr(&ptr_to_struct, &indices)?;
+                let element_ty = match element_ptr_ty {
+                    TaintedType::UntaintedPointer(pointee) => pointee.ty().clone(),
+                    _ => return Err(format!("ExtractValue: expected get_element_ptr to return an UntaintedPointer here; got {}", element_ptr_ty)),
+                };
+                self.fn_taint_states.get_current().update_var_taintedtype(ev.get_result().clone(), element_ty)
+            },
+            Instruction::InsertValue(iv) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let struct_ty = cur_fn.get_type_of_operand(&iv.aggregate)?;
+                let element_to_insert = cur_fn.get_type_of_operand(&iv.element)?;
+                // We make a pointer to the struct, and add an extra index
+                // representing getting element 0 of the resulting implicit
+                // array of structs, because get_element_ptr expects a pointer
+                let ptr_to_struct = TaintedType::untainted_ptr_to(struct_ty.clone());
+                let indices: Vec<u32> = std::iter::once(&0).chain(iv.indices.iter()).copied().collect();
+                let ptr_to_indicated_element = self.get_element_ptr(&ptr_to_struct, &indices)?;
+                let cur_fn = self.fn_taint_states.get_current();
+                match ptr_to_indicated_element {
+                    TaintedType::UntaintedPointer(mut pointee) | TaintedType::TaintedPointer(mut pointee) => {
+                        cur_fn.update_pointee_taintedtype(&mut pointee, &element_to_insert)?;
+                    },
+                    _ => panic!("Expected get_element_ptr to return a pointer, but got {}", ptr_to_indicated_element),
+                }
+                cur_fn.update_var_taintedtype(iv.get_result().clone(), struct_ty)
+            },
+            Instruction::Alloca(alloca) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let result_ty = if cur_fn.is_scalar_operand_tainted(&alloca.num_elements)? {
+                    TaintedType::TaintedValue
+                } else {
+                    let pointee = cur_fn.named_structs.write().unwrap().pointee_from_llvm(&alloca.allocated_type);
+                    TaintedType::untainted_ptr_to_pointee(pointee)
+                };
+                cur_fn.update_var_taintedtype(alloca.get_result().clone(), result_ty)
+            },
+            Instruction::Load(load) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let addr_ty = cur_fn.get_type_of_operand(&load.address)?;
+                let addr_sanitized = cur_fn.is_address_sanitized(&load.address);
+                let reaching_stores = self.get_reaching_stores();
+                let result_ty = match reaching_stores {
+                    Some(stores) => self.get_stack_slot_load_ty(&self.cur_mod.type_of(load), &stores, &addr_ty)?,
+                    None => self.get_load_result_ty(&addr_ty)?,
+                };
+                let tainted_memory = self.is_device_memory(&load.address)
+                    || self.address_space(&load.address).is_some_and(|space| self.config.tainted_address_spaces.contains(&space));
+                let result_ty = if tainted_memory { self.to_tainted(&result_ty) } else { result_ty };
+                let result_ty = if addr_sanitized { result_ty.sanitized() } else { result_ty };
+                self.fn_taint_states.get_current().update_var_taintedtype(load.get_result().clone(), result_ty)
+            },
+            Instruction::Store(store) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let mut addr_ty = cur_fn.get_type_of_operand(&store.address)?;
+                let new_value_ty = cur_fn.get_type_of_operand(&store.value)?;
+                let mut changed = false;
+                if self.address_space(&store.address).is_some_and(|space| self.config.sink_address_spaces.contains(&space))
+                    && self.is_type_tainted(&new_value_ty)
+                {
+                    let block = self.cur_block.cloned().unwrap();
+                    let index = self.cur_inst.unwrap();
+                    changed |= self.fn_taint_states.get_current().mark_sink_tainted(block, index);
+                }
+                changed |= self.process_store(&new_value_ty, &mut addr_ty)?;
+                Ok(changed)
+            },
+            Instruction::Fence(_) => Ok(false),
+            Instruction::GetElementPtr(gep) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let ptr = cur_fn.get_type_of_operand(&gep.address)?;
+                let index_policy = self.config.propagation_policy(config::InstructionClass::GepIndex);
+                let index_tys = if index_policy != config::PropagationPolicy::Drop {
+                    gep.indices.iter().map(|index| cur_fn.get_type_of_operand(index)).collect::<Result<Vec<_>, _>>()?
+                } else {
+                    vec![]
+                };
+                let result_ty = match &ptr {
+                    // on a vector of pointers, the lanes all share one
+                    // pointee (see `TaintedType::ArrayOrVector`), so we compute
+                    // the result for that, and get a vector of the results
+                    TaintedType::ArrayOrVector(element) => {
+                        let element_ptr = element.ty().clone();
+                        let lane_ty = self.get_element_ptr(&element_ptr, &gep.indices)?;
+                        TaintedType::array_or_vec_of(lane_ty)
+                    },
+                    _ => {
+                        let result_ty = self.get_element_ptr(&ptr, &gep.indices)?;
+                        // a scalar base with vector indices gives a vector of
+                        // pointers too. (`llvm-ir` types a GEP by its address
+                        // alone, so we check the indices ourselves.)
+                        if gep.indices.iter().any(|index| matches!(self.cur_mod.type_of(index).as_ref(), Type::VectorType { .. })) {
+                            TaintedType::array_or_vec_of(result_ty)
+                        } else {
+                            result_ty
+                        }
+                    },
+                };
+                let result_ty = if index_tys.iter().any(|ty| self.is_type_tainted(ty)) {
+                    if index_policy == config::PropagationPolicy::TaintConservatively {
+                        let lane_ty = match &result_ty {
+                            TaintedType::ArrayOrVector(element) => element.ty().clone(),
+                            ty => ty.clone(),
+                        };
+                        lane_ty.taint_contents(&mut self.named_structs.write().unwrap());
+                    }
+                    self.to_tainted(&result_ty)
+                } else {
+                    result_ty
+                };
+                self.fn_taint_states.get_current().update_var_taintedtype(gep.get_result().clone(), result_ty)
+            },
+            Instruction::PtrToInt(pti) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                match cur_fn.get_type_of_operand(&pti.operand)? {
+                    TaintedType::UntaintedPointer(_) | TaintedType::UntaintedFnPtr(_) => {
+                        cur_fn.update_var_taintedtype(pti.get_result().clone(), TaintedType::UntaintedValue)
+                    },
+                    TaintedType::TaintedPointer(_) | TaintedType::TaintedFnPtr(_) => {
+                        cur_fn.update_var_taintedtype(pti.get_result().clone(), TaintedType::TaintedValue)
+                    },
+                    TaintedType::UntaintedValue => {
+                        Err(format!("PtrToInt on an UntaintedValue: {:?}", &pti.operand))
+                    },
+                    TaintedType::TaintedValue => {
+                        Err(format!("PtrToInt on an TaintedValue: {:?}", &pti.operand))
+                    },
+                    TaintedType::Token => {
+                        Err(format!("PtrToInt on a token: {:?}", &pti.operand))
+                    },
+                    TaintedType::ArrayOrVector(_) => {
+                        Err(format!("PtrToInt on an array or vector: {:?}", &pti.operand))
+                    },
+                    TaintedType::Struct(_) | TaintedType::NamedStruct(_) => {
+                        Err(format!("PtrToInt on a struct: {:?}", &pti.operand))
+                    },
+                }
+            },
+            Instruction::IntToPtr(itp) => {
+                // we make the (potentially unsound) assumption that the
+                // pointed-to contents are both untainted and unaliased,
+                // meaning that no pointers to any part of those contents
+                // (or anything referred to by those contents) already exist
+                let conservative = self.record_assumption("inttoptr: assumed to point to untainted, unaliased memory")?;
+                let untainted_ptr_ty = self.type_from_llvm(&itp.to_type);
+                // all we do is create a tainted pointer from a tainted
+                // value, and an untainted pointer from an untainted value
+                let cur_fn = self.fn_taint_states.get_current();
+                let in_ty = cur_fn.get_type_of_operand(&itp.operand)?;
+                let ptr_ty = if conservative || self.is_type_tainted(&in_ty) {
+                    self.to_tainted(&untainted_ptr_ty)
+                } else {
+                    untainted_ptr_ty
+                };
+                let mut changed = self.fn_taint_states.get_current().update_var_taintedtype(itp.get_result().clone(), ptr_ty)?;
+                if conservative {
+                    // taint the contents of the pointer we've already stored
+                    // for the result, not those of the fresh one we just
+                    // created, which would be a change every time
+                    let stored_ty = self.fn_taint_states.get_current().get_taint_map()[itp.get_result()].clone();
+                    changed |= self.taint_pointee_of(&stored_ty)?;
+                }
+                Ok(changed)
+            },
+            Instruction::Phi(phi) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let mut incoming_types = phi
+                    .incoming_values
+                    .iter()
+                    .map(|(op, _)| cur_fn.get_type_of_operand(op))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter();
+                let mut result_ty = incoming_types.next().expect("Phi with no incoming values");
+                for ty in incoming_types {
+                    result_ty = self.named_structs.write().unwrap().join(&result_ty, &ty, self.cur_fn)?;
+                }
+                // in addition, the result should be tainted if the attacker can influence
+                // the control flow sufficiently to choose the result of this phi.
+                //
+                // Examples: (blocks A, B, C, etc)
+                // Suppose the branch condition in A's terminator is tainted.
+                //
+                //    A          A           A          A                 A      B
+                //  /   \      /   \         | \      /   \             /   \  /   \
+                // B     C    B     C   Z    |  B    B     C <-- \     Z     D      Y
+                //  \   /      \   /   /     | /     |     |      |
+                //    D   Z      D  --       D       Y     Z --> /
+                //    | /        |   /                \   /
+                //    E          E -                    D
+                //
+                // In all of the above examples, if D has a phi node, the result of that
+                // phi should be tainted; but if E has a phi node, the result of that phi
+                // should not be tainted, because A's paths have already re-converged by
+                // then (at D, A's immediate post-dominator, in the first two examples).
+                //
+                // A phi chooses its result according to the edge control arrives on. So
+                // A chooses the result if one of the incoming blocks is A itself (the
+                // third and fifth examples), or is immediately control-dependent on A
+                // (e.g. B and C in the first example; or a loop latch, if A decides
+                // whether to stay in the loop). A also chooses the result if D is where
+                // A's paths re-converge, i.e., A's immediate post-dominator, even if
+                // the incoming blocks are immediately control-dependent only on other
+                // (untainted) branches which A chose between.
+                //
+                // A phi in a block which merely executes under tainted control (i.e., is
+                // control-dependent on A) is not tainted by this alone: which edge it
+                // takes is decided by other branches, and its result can only be used
+                // after A's paths re-converge via another phi, which is tainted.
+                let base_fn = self.clones.base_function(self.cur_fn);
+                let (f, _) = self.analysis.get_func_by_name(base_fn).ok_or_else(|| format!("current function {:?} not found", base_fn))?;
+                let fn_analysis = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(base_fn);
+                let cdg = fn_analysis.control_dependence_graph();
+                let postdomtree = fn_analysis.postdominator_tree();
+                let cur_block = self.cur_block.unwrap();
+                let chosen_by_incoming_edge = phi.incoming_values.iter().any(|(_, block)| {
+                    cur_fn.is_terminator_tainted(block)
+                        || cdg.get_imm_control_dependencies(block).any(|dep| cur_fn.is_terminator_tainted(dep))
+                });
+                let is_reconvergence_point = || f.basic_blocks
+                    .iter()
+                    .filter(|bb| cur_fn.is_terminator_tainted(&bb.name))
+                    .any(|bb| postdomtree.ipostdom(&bb.name) == Some(CFGNode::Block(cur_block)));
+                if chosen_by_incoming_edge || is_reconvergence_point() {
+                    result_ty = self.to_tainted(&result_ty);
+                }
+                self.fn_taint_states.get_current().update_var_taintedtype(phi.get_result().clone(), result_ty)
+            },
+            Instruction::AtomicRMW(rmw) => {
+                let cur_fn = self.fn_taint_states.get_current();
+                let mut addr_ty = cur_fn.get_type_of_operand(&rmw.address)?;
+                let value_ty = cur_fn.get_type_of_operand(&rmw.value)?;
+                let loaded_ty = self.get_load_result_ty(&addr_ty)?;
+                let ty_to_store = loaded_ty.join(&value_ty)?;
+                self.process_store(&ty_to_store, &mut addr_ty)?;
+                self.fn_taint_states.get_current().update_var_taintedtype(rmw.get_result().clone(), loaded_ty)
+            },
+            Instruction::VAArg(va_arg) => {
+                // the argument we get is tainted if the va_list is
+                let cur_fn = self.fn_taint_states.get_current();
+                let va_list_ty = cur_fn.get_type_of_operand(&va_arg.arg_list)?;
+                let va_list_contents_ty = self.get_load_result_ty(&va_list_ty)?;
+                let result_ty = self.type_from_llvm(&va_arg.cur_type);
+                let result_ty = if self.is_type_tainted(&va_list_contents_ty) {
+                    self.to_tainted(&result_ty)
+                } else {
+                    result_ty
+                };
+                self.fn_taint_states.get_current().update_var_taintedtype(va_arg.dest.clone(), result_ty)
+            },
+            Instruction::Call(call) => {
+                // Operand bundles (`deopt`, `funclet`, `kcfi`, etc.) aren't
+                // among `call.arguments`, so they never shift the arguments
+                // against the callee's parameters, and we ignore them. That's
+                // sound for the bundles compilers emit: `deopt` state is only
+                // read if the caller's frame is deoptimized, `funclet` names
+                // the EH pad the call is in, and `kcfi` carries a constant
+                // type hash, so none of them flows into the callee or the
+                // result.
+                match &call.function {
+                    Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
+                        Constant::GlobalReference { name: Name::Name(name), .. } => {
+                            if self.config.honor_annotations && name.starts_with("llvm.var.annotation") {
+                                self.process_var_annotation(call)
+                            } else if self.config.is_noop_intrinsic(name) {
+                                // these are all safe to ignore, and anything
+                                // they return is untainted
+                                match &call.dest {
+                                    Some(dest) => {
+                                        let ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                        self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)
+                                    },
+                                    None => Ok(false),
+                                }
                             } else {
-                                TaintedType::from_llvm_type(&bc.to_type)
+                                match Intrinsic::from_name(name) {
+                                    Some(Intrinsic::Memset) => {
+                                        // update the address type as appropriate, just like for Store
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let address_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least three arguments, but it has {}", name, call.arguments.len()))?;
+                                        let value_operand = call.arguments.get(1).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least three arguments, but it has {}", name, call.arguments.len()))?;
+                                        let address_ty = cur_fn.get_type_of_operand(address_operand)?;
+                                        let value_ty = cur_fn.get_type_of_operand(value_operand)?;
+                                        let mut pointee = match address_ty {
+                                            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => pointee,
+                                            _ => return Err(format!("{}: expected first argument to be a pointer, but it was {}", name, address_ty)),
+                                        };
+                                        cur_fn.update_pointee_taintedtype(&mut pointee, &value_ty)
+                                    },
+                                    Some(Intrinsic::Memcpy) => {
+                                        // the destination contents become tainted if the
+                                        // source contents are tainted. The two may have
+                                        // different types (e.g., copying between structs
+                                        // via `i8*`), so we don't try to join them.
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let dest_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least three arguments, but it has {}", name, call.arguments.len()))?;
+                                        let src_operand = call.arguments.get(1).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least three arguments, but it has {}", name, call.arguments.len()))?;
+                                        let dest_ty = cur_fn.get_type_of_operand(dest_operand)?;
+                                        let src_ty = cur_fn.get_type_of_operand(src_operand)?;
+                                        let mut dest_pointee = match dest_ty {
+                                            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => pointee,
+                                            _ => return Err(format!("{}: expected first argument to be a pointer, but it was {}", name, dest_ty)),
+                                        };
+                                        let src_contents_ty = self.get_load_result_ty(&src_ty)?;
+                                        if self.is_type_tainted(&src_contents_ty) {
+                                            let tainted_dest_contents_ty = self.to_tainted(&dest_pointee.cloned_ty());
+                                            let cur_fn = self.fn_taint_states.get_current();
+                                            cur_fn.update_pointee_taintedtype(&mut dest_pointee, &tainted_dest_contents_ty)
+                                        } else {
+                                            Ok(false)
+                                        }
+                                    },
+                                    Some(Intrinsic::VaStart) => {
+                                        // the va_list now carries the joined taint of
+                                        // the extra arguments passed to this function
+                                        let varargs_tainted = self.fn_summaries.get(self.cur_fn).is_some_and(|summary| summary.are_varargs_tainted());
+                                        if varargs_tainted {
+                                            let va_list_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have one argument, but it has {}", name, call.arguments.len()))?;
+                                            let mut va_list = self.get_pointee_through_casts(va_list_operand)?;
+                                            let tainted_va_list_ty = self.to_tainted(&va_list.cloned_ty());
+                                            self.fn_taint_states.get_current().update_pointee_taintedtype(&mut va_list, &tainted_va_list_ty)
+                                        } else {
+                                            Ok(false)
+                                        }
+                                    },
+                                    Some(Intrinsic::VaCopy) => {
+                                        // the destination va_list becomes tainted if
+                                        // the source va_list is
+                                        let dest_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have two arguments, but it has {}", name, call.arguments.len()))?;
+                                        let src_operand = call.arguments.get(1).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have two arguments, but it has {}", name, call.arguments.len()))?;
+                                        let mut dest = self.get_pointee_through_casts(dest_operand)?;
+                                        let src = self.get_pointee_through_casts(src_operand)?;
+                                        if self.is_type_tainted(&src.cloned_ty()) {
+                                            let tainted_dest_ty = self.to_tainted(&dest.cloned_ty());
+                                            self.fn_taint_states.get_current().update_pointee_taintedtype(&mut dest, &tainted_dest_ty)
+                                        } else {
+                                            Ok(false)
+                                        }
+                                    },
+                                    Some(Intrinsic::VaEnd) => Ok(false),
+                                    Some(Intrinsic::Passthrough) => {
+                                        // the result has the same taint as the
+                                        // operand. Any other arguments (e.g.,
+                                        // the `i1` flag of `llvm.ctlz`) are
+                                        // immediates, so we ignore them
+                                        let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least one argument, but it has {}", name, call.arguments.len()))?;
+                                        let result_ty = cur_fn.get_type_of_operand(operand)?;
+                                        cur_fn.update_var_taintedtype(dest.clone(), result_ty)
+                                    },
+                                    Some(Intrinsic::FunnelShift) => {
+                                        // like a binary op, but with three operands
+                                        let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        if call.arguments.len() != 3 {
+                                            return Err(format!("Expected {} to have three arguments, but it has {}", name, call.arguments.len()));
+                                        }
+                                        let mut result_ty = cur_fn.get_type_of_operand(&call.arguments[0].0)?;
+                                        for (operand, _) in &call.arguments[1 ..] {
+                                            result_ty = result_ty.join(&cur_fn.get_type_of_operand(operand)?)?;
+                                        }
+                                        cur_fn.update_var_taintedtype(dest.clone(), result_ty)
+                                    },
+                                    Some(Intrinsic::MatrixMultiply) => {
+                                        // every element of the result depends
+                                        // on a whole row and column of the
+                                        // operands
+                                        let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                                        if call.arguments.len() != 5 {
+                                            return Err(format!("Expected {} to have five arguments, but it has {}", name, call.arguments.len()));
+                                        }
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let lhs_ty = cur_fn.get_type_of_operand(&call.arguments[0].0)?;
+                                        let rhs_ty = cur_fn.get_type_of_operand(&call.arguments[1].0)?;
+                                        let result_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                        let result_ty = if self.is_type_tainted(&lhs_ty) || self.is_type_tainted(&rhs_ty) {
+                                            self.to_tainted(&result_ty)
+                                        } else {
+                                            result_ty
+                                        };
+                                        self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), result_ty)
+                                    },
+                                    Some(Intrinsic::MatrixLoad) => {
+                                        // like a load of each element, with the
+                                        // stride between columns like an index
+                                        // of a `getelementptr`
+                                        let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                                        if call.arguments.len() != 5 {
+                                            return Err(format!("Expected {} to have five arguments, but it has {}", name, call.arguments.len()));
+                                        }
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let ptr_ty = cur_fn.get_type_of_operand(&call.arguments[0].0)?;
+                                        let stride_tainted = cur_fn.is_scalar_operand_tainted(&call.arguments[1].0)?;
+                                        let result_ty = TaintedType::array_or_vec_of(self.get_load_result_ty(&ptr_ty)?);
+                                        let result_ty = if stride_tainted && self.config.propagation_policy(config::InstructionClass::GepIndex) != config::PropagationPolicy::Drop {
+                                            self.to_tainted(&result_ty)
+                                        } else {
+                                            result_ty
+                                        };
+                                        self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), result_ty)
+                                    },
+                                    Some(Intrinsic::MatrixStore) => {
+                                        // like a store of each element
+                                        if call.arguments.len() != 6 {
+                                            return Err(format!("Expected {} to have six arguments, but it has {}", name, call.arguments.len()));
+                                        }
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        let element_ty = match cur_fn.get_type_of_operand(&call.arguments[0].0)? {
+                                            TaintedType::ArrayOrVector(element) => element.ty().clone(),
+                                            ty => ty,
+                                        };
+                                        let mut pointee = match cur_fn.get_type_of_operand(&call.arguments[1].0)? {
+                                            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => pointee,
+                                            ty => return Err(format!("{}: expected second argument to be a pointer, but it was {}", name, ty)),
+                                        };
+                                        cur_fn.update_pointee_taintedtype(&mut pointee, &element_ty)
+                                    },
+                                    Some(Intrinsic::Coroutine) => self.process_coro_intrinsic(call, name),
+                                    Some(Intrinsic::GcStatepoint) => self.process_gc_intrinsic(call, name),
+                                    Some(Intrinsic::ReadRegister) => {
+                                        let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                                        let ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                        let ty = if self.config.register_reads_tainted {
+                                            self.to_tainted(&ty)
+                                        } else {
+                                            ty
+                                        };
+                                        self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)
+                                    },
+                                    Some(Intrinsic::WriteRegister) => {
+                                        let value_operand = call.arguments.get(1).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have two arguments, but it has {}", name, call.arguments.len()))?;
+                                        let cur_fn = self.fn_taint_states.get_current();
+                                        if self.config.register_writes_are_sinks && cur_fn.is_scalar_operand_tainted(value_operand)? {
+                                            let block = self.cur_block.cloned().unwrap();
+                                            let index = self.cur_inst.unwrap();
+                                            Ok(self.fn_taint_states.get_current().mark_sink_tainted(block, index))
+                                        } else {
+                                            Ok(false)
+                                        }
+                                    },
+                                    None => if let Some(allocator) = self.allocator(name) {
+                                        self.process_allocator_call(call, allocator)
+                                    } else if let Some(format_fn) = self.format_function(name) {
+                                        self.process_format_call(call, format_fn)
+                                    } else if let Some(kernel_fn) = self.kernel_function(name) {
+                                        self.process_kernel_call(call, kernel_fn)
+                                    } else if let Some(wasi_fn) = self.wasi_function(name) {
+                                        self.process_wasi_call(call, wasi_fn)
+                                    } else if let Some(gpu_fn) = self.gpu_function(name) {
+                                        self.process_gpu_call(call, gpu_fn)
+                                    } else if let Some(jump_fn) = self.jump_function(name) {
+                                        self.process_jump_call(call, jump_fn)
+                                    } else if let Some((callback, arg)) = self.config.get_thread_spawn_function(name) {
+                                        let callback_changed = self.process_thread_spawn(call, name, callback, arg)?;
+                                        Ok(self.process_function_call(call, name)? || callback_changed)
+                                    } else {
+                                        self.process_function_call(call, name)
+                                    },
+                                }
                             }
                         },
-                    };
-                    self.fn_taint_states.get_current().update_var_taintedtype(bc.get_result().clone(), result_ty)
-                },
-                Instruction::ExtractElement(ee) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let result_ty = if cur_fn.is_scalar_operand_tainted(&ee.index)? {
-                        TaintedType::TaintedValue
-                    } else {
-                        cur_fn.get_type_of_operand(&ee.vector)? // in our type system, the type of a vector and the type of one of its elements are the same
-                    };
-                    cur_fn.update_var_taintedtype(ee.get_result().clone(), result_ty)
-                },
-                Instruction::InsertElement(ie) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let result_ty = if cur_fn.is_scalar_operand_tainted(&ie.index)?
-                        || cur_fn.is_scalar_operand_tainted(&ie.element)?
-                    {
-                        TaintedType::TaintedValue
-                    } else {
-                        cur_fn.get_type_of_operand(&ie.vector)? // in our type system, inserting an untainted element does't change the type of the vector
-                    };
-                    cur_fn.update_var_taintedtype(ie.get_result().clone(), result_ty)
-                },
-                Instruction::ShuffleVector(sv) => {
-                    // Vector operands are still scalars in our type system
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let op0_ty = cur_fn.get_type_of_operand(&sv.operand0)?;
-                    let op1_ty = cur_fn.get_type_of_operand(&sv.operand1)?;
-                    let result_ty = op0_ty.join(&op1_ty)?;
-                    cur_fn.update_var_taintedtype(sv.get_result().clone(), result_ty)
-                },
-                Instruction::ExtractValue(ev) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    // We make a pointer to the struct, and add an extra index
-                    // representing getting element 0 of the resulting implicit
-                    // array of structs, because get_element_ptr expects a pointer
-                    let ptr_to_struct =
-                        TaintedType::untainted_ptr_to(cur_fn.get_type_of_operand(&ev.aggregate)?);
-                    let indices: Vec<u32> = std::iter::once(&0).chain(ev.indices.iter()).copied().collect();
-                    let element_ptr_ty = self.get_element_ptr(&ptr_to_struct, &indices)?;
-                    let element_ty = match element_ptr_ty {
-                        TaintedType::UntaintedPointer(pointee) => pointee.ty().clone(),
-                        _ => return Err(format!("ExtractValue: expected get_element_ptr to return an UntaintedPointer here; got {}", element_ptr_ty)),
-                    };
-                    self.fn_taint_states.get_current().update_var_taintedtype(ev.get_result().clone(), element_ty)
-                },
-                Instruction::InsertValue(iv) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let struct_ty = cur_fn.get_type_of_operand(&iv.aggregate)?;
-                    let element_to_insert = cur_fn.get_type_of_operand(&iv.element)?;
-                    // We make a pointer to the struct, and add an extra index
-                    // representing getting element 0 of the resulting implicit
-                    // array of structs, because get_element_ptr expects a pointer
-                    let ptr_to_struct = TaintedType::untainted_ptr_to(struct_ty.clone());
-                    let indices: Vec<u32> = std::iter::once(&0).chain(iv.indices.iter()).copied().collect();
-                    let ptr_to_indicated_element = self.get_element_ptr(&ptr_to_struct, &indices)?;
-                    let cur_fn = self.fn_taint_states.get_current();
-                    match ptr_to_indicated_element {
-                        TaintedType::UntaintedPointer(mut pointee) | TaintedType::TaintedPointer(mut pointee) => {
-                            cur_fn.update_pointee_taintedtype(&mut pointee, &element_to_insert)?;
-                        },
-                        _ => panic!("Expected get_element_ptr to return a pointer, but got {}", ptr_to_indicated_element),
-                    }
-                    cur_fn.update_var_taintedtype(iv.get_result().clone(), struct_ty)
-                },
-                Instruction::Alloca(alloca) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let result_ty = if cur_fn.is_scalar_operand_tainted(&alloca.num_elements)? {
-                        TaintedType::TaintedValue
-                    } else {
-                        TaintedType::untainted_ptr_to(TaintedType::from_llvm_type(
-                            &alloca.allocated_type,
-                        ))
-                    };
-                    cur_fn.update_var_taintedtype(alloca.get_result().clone(), result_ty)
-                },
-                Instruction::Load(load) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let addr_ty = cur_fn.get_type_of_operand(&load.address)?;
-                    let result_ty = self.get_load_result_ty(&addr_ty)?;
-                    self.fn_taint_states.get_current().update_var_taintedtype(load.get_result().clone(), result_ty)
-                },
-                Instruction::Store(store) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let mut addr_ty = cur_fn.get_type_of_operand(&store.address)?;
-                    let new_value_ty = cur_fn.get_type_of_operand(&store.value)?;
-                    self.process_store(&new_value_ty, &mut addr_ty)
-                },
-                Instruction::Fence(_) => Ok(false),
-                Instruction::GetElementPtr(gep) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let ptr = cur_fn.get_type_of_operand(&gep.address)?;
-                    let result_ty = self.get_element_ptr(&ptr, &gep.indices)?;
-                    self.fn_taint_states.get_current().update_var_taintedtype(gep.get_result().clone(), result_ty)
-                },
-                Instruction::PtrToInt(pti) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    match cur_fn.get_type_of_operand(&pti.operand)? {
-                        TaintedType::UntaintedPointer(_) | TaintedType::UntaintedFnPtr => {
-                            cur_fn.update_var_taintedtype(pti.get_result().clone(), TaintedType::UntaintedValue)
-                        },
-                        TaintedType::TaintedPointer(_) | TaintedType::TaintedFnPtr => {
-                            cur_fn.update_var_taintedtype(pti.get_result().clone(), TaintedType::TaintedValue)
-                        },
-                        TaintedType::UntaintedValue => {
-                            Err(format!("PtrToInt on an UntaintedValue: {:?}", &pti.operand))
-                        },
-                        TaintedType::TaintedValue => {
-                            Err(format!("PtrToInt on an TaintedValue: {:?}", &pti.operand))
-                        },
-                        TaintedType::ArrayOrVector(_) => {
-                            Err(format!("PtrToInt on an array or vector: {:?}", &pti.operand))
-                        },
-                        TaintedType::Struct(_) | TaintedType::NamedStruct(_) => {
-                            Err(format!("PtrToInt on a struct: {:?}", &pti.operand))
-                        },
-                    }
-                },
-                Instruction::IntToPtr(itp) => {
-                    // we make the (potentially unsound) assumption that the
-                    // pointed-to contents are both untainted and unaliased,
-                    // meaning that no pointers to any part of those contents
-                    // (or anything referred to by those contents) already exist
-                    let untainted_ptr_ty = TaintedType::from_llvm_type(&itp.to_type);
-                    // all we do is create a tainted pointer from a tainted
-                    // value, and an untainted pointer from an untainted value
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let in_ty = cur_fn.get_type_of_operand(&itp.operand)?;
-                    let ptr_ty = if self.is_type_tainted(&in_ty) {
-                        self.to_tainted(&untainted_ptr_ty)
-                    } else {
-                        untainted_ptr_ty
-                    };
-                    self.fn_taint_states.get_current().update_var_taintedtype(itp.get_result().clone(), ptr_ty)
-                },
-                Instruction::ICmp(icmp) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let op0_ty = cur_fn.get_type_of_operand(&icmp.operand0)?;
-                    let op1_ty = cur_fn.get_type_of_operand(&icmp.operand1)?;
-                    let result_ty = if self.is_type_tainted(&op0_ty) || self.is_type_tainted(&op1_ty) {
-                        TaintedType::TaintedValue
-                    } else {
-                        TaintedType::UntaintedValue
-                    };
-                    self.fn_taint_states.get_current().update_var_taintedtype(icmp.get_result().clone(), result_ty)
-                },
-                Instruction::FCmp(fcmp) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let op0_ty = cur_fn.get_type_of_operand(&fcmp.operand0)?;
-                    let op1_ty = cur_fn.get_type_of_operand(&fcmp.operand1)?;
-                    let result_ty = if self.is_type_tainted(&op0_ty) || self.is_type_tainted(&op1_ty) {
-                        TaintedType::TaintedValue
-                    } else {
-                        TaintedType::UntaintedValue
-                    };
-                    self.fn_taint_states.get_current().update_var_taintedtype(fcmp.get_result().clone(), result_ty)
-                },
-                Instruction::Phi(phi) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let mut incoming_types = phi
-                        .incoming_values
-                        .iter()
-                        .map(|(op, _)| cur_fn.get_type_of_operand(op))
-                        .collect::<Result<Vec<_>, _>>()?
-                        .into_iter();
-                    let mut result_ty = incoming_types.next().expect("Phi with no incoming values");
-                    for ty in incoming_types {
-                        result_ty = result_ty.join(&ty)?;
-                    }
-                    // in addition, the result should be tainted if the attacker can influence
-                    // the control flow sufficiently to choose the result of this phi.
-                    //
-                    // Examples: (blocks A, B, C, etc)
-                    // Suppose the branch condition in A's terminator is tainted.
-                    //
-                    //    A          A           A          A                 A      B
-                    //  /   \      /   \         | \      /   \             /   \  /   \
-                    // B     C    B     C   Z    |  B    B     C <-- \     Z     D      Y
-                    //  \   /      \   /   /     | /     |     |      |
-                    //    D   Z      D  --       D       Y     Z --> /
-                    //    | /        |   /                \   /
-                    //    E          E -                    D
-                    //
-                    // In all of the above examples, if D has a phi node, the result of that
-                    // phi should be tainted; but if E has a phi node, the result of that phi
-                    // should not be tainted.
-                    // In all of the above examples, either D itself is control-dependent on A
-                    // (as in the fifth example), or at least one of D's predecessors is. (Not
-                    // necessarily all, as the second and third example show.)
-                    // But E is not control-dependent on A, and neither are any of E's
-                    // predecessors.
-                    // So we taint the phi result if either D is control-dependent on A or if
-                    // any of D's predecessors are control-dependent on A.
-                    // I.e., we taint this phi's result if the current block is control-
-                    // dependent on a block with tainted terminator, or if any of the incoming
-                    // phi blocks are control-dependent on a block with tainted terminator.
-                    let cdg = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(self.cur_fn).control_dependence_graph();
-                    let is_ctrl_dep_on_tainted_term = |block: &'m Name| {
-                        cdg.get_control_dependencies(block)
-                            .any(|dep| cur_fn.is_terminator_tainted(dep))
-                    };
-                    if is_ctrl_dep_on_tainted_term(&self.cur_block.unwrap()) {
-                        result_ty = self.to_tainted(&result_ty);
-                    } else if phi.incoming_values.iter().any(|(_, block)| is_ctrl_dep_on_tainted_term(block)) {
-                        result_ty = self.to_tainted(&result_ty);
-                    }
-                    self.fn_taint_states.get_current().update_var_taintedtype(phi.get_result().clone(), result_ty)
-                },
-                Instruction::Select(select) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let result_ty = if cur_fn.is_scalar_operand_tainted(&select.condition)? {
-                        TaintedType::TaintedValue
-                    } else {
-                        let true_ty = cur_fn.get_type_of_operand(&select.true_value)?;
-                        let false_ty = cur_fn.get_type_of_operand(&select.false_value)?;
-                        true_ty.join(&false_ty)?
-                    };
-                    cur_fn.update_var_taintedtype(select.get_result().clone(), result_ty)
-                },
-                Instruction::AtomicRMW(rmw) => {
-                    let cur_fn = self.fn_taint_states.get_current();
-                    let mut addr_ty = cur_fn.get_type_of_operand(&rmw.address)?;
-                    let value_ty = cur_fn.get_type_of_operand(&rmw.value)?;
-                    let loaded_ty = self.get_load_result_ty(&addr_ty)?;
-                    let ty_to_store = loaded_ty.join(&value_ty)?;
-                    self.process_store(&ty_to_store, &mut addr_ty)?;
-                    self.fn_taint_states.get_current().update_var_taintedtype(rmw.get_result().clone(), loaded_ty)
-                },
-                Instruction::Call(call) => {
-                    match &call.function {
-                        Either::Right(Operand::ConstantOperand(cref)) => match cref.as_ref() {
-                            Constant::GlobalReference { name: Name::Name(name), .. } => {
-                                if name.starts_with("llvm.lifetime")
-                                    || name.starts_with("llvm.invariant")
-                                    || name.starts_with("llvm.launder.invariant")
-                                    || name.starts_with("llvm.strip.invariant")
-                                    || name.starts_with("llvm.dbg")
-                                {
-                                    Ok(false) // these are all safe to ignore
-                                } else if name.starts_with("llvm.memset") {
-                                    // update the address type as appropriate, just like for Store
-                                    let cur_fn = self.fn_taint_states.get_current();
-                                    let address_operand = call.arguments.get(0).map(|(op, _)| op).ok_or_else(|| format!("Expected llvm.memset to have at least three arguments, but it has {}", call.arguments.len()))?;
-                                    let value_operand = call.arguments.get(1).map(|(op, _)| op).ok_or_else(|| format!("Expected llvm.memset to have at least three arguments, but it has {}", call.arguments.len()))?;
-                                    let address_ty = cur_fn.get_type_of_operand(address_operand)?;
-                                    let value_ty = cur_fn.get_type_of_operand(value_operand)?;
-                                    let mut pointee = match address_ty {
-                                        TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => pointee,
-                                        _ => return Err(format!("llvm.memset: expected first argument to be a pointer, but it was {}", address_ty)),
-                                    };
-                                    cur_fn.update_pointee_taintedtype(&mut pointee, &value_ty)
-                                } else {
-                                    self.process_function_call(call, name)
+                        Constant::GlobalReference{ name, .. } => self.process_unhandled(
+                            "call of a function with a numbered name",
+                            &instruction_operands(inst),
+                            call.dest.as_ref().map(|dest| (dest, self.cur_mod.type_of(inst))),
+                            || format!("Call of a function with a numbered name: {:?}", name),
+                        ),
+                        _ => self.process_unhandled(
+                            "call of a constant function pointer",
+                            &instruction_operands(inst),
+                            call.dest.as_ref().map(|dest| (dest, self.cur_mod.type_of(inst))),
+                            || "Call of a constant function pointer".into(),
+                        ),
+                    },
+                    Either::Right(_) => {
+                        if let Some(jni_fn) = self.jni_function(call) {
+                            return self.process_jni_call(call, jni_fn);
+                        }
+                        // the called operand is a pointer to the function
+                        // type, but functions are grouped by the function
+                        // type itself
+                        let func_ty = match self.cur_mod.type_of(&call.function).as_ref() {
+                            Type::PointerType { pointee_type, .. } => pointee_type.clone(),
+                            _ => self.cur_mod.type_of(&call.function),
+                        };
+                        // Assume that this function pointer could point to any function in
+                        // the analyzed module(s) that has the appropriate type
+                        let mut targets: Vec<&'m str> = self.analysis.functions_by_type().functions_with_type(&func_ty).sorted_unstable().collect();
+                        // but for a virtual call, or a call of a function
+                        // pointer from a struct field, only the functions in
+                        // the right vtable slot or stored to that field, if
+                        // any of them has that type
+                        if let Some(known) = self.known_call_targets(call) {
+                            if targets.iter().any(|target| known.contains(target)) {
+                                targets.retain(|target| known.contains(target));
+                            }
+                        }
+                        // and for a function pointer whose targets we know,
+                        // only those
+                        if self.config.resolve_fn_ptr_targets {
+                            if let Some(known) = self.fn_ptr_targets(call)? {
+                                if targets.iter().any(|target| known.contains(*target)) {
+                                    targets.retain(|target| known.contains(*target));
                                 }
-                            },
-                            Constant::GlobalReference{ name, .. } => {
-                                unimplemented!("Call of a function with a numbered name: {:?}", name)
-                            },
-                            _ => unimplemented!("Call of a constant function pointer"),
-                        },
-                        Either::Right(_) => {
-                            let func_ty = self.cur_mod.type_of(&call.function);
-                            // Assume that this function pointer could point to any function in
-                            // the analyzed module(s) that has the appropriate type
-                            let targets: Vec<&'m str> = self.analysis.functions_by_type().functions_with_type(&func_ty).collect();
-                            if targets.is_empty() {
-                                // no valid targets for the function pointer in
-                                // the analyzed module(s); treat this as a call
-                                // to an external function
-                                use config::ExternalFunctionHandling;
-                                match self.config.ext_functions_default {
-                                    ExternalFunctionHandling::IgnoreAndReturnUntainted => {
+                            }
+                        }
+                        if targets.is_empty() {
+                            // no valid targets for the function pointer in
+                            // the analyzed module(s); treat this as a call
+                            // to an external function
+                            use config::ExternalFunctionHandling;
+                            let default = self.config.ext_functions_default_for(std::iter::once(self.cur_mod));
+                            let handling = if self.config.ext_function_attributes {
+                                let call_site = (call.function_attributes.as_slice(), call.return_attributes.as_slice());
+                                attributes::refine_ext_handling(default, std::iter::once(call_site))
+                            } else {
+                                default
+                            };
+                            match handling {
+                                ExternalFunctionHandling::IgnoreAndReturnUntainted => {
+                                    if self.record_assumption("call through a function pointer with no known targets: assumed to return untainted data")? {
+                                        return self.process_call_conservatively(call);
+                                    }
+                                    match &call.dest {
+                                        None => Ok(false),
+                                        Some(dest) => {
+                                            let untainted_ret_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                            self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), untainted_ret_ty)
+                                        },
+                                    }
+                                },
+                                ExternalFunctionHandling::IgnoreAndReturnTainted => {
+                                    match &call.dest {
+                                        None => Ok(false),
+                                        Some(dest) => {
+                                            let untainted_ret_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                            let ret_ty = if self.ext_sources_tainted {
+                                                self.to_tainted(&untainted_ret_ty)
+                                            } else {
+                                                untainted_ret_ty
+                                            };
+                                            self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ret_ty)
+                                        },
+                                    }
+                                },
+                                ExternalFunctionHandling::PropagateTaintShallow => {
+                                    let cur_fn = self.fn_taint_states.get_current();
+                                    if call
+                                        .arguments
+                                        .iter()
+                                        .map(|(o, _)| cur_fn.get_type_of_operand(o))
+                                        .collect::<Result<Vec<_>, String>>()?
+                                        .into_iter()
+                                        .any(|t| self.is_type_tainted(&t))
+                                    {
+                                        // just like IgnoreAndReturnTainted
                                         match &call.dest {
                                             None => Ok(false),
                                             Some(dest) => {
-                                                let untainted_ret_ty = TaintedType::from_llvm_type(&self.cur_mod.type_of(call));
-                                                self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), untainted_ret_ty)
+                                                let untainted_ret_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                                let tainted_ret_ty = self.to_tainted(&untainted_ret_ty);
+                                                self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), tainted_ret_ty)
                                             },
                                         }
-                                    },
-                                    ExternalFunctionHandling::IgnoreAndReturnTainted => {
+                                    } else {
+                                        // just like IgnoreAndReturnUntainted
                                         match &call.dest {
                                             None => Ok(false),
                                             Some(dest) => {
-                                                let untainted_ret_ty = TaintedType::from_llvm_type(&self.cur_mod.type_of(call));
-                                                let tainted_ret_ty = self.to_tainted(&untainted_ret_ty);
-                                                self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), tainted_ret_ty)
+                                                let untainted_ret_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                                                self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), untainted_ret_ty)
                                             },
                                         }
-                                    },
-                                    ExternalFunctionHandling::PropagateTaintShallow => {
-                                        let cur_fn = self.fn_taint_states.get_current();
-                                        if call
-                                            .arguments
-                                            .iter()
-                                            .map(|(o, _)| cur_fn.get_type_of_operand(o))
-                                            .collect::<Result<Vec<_>, String>>()?
-                                            .into_iter()
-                                            .any(|t| self.is_type_tainted(&t))
-                                        {
-                                            // just like IgnoreAndReturnTainted
-                                            match &call.dest {
-                                                None => Ok(false),
-                                                Some(dest) => {
-                                                    let untainted_ret_ty = TaintedType::from_llvm_type(&self.cur_mod.type_of(call));
-                                                    let tainted_ret_ty = self.to_tainted(&untainted_ret_ty);
-                                                    self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), tainted_ret_ty)
-                                                },
-                                            }
-                                        } else {
-                                            // just like IgnoreAndReturnUntainted
-                                            match &call.dest {
-                                                None => Ok(false),
-                                                Some(dest) => {
-                                                    let untainted_ret_ty = TaintedType::from_llvm_type(&self.cur_mod.type_of(call));
-                                                    self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), untainted_ret_ty)
-                                                },
-                                            }
-                                        }
-                                    },
-                                    ExternalFunctionHandling::PropagateTaintDeep => {
-                                        unimplemented!("ExternalFunctionHandling::PropagateTaintDeep")
-                                    },
-                                    ExternalFunctionHandling::Panic => {
-                                        panic!("Call of a function pointer")
-                                    },
-                                }
-                            } else {
-                                let mut changed = false;
-                                // we could call any of these targets. Taint accordingly.
-                                for target in targets {
-                                    changed |= self.process_function_call(call, target)?;
-                                }
-                                Ok(changed)
+                                    }
+                                },
+                                ExternalFunctionHandling::PropagateTaintDeep => {
+                                    unimplemented!("ExternalFunctionHandling::PropagateTaintDeep")
+                                },
+                                ExternalFunctionHandling::Panic => {
+                                    panic!("Call of a function pointer")
+                                },
                             }
-                        },
-                        Either::Left(_) => unimplemented!("inline assembly"),
+                        } else {
+                            let mut changed = false;
+                            // we could call any of these targets. Taint accordingly.
+                            for target in targets {
+                                changed |= self.process_function_call(call, target)?;
+                            }
+                            Ok(changed)
+                        }
+                    },
+                    Either::Left(_) => {
+                        if self.record_assumption("inline assembly: assumed to neither read nor write memory")? {
+                            return self.process_call_conservatively(call);
+                        }
+                        self.process_unhandled(
+                            "inline assembly",
+                            &instruction_operands(inst),
+                            call.dest.as_ref().map(|dest| (dest, self.cur_mod.type_of(inst))),
+                            || "inline assembly".into(),
+                        )
+                    },
+                }
+            },
+            _ => self.process_unhandled(
+                instruction_kind(inst),
+                &instruction_operands(inst),
+                inst.try_get_result().map(|dest| (dest, self.cur_mod.type_of(inst))),
+                || format!("instruction {:?}", inst),
+            ),
+        }
+    }
+
+    /// Handle the need for the given unsound assumption at the current
+    /// instruction (or terminator), according to
+    /// `Config::unsound_assumptions`: record it, for
+    /// `TaintResult::get_unsound_assumptions()`, or return an error.
+    ///
+    /// Returns `true` if the caller should instead handle the instruction
+    /// conservatively, or `false` if it should make the assumption.
+    fn record_assumption(&mut self, assumption: impl Into<String>) -> Result<bool, String> {
+        let assumption = assumption.into();
+        match self.config.unsound_assumptions {
+            config::UnsoundAssumptionHandling::Assume => {},
+            config::UnsoundAssumptionHandling::Panic => return Err(format!("Unsound assumption required: {}", assumption)),
+            config::UnsoundAssumptionHandling::Conservative => return Ok(true),
+        }
+        let block = self.cur_block.cloned().unwrap();
+        let index = self.cur_inst;
+        let cur_fn = self.fn_taint_states.get_current();
+        if cur_fn.mark_assumption(block.clone(), index, assumption.as_str()) {
+            debug!("In function {:?}, block {}: {}", self.cur_fn, block, assumption);
+        }
+        Ok(false)
+    }
+
+    /// Process a call we know nothing about conservatively (see
+    /// `UnsoundAssumptionHandling::Conservative`): its result is tainted, and
+    /// so are the contents of any pointers passed to it.
+    ///
+    /// Returns `true` if a change was made to the `FunctionTaintState`, or `false` if not.
+    fn process_call_conservatively(&mut self, call: &instruction::Call) -> Result<bool, String> {
+        let cur_fn = self.fn_taint_states.get_current();
+        let arg_types = call.arguments
+            .iter()
+            .map(|(arg, _)| cur_fn.get_type_of_operand(arg))
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut changed = false;
+        for ty in &arg_types {
+            changed |= self.taint_pointee_of(ty)?;
+        }
+        if let Some(dest) = &call.dest {
+            let untainted_ret_ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+            let tainted_ret_ty = self.to_tainted(&untainted_ret_ty);
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), tainted_ret_ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Process an instruction or terminator which the analysis has no
+    /// specific handling for, according to `Config::unknown_instructions`.
+    ///
+    /// `construct` describes the instruction or terminator (e.g. `"freeze"`),
+    /// `operands` are its operands, and `result` is the name and LLVM type of
+    /// its result, if any. Under `UnknownInstructionHandling::Panic`, this
+    /// panics with the message produced by `unimplemented_msg`.
+    ///
+    /// Returns `true` if a change was made to the `FunctionTaintState`, or `false` if not.
+    fn process_unhandled(
+        &mut self,
+        construct: &str,
+        operands: &[Operand],
+        result: Option<(&Name, TypeRef)>,
+        unimplemented_msg: impl FnOnce() -> String,
+    ) -> Result<bool, String> {
+        match self.config.unknown_instructions {
+            config::UnknownInstructionHandling::Panic => unimplemented!("{}", unimplemented_msg()),
+            config::UnknownInstructionHandling::JoinOperands => {},
+        }
+        let block = self.cur_block.cloned().unwrap();
+        let index = self.cur_inst;
+        let cur_fn = self.fn_taint_states.get_current();
+        let operand_tys = operands.iter().map(|op| cur_fn.get_type_of_operand(op)).collect::<Result<Vec<_>, _>>()?;
+        if cur_fn.mark_unhandled(block.clone(), index, construct) {
+            warn!("No specific handling for {} in function {:?}, block {}; assuming it propagates the taint of its operands", construct, self.cur_fn, block);
+        }
+        let any_tainted = operand_tys.iter().any(|ty| self.is_type_tainted(ty));
+        let mut changed = false;
+        if let Some((dest, llvm_ty)) = result {
+            if !matches!(llvm_ty.as_ref(), Type::VoidType) {
+                let ty = self.type_from_llvm(&llvm_ty);
+                let ty = if any_tainted { self.to_tainted(&ty) } else { ty };
+                changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+            }
+        }
+        if index.is_none() && any_tainted {
+            changed |= self.fn_taint_states.get_current().mark_terminator_tainted(block);
+        }
+        Ok(changed)
+    }
+
+    /// If the given instruction is one which `Config::bit_level_taint` tracks
+    /// bits through (see `tainted_result_bits()`), process it, returning
+    /// `true` if a change was made to the current function's
+    /// `FunctionTaintState`; otherwise return `None`.
+    fn process_bit_level_instruction(&mut self, inst: &'m Instruction) -> Result<Option<bool>, String> {
+        if let Some(class) = instruction_class(inst) {
+            if self.config.propagation_policy(class) != config::PropagationPolicy::Propagate {
+                return Ok(None);
+            }
+        }
+        let cur_mod = self.cur_mod;
+        let cur_fn = self.fn_taint_states.get_current();
+        let bits = tainted_result_bits(inst, cur_mod, |op| {
+            if !cur_fn.get_type_of_operand(op)?.is_tainted_nonamedstruct() {
+                return Ok(0);
+            }
+            let tracked = match op {
+                Operand::LocalOperand { name, .. } => cur_fn.get_tainted_bits(name),
+                _ => None,
+            };
+            Ok(tracked.unwrap_or_else(|| int_width(&cur_mod.type_of(op)).map_or(u64::MAX, low_bits)))
+        })?;
+        let bits = match bits {
+            Some(_) if sanitizes_result(inst, self.config) => 0,
+            Some(bits) => bits,
+            None => return Ok(None),
+        };
+        let dest = inst.try_get_result().expect("instructions handled by tainted_result_bits() have results").clone();
+        let result_ty = if bits == 0 { TaintedType::UntaintedValue } else { TaintedType::TaintedValue };
+        let mut changed = cur_fn.update_tainted_bits(dest.clone(), bits);
+        changed |= cur_fn.update_var_taintedtype(dest, result_ty)?;
+        Ok(Some(changed))
+    }
+
+    /// Get the `TaintedType` of the value loaded from the given address.
+    /// If `Config::flow_sensitive_memory` or `Config::promote_allocas` is set
+    /// and the current instruction is a load from a stack slot, get the stores which can reach it (see
+    /// `StackSlots`)
+    fn get_reaching_stores(&self) -> Option<Vec<ReachingStore<'m>>> {
+        let slots = self.stack_slots.get(self.cur_fn)?;
+        slots.reaching_stores(self.cur_block?, self.cur_inst?).map(|stores| stores.to_vec())
+    }
+
+    /// Get the `TaintedType` of a load of the given type from a stack slot,
+    /// which the given stores can reach. This is the join of the types of the
+    /// values stored, where a value stored under tainted control flow is
+    /// tainted, as in `process_store()`.
+    ///
+    /// For a slot holding a pointer, joining would give a fresh pointer, so
+    /// this is only the pointer stored if exactly one store can reach the
+    /// load; otherwise it's the type of a load from `addr_ty`, as usual.
+    fn get_stack_slot_load_ty(&mut self, loaded_ty: &Type, stores: &[ReachingStore<'m>], addr_ty: &TaintedType) -> Result<TaintedType, String> {
+        let is_data_ptr = matches!(loaded_ty, Type::PointerType { pointee_type, .. } if !matches!(pointee_type.as_ref(), Type::FuncType { .. }));
+        if is_data_ptr && stores.len() != 1 {
+            return self.get_load_result_ty(addr_ty);
+        }
+        let cdg = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(self.clones.base_function(self.cur_fn)).control_dependence_graph();
+        if is_data_ptr {
+            let store = &stores[0];
+            let cur_fn = self.fn_taint_states.get_current();
+            let value_ty = cur_fn.get_type_of_operand(store.value)?;
+            let need_to_taint = cdg
+                .get_control_dependencies(store.block)
+                .any(|dep| cur_fn.is_terminator_tainted(dep));
+            return Ok(if need_to_taint { self.to_tainted(&value_ty) } else { value_ty });
+        }
+        let mut result_ty = self.named_structs.read().unwrap().fresh_type_from_llvm(loaded_ty);
+        for store in stores {
+            let cur_fn = self.fn_taint_states.get_current();
+            let value_ty = cur_fn.get_type_of_operand(store.value)?;
+            let need_to_taint = cdg
+                .get_control_dependencies(store.block)
+                .any(|dep| cur_fn.is_terminator_tainted(dep));
+            let value_ty = if need_to_taint { self.to_tainted(&value_ty) } else { value_ty };
+            result_ty = self.named_structs.write().unwrap().join(&result_ty, &value_ty, self.cur_fn)?;
+        }
+        Ok(result_ty)
+    }
+
+    fn get_load_result_ty(&mut self, addr: &TaintedType) -> Result<TaintedType, String> {
+        match addr {
+            TaintedType::UntaintedValue | TaintedType::TaintedValue => {
+                Err(format!(
+                    "Load: address is not a pointer: got type {}",
+                    addr
+                ))
+            },
+            TaintedType::UntaintedFnPtr(_) | TaintedType::TaintedFnPtr(_) => {
+                Err("Loading from a function pointer".into())
+            },
+            TaintedType::ArrayOrVector(_)
+            | TaintedType::Struct(_)
+            | TaintedType::NamedStruct(_)
+            | TaintedType::Token => {
+                Err(format!(
+                    "Load: address is not a pointer: got type {}",
+                    addr
+                ))
+            },
+            TaintedType::UntaintedPointer(pointee) => {
+                Ok(pointee.ty().clone())
+            },
+            TaintedType::TaintedPointer(pointee) => {
+                if self.config.dereferencing_tainted_ptr_gives_tainted {
+                    pointee.taint(&mut self.named_structs.write().unwrap());
+                }
+                Ok(pointee.ty().clone())
+            },
+        }
+    }
+
+    /// Process the store of a value to an address.
+    fn process_store(&mut self, value: &TaintedType, addr: &mut TaintedType) -> Result<bool, String> {
+        match addr {
+            TaintedType::UntaintedValue | TaintedType::TaintedValue => {
+                Err(format!(
+                    "Store: address is not a pointer: got type {}",
+                    addr
+                ))
+            },
+            TaintedType::UntaintedFnPtr(_) | TaintedType::TaintedFnPtr(_) => {
+                Err("Storing to a function pointer".into())
+            },
+            TaintedType::ArrayOrVector(_)
+            | TaintedType::Struct(_)
+            | TaintedType::NamedStruct(_)
+            | TaintedType::Token => {
+                Err(format!(
+                    "Store: address is not a pointer: got type {}",
+                    addr
+                ))
+            },
+            TaintedType::UntaintedPointer(ref mut pointee) | TaintedType::TaintedPointer(ref mut pointee) => {
+                // Storing to a location while control-flow is tainted also
+                // needs to result in the stored value being marked tainted.
+                // This is because a tainted value (in some branch condition
+                // etc) influenced the value stored at this location.
+                let cur_fn = self.fn_taint_states.get_current();
+                let cdg = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(self.clones.base_function(self.cur_fn)).control_dependence_graph();
+                let need_to_taint = cdg
+                    .get_control_dependencies(self.cur_block.unwrap())
+                    .any(|dep| cur_fn.is_terminator_tainted(dep));
+
+                // now update the store address's type based on the value being
+                // stored through it.
+                // specifically, update the pointee in that address type:
+                // e.g., if we're storing a tainted value, we want
+                // to update the address type to "pointer to tainted"
+                if need_to_taint {
+                    let tainted_val = self.to_tainted(value);
+                    self.fn_taint_states.get_current().update_pointee_taintedtype(pointee, &tainted_val)
+                } else {
+                    cur_fn.update_pointee_taintedtype(pointee, value)
+                }
+            },
+        }
+    }
+
+    /// Get the `Pointee` of the given pointer operand, looking through any
+    /// pointer casts (`bitcast` or `addrspacecast` instructions in the current
+    /// function, or the equivalent constant expressions).
+    ///
+    /// Casting a pointer normally gives a pointer to a fresh `Pointee` (see the
+    /// handling of `BitCast`), but intrinsics such as `llvm.va_start` are
+    /// always passed an `i8*` cast of the object they operate on, and it's the
+    /// `Pointee` of that original object that we need.
+    fn get_pointee_through_casts(&mut self, op: &Operand) -> Result<Pointee, String> {
+        let (func, _) = self.analysis.get_func_by_name(self.cur_base_fn()).ok_or_else(|| format!("get_pointee_through_casts: current function {:?} not found", self.cur_fn))?;
+        let mut op = op.clone();
+        loop {
+            let cast_source = match &op {
+                Operand::LocalOperand { name, .. } => func.basic_blocks
+                    .iter()
+                    .flat_map(|bb| bb.instrs.iter())
+                    .find_map(|inst| match inst {
+                        Instruction::BitCast(bc) if &bc.dest == name => Some(bc.operand.clone()),
+                        Instruction::AddrSpaceCast(ac) if &ac.dest == name => Some(ac.operand.clone()),
+                        _ => None,
+                    }),
+                Operand::ConstantOperand(cref) => match cref.as_ref() {
+                    Constant::BitCast(bc) => Some(Operand::ConstantOperand(bc.operand.clone())),
+                    Constant::AddrSpaceCast(ac) => Some(Operand::ConstantOperand(ac.operand.clone())),
+                    _ => None,
+                },
+                Operand::MetadataOperand => None,
+            };
+            match cast_source {
+                Some(source) => op = source,
+                None => break,
+            }
+        }
+        let cur_fn = self.fn_taint_states.get_current();
+        match cur_fn.get_type_of_operand(&op)? {
+            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => Ok(pointee),
+            ty => Err(format!("Expected {} to be a pointer, but it has type {}", op, ty)),
+        }
+    }
+
+    /// Process a call of `llvm.var.annotation`, if `Config::honor_annotations`
+    /// is set: the contents of a local variable annotated as a source become
+    /// tainted. Other annotations have no effect on taint.
+    fn process_var_annotation(&mut self, call: &'m instruction::Call) -> Result<bool, String> {
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected llvm.var.annotation to have at least {} arguments, but it has {}", i + 1, call.arguments.len()))
+        };
+        let annotation = match arg(1)? {
+            Operand::ConstantOperand(cref) => annotations::constant_string(self.cur_mod, cref),
+            _ => None,
+        };
+        if annotation.as_deref() == Some(annotations::SOURCE_ANNOTATION) {
+            let mut var = self.get_pointee_through_casts(arg(0)?)?;
+            let tainted_ty = self.to_tainted(&var.cloned_ty());
+            self.fn_taint_states.get_current().update_pointee_taintedtype(&mut var, &tainted_ty)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Process a call of one of the `llvm.coro.*` intrinsics.
+    ///
+    /// All coroutine frames share a single joined taint (`coro_frames_tainted`),
+    /// much as the extra arguments of a variadic function do. The frames become
+    /// tainted if any coroutine's promise holds tainted data, or if a handle
+    /// which is tainted (or points to tainted data) is resumed, destroyed, or
+    /// used to get the promise. From then on, every coroutine's promise, and
+    /// the data pointed to by every handle and promise pointer, is tainted.
+    ///
+    /// This models coroutines before they are split by LLVM's `CoroSplit`
+    /// pass. After splitting, the ramp, resume, and destroy functions share the
+    /// frame through its named struct type, like any other named struct.
+    fn process_coro_intrinsic(&mut self, call: &'m instruction::Call, name: &str) -> Result<bool, String> {
+        self.coro_users.insert(self.cur_fn);
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least {} arguments, but it has {}", name, i + 1, call.arguments.len()))
+        };
+        let kind = name.strip_prefix("llvm.coro.").and_then(|rest| rest.split('.').next()).unwrap_or("");
+        let mut changed = false;
+        let mut frames_became_tainted = false;
+        match kind {
+            "id" => {
+                // the second argument is the promise, or null if there is none
+                let promise = arg(1)?;
+                let is_null = matches!(promise, Operand::ConstantOperand(cref) if matches!(cref.as_ref(), Constant::Null(_)));
+                if !is_null {
+                    let mut promise_pointee = self.get_pointee_through_casts(promise)?;
+                    if self.coro_frames_tainted {
+                        let tainted_ty = self.to_tainted(&promise_pointee.cloned_ty());
+                        changed |= self.fn_taint_states.get_current().update_pointee_taintedtype(&mut promise_pointee, &tainted_ty)?;
+                    } else if self.is_type_tainted(&promise_pointee.cloned_ty()) {
+                        frames_became_tainted = true;
                     }
+                }
+            },
+            "resume" | "destroy" | "promise" => {
+                let handle_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(0)?)?;
+                let handle_tainted = match &handle_ty {
+                    TaintedType::UntaintedPointer(pointee) => self.is_type_tainted(&pointee.cloned_ty()),
+                    ty => self.is_type_tainted(ty),
+                };
+                if handle_tainted && !self.coro_frames_tainted {
+                    frames_became_tainted = true;
+                }
+            },
+            _ => {},
+        }
+        if frames_became_tainted {
+            debug!("Coroutine frames became tainted in {:?}", self.cur_fn);
+            self.coro_frames_tainted = true;
+            let mut worklist = self.worklist.write().unwrap();
+            for user in &self.coro_users {
+                worklist.add(user);
+            }
+        }
+        if let Some(dest) = &call.dest {
+            // everything else (tokens, sizes, suspend results, etc.) is untainted
+            let ty = self.named_structs.read().unwrap().fresh_type_from_llvm(&self.cur_mod.type_of(call));
+            let ty = match (kind, ty) {
+                ("begin" | "frame" | "free" | "promise" | "noop", TaintedType::UntaintedPointer(pointee)) if self.coro_frames_tainted => {
+                    TaintedType::untainted_ptr_to(self.to_tainted(&pointee.cloned_ty()))
                 },
-                _ => unimplemented!("instruction {:?}", inst),
+                (_, ty) => ty,
+            };
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Process a call of `llvm.experimental.gc.statepoint`, `gc.result`, or
+    /// `gc.relocate`, which frontends for garbage-collected languages use at
+    /// safepoints.
+    ///
+    /// A statepoint wraps a call, which we process like an ordinary call of
+    /// its target with the wrapped arguments. The statepoint's token carries
+    /// the `TaintedType` of the wrapped call's result, which `gc.result` then
+    /// extracts.
+    ///
+    /// A relocated pointer is the original pointer after the collector may
+    /// have moved what it points to, so it has the original's `TaintedType`.
+    /// Before LLVM 11, the pointers to relocate are extra arguments of the
+    /// statepoint, and `gc.relocate` gives the original's index among them.
+    /// Since then, they're in a `gc-live` operand bundle, which llvm-ir doesn't
+    /// expose; then we can't tell which pointer is relocated, so, much like for
+    /// a `phi`, we join all the values of the same type in the function.
+    fn process_gc_intrinsic(&mut self, call: &'m instruction::Call, name: &str) -> Result<bool, String> {
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least {} arguments, but it has {}", name, i + 1, call.arguments.len()))
+        };
+        let kind = name.strip_prefix("llvm.experimental.gc.").and_then(|rest| rest.split('.').next()).unwrap_or("");
+        match kind {
+            "statepoint" => {
+                // the arguments are the ID, the number of patch bytes, the
+                // target, the number of call arguments, the flags, the call
+                // arguments, and then the transition, deopt, and (before LLVM
+                // 11) GC arguments
+                let target = arg(2)?;
+                let num_call_args = statepoint_num_call_args(call).ok_or_else(|| format!("Expected {} to have a constant number of call arguments", name))?;
+                let call_args = call.arguments.get(5 .. 5 + num_call_args).ok_or_else(|| format!("Expected {} to have {} call arguments, but it has {} arguments in all", name, num_call_args, call.arguments.len()))?;
+                let returns_value = match self.cur_mod.type_of(target).as_ref() {
+                    Type::PointerType { pointee_type, .. } => matches!(pointee_type.as_ref(), Type::FuncType { result_type, .. } if !matches!(result_type.as_ref(), Type::VoidType)),
+                    _ => false,
+                };
+                let wrapped = instruction::Call {
+                    function: Either::Right(target.clone()),
+                    arguments: call_args.to_vec(),
+                    return_attributes: Vec::new(),
+                    dest: call.dest.clone().filter(|_| returns_value),
+                    function_attributes: Vec::new(),
+                    is_tail_call: false,
+                    calling_convention: call.calling_convention,
+                    debugloc: None,
+                };
+                let mut changed = false;
+                if let (Some(dest), false) = (&call.dest, returns_value) {
+                    changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), TaintedType::Token)?;
+                }
+                let target_name = match target {
+                    Operand::ConstantOperand(cref) => match annotations::strip_casts(cref.as_ref()) {
+                        Constant::GlobalReference { name: Name::Name(target_name), .. } => Some(target_name.as_str()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match target_name {
+                    Some(target_name) => {
+                        // the call graph only has the call of the statepoint,
+                        // but we need to hear about changes to the target's
+                        // summary
+                        self.clones.add_caller(target_name, self.cur_fn);
+                        changed |= self.process_function_call(&wrapped, target_name)?;
+                    },
+                    None => {
+                        let operands: Vec<Operand> = call_args.iter().map(|(op, _)| op.clone()).collect();
+                        changed |= self.process_unhandled(
+                            "gc.statepoint of a function pointer",
+                            &operands,
+                            wrapped.dest.as_ref().map(|dest| (dest, self.cur_mod.type_of(&wrapped))),
+                            || format!("{} of a function pointer", name),
+                        )?;
+                    },
+                }
+                Ok(changed)
+            },
+            "result" => {
+                let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                let result_llvm_ty = self.cur_mod.type_of(call);
+                let cur_fn = self.fn_taint_states.get_current();
+                let token_ty = match arg(0)? {
+                    Operand::LocalOperand { name: token, .. } => cur_fn.get_taint_map().get(token).cloned(),
+                    _ => None,
+                };
+                let ty = token_ty.unwrap_or_else(|| cur_fn.type_from_llvm(&result_llvm_ty));
+                cur_fn.update_var_taintedtype(dest.clone(), ty)
+            },
+            "relocate" => {
+                let dest = call.dest.as_ref().ok_or_else(|| format!("Expected {} to have a result", name))?;
+                let derived_index = match arg(2)?.as_constant() {
+                    Some(Constant::Int { value, .. }) => *value,
+                    _ => return Err(format!("Expected {} to have a constant index", name)),
+                };
+                let (f, _) = self.analysis.get_func_by_name(self.cur_base_fn()).ok_or_else(|| format!("current function {:?} not found", self.cur_fn))?;
+                let statepoint = match arg(0)? {
+                    Operand::LocalOperand { name: token, .. } => find_definition(f, token),
+                    _ => None,
+                };
+                let original = match statepoint {
+                    Some(Instruction::Call(statepoint)) => statepoint_num_call_args(statepoint)
+                        .filter(|num_call_args| statepoint.arguments.len() > 7 + num_call_args)
+                        .and_then(|_| statepoint.arguments.get(usize::try_from(derived_index).ok()?))
+                        .map(|(op, _)| op.clone()),
+                    _ => None,
+                };
+                let result_llvm_ty = self.cur_mod.type_of(call);
+                let candidates: Vec<Operand> = match original {
+                    Some(original) => vec![original],
+                    None => {
+                        let params = f.parameters
+                            .iter()
+                            .map(|param| (&param.name, param.ty.clone()));
+                        let results = f.basic_blocks
+                            .iter()
+                            .flat_map(|bb| bb.instrs.iter())
+                            .filter_map(|inst| inst.try_get_result().map(|name| (name, self.cur_mod.type_of(inst))));
+                        params
+                            .chain(results)
+                            .filter(|(name, ty)| *name != dest && *ty == result_llvm_ty)
+                            .map(|(name, ty)| Operand::LocalOperand { name: name.clone(), ty })
+                            .collect()
+                    },
+                };
+                let cur_fn = self.fn_taint_states.get_current();
+                let candidate_tys = candidates
+                    .iter()
+                    .map(|op| cur_fn.get_type_of_operand(op))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let ty = match candidate_tys.split_first() {
+                    Some((first, rest)) => rest.iter().try_fold(first.clone(), |ty, other| ty.join(other))?,
+                    None => cur_fn.type_from_llvm(&result_llvm_ty),
+                };
+                cur_fn.update_var_taintedtype(dest.clone(), ty)
+            },
+            _ => Err(format!("Unexpected GC intrinsic {}", name)),
+        }
+    }
+
+    /// Get the `Allocator` the function with the given name is, if we're
+    /// modeling it (see `Config::model_allocators`)
+    fn allocator(&self, fn_name: &str) -> Option<Allocator> {
+        let allocator = Allocator::from_name(fn_name)?;
+        if !self.config.model_allocators
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(allocator)
+    }
+
+    /// Process a call of an `Allocator`: the result is an untainted pointer to
+    /// the call's `Allocation`, which is created the first time we process the
+    /// call. For `realloc`, the allocation also becomes tainted if the memory
+    /// passed to it is (or if that memory is itself an allocation, any of its
+    /// casts).
+    ///
+    /// A call of a deallocator has no effect, except that it's recorded if it's
+    /// passed a tainted pointer and `Config::report_tainted_frees` is set.
+    fn process_allocator_call(&mut self, call: &'m instruction::Call, allocator: Allocator) -> Result<bool, String> {
+        if allocator == Allocator::Free {
+            let ptr_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected deallocator to have at least one argument, but it has {}", call.arguments.len()))?;
+            let ptr_ty = self.fn_taint_states.get_current().get_type_of_operand(ptr_operand)?;
+            if self.config.report_tainted_frees && self.is_type_tainted(&ptr_ty) {
+                let block = self.cur_block.cloned().unwrap();
+                let index = self.cur_inst.unwrap();
+                return Ok(self.fn_taint_states.get_current().mark_free_tainted(block, index));
             }
+            return Ok(false);
+        }
+        let dest = match &call.dest {
+            Some(dest) => dest,
+            None => return Ok(false), // the memory is leaked, and no one can see it
+        };
+        let key = (self.cur_fn, dest.clone());
+        if !self.allocations.contains_key(&key) {
+            let (f, _) = self.analysis.get_func_by_name(self.cur_base_fn()).ok_or_else(|| format!("current function {:?} not found", self.cur_fn))?;
+            let raw_ty = self.cur_mod.type_of(call);
+            let typed_ty = f.basic_blocks
+                .iter()
+                .flat_map(|bb| bb.instrs.iter())
+                .find_map(|inst| match inst {
+                    Instruction::BitCast(bc) if matches!(&bc.operand, Operand::LocalOperand { name, .. } if name == dest) => match bc.to_type.as_ref() {
+                        Type::PointerType { pointee_type, .. } => Some(pointee_type.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                });
+            let (raw_pointee_ty, typed_ty) = match raw_ty.as_ref() {
+                Type::PointerType { pointee_type, .. } => (pointee_type.clone(), typed_ty.unwrap_or_else(|| pointee_type.clone())),
+                ty => return Err(format!("Expected allocator to return a pointer, but it returns {}", ty)),
+            };
+            let mut named_structs = self.named_structs.write().unwrap();
+            let allocation = Allocation {
+                raw: named_structs.pointee_from_llvm(&raw_pointee_ty),
+                typed: named_structs.pointee_from_llvm(&typed_ty),
+                typed_ty,
+            };
+            drop(named_structs);
+            self.allocations.insert(key.clone(), allocation);
+        }
+        let mut changed = false;
+        if allocator == Allocator::Realloc {
+            let old_operand = call.arguments.first().map(|(op, _)| op).ok_or_else(|| format!("Expected realloc to have at least one argument, but it has {}", call.arguments.len()))?;
+            let old_pointee = self.get_pointee_through_casts(old_operand)?;
+            let old_typed = match old_operand {
+                Operand::LocalOperand { name, .. } => self.allocations.get(&(self.cur_fn, name.clone())).map(|allocation| allocation.typed.clone()),
+                _ => None,
+            };
+            let old_tainted = self.is_type_tainted(&old_pointee.cloned_ty())
+                || old_typed.is_some_and(|typed| self.is_type_tainted(&typed.cloned_ty()));
+            if old_tainted {
+                let allocation = &self.allocations[&key];
+                let (mut raw, mut typed) = (allocation.raw.clone(), allocation.typed.clone());
+                let tainted_raw_ty = self.to_tainted(&raw.cloned_ty());
+                let tainted_typed_ty = self.to_tainted(&typed.cloned_ty());
+                let cur_fn = self.fn_taint_states.get_current();
+                changed |= cur_fn.update_pointee_taintedtype(&mut raw, &tainted_raw_ty)?;
+                changed |= cur_fn.update_pointee_taintedtype(&mut typed, &tainted_typed_ty)?;
+            }
+        }
+        let ty = TaintedType::untainted_ptr_to_pointee(self.allocations[&key].raw.clone());
+        changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        Ok(changed)
+    }
+
+    /// Is the given `bitcast` a cast of the result of an `Allocator` call to a
+    /// pointer to the allocation's type?
+    fn is_cast_of_allocation(&self, bc: &instruction::BitCast) -> bool {
+        match (&bc.operand, bc.to_type.as_ref()) {
+            (Operand::LocalOperand { name, .. }, Type::PointerType { pointee_type, .. }) => self
+                .allocations
+                .get(&(self.cur_fn, name.clone()))
+                .is_some_and(|allocation| &allocation.typed_ty == pointee_type),
+            _ => false,
+        }
+    }
+
+    /// Process a `bitcast` for which `is_cast_of_allocation()` holds, where
+    /// the result of the allocator has type `from_ty`: the result points to
+    /// the allocation's typed `Pointee`. We keep the typed and raw `Pointee`s
+    /// in sync here, so that taint stored through the `i8*` (e.g., with
+    /// `memcpy`) is seen through the casts, and vice versa.
+    fn process_allocation_cast(&mut self, bc: &'m instruction::BitCast, from_ty: &TaintedType) -> Result<bool, String> {
+        let allocation = match &bc.operand {
+            Operand::LocalOperand { name, .. } => &self.allocations[&(self.cur_fn, name.clone())],
+            _ => unreachable!("is_cast_of_allocation() only holds for local operands"),
+        };
+        let (mut raw, mut typed) = (allocation.raw.clone(), allocation.typed.clone());
+        let mut changed = false;
+        if self.is_type_tainted(&raw.cloned_ty()) {
+            let tainted_typed_ty = self.to_tainted(&typed.cloned_ty());
+            changed |= self.fn_taint_states.get_current().update_pointee_taintedtype(&mut typed, &tainted_typed_ty)?;
+        }
+        if self.is_type_tainted(&typed.cloned_ty()) {
+            let tainted_raw_ty = self.to_tainted(&raw.cloned_ty());
+            changed |= self.fn_taint_states.get_current().update_pointee_taintedtype(&mut raw, &tainted_raw_ty)?;
+        }
+        let result_ty = if self.is_type_tainted(from_ty) {
+            TaintedType::tainted_ptr_to_pointee(typed)
+        } else {
+            TaintedType::untainted_ptr_to_pointee(typed)
+        };
+        changed |= self.fn_taint_states.get_current().update_var_taintedtype(bc.get_result().clone(), result_ty)?;
+        Ok(changed)
+    }
+
+    /// Get the `FormatFunction` the function with the given name is, if we're
+    /// modeling it (see `Config::model_format_functions`)
+    fn format_function(&self, fn_name: &str) -> Option<FormatFunction> {
+        let format_fn = FormatFunction::from_name(fn_name)?;
+        if !self.config.model_format_functions
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(format_fn)
+    }
+
+    /// Get the `KernelFunction` the function with the given name is, if we're
+    /// modeling it (see `Config::model_kernel_user_copies`)
+    fn kernel_function(&self, fn_name: &str) -> Option<KernelFunction> {
+        let kernel_fn = KernelFunction::from_name(fn_name)?;
+        if !self.config.model_kernel_user_copies
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(kernel_fn)
+    }
+
+    /// Process a call of a `KernelFunction`, as described for
+    /// `Config::model_kernel_user_copies`
+    fn process_kernel_call(&mut self, call: &'m instruction::Call, kernel_fn: KernelFunction) -> Result<bool, String> {
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {:?} to have at least {} arguments, but it has {}", kernel_fn, i + 1, call.arguments.len()))
+        };
+        let mut changed = false;
+        let mut result_ty = call.dest.as_ref().map(|_| self.type_from_llvm(&self.cur_mod.type_of(call)));
+        match kernel_fn {
+            KernelFunction::FromUser { dest } => {
+                let dest_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(dest)?)?;
+                changed |= self.taint_pointee_of(&dest_ty)?;
+            },
+            KernelFunction::DupUser => {
+                result_ty = match result_ty {
+                    Some(TaintedType::UntaintedPointer(pointee)) => Some(TaintedType::untainted_ptr_to(self.to_tainted(&pointee.cloned_ty()))),
+                    ty => ty,
+                };
+            },
+            KernelFunction::ToUser { src: data } | KernelFunction::PutUser { value: data } => {
+                let data_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(data)?)?;
+                if self.is_ptr_or_pointee_tainted(&data_ty) {
+                    let block = self.cur_block.cloned().unwrap();
+                    let index = self.cur_inst.unwrap();
+                    changed |= self.fn_taint_states.get_current().mark_sink_tainted(block, index);
+                }
+            },
+        }
+        if let (Some(dest), Some(ty)) = (&call.dest, result_ty) {
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Get the `JniFunction` the given call of a function pointer calls, if
+    /// we're modeling JNI (see `Config::model_jni`)
+    fn jni_function(&self, call: &instruction::Call) -> Option<JniFunction> {
+        if !self.config.model_jni {
+            return None;
+        }
+        let (struct_name, index) = self.fn_ptr_field(call)?;
+        if !JNI_TABLE_STRUCTS.contains(&struct_name.as_str()) {
+            return None;
+        }
+        JniFunction::from_table_index(index)
+    }
+
+    /// Process a call of a `JniFunction`, as described for `Config::model_jni`
+    fn process_jni_call(&mut self, call: &'m instruction::Call, jni_fn: JniFunction) -> Result<bool, String> {
+        let mut changed = false;
+        let mut result_ty = call.dest.as_ref().map(|_| self.type_from_llvm(&self.cur_mod.type_of(call)));
+        match jni_fn {
+            JniFunction::GetData => {
+                result_ty = match result_ty {
+                    Some(TaintedType::UntaintedPointer(pointee)) => Some(TaintedType::untainted_ptr_to(self.to_tainted(&pointee.cloned_ty()))),
+                    ty => ty,
+                };
+            },
+            JniFunction::GetValue => {
+                result_ty = result_ty.map(|ty| self.to_tainted(&ty));
+            },
+            JniFunction::CopyRegion { buf } => {
+                let buf_operand = call.arguments.get(buf).map(|(op, _)| op).ok_or_else(|| format!("Expected {:?} to have at least {} arguments, but it has {}", jni_fn, buf + 1, call.arguments.len()))?;
+                let buf_ty = self.fn_taint_states.get_current().get_type_of_operand(buf_operand)?;
+                changed |= self.taint_pointee_of(&buf_ty)?;
+            },
+        }
+        if let (Some(dest), Some(ty)) = (&call.dest, result_ty) {
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Get the `WasiFunction` the function with the given name is, if we're
+    /// modeling it (see `Config::model_wasi_imports`)
+    fn wasi_function(&self, fn_name: &str) -> Option<WasiFunction> {
+        let wasi_fn = WasiFunction::from_name(fn_name)?;
+        if !self.config.model_wasi_imports
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(wasi_fn)
+    }
+
+    /// Process a call of a `WasiFunction`, as described for
+    /// `Config::model_wasi_imports`
+    fn process_wasi_call(&mut self, call: &'m instruction::Call, wasi_fn: WasiFunction) -> Result<bool, String> {
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {:?} to have at least {} arguments, but it has {}", wasi_fn, i + 1, call.arguments.len()))
+        };
+        let mut changed = false;
+        match wasi_fn {
+            WasiFunction::ReadIovecs { iovs } => {
+                let iovs_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(iovs)?)?;
+                changed |= self.taint_nested_pointees_of(&iovs_ty)?;
+            },
+            WasiFunction::ReadBuffer { buf } => {
+                let buf_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(buf)?)?;
+                changed |= self.taint_pointee_of(&buf_ty)?;
+            },
+            WasiFunction::GetStrings { ptrs, buf } => {
+                let ptrs_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(ptrs)?)?;
+                let buf_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(buf)?)?;
+                changed |= self.taint_nested_pointees_of(&ptrs_ty)?;
+                changed |= self.taint_pointee_of(&buf_ty)?;
+            },
+            WasiFunction::GetSizes { count, size } => {
+                for index in [count, size] {
+                    let ty = self.fn_taint_states.get_current().get_type_of_operand(arg(index)?)?;
+                    changed |= self.taint_pointee_of(&ty)?;
+                }
+            },
+        }
+        if let Some(dest) = &call.dest {
+            let ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Get the `GpuFunction` the function with the given name is, if we're
+    /// modeling it (see `Config::model_gpu_builtins`)
+    fn gpu_function(&self, fn_name: &str) -> Option<GpuFunction> {
+        let gpu_fn = GpuFunction::from_name(fn_name)?;
+        if !self.config.model_gpu_builtins
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(gpu_fn)
+    }
+
+    /// Process a call of a `GpuFunction`, as described for
+    /// `Config::model_gpu_builtins`
+    fn process_gpu_call(&mut self, call: &'m instruction::Call, gpu_fn: GpuFunction) -> Result<bool, String> {
+        let arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {:?} to have at least {} arguments, but it has {}", gpu_fn, i + 1, call.arguments.len()))
+        };
+        let mut changed = false;
+        let mut result_ty = call.dest.as_ref().map(|_| self.type_from_llvm(&self.cur_mod.type_of(call)));
+        match gpu_fn {
+            GpuFunction::NoEffect => {},
+            GpuFunction::Collective => {
+                let mut any_tainted = false;
+                for (op, _) in &call.arguments {
+                    let ty = self.fn_taint_states.get_current().get_type_of_operand(op)?;
+                    any_tainted |= self.is_type_tainted(&ty);
+                }
+                if any_tainted {
+                    result_ty = result_ty.map(|ty| self.to_tainted(&ty));
+                }
+            },
+            GpuFunction::Atomic { ptr } => {
+                let ptr_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(ptr)?)?;
+                let mut values_tainted = false;
+                for (i, (op, _)) in call.arguments.iter().enumerate() {
+                    if i != ptr {
+                        let ty = self.fn_taint_states.get_current().get_type_of_operand(op)?;
+                        values_tainted |= self.is_type_tainted(&ty);
+                    }
+                }
+                if values_tainted {
+                    changed |= self.taint_pointee_of(&ptr_ty)?;
+                }
+                // the result is the old contents, which may have a different
+                // type than the pointer's (e.g., through an `i32*` cast)
+                let contents_ty = self.get_load_result_ty(&ptr_ty)?;
+                if self.is_type_tainted(&contents_ty) {
+                    result_ty = result_ty.map(|ty| self.to_tainted(&ty));
+                }
+            },
+            GpuFunction::AsyncCopy { dest, src } => {
+                let dest_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(dest)?)?;
+                let src_ty = self.fn_taint_states.get_current().get_type_of_operand(arg(src)?)?;
+                let src_contents_ty = self.get_load_result_ty(&src_ty)?;
+                if self.is_type_tainted(&src_contents_ty) {
+                    changed |= self.taint_pointee_of(&dest_ty)?;
+                }
+            },
+        }
+        if let (Some(dest), Some(ty)) = (&call.dest, result_ty) {
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Mark the data pointed to by the pointers which the given pointer points
+    /// to as tainted: for a pointer to a pointer, the data that one points to;
+    /// for a pointer to a struct (e.g., an `iovec`), the data each of its
+    /// pointer fields points to. Does nothing for other types.
+    ///
+    /// Returns `true` if this was a change.
+    fn taint_nested_pointees_of(&mut self, ty: &TaintedType) -> Result<bool, String> {
+        let contents = match ty {
+            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => pointee.ty().clone(),
+            _ => return Ok(false),
+        };
+        let contents = match contents {
+            TaintedType::NamedStruct(name) => self.get_named_struct_type(name),
+            contents => contents,
+        };
+        let nested: Vec<TaintedType> = match contents {
+            TaintedType::Struct(elements) => elements.iter().map(|element| element.ty().clone()).collect(),
+            ptr @ TaintedType::UntaintedPointer(_) | ptr @ TaintedType::TaintedPointer(_) => vec![ptr],
+            _ => Vec::new(),
+        };
+        let mut changed = false;
+        for ty in nested {
+            changed |= self.taint_pointee_of(&ty)?;
+        }
+        Ok(changed)
+    }
+
+    /// Is the given address a constant address of tainted device memory (see
+    /// `Config::tainted_address_ranges`)?
+    fn is_device_memory(&self, addr: &Operand) -> bool {
+        if self.config.tainted_address_ranges.is_empty() {
+            return false;
+        }
+        let address = match self.analysis.get_func_by_name(self.cur_base_fn()) {
+            Some((f, _)) => constant_address(f, addr),
+            None => None,
+        };
+        address.is_some_and(|address| self.config.tainted_address_ranges.iter().any(|range| range.contains(&address)))
+    }
+
+    /// Get the address space of the given pointer operand, or `None` if it
+    /// isn't a pointer
+    fn address_space(&self, ptr: &Operand) -> Option<u32> {
+        match self.cur_mod.type_of(ptr).as_ref() {
+            Type::PointerType { addr_space, .. } => Some(*addr_space),
+            _ => None,
+        }
+    }
+
+    /// Get the `JumpFunction` the function with the given name is, if we're
+    /// modeling it (see `Config::model_setjmp_longjmp`)
+    fn jump_function(&self, fn_name: &str) -> Option<JumpFunction> {
+        let jump_fn = JumpFunction::from_name(fn_name)?;
+        if !self.config.model_setjmp_longjmp
+            || self.config.get_ext_function(fn_name).is_some()
+            || self.get_analyzed_func_by_name(fn_name).is_some()
+        {
+            return None;
+        }
+        Some(jump_fn)
+    }
+
+    /// Process a call of a `JumpFunction`, as described for
+    /// `Config::model_setjmp_longjmp`.
+    ///
+    /// All `setjmp`s share a single joined taint (`longjmp_values_tainted`) for
+    /// their second returns, much as coroutine frames do: the result of every
+    /// `setjmp` is tainted once the value passed to any `longjmp` is.
+    fn process_jump_call(&mut self, call: &'m instruction::Call, jump_fn: JumpFunction) -> Result<bool, String> {
+        match jump_fn {
+            JumpFunction::Setjmp => {
+                self.setjmp_users.insert(self.cur_fn);
+                match &call.dest {
+                    Some(dest) => {
+                        let ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+                        let ty = if self.longjmp_values_tainted { self.to_tainted(&ty) } else { ty };
+                        self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)
+                    },
+                    None => Ok(false),
+                }
+            },
+            JumpFunction::Longjmp { value } => {
+                let value_operand = call.arguments.get(value).map(|(op, _)| op).ok_or_else(|| format!("Expected longjmp to have at least {} arguments, but it has {}", value + 1, call.arguments.len()))?;
+                let cur_fn = self.fn_taint_states.get_current();
+                if !self.longjmp_values_tainted && cur_fn.is_scalar_operand_tainted(value_operand)? {
+                    debug!("Value passed to longjmp became tainted in {:?}", self.cur_fn);
+                    self.longjmp_values_tainted = true;
+                    let mut worklist = self.worklist.write().unwrap();
+                    for user in &self.setjmp_users {
+                        worklist.add(user);
+                    }
+                }
+                // `longjmp` doesn't return, so we don't change anything in
+                // this function
+                Ok(false)
+            },
         }
     }
 
-    /// Get the `TaintedType` of the value loaded from the given address.
-    fn get_load_result_ty(&mut self, addr: &TaintedType) -> Result<TaintedType, String> {
-        match addr {
-            TaintedType::UntaintedValue | TaintedType::TaintedValue => {
-                Err(format!(
-                    "Load: address is not a pointer: got type {}",
-                    addr
-                ))
-            },
-            TaintedType::UntaintedFnPtr | TaintedType::TaintedFnPtr => {
-                Err("Loading from a function pointer".into())
-            },
-            TaintedType::ArrayOrVector(_)
-            | TaintedType::Struct(_)
-            | TaintedType::NamedStruct(_) => {
-                Err(format!(
-                    "Load: address is not a pointer: got type {}",
-                    addr
-                ))
-            },
-            TaintedType::UntaintedPointer(pointee) => {
-                Ok(pointee.ty().clone())
+    /// Process a call of a `FormatFunction`, as described for
+    /// `Config::model_format_functions`
+    fn process_format_call(&mut self, call: &'m instruction::Call, format_fn: FormatFunction) -> Result<bool, String> {
+        let format_index = format_fn.format_index();
+        if call.arguments.len() <= format_index {
+            return Err(format!("Expected format function to have at least {} arguments, but it has {}", format_index + 1, call.arguments.len()));
+        }
+        let cur_fn = self.fn_taint_states.get_current();
+        let arg_tys = call.arguments
+            .iter()
+            .map(|(op, _)| cur_fn.get_type_of_operand(op))
+            .collect::<Result<Vec<_>, String>>()?;
+        let format_tainted = self.is_ptr_or_pointee_tainted(&arg_tys[format_index]);
+        let mut changed = false;
+        if format_tainted && self.config.report_tainted_format_strings {
+            let block = self.cur_block.cloned().unwrap();
+            let index = self.cur_inst.unwrap();
+            changed |= self.fn_taint_states.get_current().mark_format_string_tainted(block, index);
+        }
+        let extra_arg_tys = &arg_tys[format_index + 1 ..];
+        let result_tainted = match format_fn {
+            FormatFunction::Print { dest, .. } => {
+                let data_tainted = format_tainted || extra_arg_tys.iter().any(|ty| self.is_ptr_or_pointee_tainted(ty));
+                if let (true, Some(dest)) = (data_tainted, dest) {
+                    changed |= self.taint_pointee_of(&arg_tys[dest])?;
+                }
+                data_tainted
             },
-            TaintedType::TaintedPointer(pointee) => {
-                if self.config.dereferencing_tainted_ptr_gives_tainted {
-                    pointee.taint(&mut self.named_structs.borrow_mut());
+            FormatFunction::Scan { input, .. } => {
+                let input_tainted = format_tainted || match input {
+                    Some(input) => self.is_ptr_or_pointee_tainted(&arg_tys[input]),
+                    None => self.config.stream_input_tainted,
+                };
+                if input_tainted {
+                    for ty in extra_arg_tys {
+                        changed |= self.taint_pointee_of(ty)?;
+                    }
                 }
-                Ok(pointee.ty().clone())
+                input_tainted
             },
+        };
+        if let Some(dest) = &call.dest {
+            let ty = self.type_from_llvm(&self.cur_mod.type_of(call));
+            let ty = if result_tainted { self.to_tainted(&ty) } else { ty };
+            changed |= self.fn_taint_states.get_current().update_var_taintedtype(dest.clone(), ty)?;
         }
+        Ok(changed)
     }
 
-    /// Process the store of a value to an address.
-    fn process_store(&mut self, value: &TaintedType, addr: &mut TaintedType) -> Result<bool, String> {
-        match addr {
-            TaintedType::UntaintedValue | TaintedType::TaintedValue => {
-                Err(format!(
-                    "Store: address is not a pointer: got type {}",
-                    addr
-                ))
+    /// Is the given type tainted, or, if it's a pointer, the data it points to?
+    fn is_ptr_or_pointee_tainted(&mut self, ty: &TaintedType) -> bool {
+        match ty {
+            TaintedType::UntaintedPointer(pointee) => self.is_type_tainted(&pointee.cloned_ty()),
+            ty => self.is_type_tainted(ty),
+        }
+    }
+
+    /// Mark the data the given pointer points to as tainted. Does nothing if
+    /// the given type isn't a pointer.
+    ///
+    /// Returns `true` if this was a change.
+    fn taint_pointee_of(&mut self, ty: &TaintedType) -> Result<bool, String> {
+        match ty {
+            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => {
+                let mut pointee = pointee.clone();
+                let tainted_ty = self.to_tainted(&pointee.cloned_ty());
+                self.fn_taint_states.get_current().update_pointee_taintedtype(&mut pointee, &tainted_ty)
             },
-            TaintedType::UntaintedFnPtr | TaintedType::TaintedFnPtr => {
-                Err("Storing to a function pointer".into())
+            _ => Ok(false),
+        }
+    }
+
+    /// Get the functions the given call of a function pointer may call, if we
+    /// can tell from the vtable slot (see `Config::devirtualize_virtual_calls`)
+    /// or struct field (see `Config::resolve_closure_thunks`) the pointer is
+    /// loaded from
+    fn known_call_targets(&self, call: &instruction::Call) -> Option<&HashSet<&'m str>> {
+        if let Some(overriders) = self.virtual_call_slot(call).and_then(|slot| self.vtable_slots.get(&slot)) {
+            return Some(overriders);
+        }
+        if self.functions_in_fields.is_empty() {
+            return None;
+        }
+        let field = self.fn_ptr_field(call)?;
+        self.functions_in_fields.get(&field)?.as_ref()
+    }
+
+    /// Get the functions the given call of a function pointer may call, if we
+    /// know which functions the pointer points to (see
+    /// `Config::resolve_fn_ptr_targets`)
+    fn fn_ptr_targets(&mut self, call: &instruction::Call) -> Result<Option<FnTargets>, String> {
+        let fn_ptr = match &call.function {
+            Either::Right(fn_ptr) => fn_ptr,
+            Either::Left(_) => return Ok(None),
+        };
+        match self.fn_taint_states.get_current().get_type_of_operand(fn_ptr)? {
+            TaintedType::UntaintedFnPtr(targets) | TaintedType::TaintedFnPtr(targets) if !targets.is_empty() => Ok(Some(targets)),
+            _ => Ok(None),
+        }
+    }
+
+    /// If the given call is of a function pointer loaded from a field of a
+    /// named struct, get the struct's name and the field index
+    fn fn_ptr_field(&self, call: &instruction::Call) -> Option<(String, u64)> {
+        let (f, _) = self.analysis.get_func_by_name(self.cur_base_fn())?;
+        let local = |op: &Operand| match op {
+            Operand::LocalOperand { name, .. } => find_definition(f, name),
+            _ => None,
+        };
+        let fn_ptr_addr = match call.function.as_ref().right().and_then(local)? {
+            Instruction::Load(load) => &load.address,
+            _ => return None,
+        };
+        match local(fn_ptr_addr)? {
+            Instruction::GetElementPtr(gep) => modules::struct_field(self.cur_mod, gep),
+            _ => None,
+        }
+    }
+
+    /// If the given call is a virtual call (see
+    /// `Config::devirtualize_virtual_calls`), get the name of the object's
+    /// class and the index of the vtable slot it calls through.
+    ///
+    /// That is, the called function pointer must be loaded from the vtable
+    /// pointer of an object, or from a constant offset from it, and the
+    /// object must be one of the call's arguments (usually `this`), with the
+    /// type of a pointer to a class.
+    fn virtual_call_slot(&self, call: &instruction::Call) -> Option<(String, u64)> {
+        let (f, _) = self.analysis.get_func_by_name(self.cur_base_fn())?;
+        let local = |op: &Operand| match op {
+            Operand::LocalOperand { name, .. } => find_definition(f, name),
+            _ => None,
+        };
+        let fn_ptr_addr = match call.function.as_ref().right().and_then(local)? {
+            Instruction::Load(load) => &load.address,
+            _ => return None,
+        };
+        let (vtable, slot) = match local(fn_ptr_addr)? {
+            Instruction::GetElementPtr(gep) if gep.indices.len() == 1 => match gep.indices[0].as_constant()? {
+                Constant::Int { value, .. } => (&gep.address, *value),
+                _ => return None,
             },
-            TaintedType::ArrayOrVector(_)
-            | TaintedType::Struct(_)
-            | TaintedType::NamedStruct(_) => {
-                Err(format!(
-                    "Store: address is not a pointer: got type {}",
-                    addr
-                ))
+            Instruction::Load(_) => (fn_ptr_addr, 0),
+            _ => return None,
+        };
+        let vptr_addr = match local(vtable)? {
+            Instruction::Load(load) => &load.address,
+            _ => return None,
+        };
+        // the address of the vtable pointer is the object itself, maybe cast
+        let object = match local(vptr_addr) {
+            Some(Instruction::BitCast(bc)) => &bc.operand,
+            _ => vptr_addr,
+        };
+        if !call.arguments.iter().any(|(arg, _)| arg == object) {
+            return None;
+        }
+        match self.cur_mod.type_of(object).as_ref() {
+            Type::PointerType { pointee_type, .. } => match pointee_type.as_ref() {
+                Type::NamedStructType { name } => Some((demangle::class_of_struct(name)?.to_owned(), slot)),
+                _ => None,
             },
-            TaintedType::UntaintedPointer(ref mut pointee) | TaintedType::TaintedPointer(ref mut pointee) => {
-                // Storing to a location while control-flow is tainted also
-                // needs to result in the stored value being marked tainted.
-                // This is because a tainted value (in some branch condition
-                // etc) influenced the value stored at this location.
-                let cur_fn = self.fn_taint_states.get_current();
-                let cdg = self.analysis.module_analysis(&self.cur_mod.name).fn_analysis(self.cur_fn).control_dependence_graph();
-                let need_to_taint = cdg
-                    .get_control_dependencies(&self.cur_block.unwrap())
-                    .any(|dep| cur_fn.is_terminator_tainted(dep));
+            _ => None,
+        }
+    }
 
-                // now update the store address's type based on the value being
-                // stored through it.
-                // specifically, update the pointee in that address type:
-                // e.g., if we're storing a tainted value, we want
-                // to update the address type to "pointer to tainted"
-                if need_to_taint {
-                    let tainted_val = self.to_tainted(value);
-                    self.fn_taint_states.get_current().update_pointee_taintedtype(pointee, &tainted_val)
-                } else {
-                    cur_fn.update_pointee_taintedtype(pointee, value)
-                }
+    /// Process a call of a thread-spawn function (see
+    /// `Config::thread_spawn_functions`) as a call of its callback, with the
+    /// argument at index `arg`, if the callback (the argument at index
+    /// `callback`) is a function in the analyzed module(s). This doesn't
+    /// process the call of the spawn function itself.
+    fn process_thread_spawn(&mut self, call: &'m instruction::Call, name: &str, callback: usize, arg: usize) -> Result<bool, String> {
+        let get_arg = |i: usize| {
+            call.arguments.get(i).map(|(op, _)| op).ok_or_else(|| format!("Expected {} to have at least {} arguments, but it has {}", name, i + 1, call.arguments.len()))
+        };
+        let callback_op = get_arg(callback)?;
+        let arg_op = get_arg(arg)?;
+        let callback_name = match callback_op {
+            Operand::ConstantOperand(cref) => match annotations::strip_casts(cref.as_ref()) {
+                Constant::GlobalReference { name: Name::Name(callback_name), .. } => callback_name.as_str(),
+                _ => return Ok(false),
             },
+            // a callback we can't resolve here; it's still analyzed if it's
+            // called anywhere else, but without the taint of `arg`
+            _ => return Ok(false),
+        };
+        if self.get_analyzed_func_by_name(callback_name).is_none() {
+            return Ok(false);
         }
+        let wrapped = instruction::Call {
+            function: Either::Right(callback_op.clone()),
+            arguments: vec![(arg_op.clone(), Vec::new())],
+            return_attributes: Vec::new(),
+            dest: None,
+            function_attributes: Vec::new(),
+            is_tail_call: false,
+            calling_convention: call.calling_convention,
+            debugloc: None,
+        };
+        // the call graph doesn't have an edge to the callback, but we need to
+        // hear about changes to its summary
+        self.clones.add_caller(callback_name, self.cur_fn);
+        self.process_function_call(&wrapped, callback_name)
     }
 
     /// Process the a call of a function with the given name.
@@ -932,52 +3671,171 @@ impl<'m> TaintState<'m> {
         call: &instruction::Call,
         funcname: &'m str,
     ) -> Result<bool, String> {
+        // For calls of variadic functions, the summary only has the fixed
+        // parameters; the extra arguments just contribute their joined taint
+        let (num_fixed_params, callee_is_var_arg) = match &call.function {
+            Either::Right(op) => match self.cur_mod.type_of(op).as_ref() {
+                Type::PointerType { pointee_type, .. } => match pointee_type.as_ref() {
+                    Type::FuncType { param_types, is_var_arg: true, .. } => (param_types.len().min(call.arguments.len()), true),
+                    _ => (call.arguments.len(), false),
+                },
+                _ => (call.arguments.len(), false),
+            },
+            Either::Left(_) => (call.arguments.len(), false),
+        };
+        let (fixed_args, extra_args) = call.arguments.split_at(num_fixed_params);
+        let cur_fn = self.fn_taint_states.get_current();
+        let arg_types: Vec<TaintedType> = fixed_args
+            .iter()
+            .map(|(arg, attrs)| match cur_fn.get_type_of_operand(arg)? {
+                // the callee gets a copy of a `byval` argument's pointee, at
+                // an address it chooses, so only the contents carry taint
+                TaintedType::TaintedPointer(pointee) if attributes::is_byval(attrs) => Ok(TaintedType::UntaintedPointer(pointee)),
+                ty => Ok(ty),
+            })
+            .collect::<Result<_, String>>()?;
+        // a leaf function in the shared summary store needs no analysis here
+        // (unless it's annotated, as its summary doesn't reflect that)
+        if let Some(store) = self.summary_store.clone() {
+            if !self.annotations.is_sink_function(funcname) && !self.annotations.is_source_function(funcname) {
+                let arg_taint: Vec<bool> = arg_types.iter().map(|ty| self.is_type_tainted(ty)).collect();
+                if let Some(ret_tainted) = store.is_ret_tainted(funcname, &arg_taint) {
+                    let ret_ty = if ret_tainted { TaintedType::TaintedValue } else { TaintedType::UntaintedValue };
+                    return match &call.dest {
+                        Some(varname) => self.fn_taint_states.get_current().update_var_taintedtype(varname.clone(), ret_ty),
+                        None => Ok(false),
+                    };
+                }
+            }
+        }
+        // calls within a recursive cycle, and with polymorphic summaries, all
+        // calls to defined functions, may go to a clone of the callee
+        let arg_taint = if self.config.polymorphic_summaries && self.get_analyzed_func_by_name(funcname).is_some() {
+            Some(arg_types.iter().map(|ty| self.is_type_tainted(ty)).collect())
+        } else {
+            None
+        };
+        let funcname = self.clones.resolve_callee(
+            &self.analysis.call_graph(),
+            self.cur_fn,
+            self.cur_block.unwrap(),
+            self.cur_inst.unwrap(),
+            funcname,
+            arg_taint,
+        );
+        let cur_fn = self.fn_taint_states.get_current();
+        let extra_arg_types: Vec<TaintedType> = extra_args
+            .iter()
+            .map(|(arg, _)| cur_fn.get_type_of_operand(arg))
+            .collect::<Result<_, _>>()?;
+        // a `musttail` call from a variadic function to a variadic function
+        // forwards the caller's own extra arguments (the `...`) to the callee,
+        // after any explicit ones. `llvm-ir` only tells us whether a call is a
+        // tail call, not whether it's `musttail`, so we conservatively assume
+        // that any tail call here may forward them.
+        let forwards_varargs = callee_is_var_arg
+            && call.is_tail_call
+            && self.analysis.get_func_by_name(self.cur_base_fn()).is_some_and(|(f, _)| f.is_var_arg);
+        let varargs_tainted = extra_arg_types.iter().any(|ty| self.is_type_tainted(ty))
+            || (forwards_varargs && self.fn_summaries.get(self.cur_fn).is_some_and(|summary| summary.are_varargs_tainted()));
+        let base_funcname = self.clones.base_function(funcname);
+        let mut changed = false;
+        if self.get_analyzed_func_by_name(base_funcname).is_none()
+            && self.config.get_ext_function(base_funcname).is_none()
+            && self.ext_function_handling(base_funcname) == config::ExternalFunctionHandling::IgnoreAndReturnUntainted
+            && self.record_assumption(format!("call of unknown external function {:?}: assumed to return untainted data", base_funcname))?
+        {
+            changed |= self.process_call_conservatively(call)?;
+        }
+        if self.annotations.is_sink_function(base_funcname) {
+            let passes_taint = arg_types.iter().chain(extra_arg_types.iter()).any(|ty| self.is_ptr_or_pointee_tainted(ty));
+            if passes_taint {
+                let block = self.cur_block.cloned().unwrap();
+                let index = self.cur_inst.unwrap();
+                changed |= self.fn_taint_states.get_current().mark_sink_tainted(block, index);
+            }
+        }
+        let is_source = self.ext_sources_tainted && self.annotations.is_source_function(base_funcname);
+
         // Get the function summary for the called function
-        let summary = match self.fn_summaries.entry(funcname.clone()) {
+        let summary = match self.fn_summaries.entry(funcname) {
             Entry::Occupied(oentry) => oentry.into_mut(),
             Entry::Vacant(ventry) => {
                 // no summary: start with the default one (nothing tainted) and add the
                 // called function to the worklist so that we can compute a better one
-                self.worklist.borrow_mut().add(funcname);
+                self.worklist.write().unwrap().add(funcname);
                 let cur_mod = self.cur_mod;
                 ventry.insert(FunctionSummary::new_untainted(
-                    call.arguments.iter().map(|(arg, _)| cur_mod.type_of(arg)),
+                    fixed_args.iter().map(|(arg, _)| cur_mod.type_of(arg)),
                     &cur_mod.type_of(call),
-                    Rc::clone(&self.named_structs),
+                    Arc::clone(&self.named_structs),
                 ))
             },
         };
+        let sret_index = attributes::sret_index(fixed_args.iter().map(|(_, attrs)| attrs.as_slice()));
+        if let Some(index) = sret_index {
+            summary.set_sret_param(index);
+        }
         // use the `TaintedType`s of the provided arguments to update the
         // `TaintedType`s of the parameters in the function summary, if appropriate
-        let cur_fn = self.fn_taint_states.get_current();
-        let arg_types = call
-            .arguments
-            .iter()
-            .map(|(arg, _)| cur_fn.get_type_of_operand(arg))
-            .collect::<Result<_, _>>()?;
-        if summary.update_params(arg_types)? {
+        let mut summary_changed = summary.update_params(arg_types)?;
+        if varargs_tainted {
+            summary_changed |= summary.taint_varargs();
+        }
+        if is_source {
+            summary_changed |= summary.taint_ret();
+        }
+        if summary_changed {
             // summary changed: put all callers of the called function on the worklist
             // because the new summary could affect inferred types in its callers
-            let mut worklist = self.worklist.borrow_mut();
-            for caller in self.analysis.call_graph().callers(funcname) {
-                worklist.add(caller);
-            }
+            self.add_callers_to_worklist(funcname);
             // and also put the called function itself on the worklist
-            worklist.add(funcname);
+            self.worklist.write().unwrap().add(funcname);
+            self.trace_summary(funcname);
+        }
+        // the callee returns a struct through the `sret` argument by storing
+        // it there
+        if let (Some(index), Some(sret_ty)) = (sret_index, self.fn_summaries[funcname].get_sret_ty()) {
+            let cur_fn = self.fn_taint_states.get_current();
+            let mut sret_arg_ty = cur_fn.get_type_of_operand(&fixed_args[index].0)?;
+            changed |= self.process_store(&sret_ty, &mut sret_arg_ty)?;
         }
         // and finally, for non-void calls, use the return type in the summary to
         // update the type of the result in this function
-        let summary_ret_ty = summary.get_ret_ty().clone(); // this should end the life of `summary` and therefore its mutable borrow of `self.fn_summaries`
-        match &call.dest {
+        let summary_ret_ty = self.fn_summaries[funcname].get_ret_ty().clone();
+        let cur_fn = self.fn_taint_states.get_current();
+        let ret_changed = match &call.dest {
             Some(varname) => {
-                cur_fn.update_var_taintedtype(varname.clone(), summary_ret_ty.unwrap())
+                cur_fn.update_var_taintedtype(varname.clone(), summary_ret_ty.unwrap())?
             },
-            None => Ok(false), // nothing changed in the current function
+            None => false, // nothing changed in the current function
+        };
+        Ok(changed || ret_changed)
+    }
+
+    /// Put all the callers of the given function (or clone, see
+    /// `FunctionClones`) on the worklist, e.g. because its summary changed
+    fn add_callers_to_worklist(&self, fn_name: &'m str) {
+        let mut worklist = self.worklist.write().unwrap();
+        if !self.clones.is_clone(fn_name) {
+            for caller in self.analysis.call_graph().callers(fn_name) {
+                worklist.add(caller);
+            }
         }
+        for caller in self.clones.callers_of(fn_name) {
+            worklist.add(caller);
+        }
+    }
+
+    /// Get the name of the function currently being processed; if we're
+    /// processing a clone (see `FunctionClones`), the name of the function
+    /// it's a clone of
+    fn cur_base_fn(&self) -> &'m str {
+        self.clones.base_function(self.cur_fn)
     }
 
     /// Process the given `Terminator`, updating taint states if appropriate.
-    fn process_terminator(&mut self, term: &Terminator) -> Result<bool, String> {
+    fn process_terminator(&mut self, term: &'m Terminator) -> Result<bool, String> {
         match term {
             Terminator::Ret(ret) => {
                 // first mark the terminator tainted if necessary
@@ -1006,10 +3864,8 @@ impl<'m> TaintState<'m> {
                         if summary.update_ret(&ty.as_ref())? {
                             // summary changed: put all our callers on the worklist
                             // because the new summary could affect inferred types in our callers
-                            let mut worklist = self.worklist.borrow_mut();
-                            for caller in self.analysis.call_graph().callers(self.cur_fn) {
-                                worklist.add(caller);
-                            }
+                            self.add_callers_to_worklist(self.cur_fn);
+                            self.trace_summary(self.cur_fn);
                             changed = true;
                         }
                         Ok(changed)
@@ -1019,7 +3875,11 @@ impl<'m> TaintState<'m> {
             Terminator::CondBr(condbr) => {
                 let cur_fn = self.fn_taint_states.get_current();
                 let op_type = cur_fn.get_type_of_operand(&condbr.condition)?;
-                if self.is_type_tainted(&op_type) {
+                let is_sanitizing_check = (self.config.sanitize_bounds_checks || !self.config.validators.is_empty()) && {
+                    let (f, _) = self.analysis.get_func_by_name(self.cur_base_fn()).ok_or_else(|| format!("current function {:?} not found", self.cur_fn))?;
+                    self.sanitizing_check(f, condbr).is_some()
+                };
+                if self.is_type_tainted(&op_type) && !is_sanitizing_check {
                     let cur_fn = self.fn_taint_states.get_current();
                     Ok(cur_fn.mark_terminator_tainted(self.cur_block.cloned().unwrap()))
                 } else {
@@ -1048,7 +3908,12 @@ impl<'m> TaintState<'m> {
             },
             Terminator::Br(_) => Ok(false), // unconditional branches can't be tainted
             Terminator::Unreachable(_) => Ok(false),
-            _ => unimplemented!("terminator {:?}", term),
+            _ => self.process_unhandled(
+                terminator_kind(term),
+                &terminator_operands(term),
+                terminator_result(term).map(|dest| (dest, self.cur_mod.type_of(term))),
+                || format!("terminator {:?}", term),
+            ),
         }
     }
 
@@ -1057,7 +3922,7 @@ impl<'m> TaintState<'m> {
         parent_ptr: &'a TaintedType,
         indices: impl IntoIterator<Item = &'b I>,
     ) -> Result<TaintedType, String> {
-        self.named_structs.borrow_mut().get_element_ptr(&self.cur_fn, parent_ptr, indices)
+        self.named_structs.write().unwrap().get_element_ptr(self.cur_fn, parent_ptr, indices)
     }
 }
 
@@ -1075,3 +3940,724 @@ fn brief_display_instruction(inst: &Instruction) -> String {
         }
     }
 }
+
+/// Get the LLVM opcode of the given instruction, e.g. `"add"`, for tracing
+/// spans and for reporting unhandled instructions
+fn instruction_kind(inst: &Instruction) -> &'static str {
+    match inst {
+        Instruction::Add(_) => "add",
+        Instruction::Sub(_) => "sub",
+        Instruction::Mul(_) => "mul",
+        Instruction::UDiv(_) => "udiv",
+        Instruction::SDiv(_) => "sdiv",
+        Instruction::URem(_) => "urem",
+        Instruction::SRem(_) => "srem",
+        Instruction::And(_) => "and",
+        Instruction::Or(_) => "or",
+        Instruction::Xor(_) => "xor",
+        Instruction::Shl(_) => "shl",
+        Instruction::LShr(_) => "lshr",
+        Instruction::AShr(_) => "ashr",
+        Instruction::FAdd(_) => "fadd",
+        Instruction::FSub(_) => "fsub",
+        Instruction::FMul(_) => "fmul",
+        Instruction::FDiv(_) => "fdiv",
+        Instruction::FRem(_) => "frem",
+        Instruction::FNeg(_) => "fneg",
+        Instruction::ExtractElement(_) => "extractelement",
+        Instruction::InsertElement(_) => "insertelement",
+        Instruction::ShuffleVector(_) => "shufflevector",
+        Instruction::ExtractValue(_) => "extractvalue",
+        Instruction::InsertValue(_) => "insertvalue",
+        Instruction::Alloca(_) => "alloca",
+        Instruction::Load(_) => "load",
+        Instruction::Store(_) => "store",
+        Instruction::Fence(_) => "fence",
+        Instruction::CmpXchg(_) => "cmpxchg",
+        Instruction::AtomicRMW(_) => "atomicrmw",
+        Instruction::GetElementPtr(_) => "getelementptr",
+        Instruction::Trunc(_) => "trunc",
+        Instruction::ZExt(_) => "zext",
+        Instruction::SExt(_) => "sext",
+        Instruction::FPTrunc(_) => "fptrunc",
+        Instruction::FPExt(_) => "fpext",
+        Instruction::FPToUI(_) => "fptoui",
+        Instruction::FPToSI(_) => "fptosi",
+        Instruction::UIToFP(_) => "uitofp",
+        Instruction::SIToFP(_) => "sitofp",
+        Instruction::PtrToInt(_) => "ptrtoint",
+        Instruction::IntToPtr(_) => "inttoptr",
+        Instruction::BitCast(_) => "bitcast",
+        Instruction::AddrSpaceCast(_) => "addrspacecast",
+        Instruction::ICmp(_) => "icmp",
+        Instruction::FCmp(_) => "fcmp",
+        Instruction::Phi(_) => "phi",
+        Instruction::Select(_) => "select",
+        Instruction::Call(_) => "call",
+        Instruction::VAArg(_) => "va_arg",
+        Instruction::LandingPad(_) => "landingpad",
+        Instruction::CatchPad(_) => "catchpad",
+        Instruction::CleanupPad(_) => "cleanuppad",
+        // only `freeze` remains, in LLVM 10 or greater
+        #[allow(unreachable_patterns)]
+        _ => "freeze",
+    }
+}
+
+/// Get the LLVM opcode of the given terminator, e.g. `"invoke"`, for
+/// reporting unhandled terminators
+fn terminator_kind(term: &Terminator) -> &'static str {
+    match term {
+        Terminator::Ret(_) => "ret",
+        Terminator::Br(_) => "br",
+        Terminator::CondBr(_) => "br",
+        Terminator::Switch(_) => "switch",
+        Terminator::IndirectBr(_) => "indirectbr",
+        Terminator::Invoke(_) => "invoke",
+        Terminator::Resume(_) => "resume",
+        Terminator::Unreachable(_) => "unreachable",
+        Terminator::CleanupRet(_) => "cleanupret",
+        Terminator::CatchRet(_) => "catchret",
+        Terminator::CatchSwitch(_) => "catchswitch",
+        // only `callbr` remains, in LLVM 9 or greater
+        #[allow(unreachable_patterns)]
+        _ => "callbr",
+    }
+}
+
+/// Get the name of the variable the given terminator defines, if any.
+/// (`callbr`, which exists only in LLVM 9 or greater, isn't covered.)
+fn terminator_result(term: &Terminator) -> Option<&Name> {
+    match term {
+        Terminator::Invoke(invoke) => Some(&invoke.result),
+        Terminator::CatchSwitch(cs) => Some(&cs.result),
+        _ => None,
+    }
+}
+
+/// Find the instruction defining the variable with the given name in the given
+/// function, if any
+fn find_definition<'m>(f: &'m Function, name: &Name) -> Option<&'m Instruction> {
+    f.basic_blocks
+        .iter()
+        .flat_map(|bb| bb.instrs.iter())
+        .find(|inst| inst.try_get_result() == Some(name))
+}
+
+/// Get the constant integer address the given pointer was cast from with
+/// `inttoptr`, if any, looking through casts and `getelementptr`s (taking the
+/// address of a `getelementptr` to be that of its base) in the given function
+fn constant_address(f: &Function, ptr: &Operand) -> Option<u64> {
+    let int_value = |constant: &Constant| match constant {
+        Constant::Int { value, .. } => Some(*value),
+        _ => None,
+    };
+    match ptr {
+        Operand::ConstantOperand(cref) => constant_address_of(cref.as_ref()),
+        Operand::LocalOperand { name, .. } => match find_definition(f, name)? {
+            Instruction::IntToPtr(itp) => match &itp.operand {
+                Operand::ConstantOperand(cref) => int_value(cref.as_ref()),
+                _ => None,
+            },
+            Instruction::BitCast(bc) => constant_address(f, &bc.operand),
+            Instruction::AddrSpaceCast(ac) => constant_address(f, &ac.operand),
+            Instruction::GetElementPtr(gep) => constant_address(f, &gep.address),
+            _ => None,
+        },
+        Operand::MetadataOperand => None,
+    }
+}
+
+/// Like `constant_address()`, but for a constant pointer
+fn constant_address_of(ptr: &Constant) -> Option<u64> {
+    match ptr {
+        Constant::IntToPtr(itp) => match itp.operand.as_ref() {
+            Constant::Int { value, .. } => Some(*value),
+            _ => None,
+        },
+        Constant::BitCast(bc) => constant_address_of(&bc.operand),
+        Constant::AddrSpaceCast(ac) => constant_address_of(&ac.operand),
+        Constant::GetElementPtr(gep) => constant_address_of(&gep.address),
+        _ => None,
+    }
+}
+
+/// Get the number of arguments of the call wrapped by the given call of
+/// `llvm.experimental.gc.statepoint`, if it's a constant (as it should be)
+fn statepoint_num_call_args(call: &instruction::Call) -> Option<usize> {
+    match call.arguments.get(3)?.0.as_constant()? {
+        Constant::Int { value, .. } => (*value).try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Can the given instruction change the taint of memory? (If it changes any
+/// taint information at all, that is.)
+fn may_write_memory(inst: &Instruction) -> bool {
+    matches!(inst, Instruction::Store(_) | Instruction::AtomicRMW(_) | Instruction::CmpXchg(_) | Instruction::Call(_))
+}
+
+/// Compute the `TaintedType` of the result of the given instruction, if it
+/// depends only on the `TaintedType`s of its operands (as for arithmetic,
+/// casts between non-pointer types, comparisons, and selects); or `None` for
+/// any other instruction.
+///
+/// `operand_ty` gives the `TaintedType` of an operand, `is_tainted` whether a
+/// `TaintedType` is tainted, `join` the join of two `TaintedType`s (see
+/// `NamedStructs::join()`), `policy` the policy for a class of instructions
+/// (see `Config::propagation_policies`), and `sanitizes` whether an
+/// instruction sanitizes its result (see `sanitizes_result()`). These
+/// instructions have
+/// no other effects, so this can be used on many blocks in parallel (see
+/// `Config::parallel_blocks_threshold`).
+fn local_result_ty(
+    inst: &Instruction,
+    operand_ty: impl Fn(&Operand) -> Result<TaintedType, String>,
+    is_tainted: impl Fn(&TaintedType) -> bool,
+    join: impl Fn(&TaintedType, &TaintedType) -> Result<TaintedType, String>,
+    policy: impl Fn(config::InstructionClass) -> config::PropagationPolicy,
+    sanitizes: impl Fn(&Instruction) -> bool,
+) -> Option<Result<TaintedType, String>> {
+    let result_ty = propagated_result_ty(inst, &operand_ty, &is_tainted, &join)?;
+    let class = match instruction_class(inst) {
+        Some(class) => class,
+        None => return Some(result_ty),
+    };
+    Some(result_ty.and_then(|result_ty| match policy(class) {
+        config::PropagationPolicy::Propagate if sanitizes(inst) => Ok(map_lanes(result_ty, TaintedType::sanitized)),
+        config::PropagationPolicy::Propagate => Ok(result_ty),
+        config::PropagationPolicy::Drop => Ok(map_lanes(result_ty, TaintedType::sanitized)),
+        config::PropagationPolicy::TaintConservatively => {
+            let points_to_taint = |ty: &TaintedType| match ty {
+                TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => is_tainted(&pointee.cloned_ty()),
+                _ => false,
+            };
+            let mut any_points_to_taint = false;
+            for op in instruction_operands(inst) {
+                any_points_to_taint |= match operand_ty(&op)? {
+                    TaintedType::ArrayOrVector(element) => points_to_taint(&element.cloned_ty()),
+                    ty => points_to_taint(&ty),
+                };
+            }
+            Ok(if any_points_to_taint { map_lanes(result_ty, taint_lane) } else { result_ty })
+        },
+    }))
+}
+
+/// Compute the `TaintedType` of the result of the given instruction as for
+/// `local_result_ty()`, before applying `Config::propagation_policies`
+fn propagated_result_ty(
+    inst: &Instruction,
+    operand_ty: impl Fn(&Operand) -> Result<TaintedType, String>,
+    is_tainted: impl Fn(&TaintedType) -> bool,
+    join: impl Fn(&TaintedType, &TaintedType) -> Result<TaintedType, String>,
+) -> Option<Result<TaintedType, String>> {
+    let join_of = |op0: &Operand, op1: &Operand| join(&operand_ty(op0)?, &operand_ty(op1)?);
+    let compare = |op0: &Operand, op1: &Operand| -> Result<TaintedType, String> {
+        let ty0 = operand_ty(op0)?;
+        let result = if is_tainted(&ty0) || is_tainted(&operand_ty(op1)?) {
+            TaintedType::TaintedValue
+        } else {
+            TaintedType::UntaintedValue
+        };
+        // comparing vectors gives a vector of results, one per lane
+        match ty0 {
+            TaintedType::ArrayOrVector(_) => Ok(TaintedType::array_or_vec_of(result)),
+            _ => Ok(result),
+        }
+    };
+    if inst.is_binary_op() {
+        let bop: groups::BinaryOp = inst.clone().try_into().unwrap();
+        return Some(join_of(bop.get_operand0(), bop.get_operand1()));
+    }
+    match inst {
+        // the unary ops which output the same type they input, in our type system
+        Instruction::AddrSpaceCast(_)
+        | Instruction::FNeg(_)
+        | Instruction::FPExt(_)
+        | Instruction::FPToSI(_)
+        | Instruction::FPToUI(_)
+        | Instruction::FPTrunc(_)
+        | Instruction::SExt(_)
+        | Instruction::SIToFP(_)
+        | Instruction::Trunc(_)
+        | Instruction::UIToFP(_)
+        | Instruction::ZExt(_) => {
+            let uop: groups::UnaryOp = inst.clone().try_into().unwrap();
+            Some(operand_ty(uop.get_operand()))
+        },
+        // Vector operands are still scalars in our type system
+        Instruction::ShuffleVector(sv) => Some(join_of(&sv.operand0, &sv.operand1)),
+        Instruction::ICmp(icmp) => Some(compare(&icmp.operand0, &icmp.operand1)),
+        Instruction::FCmp(fcmp) => Some(compare(&fcmp.operand0, &fcmp.operand1)),
+        Instruction::Select(select) => Some(operand_ty(&select.condition).and_then(|cond_ty| {
+            // with a vector of conditions, each lane of the result is chosen
+            // by the corresponding lane of the condition; but all lanes of a
+            // vector share a single `TaintedType`, so if any lane of the
+            // condition is tainted, they all are
+            let cond_tainted = match &cond_ty {
+                TaintedType::TaintedValue => true,
+                TaintedType::UntaintedValue => false,
+                TaintedType::ArrayOrVector(element) => is_tainted(&element.cloned_ty()),
+                _ => return Err(format!("Select: expected a scalar or vector condition; got {}", cond_ty)),
+            };
+            if !cond_tainted {
+                return join_of(&select.true_value, &select.false_value);
+            }
+            match join_of(&select.true_value, &select.false_value)? {
+                TaintedType::ArrayOrVector(element) => Ok(TaintedType::array_or_vec_of(taint_lane(element.ty().clone()))),
+                _ => Ok(TaintedType::TaintedValue),
+            }
+        })),
+        _ => None,
+    }
+}
+
+/// Get the class of the given instruction for `Config::propagation_policies`,
+/// if it has one
+fn instruction_class(inst: &Instruction) -> Option<config::InstructionClass> {
+    use config::InstructionClass;
+    match inst {
+        Instruction::Add(_)
+        | Instruction::Sub(_)
+        | Instruction::Mul(_)
+        | Instruction::And(_)
+        | Instruction::Or(_)
+        | Instruction::Xor(_)
+        | Instruction::FAdd(_)
+        | Instruction::FSub(_)
+        | Instruction::FMul(_)
+        | Instruction::FNeg(_) => Some(InstructionClass::Arithmetic),
+        Instruction::UDiv(_)
+        | Instruction::SDiv(_)
+        | Instruction::URem(_)
+        | Instruction::SRem(_)
+        | Instruction::FDiv(_)
+        | Instruction::FRem(_) => Some(InstructionClass::Division),
+        Instruction::Shl(_) | Instruction::LShr(_) | Instruction::AShr(_) => Some(InstructionClass::Shift),
+        Instruction::Trunc(_)
+        | Instruction::ZExt(_)
+        | Instruction::SExt(_)
+        | Instruction::FPTrunc(_)
+        | Instruction::FPExt(_)
+        | Instruction::FPToUI(_)
+        | Instruction::FPToSI(_)
+        | Instruction::UIToFP(_)
+        | Instruction::SIToFP(_) => Some(InstructionClass::Cast),
+        Instruction::ICmp(_) | Instruction::FCmp(_) => Some(InstructionClass::Comparison),
+        _ => None,
+    }
+}
+
+/// Get the width of the given type, if it's an integer type of at most 64
+/// bits (see `Config::bit_level_taint`)
+fn int_width(ty: &Type) -> Option<u32> {
+    match ty {
+        Type::IntegerType { bits } if *bits <= 64 => Some(*bits),
+        _ => None,
+    }
+}
+
+/// Get the mask of the low `width` bits
+fn low_bits(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Get the value of the given operand, if it's an integer constant
+fn const_int(op: &Operand) -> Option<u64> {
+    match op {
+        Operand::ConstantOperand(cref) => match cref.as_ref() {
+            Constant::Int { value, .. } => Some(*value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Is the result of the given instruction untainted regardless of its
+/// operands, according to `Config::sanitizing_mask_max`,
+/// `Config::sanitizing_trunc_max_bits`, or `Config::tainting_predicates`?
+/// (Whether the instruction's policy allows this is up to the caller.)
+fn sanitizes_result(inst: &Instruction, config: &Config) -> bool {
+    let tainting_predicate = |predicate: config::ComparisonPredicate| match &config.tainting_predicates {
+        Some(predicates) => predicates.contains(&predicate),
+        None => true,
+    };
+    match (inst, config.sanitizing_mask_max, config.sanitizing_trunc_max_bits) {
+        (Instruction::ICmp(icmp), _, _) => !tainting_predicate(config::ComparisonPredicate::Int(icmp.predicate)),
+        (Instruction::FCmp(fcmp), _, _) => !tainting_predicate(config::ComparisonPredicate::FP(fcmp.predicate)),
+        (Instruction::And(and), Some(max), _) => {
+            const_int(&and.operand0).or_else(|| const_int(&and.operand1)).is_some_and(|mask| mask <= max)
+        },
+        (Instruction::Trunc(trunc), _, Some(max_bits)) => match trunc.to_type.as_ref() {
+            Type::IntegerType { bits } => *bits <= max_bits,
+            Type::VectorType { element_type, .. } => matches!(element_type.as_ref(), Type::IntegerType { bits } if *bits <= max_bits),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Does `Config::bit_level_taint` track bits through the given instruction
+/// (if its result is an integer of at most 64 bits)?
+fn tracks_bits(inst: &Instruction) -> bool {
+    matches!(
+        inst,
+        Instruction::And(_)
+            | Instruction::Or(_)
+            | Instruction::Xor(_)
+            | Instruction::Shl(_)
+            | Instruction::LShr(_)
+            | Instruction::AShr(_)
+            | Instruction::Add(_)
+            | Instruction::Sub(_)
+            | Instruction::Mul(_)
+            | Instruction::Trunc(_)
+            | Instruction::ZExt(_)
+            | Instruction::SExt(_)
+    )
+}
+
+/// Compute which bits of the result of the given instruction, in the given
+/// module, may be tainted (see `Config::bit_level_taint`), as a mask, given
+/// `operand_bits` which computes the same for an integer operand. Returns
+/// `None` if we don't track bits through the instruction.
+fn tainted_result_bits(
+    inst: &Instruction,
+    module: &Module,
+    operand_bits: impl Fn(&Operand) -> Result<u64, String>,
+) -> Result<Option<u64>, String> {
+    if !tracks_bits(inst) {
+        return Ok(None);
+    }
+    let width = match int_width(&module.type_of(inst)) {
+        Some(width) => width,
+        None => return Ok(None),
+    };
+    let all = low_bits(width);
+    // the bits from the lowest tainted bit up, which carries (or an unknown
+    // left shift) can reach
+    let from_lowest = |bits: u64| if bits == 0 { 0 } else { all & !((1 << bits.trailing_zeros()) - 1) };
+    // the bits from the highest tainted bit down, which an unknown right
+    // shift can reach
+    let to_highest = |bits: u64| if bits == 0 { 0 } else { low_bits(64 - bits.leading_zeros()) };
+    let sign = 1 << (width - 1);
+    let bits = match inst {
+        Instruction::And(and) => {
+            let (x, y) = (operand_bits(&and.operand0)?, operand_bits(&and.operand1)?);
+            // bits cleared by a constant mask are zero, whatever the other
+            // operand's bits
+            match (const_int(&and.operand0), const_int(&and.operand1)) {
+                (_, Some(mask)) => x & mask,
+                (Some(mask), _) => y & mask,
+                _ => x | y,
+            }
+        },
+        Instruction::Or(or) => {
+            let (x, y) = (operand_bits(&or.operand0)?, operand_bits(&or.operand1)?);
+            // and bits set by a constant are one
+            match (const_int(&or.operand0), const_int(&or.operand1)) {
+                (_, Some(mask)) => x & !mask,
+                (Some(mask), _) => y & !mask,
+                _ => x | y,
+            }
+        },
+        Instruction::Xor(xor) => operand_bits(&xor.operand0)? | operand_bits(&xor.operand1)?,
+        Instruction::Shl(shl) => {
+            let (x, amount) = (operand_bits(&shl.operand0)?, operand_bits(&shl.operand1)?);
+            match const_int(&shl.operand1) {
+                Some(shift) if shift < u64::from(width) => (x << shift) & all,
+                _ if amount != 0 => all,
+                _ => from_lowest(x),
+            }
+        },
+        Instruction::LShr(lshr) => {
+            let (x, amount) = (operand_bits(&lshr.operand0)?, operand_bits(&lshr.operand1)?);
+            match const_int(&lshr.operand1) {
+                Some(shift) if shift < u64::from(width) => x >> shift,
+                _ if amount != 0 => all,
+                _ => to_highest(x),
+            }
+        },
+        Instruction::AShr(ashr) => {
+            let (x, amount) = (operand_bits(&ashr.operand0)?, operand_bits(&ashr.operand1)?);
+            match const_int(&ashr.operand1) {
+                // the sign bit is copied into the bits vacated at the top
+                Some(shift) if shift < u64::from(width) => {
+                    let vacated = all & !(all >> shift);
+                    (x >> shift) | if x & sign != 0 { vacated } else { 0 }
+                },
+                _ if amount != 0 || x & sign != 0 => all,
+                _ => to_highest(x),
+            }
+        },
+        Instruction::Add(add) => from_lowest(operand_bits(&add.operand0)? | operand_bits(&add.operand1)?),
+        Instruction::Sub(sub) => from_lowest(operand_bits(&sub.operand0)? | operand_bits(&sub.operand1)?),
+        Instruction::Mul(mul) => from_lowest(operand_bits(&mul.operand0)? | operand_bits(&mul.operand1)?),
+        Instruction::Trunc(trunc) => operand_bits(&trunc.operand)? & all,
+        Instruction::ZExt(zext) => operand_bits(&zext.operand)?,
+        Instruction::SExt(sext) => {
+            let x = operand_bits(&sext.operand)?;
+            match int_width(&module.type_of(&sext.operand)) {
+                // the sign bit is copied into the new bits
+                Some(from_width) if x & (1 << (from_width - 1)) != 0 => x | (all & !low_bits(from_width)),
+                Some(_) => x,
+                None => return Ok(None),
+            }
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(bits))
+}
+
+/// Apply `f` to the given type, or if it's a vector, to its lanes
+fn map_lanes(ty: TaintedType, f: impl Fn(TaintedType) -> TaintedType) -> TaintedType {
+    match ty {
+        TaintedType::ArrayOrVector(element) => TaintedType::array_or_vec_of(f(element.ty().clone())),
+        ty => f(ty),
+    }
+}
+
+/// Taint one lane of a vector selected under a tainted condition: a pointer
+/// becomes a tainted pointer to the same data, and anything else becomes
+/// tainted
+fn taint_lane(ty: TaintedType) -> TaintedType {
+    match ty {
+        TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => TaintedType::TaintedPointer(pointee),
+        TaintedType::UntaintedFnPtr(targets) | TaintedType::TaintedFnPtr(targets) => TaintedType::TaintedFnPtr(targets),
+        _ => TaintedType::TaintedValue,
+    }
+}
+
+/// Is the given instruction handled by `local_result_ty()`?
+fn is_local_instruction(inst: &Instruction) -> bool {
+    local_result_ty(inst, |_| Ok(TaintedType::UntaintedValue), |_| false, |ty1, ty2| ty1.join(ty2), |_| config::PropagationPolicy::Propagate, |_| false).is_some()
+}
+
+/// Compute the `TaintedType`s of the results of the instructions in the given
+/// basic block, which must all be handled by `local_result_ty()`, without
+/// changing the given `FunctionTaintState`. See
+/// `TaintState::process_blocks_in_parallel()`.
+fn local_block_result_tys<'m>(
+    bb: &BasicBlock,
+    cur_fn: &FunctionTaintState<'m>,
+    named_structs: &RwLock<NamedStructs<'m>>,
+    cur_fn_name: &'m str,
+    config: &Config,
+) -> Result<Vec<TaintedType>, String> {
+    // types of the values defined so far in this block, as they'll be once
+    // the results are applied
+    let mut block_tys: HashMap<&Name, TaintedType> = HashMap::new();
+    let mut result_tys = Vec::with_capacity(bb.instrs.len());
+    for inst in &bb.instrs {
+        // there's one result per instruction so far
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("instruction", kind = instruction_kind(inst), block = %bb.name, index = result_tys.len()).entered();
+        let operand_ty = |op: &Operand| match op {
+            Operand::LocalOperand { name, .. } if block_tys.contains_key(name) => Ok(block_tys[name].clone()),
+            _ => cur_fn.get_type_of_operand(op),
+        };
+        let is_tainted = |ty: &TaintedType| named_structs.write().unwrap().is_type_tainted(ty, cur_fn_name);
+        let join = |ty1: &TaintedType, ty2: &TaintedType| named_structs.write().unwrap().join(ty1, ty2, cur_fn_name);
+        let result_ty = local_result_ty(inst, operand_ty, is_tainted, join, |class| config.propagation_policy(class), |inst| sanitizes_result(inst, config))
+            .expect("blocks processed in parallel should contain only instructions handled by local_result_ty()")
+            .map_err(|e| format!("Encountered this error:\n  {}\nwhile processing this instruction:\n  {:?}", e, inst))?;
+        let dest = inst.try_get_result().expect("instructions handled by local_result_ty() have results");
+        // like `update_var_taintedtype()`, join with the current type
+        let dest_ty = match cur_fn.get_taint_map().get(dest) {
+            Some(ty) => named_structs.write().unwrap().join(ty, &result_ty, cur_fn_name)?,
+            None => result_ty.clone(),
+        };
+        block_tys.insert(dest, dest_ty);
+        result_tys.push(result_ty);
+    }
+    Ok(result_tys)
+}
+
+/// Partition the basic blocks of the given function into levels, for
+/// `Config::parallel_blocks_threshold`: each block goes in the level after
+/// the last level containing an earlier block which defines a value it uses.
+/// (Uses of values defined in later blocks, e.g. around loops, see those
+/// values as of the previous pass, just as when processing the blocks in
+/// order.) Blocks within each level are in the order they appear in the
+/// function.
+fn block_levels(f: &Function) -> Vec<Vec<&BasicBlock>> {
+    let defining_block: HashMap<&Name, usize> = f.basic_blocks
+        .iter()
+        .enumerate()
+        .flat_map(|(index, bb)| bb.instrs.iter().filter_map(move |inst| inst.try_get_result().map(|name| (name, index))))
+        .collect();
+    let mut block_levels: Vec<usize> = Vec::with_capacity(f.basic_blocks.len());
+    let mut levels: Vec<Vec<&BasicBlock>> = Vec::new();
+    for (index, bb) in f.basic_blocks.iter().enumerate() {
+        let level = bb.instrs
+            .iter()
+            .flat_map(instruction_operands)
+            .chain(terminator_operands(&bb.term))
+            .filter_map(|op| match op {
+                Operand::LocalOperand { name, .. } => defining_block.get(&name).copied(),
+                _ => None,
+            })
+            .filter(|&def_index| def_index < index)
+            .map(|def_index| block_levels[def_index] + 1)
+            .max()
+            .unwrap_or(0);
+        block_levels.push(level);
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(bb);
+    }
+    levels
+}
+
+/// Intrinsics which we handle directly, rather than as calls to external
+/// functions
+enum Intrinsic {
+    /// `llvm.memset` and its variants
+    Memset,
+    /// `llvm.memcpy`, `llvm.memmove`, and their variants
+    Memcpy,
+    /// `llvm.va_start`
+    VaStart,
+    /// `llvm.va_copy`
+    VaCopy,
+    /// `llvm.va_end`
+    VaEnd,
+    /// Bit-manipulation intrinsics whose result has the same taint as their
+    /// (first) operand: `llvm.bswap`, `llvm.ctpop`, `llvm.ctlz`, `llvm.cttz`,
+    /// `llvm.bitreverse`, and `llvm.abs`; and `llvm.matrix.transpose`, since
+    /// all elements of a vector share one `TaintedType`
+    Passthrough,
+    /// `llvm.fshl` and `llvm.fshr`, whose result is the join of all three
+    /// operands
+    FunnelShift,
+    /// `llvm.matrix.multiply`, whose result is entirely tainted if either
+    /// matrix is tainted at all
+    MatrixMultiply,
+    /// `llvm.matrix.column.major.load`
+    MatrixLoad,
+    /// `llvm.matrix.column.major.store`
+    MatrixStore,
+    /// `llvm.coro.*`
+    Coroutine,
+    /// `llvm.experimental.gc.statepoint`, `llvm.experimental.gc.result`, and
+    /// `llvm.experimental.gc.relocate`
+    GcStatepoint,
+    /// `llvm.read_register` and `llvm.read_volatile_register`
+    ReadRegister,
+    /// `llvm.write_register`
+    WriteRegister,
+}
+
+impl Intrinsic {
+    /// Classify the intrinsic with the given name, which may include the type
+    /// suffix (e.g., `llvm.memcpy.p0i8.p0i8.i64`).
+    ///
+    /// This covers the `.inline` (e.g., `llvm.memcpy.inline`) and element-wise
+    /// atomic (e.g., `llvm.memcpy.element.unordered.atomic`) variants, since
+    /// they have the same destination and source/value arguments as the plain
+    /// intrinsics.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.strip_prefix("llvm.")?.split('.').next()? {
+            "memset" => Some(Intrinsic::Memset),
+            "memcpy" | "memmove" => Some(Intrinsic::Memcpy),
+            "va_start" => Some(Intrinsic::VaStart),
+            "va_copy" => Some(Intrinsic::VaCopy),
+            "va_end" => Some(Intrinsic::VaEnd),
+            "bswap" | "ctpop" | "ctlz" | "cttz" | "bitreverse" | "abs" => Some(Intrinsic::Passthrough),
+            "fshl" | "fshr" => Some(Intrinsic::FunnelShift),
+            "coro" => Some(Intrinsic::Coroutine),
+            "matrix" => {
+                let name = name.strip_prefix("llvm.matrix.")?;
+                if name.starts_with("transpose.") {
+                    Some(Intrinsic::Passthrough)
+                } else if name.starts_with("multiply.") {
+                    Some(Intrinsic::MatrixMultiply)
+                } else if name.starts_with("column.major.load.") {
+                    Some(Intrinsic::MatrixLoad)
+                } else if name.starts_with("column.major.store.") {
+                    Some(Intrinsic::MatrixStore)
+                } else {
+                    None
+                }
+            },
+            "experimental" => match name.strip_prefix("llvm.experimental.gc.")?.split('.').next()? {
+                "statepoint" | "result" | "relocate" => Some(Intrinsic::GcStatepoint),
+                _ => None,
+            },
+            "read_register" | "read_volatile_register" => Some(Intrinsic::ReadRegister),
+            "write_register" => Some(Intrinsic::WriteRegister),
+            _ => None,
+        }
+    }
+}
+
+/// Join the taint maps and summaries of all clones of functions (see
+/// `FunctionClones`) into those of the functions they're clones of,
+/// so that each function's results cover all of its calls
+fn merge_clones<'m>(
+    clones: &FunctionClones<'m>,
+    fn_taint_states: &mut HashMap<&'m str, FunctionTaintState<'m>>,
+    fn_summaries: &mut HashMap<&'m str, FunctionSummary<'m>>,
+) {
+    let mut clones: Vec<(&'m str, &'m str)> = clones
+        .all_clones()
+        .map(|(name, context)| (name, context.function))
+        .collect();
+    clones.sort_unstable();
+    for (clone, function) in clones {
+        if let Some(clone_fts) = fn_taint_states.remove(clone) {
+            match fn_taint_states.entry(function) {
+                Entry::Vacant(ventry) => {
+                    ventry.insert(clone_fts);
+                },
+                Entry::Occupied(oentry) => {
+                    oentry.into_mut().join_with(&clone_fts).unwrap_or_else(|e| panic!("Merging clone {:?} into function {:?}: {}", clone, function, e));
+                },
+            }
+        }
+        if let Some(clone_summary) = fn_summaries.remove(clone) {
+            match fn_summaries.entry(function) {
+                Entry::Vacant(ventry) => {
+                    ventry.insert(clone_summary);
+                },
+                Entry::Occupied(oentry) => {
+                    oentry.into_mut().join_with(&clone_summary).unwrap_or_else(|e| panic!("Merging clone {:?} into function {:?}: {}", clone, function, e));
+                },
+            }
+        }
+    }
+}
+
+/// Get the names of the functions reachable in the call graph from the given
+/// functions (including those functions themselves), which are defined in the
+/// analyzed module(s)
+fn reachable_functions<'m>(
+    analysis: &CrossModuleAnalysis<'m>,
+    call_graph: &CallGraph<'m>,
+    roots: impl IntoIterator<Item = &'m str>,
+) -> HashSet<&'m str> {
+    let mut reachable: HashSet<&'m str> = roots.into_iter().collect();
+    let mut queue: Vec<&'m str> = reachable.iter().copied().collect();
+    while let Some(f) = queue.pop() {
+        for callee in call_graph.callees(f) {
+            if reachable.insert(callee) {
+                queue.push(callee);
+            }
+        }
+    }
+    reachable.retain(|f| analysis.get_func_by_name(f).is_some());
+    reachable
+}
+
+/// Sort the given function names, for deterministic checkpoints
+fn sorted_fn_names(fn_names: &HashSet<&str>) -> Vec<String> {
+    let mut fn_names: Vec<String> = fn_names.iter().map(|name| name.to_string()).collect();
+    fn_names.sort();
+    fn_names
+}
@@ -1,9 +1,9 @@
 use crate::named_structs::NamedStructs;
 use crate::pointee::Pointee;
 use llvm_ir::Type;
-use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock};
 
 /// The type system which we use for taint-tracking
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -29,13 +29,28 @@ pub enum TaintedType {
     /// struct's contents, use `get_named_struct_type` in the `TaintState` or
     /// `TaintResult`. (This avoids infinite recursion in `TaintedType`.)
     NamedStruct(String),
-    /// An untainted function pointer
-    UntaintedFnPtr,
-    /// A tainted function pointer
-    TaintedFnPtr,
+    /// An untainted function pointer, to any of the given functions
+    UntaintedFnPtr(FnTargets),
+    /// A tainted function pointer, to any of the given functions
+    TaintedFnPtr(FnTargets),
+    /// A `token` value, e.g. from a GC statepoint, a constrained floating-point
+    /// intrinsic, or a coroutine intrinsic. Tokens are opaque and carry no
+    /// data, so they're never tainted.
+    Token,
 }
 
+/// Names of the functions a function pointer may point to, as far as we know:
+/// the functions whose addresses were taken to produce it. This is empty for
+/// function pointers we know nothing about, e.g. those produced outside the
+/// analyzed code, or derived from an LLVM type.
+pub type FnTargets = BTreeSet<String>;
+
 impl TaintedType {
+    /// Create an untainted pointer to the function with the given name
+    pub fn fn_ptr_to(fn_name: impl Into<String>) -> Self {
+        Self::UntaintedFnPtr(std::iter::once(fn_name.into()).collect())
+    }
+
     /// Create a (fresh, unaliased) pointer to the given `TaintedType`
     pub fn untainted_ptr_to(pointee: TaintedType) -> Self {
         Self::UntaintedPointer(Pointee::new(pointee))
@@ -73,7 +88,7 @@ impl TaintedType {
         Self::Struct(
             elements
                 .into_iter()
-                .map(|ty| Pointee::new(ty))
+                .map(Pointee::new)
                 .collect(),
         )
     }
@@ -86,30 +101,49 @@ impl TaintedType {
     /// Produce the equivalent (untainted) `TaintedType` for a given LLVM type.
     /// Pointers will point to fresh `TaintedType`s to represent their element
     /// types; they will be assumed not to point to existing variables.
+    ///
+    /// Target-specific types we don't model (e.g., `x86_amx`) are treated as
+    /// opaque scalars, which are tainted or untainted as a whole.
     pub fn from_llvm_type(llvm_ty: &Type) -> Self {
+        Self::from_llvm_type_with_aliases(llvm_ty, &HashMap::new())
+    }
+
+    /// Like `from_llvm_type()`, but named structs whose names are in
+    /// `struct_aliases` are referred to by the names they map to (see
+    /// `Config::unify_renamed_structs`)
+    pub(crate) fn from_llvm_type_with_aliases(llvm_ty: &Type, struct_aliases: &HashMap<String, String>) -> Self {
         match llvm_ty {
             Type::IntegerType { .. } => TaintedType::UntaintedValue,
             Type::PointerType { pointee_type, .. } => {
                 match pointee_type.as_ref() {
-                    Type::FuncType { .. } => TaintedType::UntaintedFnPtr,
-                    _ => TaintedType::untainted_ptr_to(TaintedType::from_llvm_type(&pointee_type))
+                    Type::FuncType { .. } => TaintedType::UntaintedFnPtr(FnTargets::new()),
+                    _ => TaintedType::untainted_ptr_to(TaintedType::from_llvm_type_with_aliases(pointee_type, struct_aliases))
                 }
             },
             Type::FPType(_) => TaintedType::UntaintedValue,
             Type::ArrayType { element_type, .. }
             | Type::VectorType { element_type, .. } => {
-                TaintedType::array_or_vec_of(TaintedType::from_llvm_type(&element_type))
+                TaintedType::array_or_vec_of(TaintedType::from_llvm_type_with_aliases(element_type, struct_aliases))
             },
             Type::StructType { element_types, .. } => {
-                TaintedType::struct_of(element_types.iter().map(|ty| TaintedType::from_llvm_type(ty)))
+                TaintedType::struct_of(element_types.iter().map(|ty| TaintedType::from_llvm_type_with_aliases(ty, struct_aliases)))
             },
-            Type::NamedStructType { name } => TaintedType::NamedStruct(name.into()),
+            Type::NamedStructType { name } => TaintedType::NamedStruct(struct_aliases.get(name).unwrap_or(name).into()),
             Type::X86_MMXType => TaintedType::UntaintedValue,
             Type::MetadataType => TaintedType::UntaintedValue,
-            _ => unimplemented!("TaintedType::from_llvm_type on {:?}", llvm_ty),
+            Type::TokenType => TaintedType::Token,
+            Type::VoidType | Type::FuncType { .. } | Type::LabelType => unimplemented!("TaintedType::from_llvm_type on {:?}", llvm_ty),
+            _ => TaintedType::UntaintedValue,
         }
     }
 
+    /// Produce the `TaintedType` for a value of the given LLVM type which is
+    /// entirely tainted, along with everything reachable through it (other
+    /// than named structs, whose types are shared by the whole program).
+    pub fn fully_tainted_from_llvm_type(llvm_ty: &Type) -> Self {
+        crate::transfer::fully_tainted(llvm_ty)
+    }
+
     /// Is this type tainted?
     ///
     /// This function panics on named struct types. (It does work on array,
@@ -125,8 +159,9 @@ impl TaintedType {
             TaintedType::TaintedValue => true,
             TaintedType::UntaintedPointer(_) => false,
             TaintedType::TaintedPointer(_) => true,
-            TaintedType::UntaintedFnPtr => false,
-            TaintedType::TaintedFnPtr => true,
+            TaintedType::UntaintedFnPtr(_) => false,
+            TaintedType::TaintedFnPtr(_) => true,
+            TaintedType::Token => false,
             TaintedType::ArrayOrVector(pointee) => pointee.ty().is_tainted_nonamedstruct(),
             TaintedType::Struct(elements) => {
                 // a struct is tainted if any of its elements are
@@ -142,17 +177,17 @@ impl TaintedType {
     ///
     /// Alternately you can also use `TaintState::is_type_tainted()` or
     /// `TaintResult::is_type_tainted()`.
-    pub fn is_tainted<'m>(&self, named_structs: Rc<RefCell<NamedStructs<'m>>>, cur_fn: &'m str) -> bool {
+    pub fn is_tainted<'m>(&self, named_structs: Arc<RwLock<NamedStructs<'m>>>, cur_fn: &'m str) -> bool {
         match self {
             TaintedType::NamedStruct(name) => {
-                let mut named_structs = named_structs.borrow_mut();
+                let mut named_structs = named_structs.write().unwrap();
                 let structty = named_structs.get_named_struct_type(name.clone(), cur_fn);
                 structty.is_tainted_nonamedstruct()
             },
-            TaintedType::ArrayOrVector(pointee) => pointee.ty().is_tainted(named_structs, cur_fn),
+            TaintedType::ArrayOrVector(pointee) => pointee.cloned_ty().is_tainted(named_structs, cur_fn),
             TaintedType::Struct(elements) => {
                 // a struct is tainted if any of its elements are
-                elements.iter().any(|p| p.ty().is_tainted(named_structs.clone(), cur_fn))
+                elements.iter().any(|p| p.cloned_ty().is_tainted(named_structs.clone(), cur_fn))
             },
             _ => self.is_tainted_nonamedstruct(),
         }
@@ -162,34 +197,90 @@ impl TaintedType {
     ///
     /// Recurses into structs/arrays/vectors, but not pointers:
     /// - if this pointer points to a struct/array/vector, we will taint all
-    /// elements of that struct/array/vector
+    ///   elements of that struct/array/vector
     /// - if this pointer points to another pointer P, we will taint P, but not
-    /// things pointed to by P. Of course, if
-    /// `config.dereferencing_tainted_ptr_gives_tainted` is `true`, then things
-    /// pointed to by P will also become tainted if they are ever loaded through
-    /// P.
+    ///   things pointed to by P. Of course, if
+    ///   `config.dereferencing_tainted_ptr_gives_tainted` is `true`, then things
+    ///   pointed to by P will also become tainted if they are ever loaded
+    ///   through P.
     pub fn taint_contents<'m>(&self, named_structs: &mut NamedStructs<'m>) {
         match self {
             TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => {
                 pointee.taint(named_structs);
             },
-            TaintedType::UntaintedFnPtr | TaintedType::TaintedFnPtr => {
+            TaintedType::UntaintedFnPtr(_) | TaintedType::TaintedFnPtr(_) => {
                 panic!("taint_contents on a function pointer")
             },
             _ => panic!("taint_contents: not a pointer: {}", self),
         }
     }
 
+    /// Get the untainted version of this type, shallowly: for instance, a
+    /// tainted pointer becomes an untainted pointer to the same `Pointee`, whose
+    /// contents are unchanged. Arrays, vectors, and structs are returned as-is.
+    pub(crate) fn sanitized(self) -> Self {
+        match self {
+            TaintedType::TaintedValue => TaintedType::UntaintedValue,
+            TaintedType::TaintedPointer(pointee) => TaintedType::UntaintedPointer(pointee),
+            TaintedType::TaintedFnPtr(targets) => TaintedType::UntaintedFnPtr(targets),
+            ty => ty,
+        }
+    }
+
+    /// If this is a function pointer and `from` is a function pointer (e.g.,
+    /// this is the type of a cast of `from` to another function type), get
+    /// this with the targets of `from`; otherwise get this as-is
+    pub(crate) fn with_fn_targets_of(self, from: &Self) -> Self {
+        match (self, from) {
+            (TaintedType::UntaintedFnPtr(_), TaintedType::UntaintedFnPtr(targets) | TaintedType::TaintedFnPtr(targets)) => TaintedType::UntaintedFnPtr(targets.clone()),
+            (TaintedType::TaintedFnPtr(_), TaintedType::UntaintedFnPtr(targets) | TaintedType::TaintedFnPtr(targets)) => TaintedType::TaintedFnPtr(targets.clone()),
+            (ty, _) => ty,
+        }
+    }
+
+    /// Refer to the named structs in this type (including in the data it
+    /// points to) whose names are in `struct_aliases` by the names they map
+    /// to, in place (see `Config::unify_renamed_structs`)
+    pub(crate) fn rename_named_structs(&mut self, struct_aliases: &HashMap<String, String>) {
+        match self {
+            TaintedType::NamedStruct(name) => {
+                if let Some(canonical) = struct_aliases.get(name) {
+                    *name = canonical.clone();
+                }
+            },
+            TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) | TaintedType::ArrayOrVector(pointee) => {
+                pointee.rename_named_structs(struct_aliases);
+            },
+            TaintedType::Struct(elements) => {
+                for element in elements {
+                    element.rename_named_structs(struct_aliases);
+                }
+            },
+            _ => {},
+        }
+    }
+
     /// Compute the join of two `TaintedType`s. For instance, joining a tainted
     /// and an untainted produces a tainted; joining a type with itself produces
     /// itself back.
     ///
     /// Joining two pointers will produce a fresh pointer to the join of their
     /// elements; we'll assume that the join of their elements hadn't been
-    /// created yet.
+    /// created yet. The exception is pointers whose `Pointee`s are already
+    /// linked (e.g., the same pointer computed on two passes over a
+    /// function): those keep their `Pointee`, so that they stay linked to it,
+    /// and to the named struct or global it belongs to.
     pub(crate) fn join(&self, other: &Self) -> Result<Self, String> {
         use TaintedType::*;
+        let linked = |pointee1: &Pointee, pointee2: &Pointee| pointee1.as_ptr() == pointee2.as_ptr();
         match (self, other) {
+            (UntaintedPointer(pointee1), UntaintedPointer(pointee2)) if linked(pointee1, pointee2) => Ok(self.clone()),
+            (UntaintedPointer(pointee1), TaintedPointer(pointee2))
+            | (TaintedPointer(pointee1), UntaintedPointer(pointee2))
+            | (TaintedPointer(pointee1), TaintedPointer(pointee2)) if linked(pointee1, pointee2) => Ok(TaintedPointer(pointee1.clone())),
+            (ArrayOrVector(element1), ArrayOrVector(element2)) if linked(element1, element2) => Ok(self.clone()),
+            (Struct(elements1), Struct(elements2))
+                if elements1.len() == elements2.len() && elements1.iter().zip(elements2.iter()).all(|(el1, el2)| linked(el1, el2)) => Ok(self.clone()),
             (UntaintedValue, UntaintedValue) => Ok(UntaintedValue),
             (UntaintedValue, TaintedValue) => Ok(TaintedValue),
             (TaintedValue, UntaintedValue) => Ok(TaintedValue),
@@ -222,8 +313,7 @@ impl TaintedType {
                             .iter()
                             .zip(elements2.iter())
                             .map(|(el1, el2)| el1.ty().join(&el2.ty()))
-                            .collect::<Result<Vec<_>, String>>()?
-                            .into_iter(),
+                            .collect::<Result<Vec<_>, String>>()?,
                     ))
                 }
             },
@@ -234,22 +324,70 @@ impl TaintedType {
                     Err(format!("join: type mismatch: struct named {:?} vs. struct named {:?}", name1, name2))
                 }
             },
-            (UntaintedFnPtr, UntaintedFnPtr) => Ok(UntaintedFnPtr),
-            (UntaintedFnPtr, TaintedFnPtr) => Ok(TaintedFnPtr),
-            (TaintedFnPtr, UntaintedFnPtr) => Ok(TaintedFnPtr),
-            (TaintedFnPtr, TaintedFnPtr) => Ok(TaintedFnPtr),
+            (UntaintedFnPtr(targets1), UntaintedFnPtr(targets2)) => Ok(UntaintedFnPtr(targets1 | targets2)),
+            (UntaintedFnPtr(targets1), TaintedFnPtr(targets2))
+            | (TaintedFnPtr(targets1), UntaintedFnPtr(targets2))
+            | (TaintedFnPtr(targets1), TaintedFnPtr(targets2)) => Ok(TaintedFnPtr(targets1 | targets2)),
+            (Token, Token) => Ok(Token),
             _ => Err(format!("join: type mismatch: {} vs. {}", self, other)),
         }
     }
 }
 
+impl TaintedType {
+    /// Get an object which displays this type like its `Display`
+    /// implementation, except that anything nested more than `max_depth`
+    /// pointers/arrays/structs deep is abbreviated as `...`.
+    pub fn abbreviated(&self, max_depth: usize) -> impl fmt::Display + '_ {
+        AbbreviatedTaintedType { ty: self, max_depth }
+    }
+}
+
+struct AbbreviatedTaintedType<'a> {
+    ty: &'a TaintedType,
+    max_depth: usize,
+}
+
+impl<'a> fmt::Display for AbbreviatedTaintedType<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nested = |ty: &TaintedType| AbbreviatedTaintedType {
+            ty,
+            max_depth: self.max_depth.saturating_sub(1),
+        }.to_string();
+        match self.ty {
+            TaintedType::UntaintedPointer(_)
+            | TaintedType::TaintedPointer(_)
+            | TaintedType::ArrayOrVector(_)
+            | TaintedType::Struct(_) if self.max_depth == 0 => write!(f, "..."),
+            TaintedType::UntaintedPointer(p) => {
+                write!(f, "(UntaintedPointer to {})", nested(&p.ty()))
+            },
+            TaintedType::TaintedPointer(p) => {
+                write!(f, "(TaintedPointer to {})", nested(&p.ty()))
+            },
+            TaintedType::ArrayOrVector(p) => {
+                write!(f, "(ArrayOrVector of {})", nested(&p.ty()))
+            },
+            TaintedType::Struct(elements) => {
+                write!(f, "(Struct of {{")?;
+                for element in elements {
+                    write!(f, "{}, ", nested(&element.ty()))?;
+                }
+                write!(f, "}})")
+            },
+            ty => write!(f, "{}", ty),
+        }
+    }
+}
+
 impl fmt::Display for TaintedType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TaintedType::UntaintedValue => write!(f, "UntaintedValue"),
             TaintedType::TaintedValue => write!(f, "TaintedValue"),
-            TaintedType::UntaintedFnPtr => write!(f, "UntaintedFnPtr"),
-            TaintedType::TaintedFnPtr => write!(f, "TaintedFnPtr"),
+            TaintedType::UntaintedFnPtr(targets) => write!(f, "UntaintedFnPtr{}", DisplayFnTargets(targets)),
+            TaintedType::TaintedFnPtr(targets) => write!(f, "TaintedFnPtr{}", DisplayFnTargets(targets)),
+            TaintedType::Token => write!(f, "Token"),
             TaintedType::UntaintedPointer(p) => {
                 write!(f, "(UntaintedPointer to {})", p)
             },
@@ -272,3 +410,17 @@ impl fmt::Display for TaintedType {
         }
     }
 }
+
+/// Displays the known targets of a function pointer, if any, as a suffix for
+/// its `TaintedType`
+struct DisplayFnTargets<'a>(&'a FnTargets);
+
+impl<'a> fmt::Display for DisplayFnTargets<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let targets: Vec<String> = self.0.iter().map(|target| format!("@{}", target)).collect();
+        write!(f, " to {{{}}}", targets.join(", "))
+    }
+}
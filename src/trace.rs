@@ -0,0 +1,52 @@
+use crate::function_taint_state::FunctionTaintState;
+use crate::named_structs::NamedStructs;
+use crate::tainted_type::TaintedType;
+use llvm_ir::Operand;
+#[cfg(not(feature = "tracing"))]
+use log::warn;
+#[cfg(feature = "tracing")]
+use tracing::warn;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::sync::Mutex;
+
+/// Write one event to the propagation trace, as a single line of JSON.
+///
+/// Failures to write are logged, but otherwise ignored: a broken trace
+/// shouldn't abort the analysis.
+pub(crate) fn write_event(trace: &Mutex<Box<dyn Write + Send>>, event: &Value) {
+    let mut trace = trace.lock().unwrap();
+    if let Err(e) = writeln!(trace, "{}", event) {
+        warn!("Failed to write propagation trace event: {}", e);
+    }
+}
+
+/// Describe a `TaintedType` for the propagation trace
+pub(crate) fn type_entry<'m>(ty: &TaintedType, named_structs: &mut NamedStructs<'m>, cur_fn: &'m str) -> Value {
+    json!({
+        "type": ty.to_string(),
+        "tainted": named_structs.is_type_tainted(ty, cur_fn),
+    })
+}
+
+/// Describe each of the given `Operand`s, and its current `TaintedType`, for
+/// the propagation trace
+pub(crate) fn operand_entries<'m>(operands: &[Operand], fts: &FunctionTaintState<'m>, cur_fn: &'m str) -> Value {
+    // get all the operand types before locking the named structs, since
+    // getting the type of a constant operand may need to lock them too
+    let types: Vec<_> = operands.iter().map(|op| fts.get_type_of_operand(op)).collect();
+    let mut named_structs = fts.named_structs.write().unwrap();
+    Value::Array(operands.iter().zip(types).map(|(op, ty)| {
+        let mut entry = json!({ "operand": op.to_string() });
+        match ty {
+            Ok(ty) => {
+                entry["type"] = ty.to_string().into();
+                entry["tainted"] = named_structs.is_type_tainted(&ty, cur_fn).into();
+            },
+            Err(e) => {
+                entry["error"] = e.into();
+            },
+        }
+        entry
+    }).collect())
+}
@@ -0,0 +1,250 @@
+use crate::config::Config;
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::{FnTargets, TaintedType};
+use llvm_ir::{Module, Type};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The taint transfer of a single function, analyzed in isolation (see
+/// `do_isolated_analysis_on_function()`): how the taint of its outputs depends
+/// on the taint of its parameters.
+///
+/// Each parameter is treated as its own symbolic source, standing for the
+/// parameter and everything reachable through it. The outputs are the return
+/// value and the data reachable through each pointer parameter; an output is
+/// tainted if any part of it (including anything reachable through it) is.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TransferSummary {
+    /// Name of the function
+    function: String,
+
+    /// How the return value's taint depends on the parameters, or `None` for
+    /// void functions
+    ret: Option<Dependence>,
+
+    /// For each parameter, how the taint of the data reachable through it
+    /// depends on the (other) parameters
+    param_pointees: Vec<Dependence>,
+}
+
+/// How the taint of one output of a function depends on its parameters
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+struct Dependence {
+    /// Is the output tainted even if no parameter is, e.g. because the
+    /// function calls an external function which returns tainted data?
+    always: bool,
+
+    /// Indices of the parameters whose taint flows to the output, sorted
+    params: Vec<usize>,
+}
+
+impl TransferSummary {
+    /// Get the name of the function
+    pub fn get_function_name(&self) -> &str {
+        &self.function
+    }
+
+    /// Is the return value tainted even when no parameter is? Always `false`
+    /// for void functions.
+    pub fn is_ret_always_tainted(&self) -> bool {
+        self.ret.as_ref().map(|dep| dep.always).unwrap_or(false)
+    }
+
+    /// Get the indices of the parameters whose taint flows to the return
+    /// value, sorted. Always empty for void functions.
+    pub fn get_ret_dependencies(&self) -> &[usize] {
+        self.ret.as_ref().map(|dep| dep.params.as_slice()).unwrap_or(&[])
+    }
+
+    /// Is the data reachable through the parameter with the given index
+    /// tainted even when no parameter is? Always `false` for non-pointer
+    /// parameters.
+    pub fn is_param_pointee_always_tainted(&self, index: usize) -> bool {
+        self.param_pointees[index].always
+    }
+
+    /// Get the indices of the other parameters whose taint flows to the data
+    /// reachable through the parameter with the given index, sorted. Always
+    /// empty for non-pointer parameters.
+    pub fn get_param_pointee_dependencies(&self, index: usize) -> &[usize] {
+        &self.param_pointees[index].params
+    }
+
+    /// Instantiate the summary for a call in which the parameters with the
+    /// given indices are tainted: is the return value tainted?
+    pub fn returns_tainted_given(&self, tainted_params: &[usize]) -> bool {
+        self.ret.as_ref().map(|dep| dep.instantiate(tainted_params)).unwrap_or(false)
+    }
+
+    /// Instantiate the summary for a call in which the parameters with the
+    /// given indices are tainted: is the data reachable through the parameter
+    /// with the given index tainted afterwards? (This is only about taint the
+    /// function adds: if the parameter itself is tainted, the data reachable
+    /// through it is considered tainted already.)
+    pub fn param_pointee_tainted_given(&self, index: usize, tainted_params: &[usize]) -> bool {
+        self.param_pointees[index].instantiate(tainted_params)
+    }
+}
+
+impl Dependence {
+    fn instantiate(&self, tainted_params: &[usize]) -> bool {
+        self.always || self.params.iter().any(|param| tainted_params.contains(param))
+    }
+}
+
+impl fmt::Display for Dependence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.always {
+            write!(f, "tainted")
+        } else if self.params.is_empty() {
+            write!(f, "untainted")
+        } else {
+            let params: Vec<String> = self.params.iter().map(|param| format!("arg {}", param)).collect();
+            write!(f, "tainted if {} is tainted", params.join(" or "))
+        }
+    }
+}
+
+impl fmt::Display for TransferSummary {
+    /// Displays one line for the return value (if any), and one for the data
+    /// reachable through each parameter which can receive taint, e.g.
+    /// `ret: tainted if arg 0 or arg 2 is tainted`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}:", self.function)?;
+        if let Some(ret) = &self.ret {
+            writeln!(f, "  ret: {}", ret)?;
+        }
+        for (index, pointee) in self.param_pointees.iter().enumerate() {
+            if pointee != &Dependence::default() {
+                writeln!(f, "  *arg {}: {}", index, pointee)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Produce the `TaintedType` for a value of the given LLVM type which is
+/// entirely tainted, along with everything reachable through it.
+///
+/// Named structs are left as they are, since their types are shared by the
+/// whole program.
+pub(crate) fn fully_tainted(llvm_ty: &Type) -> TaintedType {
+    match llvm_ty {
+        Type::PointerType { pointee_type, .. } => match pointee_type.as_ref() {
+            Type::FuncType { .. } => TaintedType::TaintedFnPtr(FnTargets::new()),
+            _ => TaintedType::tainted_ptr_to(fully_tainted(pointee_type)),
+        },
+        Type::ArrayType { element_type, .. }
+        | Type::VectorType { element_type, .. } => TaintedType::array_or_vec_of(fully_tainted(element_type)),
+        Type::StructType { element_types, .. } => TaintedType::struct_of(element_types.iter().map(|ty| fully_tainted(ty))),
+        Type::NamedStructType { name } => TaintedType::NamedStruct(name.into()),
+        Type::TokenType => TaintedType::Token,
+        _ => TaintedType::TaintedValue,
+    }
+}
+
+/// Is any part of the given type tainted, including anything reachable
+/// through it?
+fn is_anything_tainted(result: &TaintResult, ty: &TaintedType) -> bool {
+    fn go(result: &TaintResult, ty: &TaintedType, seen_pointees: &mut HashSet<usize>, seen_structs: &mut HashSet<String>) -> bool {
+        match ty {
+            TaintedType::UntaintedValue | TaintedType::UntaintedFnPtr(_) | TaintedType::Token => false,
+            TaintedType::TaintedValue | TaintedType::TaintedFnPtr(_) | TaintedType::TaintedPointer(_) => true,
+            TaintedType::UntaintedPointer(pointee) | TaintedType::ArrayOrVector(pointee) => {
+                seen_pointees.insert(pointee.as_ptr() as usize) && go(result, &pointee.ty(), seen_pointees, seen_structs)
+            },
+            TaintedType::Struct(elements) => elements.iter().any(|element| {
+                seen_pointees.insert(element.as_ptr() as usize) && go(result, &element.ty(), seen_pointees, seen_structs)
+            }),
+            TaintedType::NamedStruct(name) => {
+                seen_structs.insert(name.clone())
+                    && result.named_struct_types.get(name).map(|inner| go(result, inner, seen_pointees, seen_structs)).unwrap_or(false)
+            },
+        }
+    }
+    go(result, ty, &mut HashSet::new(), &mut HashSet::new())
+}
+
+/// The outputs of one run of the analysis: whether the return value, and the
+/// data reachable through each parameter, ended up tainted
+struct RunOutputs {
+    ret: Option<bool>,
+    param_pointees: Vec<bool>,
+}
+
+pub(crate) fn analyze_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    fn_name: &str,
+) -> TransferSummary {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let func = modules
+        .iter()
+        .find_map(|module| module.get_func_by_name(fn_name))
+        .unwrap_or_else(|| panic!("Failed to find function named {:?}", fn_name));
+
+    // Run the analysis with the given parameter (if any) entirely tainted, and
+    // the others untainted
+    let run = |tainted_param: Option<usize>| -> RunOutputs {
+        let args = func.parameters
+            .iter()
+            .enumerate()
+            .map(|(index, param)| if Some(index) == tainted_param {
+                fully_tainted(&param.ty)
+            } else {
+                TaintedType::from_llvm_type(&param.ty)
+            })
+            .collect();
+        let result = TaintState::do_analysis_single_function(modules.iter().copied(), config, fn_name, Some(args), HashMap::new(), HashMap::new())
+            .into_taint_result();
+        let ret = result
+            .get_function_summary(fn_name)
+            .and_then(|summary| summary.get_ret_ty().as_ref())
+            .map(|ret_ty| is_anything_tainted(&result, ret_ty));
+        let param_pointees = func.parameters
+            .iter()
+            .enumerate()
+            .map(|(index, param)| {
+                if Some(index) == tainted_param {
+                    // only taint from other parameters is interesting
+                    return false;
+                }
+                match result.get_var_type(fn_name, &param.name) {
+                    TaintedType::UntaintedPointer(pointee) | TaintedType::TaintedPointer(pointee) => is_anything_tainted(&result, &pointee.ty()),
+                    _ => false,
+                }
+            })
+            .collect();
+        RunOutputs { ret, param_pointees }
+    };
+
+    // Outputs tainted with no tainted parameters are always tainted; other
+    // outputs depend on the parameters which taint them when tainted alone.
+    // Taint only ever flows by joining, so this is the same as considering
+    // every combination of tainted parameters.
+    let base = run(None);
+    let mut ret = base.ret.map(|always| Dependence { always, params: Vec::new() });
+    let mut param_pointees: Vec<Dependence> = base.param_pointees
+        .iter()
+        .map(|&always| Dependence { always, params: Vec::new() })
+        .collect();
+    for index in 0 .. func.parameters.len() {
+        let outputs = run(Some(index));
+        if let (Some(ret), Some(true)) = (&mut ret, outputs.ret) {
+            if !ret.always {
+                ret.params.push(index);
+            }
+        }
+        for (pointee, tainted) in param_pointees.iter_mut().zip(outputs.param_pointees) {
+            if tainted && !pointee.always {
+                pointee.params.push(index);
+            }
+        }
+    }
+    TransferSummary {
+        function: fn_name.into(),
+        ret,
+        param_pointees,
+    }
+}
@@ -0,0 +1,52 @@
+/// WASI (preview 1) imports which read data from outside the WebAssembly
+/// sandbox, which we model directly as taint sources (see
+/// `Config::model_wasi_imports`), rather than as calls to external functions
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum WasiFunction {
+    /// `fd_read`, `fd_pread`, and `sock_recv`: read into the buffers of an
+    /// array of `iovec`s
+    ReadIovecs {
+        /// Index of the `iovec` array argument
+        iovs: usize,
+    },
+    /// `fd_readdir` and `path_readlink`: read into a buffer
+    ReadBuffer {
+        /// Index of the buffer argument
+        buf: usize,
+    },
+    /// `args_get` and `environ_get`: fill a buffer with strings, and an array
+    /// with pointers to them
+    GetStrings {
+        /// Index of the array of string pointers
+        ptrs: usize,
+        /// Index of the string buffer argument
+        buf: usize,
+    },
+    /// `args_sizes_get` and `environ_sizes_get`: store the number of strings
+    /// and the size of the buffer they need
+    GetSizes {
+        /// Index of the count argument
+        count: usize,
+        /// Index of the buffer size argument
+        size: usize,
+    },
+}
+
+impl WasiFunction {
+    /// Classify the function with the given name, which is either the
+    /// `__wasi_`-prefixed wrapper wasi-libc declares, or the raw
+    /// `__imported_wasi_snapshot_preview1_`-prefixed import
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let name = name
+            .strip_prefix("__wasi_")
+            .or_else(|| name.strip_prefix("__imported_wasi_snapshot_preview1_"))?;
+        match name {
+            "fd_read" | "fd_pread" | "sock_recv" => Some(WasiFunction::ReadIovecs { iovs: 1 }),
+            "fd_readdir" => Some(WasiFunction::ReadBuffer { buf: 1 }),
+            "path_readlink" => Some(WasiFunction::ReadBuffer { buf: 3 }),
+            "args_get" | "environ_get" => Some(WasiFunction::GetStrings { ptrs: 0, buf: 1 }),
+            "args_sizes_get" | "environ_sizes_get" => Some(WasiFunction::GetSizes { count: 0, size: 1 }),
+            _ => None,
+        }
+    }
+}
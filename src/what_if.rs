@@ -0,0 +1,210 @@
+use crate::config::Config;
+use crate::labels;
+use crate::named_structs::NamedStructInitialDef;
+use crate::taint_degree::{self, SourceSet, TaintSource};
+use crate::taint_result::TaintResult;
+use crate::taint_state::TaintState;
+use crate::tainted_type::TaintedType;
+use llvm_ir::{Module, Name};
+use std::collections::{BTreeSet, HashMap};
+
+/// The result of a what-if analysis (see `do_what_if_analysis_on_function()`),
+/// which can cheaply be re-evaluated with sources disabled or validators added.
+///
+/// Internally, this keeps the result of one run of the analysis per source,
+/// with only that source tainted. Since taint only ever flows by joining, a
+/// value is tainted exactly when it's tainted in the run of some enabled
+/// source, so disabling or re-enabling a source needs no analysis at all.
+/// Adding validators re-runs only the runs in which a new validator is passed
+/// tainted data; in the other runs, the validator only ever sees untainted
+/// data, so validating it changes nothing.
+pub struct WhatIfAnalysis<'m> {
+    modules: Vec<&'m Module>,
+    config: &'m Config,
+    start_fn_name: String,
+    runs: Vec<WhatIfRun<'m>>,
+    /// Sources whose runs are currently ignored
+    disabled: BTreeSet<TaintSource>,
+}
+
+/// One run of a what-if analysis: its source, the inputs the run was started
+/// with (so that it can be re-run), and its result
+struct WhatIfRun<'m> {
+    source: TaintSource,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+    result: TaintResult<'m>,
+}
+
+impl<'m> WhatIfRun<'m> {
+    fn analyze(
+        modules: &[&'m Module],
+        config: &'m Config,
+        start_fn_name: &str,
+        args: &Option<Vec<TaintedType>>,
+        nonargs: &HashMap<Name, TaintedType>,
+        named_structs: &HashMap<String, NamedStructInitialDef>,
+    ) -> TaintResult<'m> {
+        TaintState::do_analysis_single_function(
+            modules.iter().copied(),
+            config,
+            start_fn_name,
+            args.clone(),
+            nonargs.clone(),
+            named_structs.clone(),
+        )
+        .into_taint_result()
+    }
+
+    fn is_var_tainted(&self, fn_name: &str, var_name: &Name) -> bool {
+        self.result
+            .fn_taint_states
+            .get(fn_name)
+            .and_then(|fts| fts.get_taint_map().get(var_name))
+            .is_some_and(|ty| self.result.is_type_tainted_if_known(ty))
+    }
+}
+
+impl<'m> WhatIfAnalysis<'m> {
+    /// Get all the sources of the analysis, enabled or not, sorted
+    pub fn get_sources(&self) -> Vec<&TaintSource> {
+        let mut sources: Vec<&TaintSource> = self.runs.iter().map(|run| &run.source).collect();
+        sources.sort();
+        sources
+    }
+
+    /// Disable the given source: results are re-evaluated as if it were
+    /// untainted. This doesn't re-run the analysis. Returns `false` if there
+    /// is no such source.
+    pub fn disable_source(&mut self, source: &TaintSource) -> bool {
+        if self.runs.iter().any(|run| &run.source == source) {
+            self.disabled.insert(source.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Re-enable the given source, undoing `disable_source()`
+    pub fn enable_source(&mut self, source: &TaintSource) {
+        self.disabled.remove(source);
+    }
+
+    /// Switch to the given `Config`, which must be the current one with some
+    /// `validators` added, and re-evaluate the results. Only the runs in which
+    /// one of the new validators is passed tainted data (see
+    /// `TaintResult::tainted_callsites_of()`) are re-run; the results of the
+    /// others are reused. Disabled sources are re-run as well, so that they
+    /// are up to date if re-enabled.
+    ///
+    /// If the given `Config` lacks any of the current validators, removing
+    /// them may taint anything, so every run is re-run.
+    ///
+    /// Returns the number of runs which were re-run.
+    pub fn add_validators(&mut self, config: &'m Config) -> usize {
+        let removed_any = self.config.validators.iter().any(|validator| !config.validators.contains(validator));
+        let new_validators: Vec<&String> = config
+            .validators
+            .iter()
+            .filter(|validator| !self.config.validators.contains(*validator))
+            .collect();
+        let mut rerun = 0;
+        for run in &mut self.runs {
+            let affected = removed_any
+                || new_validators
+                    .iter()
+                    .any(|validator| !run.result.tainted_callsites_of(validator).is_empty());
+            if affected {
+                run.result = WhatIfRun::analyze(&self.modules, config, &self.start_fn_name, &run.args, &run.nonargs, &run.named_structs);
+                rerun += 1;
+            }
+        }
+        self.config = config;
+        rerun
+    }
+
+    /// Is the variable with the given name in the given function tainted, by
+    /// any enabled source? Returns `false` for variables the analysis never
+    /// encountered.
+    pub fn is_var_tainted(&self, fn_name: &str, var_name: &Name) -> bool {
+        self.enabled_runs().any(|run| run.is_var_tainted(fn_name, var_name))
+    }
+
+    /// Get the enabled sources whose data may flow to the given variable,
+    /// sorted
+    pub fn get_var_sources(&self, fn_name: &str, var_name: &Name) -> Vec<TaintSource> {
+        let mut sources: Vec<TaintSource> = self
+            .enabled_runs()
+            .filter(|run| run.is_var_tainted(fn_name, var_name))
+            .map(|run| run.source.clone())
+            .collect();
+        sources.sort();
+        sources
+    }
+
+    /// Does the function with the given name return data tainted by any
+    /// enabled source? See `TaintResult::does_function_return_tainted()`.
+    pub fn does_function_return_tainted(&self, fn_name: &str) -> bool {
+        self.enabled_runs().any(|run| run.result.does_function_return_tainted(fn_name))
+    }
+
+    /// Are the contents of the global with the given name tainted by any
+    /// enabled source? See `TaintResult::is_global_tainted()`.
+    pub fn is_global_tainted(&self, global_name: &Name) -> bool {
+        self.enabled_runs().any(|run| run.result.is_global_tainted(global_name))
+    }
+
+    fn enabled_runs(&self) -> impl Iterator<Item = &WhatIfRun<'m>> {
+        self.runs.iter().filter(move |run| !self.disabled.contains(&run.source))
+    }
+}
+
+pub(crate) fn analyze_function<'m>(
+    modules: impl IntoIterator<Item = &'m Module>,
+    config: &'m Config,
+    start_fn_name: &str,
+    args: Option<Vec<TaintedType>>,
+    nonargs: HashMap<Name, TaintedType>,
+    named_structs: HashMap<String, NamedStructInitialDef>,
+) -> WhatIfAnalysis<'m> {
+    let modules: Vec<&'m Module> = modules.into_iter().collect();
+    let args = args.map(|args| taint_degree::label_args(&modules, start_fn_name, args));
+    let nonargs = taint_degree::label_nonargs(start_fn_name, nonargs);
+    let named_structs = taint_degree::label_named_structs(named_structs);
+    let sources = labels::distinct_labels(
+        args.iter()
+            .flatten()
+            .map(|(_, label)| label)
+            .chain(nonargs.values().map(|(_, label)| label))
+            .chain(named_structs.values().map(|(_, label)| label)),
+    );
+    let runs = labels::partition_sources(
+        sources,
+        |label: &SourceSet| args.as_ref().map(|args| {
+            args.iter()
+                .zip(labels::untainted_params(&modules, start_fn_name))
+                .map(|((ty, arg_label), untainted)| if arg_label == label { ty.clone() } else { untainted })
+                .collect()
+        }),
+        nonargs,
+        named_structs,
+    );
+    let runs = runs
+        .into_iter()
+        .map(|run| WhatIfRun {
+            source: run.label.0.into_iter().next().expect("each source has its own run"),
+            result: WhatIfRun::analyze(&modules, config, start_fn_name, &run.args, &run.nonargs, &run.named_structs),
+            args: run.args,
+            nonargs: run.nonargs,
+            named_structs: run.named_structs,
+        })
+        .collect();
+    WhatIfAnalysis {
+        modules,
+        config,
+        start_fn_name: start_fn_name.into(),
+        runs,
+        disabled: BTreeSet::new(),
+    }
+}
@@ -1,5 +1,8 @@
+#[cfg(not(feature = "tracing"))]
 use log::debug;
-use std::collections::HashSet;
+#[cfg(feature = "tracing")]
+use tracing::debug;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
 /// Keeps track of the set of functions which need to be processed again because
@@ -9,6 +12,16 @@ use std::iter::FromIterator;
 /// `.collect()` on an iterator)
 pub struct Worklist<'m> {
     fn_names: HashSet<&'m str>,
+
+    /// If this is nonempty, `pop()` returns the function with the lowest rank
+    /// here, rather than an arbitrary one. Functions not in this map come
+    /// after all those that are.
+    ranks: HashMap<&'m str, usize>,
+
+    /// If `true`, `pop()` breaks ties (or, with no ranks, chooses) by name
+    /// rather than arbitrarily, so that functions are always processed in the
+    /// same order
+    deterministic: bool,
 }
 
 impl<'m> Worklist<'m> {
@@ -18,25 +31,57 @@ impl<'m> Worklist<'m> {
         self.fn_names.insert(fn_name);
     }
 
-    /// Gets an arbitrary function name on the worklist, removes it from the
-    /// worklist, and returns it
+    /// Gets a function name on the worklist, removes it from the worklist, and
+    /// returns it. This is the function with the lowest rank if ranks have
+    /// been set with `set_ranks()`; otherwise the first by name if
+    /// `set_deterministic()` has been called, or else an arbitrary one.
     ///
     /// Returns `None` if the worklist was empty
     pub fn pop(&mut self) -> Option<&'m str> {
-        let fn_name: &'m str = self
-            .fn_names
-            .iter()
-            .next()?
-            .clone();
+        let fn_name: &'m str = if self.ranks.is_empty() {
+            if self.deterministic {
+                self.fn_names.iter().copied().min()?
+            } else {
+                self.fn_names.iter().next().copied()?
+            }
+        } else {
+            self.fn_names
+                .iter()
+                .copied()
+                .min_by_key(|name| (self.ranks.get(name).copied().unwrap_or(usize::MAX), *name))?
+        };
         self.fn_names.remove(fn_name);
         Some(fn_name)
     }
+
+    /// Set the order in which `pop()` returns functions: lowest rank first
+    pub fn set_ranks(&mut self, ranks: HashMap<&'m str, usize>) {
+        self.ranks = ranks;
+    }
+
+    /// Make `pop()` return functions in a fixed order, regardless of the order
+    /// they were added in
+    pub fn set_deterministic(&mut self) {
+        self.deterministic = true;
+    }
+
+    /// Is the worklist empty?
+    pub fn is_empty(&self) -> bool {
+        self.fn_names.is_empty()
+    }
+
+    /// Iterate over the function names on the worklist, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &'m str> + '_ {
+        self.fn_names.iter().copied()
+    }
 }
 
 impl<'m> FromIterator<&'m str> for Worklist<'m> {
     fn from_iter<I: IntoIterator<Item = &'m str>>(iter: I) -> Self {
         Self {
             fn_names: iter.into_iter().collect(),
+            ranks: HashMap::new(),
+            deterministic: false,
         }
     }
 }
@@ -0,0 +1,11 @@
+// Written in addrspace.ll by hand, in SSA form.
+
+#define HOST __attribute__((address_space(1)))
+#define SHARED __attribute__((address_space(4)))
+
+void kernel(HOST int *in, SHARED int *out, int *local) {
+  int v = *in;
+  int w = *local;
+  *out = v;
+  *out = w;
+}
@@ -0,0 +1,16 @@
+; ModuleID = 'addrspace.c'
+source_filename = "addrspace.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local void @kernel(i32 addrspace(1)* %in, i32 addrspace(4)* %out, i32* %local) #0 {
+entry:
+  %v = load i32, i32 addrspace(1)* %in, align 4
+  %w = load i32, i32* %local, align 4
+  store i32 %v, i32 addrspace(4)* %out, align 4
+  store i32 %w, i32 addrspace(4)* %out, align 4
+  ret void
+}
+
+attributes #0 = { noinline nounwind uwtable }
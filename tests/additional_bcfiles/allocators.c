@@ -0,0 +1,44 @@
+// Written in allocators.ll by hand, in SSA form.
+
+#include <stdlib.h>
+#include <string.h>
+
+// the allocation is shared by both casts of the pointer `malloc` returns
+int heap(int x) {
+  void *m = malloc(sizeof(int));
+  *(int *)m = x;
+  return *(int *)m;
+}
+
+// a tainted size doesn't taint the pointer `malloc` returns
+void *heap_size(long n) {
+  return malloc(n);
+}
+
+// `realloc` carries the old contents forward
+int heap_realloc(int x) {
+  int *p = malloc(sizeof(int));
+  *p = x;
+  int *q = realloc(p, 2 * sizeof(int));
+  return q[0];
+}
+
+// `calloc` memory is untainted, and stays separate from other allocations
+int heap_calloc(int x) {
+  int *p = calloc(1, sizeof(int));
+  int *q = malloc(sizeof(int));
+  *q = x;
+  return *p;
+}
+
+// taint copied into the `i8*` is seen through the cast
+int heap_memcpy(int *src) {
+  void *m = malloc(sizeof(int));
+  memcpy(m, src, sizeof(int));
+  return *(int *)m;
+}
+
+// the pointer freed may be tainted
+void heap_free(char *p) {
+  free(p);
+}
@@ -0,0 +1,68 @@
+; ModuleID = 'allocators.c'
+source_filename = "allocators.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+declare noalias i8* @malloc(i64)
+
+declare noalias i8* @calloc(i64, i64)
+
+declare noalias i8* @realloc(i8*, i64)
+
+declare void @free(i8*)
+
+declare void @llvm.memcpy.p0i8.p0i8.i64(i8* noalias nocapture writeonly, i8* noalias nocapture readonly, i64, i1 immarg)
+
+define i32 @heap(i32 %x) {
+entry:
+  %m = call noalias i8* @malloc(i64 4)
+  %p = bitcast i8* %m to i32*
+  store i32 %x, i32* %p
+  %q = bitcast i8* %m to i32*
+  %r = load i32, i32* %q
+  ret i32 %r
+}
+
+define i8* @heap_size(i64 %n) {
+entry:
+  %m = call noalias i8* @malloc(i64 %n)
+  ret i8* %m
+}
+
+define i32 @heap_realloc(i32 %x) {
+entry:
+  %m = call noalias i8* @malloc(i64 4)
+  %p = bitcast i8* %m to i32*
+  store i32 %x, i32* %p
+  %n = call i8* @realloc(i8* %m, i64 8)
+  %q = bitcast i8* %n to i32*
+  %r = load i32, i32* %q
+  ret i32 %r
+}
+
+define i32 @heap_calloc(i32 %x) {
+entry:
+  %c = call noalias i8* @calloc(i64 1, i64 4)
+  %p = bitcast i8* %c to i32*
+  %m = call noalias i8* @malloc(i64 4)
+  %q = bitcast i8* %m to i32*
+  store i32 %x, i32* %q
+  %r = load i32, i32* %p
+  ret i32 %r
+}
+
+define i32 @heap_memcpy(i32* %src) {
+entry:
+  %m = call noalias i8* @malloc(i64 4)
+  %s = bitcast i32* %src to i8*
+  call void @llvm.memcpy.p0i8.p0i8.i64(i8* %m, i8* %s, i64 4, i1 false)
+  %p = bitcast i8* %m to i32*
+  %r = load i32, i32* %p
+  ret i32 %r
+}
+
+define void @heap_free(i8* %p) {
+entry:
+  call void @free(i8* %p)
+  ret void
+}
@@ -0,0 +1,34 @@
+// Written in annotations.ll by hand, in SSA form.
+
+#define SOURCE __attribute__((annotate("taint.source")))
+#define SINK __attribute__((annotate("taint.sink")))
+
+SOURCE int secret_key = 42;
+int public_key = 7;
+
+SOURCE int read_sensor(void) {
+  return 0;
+}
+
+SINK void emit(int x) {
+}
+
+int use_keys(void) {
+  int s = secret_key;
+  int p = public_key;
+  return s + p;
+}
+
+int sample(void) {
+  int r = read_sensor();
+  return r + 1;
+}
+
+void report(int x) {
+  emit(x);
+}
+
+int local(int x) {
+  SOURCE int v = x;
+  return v;
+}
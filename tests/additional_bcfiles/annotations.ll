@@ -0,0 +1,54 @@
+; ModuleID = 'annotations.c'
+source_filename = "annotations.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@secret_key = global i32 42, align 4
+@public_key = global i32 7, align 4
+@.str = private unnamed_addr constant [13 x i8] c"taint.source\00", section "llvm.metadata"
+@.str.1 = private unnamed_addr constant [14 x i8] c"annotations.c\00", section "llvm.metadata"
+@.str.2 = private unnamed_addr constant [11 x i8] c"taint.sink\00", section "llvm.metadata"
+@llvm.global.annotations = appending global [3 x { i8*, i8*, i8*, i32, i8* }] [{ i8*, i8*, i8*, i32, i8* } { i8* bitcast (i32* @secret_key to i8*), i8* getelementptr inbounds ([13 x i8], [13 x i8]* @.str, i32 0, i32 0), i8* getelementptr inbounds ([14 x i8], [14 x i8]* @.str.1, i32 0, i32 0), i32 6, i8* null }, { i8*, i8*, i8*, i32, i8* } { i8* bitcast (i32 ()* @read_sensor to i8*), i8* getelementptr inbounds ([13 x i8], [13 x i8]* @.str, i32 0, i32 0), i8* getelementptr inbounds ([14 x i8], [14 x i8]* @.str.1, i32 0, i32 0), i32 9, i8* null }, { i8*, i8*, i8*, i32, i8* } { i8* bitcast (void (i32)* @emit to i8*), i8* getelementptr inbounds ([11 x i8], [11 x i8]* @.str.2, i32 0, i32 0), i8* getelementptr inbounds ([14 x i8], [14 x i8]* @.str.1, i32 0, i32 0), i32 13, i8* null }], section "llvm.metadata"
+
+define i32 @read_sensor() {
+entry:
+  ret i32 0
+}
+
+define void @emit(i32 %x) {
+entry:
+  ret void
+}
+
+define i32 @use_keys() {
+entry:
+  %s = load i32, i32* @secret_key, align 4
+  %p = load i32, i32* @public_key, align 4
+  %sum = add nsw i32 %s, %p
+  ret i32 %sum
+}
+
+define i32 @sample() {
+entry:
+  %r = call i32 @read_sensor()
+  %sum = add nsw i32 %r, 1
+  ret i32 %sum
+}
+
+define void @report(i32 %x) {
+entry:
+  call void @emit(i32 %x)
+  ret void
+}
+
+define i32 @local(i32 %x) {
+entry:
+  %v = alloca i32, align 4
+  %v.cast = bitcast i32* %v to i8*
+  call void @llvm.var.annotation(i8* %v.cast, i8* getelementptr inbounds ([13 x i8], [13 x i8]* @.str, i32 0, i32 0), i8* getelementptr inbounds ([14 x i8], [14 x i8]* @.str.1, i32 0, i32 0), i32 32, i8* null)
+  store i32 %x, i32* %v, align 4
+  %r = load i32, i32* %v, align 4
+  ret i32 %r
+}
+
+declare void @llvm.var.annotation(i8*, i8*, i8*, i32, i8*)
@@ -0,0 +1,18 @@
+// Written in assumptions.ll by hand, in SSA form.
+
+#include <stdint.h>
+
+int unknown(int x);
+int known(int x);
+
+int64_t (*handler)(int64_t, int64_t);
+
+int entry(uintptr_t addr, int x) {
+  int v = *(int *)addr;
+  int a;
+  __asm__("mov %1, %0" : "=r"(a) : "r"(x));
+  int u = unknown(x);
+  int k = known(x);
+  handler(1, 2);
+  return v;
+}
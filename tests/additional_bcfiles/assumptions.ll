@@ -0,0 +1,24 @@
+; ModuleID = 'assumptions.c'
+source_filename = "assumptions.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@handler = dso_local global i64 (i64, i64)* null, align 8
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @entry(i64 %addr, i32 %x) #0 {
+entry:
+  %p = inttoptr i64 %addr to i32*
+  %v = load i32, i32* %p, align 4
+  %a = call i32 asm "mov $1, $0", "=r,r"(i32 %x)
+  %u = call i32 @unknown(i32 %x)
+  %k = call i32 @known(i32 %x)
+  %fp = load i64 (i64, i64)*, i64 (i64, i64)** @handler, align 8
+  %r = call i64 %fp(i64 1, i64 2)
+  ret i32 %v
+}
+
+declare dso_local i32 @unknown(i32)
+declare dso_local i32 @known(i32)
+
+attributes #0 = { noinline nounwind uwtable }
@@ -0,0 +1,29 @@
+// Written in audit.ll by hand, in SSA form.
+
+struct state {
+  int mode;
+  int level;
+};
+
+int config;
+int untouched;
+struct state st;
+
+void set_config(int v) {
+  config = v;
+}
+
+static void set_level(int v) {
+  st.level = v;
+}
+
+// Also writes its argument to the stack pointer (a sink)
+void api(long v) {
+  set_level((int)v);
+  register long sp asm("sp") = v;  // llvm.write_register
+}
+
+// Hidden, so not part of the library's attack surface
+__attribute__((visibility("hidden"))) void internal_only(int v) {
+  untouched = v;
+}
@@ -0,0 +1,41 @@
+; ModuleID = 'audit.c'
+source_filename = "audit.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+%struct.state = type { i32, i32 }
+
+@config = global i32 0, align 4
+@untouched = global i32 0, align 4
+@st = global %struct.state zeroinitializer, align 4
+
+define void @set_config(i32 %v) {
+entry:
+  store i32 %v, i32* @config, align 4
+  ret void
+}
+
+define internal void @set_level(i32 %v) {
+entry:
+  %level = getelementptr inbounds %struct.state, %struct.state* @st, i32 0, i32 1
+  store i32 %v, i32* %level, align 4
+  ret void
+}
+
+define void @api(i64 %v) {
+entry:
+  %t = trunc i64 %v to i32
+  call void @set_level(i32 %t)
+  call void @llvm.write_register.i64(metadata !0, i64 %v)
+  ret void
+}
+
+define hidden void @internal_only(i32 %v) {
+entry:
+  store i32 %v, i32* @untouched, align 4
+  ret void
+}
+
+declare void @llvm.write_register.i64(metadata, i64)
+
+!0 = !{!"sp"}
@@ -0,0 +1,13 @@
+// Written in bits.ll by hand, in SSA form.
+
+int fields(int word) {
+  int byte = (word >> 8) & 0xff;
+  int none = (word & 0xf0) >> 8;
+  int shifted = (word & 0xf) << 4;
+  int sum = shifted + 1;
+  signed char b = (signed char)byte;
+  int s = b;
+  int z = (unsigned char)b;
+  int set = word | -1;
+  return byte + none + sum + s + z + set;
+}
@@ -0,0 +1,28 @@
+; ModuleID = 'bits.c'
+source_filename = "bits.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @fields(i32 %word) #0 {
+entry:
+  %hi = lshr i32 %word, 8
+  %byte = and i32 %hi, 255
+  %nib = and i32 %word, 240
+  %none = lshr i32 %nib, 8
+  %low = and i32 %word, 15
+  %shifted = shl i32 %low, 4
+  %sum = add nsw i32 %shifted, 1
+  %b = trunc i32 %byte to i8
+  %s = sext i8 %b to i32
+  %z = zext i8 %b to i32
+  %set = or i32 %word, -1
+  %r1 = add i32 %byte, %none
+  %r2 = add i32 %r1, %sum
+  %r3 = add i32 %r2, %s
+  %r4 = add i32 %r3, %z
+  %r = add i32 %r4, %set
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
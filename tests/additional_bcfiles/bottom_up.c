@@ -0,0 +1,21 @@
+// Written in bottom_up.ll by hand, in SSA form.
+
+int secret(void);
+
+// The secret enters at the bottom of a chain of calls, and has to work its
+// way up to top(); clean() never sees it
+int leaf(int x) {
+  return secret() + x;
+}
+
+int mid3(int x) { return leaf(x); }
+int mid2(int x) { return mid3(x); }
+int mid1(int x) { return mid2(x); }
+
+int clean(int x) { return x; }
+
+int top(void) {
+  int r = mid1(1);
+  int c = clean(2);
+  return r + c;
+}
@@ -0,0 +1,44 @@
+; ModuleID = 'bottom_up.c'
+source_filename = "bottom_up.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @leaf(i32 %x) {
+entry:
+  %s = call i32 @secret()
+  %r = add nsw i32 %s, %x
+  ret i32 %r
+}
+
+define i32 @mid3(i32 %x) {
+entry:
+  %r = call i32 @leaf(i32 %x)
+  ret i32 %r
+}
+
+define i32 @mid2(i32 %x) {
+entry:
+  %r = call i32 @mid3(i32 %x)
+  ret i32 %r
+}
+
+define i32 @mid1(i32 %x) {
+entry:
+  %r = call i32 @mid2(i32 %x)
+  ret i32 %r
+}
+
+define i32 @clean(i32 %x) {
+entry:
+  ret i32 %x
+}
+
+define i32 @top() {
+entry:
+  %r = call i32 @mid1(i32 1)
+  %c = call i32 @clean(i32 2)
+  %sum = add nsw i32 %r, %c
+  ret i32 %sum
+}
+
+declare i32 @secret()
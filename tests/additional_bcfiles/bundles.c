@@ -0,0 +1,14 @@
+// Written in bundles.ll by hand, in SSA form. The calls in bundles.ll carry
+// operand bundles, which C has no syntax for.
+
+int add_one(int x) {
+  return x + 1;
+}
+
+int with_deopt(int x, int state) {
+  return add_one(x);  // with a "deopt"(state) bundle
+}
+
+int via_pointer(int (*f)(int), int x) {
+  return f(x);  // with a "kcfi" bundle
+}
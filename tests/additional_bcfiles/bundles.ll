@@ -0,0 +1,27 @@
+; ModuleID = 'bundles.c'
+source_filename = "bundles.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @add_one(i32 %x) #0 {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @with_deopt(i32 %x, i32 %state) #0 {
+entry:
+  %r = call i32 @add_one(i32 %x) [ "deopt"(i32 %state) ]
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @via_pointer(i32 (i32)* %f, i32 %x) #0 {
+entry:
+  %r = call i32 %f(i32 %x) [ "kcfi"(i32 12345) ]
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind ssp uwtable }
@@ -0,0 +1,19 @@
+// Written in call_graph.ll by hand, in SSA form.
+
+void log_value(int x);
+
+int helper(int x) {
+  return x + 1;
+}
+
+int clean(int x) {
+  log_value(x);
+  return 2;
+}
+
+int top(int secret, int public_) {
+  int a = helper(secret);
+  int b = clean(public_);
+  int c = helper(public_);
+  return a + b + c;
+}
@@ -0,0 +1,28 @@
+; ModuleID = 'call_graph.c'
+source_filename = "call_graph.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+declare void @log_value(i32)
+
+define i32 @helper(i32 %x) {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+define i32 @clean(i32 %x) {
+entry:
+  call void @log_value(i32 %x)
+  ret i32 2
+}
+
+define i32 @top(i32 %secret, i32 %public_) {
+entry:
+  %a = call i32 @helper(i32 %secret)
+  %b = call i32 @clean(i32 %public_)
+  %c = call i32 @helper(i32 %public_)
+  %ab = add nsw i32 %a, %b
+  %abc = add nsw i32 %ab, %c
+  ret i32 %abc
+}
@@ -0,0 +1,18 @@
+// Written in callsites.ll by hand, in SSA form.
+
+#include <string.h>
+
+void log_value(int x);
+
+void copy(char *dst, const char *src, size_t n) {
+  memcpy(dst, src, n);
+}
+
+void copy_const(char *dst) {
+  memcpy(dst, "abc", 4);
+}
+
+void report(int secret, int public_) {
+  log_value(secret);
+  log_value(public_);
+}
@@ -0,0 +1,29 @@
+; ModuleID = 'callsites.c'
+source_filename = "callsites.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@.str = private unnamed_addr constant [4 x i8] c"abc\00", align 1
+
+declare void @llvm.memcpy.p0i8.p0i8.i64(i8* noalias nocapture writeonly, i8* noalias nocapture readonly, i64, i1 immarg)
+
+declare void @log_value(i32)
+
+define void @copy(i8* %dst, i8* %src, i64 %n) {
+entry:
+  call void @llvm.memcpy.p0i8.p0i8.i64(i8* %dst, i8* %src, i64 %n, i1 false)
+  ret void
+}
+
+define void @copy_const(i8* %dst) {
+entry:
+  call void @llvm.memcpy.p0i8.p0i8.i64(i8* %dst, i8* getelementptr inbounds ([4 x i8], [4 x i8]* @.str, i64 0, i64 0), i64 4, i1 false)
+  ret void
+}
+
+define void @report(i32 %secret, i32 %public_) {
+entry:
+  call void @log_value(i32 %secret)
+  call void @log_value(i32 %public_)
+  ret void
+}
@@ -0,0 +1,32 @@
+// Written in closures.ll by hand, in SSA form.
+
+struct closure {
+  int (*fn)(void *env, int x);
+  void *env;
+};
+
+int add_env(void *env, int x) {
+  int e = *(int *)env;
+  return e + x;
+}
+
+int ignore_env(void *env, int x) {
+  return x;
+}
+
+void make_closure(struct closure *c, int *env) {
+  c->fn = add_env;
+  c->env = env;
+}
+
+int call_closure(struct closure *c, int x) {
+  int (*fn)(void *, int) = c->fn;
+  void *env = c->env;
+  return fn(env, x);
+}
+
+int run(int *env) {
+  struct closure c;
+  make_closure(&c, env);
+  return call_closure(&c, 0);
+}
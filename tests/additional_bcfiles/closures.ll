@@ -0,0 +1,54 @@
+; ModuleID = 'closures.c'
+source_filename = "closures.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.closure = type { i32 (i8*, i32)*, i8* }
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @add_env(i8* %env, i32 %x) #0 {
+entry:
+  %p = bitcast i8* %env to i32*
+  %e = load i32, i32* %p, align 4
+  %r = add nsw i32 %e, %x
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @ignore_env(i8* %env, i32 %x) #0 {
+entry:
+  ret i32 %x
+}
+
+; Function Attrs: noinline nounwind uwtable
+define void @make_closure(%struct.closure* %c, i32* %env) #0 {
+entry:
+  %fn = getelementptr inbounds %struct.closure, %struct.closure* %c, i32 0, i32 0
+  store i32 (i8*, i32)* @add_env, i32 (i8*, i32)** %fn, align 8
+  %0 = bitcast i32* %env to i8*
+  %env1 = getelementptr inbounds %struct.closure, %struct.closure* %c, i32 0, i32 1
+  store i8* %0, i8** %env1, align 8
+  ret void
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @call_closure(%struct.closure* %c, i32 %x) #0 {
+entry:
+  %fn.addr = getelementptr inbounds %struct.closure, %struct.closure* %c, i32 0, i32 0
+  %fn = load i32 (i8*, i32)*, i32 (i8*, i32)** %fn.addr, align 8
+  %env.addr = getelementptr inbounds %struct.closure, %struct.closure* %c, i32 0, i32 1
+  %env = load i8*, i8** %env.addr, align 8
+  %call = call i32 %fn(i8* %env, i32 %x)
+  ret i32 %call
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @run(i32* %env) #0 {
+entry:
+  %c = alloca %struct.closure, align 8
+  call void @make_closure(%struct.closure* %c, i32* %env)
+  %r = call i32 @call_closure(%struct.closure* %c, i32 0)
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
@@ -0,0 +1,22 @@
+// Written in constant_pointers.ll by hand, in SSA form.
+
+extern char weak __attribute__((weak));
+char fallback = 0;
+
+char from_label(char *p, int c) {
+  char *r = c ? p : (char *)&&other;  // `blockaddress`
+  return *r;
+other:
+  return 0;
+}
+
+char from_select(char *p, int c) {
+  // a constant `select` of pointers
+  char *r = c ? p : (&weak == 0 ? &fallback : &weak);
+  return *r;
+}
+
+char from_null(char *p, int c) {
+  char *r = c ? p : 0;
+  return *r;
+}
@@ -0,0 +1,52 @@
+; ModuleID = 'constant_pointers.c'
+source_filename = "constant_pointers.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@weak = extern_weak global i8
+@fallback = global i8 0
+
+define i8 @from_label(i8* %p, i32 %c) {
+entry:
+  %tobool = icmp ne i32 %c, 0
+  br i1 %tobool, label %then, label %else
+
+then:
+  br label %join
+
+else:
+  br label %join
+
+join:
+  %r = phi i8* [ %p, %then ], [ blockaddress(@from_label, %other), %else ]
+  %v = load i8, i8* %r
+  ret i8 %v
+
+other:
+  ret i8 0
+}
+
+define i8 @from_select(i8* %p, i32 %c) {
+entry:
+  %tobool = icmp ne i32 %c, 0
+  br i1 %tobool, label %then, label %else
+
+then:
+  br label %join
+
+else:
+  br label %join
+
+join:
+  %r = phi i8* [ %p, %then ], [ select (i1 icmp eq (i8* @weak, i8* null), i8* @fallback, i8* @weak), %else ]
+  %v = load i8, i8* %r
+  ret i8 %v
+}
+
+define i8 @from_null(i8* %p, i32 %c) {
+entry:
+  %tobool = icmp ne i32 %c, 0
+  %r = select i1 %tobool, i8* %p, i8* null
+  %v = load i8, i8* %r
+  ret i8 %v
+}
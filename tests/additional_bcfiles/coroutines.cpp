@@ -0,0 +1,33 @@
+// coroutines.ll is a hand-simplified version of the IR clang emits for this
+// file (with -std=c++20) before the CoroSplit pass: the promise is just an int,
+// and the coroutine frame is never heap-allocated.
+#include <coroutine>
+
+struct Generator {
+  struct promise_type {
+    int value;
+    Generator get_return_object() {
+      return Generator{std::coroutine_handle<promise_type>::from_promise(*this)};
+    }
+    std::suspend_never initial_suspend() { return {}; }
+    std::suspend_always final_suspend() noexcept { return {}; }
+    std::suspend_always yield_value(int v) { value = v; return {}; }
+    void return_void() {}
+    void unhandled_exception() {}
+  };
+  std::coroutine_handle<promise_type> handle;
+};
+
+Generator generator(int x) {
+  co_yield x;
+  co_yield 0;
+}
+
+int get_yielded(int x) {
+  Generator g = generator(x);
+  g.handle.resume();
+  bool done = g.handle.done();
+  int val = g.handle.promise().value;
+  g.handle.destroy();
+  return val;
+}
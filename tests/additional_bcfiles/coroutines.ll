@@ -0,0 +1,77 @@
+; ModuleID = 'coroutines.cpp'
+; Hand-simplified version of the IR clang emits for coroutines.cpp before the
+; CoroSplit pass. The promise is just an i32 rather than a promise_type, and
+; the coroutine frame is never heap-allocated.
+source_filename = "coroutines.cpp"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i8* @generator(i32 %x) #0 {
+entry:
+  %promise = alloca i32, align 4
+  %promise.cast = bitcast i32* %promise to i8*
+  %id = call token @llvm.coro.id(i32 16, i8* %promise.cast, i8* null, i8* null)
+  %hdl = call i8* @llvm.coro.begin(token %id, i8* null)
+  store i32 %x, i32* %promise, align 4
+  %suspend = call i8 @llvm.coro.suspend(token none, i1 false)
+  switch i8 %suspend, label %suspended [
+    i8 0, label %resumed
+    i8 1, label %cleanup
+  ]
+
+resumed:
+  store i32 0, i32* %promise, align 4
+  br label %cleanup
+
+cleanup:
+  %mem = call i8* @llvm.coro.free(token %id, i8* %hdl)
+  br label %suspended
+
+suspended:
+  %unused = call i1 @llvm.coro.end(i8* %hdl, i1 false)
+  ret i8* %hdl
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @get_yielded(i32 %x) #0 {
+entry:
+  %hdl = call i8* @generator(i32 %x)
+  call void @llvm.coro.resume(i8* %hdl)
+  %done = call i1 @llvm.coro.done(i8* %hdl)
+  %p = call i8* @llvm.coro.promise(i8* %hdl, i32 4, i1 false)
+  %p.cast = bitcast i8* %p to i32*
+  %val = load i32, i32* %p.cast, align 4
+  call void @llvm.coro.destroy(i8* %hdl)
+  ret i32 %val
+}
+
+; Function Attrs: argmemonly nounwind readonly
+declare token @llvm.coro.id(i32, i8* readnone, i8* nocapture readonly, i8*) #1
+
+; Function Attrs: nounwind
+declare i8* @llvm.coro.begin(token, i8* writeonly) #2
+
+; Function Attrs: nounwind
+declare i8 @llvm.coro.suspend(token, i1) #2
+
+; Function Attrs: argmemonly nounwind readonly
+declare i8* @llvm.coro.free(token, i8* nocapture readonly) #1
+
+; Function Attrs: nounwind
+declare i1 @llvm.coro.end(i8*, i1) #2
+
+declare void @llvm.coro.resume(i8*)
+
+; Function Attrs: argmemonly nounwind readonly
+declare i1 @llvm.coro.done(i8* nocapture readonly) #1
+
+; Function Attrs: nounwind readnone
+declare i8* @llvm.coro.promise(i8* nocapture, i32, i1) #3
+
+declare void @llvm.coro.destroy(i8*)
+
+attributes #0 = { noinline nounwind optnone ssp uwtable }
+attributes #1 = { argmemonly nounwind readonly }
+attributes #2 = { nounwind }
+attributes #3 = { nounwind readnone }
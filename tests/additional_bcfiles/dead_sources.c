@@ -0,0 +1,12 @@
+// Written in dead_sources.ll by hand, in SSA form.
+
+int read_input(void);
+int read_unused(void);
+void fill_buffer(char *buf);
+
+int process(void) {
+  char buf[16];
+  read_unused();
+  fill_buffer(buf);
+  return read_input() + 1;
+}
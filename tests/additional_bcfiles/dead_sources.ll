@@ -0,0 +1,23 @@
+; ModuleID = 'dead_sources.c'
+source_filename = "dead_sources.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @process() #0 {
+entry:
+  %buf = alloca [16 x i8], align 16
+  %unused = call i32 @read_unused()
+  %buf0 = getelementptr inbounds [16 x i8], [16 x i8]* %buf, i64 0, i64 0
+  call void @fill_buffer(i8* %buf0)
+  %input = call i32 @read_input()
+  %r = add nsw i32 %input, 1
+  ret i32 %r
+}
+
+declare dso_local i32 @read_input() #1
+declare dso_local i32 @read_unused() #1
+declare dso_local void @fill_buffer(i8*) #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,20 @@
+// Written in debug_info.ll by hand, in SSA form, with the debug info clang -g
+// gives the structs.
+
+struct header {
+  int len;
+  int flags;
+};
+
+typedef struct header header_t;
+
+struct request {
+  header_t hdr;
+  int body[4];
+  struct header extra[2];
+};
+
+void fill(struct request *r, int x) {
+  r->hdr.len = x;
+  r->body[2] = x;
+}
@@ -0,0 +1,50 @@
+; ModuleID = 'debug_info.c'
+source_filename = "debug_info.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+%struct.request = type { %struct.header, [4 x i32], [2 x %struct.header] }
+%struct.header = type { i32, i32 }
+
+define void @fill(%struct.request* %r, i32 %x) !dbg !10 {
+entry:
+  %len = getelementptr inbounds %struct.request, %struct.request* %r, i32 0, i32 0, i32 0, !dbg !14
+  store i32 %x, i32* %len, align 4, !dbg !14
+  %body = getelementptr inbounds %struct.request, %struct.request* %r, i32 0, i32 1, i64 2, !dbg !15
+  store i32 %x, i32* %body, align 4, !dbg !15
+  ret void, !dbg !16
+}
+
+!llvm.dbg.cu = !{!0}
+!llvm.module.flags = !{!7, !8}
+
+!0 = distinct !DICompileUnit(language: DW_LANG_C99, file: !1, producer: "hand-written", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, enums: !2, retainedTypes: !3)
+!1 = !DIFile(filename: "debug_info.c", directory: "/tmp")
+!2 = !{}
+!3 = !{!20}
+!7 = !{i32 7, !"Dwarf Version", i32 4}
+!8 = !{i32 2, !"Debug Info Version", i32 3}
+!10 = distinct !DISubprogram(name: "fill", scope: !1, file: !1, line: 17, type: !11, scopeLine: 17, spFlags: DISPFlagDefinition, unit: !0, retainedNodes: !2)
+!11 = !DISubroutineType(types: !12)
+!12 = !{null, !13, !30}
+!13 = !DIDerivedType(tag: DW_TAG_pointer_type, baseType: !20, size: 64)
+!14 = !DILocation(line: 18, column: 14, scope: !10)
+!15 = !DILocation(line: 19, column: 14, scope: !10)
+!16 = !DILocation(line: 20, column: 1, scope: !10)
+!20 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "request", file: !1, line: 11, size: 384, elements: !21)
+!21 = !{!22, !23, !27}
+!22 = !DIDerivedType(tag: DW_TAG_member, name: "hdr", scope: !20, file: !1, line: 12, baseType: !31, size: 64)
+!23 = !DIDerivedType(tag: DW_TAG_member, name: "body", scope: !20, file: !1, line: 13, baseType: !24, size: 128, offset: 64)
+!24 = !DICompositeType(tag: DW_TAG_array_type, baseType: !30, size: 128, elements: !25)
+!25 = !{!26}
+!26 = !DISubrange(count: 4)
+!27 = !DIDerivedType(tag: DW_TAG_member, name: "extra", scope: !20, file: !1, line: 14, baseType: !28, size: 128, offset: 192)
+!28 = !DICompositeType(tag: DW_TAG_array_type, baseType: !32, size: 128, elements: !29)
+!29 = !{!36}
+!30 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!31 = !DIDerivedType(tag: DW_TAG_typedef, name: "header_t", file: !1, line: 9, baseType: !32)
+!32 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "header", file: !1, line: 4, size: 64, elements: !33)
+!33 = !{!34, !35}
+!34 = !DIDerivedType(tag: DW_TAG_member, name: "len", scope: !32, file: !1, line: 5, baseType: !30, size: 32)
+!35 = !DIDerivedType(tag: DW_TAG_member, name: "flags", scope: !32, file: !1, line: 6, baseType: !30, size: 32, offset: 32)
+!36 = !DISubrange(count: 2)
@@ -0,0 +1,14 @@
+// Written in demangle.ll by hand, in SSA form.
+
+namespace foo {
+  int bar(const char *s);
+}
+
+namespace util {
+  int twice(int x) { return 2 * x; }
+  long twice(long x) { return 2 * x; }
+}
+
+int call_bar(const char *s) {
+  return foo::bar(s) + util::twice(1) + (int)util::twice(1L);
+}
@@ -0,0 +1,29 @@
+; ModuleID = 'demangle.cpp'
+source_filename = "demangle.cpp"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+declare i32 @_ZN3foo3barEPKc(i8*)
+
+define i32 @_ZN4util5twiceEi(i32 %x) {
+entry:
+  %r = mul nsw i32 %x, 2
+  ret i32 %r
+}
+
+define i64 @_ZN4util5twiceEl(i64 %x) {
+entry:
+  %r = mul nsw i64 %x, 2
+  ret i64 %r
+}
+
+define i32 @_Z8call_barPKc(i8* %s) {
+entry:
+  %a = call i32 @_ZN3foo3barEPKc(i8* %s)
+  %b = call i32 @_ZN4util5twiceEi(i32 1)
+  %c = call i64 @_ZN4util5twiceEl(i64 1)
+  %c32 = trunc i64 %c to i32
+  %ab = add i32 %a, %b
+  %r = add i32 %ab, %c32
+  ret i32 %r
+}
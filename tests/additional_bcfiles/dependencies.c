@@ -0,0 +1,11 @@
+// Written in dependencies.ll by hand, in SSA form.
+
+int g;
+
+static int leaf(int x) { return x * 3; }
+int caller(int x) { return leaf(x) + 1; }
+
+void writer(int x) { g = x; }
+int reader(void) { return g; }
+
+int alone(int x) { return x - 1; }
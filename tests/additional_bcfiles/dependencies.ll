@@ -0,0 +1,37 @@
+; ModuleID = 'dependencies.c'
+source_filename = "dependencies.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@g = global i32 0, align 4
+
+define internal i32 @leaf(i32 %x) {
+entry:
+  %r = mul nsw i32 %x, 3
+  ret i32 %r
+}
+
+define i32 @caller(i32 %x) {
+entry:
+  %y = call i32 @leaf(i32 %x)
+  %r = add nsw i32 %y, 1
+  ret i32 %r
+}
+
+define void @writer(i32 %x) {
+entry:
+  store i32 %x, i32* @g, align 4
+  ret void
+}
+
+define i32 @reader() {
+entry:
+  %r = load i32, i32* @g, align 4
+  ret i32 %r
+}
+
+define i32 @alone(i32 %x) {
+entry:
+  %r = sub nsw i32 %x, 1
+  ret i32 %r
+}
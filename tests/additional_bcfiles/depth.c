@@ -0,0 +1,16 @@
+// Two functions which each walk their own (uninitialized, for brevity) chain
+// of pointers, and so never share any data. Written in depth.ll by hand.
+
+void store_deep(int x) {
+  int ***p;
+  int **q = *p;
+  int *r = *q;
+  *r = x;
+}
+
+int load_deep(void) {
+  int ***p;
+  int **q = *p;
+  int *r = *q;
+  return *r;
+}
@@ -0,0 +1,22 @@
+; ModuleID = 'depth.c'
+source_filename = "depth.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define void @store_deep(i32 %x) {
+entry:
+  %p = alloca i32**, align 8
+  %q = load i32**, i32*** %p, align 8
+  %r = load i32*, i32** %q, align 8
+  store i32 %x, i32* %r, align 4
+  ret void
+}
+
+define i32 @load_deep() {
+entry:
+  %p = alloca i32**, align 8
+  %q = load i32**, i32*** %p, align 8
+  %r = load i32*, i32** %q, align 8
+  %v = load i32, i32* %r, align 4
+  ret i32 %v
+}
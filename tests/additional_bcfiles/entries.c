@@ -0,0 +1,11 @@
+// Written in entries.ll by hand, in SSA form.
+
+static int helper(int x) { return x + 1; }
+
+int handle_get(int id) {
+  return helper(id);
+}
+
+int handle_post(int *body) {
+  return helper(*body);
+}
@@ -0,0 +1,23 @@
+; ModuleID = 'entries.c'
+source_filename = "entries.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+define internal i32 @helper(i32 %x) {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+define i32 @handle_get(i32 %id) {
+entry:
+  %r = call i32 @helper(i32 %id)
+  ret i32 %r
+}
+
+define i32 @handle_post(i32* %body) {
+entry:
+  %v = load i32, i32* %body, align 4
+  %r = call i32 @helper(i32 %v)
+  ret i32 %r
+}
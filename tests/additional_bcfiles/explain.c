@@ -0,0 +1,14 @@
+// Written in explain.ll by hand, in SSA form.
+
+int twice(int x) {
+  return x * 2;
+}
+
+int run(int secret, int public_) {
+  int slot;
+  int d = twice(secret);
+  slot = d;
+  int v = slot;
+  int r = v + public_;
+  return r;
+}
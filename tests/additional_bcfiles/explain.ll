@@ -0,0 +1,20 @@
+; ModuleID = 'explain.c'
+source_filename = "explain.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @twice(i32 %x) {
+entry:
+  %y = mul nsw i32 %x, 2
+  ret i32 %y
+}
+
+define i32 @run(i32 %secret, i32 %public_) {
+entry:
+  %slot = alloca i32, align 4
+  %d = call i32 @twice(i32 %secret)
+  store i32 %d, i32* %slot, align 4
+  %v = load i32, i32* %slot, align 4
+  %r = add nsw i32 %v, %public_
+  ret i32 %r
+}
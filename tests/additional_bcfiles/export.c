@@ -0,0 +1,16 @@
+// Written in export.ll by hand, in SSA form, with debug locations. There, the
+// product in `scale` is named `%"x,y"`, a name which needs quoting in CSV.
+
+struct pair {
+  int first;
+  int second;
+};
+
+int counter;
+
+int scale(struct pair *p, int k) {
+  int a = p->first;
+  int xy = a * k;
+  counter = xy;
+  return p->second;
+}
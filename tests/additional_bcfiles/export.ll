@@ -0,0 +1,39 @@
+; ModuleID = 'export.c'
+source_filename = "export.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+%struct.pair = type { i32, i32 }
+
+@counter = global i32 0, align 4, !dbg !0
+
+define i32 @scale(%struct.pair* %p, i32 %k) !dbg !10 {
+entry:
+  %first = getelementptr inbounds %struct.pair, %struct.pair* %p, i32 0, i32 0, !dbg !14
+  %a = load i32, i32* %first, align 4, !dbg !14
+  %"x,y" = mul nsw i32 %a, %k, !dbg !15
+  store i32 %"x,y", i32* @counter, align 4, !dbg !16
+  %second = getelementptr inbounds %struct.pair, %struct.pair* %p, i32 0, i32 1, !dbg !17
+  %r = load i32, i32* %second, align 4, !dbg !17
+  ret i32 %r, !dbg !17
+}
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!7, !8}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "counter", scope: !2, file: !3, line: 9, type: !6, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, producer: "hand-written", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, enums: !4, globals: !5)
+!3 = !DIFile(filename: "export.c", directory: "/tmp")
+!4 = !{}
+!5 = !{!0}
+!6 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!7 = !{i32 7, !"Dwarf Version", i32 4}
+!8 = !{i32 2, !"Debug Info Version", i32 3}
+!10 = distinct !DISubprogram(name: "scale", scope: !3, file: !3, line: 11, type: !11, scopeLine: 11, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !4)
+!11 = !DISubroutineType(types: !12)
+!12 = !{!6}
+!14 = !DILocation(line: 12, column: 14, scope: !10)
+!15 = !DILocation(line: 13, column: 15, scope: !10)
+!16 = !DILocation(line: 14, column: 11, scope: !10)
+!17 = !DILocation(line: 15, column: 13, scope: !10)
@@ -0,0 +1,21 @@
+// Written in ext_attrs.ll by hand, in SSA form.
+// The calls of `lookup` are `readnone`, and `my_alloc` returns `noalias`.
+
+int lookup(int *p, int i) __attribute__((const));
+void *my_alloc(long n) __attribute__((malloc));
+
+// `lookup` can't read `*p`, so only the address matters
+int lookup_contents(int x) {
+  int p = x;
+  return lookup(&p, 0);
+}
+
+int lookup_value(int x) {
+  int p;
+  return lookup(&p, x);
+}
+
+// the pointer `my_alloc` returns doesn't depend on `n`
+void *alloc(long n) {
+  return my_alloc(n);
+}
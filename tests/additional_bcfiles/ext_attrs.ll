@@ -0,0 +1,31 @@
+; ModuleID = 'ext_attrs.c'
+source_filename = "ext_attrs.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+declare i32 @lookup(i32*, i32)
+
+declare noalias i8* @my_alloc(i64)
+
+define i32 @lookup_contents(i32 %x) {
+entry:
+  %p = alloca i32
+  store i32 %x, i32* %p
+  %r = call i32 @lookup(i32* %p, i32 0) #0
+  ret i32 %r
+}
+
+define i32 @lookup_value(i32 %x) {
+entry:
+  %p = alloca i32
+  %r = call i32 @lookup(i32* %p, i32 %x) #0
+  ret i32 %r
+}
+
+define i8* @alloc(i64 %n) {
+entry:
+  %m = call noalias i8* @my_alloc(i64 %n)
+  ret i8* %m
+}
+
+attributes #0 = { readnone }
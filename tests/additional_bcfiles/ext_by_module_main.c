@@ -0,0 +1,10 @@
+// Written in ext_by_module_main.ll by hand, in SSA form.
+
+int mine(int x);
+int vendor_entry(int x);
+
+int entry(int x) {
+  int v = vendor_entry(x);
+  int m = mine(x);
+  return v + m;
+}
@@ -0,0 +1,20 @@
+; ModuleID = 'ext_by_module_main.c'
+source_filename = "ext_by_module_main.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @entry(i32 %x) #0 {
+entry:
+  %v = call i32 @vendor_entry(i32 %x)
+  %m = call i32 @mine(i32 %x)
+  %r = add nsw i32 %v, %m
+  ret i32 %r
+}
+
+declare i32 @vendor_entry(i32) #1
+
+declare i32 @mine(i32) #1
+
+attributes #0 = { noinline nounwind ssp uwtable }
+attributes #1 = { nounwind }
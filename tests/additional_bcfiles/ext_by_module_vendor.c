@@ -0,0 +1,7 @@
+// Written in ext_by_module_vendor.ll by hand, in SSA form.
+
+int blob_helper(int x);
+
+int vendor_entry(int x) {
+  return blob_helper(x);
+}
@@ -0,0 +1,13 @@
+// Written in fn_ptr_tables.ll by hand, in SSA form.
+
+typedef int (*handler_t)(int);
+
+static int double_it(int x) { return x * 2; }
+static int identity(int x) { return x; }
+int other(int x) { return x + 1; }
+
+static handler_t handlers[] = { double_it, identity, 0 };
+
+int dispatch(int i, int x) {
+  return handlers[i](x);
+}
@@ -0,0 +1,32 @@
+; ModuleID = 'fn_ptr_tables.c'
+source_filename = "fn_ptr_tables.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@handlers = internal global [3 x i32 (i32)*] [i32 (i32)* @double_it, i32 (i32)* @identity, i32 (i32)* null], align 16
+
+define internal i32 @double_it(i32 %x) {
+entry:
+  %r = mul nsw i32 %x, 2
+  ret i32 %r
+}
+
+define internal i32 @identity(i32 %x) {
+entry:
+  ret i32 %x
+}
+
+define i32 @other(i32 %x) {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+define i32 @dispatch(i32 %i, i32 %x) {
+entry:
+  %idx = sext i32 %i to i64
+  %slot = getelementptr inbounds [3 x i32 (i32)*], [3 x i32 (i32)*]* @handlers, i64 0, i64 %idx
+  %fp = load i32 (i32)*, i32 (i32)** %slot, align 8
+  %r = call i32 %fp(i32 %x)
+  ret i32 %r
+}
@@ -0,0 +1,14 @@
+// Written in fn_ptrs.ll by hand, in SSA form.
+
+static int inc(int x) { return x + 1; }
+static int dec(int x) { return x - 1; }
+
+typedef int (*op_t)(int);
+
+int apply(int c, int x) {
+  op_t f = c ? inc : dec;
+  op_t slot = f;
+  op_t g = slot;
+  char (*h)(char) = (char (*)(char))inc;
+  return g(x);
+}
@@ -0,0 +1,28 @@
+; ModuleID = 'fn_ptrs.c'
+source_filename = "fn_ptrs.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+define internal i32 @inc(i32 %x) {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+define internal i32 @dec(i32 %x) {
+entry:
+  %r = sub nsw i32 %x, 1
+  ret i32 %r
+}
+
+define i32 @apply(i32 %c, i32 %x) {
+entry:
+  %slot = alloca i32 (i32)*, align 8
+  %tobool = icmp ne i32 %c, 0
+  %f = select i1 %tobool, i32 (i32)* @inc, i32 (i32)* @dec
+  store i32 (i32)* %f, i32 (i32)** %slot, align 8
+  %g = load i32 (i32)*, i32 (i32)** %slot, align 8
+  %h = bitcast i32 (i32)* @inc to i8 (i8)*
+  %r = call i32 %g(i32 %x)
+  ret i32 %r
+}
@@ -0,0 +1,24 @@
+// Written in format_functions.ll by hand, in SSA form.
+
+#include <stdio.h>
+
+int parse(const char *s) {
+  int x;
+  sscanf(s, "%d", &x);
+  return x;
+}
+
+int read_stdin(void) {
+  int x;
+  scanf("%d", &x);
+  return x;
+}
+
+char format(char *buf, int x) {
+  snprintf(buf, 16, "%d", x);
+  return buf[0];
+}
+
+void log_message(const char *msg) {
+  printf(msg);
+}
@@ -0,0 +1,43 @@
+; ModuleID = 'format_functions.c'
+source_filename = "format_functions.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@.str = private unnamed_addr constant [3 x i8] c"%d\00", align 1
+
+declare i32 @sscanf(i8*, i8*, ...)
+
+declare i32 @scanf(i8*, ...)
+
+declare i32 @snprintf(i8*, i64, i8*, ...)
+
+declare i32 @printf(i8*, ...)
+
+define i32 @parse(i8* %s) {
+entry:
+  %x = alloca i32
+  %n = call i32 (i8*, i8*, ...) @sscanf(i8* %s, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str, i64 0, i64 0), i32* %x)
+  %r = load i32, i32* %x
+  ret i32 %r
+}
+
+define i32 @read_stdin() {
+entry:
+  %x = alloca i32
+  %n = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str, i64 0, i64 0), i32* %x)
+  %r = load i32, i32* %x
+  ret i32 %r
+}
+
+define signext i8 @format(i8* %buf, i32 %x) {
+entry:
+  %n = call i32 (i8*, i64, i8*, ...) @snprintf(i8* %buf, i64 16, i8* getelementptr inbounds ([3 x i8], [3 x i8]* @.str, i64 0, i64 0), i32 %x)
+  %r = load i8, i8* %buf
+  ret i8 %r
+}
+
+define void @log_message(i8* %msg) {
+entry:
+  %n = call i32 (i8*, ...) @printf(i8* %msg)
+  ret void
+}
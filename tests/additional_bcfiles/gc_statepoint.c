@@ -0,0 +1,19 @@
+// Written in gc_statepoint.ll by hand, in SSA form. The calls in
+// gc_statepoint.ll go through the GC statepoint intrinsics, as emitted by
+// frontends for garbage-collected languages, which C has no syntax for.
+
+int add_one(int x) {
+  return x + 1;
+}
+
+void safepoint(void) {
+}
+
+int call_add_one(int x) {
+  return add_one(x);  // wrapped in a statepoint, with the result from gc.result
+}
+
+int load_relocated(int* p) {
+  safepoint();  // wrapped in a statepoint, with p in its "gc-live" bundle
+  return *p;  // through p as relocated by gc.relocate
+}
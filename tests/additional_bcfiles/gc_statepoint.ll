@@ -0,0 +1,44 @@
+; ModuleID = 'gc_statepoint.c'
+source_filename = "gc_statepoint.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @add_one(i32 %x) #0 {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define void @safepoint() #0 {
+entry:
+  ret void
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @call_add_one(i32 %x) #0 gc "statepoint-example" {
+entry:
+  %tok = call token (i64, i32, i32 (i32)*, i32, i32, ...) @llvm.experimental.gc.statepoint.p0f_i32i32f(i64 0, i32 0, i32 (i32)* @add_one, i32 1, i32 0, i32 %x, i32 0, i32 0)
+  %r = call i32 @llvm.experimental.gc.result.i32(token %tok)
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @load_relocated(i32 addrspace(1)* %p) #0 gc "statepoint-example" {
+entry:
+  %tok = call token (i64, i32, void ()*, i32, i32, ...) @llvm.experimental.gc.statepoint.p0f_isVoidf(i64 0, i32 0, void ()* @safepoint, i32 0, i32 0, i32 0, i32 0) [ "gc-live"(i32 addrspace(1)* %p) ]
+  %p.rel = call i32 addrspace(1)* @llvm.experimental.gc.relocate.p1i32(token %tok, i32 0, i32 0)
+  %v = load i32, i32 addrspace(1)* %p.rel, align 4
+  ret i32 %v
+}
+
+declare token @llvm.experimental.gc.statepoint.p0f_i32i32f(i64 immarg, i32 immarg, i32 (i32)*, i32 immarg, i32 immarg, ...)
+
+declare token @llvm.experimental.gc.statepoint.p0f_isVoidf(i64 immarg, i32 immarg, void ()*, i32 immarg, i32 immarg, ...)
+
+declare i32 @llvm.experimental.gc.result.i32(token)
+
+declare i32 addrspace(1)* @llvm.experimental.gc.relocate.p1i32(token, i32 immarg, i32 immarg)
+
+attributes #0 = { noinline nounwind ssp uwtable }
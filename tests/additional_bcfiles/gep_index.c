@@ -0,0 +1,7 @@
+// Written in gep_index.ll by hand, in SSA form.
+
+int table[256];
+
+int lookup(int *base, unsigned char i) {
+  return table[i] + base[4];
+}
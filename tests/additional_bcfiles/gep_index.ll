@@ -0,0 +1,20 @@
+; ModuleID = 'gep_index.c'
+source_filename = "gep_index.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@table = dso_local global [256 x i32] zeroinitializer, align 16
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @lookup(i32* %base, i8 zeroext %i) #0 {
+entry:
+  %idx = zext i8 %i to i64
+  %p = getelementptr inbounds [256 x i32], [256 x i32]* @table, i64 0, i64 %idx
+  %v = load i32, i32* %p, align 4
+  %q = getelementptr inbounds i32, i32* %base, i64 4
+  %w = load i32, i32* %q, align 4
+  %r = add nsw i32 %v, %w
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
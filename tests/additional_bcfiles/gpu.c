@@ -0,0 +1,18 @@
+// Written in gpu.ll by hand, in SSA form.
+
+int get_input(void);
+
+__device__ int token;
+__device__ int out[32];
+
+__device__ void produce(void) {
+  token = get_input();
+}
+
+__global__ void kernel(void) {
+  __shared__ int tile[32];
+  unsigned tid = threadIdx.x;
+  tile[tid] = token;
+  __syncthreads();
+  out[tid] = tile[tid];
+}
@@ -0,0 +1,44 @@
+; ModuleID = 'gpu.c'
+source_filename = "gpu.c"
+target datalayout = "e-i64:64-i128:128-v16:16-v32:32-n16:32:64"
+target triple = "nvptx64-nvidia-cuda"
+
+@token = addrspace(1) global i32 0, align 4
+@out = addrspace(1) global [32 x i32] zeroinitializer, align 4
+@tile = internal addrspace(3) global [32 x i32] undef, align 4
+
+; Function Attrs: noinline nounwind
+define void @produce() #0 {
+entry:
+  %input = call i32 @get_input()
+  store i32 %input, i32 addrspace(1)* @token, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind
+define ptx_kernel void @kernel() #0 {
+entry:
+  %tid = call i32 @llvm.nvvm.read.ptx.sreg.tid.x()
+  %idx = zext i32 %tid to i64
+  %v = load i32, i32 addrspace(1)* @token, align 4
+  %slot = getelementptr inbounds [32 x i32], [32 x i32] addrspace(3)* @tile, i64 0, i64 %idx
+  store i32 %v, i32 addrspace(3)* %slot, align 4
+  call void @llvm.nvvm.barrier0()
+  %w = load i32, i32 addrspace(3)* %slot, align 4
+  %dst = getelementptr inbounds [32 x i32], [32 x i32] addrspace(1)* @out, i64 0, i64 %idx
+  store i32 %w, i32 addrspace(1)* %dst, align 4
+  ret void
+}
+
+declare i32 @get_input() #1
+
+; Function Attrs: nounwind readnone
+declare i32 @llvm.nvvm.read.ptx.sreg.tid.x() #2
+
+; Function Attrs: convergent nounwind
+declare void @llvm.nvvm.barrier0() #3
+
+attributes #0 = { noinline nounwind }
+attributes #1 = { nounwind }
+attributes #2 = { nounwind readnone }
+attributes #3 = { convergent nounwind }
@@ -0,0 +1,17 @@
+// Written in gpu_builtins.ll by hand, in SSA form.
+
+int read_secret(void);
+
+__kernel void lookup(int *table, int *out, int *counter, const int *src, int *tile) {
+  size_t gid = get_global_id(0);
+  int secret = read_secret();
+  int shared = work_group_broadcast(secret, 0);
+  barrier(CLK_LOCAL_MEM_FENCE);
+  int old = atomic_add(counter, secret);
+  event_t event = async_work_group_copy(tile, src, 64, 0);
+  int t = tile[gid];
+  int v = table[shared];
+  if (v > 0) {
+    out[gid] = t;
+  }
+}
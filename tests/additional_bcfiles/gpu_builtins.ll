@@ -0,0 +1,47 @@
+; ModuleID = 'gpu_builtins.c'
+source_filename = "gpu_builtins.c"
+target datalayout = "e-i64:64-v16:16-v24:32-v32:32-v48:64-v96:128-v192:256-v256:256-v512:512-v1024:1024"
+target triple = "spir64-unknown-unknown"
+
+%opencl.event_t = type opaque
+
+; Function Attrs: noinline nounwind
+define spir_kernel void @lookup(i32* %table, i32* %out, i32* %counter, i32* %src, i32* %tile) #0 {
+entry:
+  %gid = call i64 @_Z13get_global_idj(i32 0)
+  %secret = call i32 @read_secret()
+  %shared = call i32 @_Z20work_group_broadcastim(i32 %secret, i64 0)
+  call void @_Z7barrierj(i32 1)
+  %old = call i32 @_Z10atomic_addPVii(i32* %counter, i32 %secret)
+  %event = call %opencl.event_t* @_Z21async_work_group_copyPiPKim9ocl_event(i32* %tile, i32* %src, i64 64, %opencl.event_t* null)
+  %slot = getelementptr inbounds i32, i32* %tile, i64 %gid
+  %t = load i32, i32* %slot, align 4
+  %idx = sext i32 %shared to i64
+  %entry_ptr = getelementptr inbounds i32, i32* %table, i64 %idx
+  %v = load i32, i32* %entry_ptr, align 4
+  %pos = icmp sgt i32 %v, 0
+  br i1 %pos, label %if.then, label %if.end
+
+if.then:
+  %dst = getelementptr inbounds i32, i32* %out, i64 %gid
+  store i32 %t, i32* %dst, align 4
+  br label %if.end
+
+if.end:
+  ret void
+}
+
+declare i64 @_Z13get_global_idj(i32) #1
+
+declare i32 @read_secret() #1
+
+declare i32 @_Z20work_group_broadcastim(i32, i64) #1
+
+declare void @_Z7barrierj(i32) #1
+
+declare i32 @_Z10atomic_addPVii(i32*, i32) #1
+
+declare %opencl.event_t* @_Z21async_work_group_copyPiPKim9ocl_event(i32*, i32*, i64, %opencl.event_t*) #1
+
+attributes #0 = { noinline nounwind }
+attributes #1 = { nounwind }
@@ -0,0 +1,23 @@
+// Written in implicit.ll by hand, in SSA form.
+
+// Which value `i` gets depends only on `y`, even though it's computed only
+// when `x > 0`; but which value `m` gets depends on `x`.
+int implicit(int x, int y) {
+  int m;
+  if (x > 0) {
+    int i = (y > 0) ? 1 : 2;
+    m = i;
+  } else {
+    m = 3;
+  }
+  return m;
+}
+
+// The number of iterations, and so `n`, depends on `x`.
+int count(int x) {
+  int n = 0;
+  while (n + 1 < x) {
+    n++;
+  }
+  return n + 1;
+}
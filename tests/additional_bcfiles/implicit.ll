@@ -0,0 +1,48 @@
+; ModuleID = 'implicit.c'
+source_filename = "implicit.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @implicit(i32 %x, i32 %y) {
+entry:
+  %cx = icmp sgt i32 %x, 0
+  br i1 %cx, label %inner, label %other
+
+inner:
+  %cy = icmp sgt i32 %y, 0
+  br i1 %cy, label %p, label %q
+
+p:
+  br label %innermerge
+
+q:
+  br label %innermerge
+
+innermerge:
+  %i = phi i32 [ 1, %p ], [ 2, %q ]
+  br label %merge
+
+other:
+  br label %merge
+
+merge:
+  %m = phi i32 [ %i, %innermerge ], [ 3, %other ]
+  ret i32 %m
+}
+
+define i32 @count(i32 %x) {
+entry:
+  br label %header
+
+header:
+  %n = phi i32 [ 0, %entry ], [ %n1, %latch ]
+  %n1 = add nsw i32 %n, 1
+  %c = icmp slt i32 %n1, %x
+  br i1 %c, label %latch, label %exit
+
+latch:
+  br label %header
+
+exit:
+  ret i32 %n1
+}
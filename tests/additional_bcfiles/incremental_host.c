@@ -0,0 +1,9 @@
+// Written in incremental_host.ll by hand, in SSA form.
+// plugin_id() is defined in incremental_plugin.c, which the test adds to the
+// analysis only after analyzing this module on its own.
+
+int plugin_id(int x);
+
+int host(int x) {
+  return plugin_id(x);
+}
@@ -0,0 +1,12 @@
+; ModuleID = 'incremental_host.c'
+source_filename = "incremental_host.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @host(i32 %x) {
+entry:
+  %r = call i32 @plugin_id(i32 %x)
+  ret i32 %r
+}
+
+declare i32 @plugin_id(i32)
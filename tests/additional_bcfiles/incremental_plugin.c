@@ -0,0 +1,12 @@
+// Written in incremental_plugin.ll by hand, in SSA form.
+
+int secret(void);
+
+int plugin_id(int x) {
+  return x;
+}
+
+// Not called by the host
+int plugin_other(void) {
+  return secret();
+}
@@ -0,0 +1,17 @@
+; ModuleID = 'incremental_plugin.c'
+source_filename = "incremental_plugin.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @plugin_id(i32 %x) {
+entry:
+  ret i32 %x
+}
+
+define i32 @plugin_other() {
+entry:
+  %r = call i32 @secret()
+  ret i32 %r
+}
+
+declare i32 @secret()
@@ -0,0 +1,17 @@
+// Written in indirect_calls.ll by hand, in SSA form.
+
+int add_one(int x) {
+  return x + 1;
+}
+
+long constant(long x) {
+  return 0;
+}
+
+int call_add_one(int (*f)(int), int x) {
+  return f(x);
+}
+
+long call_constant(long (*f)(long), long x) {
+  return f(x);
+}
@@ -0,0 +1,33 @@
+; ModuleID = 'indirect_calls.c'
+source_filename = "indirect_calls.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @add_one(i32 %x) #0 {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i64 @constant(i64 %x) #0 {
+entry:
+  ret i64 0
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @call_add_one(i32 (i32)* %f, i32 %x) #0 {
+entry:
+  %r = call i32 %f(i32 %x)
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i64 @call_constant(i64 (i64)* %f, i64 %x) #0 {
+entry:
+  %r = call i64 %f(i64 %x)
+  ret i64 %r
+}
+
+attributes #0 = { noinline nounwind ssp uwtable }
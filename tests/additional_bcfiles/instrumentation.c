@@ -0,0 +1,28 @@
+// Written in instrumentation.ll by hand, in SSA form.
+
+#include <stdlib.h>
+
+int secret_copy;
+int counter;
+
+int helper(int x) {
+  return x + 1;
+}
+
+int untouched(int x) {
+  return x * 3;
+}
+
+int never_called(void) {
+  return 0;
+}
+
+int entry(int secret) {
+  int buf = secret;
+  int scratch = 1;
+  int *mem = malloc(sizeof(int));
+  *mem = secret;
+  secret_copy = secret;
+  counter = untouched(scratch);
+  return helper(buf);
+}
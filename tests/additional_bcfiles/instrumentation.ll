@@ -0,0 +1,51 @@
+; ModuleID = 'instrumentation.c'
+source_filename = "instrumentation.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@secret_copy = dso_local global i32 0, align 4
+@counter = dso_local global i32 0, align 4
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @helper(i32 %x) #0 {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @untouched(i32 %x) #0 {
+entry:
+  %r = mul nsw i32 %x, 3
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @never_called() #0 {
+entry:
+  ret i32 0
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @entry(i32 %secret) #0 {
+entry:
+  %buf = alloca i32, align 4
+  %scratch = alloca i32, align 4
+  store i32 %secret, i32* %buf, align 4
+  store i32 1, i32* %scratch, align 4
+  %mem = call noalias i8* @malloc(i64 4) #1
+  %typed = bitcast i8* %mem to i32*
+  store i32 %secret, i32* %typed, align 4
+  store i32 %secret, i32* @secret_copy, align 4
+  %s = load i32, i32* %scratch, align 4
+  %n = call i32 @untouched(i32 %s)
+  store i32 %n, i32* @counter, align 4
+  %b = load i32, i32* %buf, align 4
+  %c = call i32 @helper(i32 %b)
+  ret i32 %c
+}
+
+declare dso_local noalias i8* @malloc(i64) #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,81 @@
+#include <string.h>
+
+// Each of these copies or sets a single char, so that the copied-to location
+// is the same `i8` the intrinsic's pointer argument points to.
+// The element-wise atomic variants have no C equivalent; they were added to
+// intrinsics.ll by hand, as copy_atomic() and set_atomic().
+
+char copy_inline(char x) {
+  char a = x;
+  char b;
+  __builtin_memcpy_inline(&b, &a, 1);
+  return b;
+}
+
+char copy_untainted(char x) {
+  char a = 3;
+  char b;
+  memcpy(&b, &a, 1);
+  return b;
+}
+
+char move(char x) {
+  char a = x;
+  char b;
+  memmove(&b, &a, 1);
+  return b;
+}
+
+char set_inline(char x) {
+  char b;
+  __builtin_memset_inline(&b, x, 1);
+  return b;
+}
+
+// The variable-length array makes clang save and restore the stack pointer
+// with llvm.stacksave and llvm.stackrestore. llvm.donothing and
+// llvm.sideeffect have no C equivalent; they were added to intrinsics.ll by
+// hand.
+char noop_intrinsics(char x) {
+  char vla[x];
+  __builtin_prefetch(vla);
+  __builtin_assume(x != 0);
+  __builtin_ia32_pause();
+  return x;
+}
+
+// Named register variables make clang read and write the stack pointer with
+// llvm.read_register and llvm.write_register. (Clang only supports this for
+// the stack pointer, and only on some targets, so these were added to
+// intrinsics.ll by hand.)
+long read_sp(void) {
+  register long sp __asm__("sp");
+  return sp;
+}
+
+void write_sp(long x) {
+  register long sp __asm__("sp");
+  sp = x + 16;
+}
+
+// The result depends only on y, through ctpop, cttz, and abs. Clang lowers
+// __builtin_abs to a compare and select rather than llvm.abs, so
+// intrinsics.ll calls llvm.abs by hand.
+int bit_manipulation(int x, int y) {
+  int a = __builtin_bswap32(x);
+  int b = __builtin_popcount(y);
+  int c = __builtin_clz(x);
+  int d = __builtin_ctz(y);
+  int e = __builtin_bitreverse32(x);
+  int f = __builtin_abs(y);
+  return b + d + f;
+}
+
+// Clang emits llvm.fshl and llvm.fshr for these rotates at -O1 and above; at
+// -O0 it emits shifts and ors, so intrinsics.ll calls them by hand. The
+// vector llvm.fshl on constants was also added by hand.
+unsigned funnel_shift(unsigned x, unsigned y, unsigned amt) {
+  unsigned a = (x << (amt & 31)) | (x >> ((32 - amt) & 31));
+  unsigned b = (y >> 7) | (y << 25);
+  return b;
+}
@@ -0,0 +1,191 @@
+; ModuleID = 'intrinsics.c'
+source_filename = "intrinsics.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @copy_inline(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  %3 = alloca i8, align 1
+  store i8 %0, i8* %2, align 1
+  call void @llvm.memcpy.inline.p0i8.p0i8.i64(i8* align 1 %3, i8* align 1 %2, i64 1, i1 false)
+  %4 = load i8, i8* %3, align 1
+  ret i8 %4
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @copy_untainted(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  %3 = alloca i8, align 1
+  store i8 3, i8* %2, align 1
+  call void @llvm.memcpy.p0i8.p0i8.i64(i8* align 1 %3, i8* align 1 %2, i64 1, i1 false)
+  %4 = load i8, i8* %3, align 1
+  ret i8 %4
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @move(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  %3 = alloca i8, align 1
+  store i8 %0, i8* %2, align 1
+  call void @llvm.memmove.p0i8.p0i8.i64(i8* align 1 %3, i8* align 1 %2, i64 1, i1 false)
+  %4 = load i8, i8* %3, align 1
+  ret i8 %4
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @set_inline(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  call void @llvm.memset.inline.p0i8.i64(i8* align 1 %2, i8 %0, i64 1, i1 false)
+  %3 = load i8, i8* %2, align 1
+  ret i8 %3
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @copy_atomic(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  %3 = alloca i8, align 1
+  store i8 %0, i8* %2, align 1
+  call void @llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64(i8* align 1 %3, i8* align 1 %2, i64 1, i32 1)
+  %4 = load i8, i8* %3, align 1
+  ret i8 %4
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @set_atomic(i8 signext) #0 {
+  %2 = alloca i8, align 1
+  call void @llvm.memset.element.unordered.atomic.p0i8.i64(i8* align 1 %2, i8 %0, i64 1, i32 1)
+  %3 = load i8, i8* %2, align 1
+  ret i8 %3
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define signext i8 @noop_intrinsics(i8 signext) #0 {
+  %2 = call i8* @llvm.stacksave()
+  %3 = zext i8 %0 to i64
+  %4 = alloca i8, i64 %3, align 16
+  call void @llvm.prefetch.p0i8(i8* %4, i32 0, i32 3, i32 1)
+  %5 = icmp ne i8 %0, 0
+  call void @llvm.assume(i1 %5)
+  call void @llvm.donothing()
+  call void @llvm.sideeffect()
+  call void @llvm.x86.sse2.pause()
+  call void @llvm.stackrestore(i8* %2)
+  ret i8 %0
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i64 @read_sp() #0 {
+  %1 = call i64 @llvm.read_register.i64(metadata !0)
+  ret i64 %1
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define void @write_sp(i64) #0 {
+  %2 = add i64 %0, 16
+  call void @llvm.write_register.i64(metadata !0, i64 %2)
+  ret void
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @bit_manipulation(i32, i32) #0 {
+  %3 = call i32 @llvm.bswap.i32(i32 %0)
+  %4 = call i32 @llvm.ctpop.i32(i32 %1)
+  %5 = call i32 @llvm.ctlz.i32(i32 %0, i1 false)
+  %6 = call i32 @llvm.cttz.i32(i32 %1, i1 true)
+  %7 = call i32 @llvm.bitreverse.i32(i32 %0)
+  %8 = call i32 @llvm.abs.i32(i32 %1, i1 false)
+  %9 = add i32 %4, %6
+  %10 = add i32 %9, %8
+  ret i32 %10
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @funnel_shift(i32, i32, i32) #0 {
+  %4 = call i32 @llvm.fshl.i32(i32 %0, i32 %0, i32 %2)
+  %5 = call i32 @llvm.fshr.i32(i32 %1, i32 %1, i32 7)
+  %6 = call <2 x i32> @llvm.fshl.v2i32(<2 x i32> <i32 1, i32 2>, <2 x i32> <i32 3, i32 4>, <2 x i32> <i32 5, i32 6>)
+  ret i32 %5
+}
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memcpy.inline.p0i8.p0i8.i64(i8* noalias nocapture writeonly, i8* noalias nocapture readonly, i64 immarg, i1 immarg) #1
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memcpy.p0i8.p0i8.i64(i8* noalias nocapture writeonly, i8* noalias nocapture readonly, i64, i1 immarg) #1
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memmove.p0i8.p0i8.i64(i8* nocapture, i8* nocapture readonly, i64, i1 immarg) #1
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memset.inline.p0i8.i64(i8* nocapture writeonly, i8, i64 immarg, i1 immarg) #1
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64(i8* nocapture writeonly, i8* nocapture readonly, i64, i32 immarg) #1
+
+; Function Attrs: argmemonly nounwind
+declare void @llvm.memset.element.unordered.atomic.p0i8.i64(i8* nocapture writeonly, i8, i64, i32 immarg) #1
+
+; Function Attrs: nounwind
+declare i8* @llvm.stacksave() #2
+
+; Function Attrs: nounwind
+declare void @llvm.stackrestore(i8*) #2
+
+; Function Attrs: inaccessiblemem_or_argmemonly nounwind willreturn
+declare void @llvm.prefetch.p0i8(i8* nocapture readonly, i32 immarg, i32 immarg, i32 immarg) #3
+
+; Function Attrs: nounwind willreturn
+declare void @llvm.assume(i1 noundef) #2
+
+; Function Attrs: nounwind readnone
+declare void @llvm.donothing() #4
+
+; Function Attrs: inaccessiblememonly nounwind willreturn
+declare void @llvm.sideeffect() #2
+
+; Function Attrs: nounwind
+declare void @llvm.x86.sse2.pause() #2
+
+; Function Attrs: nounwind readonly
+declare i64 @llvm.read_register.i64(metadata) #5
+
+; Function Attrs: nounwind
+declare void @llvm.write_register.i64(metadata, i64) #2
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.bswap.i32(i32) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.ctpop.i32(i32) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.ctlz.i32(i32, i1 immarg) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.cttz.i32(i32, i1 immarg) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.bitreverse.i32(i32) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.abs.i32(i32, i1 immarg) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.fshl.i32(i32, i32, i32) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare i32 @llvm.fshr.i32(i32, i32, i32) #6
+
+; Function Attrs: nofree nosync nounwind readnone speculatable willreturn
+declare <2 x i32> @llvm.fshl.v2i32(<2 x i32>, <2 x i32>, <2 x i32>) #6
+
+attributes #0 = { noinline nounwind optnone ssp uwtable }
+attributes #1 = { argmemonly nounwind }
+attributes #2 = { nounwind }
+attributes #3 = { inaccessiblemem_or_argmemonly nounwind willreturn }
+attributes #4 = { nounwind readnone }
+attributes #5 = { nounwind readonly }
+attributes #6 = { nofree nosync nounwind readnone speculatable willreturn }
+
+!0 = !{!"sp"}
@@ -0,0 +1,19 @@
+// Written in jni.ll by hand, in SSA form. In jni.ll, the entries of the JNI
+// function table which aren't called here are declared as `i8*`.
+
+#include <jni.h>
+
+jchar first_char(JNIEnv *env, jstring s) {
+  const char *chars = (*env)->GetStringUTFChars(env, s, NULL);
+  return chars[0];
+}
+
+jsize array_length(JNIEnv *env, jbyteArray arr) {
+  return (*env)->GetArrayLength(env, arr);
+}
+
+jbyte first_byte(JNIEnv *env, jbyteArray arr) {
+  jbyte buf[16];
+  (*env)->GetByteArrayRegion(env, arr, 0, 16, buf);
+  return buf[0];
+}
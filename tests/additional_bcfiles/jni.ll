@@ -0,0 +1,44 @@
+; ModuleID = 'jni.c'
+source_filename = "jni.c"
+target datalayout = "e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128"
+target triple = "aarch64-unknown-linux-android21"
+
+%struct.JNINativeInterface_ = type { i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8* (%struct.JNINativeInterface_**, %struct._jobject*, i8*)*, i8*, i32 (%struct.JNINativeInterface_**, %struct._jobject*)*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, void (%struct.JNINativeInterface_**, %struct._jobject*, i32, i32, i8*)*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8*, i8* }
+%struct._jobject = type opaque
+
+; Function Attrs: noinline nounwind
+define zeroext i16 @first_char(%struct.JNINativeInterface_** %env, %struct._jobject* %s) #0 {
+entry:
+  %table = load %struct.JNINativeInterface_*, %struct.JNINativeInterface_** %env, align 8
+  %fn.addr = getelementptr inbounds %struct.JNINativeInterface_, %struct.JNINativeInterface_* %table, i32 0, i32 169
+  %fn = load i8* (%struct.JNINativeInterface_**, %struct._jobject*, i8*)*, i8* (%struct.JNINativeInterface_**, %struct._jobject*, i8*)** %fn.addr, align 8
+  %chars = call i8* %fn(%struct.JNINativeInterface_** %env, %struct._jobject* %s, i8* null)
+  %c = load i8, i8* %chars, align 1
+  %ret = zext i8 %c to i16
+  ret i16 %ret
+}
+
+; Function Attrs: noinline nounwind
+define i32 @array_length(%struct.JNINativeInterface_** %env, %struct._jobject* %arr) #0 {
+entry:
+  %table = load %struct.JNINativeInterface_*, %struct.JNINativeInterface_** %env, align 8
+  %fn.addr = getelementptr inbounds %struct.JNINativeInterface_, %struct.JNINativeInterface_* %table, i32 0, i32 171
+  %fn = load i32 (%struct.JNINativeInterface_**, %struct._jobject*)*, i32 (%struct.JNINativeInterface_**, %struct._jobject*)** %fn.addr, align 8
+  %len = call i32 %fn(%struct.JNINativeInterface_** %env, %struct._jobject* %arr)
+  ret i32 %len
+}
+
+; Function Attrs: noinline nounwind
+define signext i8 @first_byte(%struct.JNINativeInterface_** %env, %struct._jobject* %arr) #0 {
+entry:
+  %buf = alloca [16 x i8], align 1
+  %buf0 = getelementptr inbounds [16 x i8], [16 x i8]* %buf, i32 0, i32 0
+  %table = load %struct.JNINativeInterface_*, %struct.JNINativeInterface_** %env, align 8
+  %fn.addr = getelementptr inbounds %struct.JNINativeInterface_, %struct.JNINativeInterface_* %table, i32 0, i32 200
+  %fn = load void (%struct.JNINativeInterface_**, %struct._jobject*, i32, i32, i8*)*, void (%struct.JNINativeInterface_**, %struct._jobject*, i32, i32, i8*)** %fn.addr, align 8
+  call void %fn(%struct.JNINativeInterface_** %env, %struct._jobject* %arr, i32 0, i32 16, i8* %buf0)
+  %b = load i8, i8* %buf0, align 1
+  ret i8 %b
+}
+
+attributes #0 = { noinline nounwind }
@@ -0,0 +1,25 @@
+// Written in kernel.ll by hand, in SSA form.
+
+#include <linux/uaccess.h>
+
+long handle_ioctl(void __user *argp) {
+  char cmd;
+  if (copy_from_user(&cmd, argp, 1))
+    return -EFAULT;
+  return cmd;
+}
+
+long echo_ioctl(void __user *argp) {
+  char cmd;
+  char version = 3;
+  if (copy_from_user(&cmd, argp, 1))
+    return -EFAULT;
+  if (copy_to_user(argp, &version, 1))
+    return -EFAULT;
+  return copy_to_user(argp, &cmd, 1);
+}
+
+char dup_ioctl(void __user *argp) {
+  char *p = memdup_user(argp, 1);
+  return *p;
+}
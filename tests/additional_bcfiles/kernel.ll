@@ -0,0 +1,61 @@
+; ModuleID = 'kernel.c'
+source_filename = "kernel.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind
+define i64 @handle_ioctl(i8* %argp) #0 {
+entry:
+  %cmd = alloca i8, align 1
+  %left = call i64 @_copy_from_user(i8* %cmd, i8* %argp, i64 1)
+  %failed = icmp ne i64 %left, 0
+  br i1 %failed, label %fault, label %ok
+
+fault:
+  ret i64 -14
+
+ok:
+  %c = load i8, i8* %cmd, align 1
+  %ret = sext i8 %c to i64
+  ret i64 %ret
+}
+
+; Function Attrs: noinline nounwind
+define i64 @echo_ioctl(i8* %argp) #0 {
+entry:
+  %cmd = alloca i8, align 1
+  %version = alloca i8, align 1
+  store i8 3, i8* %version, align 1
+  %left = call i64 @_copy_from_user(i8* %cmd, i8* %argp, i64 1)
+  %failed = icmp ne i64 %left, 0
+  br i1 %failed, label %fault, label %send_version
+
+send_version:
+  %left1 = call i64 @_copy_to_user(i8* %argp, i8* %version, i64 1)
+  %failed1 = icmp ne i64 %left1, 0
+  br i1 %failed1, label %fault, label %send_cmd
+
+send_cmd:
+  %left2 = call i64 @_copy_to_user(i8* %argp, i8* %cmd, i64 1)
+  ret i64 %left2
+
+fault:
+  ret i64 -14
+}
+
+; Function Attrs: noinline nounwind
+define signext i8 @dup_ioctl(i8* %argp) #0 {
+entry:
+  %p = call i8* @memdup_user(i8* %argp, i64 1)
+  %v = load i8, i8* %p, align 1
+  ret i8 %v
+}
+
+declare i64 @_copy_from_user(i8*, i8*, i64) #1
+
+declare i64 @_copy_to_user(i8*, i8*, i64) #1
+
+declare i8* @memdup_user(i8*, i64) #1
+
+attributes #0 = { noinline nounwind }
+attributes #1 = { nounwind }
@@ -0,0 +1,7 @@
+// Written in lenient_joins.ll by hand, in SSA form.
+
+void first(char *p, int c) {
+  // `q` is given a different shape than `p + 1` has: a pointer to a struct
+  char *q = p + 1;
+  char *r = c ? q : 0;
+}
@@ -0,0 +1,21 @@
+; ModuleID = 'lenient_joins.c'
+source_filename = "lenient_joins.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+define void @first(i8* %p, i32 %c) {
+entry:
+  %q = getelementptr inbounds i8, i8* %p, i64 1
+  %tobool = icmp ne i32 %c, 0
+  br i1 %tobool, label %then, label %else
+
+then:
+  br label %join
+
+else:
+  br label %join
+
+join:
+  %r = phi i8* [ %q, %then ], [ null, %else ]
+  ret void
+}
@@ -0,0 +1,8 @@
+// Written in masks.ll by hand, in SSA form.
+
+int flags(int x) {
+  int flag = x & 1;
+  int nibble = x & 0xf;
+  int byte = x & 0xff;
+  return flag + nibble + byte;
+}
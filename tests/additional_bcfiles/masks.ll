@@ -0,0 +1,17 @@
+; ModuleID = 'masks.c'
+source_filename = "masks.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @flags(i32 %x) #0 {
+entry:
+  %flag = and i32 %x, 1
+  %nibble = and i32 15, %x
+  %byte = and i32 %x, 255
+  %r1 = add nsw i32 %flag, %nibble
+  %r = add nsw i32 %r1, %byte
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
@@ -0,0 +1,12 @@
+// Written in matrix.ll by hand, in SSA form.
+// Compile with -fenable-matrix.
+
+typedef double m2x2_t __attribute__((matrix_type(2, 2)));
+
+void mul(double *a, double *b, double *out) {
+  m2x2_t ma = __builtin_matrix_column_major_load(a, 2, 2, 2);
+  m2x2_t mb = __builtin_matrix_column_major_load(b, 2, 2, 2);
+  m2x2_t prod = ma * mb;
+  m2x2_t t = __builtin_matrix_transpose(prod);
+  __builtin_matrix_column_major_store(t, out, 2);
+}
@@ -0,0 +1,25 @@
+; ModuleID = 'matrix.c'
+source_filename = "matrix.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local void @mul(double* %a, double* %b, double* %out) #0 {
+entry:
+  %ma = call <4 x double> @llvm.matrix.column.major.load.v4f64.i64(double* %a, i64 2, i1 false, i32 2, i32 2)
+  %mb = call <4 x double> @llvm.matrix.column.major.load.v4f64.i64(double* %b, i64 2, i1 false, i32 2, i32 2)
+  %prod = call <4 x double> @llvm.matrix.multiply.v4f64.v4f64.v4f64(<4 x double> %ma, <4 x double> %mb, i32 2, i32 2, i32 2)
+  %t = call <4 x double> @llvm.matrix.transpose.v4f64(<4 x double> %prod, i32 2, i32 2)
+  call void @llvm.matrix.column.major.store.v4f64.i64(<4 x double> %t, double* %out, i64 2, i1 false, i32 2, i32 2)
+  ret void
+}
+
+declare <4 x double> @llvm.matrix.column.major.load.v4f64.i64(double* nocapture, i64, i1 immarg, i32 immarg, i32 immarg) #1
+declare <4 x double> @llvm.matrix.multiply.v4f64.v4f64.v4f64(<4 x double>, <4 x double>, i32 immarg, i32 immarg, i32 immarg) #3
+declare <4 x double> @llvm.matrix.transpose.v4f64(<4 x double>, i32 immarg, i32 immarg) #3
+declare void @llvm.matrix.column.major.store.v4f64.i64(<4 x double>, double* nocapture writeonly, i64, i1 immarg, i32 immarg, i32 immarg) #2
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { argmemonly nofree nosync nounwind readonly willreturn }
+attributes #2 = { argmemonly nofree nosync nounwind willreturn writeonly }
+attributes #3 = { nofree nosync nounwind readnone speculatable willreturn }
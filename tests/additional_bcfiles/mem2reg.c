@@ -0,0 +1,20 @@
+// Written in mem2reg.ll by hand, in SSA form, as clang -O0 would emit it.
+
+int read_input(void);
+
+int pick(int *secret, int *public) {
+  int *p = secret;
+  p = public;
+  return *p;
+}
+
+int reassign(int x) {
+  int y = x;
+  y = 0;
+  return y;
+}
+
+void fill(int *buf) {
+  int *b = buf;
+  *b = read_input();
+}
@@ -0,0 +1,41 @@
+; ModuleID = 'mem2reg.c'
+source_filename = "mem2reg.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind optnone uwtable
+define i32 @pick(i32* %secret, i32* %public) #0 {
+entry:
+  %p = alloca i32*, align 8
+  store i32* %secret, i32** %p, align 8
+  store i32* %public, i32** %p, align 8
+  %q = load i32*, i32** %p, align 8
+  %v = load i32, i32* %q, align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind optnone uwtable
+define i32 @reassign(i32 %x) #0 {
+entry:
+  %y = alloca i32, align 4
+  store i32 %x, i32* %y, align 4
+  store i32 0, i32* %y, align 4
+  %v = load i32, i32* %y, align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind optnone uwtable
+define void @fill(i32* %buf) #0 {
+entry:
+  %b = alloca i32*, align 8
+  store i32* %buf, i32** %b, align 8
+  %input = call i32 @read_input()
+  %ptr = load i32*, i32** %b, align 8
+  store i32 %input, i32* %ptr, align 4
+  ret void
+}
+
+declare i32 @read_input() #1
+
+attributes #0 = { noinline nounwind optnone uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,22 @@
+// Written in mmio.ll by hand, in SSA form.
+
+#define UART_DR (*(volatile unsigned *)0x40011004)
+
+volatile unsigned uart_status __attribute__((section(".periph")));
+
+unsigned read_uart(void) {
+  return UART_DR;
+}
+
+unsigned read_ram(void) {
+  return *(volatile unsigned *)0x20000000;
+}
+
+unsigned read_reg(void) {
+  volatile unsigned *base = (volatile unsigned *)0x40011000;
+  return base[2];
+}
+
+unsigned read_status(void) {
+  return uart_status;
+}
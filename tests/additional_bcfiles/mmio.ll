@@ -0,0 +1,38 @@
+; ModuleID = 'mmio.c'
+source_filename = "mmio.c"
+target datalayout = "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64"
+target triple = "thumbv7em-none-unknown-eabi"
+
+@uart_status = global i32 0, section ".periph", align 4
+
+; Function Attrs: noinline nounwind
+define i32 @read_uart() #0 {
+entry:
+  %v = load volatile i32, i32* inttoptr (i32 1073811460 to i32*), align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind
+define i32 @read_ram() #0 {
+entry:
+  %v = load volatile i32, i32* inttoptr (i32 536870912 to i32*), align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind
+define i32 @read_reg() #0 {
+entry:
+  %base = inttoptr i32 1073811456 to i32*
+  %reg = getelementptr inbounds i32, i32* %base, i32 2
+  %v = load volatile i32, i32* %reg, align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind
+define i32 @read_status() #0 {
+entry:
+  %v = load volatile i32, i32* @uart_status, align 4
+  ret i32 %v
+}
+
+attributes #0 = { noinline nounwind }
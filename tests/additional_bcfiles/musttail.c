@@ -0,0 +1,20 @@
+// Written in musttail.ll by hand, in SSA form, for arm64-apple-macosx, where
+// va_list is a plain `char*`. `forward()` is a thunk which forwards its
+// arguments, including the variadic ones, with a `musttail` call; C has no
+// way to write that directly.
+
+#include <stdarg.h>
+
+int first_vararg(int n, ...) {
+  va_list ap;
+  va_start(ap, n);
+  int x = va_arg(ap, int);
+  va_end(ap);
+  return x;
+}
+
+int forward(int n, ...);  // musttail call of first_vararg(n, ...)
+
+int caller(int x) {
+  return forward(1, x);
+}
@@ -0,0 +1,38 @@
+; ModuleID = 'musttail.c'
+source_filename = "musttail.c"
+target datalayout = "e-m:o-i64:64-i128:128-n32:64-S128"
+target triple = "arm64-apple-macosx11.0.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @first_vararg(i32 %n, ...) #0 {
+entry:
+  %ap = alloca i8*, align 8
+  %ap1 = bitcast i8** %ap to i8*
+  call void @llvm.va_start(i8* %ap1)
+  %x = va_arg i8** %ap, i32
+  call void @llvm.va_end(i8* %ap1)
+  ret i32 %x
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @forward(i32 %n, ...) #0 {
+entry:
+  %r = musttail call i32 (i32, ...) @first_vararg(i32 %n, ...)
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @caller(i32 %x) #0 {
+entry:
+  %r = call i32 (i32, ...) @forward(i32 1, i32 %x)
+  ret i32 %r
+}
+
+; Function Attrs: nounwind
+declare void @llvm.va_start(i8*) #1
+
+; Function Attrs: nounwind
+declare void @llvm.va_end(i8*) #1
+
+attributes #0 = { noinline nounwind ssp uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,20 @@
+// Written in parallel.ll by hand, in SSA form.
+// Many blocks which only do arithmetic (processed in parallel with
+// Config::parallel_blocks_threshold), a loop, and a store, which isn't.
+
+int dispatch(int op, int x, int y, int *out) {
+  int r;
+  switch (op) {
+    case 0: r = x + 1; break;
+    case 1: r = y * 2; break;
+    case 2: r = (x < y) ? y : 0; break;
+    case 3: r = y - 3; break;
+    default: r = 0; break;
+  }
+  int acc = r;
+  for (int i = 0; i < y; i++) {
+    acc = acc ^ i;
+  }
+  *out = acc;
+  return (y > 0) ? y + 5 : y;
+}
@@ -0,0 +1,56 @@
+; ModuleID = 'parallel.c'
+source_filename = "parallel.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @dispatch(i32 %op, i32 %x, i32 %y, i32* %out) {
+entry:
+  switch i32 %op, label %default [
+    i32 0, label %case0
+    i32 1, label %case1
+    i32 2, label %case2
+    i32 3, label %case3
+  ]
+
+case0:
+  %r0 = add nsw i32 %x, 1
+  br label %merge
+
+case1:
+  %r1 = mul nsw i32 %y, 2
+  br label %merge
+
+case2:
+  %c2 = icmp slt i32 %x, %y
+  %r2 = select i1 %c2, i32 %y, i32 0
+  br label %merge
+
+case3:
+  %r3 = sub nsw i32 %y, 3
+  br label %merge
+
+default:
+  br label %merge
+
+merge:
+  %r = phi i32 [ %r0, %case0 ], [ %r1, %case1 ], [ %r2, %case2 ], [ %r3, %case3 ], [ 0, %default ]
+  br label %loop
+
+loop:
+  %i = phi i32 [ 0, %merge ], [ %inext, %body ]
+  %acc = phi i32 [ %r, %merge ], [ %accnext, %body ]
+  %cond = icmp slt i32 %i, %y
+  br i1 %cond, label %body, label %exit
+
+body:
+  %accnext = xor i32 %acc, %i
+  %inext = add nsw i32 %i, 1
+  br label %loop
+
+exit:
+  store i32 %acc, i32* %out
+  %pos = icmp sgt i32 %y, 0
+  %y5 = add nsw i32 %y, 5
+  %ret = select i1 %pos, i32 %y5, i32 %y
+  ret i32 %ret
+}
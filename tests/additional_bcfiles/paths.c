@@ -0,0 +1,28 @@
+// Written in paths.ll by hand, in SSA form.
+
+int read_input(void);
+void write_output(int x);
+
+int get(void) {
+  return read_input();
+}
+
+int wrap(void) {
+  int g = get();
+  return g + 1;
+}
+
+void emit(int x) {
+  write_output(x);
+}
+
+void quiet(int x) {
+  write_output(0);
+}
+
+void run(void) {
+  int v = wrap();
+  emit(v);
+  quiet(v);
+  write_output(v);
+}
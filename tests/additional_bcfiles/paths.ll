@@ -0,0 +1,42 @@
+; ModuleID = 'paths.c'
+source_filename = "paths.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+declare i32 @read_input()
+
+declare void @write_output(i32)
+
+define i32 @get() {
+entry:
+  %r = call i32 @read_input()
+  ret i32 %r
+}
+
+define i32 @wrap() {
+entry:
+  %g = call i32 @get()
+  %r = add nsw i32 %g, 1
+  ret i32 %r
+}
+
+define void @emit(i32 %x) {
+entry:
+  call void @write_output(i32 %x)
+  ret void
+}
+
+define void @quiet(i32 %x) {
+entry:
+  call void @write_output(i32 0)
+  ret void
+}
+
+define void @run() {
+entry:
+  %v = call i32 @wrap()
+  call void @emit(i32 %v)
+  call void @quiet(i32 %v)
+  call void @write_output(i32 %v)
+  ret void
+}
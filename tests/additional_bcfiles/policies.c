@@ -0,0 +1,19 @@
+// Written in policies.ll by hand, in SSA form.
+
+int arith(int x, int y) {
+  int sum = x + y;
+  unsigned quot = (unsigned)x / (unsigned)y;
+  int sh = x << 1;
+  int cmp = x == y;
+  long ext = x;
+  return sum + quot + sh + cmp + (int)ext;
+}
+
+int same(int *a, int *b) {
+  return a == b;
+}
+
+int index(int *buf, long i) {
+  int *p = &buf[i];
+  return *p;
+}
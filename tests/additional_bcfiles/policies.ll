@@ -0,0 +1,39 @@
+; ModuleID = 'policies.c'
+source_filename = "policies.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @arith(i32 %x, i32 %y) #0 {
+entry:
+  %sum = add nsw i32 %x, %y
+  %quot = udiv i32 %x, %y
+  %sh = shl i32 %x, 1
+  %cmp = icmp eq i32 %x, %y
+  %cmp.ext = zext i1 %cmp to i32
+  %ext = sext i32 %x to i64
+  %ext.trunc = trunc i64 %ext to i32
+  %r1 = add i32 %sum, %quot
+  %r2 = add i32 %r1, %sh
+  %r3 = add i32 %r2, %cmp.ext
+  %r = add i32 %r3, %ext.trunc
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @same(i32* %a, i32* %b) #0 {
+entry:
+  %cmp = icmp eq i32* %a, %b
+  %r = zext i1 %cmp to i32
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @index(i32* %buf, i64 %i) #0 {
+entry:
+  %p = getelementptr inbounds i32, i32* %buf, i64 %i
+  %v = load i32, i32* %p, align 4
+  ret i32 %v
+}
+
+attributes #0 = { noinline nounwind uwtable }
@@ -0,0 +1,25 @@
+// Written in polymorphic.ll by hand, in SSA form.
+
+int secret(void);
+
+int id(int x) {
+  return x;
+}
+
+// Only the first call to id() passes it a secret
+int top(void) {
+  int a = id(secret());
+  int b = id(1);
+  return a + b;
+}
+
+// The secret is passed two calls deep, through wrap()
+int wrap(int x) {
+  return id(x);
+}
+
+int nested(void) {
+  int a = wrap(secret());
+  int b = wrap(2);
+  return a + b;
+}
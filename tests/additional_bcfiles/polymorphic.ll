@@ -0,0 +1,35 @@
+; ModuleID = 'polymorphic.c'
+source_filename = "polymorphic.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @id(i32 %x) {
+entry:
+  ret i32 %x
+}
+
+define i32 @top() {
+entry:
+  %s = call i32 @secret()
+  %a = call i32 @id(i32 %s)
+  %b = call i32 @id(i32 1)
+  %sum = add nsw i32 %a, %b
+  ret i32 %sum
+}
+
+define i32 @wrap(i32 %x) {
+entry:
+  %r = call i32 @id(i32 %x)
+  ret i32 %r
+}
+
+define i32 @nested() {
+entry:
+  %s = call i32 @secret()
+  %a = call i32 @wrap(i32 %s)
+  %b = call i32 @wrap(i32 2)
+  %sum = add nsw i32 %a, %b
+  ret i32 %sum
+}
+
+declare i32 @secret()
@@ -0,0 +1,13 @@
+// Written in predicates.ll by hand, in SSA form.
+
+int check(int x, double d) {
+  int eq = x == 42;
+  int same = d == 1.0;
+  int sign;
+  if (x < 0) {
+    sign = -1;
+  } else {
+    sign = 1;
+  }
+  return eq + same + sign;
+}
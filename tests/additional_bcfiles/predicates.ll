@@ -0,0 +1,29 @@
+; ModuleID = 'predicates.c'
+source_filename = "predicates.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @check(i32 %x, double %d) #0 {
+entry:
+  %eq = icmp eq i32 %x, 42
+  %same = fcmp oeq double %d, 1.000000e+00
+  %lt = icmp slt i32 %x, 0
+  br i1 %lt, label %neg, label %nonneg
+
+neg:
+  br label %done
+
+nonneg:
+  br label %done
+
+done:
+  %sign = phi i32 [ -1, %neg ], [ 1, %nonneg ]
+  %eq.ext = zext i1 %eq to i32
+  %same.ext = zext i1 %same to i32
+  %r1 = add nsw i32 %eq.ext, %same.ext
+  %r = add nsw i32 %r1, %sign
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
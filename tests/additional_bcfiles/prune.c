@@ -0,0 +1,16 @@
+// Written in prune.ll by hand, in SSA form.
+
+int secret(void);
+
+int helper(int x) {
+  return x + 1;
+}
+
+int entry(int x) {
+  return helper(x);
+}
+
+// Never called: with pruning, its secret never reaches helper()
+int dead(void) {
+  return helper(secret());
+}
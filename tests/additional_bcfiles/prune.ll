@@ -0,0 +1,25 @@
+; ModuleID = 'prune.c'
+source_filename = "prune.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @helper(i32 %x) {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+define i32 @entry(i32 %x) {
+entry:
+  %r = call i32 @helper(i32 %x)
+  ret i32 %r
+}
+
+define i32 @dead() {
+entry:
+  %s = call i32 @secret()
+  %r = call i32 @helper(i32 %s)
+  ret i32 %r
+}
+
+declare i32 @secret()
@@ -0,0 +1,14 @@
+// Written in recursion.ll by hand, in SSA form.
+
+int secret(void);
+
+// Each recursive call passes a secret, but ignores the result, so rec()
+// only returns a secret when its caller passed one in
+int rec(int n, int v) {
+  if (n > 0) rec(n - 1, secret());
+  return v;
+}
+
+int top(void) {
+  return rec(3, 1);
+}
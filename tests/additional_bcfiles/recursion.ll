@@ -0,0 +1,27 @@
+; ModuleID = 'recursion.c'
+source_filename = "recursion.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @rec(i32 %n, i32 %v) {
+entry:
+  %more = icmp sgt i32 %n, 0
+  br i1 %more, label %recurse, label %done
+
+recurse:
+  %m = sub nsw i32 %n, 1
+  %s = call i32 @secret()
+  %ignored = call i32 @rec(i32 %m, i32 %s)
+  br label %done
+
+done:
+  ret i32 %v
+}
+
+define i32 @top() {
+entry:
+  %r = call i32 @rec(i32 3, i32 1)
+  ret i32 %r
+}
+
+declare i32 @secret()
@@ -0,0 +1,17 @@
+// Written in renamed_structs_linked.ll by hand, in SSA form.
+// This stands in for a module linked from several contexts, where LLVM
+// renamed `struct point` and `struct line` to `struct.point.7` and
+// `struct.line.3`.
+
+struct point { int x; int y; };
+struct line { struct point a; struct point b; };
+
+int read_input(void);
+
+void read_point(struct point *p) {
+  p->x = read_input();
+}
+
+void read_line(struct line *l) {
+  l->b.y = read_input();
+}
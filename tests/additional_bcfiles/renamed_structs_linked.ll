@@ -0,0 +1,30 @@
+; ModuleID = 'renamed_structs_linked.c'
+source_filename = "renamed_structs_linked.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.point.7 = type { i32, i32 }
+%struct.line.3 = type { %struct.point.7, %struct.point.7 }
+
+; Function Attrs: noinline nounwind uwtable
+define void @read_point(%struct.point.7* %p) #0 {
+entry:
+  %input = call i32 @read_input()
+  %x = getelementptr inbounds %struct.point.7, %struct.point.7* %p, i32 0, i32 0
+  store i32 %input, i32* %x, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind uwtable
+define void @read_line(%struct.line.3* %l) #0 {
+entry:
+  %input = call i32 @read_input()
+  %y = getelementptr inbounds %struct.line.3, %struct.line.3* %l, i32 0, i32 1, i32 1
+  store i32 %input, i32* %y, align 4
+  ret void
+}
+
+declare i32 @read_input() #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,19 @@
+// Written in renamed_structs_main.ll by hand, in SSA form.
+
+struct point { int x; int y; };
+struct line { struct point a; struct point b; };
+
+void read_point(struct point *p);
+void read_line(struct line *l);
+
+int get_x(void) {
+  struct point p;
+  read_point(&p);
+  return p.x;
+}
+
+int get_end_y(void) {
+  struct line l;
+  read_line(&l);
+  return l.b.y;
+}
@@ -0,0 +1,34 @@
+; ModuleID = 'renamed_structs_main.c'
+source_filename = "renamed_structs_main.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.point = type { i32, i32 }
+%struct.line = type { %struct.point, %struct.point }
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @get_x() #0 {
+entry:
+  %p = alloca %struct.point, align 4
+  call void @read_point(%struct.point* %p)
+  %x = getelementptr inbounds %struct.point, %struct.point* %p, i32 0, i32 0
+  %v = load i32, i32* %x, align 4
+  ret i32 %v
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @get_end_y() #0 {
+entry:
+  %l = alloca %struct.line, align 4
+  call void @read_line(%struct.line* %l)
+  %y = getelementptr inbounds %struct.line, %struct.line* %l, i32 0, i32 1, i32 1
+  %v = load i32, i32* %y, align 4
+  ret i32 %v
+}
+
+declare void @read_point(%struct.point*) #1
+
+declare void @read_line(%struct.line*) #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
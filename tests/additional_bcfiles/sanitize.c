@@ -0,0 +1,23 @@
+// bounded() is compiled at -O0. The others are written as SSA, as they'd
+// appear after optimization, and were written in sanitize.ll by hand.
+
+#include <stdbool.h>
+
+bool is_valid(int x);
+int check(int x);
+
+int bounded(int x) {
+  if (x >= 16) return -1;
+  return x * 2;
+}
+
+int validated(int x) {
+  int z = x + 2;
+  if (is_valid(x)) return x + 1;
+  return 0;
+}
+
+int validated_int(int x) {
+  if (check(x) == 0) return 0;
+  return x + 1;
+}
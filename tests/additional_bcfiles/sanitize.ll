@@ -0,0 +1,68 @@
+; ModuleID = 'sanitize.c'
+source_filename = "sanitize.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @bounded(i32) #0 {
+  %2 = alloca i32, align 4
+  %3 = alloca i32, align 4
+  store i32 %0, i32* %3, align 4
+  %4 = load i32, i32* %3, align 4
+  %5 = icmp sge i32 %4, 16
+  br i1 %5, label %6, label %7
+
+6:                                                ; preds = %1
+  store i32 -1, i32* %2, align 4
+  br label %10
+
+7:                                                ; preds = %1
+  %8 = load i32, i32* %3, align 4
+  %9 = mul nsw i32 %8, 2
+  store i32 %9, i32* %2, align 4
+  br label %10
+
+10:                                               ; preds = %7, %6
+  %11 = load i32, i32* %2, align 4
+  ret i32 %11
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @validated(i32 %x) #1 {
+entry:
+  %z = add nsw i32 %x, 2
+  %ok = call zeroext i1 @is_valid(i32 %x)
+  br i1 %ok, label %good, label %end
+
+good:                                             ; preds = %entry
+  %y = add nsw i32 %x, 1
+  br label %end
+
+end:                                              ; preds = %good, %entry
+  %r = phi i32 [ %y, %good ], [ 0, %entry ]
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @validated_int(i32 %x) #1 {
+entry:
+  %v = call i32 @check(i32 %x)
+  %c = icmp eq i32 %v, 0
+  br i1 %c, label %end, label %good
+
+good:                                             ; preds = %entry
+  %y = add nsw i32 %x, 1
+  br label %end
+
+end:                                              ; preds = %good, %entry
+  %r = phi i32 [ %y, %good ], [ 0, %entry ]
+  ret i32 %r
+}
+
+declare zeroext i1 @is_valid(i32) #2
+
+declare i32 @check(i32) #2
+
+attributes #0 = { noinline nounwind optnone ssp uwtable }
+attributes #1 = { noinline nounwind ssp uwtable }
+attributes #2 = { nounwind }
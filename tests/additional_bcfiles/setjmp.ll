@@ -0,0 +1,45 @@
+; ModuleID = 'setjmp.c'
+source_filename = "setjmp.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.__jmp_buf_tag = type { [8 x i64], i32, %struct.__sigset_t }
+%struct.__sigset_t = type { [16 x i64] }
+
+@env = global [1 x %struct.__jmp_buf_tag] zeroinitializer, align 16
+
+; Function Attrs: noinline nounwind uwtable
+define void @fail() #0 {
+entry:
+  %code = call i32 @read_input()
+  call void @longjmp(%struct.__jmp_buf_tag* getelementptr inbounds ([1 x %struct.__jmp_buf_tag], [1 x %struct.__jmp_buf_tag]* @env, i64 0, i64 0), i32 %code) #3
+  unreachable
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @run() #0 {
+entry:
+  %r = call i32 @_setjmp(%struct.__jmp_buf_tag* getelementptr inbounds ([1 x %struct.__jmp_buf_tag], [1 x %struct.__jmp_buf_tag]* @env, i64 0, i64 0)) #2
+  %cmp = icmp ne i32 %r, 0
+  br i1 %cmp, label %if.then, label %if.end
+
+if.then:
+  ret i32 %r
+
+if.end:
+  call void @fail()
+  ret i32 0
+}
+
+declare i32 @read_input() #1
+
+; Function Attrs: noreturn nounwind
+declare void @longjmp(%struct.__jmp_buf_tag*, i32) #1
+
+; Function Attrs: returns_twice nounwind
+declare i32 @_setjmp(%struct.__jmp_buf_tag*) #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
+attributes #2 = { nounwind returns_twice }
+attributes #3 = { noreturn nounwind }
@@ -0,0 +1,26 @@
+// Written in signals.ll by hand, in SSA form.
+
+#include <signal.h>
+
+int read_input(void);
+
+int last_input;
+int pending;
+int term_value;
+
+void on_alarm(int sig) {
+  pending = last_input;
+}
+
+void on_term(int sig, siginfo_t *info, void *context) {
+  term_value = last_input;
+}
+
+int main(void) {
+  struct sigaction sa;
+  sa.sa_sigaction = on_term;
+  sigaction(SIGTERM, &sa, 0);
+  signal(SIGALRM, on_alarm);
+  last_input = read_input();
+  return pending;
+}
@@ -0,0 +1,53 @@
+; ModuleID = 'signals.c'
+source_filename = "signals.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.sigaction = type { %union.anon, %struct.__sigset_t, i32, void ()* }
+%union.anon = type { void (i32)* }
+%struct.__sigset_t = type { [16 x i64] }
+%struct.siginfo_t = type { i32, i32, i32, [116 x i8] }
+
+@last_input = global i32 0, align 4
+@pending = global i32 0, align 4
+@term_value = global i32 0, align 4
+
+; Function Attrs: noinline nounwind uwtable
+define void @on_alarm(i32 %sig) #0 {
+entry:
+  %v = load i32, i32* @last_input, align 4
+  store i32 %v, i32* @pending, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind uwtable
+define void @on_term(i32 %sig, %struct.siginfo_t* %info, i8* %context) #0 {
+entry:
+  %v = load i32, i32* @last_input, align 4
+  store i32 %v, i32* @term_value, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @main() #0 {
+entry:
+  %sa = alloca %struct.sigaction, align 8
+  %handler = getelementptr inbounds %struct.sigaction, %struct.sigaction* %sa, i32 0, i32 0
+  %sa_sigaction = bitcast %union.anon* %handler to void (i32, %struct.siginfo_t*, i8*)**
+  store void (i32, %struct.siginfo_t*, i8*)* @on_term, void (i32, %struct.siginfo_t*, i8*)** %sa_sigaction, align 8
+  %call = call i32 @sigaction(i32 15, %struct.sigaction* %sa, %struct.sigaction* null)
+  %call1 = call void (i32)* @signal(i32 14, void (i32)* @on_alarm)
+  %input = call i32 @read_input()
+  store i32 %input, i32* @last_input, align 4
+  %p = load i32, i32* @pending, align 4
+  ret i32 %p
+}
+
+declare i32 @sigaction(i32, %struct.sigaction*, %struct.sigaction*) #1
+
+declare void (i32)* @signal(i32, void (i32)*) #1
+
+declare i32 @read_input() #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,26 @@
+// Written in spectre.ll by hand, in SSA form.
+
+#include <stddef.h>
+#include <stdint.h>
+
+unsigned array1_size = 16;
+uint8_t array1[16];
+uint8_t array2[256 * 256];
+
+uint8_t victim(size_t x) {
+  if (x < array1_size) {
+    return array2[array1[x] * 256];
+  }
+  return 0;
+}
+
+uint8_t no_dependent_use(size_t x) {
+  if (x < array1_size) {
+    return array1[x];
+  }
+  return 0;
+}
+
+uint8_t unchecked(size_t x) {
+  return array2[array1[x] * 256];
+}
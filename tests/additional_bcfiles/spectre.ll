@@ -0,0 +1,62 @@
+; ModuleID = 'spectre.c'
+source_filename = "spectre.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+@array1_size = dso_local global i32 16, align 4
+@array1 = dso_local global [16 x i8] zeroinitializer, align 16
+@array2 = dso_local global [65536 x i8] zeroinitializer, align 16
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local zeroext i8 @victim(i64 %x) #0 {
+entry:
+  %size = load i32, i32* @array1_size, align 4
+  %size64 = zext i32 %size to i64
+  %inbounds = icmp ult i64 %x, %size64
+  br i1 %inbounds, label %then, label %end
+
+then:
+  %p1 = getelementptr inbounds [16 x i8], [16 x i8]* @array1, i64 0, i64 %x
+  %v = load i8, i8* %p1, align 1
+  %v64 = zext i8 %v to i64
+  %idx = mul i64 %v64, 256
+  %p2 = getelementptr inbounds [65536 x i8], [65536 x i8]* @array2, i64 0, i64 %idx
+  %r = load i8, i8* %p2, align 1
+  br label %end
+
+end:
+  %ret = phi i8 [ 0, %entry ], [ %r, %then ]
+  ret i8 %ret
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local zeroext i8 @no_dependent_use(i64 %x) #0 {
+entry:
+  %size = load i32, i32* @array1_size, align 4
+  %size64 = zext i32 %size to i64
+  %inbounds = icmp ult i64 %x, %size64
+  br i1 %inbounds, label %then, label %end
+
+then:
+  %p1 = getelementptr inbounds [16 x i8], [16 x i8]* @array1, i64 0, i64 %x
+  %v = load i8, i8* %p1, align 1
+  br label %end
+
+end:
+  %ret = phi i8 [ 0, %entry ], [ %v, %then ]
+  ret i8 %ret
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local zeroext i8 @unchecked(i64 %x) #0 {
+entry:
+  %p1 = getelementptr inbounds [16 x i8], [16 x i8]* @array1, i64 0, i64 %x
+  %v = load i8, i8* %p1, align 1
+  %v64 = zext i8 %v to i64
+  %idx = mul i64 %v64, 256
+  %p2 = getelementptr inbounds [65536 x i8], [65536 x i8]* @array2, i64 0, i64 %idx
+  %r = load i8, i8* %p2, align 1
+  ret i8 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
@@ -0,0 +1,37 @@
+// Written in sret.ll by hand, in SSA form.
+// `struct pair` is a literal (unnamed) struct type in sret.ll.
+
+struct pair { int first; int second; };
+
+// returned through a hidden `sret` pointer
+struct pair make_pair(int a, int b) {
+  struct pair p = { a, b };
+  return p;
+}
+
+int sret_caller(int x) {
+  struct pair p = make_pair(x, 1);
+  return p.first;
+}
+
+// `p` is passed `byval`
+int first(struct pair p) {
+  return p.first;
+}
+
+// the address of the argument depends on `addr`, but its contents don't
+int byval_address(long addr) {
+  return first(*(struct pair *)addr);
+}
+
+int byval_contents(int x) {
+  struct pair p = { x, 0 };
+  return first(p);
+}
+
+struct pair ext_pair(int x);
+
+int ext_caller(int x) {
+  struct pair p = ext_pair(x);
+  return p.second;
+}
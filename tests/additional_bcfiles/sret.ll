@@ -0,0 +1,56 @@
+; ModuleID = 'sret.c'
+source_filename = "sret.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define void @make_pair({ i32, i32 }* noalias sret({ i32, i32 }) %out, i32 %a, i32 %b) {
+entry:
+  %p0 = getelementptr inbounds { i32, i32 }, { i32, i32 }* %out, i32 0, i32 0
+  store i32 %a, i32* %p0
+  %p1 = getelementptr inbounds { i32, i32 }, { i32, i32 }* %out, i32 0, i32 1
+  store i32 %b, i32* %p1
+  ret void
+}
+
+define i32 @sret_caller(i32 %x) {
+entry:
+  %tmp = alloca { i32, i32 }
+  call void @make_pair({ i32, i32 }* sret({ i32, i32 }) %tmp, i32 %x, i32 1)
+  %p = getelementptr inbounds { i32, i32 }, { i32, i32 }* %tmp, i32 0, i32 0
+  %v = load i32, i32* %p
+  ret i32 %v
+}
+
+define i32 @first({ i32, i32 }* byval({ i32, i32 }) %s) {
+entry:
+  %p = getelementptr inbounds { i32, i32 }, { i32, i32 }* %s, i32 0, i32 0
+  %v = load i32, i32* %p
+  ret i32 %v
+}
+
+define i32 @byval_address(i64 %addr) {
+entry:
+  %s = inttoptr i64 %addr to { i32, i32 }*
+  %r = call i32 @first({ i32, i32 }* byval({ i32, i32 }) %s)
+  ret i32 %r
+}
+
+define i32 @byval_contents(i32 %x) {
+entry:
+  %s = alloca { i32, i32 }
+  %p0 = getelementptr inbounds { i32, i32 }, { i32, i32 }* %s, i32 0, i32 0
+  store i32 %x, i32* %p0
+  %r = call i32 @first({ i32, i32 }* byval({ i32, i32 }) %s)
+  ret i32 %r
+}
+
+declare void @ext_pair({ i32, i32 }* sret({ i32, i32 }), i32)
+
+define i32 @ext_caller(i32 %x) {
+entry:
+  %tmp = alloca { i32, i32 }
+  call void @ext_pair({ i32, i32 }* sret({ i32, i32 }) %tmp, i32 %x)
+  %p = getelementptr inbounds { i32, i32 }, { i32, i32 }* %tmp, i32 0, i32 1
+  %v = load i32, i32* %p
+  ret i32 %v
+}
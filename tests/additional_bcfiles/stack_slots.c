@@ -0,0 +1,42 @@
+// The functions here were written in stack_slots.ll by hand, with their
+// lifetime markers, as they'd appear when clang reuses one stack slot for two
+// variables in disjoint scopes.
+
+void observe(int *p);
+
+int reuse(int x) {
+  {
+    volatile int a = x;  // first lifetime of the slot
+    a;
+  }
+  {
+    volatile int b = 7;  // second lifetime of the same slot
+    return b;
+  }
+}
+
+// As reuse(), but the slot's address escapes to observe(), so its contents
+// are handled flow-insensitively
+int escaped(int x) {
+  {
+    int a = x;
+    observe(&a);
+  }
+  {
+    volatile int b = 7;
+    return b;
+  }
+}
+
+int overwrite(int x) {
+  volatile int a = x;
+  a;
+  a = 0;  // overwrites the tainted value entirely
+  return a;
+}
+
+int overwrite_if(int x) {
+  volatile int m = 0;
+  if (x > 0) m = 1;  // implicit flow from x
+  return m;
+}
@@ -0,0 +1,66 @@
+; ModuleID = 'stack_slots.c'
+source_filename = "stack_slots.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @reuse(i32 %x) {
+entry:
+  %slot = alloca i32, align 4
+  %c = bitcast i32* %slot to i8*
+  call void @llvm.lifetime.start.p0i8(i64 4, i8* %c)
+  store i32 %x, i32* %slot, align 4
+  %a = load i32, i32* %slot, align 4
+  call void @llvm.lifetime.end.p0i8(i64 4, i8* %c)
+  call void @llvm.lifetime.start.p0i8(i64 4, i8* %c)
+  store i32 7, i32* %slot, align 4
+  %b = load i32, i32* %slot, align 4
+  call void @llvm.lifetime.end.p0i8(i64 4, i8* %c)
+  ret i32 %b
+}
+
+define i32 @escaped(i32 %x) {
+entry:
+  %slot = alloca i32, align 4
+  %c = bitcast i32* %slot to i8*
+  call void @llvm.lifetime.start.p0i8(i64 4, i8* %c)
+  store i32 %x, i32* %slot, align 4
+  call void @observe(i32* %slot)
+  call void @llvm.lifetime.end.p0i8(i64 4, i8* %c)
+  call void @llvm.lifetime.start.p0i8(i64 4, i8* %c)
+  store i32 7, i32* %slot, align 4
+  %b = load i32, i32* %slot, align 4
+  call void @llvm.lifetime.end.p0i8(i64 4, i8* %c)
+  ret i32 %b
+}
+
+define i32 @overwrite(i32 %x) {
+entry:
+  %slot = alloca i32, align 4
+  store i32 %x, i32* %slot, align 4
+  %a = load i32, i32* %slot, align 4
+  store i32 0, i32* %slot, align 4
+  %b = load i32, i32* %slot, align 4
+  ret i32 %b
+}
+
+define i32 @overwrite_if(i32 %x) {
+entry:
+  %slot = alloca i32, align 4
+  store i32 0, i32* %slot, align 4
+  %t = icmp sgt i32 %x, 0
+  br i1 %t, label %then, label %join
+
+then:
+  store i32 1, i32* %slot, align 4
+  br label %join
+
+join:
+  %m = load i32, i32* %slot, align 4
+  ret i32 %m
+}
+
+declare void @observe(i32*)
+
+declare void @llvm.lifetime.start.p0i8(i64 immarg, i8* nocapture)
+
+declare void @llvm.lifetime.end.p0i8(i64 immarg, i8* nocapture)
@@ -0,0 +1,18 @@
+// Written in static_init.ll by hand, in SSA form.
+
+int read_env(void);
+
+int config_value;
+int shutdown_value;
+
+__attribute__((constructor)) void init_config(void) {
+  config_value = read_env();
+}
+
+__attribute__((destructor)) void fini(void) {
+  shutdown_value = config_value;
+}
+
+int main(void) {
+  return config_value;
+}
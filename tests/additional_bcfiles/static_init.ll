@@ -0,0 +1,37 @@
+; ModuleID = 'static_init.c'
+source_filename = "static_init.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@config_value = common global i32 0, align 4
+@shutdown_value = common global i32 0, align 4
+@llvm.global_ctors = appending global [1 x { i32, void ()*, i8* }] [{ i32, void ()*, i8* } { i32 65535, void ()* @init_config, i8* null }]
+@llvm.global_dtors = appending global [1 x { i32, void ()*, i8* }] [{ i32, void ()*, i8* } { i32 65535, void ()* @fini, i8* null }]
+
+; Function Attrs: noinline nounwind ssp uwtable
+define void @init_config() #0 {
+entry:
+  %v = call i32 @read_env()
+  store i32 %v, i32* @config_value, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define void @fini() #0 {
+entry:
+  %c = load i32, i32* @config_value, align 4
+  store i32 %c, i32* @shutdown_value, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @main() #0 {
+entry:
+  %c = load i32, i32* @config_value, align 4
+  ret i32 %c
+}
+
+declare i32 @read_env() #1
+
+attributes #0 = { noinline nounwind ssp uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,29 @@
+// Written in taint_blind.ll by hand, in SSA form.
+
+int secret(void);
+
+int counter;
+
+int mix(int x) {
+  return x * 3;
+}
+
+int reader(void) {
+  int m = mix(secret());
+  counter = m;
+  return m;
+}
+
+// Never sees the secret, but reads the global `reader` writes to
+int get_count(void) {
+  return counter;
+}
+
+int blind_helper(int x) {
+  return x + 1;
+}
+
+// Never sees the secret at all
+int blind(int x) {
+  return blind_helper(x) * 2;
+}
@@ -0,0 +1,49 @@
+; ModuleID = 'taint_blind.c'
+source_filename = "taint_blind.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@counter = common global i32 0, align 4
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @mix(i32 %x) #0 {
+entry:
+  %r = mul nsw i32 %x, 3
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @reader() #0 {
+entry:
+  %s = call i32 @secret()
+  %m = call i32 @mix(i32 %s)
+  store i32 %m, i32* @counter, align 4
+  ret i32 %m
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @get_count() #0 {
+entry:
+  %c = load i32, i32* @counter, align 4
+  ret i32 %c
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @blind_helper(i32 %x) #0 {
+entry:
+  %r = add nsw i32 %x, 1
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @blind(i32 %x) #0 {
+entry:
+  %h = call i32 @blind_helper(i32 %x)
+  %r = mul nsw i32 %h, 2
+  ret i32 %r
+}
+
+declare i32 @secret() #1
+
+attributes #0 = { noinline nounwind ssp uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,10 @@
+// Written in target_types.ll by hand, in SSA form. C has no way to name the
+// x86_amx tile type, which only the tile intrinsics produce and consume.
+
+typedef int v256i32 __attribute__((vector_size(1024)));
+
+int through_tile(v256i32 v) {
+  x86_amx t = __builtin_cast_vector_to_tile(v);
+  v256i32 w = __builtin_cast_tile_to_vector(t);
+  return w[0];
+}
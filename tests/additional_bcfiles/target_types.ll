@@ -0,0 +1,19 @@
+; ModuleID = 'target_types.c'
+source_filename = "target_types.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @through_tile(<256 x i32> %v) #0 {
+entry:
+  %t = call x86_amx @llvm.x86.cast.vector.to.tile.v256i32(<256 x i32> %v)
+  %w = call <256 x i32> @llvm.x86.cast.tile.to.vector.v256i32(x86_amx %t)
+  %e = extractelement <256 x i32> %w, i32 0
+  ret i32 %e
+}
+
+declare x86_amx @llvm.x86.cast.vector.to.tile.v256i32(<256 x i32>)
+
+declare <256 x i32> @llvm.x86.cast.tile.to.vector.v256i32(x86_amx)
+
+attributes #0 = { noinline nounwind ssp uwtable }
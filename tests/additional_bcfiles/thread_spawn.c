@@ -0,0 +1,29 @@
+// Written in thread_spawn.ll by hand, in SSA form.
+
+#include <pthread.h>
+
+// the job is `{ value, result }`
+void *worker(void *arg) {
+  int *j = arg;
+  int v = j[0];
+  j[1] = v * 2;
+  return 0;
+}
+
+int start(int x) {
+  int j[2];
+  pthread_t t;
+  j[0] = x;
+  pthread_create(&t, 0, worker, j);
+  pthread_join(t, 0);
+  return j[1];
+}
+
+void *other_worker(void *arg) {
+  return arg;
+}
+
+void start_indirect(void *(*f)(void *), int *x) {
+  pthread_t t;
+  pthread_create(&t, 0, f, x);
+}
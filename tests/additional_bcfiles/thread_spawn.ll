@@ -0,0 +1,55 @@
+; ModuleID = 'thread_spawn.c'
+source_filename = "thread_spawn.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%union.pthread_attr_t = type { i64, [48 x i8] }
+
+; Function Attrs: noinline nounwind uwtable
+define i8* @worker(i8* %arg) #0 {
+entry:
+  %j = bitcast i8* %arg to i32*
+  %v = load i32, i32* %j, align 4
+  %mul = mul nsw i32 %v, 2
+  %result = getelementptr inbounds i32, i32* %j, i64 1
+  store i32 %mul, i32* %result, align 4
+  ret i8* null
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @start(i32 %x) #0 {
+entry:
+  %j = alloca [2 x i32], align 4
+  %t = alloca i64, align 8
+  %value = getelementptr inbounds [2 x i32], [2 x i32]* %j, i64 0, i64 0
+  store i32 %x, i32* %value, align 4
+  %arg = bitcast [2 x i32]* %j to i8*
+  %call = call i32 @pthread_create(i64* %t, %union.pthread_attr_t* null, i8* (i8*)* @worker, i8* %arg)
+  %tid = load i64, i64* %t, align 8
+  %call1 = call i32 @pthread_join(i64 %tid, i8** null)
+  %result = getelementptr inbounds [2 x i32], [2 x i32]* %j, i64 0, i64 1
+  %r = load i32, i32* %result, align 4
+  ret i32 %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i8* @other_worker(i8* %arg) #0 {
+entry:
+  ret i8* %arg
+}
+
+; Function Attrs: noinline nounwind uwtable
+define void @start_indirect(i8* (i8*)* %f, i32* %x) #0 {
+entry:
+  %t = alloca i64, align 8
+  %arg = bitcast i32* %x to i8*
+  %call = call i32 @pthread_create(i64* %t, %union.pthread_attr_t* null, i8* (i8*)* %f, i8* %arg)
+  ret void
+}
+
+declare i32 @pthread_create(i64*, %union.pthread_attr_t*, i8* (i8*)*, i8*) #1
+
+declare i32 @pthread_join(i64, i8**) #1
+
+attributes #0 = { noinline nounwind uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,13 @@
+// Two functions which communicate only through memory reachable from a
+// global, as two threads might. Neither calls the other. (Some other code is
+// responsible for pointing `shared` to a buffer.)
+
+int *shared;
+
+void writer(int x) {
+  shared[1] = x;
+}
+
+int reader(void) {
+  return shared[2];
+}
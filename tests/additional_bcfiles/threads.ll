@@ -0,0 +1,27 @@
+; ModuleID = 'threads.c'
+source_filename = "threads.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+@shared = common global i32* null, align 8
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define void @writer(i32) #0 {
+  %2 = alloca i32, align 4
+  store i32 %0, i32* %2, align 4
+  %3 = load i32, i32* %2, align 4
+  %4 = load i32*, i32** @shared, align 8
+  %5 = getelementptr inbounds i32, i32* %4, i64 1
+  store i32 %3, i32* %5, align 4
+  ret void
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @reader() #0 {
+  %1 = load i32*, i32** @shared, align 8
+  %2 = getelementptr inbounds i32, i32* %1, i64 2
+  %3 = load i32, i32* %2, align 4
+  ret i32 %3
+}
+
+attributes #0 = { noinline nounwind optnone ssp uwtable }
@@ -0,0 +1,19 @@
+// Written in transfer.ll by hand, in SSA form.
+
+int secret(void);
+
+int add_first_and_third(int a, int b, int c) {
+  return a + c;
+}
+
+void copy_into(int *dst, int v) {
+  *dst = v;
+}
+
+int load_from(int *p) {
+  return *p;
+}
+
+int from_secret(int x) {
+  return secret();
+}
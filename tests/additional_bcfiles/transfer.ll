@@ -0,0 +1,30 @@
+; ModuleID = 'transfer.c'
+source_filename = "transfer.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @add_first_and_third(i32 %a, i32 %b, i32 %c) {
+entry:
+  %sum = add nsw i32 %a, %c
+  ret i32 %sum
+}
+
+define void @copy_into(i32* %dst, i32 %v) {
+entry:
+  store i32 %v, i32* %dst, align 4
+  ret void
+}
+
+define i32 @load_from(i32* %p) {
+entry:
+  %v = load i32, i32* %p, align 4
+  ret i32 %v
+}
+
+define i32 @from_secret(i32 %x) {
+entry:
+  %s = call i32 @secret()
+  ret i32 %s
+}
+
+declare i32 @secret()
@@ -0,0 +1,10 @@
+// Written in truncs.ll by hand, in SSA form.
+
+#include <stdbool.h>
+
+int narrow(long x) {
+  bool bit = (bool)(x & 1);
+  char byte = (char)x;
+  int half = (int)x;
+  return bit + byte + half;
+}
@@ -0,0 +1,19 @@
+; ModuleID = 'truncs.c'
+source_filename = "truncs.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local i32 @narrow(i64 %x) #0 {
+entry:
+  %bit = trunc i64 %x to i1
+  %byte = trunc i64 %x to i8
+  %half = trunc i64 %x to i32
+  %bit.ext = zext i1 %bit to i32
+  %byte.ext = sext i8 %byte to i32
+  %r1 = add nsw i32 %bit.ext, %byte.ext
+  %r = add nsw i32 %r1, %half
+  ret i32 %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
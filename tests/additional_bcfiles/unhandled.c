@@ -0,0 +1,16 @@
+// Written in unhandled.ll by hand, in SSA form.
+
+// `freeze` has no C equivalent: in unhandled.ll, `x` is frozen before the add
+int frozen(int x) {
+  return x + 1;
+}
+
+int via_asm(int x) {
+  int r;
+  asm("mov %1, %0" : "=r"(r) : "r"(x));
+  return r;
+}
+
+int caller(int x, int y) {
+  return frozen(x) + via_asm(y);
+}
@@ -0,0 +1,25 @@
+; ModuleID = 'unhandled.c'
+source_filename = "unhandled.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+define i32 @frozen(i32 %x) {
+entry:
+  %f = freeze i32 %x
+  %r = add nsw i32 %f, 1
+  ret i32 %r
+}
+
+define i32 @via_asm(i32 %x) {
+entry:
+  %r = call i32 asm "mov $1, $0", "=r,r"(i32 %x)
+  ret i32 %r
+}
+
+define i32 @caller(i32 %x, i32 %y) {
+entry:
+  %a = call i32 @frozen(i32 %x)
+  %b = call i32 @via_asm(i32 %y)
+  %r = add nsw i32 %a, %b
+  ret i32 %r
+}
@@ -0,0 +1,24 @@
+#include <stdarg.h>
+
+// varargs.ll is written for arm64-apple-macosx, where va_list is a plain
+// `char*`, and uses the `va_arg` instruction rather than clang's inline
+// expansion of va_arg().
+
+int first_vararg(int n, ...) {
+  va_list ap, ap2;
+  va_start(ap, n);
+  va_copy(ap2, ap);
+  int x = va_arg(ap2, int);
+  va_end(ap2);
+  va_end(ap);
+  return x;
+}
+
+int call_untainted(int x) {
+  return first_vararg(1, 2);
+}
+
+int call_both(int x) {
+  first_vararg(1, 2);
+  return first_vararg(2, 3, x);
+}
@@ -0,0 +1,56 @@
+; ModuleID = 'varargs.c'
+source_filename = "varargs.c"
+target datalayout = "e-m:o-i64:64-i128:128-n32:64-S128"
+target triple = "arm64-apple-macosx11.0.0"
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @first_vararg(i32, ...) #0 {
+  %2 = alloca i32, align 4
+  %3 = alloca i8*, align 8
+  %4 = alloca i8*, align 8
+  %5 = alloca i32, align 4
+  store i32 %0, i32* %2, align 4
+  %6 = bitcast i8** %3 to i8*
+  call void @llvm.va_start(i8* %6)
+  %7 = bitcast i8** %4 to i8*
+  %8 = bitcast i8** %3 to i8*
+  call void @llvm.va_copy(i8* %7, i8* %8)
+  %9 = va_arg i8** %4, i32
+  store i32 %9, i32* %5, align 4
+  %10 = bitcast i8** %4 to i8*
+  call void @llvm.va_end(i8* %10)
+  %11 = bitcast i8** %3 to i8*
+  call void @llvm.va_end(i8* %11)
+  %12 = load i32, i32* %5, align 4
+  ret i32 %12
+}
+
+; Function Attrs: nounwind
+declare void @llvm.va_start(i8*) #1
+
+; Function Attrs: nounwind
+declare void @llvm.va_copy(i8*, i8*) #1
+
+; Function Attrs: nounwind
+declare void @llvm.va_end(i8*) #1
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @call_untainted(i32) #0 {
+  %2 = alloca i32, align 4
+  store i32 %0, i32* %2, align 4
+  %3 = call i32 (i32, ...) @first_vararg(i32 1, i32 2)
+  ret i32 %3
+}
+
+; Function Attrs: noinline nounwind optnone ssp uwtable
+define i32 @call_both(i32) #0 {
+  %2 = alloca i32, align 4
+  store i32 %0, i32* %2, align 4
+  %3 = call i32 (i32, ...) @first_vararg(i32 1, i32 2)
+  %4 = load i32, i32* %2, align 4
+  %5 = call i32 (i32, ...) @first_vararg(i32 2, i32 3, i32 %4)
+  ret i32 %5
+}
+
+attributes #0 = { noinline nounwind optnone ssp uwtable }
+attributes #1 = { nounwind }
@@ -0,0 +1,20 @@
+// Written in vector_gep.ll by hand, in SSA form. The getelementptrs there
+// operate on vectors of 4 pointers, as a vectorizer produces for gathers,
+// which C can't express directly; for each lane, they compute:
+
+struct point {
+  int x;
+  int y;
+};
+
+int *lanes(int *ptr, long idx) {
+  return &ptr[idx];
+}
+
+int *splat_base(int *base, long idx) {
+  return &base[idx];
+}
+
+int *fields(struct point *pt) {
+  return &pt->y;
+}
@@ -0,0 +1,29 @@
+; ModuleID = 'vector_gep.c'
+source_filename = "vector_gep.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%struct.point = type { i32, i32 }
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local <4 x i32*> @lanes(<4 x i32*> %ptrs, <4 x i64> %idx) #0 {
+entry:
+  %r = getelementptr inbounds i32, <4 x i32*> %ptrs, <4 x i64> %idx
+  ret <4 x i32*> %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local <4 x i32*> @splat_base(i32* %base, <4 x i64> %idx) #0 {
+entry:
+  %r = getelementptr inbounds i32, i32* %base, <4 x i64> %idx
+  ret <4 x i32*> %r
+}
+
+; Function Attrs: noinline nounwind uwtable
+define dso_local <4 x i32*> @fields(<4 x %struct.point*> %pts) #0 {
+entry:
+  %r = getelementptr inbounds %struct.point, <4 x %struct.point*> %pts, <4 x i64> zeroinitializer, <4 x i32> <i32 1, i32 1, i32 1, i32 1>
+  ret <4 x i32*> %r
+}
+
+attributes #0 = { noinline nounwind uwtable }
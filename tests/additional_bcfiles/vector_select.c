@@ -0,0 +1,15 @@
+// Written in vector_select.ll by hand, in SSA form.
+
+typedef int int4 __attribute__((ext_vector_type(4)));
+typedef int int2 __attribute__((ext_vector_type(2)));
+typedef int *intp2 __attribute__((ext_vector_type(2)));
+
+int4 choose(int4 a, int4 b, int4 c) {
+  return a > b ? a : c;
+}
+
+// In vector_select.ll, the mask is a vector of `i1`, as after the comparison
+// of `mask` with zero is folded into the caller
+intp2 choose_ptrs(int2 mask, intp2 p, intp2 q) {
+  return mask ? p : q;
+}
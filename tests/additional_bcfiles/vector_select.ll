@@ -0,0 +1,21 @@
+; ModuleID = 'vector_select.c'
+source_filename = "vector_select.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define <4 x i32> @choose(<4 x i32> %a, <4 x i32> %b, <4 x i32> %c) #0 {
+entry:
+  %cmp = icmp sgt <4 x i32> %a, %b
+  %r = select <4 x i1> %cmp, <4 x i32> %a, <4 x i32> %c
+  ret <4 x i32> %r
+}
+
+; Function Attrs: noinline nounwind ssp uwtable
+define <2 x i32*> @choose_ptrs(<2 x i1> %mask, <2 x i32*> %p, <2 x i32*> %q) #0 {
+entry:
+  %r = select <2 x i1> %mask, <2 x i32*> %p, <2 x i32*> %q
+  ret <2 x i32*> %r
+}
+
+attributes #0 = { noinline nounwind ssp uwtable }
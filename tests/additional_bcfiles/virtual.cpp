@@ -0,0 +1,27 @@
+// Written in virtual.ll by hand, in SSA form.
+
+class Base {
+public:
+  virtual int get(int x) { return 0; }
+  virtual int other(int x) { return x; }
+};
+
+class Derived : public Base {
+public:
+  int get(int x) override { return 1; }
+};
+
+// Not derived from Base. With opaque pointers, its `get` has the same type as
+// `Base::get`; in virtual.ll, its `this` has Base's type to match.
+class Unrelated {
+public:
+  virtual int get(int x) { return x; }
+};
+
+int call_get(Base *b, int x) {
+  return b->get(x);
+}
+
+int call_other(Base *b, int x) {
+  return b->other(x);
+}
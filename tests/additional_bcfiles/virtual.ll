@@ -0,0 +1,66 @@
+; ModuleID = 'virtual.cpp'
+source_filename = "virtual.cpp"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-pc-linux-gnu"
+
+%class.Base = type { i32 (...)** }
+%class.Derived = type { %class.Base }
+
+@_ZTV4Base = linkonce_odr unnamed_addr constant { [4 x i8*] } { [4 x i8*] [i8* null, i8* bitcast ({ i8*, i8* }* @_ZTI4Base to i8*), i8* bitcast (i32 (%class.Base*, i32)* @_ZN4Base3getEi to i8*), i8* bitcast (i32 (%class.Base*, i32)* @_ZN4Base5otherEi to i8*)] }, align 8
+@_ZTV7Derived = linkonce_odr unnamed_addr constant { [4 x i8*] } { [4 x i8*] [i8* null, i8* bitcast ({ i8*, i8*, i8* }* @_ZTI7Derived to i8*), i8* bitcast (i32 (%class.Derived*, i32)* @_ZN7Derived3getEi to i8*), i8* bitcast (i32 (%class.Base*, i32)* @_ZN4Base5otherEi to i8*)] }, align 8
+@_ZTVN10__cxxabiv117__class_type_infoE = external global i8*
+@_ZTS4Base = linkonce_odr constant [6 x i8] c"4Base\00", align 1
+@_ZTI4Base = linkonce_odr constant { i8*, i8* } { i8* bitcast (i8** getelementptr inbounds (i8*, i8** @_ZTVN10__cxxabiv117__class_type_infoE, i64 2) to i8*), i8* getelementptr inbounds ([6 x i8], [6 x i8]* @_ZTS4Base, i32 0, i32 0) }, align 8
+@_ZTVN10__cxxabiv120__si_class_type_infoE = external global i8*
+@_ZTS7Derived = linkonce_odr constant [9 x i8] c"7Derived\00", align 1
+@_ZTI7Derived = linkonce_odr constant { i8*, i8*, i8* } { i8* bitcast (i8** getelementptr inbounds (i8*, i8** @_ZTVN10__cxxabiv120__si_class_type_infoE, i64 2) to i8*), i8* getelementptr inbounds ([9 x i8], [9 x i8]* @_ZTS7Derived, i32 0, i32 0), i8* bitcast ({ i8*, i8* }* @_ZTI4Base to i8*) }, align 8
+@_ZTV9Unrelated = linkonce_odr unnamed_addr constant { [3 x i8*] } { [3 x i8*] [i8* null, i8* bitcast ({ i8*, i8* }* @_ZTI9Unrelated to i8*), i8* bitcast (i32 (%class.Base*, i32)* @_ZN9Unrelated3getEi to i8*)] }, align 8
+@_ZTS9Unrelated = linkonce_odr constant [11 x i8] c"9Unrelated\00", align 1
+@_ZTI9Unrelated = linkonce_odr constant { i8*, i8* } { i8* bitcast (i8** getelementptr inbounds (i8*, i8** @_ZTVN10__cxxabiv117__class_type_infoE, i64 2) to i8*), i8* getelementptr inbounds ([11 x i8], [11 x i8]* @_ZTS9Unrelated, i32 0, i32 0) }, align 8
+
+; Function Attrs: noinline nounwind uwtable
+define linkonce_odr i32 @_ZN4Base3getEi(%class.Base* %this, i32 %x) #0 {
+entry:
+  ret i32 0
+}
+
+; Function Attrs: noinline nounwind uwtable
+define linkonce_odr i32 @_ZN4Base5otherEi(%class.Base* %this, i32 %x) #0 {
+entry:
+  ret i32 %x
+}
+
+; Function Attrs: noinline nounwind uwtable
+define linkonce_odr i32 @_ZN7Derived3getEi(%class.Derived* %this, i32 %x) #0 {
+entry:
+  ret i32 1
+}
+
+; Function Attrs: noinline nounwind uwtable
+define linkonce_odr i32 @_ZN9Unrelated3getEi(%class.Base* %this, i32 %x) #0 {
+entry:
+  ret i32 %x
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @_Z8call_getP4Basei(%class.Base* %b, i32 %x) #0 {
+entry:
+  %0 = bitcast %class.Base* %b to i32 (%class.Base*, i32)***
+  %vtable = load i32 (%class.Base*, i32)**, i32 (%class.Base*, i32)*** %0, align 8
+  %1 = load i32 (%class.Base*, i32)*, i32 (%class.Base*, i32)** %vtable, align 8
+  %call = call i32 %1(%class.Base* %b, i32 %x)
+  ret i32 %call
+}
+
+; Function Attrs: noinline nounwind uwtable
+define i32 @_Z10call_otherP4Basei(%class.Base* %b, i32 %x) #0 {
+entry:
+  %0 = bitcast %class.Base* %b to i32 (%class.Base*, i32)***
+  %vtable = load i32 (%class.Base*, i32)**, i32 (%class.Base*, i32)*** %0, align 8
+  %vfn = getelementptr inbounds i32 (%class.Base*, i32)*, i32 (%class.Base*, i32)** %vtable, i64 1
+  %1 = load i32 (%class.Base*, i32)*, i32 (%class.Base*, i32)** %vfn, align 8
+  %call = call i32 %1(%class.Base* %b, i32 %x)
+  ret i32 %call
+}
+
+attributes #0 = { noinline nounwind uwtable }
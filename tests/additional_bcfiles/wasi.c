@@ -0,0 +1,20 @@
+// Written in wasi.ll by hand, in SSA form.
+
+#include <wasi/api.h>
+
+char read_byte(void) {
+  uint8_t buf[16];
+  __wasi_iovec_t iov = { buf, sizeof(buf) };
+  __wasi_size_t nread;
+  __wasi_fd_read(0, &iov, 1, &nread);
+  return iov.buf[0];
+}
+
+char first_arg_byte(void) {
+  __wasi_size_t argc, buf_size;
+  __wasi_args_sizes_get(&argc, &buf_size);
+  uint8_t *argv[4];
+  uint8_t argv_buf[64];
+  __wasi_args_get(argv, argv_buf);
+  return argv[0][0];
+}
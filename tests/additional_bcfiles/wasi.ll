@@ -0,0 +1,50 @@
+; ModuleID = 'wasi.c'
+source_filename = "wasi.c"
+target datalayout = "e-m:e-p:32:32-p10:8:8-p20:8:8-i64:64-n32:64-S128-ni:1:10:20"
+target triple = "wasm32-unknown-wasi"
+
+%struct.__wasi_iovec_t = type { i8*, i32 }
+
+; Function Attrs: noinline nounwind
+define signext i8 @read_byte() #0 {
+entry:
+  %buf = alloca [16 x i8], align 16
+  %iov = alloca %struct.__wasi_iovec_t, align 4
+  %nread = alloca i32, align 4
+  %buf0 = getelementptr inbounds [16 x i8], [16 x i8]* %buf, i32 0, i32 0
+  %iov.buf = getelementptr inbounds %struct.__wasi_iovec_t, %struct.__wasi_iovec_t* %iov, i32 0, i32 0
+  store i8* %buf0, i8** %iov.buf, align 4
+  %iov.len = getelementptr inbounds %struct.__wasi_iovec_t, %struct.__wasi_iovec_t* %iov, i32 0, i32 1
+  store i32 16, i32* %iov.len, align 4
+  %err = call zeroext i16 @__wasi_fd_read(i32 0, %struct.__wasi_iovec_t* %iov, i32 1, i32* %nread)
+  %n = load i32, i32* %nread, align 4
+  %data = load i8*, i8** %iov.buf, align 4
+  %b = load i8, i8* %data, align 1
+  ret i8 %b
+}
+
+; Function Attrs: noinline nounwind
+define signext i8 @first_arg_byte() #0 {
+entry:
+  %argc = alloca i32, align 4
+  %buf_size = alloca i32, align 4
+  %argv = alloca [4 x i8*], align 16
+  %argv_buf = alloca [64 x i8], align 16
+  %err = call zeroext i16 @__wasi_args_sizes_get(i32* %argc, i32* %buf_size)
+  %count = load i32, i32* %argc, align 4
+  %argv0 = getelementptr inbounds [4 x i8*], [4 x i8*]* %argv, i32 0, i32 0
+  %argv_buf0 = getelementptr inbounds [64 x i8], [64 x i8]* %argv_buf, i32 0, i32 0
+  %err1 = call zeroext i16 @__wasi_args_get(i8** %argv0, i8* %argv_buf0)
+  %arg0 = load i8*, i8** %argv0, align 4
+  %c = load i8, i8* %arg0, align 1
+  ret i8 %c
+}
+
+declare zeroext i16 @__wasi_fd_read(i32, %struct.__wasi_iovec_t*, i32, i32*) #1
+
+declare zeroext i16 @__wasi_args_sizes_get(i32*, i32*) #1
+
+declare zeroext i16 @__wasi_args_get(i8**, i8*) #1
+
+attributes #0 = { noinline nounwind }
+attributes #1 = { nounwind }
@@ -0,0 +1,11 @@
+// Written in what_if.ll by hand, in SSA form.
+
+#include <stdbool.h>
+
+bool is_valid(int x);
+
+int check(int a, int b) {
+  int s = a + b;
+  if (is_valid(a)) return a + 1;
+  return b * 2;
+}
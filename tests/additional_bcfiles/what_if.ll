@@ -0,0 +1,25 @@
+; ModuleID = 'what_if.c'
+source_filename = "what_if.c"
+target datalayout = "e-m:o-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-apple-macosx10.15.0"
+
+; Function Attrs: noinline nounwind ssp uwtable
+define i32 @check(i32 %a, i32 %b) #0 {
+entry:
+  %s = add nsw i32 %a, %b
+  %ok = call zeroext i1 @is_valid(i32 %a)
+  br i1 %ok, label %valid, label %invalid
+
+valid:                                            ; preds = %entry
+  %r = add nsw i32 %a, 1
+  ret i32 %r
+
+invalid:                                          ; preds = %entry
+  %d = mul nsw i32 %b, 2
+  ret i32 %d
+}
+
+declare zeroext i1 @is_valid(i32) #1
+
+attributes #0 = { noinline nounwind ssp uwtable }
+attributes #1 = { nounwind }
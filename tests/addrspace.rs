@@ -0,0 +1,39 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/addrspace.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "kernel", None, HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn address_space_policies() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, address spaces don't matter
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("v")), &TaintedType::UntaintedValue);
+    assert!(taint_result.get_tainted_sinks("kernel").is_empty());
+
+    // loads from addrspace(1) are sources, and stores to addrspace(4) sinks;
+    // only the store of the tainted value is reported
+    config.tainted_address_spaces.insert(1);
+    config.sink_address_spaces.insert(4);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("w")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_tainted_sinks("kernel"), vec![(&Name::from("entry"), 2)]);
+}
@@ -0,0 +1,132 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/allocators.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.model_allocators = true;
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, fn_name: &str, args: Vec<TaintedType>) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, fn_name, Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn casts_share_allocation() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    // the tainted value stored through `%p` is loaded through `%q`
+    let taint_result = analyze(&modules, &config, "heap", vec![TaintedType::TaintedValue]);
+    assert!(matches!(taint_result.get_var_type("heap", &Name::from("m")), TaintedType::UntaintedPointer(_)));
+    assert_eq!(taint_result.get_var_type("heap", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn fresh_pointer() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let taint_result = analyze(&modules, &config, "heap_size", vec![TaintedType::TaintedValue]);
+    assert!(matches!(taint_result.get_var_type("heap_size", &Name::from("m")), TaintedType::UntaintedPointer(_)));
+}
+
+#[test]
+fn realloc() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let taint_result = analyze(&modules, &config, "heap_realloc", vec![TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_var_type("heap_realloc", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn separate_allocations() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let taint_result = analyze(&modules, &config, "heap_calloc", vec![TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_var_type("heap_calloc", &Name::from("r")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn memcpy_into_allocation() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)];
+    let taint_result = analyze(&modules, &config, "heap_memcpy", args);
+    assert_eq!(taint_result.get_var_type("heap_memcpy", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn not_modeled_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::PropagateTaintShallow;
+
+    // `malloc` is an ordinary external function, so its result is tainted by
+    // the tainted size
+    let taint_result = analyze(&modules, &config, "heap_size", vec![TaintedType::TaintedValue]);
+    assert!(matches!(taint_result.get_var_type("heap_size", &Name::from("m")), TaintedType::TaintedPointer(_)));
+}
+
+#[test]
+fn configured_allocator() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    config.ext_functions.insert("malloc".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    // `ext_functions` takes precedence over the built-in model
+    let taint_result = analyze(&modules, &config, "heap_size", vec![TaintedType::UntaintedValue]);
+    assert!(matches!(taint_result.get_var_type("heap_size", &Name::from("m")), TaintedType::TaintedPointer(_)));
+}
+
+#[test]
+fn tainted_free() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    let args = vec![TaintedType::tainted_ptr_to(TaintedType::UntaintedValue)];
+
+    // frees aren't reported by default
+    let taint_result = analyze(&modules, &config, "heap_free", args.clone());
+    assert!(taint_result.get_tainted_frees("heap_free").is_empty());
+
+    config.report_tainted_frees = true;
+    let taint_result = analyze(&modules, &config, "heap_free", args);
+    assert_eq!(taint_result.get_tainted_frees("heap_free"), vec![(&Name::from("entry"), 0)]);
+    assert!(taint_result.get_tainted_sinks("heap_free").is_empty());
+
+    let mut sarif = Vec::new();
+    taint_result.write_sarif(&OutputFilter::default(), &mut sarif).unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&sarif).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "tainted-free");
+
+    // deallocators are only modeled along with allocators
+    config.model_allocators = false;
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    let taint_result = analyze(&modules, &config, "heap_free", vec![TaintedType::tainted_ptr_to(TaintedType::UntaintedValue)]);
+    assert!(taint_result.get_tainted_frees("heap_free").is_empty());
+}
@@ -0,0 +1,48 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/parallel.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn annotated_ir() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let args = vec![
+        TaintedType::TaintedValue,
+        TaintedType::UntaintedValue,
+        TaintedType::UntaintedValue,
+        TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    ];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "dispatch", Some(args), HashMap::new(), HashMap::new());
+
+    let mut ir = Vec::new();
+    taint_result.write_annotated_ir(&mut ir).unwrap();
+    let ir = String::from_utf8(ir).unwrap();
+    assert!(ir.contains(&format!("; ModuleID = '{}'\n", modules[0].name)));
+    assert!(ir.contains("\ndefine i32 @dispatch(i32 %op, i32 %x, i32 %y, i32* %out) {\n"));
+    assert!(ir.contains("  ; %op: TaintedValue [T]\n"));
+    assert!(ir.contains("  ; %x: UntaintedValue\n"));
+    assert!(ir.contains("  ; %out: (UntaintedPointer to TaintedValue)\n"));
+
+    // each instruction is annotated with the type of its result, and the
+    // tainted switch is flagged
+    assert!(ir.contains("\ncase1:\n"));
+    assert!(ir.contains("  %r1 = mul i32 %y, i32 2  ; UntaintedValue\n"));
+    let switch = ir.lines().find(|line| line.starts_with("  switch i32 %op")).unwrap();
+    assert!(switch.ends_with("  ; [T]"));
+    let phi = ir.lines().find(|line| line.starts_with("  %r = phi")).unwrap();
+    assert!(phi.ends_with("; TaintedValue [T]"));
+    // and stores, which have no result, aren't annotated
+    assert!(ir.contains("\n  store i32 %acc, i32* %out, align 4\n"));
+}
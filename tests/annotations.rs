@@ -0,0 +1,81 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/annotations.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, fn_name: &str, args: Vec<TaintedType>) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, fn_name, Some(args), HashMap::new(), HashMap::new())
+}
+
+fn honoring_annotations() -> Config {
+    let mut config = Config::default();
+    config.honor_annotations = true;
+    config
+}
+
+#[test]
+fn source_global() {
+    init_logging();
+    let modules = [get_module()];
+    let config = honoring_annotations();
+    let taint_result = analyze(&modules, &config, "use_keys", vec![]);
+    assert_eq!(taint_result.get_var_type("use_keys", &Name::from("s")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("use_keys", &Name::from("p")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("use_keys", &Name::from("sum")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn source_function() {
+    init_logging();
+    let modules = [get_module()];
+    let config = honoring_annotations();
+    let taint_result = analyze(&modules, &config, "sample", vec![]);
+    assert_eq!(taint_result.get_var_type("sample", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("sample", &Name::from("sum")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn source_local() {
+    init_logging();
+    let modules = [get_module()];
+    let config = honoring_annotations();
+    let taint_result = analyze(&modules, &config, "local", vec![TaintedType::UntaintedValue]);
+    assert_eq!(taint_result.get_var_type("local", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn sink_function() {
+    init_logging();
+    let modules = [get_module()];
+    let config = honoring_annotations();
+    let taint_result = analyze(&modules, &config, "report", vec![TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_tainted_sinks("report"), vec![(&Name::from("entry"), 0)]);
+
+    let taint_result = analyze(&modules, &config, "report", vec![TaintedType::UntaintedValue]);
+    assert!(taint_result.get_tainted_sinks("report").is_empty());
+}
+
+#[test]
+fn annotations_ignored_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config, "use_keys", vec![]);
+    assert_eq!(taint_result.get_var_type("use_keys", &Name::from("s")), &TaintedType::UntaintedValue);
+    let taint_result = analyze(&modules, &config, "sample", vec![]);
+    assert_eq!(taint_result.get_var_type("sample", &Name::from("r")), &TaintedType::UntaintedValue);
+    let taint_result = analyze(&modules, &config, "local", vec![TaintedType::UntaintedValue]);
+    assert_eq!(taint_result.get_var_type("local", &Name::from("r")), &TaintedType::UntaintedValue);
+    let taint_result = analyze(&modules, &config, "report", vec![TaintedType::TaintedValue]);
+    assert!(taint_result.get_tainted_sinks("report").is_empty());
+}
@@ -0,0 +1,69 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/assumptions.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn unsound_assumptions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    config.ext_functions.insert("known".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    config.unknown_instructions = config::UnknownInstructionHandling::JoinOperands;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "entry", None, HashMap::new(), HashMap::new());
+
+    // the call of `known` follows its configuration, so it isn't an assumption
+    let entry = Name::from("entry");
+    let assumptions = taint_result.get_unsound_assumptions();
+    assert_eq!(
+        assumptions.iter().map(|(fn_name, block, index, _)| (*fn_name, *block, *index)).collect::<Vec<_>>(),
+        vec![("entry", &entry, Some(0)), ("entry", &entry, Some(2)), ("entry", &entry, Some(3)), ("entry", &entry, Some(6))],
+    );
+    assert!(assumptions[0].3.starts_with("inttoptr"));
+    assert!(assumptions[1].3.starts_with("inline assembly"));
+    assert!(assumptions[2].3.contains("\"unknown\""));
+    assert!(assumptions[3].3.contains("function pointer"));
+}
+
+#[test]
+fn conservative_assumptions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    config.ext_functions.insert("known".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    config.unsound_assumptions = config::UnsoundAssumptionHandling::Conservative;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "entry", None, HashMap::new(), HashMap::new());
+
+    // nothing is assumed, so the inline assembly needs no `unknown_instructions`
+    assert!(taint_result.get_unsound_assumptions().is_empty());
+    assert!(taint_result.get_unhandled_constructs().is_empty());
+
+    // everything which would have been assumed untainted is tainted, even
+    // with untainted arguments
+    for var in &["v", "a", "u", "r"] {
+        assert_eq!(taint_result.get_var_type("entry", &Name::from(*var)), &TaintedType::TaintedValue, "expected {} to be tainted", var);
+    }
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("k")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+#[should_panic(expected = "Unsound assumption required: inttoptr")]
+fn panic_on_unsound_assumption() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.unsound_assumptions = config::UnsoundAssumptionHandling::Panic;
+    do_taint_analysis_on_function(&modules, &config, "entry", None, HashMap::new(), HashMap::new());
+}
@@ -0,0 +1,64 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/audit.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn library_audit() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.register_writes_are_sinks = true;
+    let audit = do_library_audit(&modules, &config);
+
+    // `set_level` is internal and `internal_only` is hidden
+    assert_eq!(audit.get_entry_points(), &["api", "set_config"]);
+    assert_eq!(audit.get_tainted_sinks(), vec![("api", &Name::from("entry"), 2)]);
+    assert_eq!(audit.get_tainted_globals(), vec![&Name::from("config"), &Name::from("st")]);
+    assert!(!audit.get_taint_result().is_global_tainted(&Name::from("untouched")));
+    assert_eq!(audit.get_tainted_fields(), vec![("struct.state", 1)]);
+    assert_eq!(
+        audit.to_string(),
+        "entry points: api, set_config\n\
+         tainted sinks:\n  api block %entry instruction 2\n\
+         tainted globals:\n  %config\n  %st\n\
+         tainted fields:\n  \"struct.state\" field 1\n",
+    );
+}
+
+#[test]
+fn sarif() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.register_writes_are_sinks = true;
+    let audit = do_library_audit(&modules, &config);
+
+    let mut sarif = Vec::new();
+    audit.get_taint_result().write_sarif(&OutputFilter::default(), &mut sarif).unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&sarif).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "tainted-sink");
+    assert_eq!(results[0]["locations"][0]["logicalLocations"][0]["name"], "api");
+
+    // no findings in the other functions
+    let filter = OutputFilter {
+        functions: vec!["set_config".into()],
+        only_tainted: false,
+    };
+    let mut sarif = Vec::new();
+    audit.get_taint_result().write_sarif(&filter, &mut sarif).unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&sarif).unwrap();
+    assert_eq!(sarif["runs"][0]["results"], serde_json::json!([]));
+}
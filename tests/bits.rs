@@ -0,0 +1,55 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/bits.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn whole_value_taint() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "fields";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("none")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("set")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_tainted_bits(funcname, &Name::from("byte")), None);
+}
+
+#[test]
+fn bit_level_taint() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.bit_level_taint = true;
+    let funcname = "fields";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    let bits = |var: &str| taint_result.get_tainted_bits(funcname, &Name::from(var));
+
+    // shifts and masks move and clear bits
+    assert_eq!(bits("byte"), Some(0xff));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("byte")), &TaintedType::TaintedValue);
+    assert_eq!(bits("none"), Some(0));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("none")), &TaintedType::UntaintedValue);
+    assert_eq!(bits("shifted"), Some(0xf0));
+    assert_eq!(bits("set"), Some(0));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("set")), &TaintedType::UntaintedValue);
+
+    // carries reach every bit above the lowest tainted one
+    assert_eq!(bits("sum"), Some(0xffff_fff0));
+
+    // truncation and extension
+    assert_eq!(bits("b"), Some(0xff));
+    assert_eq!(bits("s"), Some(0xffff_ffff));
+    assert_eq!(bits("z"), Some(0xff));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
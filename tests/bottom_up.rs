@@ -0,0 +1,57 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/bottom_up.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn config(bottom_up: bool) -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.bottom_up = bottom_up;
+    config
+}
+
+/// Run the analysis to completion, returning the result and the number of
+/// steps it took
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> (TaintResult<'m>, usize) {
+    let mut ts = TaintState::new_single_function(modules, config, "top", Some(vec![]), HashMap::new(), HashMap::new());
+    let mut steps = 0;
+    while ts.step() {
+        steps += 1;
+    }
+    (ts.into_taint_result(), steps)
+}
+
+#[test]
+fn bottom_up() {
+    init_logging();
+    let modules = [get_module()];
+    let top_down_config = config(false);
+    let bottom_up_config = config(true);
+    let (top_down, top_down_steps) = analyze(&modules, &top_down_config);
+    let (bottom_up, bottom_up_steps) = analyze(&modules, &bottom_up_config);
+
+    // same results either way
+    for (fn_name, var_name) in &[("top", "r"), ("top", "c"), ("top", "sum"), ("mid1", "r"), ("leaf", "x"), ("clean", "x")] {
+        assert_eq!(
+            bottom_up.get_var_type(fn_name, &Name::from(*var_name)),
+            top_down.get_var_type(fn_name, &Name::from(*var_name)),
+            "{} in {}", var_name, fn_name,
+        );
+    }
+    assert_eq!(bottom_up.get_var_type("top", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(bottom_up.get_var_type("top", &Name::from("c")), &TaintedType::UntaintedValue);
+
+    // but the secret's taint reaches `top` without repeatedly going back to
+    // the top of the call chain
+    assert!(bottom_up_steps < top_down_steps, "bottom-up took {} steps, top-down took {}", bottom_up_steps, top_down_steps);
+}
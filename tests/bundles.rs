@@ -0,0 +1,64 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/bundles.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn deopt_bundle() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // the tainted deopt state doesn't reach the callee or the result
+    let funcname = "with_deopt";
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let summary = taint_result.get_function_summary("add_one").unwrap();
+    assert_eq!(summary.get_params().collect::<Vec<_>>(), vec![&TaintedType::UntaintedValue]);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::UntaintedValue);
+
+    // but the arguments still do
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn kcfi_bundle() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "via_pointer";
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedFnPtr(FnTargets::new()), TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_function_summary("add_one").unwrap().get_params().count(), 1);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,93 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/call_graph.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("log_value".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = vec![TaintedType::TaintedValue, TaintedType::UntaintedValue];
+    do_taint_analysis_on_function(modules, config, "top", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn call_graph_dot() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = analyze(&modules, &config);
+
+    let mut dot = Vec::new();
+    taint_result.write_call_graph_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.starts_with("digraph \"call graph\" {\n"));
+    assert!(dot.ends_with("}\n"));
+    // `helper` returns tainted data; `log_value` is external
+    assert!(dot.contains("  \"helper\" [label=\"helper\", style=filled, fillcolor=lightpink];\n"));
+    assert!(dot.contains("  \"clean\" [label=\"clean\"];\n"));
+    assert!(dot.contains("  \"log_value\" [label=\"log_value\", style=dashed];\n"));
+    assert!(dot.contains("  \"top\" [label=\"top\", style=filled, fillcolor=lightpink];\n"));
+    // taint flows into and out of `helper`, but not across the other edges
+    assert!(dot.contains("  \"top\" -> \"helper\" [label=\"args, ret\", color=red, fontcolor=red];\n"));
+    assert!(dot.contains("  \"top\" -> \"clean\";\n"));
+    assert!(dot.contains("  \"clean\" -> \"log_value\";\n"));
+}
+
+#[test]
+fn call_graph_json() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = analyze(&modules, &config);
+
+    let mut json = Vec::new();
+    taint_result.write_call_graph_json(&mut json).unwrap();
+    let graph: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+    let functions = graph["functions"].as_array().unwrap();
+    let names: Vec<&str> = functions.iter().map(|f| f["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["clean", "helper", "log_value", "top"]);
+    assert_eq!(functions[1]["returns_tainted"], true);
+    assert_eq!(functions[1]["analyzed"], true);
+    assert_eq!(functions[1]["module"], modules[0].name.as_str());
+    assert_eq!(functions[2]["analyzed"], false);
+    assert_eq!(functions[2]["module"], serde_json::Value::Null);
+
+    let edges = graph["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 3);
+    assert_eq!(edges[0], serde_json::json!({
+        "caller": "clean",
+        "callee": "log_value",
+        "calls": 1,
+        "tainted_args": false,
+        "tainted_return": false,
+    }));
+    assert_eq!(edges[1], serde_json::json!({
+        "caller": "top",
+        "callee": "clean",
+        "calls": 1,
+        "tainted_args": false,
+        "tainted_return": false,
+    }));
+    assert_eq!(edges[2], serde_json::json!({
+        "caller": "top",
+        "callee": "helper",
+        "calls": 2,
+        "tainted_args": true,
+        "tainted_return": true,
+    }));
+}
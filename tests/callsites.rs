@@ -0,0 +1,56 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/callsites.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.model_allocators = true;
+    config.ext_functions.insert("log_value".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+#[test]
+fn tainted_callsites() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let args = vec![
+        ("copy", vec![
+            TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+            TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+            TaintedType::UntaintedValue,
+        ]),
+        ("copy_const", vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)]),
+        ("report", vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+    ].into_iter().collect();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, args, HashMap::new(), HashMap::new());
+
+    let entry = Name::from("entry");
+    // `memcpy` matches the `llvm.memcpy.*` intrinsics; only the copy of the
+    // tainted source is reported
+    assert_eq!(taint_result.tainted_callsites_of("memcpy"), vec![("copy", &entry, 0)]);
+    // only the call passing `%secret` is reported
+    assert_eq!(taint_result.tainted_callsites_of("log_value"), vec![("report", &entry, 0)]);
+    assert!(taint_result.tainted_callsites_of("free").is_empty());
+}
+
+#[test]
+fn no_tainted_callsites() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert!(taint_result.tainted_callsites_of("memcpy").is_empty());
+    assert!(taint_result.tainted_callsites_of("log_value").is_empty());
+}
@@ -0,0 +1,61 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/parallel.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn cfg_dot() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    // only `op`, which selects the case of the switch, is tainted
+    let args = vec![
+        TaintedType::TaintedValue,
+        TaintedType::UntaintedValue,
+        TaintedType::UntaintedValue,
+        TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    ];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "dispatch", Some(args), HashMap::new(), HashMap::new());
+
+    let mut dot = Vec::new();
+    taint_result.write_cfg_dot("dispatch", &mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.starts_with("digraph \"dispatch\" {\n"));
+    assert!(dot.ends_with("}\n"));
+    let node_line = |block: &str| {
+        dot.lines()
+            .find(|line| line.contains(&format!("[label=\"%{}:\\l", block)))
+            .unwrap_or_else(|| panic!("no node for block {:?}", block))
+    };
+
+    // the switch on `op` is tainted, and the cases execute under tainted control
+    assert!(node_line("entry").contains("switch i32 %op"));
+    assert!(node_line("entry").contains("; [T]\\l"));
+    assert!(!node_line("entry").contains("fillcolor"));
+    for block in &["case0", "case1", "case2", "case3", "default"] {
+        assert!(node_line(block).contains("fillcolor"), "expected {} to execute under tainted control", block);
+    }
+    // the blocks after the switch re-converges don't; the phi is tainted by implicit flow
+    for block in &["merge", "loop", "body", "exit"] {
+        assert!(!node_line(block).contains("fillcolor"), "expected {} not to execute under tainted control", block);
+    }
+    assert!(node_line("merge").contains("[T]"));
+    assert!(node_line("case1").contains("%r1 = mul i32 %y, i32 2  ; UntaintedValue\\l"));
+
+    // edges, including to the return node
+    assert!(dot.contains("  ret [label=\"return\", shape=oval];\n"));
+    assert!(dot.contains("  b0 -> b1;\n"));
+    assert!(dot.contains("  b9 -> ret;\n"));
+
+    assert!(taint_result.write_cfg_dot("nonexistent", &mut Vec::new()).is_err());
+}
@@ -0,0 +1,83 @@
+use std::process::{Command, Output};
+
+fn run_cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_llvm-ir-taint"))
+        .args(args)
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run the CLI: {}", e))
+}
+
+fn stdout_of(args: &[&str]) -> String {
+    let output = run_cli(args);
+    assert!(output.status.success(), "CLI failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn formats() {
+    let audit = "tests/additional_bcfiles/audit.bc";
+    let entry = ["--entry", "api", "--tainted-arg", "0"];
+
+    let text = stdout_of(&[&entry[..], &[audit]].concat());
+    assert!(text.starts_with("function \"api\":\n"));
+    assert!(text.contains("  [T] %t: TaintedValue\n"));
+    assert!(text.contains("function \"set_level\":\n"));
+    assert!(text.contains("named structs:\n"));
+
+    let text = stdout_of(&[&entry[..], &["--only-tainted", "--function", "api", audit]].concat());
+    assert_eq!(text, "function \"api\":\n parameters:\n  [T] %v: TaintedValue\n block %entry:\n  [T] %t: TaintedValue\n");
+
+    let export = "tests/additional_bcfiles/export.bc";
+    let scale = ["--entry", "scale", "--tainted-arg", "1"];
+    let csv = stdout_of(&[&scale[..], &["--format", "csv", "--only-tainted", "--function", "scale", export]].concat());
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines, vec![
+        "module,function,value,status,labels,location",
+        "tests/additional_bcfiles/export.bc,scale,%k,tainted,,export.c:11",
+        "tests/additional_bcfiles/export.bc,scale,\"%x,y\",tainted,,export.c:13:15",
+    ]);
+
+    let json = stdout_of(&[&scale[..], &["--format", "json", "--function", "scale", export]].concat());
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 7);
+
+    let dot = stdout_of(&["--format", "dot", export]);
+    assert!(dot.starts_with("digraph \"call graph\" {\n"));
+    let dot = stdout_of(&[&entry[..], &["--format", "dot", "--function", "api", audit]].concat());
+    assert!(dot.starts_with("digraph \"api\" {\n"));
+
+    let sarif = stdout_of(&[&["--format", "sarif"], &entry[..], &[audit]].concat());
+    let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+    assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "tainted-sink");
+}
+
+#[test]
+fn bad_usage() {
+    let output = run_cli(&["--format", "xml", "tests/additional_bcfiles/audit.bc"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("llvm-ir-taint: unknown format \"xml\"\n"));
+
+    let output = run_cli(&["--tainted-arg", "0", "tests/additional_bcfiles/audit.bc"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run_cli(&["--entry", "no_such_function", "tests/additional_bcfiles/audit.bc"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn multiple_inputs() {
+    let args = ["--entry", "host", "--tainted-arg", "0", "--only-tainted", "--function", "plugin_id"];
+    let expected = "function \"plugin_id\":\n parameters:\n  [T] %x: TaintedValue\n block %entry [tainted terminator]:\n";
+
+    // the plugin module from a static archive
+    let text = stdout_of(&[&args[..], &["tests/additional_bcfiles/incremental_host.bc", "tests/additional_bcfiles/incremental_plugin.a"]].concat());
+    assert_eq!(text, expected);
+
+    // the host module as bitcode and the plugin module as textual IR, from a
+    // response file
+    let text = stdout_of(&[&args[..], &["@tests/additional_bcfiles/incremental.rsp"]].concat());
+    assert_eq!(text, expected);
+
+    let output = run_cli(&["@tests/additional_bcfiles/no_such_file.rsp"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,40 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/closures.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)];
+    do_taint_analysis_on_function(modules, config, "run", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn resolve_closure_thunks() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, the closure may call any function of its type
+    let taint_result = analyze(&modules, &config);
+    assert!(taint_result.get_function_summary("add_env").is_some());
+    assert!(taint_result.get_function_summary("ignore_env").is_some());
+
+    // but only `add_env` is ever stored in it, and the tainted environment
+    // reaches its body
+    config.resolve_closure_thunks = true;
+    let taint_result = analyze(&modules, &config);
+    assert!(taint_result.get_function_summary("ignore_env").is_none());
+    assert_eq!(taint_result.get_var_type("add_env", &Name::from("e")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("call_closure", &Name::from("call")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("run", &Name::from("r")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,56 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/constant_pointers.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn args() -> Vec<TaintedType> {
+    vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue), TaintedType::UntaintedValue]
+}
+
+#[test]
+fn block_address() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "from_label";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn constant_select() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "from_select";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn null() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "from_null";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,31 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/dead_sources.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn dead_sources() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    for source in &["read_input", "read_unused", "fill_buffer", "never_called"] {
+        config.ext_functions.insert(source.to_string(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    }
+    let ts = TaintState::do_analysis_single_function(&modules, &config, "process", Some(vec![]), HashMap::new(), HashMap::new());
+
+    // only the result of `read_input` is used; `fill_buffer` returns nothing
+    // to taint, and `never_called` is never called
+    assert_eq!(ts.get_dead_sources(), vec!["fill_buffer", "never_called", "read_unused"]);
+    let taint_result = ts.into_taint_result();
+    assert_eq!(taint_result.get_var_type("process", &Name::from("r")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,57 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/debug_info.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_debug_info() -> DebugInfo {
+    let path = "tests/additional_bcfiles/debug_info.ll";
+    DebugInfo::from_ll_path(path)
+        .unwrap_or_else(|e| panic!("Failed to read debug info from {:?}: {}", path, e))
+}
+
+#[test]
+fn field_paths() {
+    init_logging();
+    let modules = [get_module()];
+    let debug_info = get_debug_info();
+    let config = Config::default();
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::NamedStruct("struct.request".into())), TaintedType::TaintedValue];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "fill", Some(args), HashMap::new(), HashMap::new());
+
+    // `hdr` is a `header_t`, a typedef of `struct header`
+    assert_eq!(debug_info.resolve_field_path("request.hdr.len"), Ok(("struct.request".into(), vec![0, 0])));
+    assert!(taint_result.is_field_tainted_by_path(&debug_info, "request.hdr.len"));
+    assert!(!taint_result.is_field_tainted_by_path(&debug_info, "request.hdr.flags"));
+    assert!(taint_result.is_field_tainted_by_path(&debug_info, "request.body"));
+    assert_eq!(
+        taint_result.get_field_type_by_path(&debug_info, "request.body"),
+        Ok(TaintedType::array_or_vec_of(TaintedType::TaintedValue)),
+    );
+
+    // the members of an array of structs are those of its elements, which
+    // share their taint with every other `struct header`
+    assert_eq!(debug_info.resolve_field_path("request.extra.flags"), Ok(("struct.request".into(), vec![2, 0, 1])));
+    assert!(taint_result.is_field_tainted_by_path(&debug_info, "request.extra.len"));
+    assert!(!taint_result.is_field_tainted_by_path(&debug_info, "request.extra.flags"));
+    assert!(taint_result.is_field_tainted_by_path(&debug_info, "header.len"));
+
+    assert!(debug_info.resolve_field_path("request.hdr.size").is_err());
+    assert!(debug_info.resolve_field_path("request.body.len").is_err());
+    assert!(debug_info.resolve_field_path("response.hdr").is_err());
+}
+
+#[test]
+fn no_debug_info() {
+    let debug_info = DebugInfo::from_ll_str("define void @f() {\nentry:\n  ret void\n}\n");
+    assert!(debug_info.resolve_field_path("request.hdr.len").is_err());
+}
@@ -0,0 +1,64 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/demangle.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn demangled_names() {
+    assert_eq!(demangle("_ZN3foo3barEPKc"), Some("foo::bar(char const*)".into()));
+    assert_eq!(demangle("_ZN4core3fmt5write17h0123456789abcdefE"), Some("core::fmt::write".into()));
+    assert_eq!(demangle("malloc"), None);
+}
+
+#[test]
+fn config_by_demangled_name() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("foo::bar".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+
+    let args = Some(vec![TaintedType::tainted_ptr_to(TaintedType::UntaintedValue)]);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "_Z8call_barPKc", args, HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("_Z8call_barPKc", &Name::from("a")), &TaintedType::TaintedValue);
+
+    // the mangled name takes precedence
+    config.ext_functions.insert("_ZN3foo3barEPKc".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    let args = Some(vec![TaintedType::tainted_ptr_to(TaintedType::UntaintedValue)]);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "_Z8call_barPKc", args, HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("_Z8call_barPKc", &Name::from("a")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn find_functions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.find_functions("util::twice"), vec!["_ZN4util5twiceEi", "_ZN4util5twiceEl"]);
+    assert_eq!(taint_result.find_functions("util::twice(long)"), vec!["_ZN4util5twiceEl"]);
+    assert_eq!(taint_result.find_functions("_Z8call_barPKc"), vec!["_Z8call_barPKc"]);
+    assert!(taint_result.to_string().contains("function \"_Z8call_barPKc\" (call_bar(char const*)):"));
+
+    // filters also go by the demangled names
+    let filter = OutputFilter {
+        functions: vec!["util::twice(long)".into()],
+        only_tainted: false,
+    };
+    let mut text = Vec::new();
+    taint_result.write_text(&filter, &mut text).unwrap();
+    let text = String::from_utf8(text).unwrap();
+    assert!(text.starts_with("function \"_ZN4util5twiceEl\" (util::twice(long)):\n"));
+    assert!(!text.contains("_ZN4util5twiceEi"));
+}
@@ -0,0 +1,32 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/dependencies.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn affected_functions() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+
+    // callers and callees depend on each other
+    assert_eq!(taint_result.functions_affected_by(&["leaf"]), vec!["caller", "leaf"]);
+    assert_eq!(taint_result.functions_affected_by(&["caller"]), vec!["caller", "leaf"]);
+    // as do functions using the same mutable global
+    assert_eq!(taint_result.functions_affected_by(&["writer"]), vec!["reader", "writer"]);
+    // but nothing else does
+    assert_eq!(taint_result.functions_affected_by(&["alone"]), vec!["alone"]);
+    assert_eq!(taint_result.functions_affected_by(&["alone", "leaf"]), vec!["alone", "caller", "leaf"]);
+    assert!(taint_result.functions_affected_by(&["missing"]).is_empty());
+}
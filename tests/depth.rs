@@ -0,0 +1,47 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/depth.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_module(
+        modules,
+        config,
+        std::iter::once(("store_deep", vec![TaintedType::TaintedValue])).collect(),
+        HashMap::new(),
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn max_pointer_depth() {
+    init_logging();
+    let modules = [get_module()];
+
+    // by default, each function's pointer chain is tracked separately
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("load_deep", &Name::from("v")), &TaintedType::UntaintedValue);
+
+    // with a depth limit of 1, the `i32`s at the end of both chains are deep,
+    // so they share a single summary taint; and `load_deep` must see the
+    // store in `store_deep` whichever order they're processed in
+    let mut config = Config::default();
+    config.max_pointer_depth = Some(1);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("load_deep", &Name::from("v")), &TaintedType::TaintedValue);
+    assert!(taint_result.does_function_return_tainted("load_deep"));
+
+    // the pointers themselves are still untainted
+    assert!(!taint_result.is_type_tainted(taint_result.get_var_type("load_deep", &Name::from("r"))));
+}
@@ -0,0 +1,49 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/bottom_up.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn start<'m>(modules: &'m [Module], config: &'m Config) -> TaintState<'m> {
+    TaintState::new_multiple_functions(modules, config, HashMap::new(), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn deterministic() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.deterministic = true;
+
+    // every step of several runs is the same (each run has its own hash maps,
+    // with their own random iteration orders)
+    let mut first = start(&modules, &config);
+    let mut steps = vec![first.checkpoint()];
+    while first.step() {
+        steps.push(first.checkpoint());
+    }
+    for _ in 0 .. 4 {
+        let mut ts = start(&modules, &config);
+        for (index, step) in steps.iter().enumerate() {
+            assert_eq!(&ts.checkpoint(), step, "step {}", index);
+            ts.step();
+        }
+        assert!(ts.is_done());
+    }
+
+    // and in particular, functions are processed in order by name
+    let mut ts = start(&modules, &config);
+    let first_fn = ts.get_pending_functions().into_iter().min().unwrap();
+    ts.step();
+    assert!(!ts.get_pending_functions().contains(&first_fn));
+}
@@ -0,0 +1,79 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/entries.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_args() -> HashMap<&'static str, Vec<TaintedType>> {
+    vec![
+        ("handle_get", vec![TaintedType::UntaintedValue]),
+        ("handle_post", vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)]),
+    ].into_iter().collect()
+}
+
+#[test]
+fn results_per_entry() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.per_entry_results = true;
+    let taint_result = do_taint_analysis_on_module(&modules, &config, get_args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_entry_points(), vec!["handle_get", "handle_post"]);
+
+    // the shared helper is tainted when entered through `handle_post`, but
+    // not through `handle_get`
+    let r = Name::from("r");
+    let get_result = taint_result.get_entry_result("handle_get").unwrap();
+    assert_eq!(get_result.get_var_type("helper", &r), &TaintedType::UntaintedValue);
+    assert_eq!(get_result.get_var_type("handle_get", &r), &TaintedType::UntaintedValue);
+    let post_result = taint_result.get_entry_result("handle_post").unwrap();
+    assert_eq!(post_result.get_var_type("helper", &r), &TaintedType::TaintedValue);
+    assert_eq!(post_result.get_var_type("handle_post", &r), &TaintedType::TaintedValue);
+
+    // the merged result is as usual
+    assert_eq!(taint_result.get_var_type("helper", &r), &TaintedType::TaintedValue);
+    assert!(taint_result.get_entry_result("helper").is_none());
+}
+
+#[test]
+fn no_results_per_entry_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, get_args(), HashMap::new(), HashMap::new());
+    assert!(taint_result.get_entry_points().is_empty());
+    assert!(taint_result.get_entry_result("handle_post").is_none());
+    assert_eq!(taint_result.get_var_type("helper", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn shared_leaf_summaries() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.per_entry_results = true;
+    config.share_leaf_summaries = true;
+    assert_eq!(SummaryStore::new(&modules, &config).functions(), vec!["helper"]);
+    let taint_result = do_taint_analysis_on_module(&modules, &config, get_args(), HashMap::new(), HashMap::new());
+
+    // calls of the helper are answered from the store, with the same results
+    let r = Name::from("r");
+    let get_result = taint_result.get_entry_result("handle_get").unwrap();
+    assert_eq!(get_result.get_var_type("handle_get", &r), &TaintedType::UntaintedValue);
+    assert!(get_result.get_function_summary("helper").is_none());
+    let post_result = taint_result.get_entry_result("handle_post").unwrap();
+    assert_eq!(post_result.get_var_type("handle_post", &r), &TaintedType::TaintedValue);
+    assert!(post_result.get_function_summary("helper").is_none());
+
+    // the merged result still analyzes the helper
+    assert_eq!(taint_result.get_var_type("helper", &r), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,66 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/explain.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = vec![TaintedType::TaintedValue, TaintedType::UntaintedValue];
+    do_taint_analysis_on_function(modules, config, "run", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn explain() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.record_provenance = true;
+    let taint_result = analyze(&modules, &config);
+
+    let steps = taint_result.explain("run", &Name::from("r")).unwrap();
+    use ExplanationStepKind::*;
+    assert_eq!(
+        steps.iter().map(|step| step.kind).collect::<Vec<_>>(),
+        vec![Source, Argument, Instruction, Return, MemoryWrite, Instruction, Instruction],
+    );
+    let entry = Name::from("entry");
+    // from the tainted argument ...
+    let first = steps.first().unwrap();
+    assert_eq!((first.kind, first.function, first.value.as_str(), &first.site), (ExplanationStepKind::Source, "run", "%secret", &None));
+    // ... into and out of `twice` ...
+    assert!(steps.iter().any(|step| step.kind == ExplanationStepKind::Argument && step.function == "twice" && step.value == "%x"));
+    let ret = steps.iter().find(|step| step.kind == ExplanationStepKind::Return).unwrap();
+    assert_eq!((ret.function, ret.value.as_str()), ("run", "%d"));
+    assert_eq!(ret.site.as_ref().map(|(f, b, i, _)| (*f, b, *i)), Some(("run", &entry, 1)));
+    // ... through memory ...
+    let write = steps.iter().find(|step| step.kind == ExplanationStepKind::MemoryWrite).unwrap();
+    assert_eq!((write.function, write.value.as_str()), ("run", "*%slot"));
+    // ... to the load and the add
+    let n = steps.len();
+    assert_eq!((steps[n - 2].kind, steps[n - 2].value.as_str()), (ExplanationStepKind::Instruction, "%v"));
+    let last = &steps[n - 1];
+    assert_eq!((last.kind, last.function, last.value.as_str()), (ExplanationStepKind::Instruction, "run", "%r"));
+    let (site_fn, site_block, site_index, site_text) = last.site.clone().unwrap();
+    assert_eq!((site_fn, site_block, site_index), ("run", entry, 4));
+    assert!(site_text.contains("add"));
+
+    assert!(taint_result.explain("run", &Name::from("public_")).is_err());
+}
+
+#[test]
+fn explain_requires_provenance() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config);
+    assert!(taint_result.explain("run", &Name::from("r")).is_err());
+}
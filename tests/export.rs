@@ -0,0 +1,144 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/export.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn args(k: TaintedType) -> Vec<TaintedType> {
+    vec![TaintedType::untainted_ptr_to(TaintedType::NamedStruct("struct.pair".into())), k]
+}
+
+#[test]
+fn csv() {
+    init_logging();
+    let modules = [get_module()];
+    let modname = modules[0].name.as_str();
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "scale", Some(args(TaintedType::TaintedValue)), HashMap::new(), HashMap::new());
+
+    let mut csv = Vec::new();
+    taint_result.write_table(TableFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    // a header, a row for each of the 7 variables, the global, and the 2
+    // struct fields
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "module,function,value,status,labels,location");
+    assert!(lines.contains(&format!("{},scale,%a,untainted,,export.c:12:14", modname).as_str()));
+    assert!(lines.contains(&format!("{},scale,\"%x,y\",tainted,,export.c:13:15", modname).as_str()));
+    assert!(lines.contains(&format!("{},scale,%r,untainted,,export.c:15:13", modname).as_str()));
+    assert!(lines.iter().any(|line| line.starts_with(&format!("{},scale,%k,tainted,,", modname))));
+    assert!(lines.iter().any(|line| line.starts_with(&format!("{},,@counter,tainted,,", modname))));
+    assert!(lines.contains(&",,struct.pair[0],untainted,,"));
+    assert!(lines.contains(&",,struct.pair[1],untainted,,"));
+}
+
+#[test]
+fn tsv() {
+    init_logging();
+    let modules = [get_module()];
+    let modname = modules[0].name.as_str();
+    let config = Config::default();
+    // only the first field of the struct is tainted
+    let named_structs = std::iter::once((
+        "struct.pair".to_owned(),
+        NamedStructInitialDef::InitialDef(TaintedType::struct_of(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue])),
+    )).collect();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "scale", Some(args(TaintedType::UntaintedValue)), HashMap::new(), named_structs);
+
+    let mut tsv = Vec::new();
+    taint_result.write_table(TableFormat::Tsv, &mut tsv).unwrap();
+    let tsv = String::from_utf8(tsv).unwrap();
+    let lines: Vec<&str> = tsv.lines().collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "module\tfunction\tvalue\tstatus\tlabels\tlocation");
+    assert!(lines.contains(&format!("{}\tscale\t%a\ttainted\t\texport.c:12:14", modname).as_str()));
+    assert!(lines.contains(&format!("{}\tscale\t%x,y\ttainted\t\texport.c:13:15", modname).as_str()));
+    assert!(lines.contains(&"\t\tstruct.pair[0]\ttainted\t\t"));
+    assert!(lines.contains(&"\t\tstruct.pair[1]\tuntainted\t\t"));
+}
+
+/// The lattice `Low < High`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Level {
+    Low,
+    High,
+}
+
+impl TaintLabel for Level {
+    fn bottom() -> Self {
+        Level::Low
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        if self == &Level::High || other == &Level::High {
+            Level::High
+        } else {
+            Level::Low
+        }
+    }
+
+    fn ext_source() -> Self {
+        Level::High
+    }
+}
+
+#[test]
+fn labeled_csv() {
+    init_logging();
+    let modules = [get_module()];
+    let modname = modules[0].name.as_str();
+    let config = Config::default();
+    let args = vec![
+        (TaintedType::untainted_ptr_to(TaintedType::NamedStruct("struct.pair".into())), Level::Low),
+        (TaintedType::TaintedValue, Level::High),
+    ];
+    let result = do_labeled_taint_analysis_on_function(&modules, &config, "scale", Some(args), HashMap::new(), HashMap::new());
+
+    let mut csv = Vec::new();
+    result.write_table(TableFormat::Csv, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "module,function,value,status,labels,location");
+    assert!(lines.contains(&format!("{},scale,%a,untainted,Low,export.c:12:14", modname).as_str()));
+    assert!(lines.contains(&format!("{},scale,\"%x,y\",tainted,High,export.c:13:15", modname).as_str()));
+}
+
+#[test]
+fn filtered() {
+    init_logging();
+    let modules = [get_module()];
+    let modname = modules[0].name.as_str();
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "scale", Some(args(TaintedType::TaintedValue)), HashMap::new(), HashMap::new());
+    let filter = OutputFilter {
+        functions: vec!["scale".into()],
+        only_tainted: true,
+    };
+
+    // only the tainted variables of `scale`, and not the global
+    let mut json = Vec::new();
+    taint_result.write_filtered_table(TableFormat::Json, &filter, &mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    let rows = json.as_array().unwrap();
+    assert!(!rows.is_empty());
+    assert!(rows.iter().all(|row| row["function"] == "scale" && row["status"] == "tainted"));
+    assert!(rows.contains(&serde_json::json!({
+        "module": modname,
+        "function": "scale",
+        "value": "%x,y",
+        "status": "tainted",
+        "labels": "",
+        "location": "export.c:13:15",
+    })));
+}
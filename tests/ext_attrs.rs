@@ -0,0 +1,59 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/ext_attrs.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_function_attributes = true;
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, fn_name: &str) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, fn_name, Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn readnone() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    config.ext_functions.insert("lookup".into(), config::ExternalFunctionHandling::PropagateTaintDeep);
+
+    // `lookup` is `readnone`, so it can't see the tainted data `%p` points to
+    let taint_result = analyze(&modules, &config, "lookup_contents");
+    assert_eq!(taint_result.get_var_type("lookup_contents", &Name::from("r")), &TaintedType::UntaintedValue);
+    assert!(!taint_result.does_function_return_tainted("lookup"));
+
+    // but its result does depend on its arguments themselves
+    let taint_result = analyze(&modules, &config, "lookup_value");
+    assert_eq!(taint_result.get_var_type("lookup_value", &Name::from("r")), &TaintedType::TaintedValue);
+    assert!(taint_result.does_function_return_tainted("lookup"));
+}
+
+#[test]
+fn noalias_return() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    config.ext_functions.insert("my_alloc".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+
+    // the fresh pointer `my_alloc` returns isn't tainted by its argument
+    let taint_result = analyze(&modules, &config, "alloc");
+    assert!(matches!(taint_result.get_var_type("alloc", &Name::from("m")), TaintedType::UntaintedPointer(_)));
+
+    // unless we ignore the attributes
+    config.ext_function_attributes = false;
+    let taint_result = analyze(&modules, &config, "alloc");
+    assert!(matches!(taint_result.get_var_type("alloc", &Name::from("m")), TaintedType::TaintedPointer(_)));
+}
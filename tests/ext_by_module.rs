@@ -0,0 +1,78 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module(name: &str) -> Module {
+    let modname = format!("tests/additional_bcfiles/{}.bc", name);
+    Module::from_bc_path(&modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_modules() -> [Module; 2] {
+    [get_module("ext_by_module_main"), get_module("ext_by_module_vendor")]
+}
+
+fn get_config() -> Config {
+    // everything unresolved in our own module panics, except what we
+    // configure explicitly
+    let mut config = Config::default();
+    config.ext_functions.insert("mine".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "entry", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+#[should_panic(expected = "not found in the module")]
+fn unresolved_vendor_function_panics_by_default() {
+    init_logging();
+    let modules = get_modules();
+    let config = get_config();
+    analyze(&modules, &config);
+}
+
+#[test]
+fn override_default_for_vendor_module() {
+    init_logging();
+    let modules = get_modules();
+    let mut config = get_config();
+    config.ext_functions_default_by_module.insert("ext_by_module_vendor.bc".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("vendor_entry", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("m")), &TaintedType::UntaintedValue);
+
+    // entries in `ext_functions` take precedence
+    config.ext_functions.insert("blob_helper".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("v")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn context_only_module() {
+    init_logging();
+    let modules = get_modules();
+    let mut config = get_config();
+    config.context_only_modules.insert("ext_by_module_vendor.bc".into());
+
+    // `vendor_entry` isn't analyzed, but handled as an external function,
+    // with the default for its module
+    config.ext_functions_default_by_module.insert("ext_by_module_vendor.bc".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    let taint_result = analyze(&modules, &config);
+    assert!(!taint_result.get_function_names().any(|name| *name == "vendor_entry"));
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("v")), &TaintedType::UntaintedValue);
+
+    // or as configured in `ext_functions`
+    config.ext_functions.insert("vendor_entry".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("v")), &TaintedType::TaintedValue);
+    let ts = TaintState::do_analysis_single_function(&modules, &config, "entry", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(ts.get_undefined_functions(), vec!["mine", "vendor_entry"]);
+}
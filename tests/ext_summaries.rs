@@ -0,0 +1,42 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/prune.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn external_function_without_summary() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    // starting from `entry`, nothing calls `secret`, so it has no summary
+    let mut ts = TaintState::new_single_function(&modules, &config, "entry", None, HashMap::new(), HashMap::new());
+    assert!(ts.run_until(usize::MAX));
+    assert!(ts.get_function_summary("secret").is_none());
+
+    // put it on the worklist anyway, by editing a checkpoint
+    let mut json = Vec::new();
+    ts.checkpoint().write_json(&mut json).unwrap();
+    let mut value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    value["worklist"] = serde_json::json!(["secret"]);
+    let checkpoint = Checkpoint::read_json(value.to_string().as_bytes()).unwrap();
+    let mut ts = TaintState::resume_from_checkpoint(&modules, &config, &checkpoint).unwrap();
+    assert!(ts.run_until(usize::MAX));
+
+    // it gets the default summary, with its declared types, and then is
+    // handled as usual
+    let summary = ts.get_function_summary("secret").unwrap();
+    assert_eq!(summary.get_params().count(), 0);
+    assert_eq!(summary.get_ret_ty(), &Some(TaintedType::TaintedValue));
+}
@@ -0,0 +1,48 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/fn_ptr_tables.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn args() -> Vec<TaintedType> {
+    vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]
+}
+
+#[test]
+fn all_functions_of_type_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "dispatch", Some(args()), HashMap::new(), HashMap::new());
+    assert!(taint_result.get_function_summary("double_it").is_some());
+    assert!(taint_result.get_function_summary("identity").is_some());
+    assert!(taint_result.get_function_summary("other").is_some());
+}
+
+#[test]
+fn table_entries() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.resolve_fn_ptr_targets = true;
+
+    // the pointer loaded from the table points to one of its entries
+    let funcname = "dispatch";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    let entries = TaintedType::UntaintedFnPtr(vec!["double_it".to_owned(), "identity".to_owned()].into_iter().collect());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("fp")), &entries);
+    // so only those are called
+    assert!(taint_result.get_function_summary("double_it").is_some());
+    assert!(taint_result.get_function_summary("identity").is_some());
+    assert!(taint_result.get_function_summary("other").is_none());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
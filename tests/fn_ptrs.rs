@@ -0,0 +1,46 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/fn_ptrs.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn fn_ptr_to_any_of(fn_names: &[&str]) -> TaintedType {
+    TaintedType::UntaintedFnPtr(fn_names.iter().map(|name| name.to_string()).collect())
+}
+
+#[test]
+fn function_addresses() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "apply";
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+
+    // the select could give either function
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("f")), &fn_ptr_to_any_of(&["dec", "inc"]));
+    // storing it and loading it back keeps both
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("slot")),
+        &TaintedType::untainted_ptr_to(fn_ptr_to_any_of(&["dec", "inc"])),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("g")), &fn_ptr_to_any_of(&["dec", "inc"]));
+    // and a cast to another function type still points to the same function
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("h")), &TaintedType::fn_ptr_to("inc"));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
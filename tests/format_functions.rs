@@ -0,0 +1,93 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/format_functions.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.model_format_functions = true;
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, fn_name: &str, args: Vec<TaintedType>) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, fn_name, Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn sscanf() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)];
+    let taint_result = analyze(&modules, &config, "parse", args);
+    assert_eq!(taint_result.get_var_type("parse", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("parse", &Name::from("n")), &TaintedType::TaintedValue);
+
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)];
+    let taint_result = analyze(&modules, &config, "parse", args);
+    assert_eq!(taint_result.get_var_type("parse", &Name::from("r")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn scanf() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+
+    // input from streams isn't tainted by default
+    let taint_result = analyze(&modules, &config, "read_stdin", vec![]);
+    assert_eq!(taint_result.get_var_type("read_stdin", &Name::from("r")), &TaintedType::UntaintedValue);
+
+    config.stream_input_tainted = true;
+    let taint_result = analyze(&modules, &config, "read_stdin", vec![]);
+    assert_eq!(taint_result.get_var_type("read_stdin", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn snprintf() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let buf = || TaintedType::untainted_ptr_to(TaintedType::UntaintedValue);
+    let taint_result = analyze(&modules, &config, "format", vec![buf(), TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_var_type("format", &Name::from("r")), &TaintedType::TaintedValue);
+
+    let taint_result = analyze(&modules, &config, "format", vec![buf(), TaintedType::UntaintedValue]);
+    assert_eq!(taint_result.get_var_type("format", &Name::from("r")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn tainted_format_string() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    config.report_tainted_format_strings = true;
+
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)];
+    let taint_result = analyze(&modules, &config, "log_message", args);
+    assert_eq!(taint_result.get_tainted_format_strings("log_message"), vec![(&Name::from("entry"), 0)]);
+
+    let mut sarif = Vec::new();
+    taint_result.write_sarif(&OutputFilter::default(), &mut sarif).unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&sarif).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "tainted-format-string");
+
+    // the constant format strings are untainted
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)];
+    let taint_result = analyze(&modules, &config, "parse", args);
+    assert!(taint_result.get_tainted_format_strings("parse").is_empty());
+}
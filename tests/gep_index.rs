@@ -0,0 +1,55 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/gep_index.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue), TaintedType::TaintedValue];
+    do_taint_analysis_on_function(modules, config, "lookup", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn tainted_gep_index() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, the tainted index doesn't taint the pointer, or the value
+    // loaded through it
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(
+        taint_result.get_var_type("lookup", &Name::from("p")),
+        &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("v")), &TaintedType::UntaintedValue);
+
+    // with the option, it does, and loading through it taints what it points
+    // to; the constant index into `base` doesn't
+    config.taint_pointers_with_tainted_indices = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(
+        taint_result.get_var_type("lookup", &Name::from("p")),
+        &TaintedType::tainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("w")), &TaintedType::UntaintedValue);
+
+    // loading through the tainted pointer gives untainted data if configured
+    config.dereferencing_tainted_ptr_gives_tainted = false;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(
+        taint_result.get_var_type("lookup", &Name::from("p")),
+        &TaintedType::tainted_ptr_to(TaintedType::UntaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("v")), &TaintedType::UntaintedValue);
+}
@@ -13,6 +13,12 @@ fn get_module() -> Module {
         .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
 }
 
+fn get_threads_module() -> Module {
+    let modname = "tests/additional_bcfiles/threads.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
 #[test]
 fn globals() {
     init_logging();
@@ -37,3 +43,25 @@ fn globals() {
         Some(&TaintedType::TaintedValue)
     );
 }
+
+#[test]
+fn addl_shared_memory() {
+    init_logging();
+    let modules = [get_threads_module()];
+    let mut config = Config::default();
+    config.shared_memory = true;
+
+    // `writer` stores its tainted argument into the buffer pointed to by a
+    // global, and `reader` reads from that buffer. Neither calls the other,
+    // so without `shared_memory`, whether `reader` sees the taint depends on
+    // the order in which the functions happen to be processed; with it,
+    // `reader` must be processed again after `writer` stores to the buffer
+    let taint_result = do_taint_analysis_on_module(
+        &modules,
+        &config,
+        std::iter::once(("writer", vec![TaintedType::TaintedValue])).collect(),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type("reader", &Name::from(3)), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,54 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/gpu.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "produce", Some(vec![]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn gpu_kernels_as_entry_points() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("get_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    // by default, the kernel isn't analyzed, so nothing reads `token`
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_tainted_globals(), vec![&Name::from("token")]);
+
+    // as an entry point, the kernel copies `token` through shared memory to
+    // `out`; the thread id and the barrier don't panic, and the thread id is
+    // untainted
+    config.gpu_kernels_as_entry_points = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("tid")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("idx")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("w")), &TaintedType::TaintedValue);
+    assert_eq!(
+        taint_result.get_tainted_globals(),
+        vec![&Name::from("out"), &Name::from("tile"), &Name::from("token")],
+    );
+}
+
+#[test]
+fn gpu_kernel_directly() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "kernel", Some(vec![]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("tid")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("kernel", &Name::from("w")), &TaintedType::UntaintedValue);
+    assert!(taint_result.get_tainted_globals().is_empty());
+}
@@ -0,0 +1,96 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/gpu_builtins.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.model_gpu_builtins = true;
+    config.ext_functions.insert("read_secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config
+}
+
+fn ptr(pointee: TaintedType) -> TaintedType {
+    TaintedType::untainted_ptr_to(pointee)
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, src: TaintedType) -> TaintResult<'m> {
+    let args = vec![
+        ptr(TaintedType::UntaintedValue),
+        ptr(TaintedType::UntaintedValue),
+        ptr(TaintedType::UntaintedValue),
+        ptr(src),
+        ptr(TaintedType::UntaintedValue),
+    ];
+    do_taint_analysis_on_function(modules, config, "lookup", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn workgroup_builtins() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+
+    // the work-item id is untainted, and the broadcast of the secret is
+    // tainted
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("gid")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("shared")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("event")), &ptr(TaintedType::NamedStruct("opencl.event_t".into())));
+}
+
+#[test]
+fn device_atomics() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+
+    // adding the secret taints the counter, and so the old value returned
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("counter")), &ptr(TaintedType::TaintedValue));
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("old")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn async_copies() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("tile")), &ptr(TaintedType::UntaintedValue));
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("t")), &TaintedType::UntaintedValue);
+
+    // copying tainted data into local memory taints it
+    let taint_result = analyze(&modules, &config, TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("tile")), &ptr(TaintedType::TaintedValue));
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("t")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn tainted_indices() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+
+    // by default, the lookup at the broadcast secret doesn't taint the entry
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("v")), &TaintedType::UntaintedValue);
+
+    // with tainted indices tainting pointers, it does, and so does the
+    // branch on it
+    config.taint_pointers_with_tainted_indices = true;
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("lookup", &Name::from("pos")), &TaintedType::TaintedValue);
+}
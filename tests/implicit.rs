@@ -0,0 +1,99 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/implicit.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn phi_after_reconvergence() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "implicit",
+        Some(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    // `%i` is computed under tainted control, but only the untainted `y`
+    // chooses its value
+    assert_eq!(taint_result.get_var_type("implicit", &Name::from("i")), &TaintedType::UntaintedValue);
+    // `%m` is where the paths of the tainted branch re-converge
+    assert_eq!(taint_result.get_var_type("implicit", &Name::from("m")), &TaintedType::TaintedValue);
+
+    // with `y` tainted instead, `%i` is tainted, and so `%m` is, through `%i`
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "implicit",
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type("implicit", &Name::from("i")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("implicit", &Name::from("m")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn loop_with_tainted_exit() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "count",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    // the tainted exit condition decides how many times the latch is taken
+    assert_eq!(taint_result.get_var_type("count", &Name::from("n")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("count", &Name::from("n1")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn blocks_under_tainted_control() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "implicit",
+        Some(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    // the blocks which run only depending on `x`, including those which then
+    // also depend on the untainted `y`, since whether they run at all still
+    // depends on `x`
+    let (inner, innermerge, other) = (Name::from("inner"), Name::from("innermerge"), Name::from("other"));
+    let (p, q) = (Name::from("p"), Name::from("q"));
+    assert_eq!(
+        taint_result.blocks_under_tainted_control(),
+        vec![("implicit", &inner), ("implicit", &innermerge), ("implicit", &other), ("implicit", &p), ("implicit", &q)],
+    );
+
+    // with only `y` tainted, just the blocks choosing `%i` depend on it
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "implicit",
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.blocks_under_tainted_control(), vec![("implicit", &p), ("implicit", &q)]);
+}
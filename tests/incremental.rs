@@ -0,0 +1,65 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module(name: &str) -> Module {
+    let modname = format!("tests/additional_bcfiles/{}.bc", name);
+    Module::from_bc_path(&modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config
+}
+
+fn start<'m>(modules: &'m [Module], config: &'m Config) -> TaintState<'m> {
+    let mut ts = TaintState::new_single_function(modules, config, "host", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert!(ts.run_until(usize::MAX));
+    ts
+}
+
+#[test]
+fn add_plugin_module() {
+    init_logging();
+    let host = [get_module("incremental_host")];
+    let plugin = [get_module("incremental_plugin")];
+    let config = get_config();
+
+    // on its own, `plugin_id` is external and ignored
+    let mut ts = start(&host, &config);
+    assert_eq!(ts.get_function_taint_map("host").unwrap()[&Name::from("r")], TaintedType::UntaintedValue);
+
+    // once it's defined, the host sees its argument flow through
+    ts.add_modules(&plugin, false);
+    assert!(ts.run_until(usize::MAX));
+    assert!(ts.get_function_taint_map("plugin_other").is_none());
+    let taint_result = ts.into_taint_result();
+    assert_eq!(taint_result.get_var_type("plugin_id", &Name::from("x")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("host", &Name::from("r")), &TaintedType::TaintedValue);
+
+    // same as analyzing both modules from the start
+    let both = [get_module("incremental_host"), get_module("incremental_plugin")];
+    let fresh = do_taint_analysis_on_function(&both, &config, "host", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("host", &Name::from("r")), fresh.get_var_type("host", &Name::from("r")));
+}
+
+#[test]
+fn analyze_new_functions() {
+    init_logging();
+    let host = [get_module("incremental_host")];
+    let plugin = [get_module("incremental_plugin")];
+    let config = get_config();
+
+    let mut ts = start(&host, &config);
+    ts.add_modules(&plugin, true);
+    assert!(ts.run_until(usize::MAX));
+    assert_eq!(ts.get_function_taint_map("plugin_other").unwrap()[&Name::from("r")], TaintedType::TaintedValue);
+}
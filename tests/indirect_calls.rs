@@ -0,0 +1,58 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/indirect_calls.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn taint_reaches_target() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // `add_one` is the only function of the pointer's type, so it receives
+    // the tainted argument
+    let funcname = "call_add_one";
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedFnPtr(FnTargets::new()), TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let summary = taint_result.get_function_summary("add_one").unwrap();
+    assert_eq!(summary.get_params().collect::<Vec<_>>(), vec![&TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn result_comes_from_target() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // `constant` is the only function of the pointer's type, and its result
+    // is untainted, so the result of the call is too
+    let funcname = "call_constant";
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedFnPtr(FnTargets::new()), TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let summary = taint_result.get_function_summary("constant").unwrap();
+    assert_eq!(summary.get_params().collect::<Vec<_>>(), vec![&TaintedType::TaintedValue]);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::UntaintedValue);
+}
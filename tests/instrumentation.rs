@@ -0,0 +1,37 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/instrumentation.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn instrumentation_guidance() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.model_allocators = true;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "entry", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    let guidance = taint_result.instrumentation_guidance();
+
+    // `untouched` is reached, but only with untainted data, and
+    // `never_called` isn't reached at all
+    assert_eq!(guidance.functions, vec!["entry", "helper"]);
+    assert_eq!(guidance.uninstrumented, vec!["never_called", "untouched"]);
+    assert_eq!(guidance.allocations, vec![("entry", &Name::from("buf")), ("entry", &Name::from("mem"))]);
+    assert_eq!(guidance.globals, vec![&Name::from("secret_copy")]);
+
+    let mut abilist = Vec::new();
+    guidance.write_dfsan_abilist(&mut abilist).unwrap();
+    let abilist = String::from_utf8(abilist).unwrap();
+    assert!(abilist.contains("fun:untouched=uninstrumented\nfun:untouched=discard\n"));
+    assert!(!abilist.contains("fun:helper="));
+}
@@ -0,0 +1,515 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/intrinsics.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_varargs_module() -> Module {
+    let modname = "tests/additional_bcfiles/varargs.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_musttail_module() -> Module {
+    let modname = "tests/additional_bcfiles/musttail.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_coroutines_module() -> Module {
+    let modname = "tests/additional_bcfiles/coroutines.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_gc_statepoint_module() -> Module {
+    let modname = "tests/additional_bcfiles/gc_statepoint.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_target_types_module() -> Module {
+    let modname = "tests/additional_bcfiles/target_types.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+/// Analyze the given function with a tainted argument, and return whether its
+/// return value is tainted
+fn returns_tainted(modules: &[Module], funcname: &str) -> bool {
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(
+        modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let taintmap = taint_result.get_function_taint_map(funcname);
+    taint_result.is_type_tainted(&taintmap[&Name::from(4)])
+}
+
+#[test]
+fn memcpy_variants() {
+    init_logging();
+    let modules = [get_module()];
+
+    // copying a tainted value with any variant of memcpy or memmove should
+    // taint the destination
+    assert!(returns_tainted(&modules, "copy_inline"));
+    assert!(returns_tainted(&modules, "copy_atomic"));
+    assert!(returns_tainted(&modules, "move"));
+
+    // but copying an untainted value shouldn't
+    assert!(!returns_tainted(&modules, "copy_untainted"));
+}
+
+#[test]
+fn memset_variants() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // setting memory to a tainted value with any variant of memset should
+    // taint the memory
+    for funcname in &["set_inline", "set_atomic"] {
+        let taint_result = do_taint_analysis_on_function(
+            &modules,
+            &config,
+            funcname,
+            Some(vec![TaintedType::TaintedValue]),
+            HashMap::new(),
+            HashMap::new(),
+        );
+        assert_eq!(
+            taint_result.get_function_taint_map(funcname).get(&Name::from(3)),
+            Some(&TaintedType::TaintedValue),
+        );
+    }
+}
+
+#[test]
+fn varargs() {
+    init_logging();
+    let modules = [get_varargs_module()];
+    let config = Config::default();
+
+    // if no extra argument is tainted, the va_list and everything read from
+    // it should be untainted
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "call_untainted",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(!taint_result.get_function_summary("first_vararg").unwrap().are_varargs_tainted());
+    assert!(!taint_result.does_function_return_tainted("first_vararg"));
+
+    // a tainted extra argument, at a call site with a different number of
+    // extra arguments than another call site, should taint the va_list, its
+    // copy made by va_copy, and the value read from the copy by va_arg
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "call_both",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let summary = taint_result.get_function_summary("first_vararg").unwrap();
+    assert!(summary.are_varargs_tainted());
+    assert_eq!(summary.get_params().count(), 1);
+    let callee_taintmap = taint_result.get_function_taint_map("first_vararg");
+    assert_eq!(
+        callee_taintmap.get(&Name::from(3)),
+        Some(&TaintedType::untainted_ptr_to(TaintedType::tainted_ptr_to(TaintedType::UntaintedValue))),
+    );
+    assert_eq!(callee_taintmap.get(&Name::from(9)), Some(&TaintedType::TaintedValue));
+    assert!(taint_result.does_function_return_tainted("first_vararg"));
+    assert_eq!(taint_result.get_var_type("call_both", &Name::from(5)), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn musttail_forwards_varargs() {
+    init_logging();
+    let modules = [get_musttail_module()];
+    let config = Config::default();
+
+    // `forward` passes its extra arguments on to `first_vararg` with a
+    // `musttail` call, so the tainted argument reaches `va_arg` there, and
+    // comes back through both summaries
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "caller",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.get_function_summary("forward").unwrap().are_varargs_tainted());
+    assert!(taint_result.get_function_summary("first_vararg").unwrap().are_varargs_tainted());
+    assert_eq!(taint_result.get_var_type("first_vararg", &Name::from("x")), &TaintedType::TaintedValue);
+    assert!(taint_result.does_function_return_tainted("forward"));
+    assert_eq!(taint_result.get_var_type("caller", &Name::from("r")), &TaintedType::TaintedValue);
+
+    // with an untainted argument, nothing is tainted
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "caller",
+        Some(vec![TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(!taint_result.get_function_summary("first_vararg").unwrap().are_varargs_tainted());
+    assert_eq!(taint_result.get_var_type("caller", &Name::from("r")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn noop_intrinsics() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "noop_intrinsics";
+
+    // by default, llvm.x86.sse2.pause isn't a no-op intrinsic, so it gets
+    // external function handling (and a summary), while all the others are
+    // ignored
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.get_function_summary("llvm.x86.sse2.pause").is_some());
+    assert!(taint_result.get_function_summary("llvm.stacksave").is_none());
+    assert!(taint_result.get_function_summary("llvm.prefetch.p0i8").is_none());
+    assert!(taint_result.get_function_summary("llvm.assume").is_none());
+
+    // after adding it to the no-op intrinsics, it's ignored too, so we don't
+    // need any external function handling at all. The result of llvm.stacksave
+    // should be untainted
+    let mut config = Config::default();
+    config.noop_intrinsics.insert("llvm.x86.sse2.pause".into());
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.get_function_summary("llvm.x86.sse2.pause").is_none());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from(2)),
+        &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    );
+}
+
+#[test]
+fn register_intrinsics() {
+    init_logging();
+    let modules = [get_module()];
+
+    // by default, values read from registers are untainted, and writes to
+    // registers are ignored
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "read_sp", None, HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("read_sp", &Name::from(1)), &TaintedType::UntaintedValue);
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "write_sp",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.get_tainted_sinks("write_sp").is_empty());
+
+    // both can be changed: then writing a tainted value to a register is a
+    // tainted sink
+    let mut config = Config::default();
+    config.register_reads_tainted = true;
+    config.register_writes_are_sinks = true;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "read_sp", None, HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("read_sp", &Name::from(1)), &TaintedType::TaintedValue);
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "write_sp",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_tainted_sinks("write_sp"), vec![(&Name::from(1), 1)]);
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "write_sp",
+        Some(vec![TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.get_tainted_sinks("write_sp").is_empty());
+}
+
+#[test]
+fn bit_manipulation() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "bit_manipulation";
+
+    // each result should have the same taint as the intrinsic's operand
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let taintmap = taint_result.get_function_taint_map(funcname);
+    for tainted in &[3, 5, 7] {
+        assert_eq!(taintmap.get(&Name::from(*tainted)), Some(&TaintedType::TaintedValue));
+    }
+    for untainted in &[4, 6, 8, 10] {
+        assert_eq!(taintmap.get(&Name::from(*untainted)), Some(&TaintedType::UntaintedValue));
+    }
+
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let taintmap = taint_result.get_function_taint_map(funcname);
+    for tainted in &[4, 6, 8, 10] {
+        assert_eq!(taintmap.get(&Name::from(*tainted)), Some(&TaintedType::TaintedValue));
+    }
+    for untainted in &[3, 5, 7] {
+        assert_eq!(taintmap.get(&Name::from(*untainted)), Some(&TaintedType::UntaintedValue));
+    }
+}
+
+#[test]
+fn funnel_shift() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "funnel_shift";
+
+    // a tainted shift amount taints the result, not just tainted data
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(4)), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(5)), &TaintedType::UntaintedValue);
+    assert!(!taint_result.is_type_tainted(taint_result.get_var_type(funcname, &Name::from(6))));
+
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue, TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(4)), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(5)), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn coroutines() {
+    init_logging();
+    let modules = [get_coroutines_module()];
+    let config = Config::default();
+    let funcname = "get_yielded";
+
+    // an untainted value yielded by the coroutine is untainted in the caller
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("val")), &TaintedType::UntaintedValue);
+
+    // a tainted value stored into the coroutine's promise should be tainted
+    // when the caller reads it through the handle, even though the caller is
+    // processed before the coroutine
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(
+        taint_result.get_var_type("generator", &Name::from("promise")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("hdl")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("done")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("val")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn gc_statepoint() {
+    init_logging();
+    let modules = [get_gc_statepoint_module()];
+    let config = Config::default();
+    let funcname = "call_add_one";
+
+    // the wrapped call is processed like a direct call of `add_one`, and
+    // `gc.result` gets its result
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type("add_one", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn gc_relocate() {
+    init_logging();
+    let modules = [get_gc_statepoint_module()];
+    let config = Config::default();
+    let funcname = "load_relocated";
+
+    // the relocated pointer points to the same tainted data
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("p.rel")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::TaintedValue);
+    assert!(taint_result.get_function_names().any(|name| *name == "safepoint"));
+
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn tokens() {
+    init_logging();
+    let config = Config::default();
+    assert_eq!(TaintedType::from_llvm_type(&llvm_ir::Type::TokenType), TaintedType::Token);
+
+    // the coroutine's token stays a token, even with tainted data in its
+    // promise
+    let modules = [get_coroutines_module()];
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "get_yielded",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type("generator", &Name::from("id")), &TaintedType::Token);
+    assert!(!taint_result.is_type_tainted(&TaintedType::Token));
+
+    // as does the token of a statepoint wrapping a `void` call
+    let modules = [get_gc_statepoint_module()];
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        "load_relocated",
+        Some(vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type("load_relocated", &Name::from("tok")), &TaintedType::Token);
+}
+
+#[test]
+fn target_specific_types() {
+    init_logging();
+    let modules = [get_target_types_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("llvm.x86.cast.vector.to.tile.v256i32".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    config.ext_functions.insert("llvm.x86.cast.tile.to.vector.v256i32".into(), config::ExternalFunctionHandling::PropagateTaintShallow);
+    let funcname = "through_tile";
+
+    // the `x86_amx` tile is an opaque scalar, which carries the taint of the
+    // vector through to the result
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::array_or_vec_of(TaintedType::TaintedValue)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("t")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("e")), &TaintedType::TaintedValue);
+
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::array_or_vec_of(TaintedType::UntaintedValue)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("t")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("e")), &TaintedType::UntaintedValue);
+}
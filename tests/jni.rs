@@ -0,0 +1,41 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/jni.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_module(modules, config, HashMap::new(), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn jni_functions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.model_jni = true;
+    let taint_result = analyze(&modules, &config);
+
+    // the pointer returned by `GetStringUTFChars` isn't tainted, but the
+    // characters are
+    assert_eq!(
+        taint_result.get_var_type("first_char", &Name::from("chars")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type("first_char", &Name::from("c")), &TaintedType::TaintedValue);
+
+    // values returned from the JVM are tainted
+    assert_eq!(taint_result.get_var_type("array_length", &Name::from("len")), &TaintedType::TaintedValue);
+
+    // and so is the data copied out of an array
+    assert_eq!(taint_result.get_var_type("first_byte", &Name::from("b")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,57 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/kernel.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.model_kernel_user_copies = true;
+    config
+}
+
+#[test]
+fn copy_from_user() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("handle_ioctl", &Name::from("c")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("handle_ioctl", &Name::from("ret")), &TaintedType::TaintedValue);
+    // the number of bytes left uncopied isn't tainted
+    assert_eq!(taint_result.get_var_type("handle_ioctl", &Name::from("left")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("handle_ioctl", &Name::from("failed")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn memdup_user() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type("dup_ioctl", &Name::from("p")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+    assert_eq!(taint_result.get_var_type("dup_ioctl", &Name::from("v")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn copy_to_user_sink() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    // only the copy of the user's data back out is reported, not the copy of
+    // the constant version
+    assert_eq!(taint_result.get_tainted_sinks("echo_ioctl"), vec![(&Name::from("send_cmd"), 0)]);
+}
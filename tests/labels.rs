@@ -0,0 +1,208 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/intrinsics.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+/// The diamond lattice `Public < {Secret, Untrusted} < SecretAndUntrusted`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Label {
+    Public,
+    Secret,
+    Untrusted,
+    SecretAndUntrusted,
+}
+
+impl TaintLabel for Label {
+    fn bottom() -> Self {
+        Label::Public
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a.clone(),
+            (Label::Public, other) | (other, Label::Public) => other.clone(),
+            _ => Label::SecretAndUntrusted,
+        }
+    }
+
+    fn ext_source() -> Self {
+        Label::Untrusted
+    }
+}
+
+#[test]
+fn diamond_lattice() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    assert!(Label::Secret.leq(&Label::SecretAndUntrusted));
+    assert!(!Label::Secret.leq(&Label::Untrusted));
+
+    // `bit_manipulation` computes %3, %5, and %7 from its first argument, and
+    // %4, %6, %8, and the return value %10 from its second
+    let funcname = "bit_manipulation";
+    let result = do_labeled_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![(TaintedType::TaintedValue, Label::Secret), (TaintedType::TaintedValue, Label::Untrusted)]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let labels = result.get_function_label_map(funcname);
+    assert_eq!(labels[&Name::from(3)], Label::Secret);
+    assert_eq!(labels[&Name::from(4)], Label::Untrusted);
+    assert_eq!(labels[&Name::from(10)], Label::Untrusted);
+    assert!(result.get_taint_result_for_label(&Label::SecretAndUntrusted).is_none());
+
+    // `funnel_shift` computes %4 from its first and third arguments, and %5
+    // from its second
+    let funcname = "funnel_shift";
+    let result = do_labeled_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![
+            (TaintedType::TaintedValue, Label::Secret),
+            (TaintedType::UntaintedValue, Label::Public),
+            (TaintedType::TaintedValue, Label::Untrusted),
+        ]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(result.get_var_label(funcname, &Name::from(4)), Label::SecretAndUntrusted);
+    assert_eq!(result.get_var_label(funcname, &Name::from(5)), Label::Public);
+
+    // a nonargument with a label
+    let result = do_labeled_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        None,
+        std::iter::once((Name::from(1), (TaintedType::TaintedValue, Label::Secret))).collect(),
+        HashMap::new(),
+    );
+    assert_eq!(result.get_var_label(funcname, &Name::from(4)), Label::Public);
+    assert_eq!(result.get_var_label(funcname, &Name::from(5)), Label::Secret);
+}
+
+#[test]
+fn ext_sources() {
+    init_logging();
+    let modname = "tests/additional_bcfiles/dead_sources.bc";
+    let modules = [Module::from_bc_path(modname).unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))];
+    let mut config = Config::default();
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.ext_functions.insert("read_unused".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config.ext_functions.insert("fill_buffer".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+
+    // `process` returns %r, computed from the result of `read_input`, which
+    // gets the label of the external sources rather than that of %unused
+    let funcname = "process";
+    let result = do_labeled_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        None,
+        std::iter::once((Name::from("unused"), (TaintedType::TaintedValue, Label::Secret))).collect(),
+        HashMap::new(),
+    );
+    assert_eq!(result.get_var_label(funcname, &Name::from("unused")), Label::Secret);
+    assert_eq!(result.get_var_label(funcname, &Name::from("r")), Label::Untrusted);
+    assert_eq!(result.get_return_label(funcname), Label::Untrusted);
+
+    let result = do_taint_degree_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        None,
+        std::iter::once((Name::from("unused"), TaintedType::TaintedValue)).collect(),
+        HashMap::new(),
+    );
+    assert_eq!(result.get_var_sources(funcname, &Name::from("r")), vec![TaintSource::ExtFunctions]);
+}
+
+#[test]
+fn taint_degree() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // in `funnel_shift`, %4 depends on the first and third arguments, and %5 on
+    // the second
+    let funcname = "funnel_shift";
+    let result = do_taint_degree_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::TaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(result.get_var_degree(funcname, &Name::from(4)), 2);
+    assert_eq!(
+        result.get_var_sources(funcname, &Name::from(4)),
+        vec![
+            TaintSource::Arg { function: funcname.into(), index: 0 },
+            TaintSource::Arg { function: funcname.into(), index: 2 },
+        ],
+    );
+    assert_eq!(result.get_var_degree(funcname, &Name::from(5)), 1);
+    assert_eq!(result.get_var_degree(funcname, &Name::from(6)), 0);
+
+    // untainted arguments aren't sources, but nonarguments are
+    let result = do_taint_degree_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::UntaintedValue, TaintedType::UntaintedValue, TaintedType::TaintedValue]),
+        std::iter::once((Name::from(5), TaintedType::TaintedValue)).collect(),
+        HashMap::new(),
+    );
+    let degrees = result.get_function_degree_map(funcname);
+    assert_eq!(degrees[&Name::from(4)], 1);
+    assert_eq!(degrees[&Name::from(5)], 1);
+    assert_eq!(degrees[&Name::from(6)], 0);
+    assert_eq!(
+        result.get_var_sources(funcname, &Name::from(5)),
+        vec![TaintSource::NonArg { function: funcname.into(), name: Name::from(5) }],
+    );
+}
+
+#[test]
+fn minimal_sources() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // as in `taint_degree`: in `funnel_shift`, %4 depends on the first and
+    // third arguments, and %5 on the second
+    let funcname = "funnel_shift";
+    let result = do_taint_degree_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::TaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let arg = |index| TaintSource::Arg { function: funcname.into(), index };
+    let (four, five, six) = (Name::from(4), Name::from(5), Name::from(6));
+    assert_eq!(result.get_minimal_sources(&[(funcname, &four)]), Some(vec![arg(0)]));
+    assert_eq!(result.get_minimal_sources(&[(funcname, &five)]), Some(vec![arg(1)]));
+    assert_eq!(result.get_minimal_sources(&[(funcname, &four), (funcname, &five)]), Some(vec![arg(0), arg(1)]));
+    assert_eq!(result.get_minimal_sources(&[]), Some(vec![]));
+    // %6 is untainted
+    assert_eq!(result.get_minimal_sources(&[(funcname, &four), (funcname, &six)]), None);
+}
@@ -0,0 +1,53 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn bc_path(name: &str) -> String {
+    format!("tests/additional_bcfiles/{}.bc", name)
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config
+}
+
+#[test]
+fn load_on_demand() {
+    init_logging();
+    let modules = LazyModules::new(["incremental_plugin", "prune", "incremental_host"].iter().map(|name| bc_path(name)))
+        .unwrap();
+    assert_eq!(modules.loaded_modules().count(), 0);
+    let config = get_config();
+    let taint_result = do_taint_analysis_on_function_lazily(&modules, &config, "host", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new())
+        .unwrap();
+
+    // `prune.bc` defines nothing `host` needs
+    let mut loaded: Vec<&str> = modules.loaded_modules().map(|module| module.name.as_str()).collect();
+    loaded.sort_unstable();
+    assert_eq!(loaded, vec![bc_path("incremental_host"), bc_path("incremental_plugin")]);
+
+    // `plugin_id` was never treated as external (which would panic, with the
+    // default `Config::ext_functions_default`)
+    assert_eq!(taint_result.get_var_type("plugin_id", &Name::from("x")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("host", &Name::from("r")), &TaintedType::TaintedValue);
+
+    // same as with all the modules up front
+    let all: Vec<Module> = modules.get_paths().iter().map(|path| Module::from_bc_path(path).unwrap()).collect();
+    let eager = do_taint_analysis_on_function(&all, &config, "host", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("host", &Name::from("r")), eager.get_var_type("host", &Name::from("r")));
+}
+
+#[test]
+fn missing_start_function() {
+    init_logging();
+    let modules = LazyModules::new(std::iter::once(bc_path("incremental_plugin"))).unwrap();
+    let config = get_config();
+    assert!(do_taint_analysis_on_function_lazily(&modules, &config, "host", None, HashMap::new(), HashMap::new()).is_err());
+    assert!(LazyModules::new(std::iter::once(bc_path("nonexistent"))).is_err());
+}
@@ -0,0 +1,53 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/lenient_joins.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+/// The struct `%q` is given as pointing to, which doesn't match what `%p`
+/// and `null` point to
+fn view() -> TaintedType {
+    TaintedType::struct_of(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue])
+}
+
+fn args() -> Vec<TaintedType> {
+    vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue), TaintedType::UntaintedValue]
+}
+
+fn nonargs() -> HashMap<Name, TaintedType> {
+    let mut nonargs = HashMap::new();
+    nonargs.insert(Name::from("q"), TaintedType::untainted_ptr_to(view()));
+    nonargs
+}
+
+#[test]
+#[should_panic(expected = "type mismatch")]
+fn mismatch_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    do_taint_analysis_on_function(&modules, &config, "first", Some(args()), nonargs(), HashMap::new());
+}
+
+#[test]
+fn mismatched_pointees() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.lenient_joins = true;
+
+    // `%q` keeps the shape it was given, and so does the phi of it and `null`
+    let funcname = "first";
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), nonargs(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("q")), &TaintedType::untainted_ptr_to(view()));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::untainted_ptr_to(view()));
+}
@@ -0,0 +1,69 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::config::{InstructionClass, PropagationPolicy};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/masks.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "flags", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new())
+}
+
+fn is_tainted(taint_result: &TaintResult, var: &str) -> bool {
+    taint_result.get_var_type("flags", &Name::from(var)) == &TaintedType::TaintedValue
+}
+
+#[test]
+fn mask_sanitization() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, masking doesn't sanitize
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "flag"));
+    assert!(is_tainted(&taint_result, "nibble"));
+
+    // masks up to the configured maximum do, with the constant on either side
+    config.sanitizing_mask_max = Some(15);
+    let taint_result = analyze(&modules, &config);
+    assert!(!is_tainted(&taint_result, "flag"));
+    assert!(!is_tainted(&taint_result, "nibble"));
+    assert!(is_tainted(&taint_result, "byte"));
+    assert!(is_tainted(&taint_result, "r"));
+
+    config.sanitizing_mask_max = Some(1);
+    let taint_result = analyze(&modules, &config);
+    assert!(!is_tainted(&taint_result, "flag"));
+    assert!(is_tainted(&taint_result, "nibble"));
+}
+
+#[test]
+fn mask_sanitization_with_other_options() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.sanitizing_mask_max = Some(15);
+
+    // with bit-level taint, the masked bits are cleared
+    config.bit_level_taint = true;
+    let taint_result = analyze(&modules, &config);
+    assert!(!is_tainted(&taint_result, "nibble"));
+    assert_eq!(taint_result.get_tainted_bits("flags", &Name::from("nibble")), Some(0));
+    assert_eq!(taint_result.get_tainted_bits("flags", &Name::from("byte")), Some(0xff));
+
+    // a conservative policy overrides it
+    config.bit_level_taint = false;
+    config.propagation_policies.insert(InstructionClass::Arithmetic, PropagationPolicy::TaintConservatively);
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "nibble"));
+}
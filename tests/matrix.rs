@@ -0,0 +1,53 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/matrix.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn matrix_intrinsics() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "mul";
+    let args = vec![
+        TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+        TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+        TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    ];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    let tainted_matrix = TaintedType::array_or_vec_of(TaintedType::TaintedValue);
+    let untainted_matrix = TaintedType::array_or_vec_of(TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("ma")), &tainted_matrix);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("mb")), &untainted_matrix);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("prod")), &tainted_matrix);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("t")), &tainted_matrix);
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("out")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+}
+
+#[test]
+fn untainted_matrices() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "mul";
+    let args = (0 .. 3).map(|_| TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)).collect();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("prod")), &TaintedType::array_or_vec_of(TaintedType::UntaintedValue));
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("out")),
+        &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    );
+}
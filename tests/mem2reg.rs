@@ -0,0 +1,65 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/mem2reg.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn ptr(pointee: TaintedType) -> TaintedType {
+    TaintedType::untainted_ptr_to(pointee)
+}
+
+#[test]
+fn pointer_slot() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "pick";
+    let args = || Some(vec![ptr(TaintedType::TaintedValue), ptr(TaintedType::UntaintedValue)]);
+
+    // by default, the pointer loaded from the slot points to the join of
+    // both pointers' data
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::TaintedValue);
+
+    // promoted, it's the pointer last stored
+    let mut config = Config::default();
+    config.promote_allocas = true;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("q")), &ptr(TaintedType::UntaintedValue));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn scalar_slot() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "reassign";
+    let mut config = Config::default();
+    config.promote_allocas = true;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("v")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn promoted_pointer_aliases() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "fill";
+    let mut config = Config::default();
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.promote_allocas = true;
+
+    // the pointer loaded from the slot still points to the caller's buffer
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(vec![ptr(TaintedType::UntaintedValue)]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("ptr")), &ptr(TaintedType::TaintedValue));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("buf")), &ptr(TaintedType::TaintedValue));
+}
@@ -0,0 +1,47 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/mmio.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_var_types(config: &Config) -> Vec<TaintedType> {
+    let modules = [get_module()];
+    let taint_result = do_taint_analysis_on_module(&modules, config, HashMap::new(), HashMap::new(), HashMap::new());
+    ["read_uart", "read_ram", "read_reg", "read_status"]
+        .iter()
+        .map(|funcname| taint_result.get_var_type(funcname, &Name::from("v")).clone())
+        .collect()
+}
+
+#[test]
+fn device_memory() {
+    init_logging();
+    let mut config = Config::default();
+
+    // by default, device memory is untainted
+    assert_eq!(get_var_types(&config), vec![TaintedType::UntaintedValue; 4]);
+
+    // loads from the peripheral range are tainted, whether the address is a
+    // constant expression or an instruction, but not from RAM
+    config.tainted_address_ranges.push(0x4000_0000 .. 0x5000_0000);
+    assert_eq!(
+        get_var_types(&config),
+        vec![TaintedType::TaintedValue, TaintedType::UntaintedValue, TaintedType::TaintedValue, TaintedType::UntaintedValue],
+    );
+
+    // and globals in the peripheral section are tainted too
+    config.tainted_sections.insert(".periph".into());
+    assert_eq!(
+        get_var_types(&config),
+        vec![TaintedType::TaintedValue, TaintedType::UntaintedValue, TaintedType::TaintedValue, TaintedType::TaintedValue],
+    );
+}
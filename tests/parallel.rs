@@ -0,0 +1,55 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/parallel.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, tainted_arg: usize) -> TaintResult<'m> {
+    let args = (0 .. 3)
+        .map(|index| if index == tainted_arg { TaintedType::TaintedValue } else { TaintedType::UntaintedValue })
+        .chain(std::iter::once(TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)))
+        .collect();
+    do_taint_analysis_on_function(modules, config, "dispatch", Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn parallel_blocks() {
+    init_logging();
+    let modules = [get_module()];
+    let sequential_config = Config::default();
+    let mut parallel_config = Config::default();
+    parallel_config.parallel_blocks_threshold = Some(1);
+
+    for tainted_arg in 0 .. 3 {
+        let sequential = analyze(&modules, &sequential_config, tainted_arg);
+        let parallel = analyze(&modules, &parallel_config, tainted_arg);
+        assert_eq!(
+            sequential.get_function_taint_map("dispatch"),
+            parallel.get_function_taint_map("dispatch"),
+            "with arg {} tainted",
+            tainted_arg,
+        );
+    }
+
+    // with `x` tainted, only the arms using it, and what they flow to
+    let taint_result = analyze(&modules, &parallel_config, 1);
+    for var in &["r0", "c2", "r2", "r", "acc", "accnext"] {
+        assert_eq!(taint_result.get_var_type("dispatch", &Name::from(*var)), &TaintedType::TaintedValue, "{}", var);
+    }
+    for var in &["r1", "r3", "i", "cond", "ret"] {
+        assert_eq!(taint_result.get_var_type("dispatch", &Name::from(*var)), &TaintedType::UntaintedValue, "{}", var);
+    }
+    assert_eq!(
+        taint_result.get_var_type("dispatch", &Name::from("out")),
+        &TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+    );
+}
@@ -0,0 +1,62 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/paths.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config(source: config::ExternalFunctionHandling) -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("read_input".into(), source);
+    config.ext_functions.insert("write_output".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+fn step<'m>(caller: &'m str, block: &'m Name, index: usize, callee: &'m str, flow: TaintFlow) -> TaintPathStep<'m> {
+    TaintPathStep { caller, block, index, callee, flow }
+}
+
+#[test]
+fn taint_paths() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config(config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "run", None, HashMap::new(), HashMap::new());
+
+    let entry = Name::from("entry");
+    let up = [
+        step("get", &entry, 0, "read_input", TaintFlow::Return),
+        step("wrap", &entry, 0, "get", TaintFlow::Return),
+        step("run", &entry, 0, "wrap", TaintFlow::Return),
+    ];
+    let direct: Vec<TaintPathStep> = up.iter().cloned()
+        .chain(std::iter::once(step("run", &entry, 3, "write_output", TaintFlow::Argument)))
+        .collect();
+    let via_emit: Vec<TaintPathStep> = up.iter().cloned()
+        .chain(vec![
+            step("run", &entry, 1, "emit", TaintFlow::Argument),
+            step("emit", &entry, 0, "write_output", TaintFlow::Argument),
+        ])
+        .collect();
+    // `quiet` is passed tainted data, but doesn't pass it on to the sink
+    assert_eq!(taint_result.find_taint_paths("read_input", "write_output", 10), vec![direct.clone(), via_emit]);
+    assert_eq!(taint_result.find_taint_paths("read_input", "write_output", 1), vec![direct]);
+    assert!(taint_result.find_taint_paths("write_output", "read_input", 10).is_empty());
+}
+
+#[test]
+fn no_taint_paths_without_taint() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config(config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "run", None, HashMap::new(), HashMap::new());
+    assert!(taint_result.find_taint_paths("read_input", "write_output", 10).is_empty());
+}
@@ -0,0 +1,101 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::config::{InstructionClass, PropagationPolicy};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/policies.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn dropped_classes() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "arith";
+    let args = || Some(vec![TaintedType::TaintedValue, TaintedType::UntaintedValue]);
+
+    // by default, everything computed from `x` is tainted
+    let mut config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    for var in &["sum", "quot", "sh", "cmp", "ext", "ext.trunc", "r"] {
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from(*var)), &TaintedType::TaintedValue, "{}", var);
+    }
+
+    // dropping comparisons, casts, and divisions
+    config.propagation_policies.insert(InstructionClass::Comparison, PropagationPolicy::Drop);
+    config.propagation_policies.insert(InstructionClass::Cast, PropagationPolicy::Drop);
+    config.propagation_policies.insert(InstructionClass::Division, PropagationPolicy::Drop);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    for var in &["quot", "cmp", "cmp.ext", "ext", "ext.trunc"] {
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from(*var)), &TaintedType::UntaintedValue, "{}", var);
+    }
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("sum")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("sh")), &TaintedType::TaintedValue);
+
+    // and shifts
+    config.propagation_policies.insert(InstructionClass::Shift, PropagationPolicy::Drop);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("sh")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn conservative_comparison() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "same";
+    let args = || Some(vec![
+        TaintedType::untainted_ptr_to(TaintedType::TaintedValue),
+        TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    ]);
+
+    // comparing the pointers doesn't look at the data they point to
+    let mut config = Config::default();
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("cmp")), &TaintedType::UntaintedValue);
+
+    // unless comparisons are tainted conservatively
+    config.propagation_policies.insert(InstructionClass::Comparison, PropagationPolicy::TaintConservatively);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("cmp")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn gep_index_policies() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "index";
+    let args = || Some(vec![TaintedType::untainted_ptr_to(TaintedType::UntaintedValue), TaintedType::TaintedValue]);
+    let p = Name::from("p");
+
+    // by default, follows `taint_pointers_with_tainted_indices`. (Loads
+    // through `p` leave what it points to alone, so that propagating and
+    // tainting conservatively can be told apart.)
+    let mut config = Config::default();
+    config.dereferencing_tainted_ptr_gives_tainted = false;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &p), &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue));
+
+    config.propagation_policies.insert(InstructionClass::GepIndex, PropagationPolicy::Propagate);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &p), &TaintedType::tainted_ptr_to(TaintedType::UntaintedValue));
+
+    // conservatively, the data pointed to is tainted too
+    config.propagation_policies.insert(InstructionClass::GepIndex, PropagationPolicy::TaintConservatively);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &p), &TaintedType::tainted_ptr_to(TaintedType::TaintedValue));
+
+    // an entry overrides `taint_pointers_with_tainted_indices`
+    config.taint_pointers_with_tainted_indices = true;
+    config.propagation_policies.insert(InstructionClass::GepIndex, PropagationPolicy::Drop);
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, args(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &p), &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue));
+}
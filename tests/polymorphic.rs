@@ -0,0 +1,65 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/polymorphic.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn config(polymorphic_summaries: bool) -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.polymorphic_summaries = polymorphic_summaries;
+    config
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, start_fn: &str) -> TaintResult<'m> {
+    do_taint_analysis_on_function(
+        modules,
+        config,
+        start_fn,
+        Some(vec![]),
+        HashMap::new(),
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn polymorphic_summaries() {
+    init_logging();
+    let modules = [get_module()];
+
+    // with a single summary for `id`, the secret passed by the first call
+    // pollutes the result of the second
+    let merged_config = config(false);
+    let taint_result = analyze(&modules, &merged_config, "top");
+    assert_eq!(taint_result.get_var_type("top", &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("top", &Name::from("b")), &TaintedType::TaintedValue);
+
+    // with polymorphic summaries it doesn't
+    let polymorphic_config = config(true);
+    let taint_result = analyze(&modules, &polymorphic_config, "top");
+    assert_eq!(taint_result.get_var_type("top", &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("top", &Name::from("b")), &TaintedType::UntaintedValue);
+    // but the results for `id` itself still cover both calls
+    assert_eq!(taint_result.get_var_type("id", &Name::from("x")), &TaintedType::TaintedValue);
+    assert!(taint_result.does_function_return_tainted("id"));
+}
+
+#[test]
+fn nested_polymorphic_summaries() {
+    init_logging();
+    let modules = [get_module()];
+    let config = config(true);
+    let taint_result = analyze(&modules, &config, "nested");
+    assert_eq!(taint_result.get_var_type("nested", &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("nested", &Name::from("b")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("wrap", &Name::from("r")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,68 @@
+use llvm_ir::{FPPredicate, IntPredicate, Module, Name};
+use llvm_ir_taint::config::{ComparisonPredicate, InstructionClass, PropagationPolicy};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/predicates.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = vec![TaintedType::TaintedValue, TaintedType::TaintedValue];
+    do_taint_analysis_on_function(modules, config, "check", Some(args), HashMap::new(), HashMap::new())
+}
+
+fn is_tainted(taint_result: &TaintResult, var: &str) -> bool {
+    taint_result.get_var_type("check", &Name::from(var)) == &TaintedType::TaintedValue
+}
+
+#[test]
+fn all_predicates() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, every comparison is tainted, and so is the value chosen by
+    // the tainted branch
+    let taint_result = analyze(&modules, &config);
+    for var in &["eq", "same", "lt", "sign"] {
+        assert!(is_tainted(&taint_result, var), "{}", var);
+    }
+
+    // comparisons can be treated as sanitized
+    config.propagation_policies.insert(InstructionClass::Comparison, PropagationPolicy::Drop);
+    let taint_result = analyze(&modules, &config);
+    for var in &["eq", "same", "lt", "sign"] {
+        assert!(!is_tainted(&taint_result, var), "{}", var);
+    }
+}
+
+#[test]
+fn specific_predicates() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.tainting_predicates = Some(vec![
+        ComparisonPredicate::Int(IntPredicate::EQ),
+        ComparisonPredicate::Int(IntPredicate::NE),
+    ]);
+
+    // only the equality test is tainted
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "eq"));
+    assert!(!is_tainted(&taint_result, "same"));
+    assert!(!is_tainted(&taint_result, "lt"));
+    assert!(!is_tainted(&taint_result, "sign"));
+
+    config.tainting_predicates.as_mut().unwrap().push(ComparisonPredicate::FP(FPPredicate::OEQ));
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "same"));
+    assert!(!is_tainted(&taint_result, "lt"));
+}
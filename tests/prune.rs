@@ -0,0 +1,40 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/prune.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = std::iter::once(("entry", vec![TaintedType::UntaintedValue])).collect();
+    do_taint_analysis_on_module(modules, config, args, HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn prune_unreachable_functions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    // by default, every function is analyzed, including `dead`
+    let taint_result = analyze(&modules, &config);
+    assert!(taint_result.get_function_names().any(|name| *name == "dead"));
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("r")), &TaintedType::TaintedValue);
+
+    // with pruning, only `entry` and what it calls
+    config.prune_unreachable_functions = true;
+    let taint_result = analyze(&modules, &config);
+    let mut fn_names: Vec<&str> = taint_result.get_function_names().copied().collect();
+    fn_names.sort_unstable();
+    assert_eq!(fn_names, vec!["entry", "helper"]);
+    assert_eq!(taint_result.get_var_type("entry", &Name::from("r")), &TaintedType::UntaintedValue);
+}
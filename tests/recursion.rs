@@ -0,0 +1,58 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/recursion.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(
+        modules,
+        config,
+        "top",
+        Some(vec![]),
+        HashMap::new(),
+        HashMap::new(),
+    )
+}
+
+fn config_with_depth(depth: usize) -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.recursion_call_string_depth = depth;
+    config
+}
+
+#[test]
+fn merged_summary() {
+    init_logging();
+    let modules = [get_module()];
+
+    // with one summary for `rec`, the secrets passed by the recursive calls
+    // make every call to `rec` return tainted
+    let config = config_with_depth(0);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("top", &Name::from("r")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn call_string_clones() {
+    init_logging();
+    let modules = [get_module()];
+
+    // with clones for recursive calls, `top`'s call to `rec` is analyzed
+    // separately from the recursive calls, and returns untainted
+    for depth in 1 ..= 3 {
+        let config = config_with_depth(depth);
+        let taint_result = analyze(&modules, &config);
+        assert_eq!(taint_result.get_var_type("top", &Name::from("r")), &TaintedType::UntaintedValue, "depth {}", depth);
+    }
+}
@@ -0,0 +1,61 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module(name: &str) -> Module {
+    let modname = format!("tests/additional_bcfiles/{}.bc", name);
+    Module::from_bc_path(&modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_modules() -> [Module; 2] {
+    [get_module("renamed_structs_main"), get_module("renamed_structs_linked")]
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config
+}
+
+#[test]
+#[should_panic(expected = "type mismatch")]
+fn renamed_structs_mismatch_by_default() {
+    init_logging();
+    let modules = get_modules();
+    let config = get_config();
+    do_taint_analysis_on_function(&modules, &config, "get_x", Some(vec![]), HashMap::new(), HashMap::new());
+}
+
+#[test]
+fn unified_struct() {
+    init_logging();
+    let modules = get_modules();
+    let mut config = get_config();
+    config.unify_renamed_structs = true;
+
+    // the field `read_point` taints through `struct.point.7` is the one
+    // `get_x` reads through `struct.point`
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "get_x", Some(vec![]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("get_x", &Name::from("v")), &TaintedType::TaintedValue);
+    assert!(taint_result.is_field_tainted("struct.point", &[0]));
+}
+
+#[test]
+fn unified_nested_struct() {
+    init_logging();
+    let modules = get_modules();
+    let mut config = get_config();
+    config.unify_renamed_structs = true;
+
+    // `struct.line.3` is unified with `struct.line` even though its fields
+    // are the renamed `struct.point.7`
+    let taint_result = do_taint_analysis_on_function(&modules, &config, "get_end_y", Some(vec![]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("get_end_y", &Name::from("v")), &TaintedType::TaintedValue);
+    assert!(taint_result.is_field_tainted("struct.point", &[1]));
+}
@@ -0,0 +1,75 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/sanitize.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, funcname: &str) -> TaintResult<'m> {
+    do_taint_analysis_on_function(
+        modules,
+        config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn bounds_check() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "bounded";
+
+    // by default, the bounds check doesn't sanitize anything
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(8)), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(11)), &TaintedType::TaintedValue);
+
+    // with sanitization, the value reloaded after the check is untainted, and
+    // so is everything computed from it
+    let mut config = Config::default();
+    config.sanitize_bounds_checks = true;
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(4)), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(8)), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(9)), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from(11)), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn validators() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+
+    // without any validators, the checks don't sanitize anything
+    for funcname in &["validated", "validated_int"] {
+        let taint_result = analyze(&modules, &config, funcname);
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from("y")), &TaintedType::TaintedValue);
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::TaintedValue);
+    }
+
+    // validators returning `i1`, or compared against zero, sanitize their
+    // argument in the blocks where the validation passed, but not before
+    config.validators.insert("is_valid".into());
+    config.validators.insert("check".into());
+    for funcname in &["validated", "validated_int"] {
+        let taint_result = analyze(&modules, &config, funcname);
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from("y")), &TaintedType::UntaintedValue);
+        assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &TaintedType::UntaintedValue);
+    }
+    let taint_result = analyze(&modules, &config, "validated");
+    assert_eq!(taint_result.get_var_type("validated", &Name::from("z")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,55 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/setjmp.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "run", Some(vec![]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn setjmp_longjmp() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.model_setjmp_longjmp = true;
+
+    // the value passed to `longjmp` is untainted, so `setjmp` returns
+    // untainted values
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("run", &Name::from("r")), &TaintedType::UntaintedValue);
+
+    // a tainted value passed to `longjmp` in the callee taints the result of
+    // the `setjmp` in `run`, which was processed before it
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("fail", &Name::from("code")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("run", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("run", &Name::from("cmp")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn not_modeled_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+
+    // `setjmp` is an ordinary external function, so it never sees the
+    // tainted value passed to `longjmp`
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("fail", &Name::from("code")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("run", &Name::from("r")), &TaintedType::UntaintedValue);
+}
@@ -0,0 +1,45 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/signals.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "main", Some(vec![]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn signal_handlers_as_entry_points() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("read_input".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config.ext_functions.insert("signal".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config.ext_functions.insert("sigaction".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+
+    // by default, the handlers aren't analyzed, so nothing taints `pending`
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("main", &Name::from("p")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_tainted_globals(), vec![&Name::from("last_input")]);
+
+    // as entry points, the handlers see the tainted input, and the one
+    // registered with `signal` passes it back to `main` through `pending`
+    config.signal_handlers_as_entry_points = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("on_alarm", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("on_term", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("main", &Name::from("p")), &TaintedType::TaintedValue);
+    assert_eq!(
+        taint_result.get_tainted_globals(),
+        vec![&Name::from("last_input"), &Name::from("pending"), &Name::from("term_value")],
+    );
+}
@@ -0,0 +1,47 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/spectre.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, arg: TaintedType) -> TaintResult<'m> {
+    let args = ["victim", "no_dependent_use", "unchecked"]
+        .iter()
+        .map(|&fn_name| (fn_name, vec![arg.clone()]))
+        .collect();
+    do_taint_analysis_on_module(modules, config, args, HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn spectre_v1_gadget() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+
+    // only `victim` both bounds-checks the index and leaks the loaded value;
+    // `no_dependent_use` doesn't use it, and `unchecked` has no branch to
+    // mispredict
+    let taint_result = analyze(&modules, &config, TaintedType::TaintedValue);
+    assert_eq!(taint_result.find_spectre_gadgets(), vec![SpectreGadget {
+        function: "victim",
+        index: &Name::from("x"),
+        bounds_check: &Name::from("entry"),
+        access_block: &Name::from("then"),
+        access_index: 1,
+        transmit_block: &Name::from("then"),
+        transmit_index: Some(5),
+    }]);
+
+    // with an untainted index, there are no gadgets
+    let taint_result = analyze(&modules, &config, TaintedType::UntaintedValue);
+    assert_eq!(taint_result.find_spectre_gadgets(), vec![]);
+}
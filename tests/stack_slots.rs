@@ -0,0 +1,85 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/stack_slots.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, funcname: &str) -> TaintResult<'m> {
+    do_taint_analysis_on_function(
+        modules,
+        config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn lifetime_start() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "reuse";
+
+    // by default, the slot is tainted for its whole life
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("b")), &TaintedType::TaintedValue);
+
+    // with flow-sensitive memory, the second lifetime of the slot starts out
+    // untainted
+    let mut config = Config::default();
+    config.flow_sensitive_memory = true;
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("b")), &TaintedType::UntaintedValue);
+    assert!(!taint_result.does_function_return_tainted(funcname));
+}
+
+#[test]
+fn escaped_slot() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "escaped";
+    let mut config = Config::default();
+    config.ext_functions_default = config::ExternalFunctionHandling::IgnoreAndReturnUntainted;
+    config.flow_sensitive_memory = true;
+
+    // the slot's address escapes, so it isn't tracked flow-sensitively
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("b")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn strong_updates() {
+    init_logging();
+    let modules = [get_module()];
+    let funcname = "overwrite";
+
+    // by default, stores only add taint to the slot
+    let config = Config::default();
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("b")), &TaintedType::TaintedValue);
+
+    // with flow-sensitive memory, overwriting the slot clears its taint
+    let mut config = Config::default();
+    config.flow_sensitive_memory = true;
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("a")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("b")), &TaintedType::UntaintedValue);
+
+    // but a store under tainted control flow still taints the slot
+    let funcname = "overwrite_if";
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("m")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,39 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/static_init.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "main", Some(vec![]), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn static_initializers_as_entry_points() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("read_env".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    // by default, the constructor isn't analyzed, so `main` sees an untainted
+    // global
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("main", &Name::from("c")), &TaintedType::UntaintedValue);
+    assert!(!taint_result.is_global_tainted(&Name::from("config_value")));
+
+    // as entry points, the constructor taints the global before `main` runs,
+    // and the destructor copies it to another
+    config.static_initializers_as_entry_points = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("main", &Name::from("c")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_tainted_globals(), vec![&Name::from("config_value"), &Name::from("shutdown_value")]);
+}
@@ -362,3 +362,341 @@ fn addl_structtest() {
         Some(&TaintedType::TaintedValue),
     );
 }
+
+#[test]
+fn addl_field_queries() {
+    init_logging();
+    let funcname = "caller";
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // Same setup as `addl_structtest`, but this time we query the individual
+    // fields of struct.ThreeInts by index path
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.is_field_tainted("struct.ThreeInts", &[0]));
+    assert!(!taint_result.is_field_tainted("struct.ThreeInts", &[1]));
+    assert!(taint_result.is_field_tainted("struct.ThreeInts", &[2]));
+    assert!(taint_result.is_field_tainted("struct.ThreeInts", &[]));
+    assert!(taint_result.get_field_type("struct.ThreeInts", &[3]).is_err());
+    assert!(taint_result.get_field_type("struct.ThreeInts", &[0, 0]).is_err());
+}
+
+#[test]
+fn addl_display() {
+    init_logging();
+    let funcname = "caller";
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // The displayed result should flag tainted variables and struct types, and
+    // should be stable from one analysis to the next
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let displayed = taint_result.to_string();
+    assert!(displayed.contains("function \"caller\":"));
+    assert!(displayed.contains("function \"called\":"));
+    assert!(displayed.contains("[T] %8: TaintedValue"));
+    assert!(displayed.contains("[T] \"struct.ThreeInts\""));
+    let taint_result_again = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert_eq!(displayed, taint_result_again.to_string());
+}
+
+#[test]
+fn addl_summaries() {
+    init_logging();
+    let funcname = "caller";
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // The final summaries should record that caller() returns tainted data,
+    // while called() has a void return type and a pointer parameter
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(taint_result.does_function_return_tainted("caller"));
+    assert!(!taint_result.does_function_return_tainted("called"));
+    assert_eq!(taint_result.get_functions_returning_tainted().collect::<Vec<_>>(), vec!["caller"]);
+    let called_summary = taint_result.get_function_summary("called").expect("called() should have a summary");
+    assert_eq!(called_summary.get_ret_ty(), &None);
+    assert_eq!(called_summary.get_params().count(), 1);
+}
+
+/// A `Write` which appends to a buffer that the test can inspect afterwards
+#[derive(Clone, Default)]
+struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn addl_propagation_trace() {
+    init_logging();
+    let funcname = "caller";
+    let module = get_addl_module();
+    let modules = [module];
+    let buffer = SharedBuffer::default();
+    let mut config = Config::default();
+    config.propagation_trace = Some(std::sync::Mutex::new(Box::new(buffer.clone())));
+
+    // Every line of the trace should be a JSON object, the steps should be
+    // numbered in order, and we should see the step which taints %8 in
+    // caller() as well as the step which taints caller()'s return value in its
+    // summary
+    let _ = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let events: Vec<serde_json::Value> = trace
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("Failed to parse trace line {:?}: {}", line, e)))
+        .collect();
+    assert!(!events.is_empty());
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event["step"], i);
+    }
+    assert!(events.iter().any(|event| {
+        event["kind"] == "instruction"
+            && event["function"] == "caller"
+            && event["result"] == "%8"
+            && event["type"] == "TaintedValue"
+            && event["tainted"] == true
+    }));
+    assert!(events.iter().any(|event| {
+        event["kind"] == "summary" && event["function"] == "caller" && event["ret"]["tainted"] == true
+    }));
+}
+
+#[test]
+fn addl_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TaintResult>();
+    assert_send_sync::<Config>();
+
+    init_logging();
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // run the analysis on one thread, and query the result from others
+    let taint_result = std::thread::scope(|s| {
+        s.spawn(|| do_taint_analysis_on_function(
+            &modules,
+            &config,
+            "caller",
+            Some(vec![TaintedType::TaintedValue]),
+            HashMap::new(),
+            HashMap::new(),
+        )).join().unwrap()
+    });
+    std::thread::scope(|s| {
+        let queries: Vec<_> = (0 .. 2).map(|_| s.spawn(|| {
+            taint_result.get_var_type("caller", &Name::from(8)).clone()
+        })).collect();
+        for query in queries {
+            assert_eq!(query.join().unwrap(), TaintedType::TaintedValue);
+        }
+    });
+}
+
+#[test]
+fn addl_step_and_resume() {
+    init_logging();
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // Running one step at a time should let us inspect intermediate state,
+    // and should end up with the same result as running to completion.
+    // %8 in caller() only becomes tainted once called() has been processed,
+    // so after a single step it should still be untainted
+    let mut ts = TaintState::new_single_function(
+        &modules,
+        &config,
+        "caller",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(!ts.is_done());
+    assert_eq!(ts.get_pending_functions(), vec!["caller"]);
+    assert!(ts.get_function_summary("called").is_none());
+
+    assert!(!ts.run_until(1));
+    let caller_map = ts.get_function_taint_map("caller").expect("caller() should have a taint map after one step");
+    assert_eq!(caller_map.get(&Name::from(8)), Some(&TaintedType::UntaintedValue));
+    assert!(ts.get_function_summary("called").is_some());
+    assert!(ts.get_pending_functions().contains(&"called"));
+
+    assert!(ts.run_until(usize::MAX));
+    assert!(ts.is_done());
+    assert!(!ts.step());
+    assert!(ts.get_pending_functions().is_empty());
+    let taint_result = ts.into_taint_result();
+    assert!(taint_result.does_function_return_tainted("caller"));
+    assert_eq!(taint_result.get_var_type("caller", &Name::from(8)), &TaintedType::TaintedValue);
+    assert_eq!(
+        taint_result.get_named_struct_type("struct.ThreeInts"),
+        &TaintedType::struct_of(vec![
+            TaintedType::TaintedValue,
+            TaintedType::UntaintedValue,
+            TaintedType::TaintedValue,
+        ]),
+    );
+}
+
+#[test]
+fn addl_snapshot() {
+    init_logging();
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // A snapshot after one step should show the intermediate state, and
+    // shouldn't change as the analysis continues
+    let mut ts = TaintState::new_single_function(
+        &modules,
+        &config,
+        "caller",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(ts.step());
+    let snapshot = ts.snapshot();
+    assert_eq!(snapshot.get_var_type("caller", &Name::from(8)), &TaintedType::UntaintedValue);
+
+    assert!(ts.run_until(usize::MAX));
+    assert_eq!(snapshot.get_var_type("caller", &Name::from(8)), &TaintedType::UntaintedValue);
+    let final_snapshot = ts.snapshot();
+    assert_eq!(final_snapshot.get_var_type("caller", &Name::from(8)), &TaintedType::TaintedValue);
+    assert!(final_snapshot.does_function_return_tainted("caller"));
+
+    // and a snapshot at the fixpoint should match the final result
+    let taint_result = ts.into_taint_result();
+    assert_eq!(final_snapshot.get_function_taint_map("caller"), taint_result.get_function_taint_map("caller"));
+    assert_eq!(final_snapshot.get_named_struct_type("struct.ThreeInts"), taint_result.get_named_struct_type("struct.ThreeInts"));
+}
+
+#[test]
+fn addl_checkpoint() {
+    init_logging();
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+
+    // Checkpointing after one step and resuming from the serialized
+    // checkpoint should reproduce the same state, and should reach the same
+    // result as running to completion without interruption
+    let mut ts = TaintState::new_single_function(
+        &modules,
+        &config,
+        "caller",
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(ts.step());
+    let checkpoint = ts.checkpoint();
+    assert_eq!(ts.checkpoint(), checkpoint);
+    let mut serialized = Vec::new();
+    checkpoint.write_json(&mut serialized).unwrap();
+    drop(ts);
+
+    let restored = Checkpoint::read_json(serialized.as_slice()).unwrap();
+    assert_eq!(restored, checkpoint);
+    let mut ts = TaintState::resume_from_checkpoint(&modules, &config, &restored).unwrap();
+    assert_eq!(ts.checkpoint(), checkpoint);
+    assert!(ts.run_until(usize::MAX));
+    let taint_result = ts.into_taint_result();
+    assert_eq!(taint_result.get_var_type("caller", &Name::from(8)), &TaintedType::TaintedValue);
+    assert!(taint_result.does_function_return_tainted("caller"));
+    assert_eq!(
+        taint_result.get_named_struct_type("struct.ThreeInts"),
+        &TaintedType::struct_of(vec![
+            TaintedType::TaintedValue,
+            TaintedType::UntaintedValue,
+            TaintedType::TaintedValue,
+        ]),
+    );
+
+    // A checkpoint referring to functions which don't exist should be rejected
+    let bogus = String::from_utf8(serialized).unwrap().replace("\"called\"", "\"no_such_function\"");
+    let bogus = Checkpoint::read_json(bogus.as_bytes()).unwrap();
+    assert!(TaintState::resume_from_checkpoint(&modules, &config, &bogus).is_err());
+}
+
+#[test]
+fn addl_filtered_text() {
+    init_logging();
+    let funcname = "caller";
+    let module = get_addl_module();
+    let modules = [module];
+    let config = Config::default();
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+
+    // only the tainted variables of `caller`, and not the named structs
+    let filter = OutputFilter {
+        functions: vec!["caller".into()],
+        only_tainted: true,
+    };
+    let mut text = Vec::new();
+    taint_result.write_text(&filter, &mut text).unwrap();
+    let text = String::from_utf8(text).unwrap();
+    assert!(text.starts_with("function \"caller\":\n"));
+    assert!(text.contains("[T] %8: TaintedValue"));
+    assert!(!text.contains("function \"called\":"));
+    assert!(!text.contains("named structs:"));
+    assert!(text.lines().filter(|line| line.starts_with("  ")).all(|line| line.starts_with("  [T] ")));
+
+    // a filter which keeps everything gives the `Display` output
+    let mut text = Vec::new();
+    taint_result.write_text(&OutputFilter::default(), &mut text).unwrap();
+    assert_eq!(String::from_utf8(text).unwrap(), taint_result.to_string());
+}
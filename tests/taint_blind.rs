@@ -0,0 +1,57 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/taint_blind.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+    config
+}
+
+#[test]
+fn skip_taint_blind_functions() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+
+    // by default, every function is analyzed
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert!(taint_result.get_function_names().any(|name| *name == "blind"));
+    assert!(taint_result.get_function_names().any(|name| *name == "blind_helper"));
+
+    // with the pre-pass, `blind` and `blind_helper` are skipped, but the
+    // results of the other functions are unchanged, including `get_count`,
+    // which only sees the secret through the global
+    config.skip_taint_blind_functions = true;
+    let taint_result = do_taint_analysis_on_module(&modules, &config, HashMap::new(), HashMap::new(), HashMap::new());
+    assert!(!taint_result.get_function_names().any(|name| *name == "blind"));
+    assert!(!taint_result.get_function_names().any(|name| *name == "blind_helper"));
+    assert_eq!(taint_result.get_var_type("reader", &Name::from("m")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("mix", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("get_count", &Name::from("c")), &TaintedType::TaintedValue);
+}
+
+#[test]
+fn tainted_entry_points_observe_taint() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    config.skip_taint_blind_functions = true;
+
+    // given a tainted argument, `blind` and its callee are no longer blind
+    let args = std::iter::once(("blind", vec![TaintedType::TaintedValue])).collect();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, args, HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("blind", &Name::from("r")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("blind_helper", &Name::from("r")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,56 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/thread_spawn.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("pthread_create".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config.ext_functions.insert("pthread_join".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+#[test]
+fn pthread_create_callback() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = get_config();
+    let funcname = "start";
+
+    // the taint of the job passed to the thread reaches the worker
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("worker", &Name::from("v")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("worker", &Name::from("mul")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("call")), &TaintedType::UntaintedValue);
+
+    // but not if `pthread_create` isn't a thread-spawn function
+    config.thread_spawn_functions.remove("pthread_create");
+    let taint_result = do_taint_analysis_on_module(&modules, &config, std::iter::once((funcname, vec![TaintedType::TaintedValue])).collect(), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type("worker", &Name::from("v")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn unresolved_thread_callback() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+
+    // a callback through a function pointer isn't resolved, so the other
+    // worker doesn't see the tainted argument
+    let args = std::iter::once(("start_indirect", vec![TaintedType::UntaintedFnPtr(FnTargets::new()), TaintedType::untainted_ptr_to(TaintedType::TaintedValue)])).collect();
+    let taint_result = do_taint_analysis_on_module(&modules, &config, args, HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type("other_worker", &Name::from("arg")),
+        &TaintedType::untainted_ptr_to(TaintedType::UntaintedValue),
+    );
+}
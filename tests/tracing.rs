@@ -0,0 +1,79 @@
+#![cfg(feature = "tracing")]
+
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/parallel.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+/// Name and `kind` field (if any) of a span
+type SpanInfo = (String, Option<String>);
+
+/// Records every span created
+#[derive(Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<SpanInfo>>>,
+    next_id: AtomicU64,
+}
+
+struct KindVisitor(Option<String>);
+
+impl Visit for KindVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "kind" {
+            self.0 = Some(value.into());
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        let mut visitor = KindVisitor(None);
+        span.record(&mut visitor);
+        self.spans.lock().unwrap().push((span.metadata().name().into(), visitor.0));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+    fn event(&self, _event: &Event) {}
+    fn enter(&self, _span: &Id) {}
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn spans() {
+    let modules = [get_module()];
+    let config = Config::default();
+    let recorder = SpanRecorder::default();
+    let spans = Arc::clone(&recorder.spans);
+    let args = vec![TaintedType::TaintedValue, TaintedType::UntaintedValue, TaintedType::UntaintedValue, TaintedType::untainted_ptr_to(TaintedType::UntaintedValue)];
+    tracing::subscriber::with_default(recorder, || {
+        do_taint_analysis_on_function(&modules, &config, "dispatch", Some(args), HashMap::new(), HashMap::new());
+    });
+
+    let spans = spans.lock().unwrap();
+    assert!(spans.iter().any(|(name, _)| name == "function_pass"));
+    for kind in &["add", "icmp", "select", "phi", "store"] {
+        assert!(
+            spans.iter().any(|(name, span_kind)| name == "instruction" && span_kind.as_deref() == Some(*kind)),
+            "no instruction span of kind {:?}",
+            kind,
+        );
+    }
+}
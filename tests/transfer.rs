@@ -0,0 +1,44 @@
+use llvm_ir::Module;
+use llvm_ir_taint::*;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/transfer.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+#[test]
+fn isolated_transfer_summaries() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.ext_functions.insert("secret".into(), config::ExternalFunctionHandling::IgnoreAndReturnTainted);
+
+    let summary = do_isolated_analysis_on_function(&modules, &config, "add_first_and_third");
+    assert!(!summary.is_ret_always_tainted());
+    assert_eq!(summary.get_ret_dependencies(), &[0, 2]);
+    assert!(summary.returns_tainted_given(&[2]));
+    assert!(!summary.returns_tainted_given(&[1]));
+    assert_eq!(summary.to_string(), "add_first_and_third:\n  ret: tainted if arg 0 or arg 2 is tainted\n");
+
+    let summary = do_isolated_analysis_on_function(&modules, &config, "copy_into");
+    assert_eq!(summary.get_ret_dependencies(), &[] as &[usize]);
+    assert_eq!(summary.get_param_pointee_dependencies(0), &[1]);
+    assert!(summary.param_pointee_tainted_given(0, &[1]));
+    assert!(!summary.param_pointee_tainted_given(0, &[]));
+    assert_eq!(summary.to_string(), "copy_into:\n  *arg 0: tainted if arg 1 is tainted\n");
+
+    let summary = do_isolated_analysis_on_function(&modules, &config, "load_from");
+    assert_eq!(summary.get_ret_dependencies(), &[0]);
+    assert_eq!(summary.get_param_pointee_dependencies(0), &[] as &[usize]);
+
+    let summary = do_isolated_analysis_on_function(&modules, &config, "from_secret");
+    assert!(summary.is_ret_always_tainted());
+    assert!(summary.returns_tainted_given(&[]));
+    assert_eq!(summary.to_string(), "from_secret:\n  ret: tainted\n");
+}
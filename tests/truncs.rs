@@ -0,0 +1,60 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::config::{InstructionClass, PropagationPolicy};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/truncs.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_function(modules, config, "narrow", Some(vec![TaintedType::TaintedValue]), HashMap::new(), HashMap::new())
+}
+
+fn is_tainted(taint_result: &TaintResult, var: &str) -> bool {
+    taint_result.get_var_type("narrow", &Name::from(var)) == &TaintedType::TaintedValue
+}
+
+#[test]
+fn trunc_sanitization() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+
+    // by default, truncation propagates taint
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "bit"));
+    assert!(is_tainted(&taint_result, "byte"));
+
+    config.sanitizing_trunc_max_bits = Some(1);
+    let taint_result = analyze(&modules, &config);
+    assert!(!is_tainted(&taint_result, "bit"));
+    assert!(!is_tainted(&taint_result, "bit.ext"));
+    assert!(is_tainted(&taint_result, "byte"));
+
+    config.sanitizing_trunc_max_bits = Some(8);
+    let taint_result = analyze(&modules, &config);
+    assert!(!is_tainted(&taint_result, "bit"));
+    assert!(!is_tainted(&taint_result, "byte"));
+    assert!(is_tainted(&taint_result, "half"));
+    assert!(is_tainted(&taint_result, "r"));
+
+    // with bit-level taint, the remaining bits are cleared
+    config.bit_level_taint = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_tainted_bits("narrow", &Name::from("byte")), Some(0));
+    assert_eq!(taint_result.get_tainted_bits("narrow", &Name::from("half")), Some(0xffff_ffff));
+
+    // a policy for casts other than `Propagate` overrides it
+    config.bit_level_taint = false;
+    config.propagation_policies.insert(InstructionClass::Cast, PropagationPolicy::TaintConservatively);
+    let taint_result = analyze(&modules, &config);
+    assert!(is_tainted(&taint_result, "bit"));
+}
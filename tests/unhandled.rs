@@ -0,0 +1,49 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/unhandled.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    let args = std::iter::once(("caller", vec![TaintedType::TaintedValue, TaintedType::UntaintedValue])).collect();
+    do_taint_analysis_on_module(modules, config, args, HashMap::new(), HashMap::new())
+}
+
+#[test]
+#[should_panic(expected = "not implemented")]
+fn unknown_instructions_panic_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    analyze(&modules, &config);
+}
+
+#[test]
+fn unknown_instructions_join_operands() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.unknown_instructions = config::UnknownInstructionHandling::JoinOperands;
+    let taint_result = analyze(&modules, &config);
+
+    // the tainted `x` flows through the `freeze`
+    assert_eq!(taint_result.get_var_type("frozen", &Name::from("f")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("frozen", &Name::from("r")), &TaintedType::TaintedValue);
+    // the untainted `y` doesn't become tainted through the inline assembly
+    assert_eq!(taint_result.get_var_type("via_asm", &Name::from("r")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("caller", &Name::from("r")), &TaintedType::TaintedValue);
+
+    assert_eq!(taint_result.get_unhandled_constructs(), vec![
+        ("frozen", &Name::from("entry"), Some(0), "freeze"),
+        ("via_asm", &Name::from("entry"), Some(0), "inline assembly"),
+    ]);
+}
@@ -0,0 +1,76 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/vector_gep.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn vector(element: TaintedType) -> TaintedType {
+    TaintedType::array_or_vec_of(element)
+}
+
+#[test]
+fn vector_of_pointers_base() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    let funcname = "lanes";
+
+    // each lane points to the same (tainted) data as the base pointers
+    let args = || vec![vector(TaintedType::untainted_ptr_to(TaintedType::TaintedValue)), vector(TaintedType::TaintedValue)];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &vector(TaintedType::untainted_ptr_to(TaintedType::TaintedValue)),
+    );
+
+    // a tainted lane of the index taints every lane of the result
+    config.taint_pointers_with_tainted_indices = true;
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args()), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &vector(TaintedType::tainted_ptr_to(TaintedType::TaintedValue)),
+    );
+}
+
+#[test]
+fn vector_indices() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "splat_base";
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::TaintedValue), vector(TaintedType::UntaintedValue)];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &vector(TaintedType::untainted_ptr_to(TaintedType::TaintedValue)),
+    );
+}
+
+#[test]
+fn vector_struct_fields() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "fields";
+    // only the second field of the struct is tainted
+    let named_structs = std::iter::once((
+        "struct.point".to_owned(),
+        NamedStructInitialDef::InitialDef(TaintedType::struct_of(vec![TaintedType::UntaintedValue, TaintedType::TaintedValue])),
+    )).collect();
+    let args = vec![vector(TaintedType::untainted_ptr_to(TaintedType::NamedStruct("struct.point".into())))];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), named_structs);
+    let field = Pointee::new_named_struct_element(TaintedType::TaintedValue, "struct.point".into());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &vector(TaintedType::untainted_ptr_to_pointee(field)),
+    );
+}
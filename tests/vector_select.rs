@@ -0,0 +1,61 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/vector_select.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn vector(element: TaintedType) -> TaintedType {
+    TaintedType::array_or_vec_of(element)
+}
+
+#[test]
+fn vector_condition() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "choose";
+
+    // a tainted lane in the comparison taints the condition, and so every
+    // lane of the result
+    let args = vec![vector(TaintedType::UntaintedValue), vector(TaintedType::TaintedValue), vector(TaintedType::UntaintedValue)];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("cmp")), &vector(TaintedType::TaintedValue));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &vector(TaintedType::TaintedValue));
+
+    // with an untainted condition, the result is the join of the operands
+    let args = vec![vector(TaintedType::UntaintedValue), vector(TaintedType::UntaintedValue), vector(TaintedType::TaintedValue)];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("cmp")), &vector(TaintedType::UntaintedValue));
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &vector(TaintedType::TaintedValue));
+}
+
+#[test]
+fn vector_of_pointers() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    let funcname = "choose_ptrs";
+    let ptrs = || vector(TaintedType::untainted_ptr_to(TaintedType::UntaintedValue));
+
+    let args = vec![vector(TaintedType::UntaintedValue), ptrs(), ptrs()];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("r")), &ptrs());
+
+    // with a tainted mask, the lanes of the result are tainted pointers, but
+    // the data they point to isn't
+    let args = vec![vector(TaintedType::TaintedValue), ptrs(), ptrs()];
+    let taint_result = do_taint_analysis_on_function(&modules, &config, funcname, Some(args), HashMap::new(), HashMap::new());
+    assert_eq!(
+        taint_result.get_var_type(funcname, &Name::from("r")),
+        &vector(TaintedType::tainted_ptr_to(TaintedType::UntaintedValue)),
+    );
+}
@@ -0,0 +1,46 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/virtual.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config, funcname: &'m str) -> TaintResult<'m> {
+    let args = vec![TaintedType::untainted_ptr_to(TaintedType::NamedStruct("class.Base".into())), TaintedType::TaintedValue];
+    do_taint_analysis_on_function(modules, config, funcname, Some(args), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn devirtualize_virtual_calls() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.devirtualize_virtual_calls = true;
+
+    // `b->get(x)` goes through the first slot of the vtables of `Base` and
+    // the classes derived from it, so it can't call `Base::other`, which
+    // would return the tainted `x`, nor `Unrelated::get`, which is in the
+    // first slot of a vtable of an unrelated class
+    let funcname = "_Z8call_getP4Basei";
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("call")), &TaintedType::UntaintedValue);
+
+    // `b->other(x)` goes through the second slot
+    let funcname = "_Z10call_otherP4Basei";
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("call")), &TaintedType::TaintedValue);
+
+    // without devirtualization, any function of the right type is a target
+    config.devirtualize_virtual_calls = false;
+    let funcname = "_Z8call_getP4Basei";
+    let taint_result = analyze(&modules, &config, funcname);
+    assert_eq!(taint_result.get_var_type(funcname, &Name::from("call")), &TaintedType::TaintedValue);
+}
@@ -0,0 +1,52 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/wasi.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn analyze<'m>(modules: &'m [Module], config: &'m Config) -> TaintResult<'m> {
+    do_taint_analysis_on_module(modules, config, HashMap::new(), HashMap::new(), HashMap::new())
+}
+
+#[test]
+fn fd_read() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.model_wasi_imports = true;
+    let taint_result = analyze(&modules, &config);
+    // the data read into the `iovec`'s buffer is tainted, but not the error
+    // code or the byte count
+    assert_eq!(taint_result.get_var_type("read_byte", &Name::from("b")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("read_byte", &Name::from("err")), &TaintedType::UntaintedValue);
+    assert_eq!(taint_result.get_var_type("read_byte", &Name::from("n")), &TaintedType::UntaintedValue);
+}
+
+#[test]
+fn args_get() {
+    init_logging();
+    let modules = [get_module()];
+    let mut config = Config::default();
+    config.model_wasi_imports = true;
+    let taint_result = analyze(&modules, &config);
+    assert_eq!(taint_result.get_var_type("first_arg_byte", &Name::from("count")), &TaintedType::TaintedValue);
+    assert_eq!(taint_result.get_var_type("first_arg_byte", &Name::from("c")), &TaintedType::TaintedValue);
+}
+
+#[test]
+#[should_panic(expected = "not found in the module")]
+fn wasi_imports_not_modeled_by_default() {
+    init_logging();
+    let modules = [get_module()];
+    let config = Config::default();
+    analyze(&modules, &config);
+}
@@ -0,0 +1,92 @@
+use llvm_ir::{Module, Name};
+use llvm_ir_taint::*;
+use std::collections::HashMap;
+
+fn init_logging() {
+    // capture log messages with test harness
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn get_module() -> Module {
+    let modname = "tests/additional_bcfiles/what_if.bc";
+    Module::from_bc_path(modname)
+        .unwrap_or_else(|e| panic!("Failed to parse module {:?}: {}", modname, e))
+}
+
+fn get_config() -> Config {
+    let mut config = Config::default();
+    config.ext_functions.insert("is_valid".into(), config::ExternalFunctionHandling::IgnoreAndReturnUntainted);
+    config
+}
+
+#[test]
+fn toggle_sources() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let funcname = "check";
+    let mut what_if = do_what_if_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    let a = TaintSource::Arg { function: funcname.into(), index: 0 };
+    let b = TaintSource::Arg { function: funcname.into(), index: 1 };
+    assert_eq!(what_if.get_sources(), vec![&a, &b]);
+    assert_eq!(what_if.get_var_sources(funcname, &Name::from("s")), vec![a.clone(), b.clone()]);
+    assert!(what_if.is_var_tainted(funcname, &Name::from("r")));
+    assert!(what_if.is_var_tainted(funcname, &Name::from("d")));
+
+    // without `a`, %s is still tainted by `b`, but %r is untainted
+    assert!(what_if.disable_source(&a));
+    assert_eq!(what_if.get_var_sources(funcname, &Name::from("s")), vec![b.clone()]);
+    assert!(!what_if.is_var_tainted(funcname, &Name::from("r")));
+    assert!(what_if.is_var_tainted(funcname, &Name::from("d")));
+
+    // re-enabling it restores the original results
+    what_if.enable_source(&a);
+    assert!(what_if.is_var_tainted(funcname, &Name::from("r")));
+
+    assert!(!what_if.disable_source(&TaintSource::Arg { function: funcname.into(), index: 2 }));
+}
+
+#[test]
+fn add_validators() {
+    init_logging();
+    let modules = [get_module()];
+    let config = get_config();
+    let funcname = "check";
+    let mut what_if = do_what_if_analysis_on_function(
+        &modules,
+        &config,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    assert!(what_if.is_var_tainted(funcname, &Name::from("r")));
+
+    // `is_valid` is only passed data from `a`, so only its run is re-run
+    let mut validated = get_config();
+    validated.validators.insert("is_valid".into());
+    assert_eq!(what_if.add_validators(&validated), 1);
+    assert!(!what_if.is_var_tainted(funcname, &Name::from("r")));
+    assert!(what_if.is_var_tainted(funcname, &Name::from("s")));
+    assert!(what_if.is_var_tainted(funcname, &Name::from("d")));
+
+    // the results match a full analysis with the validator
+    let taint_result = do_taint_analysis_on_function(
+        &modules,
+        &validated,
+        funcname,
+        Some(vec![TaintedType::TaintedValue, TaintedType::TaintedValue]),
+        HashMap::new(),
+        HashMap::new(),
+    );
+    for (var_name, ty) in taint_result.get_function_taint_map(funcname) {
+        assert_eq!(what_if.is_var_tainted(funcname, var_name), taint_result.is_type_tainted(ty), "{}", var_name);
+    }
+}